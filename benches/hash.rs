@@ -1,9 +1,13 @@
 use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
 use miden_crypto::{
     hash::{
+        anemoi::Anemoi256,
         blake::Blake3_256,
+        monolith::Monolith,
+        poseidon2::Poseidon2,
         rpo::{Rpo256, RpoDigest},
         rpx::{Rpx256, RpxDigest},
+        tip5::{Tip5, Tip5Digest},
     },
     Felt,
 };
@@ -102,6 +106,86 @@ fn rpx256_sequential(c: &mut Criterion) {
     });
 }
 
+fn anemoi256_2to1(c: &mut Criterion) {
+    let v: [RpoDigest; 2] = [Anemoi256::hash(&[1_u8]), Anemoi256::hash(&[2_u8])];
+    c.bench_function("Anemoi256 2-to-1 hashing (cached)", |bench| {
+        bench.iter(|| Anemoi256::merge(black_box(&v)))
+    });
+
+    c.bench_function("Anemoi256 2-to-1 hashing (random)", |bench| {
+        bench.iter_batched(
+            || {
+                [
+                    Anemoi256::hash(&rand_value::<u64>().to_le_bytes()),
+                    Anemoi256::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Anemoi256::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn poseidon2_2to1(c: &mut Criterion) {
+    let v: [RpoDigest; 2] = [Poseidon2::hash(&[1_u8]), Poseidon2::hash(&[2_u8])];
+    c.bench_function("Poseidon2 2-to-1 hashing (cached)", |bench| {
+        bench.iter(|| Poseidon2::merge(black_box(&v)))
+    });
+
+    c.bench_function("Poseidon2 2-to-1 hashing (random)", |bench| {
+        bench.iter_batched(
+            || {
+                [
+                    Poseidon2::hash(&rand_value::<u64>().to_le_bytes()),
+                    Poseidon2::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Poseidon2::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn monolith_2to1(c: &mut Criterion) {
+    let v: [RpoDigest; 2] = [Monolith::hash(&[1_u8]), Monolith::hash(&[2_u8])];
+    c.bench_function("Monolith 2-to-1 hashing (cached)", |bench| {
+        bench.iter(|| Monolith::merge(black_box(&v)))
+    });
+
+    c.bench_function("Monolith 2-to-1 hashing (random)", |bench| {
+        bench.iter_batched(
+            || {
+                [
+                    Monolith::hash(&rand_value::<u64>().to_le_bytes()),
+                    Monolith::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Monolith::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+fn tip5_2to1(c: &mut Criterion) {
+    let v: [Tip5Digest; 2] = [Tip5::hash(&[1_u8]), Tip5::hash(&[2_u8])];
+    c.bench_function("Tip5 2-to-1 hashing (cached)", |bench| {
+        bench.iter(|| Tip5::merge(black_box(&v)))
+    });
+
+    c.bench_function("Tip5 2-to-1 hashing (random)", |bench| {
+        bench.iter_batched(
+            || {
+                [
+                    Tip5::hash(&rand_value::<u64>().to_le_bytes()),
+                    Tip5::hash(&rand_value::<u64>().to_le_bytes()),
+                ]
+            },
+            |state| Tip5::merge(&state),
+            BatchSize::SmallInput,
+        )
+    });
+}
+
 fn blake3_2to1(c: &mut Criterion) {
     let v: [<Blake3_256 as Hasher>::Digest; 2] =
         [Blake3_256::hash(&[1_u8]), Blake3_256::hash(&[2_u8])];
@@ -155,6 +239,10 @@ criterion_group!(
     rpx256_sequential,
     rpo256_2to1,
     rpo256_sequential,
+    anemoi256_2to1,
+    poseidon2_2to1,
+    monolith_2to1,
+    tip5_2to1,
     blake3_2to1,
     blake3_sequential
 );
@@ -1,12 +1,18 @@
 fn main() {
-    #[cfg(feature = "std")]
-    compile_rpo_falcon();
+    // key generation and signing in RPO Falcon512 are available whenever the C implementation is
+    // compiled, i.e., in the `std` context and in the `no_std` context on all targets but wasm32,
+    // for which no C library is available
+    println!("cargo:rustc-check-cfg=cfg(falcon_signing)");
+    let target_arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    if cfg!(feature = "std") || target_arch != "wasm32" {
+        compile_rpo_falcon();
+        println!("cargo:rustc-cfg=falcon_signing");
+    }
 
     #[cfg(target_feature = "sve")]
     compile_arch_arm64_sve();
 }
 
-#[cfg(feature = "std")]
 fn compile_rpo_falcon() {
     use std::path::PathBuf;
 
@@ -20,18 +26,33 @@ fn compile_rpo_falcon() {
     let target_dir: PathBuf = ["PQClean", "crypto_sign", "falcon-512", "clean"].iter().collect();
     let common_dir: PathBuf = ["PQClean", "common"].iter().collect();
 
+    // without `std`, there is no OS entropy source, and thus the sources which depend on
+    // `randombytes()` are excluded from the build
+    let with_randombytes = cfg!(feature = "std");
+    let is_included = |path: &PathBuf| {
+        with_randombytes || !path.ends_with("randombytes.c") && !path.ends_with("pqclean.c")
+    };
     let scheme_files = glob::glob(target_dir.join("*.c").to_str().unwrap()).unwrap();
     let common_files = glob::glob(common_dir.join("*.c").to_str().unwrap()).unwrap();
 
-    cc::Build::new()
+    let mut build = cc::Build::new();
+    build
         .include(&common_dir)
         .include(target_dir)
-        .files(scheme_files.into_iter().map(|p| p.unwrap().to_string_lossy().into_owned()))
-        .files(common_files.into_iter().map(|p| p.unwrap().to_string_lossy().into_owned()))
+        .files(scheme_files.chain(common_files).map(|p| p.unwrap()).filter(is_included))
         .file(format!("{RPO_FALCON_PATH}/falcon.c"))
         .file(format!("{RPO_FALCON_PATH}/rpo.c"))
-        .flag("-O3")
-        .compile("rpo_falcon512");
+        .flag("-O3");
+
+    if with_randombytes {
+        build.define("RPO_FALCON_RANDOMBYTES", None);
+    }
+
+    // wipe secret intermediate values in the signing path
+    #[cfg(feature = "zeroize")]
+    build.define("RPO_FALCON_ZEROIZE", None);
+
+    build.compile("rpo_falcon512");
 }
 
 #[cfg(target_feature = "sve")]
@@ -0,0 +1,382 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::CipherError;
+#[cfg(feature = "zeroize")]
+use crate::utils::zeroize_elements;
+use crate::{hash::rpo::Rpo256, utils::ct_eq, Felt, Word, ONE, WORD_SIZE, ZERO};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The number of field elements in an authentication tag.
+pub const TAG_SIZE: usize = WORD_SIZE;
+
+const STATE_WIDTH: usize = Rpo256::STATE_WIDTH;
+const RATE_START: usize = Rpo256::RATE_RANGE.start;
+const RATE_WIDTH: usize = Rpo256::RATE_RANGE.end - Rpo256::RATE_RANGE.start;
+
+/// Index of the state element which separates the associated data from the plaintext.
+const PHASE_IDX: usize = Rpo256::CAPACITY_RANGE.start;
+
+/// Index of the rate element which is initialized with the encoding of the plaintext.
+const ENCODING_IDX: usize = RATE_START + WORD_SIZE;
+
+/// Encoding identifier of plaintexts consisting of field elements.
+const ELEMENTS_ENCODING: Felt = ZERO;
+
+/// Encoding identifier of plaintexts consisting of bytes.
+const BYTES_ENCODING: Felt = ONE;
+
+/// The number of bytes packed into a single field element when encrypting bytes.
+const BINARY_CHUNK_SIZE: usize = 7;
+
+/// The number of bytes in the encoding of a single ciphertext element.
+const ELEMENT_BYTES: usize = 8;
+
+// RPO AEAD
+// ================================================================================================
+
+/// An authenticated encryption scheme with associated data (AEAD) based on a keyed duplex sponge
+/// over the RPO permutation, in the style of MonkeyDuplex and SpongeWrap.
+///
+/// Encryption of a plaintext under a key and a nonce proceeds as follows:
+/// 1. The key is placed into the capacity portion of the state, the nonce and an identifier of
+///    the plaintext encoding (field elements or bytes) are placed into the rate portion, and the
+///    permutation is applied.
+/// 2. The associated data is padded by appending a 1 followed by as many 0 as necessary to make
+///    its length a multiple of the rate, and added to the rate portion of the state one block at
+///    a time, applying the permutation after each block. Then, 1 is added to the first capacity
+///    element to separate the associated data from the plaintext.
+/// 3. Each element of the plaintext is added to the next element of the rate portion, and the
+///    resulting element is emitted as the ciphertext element; the permutation is applied after
+///    every full block. The plaintext is then padded in the same way as the associated data.
+/// 4. The authentication tag is read from the digest range of the state.
+///
+/// Decryption recomputes the same sequence of states: each plaintext element is obtained by
+/// subtracting the rate element from the ciphertext element, after which the rate element is
+/// overwritten with the ciphertext element.
+///
+/// The security of the scheme relies on the nonce being unique for every encryption under the
+/// same key. Encrypting two plaintexts with the same key and nonce reveals the difference of
+/// their first blocks and allows forging ciphertexts.
+///
+/// When the `zeroize` feature is enabled, the key, the state of the sponge, the intermediate
+/// encodings of the plaintext and the decryptions of ciphertexts which fail authentication are
+/// wiped from memory when they are no longer needed.
+#[derive(Clone)]
+pub struct RpoAead {
+    key: Word,
+}
+
+impl RpoAead {
+    /// Returns a new instance of the scheme which encrypts and decrypts data under the provided
+    /// key.
+    pub fn new(key: Word) -> Self {
+        Self { key }
+    }
+
+    // FIELD ELEMENTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Encrypts and authenticates the provided plaintext, and authenticates the associated data.
+    ///
+    /// The returned ciphertext consists of one element per element of the plaintext followed by
+    /// the [TAG_SIZE] elements of the authentication tag. The associated data is not included in
+    /// the ciphertext, and must be provided again when opening it.
+    pub fn seal_elements(
+        &self,
+        nonce: Word,
+        associated_data: &[Felt],
+        plaintext: &[Felt],
+    ) -> Vec<Felt> {
+        let mut sponge = self.init_sponge(nonce, associated_data, ELEMENTS_ENCODING);
+
+        let mut ciphertext = Vec::with_capacity(plaintext.len() + TAG_SIZE);
+        ciphertext.extend(plaintext.iter().map(|&element| sponge.encrypt(element)));
+        ciphertext.extend_from_slice(&sponge.finalize());
+        ciphertext
+    }
+
+    /// Verifies and decrypts the provided ciphertext, and verifies the associated data.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The ciphertext is shorter than an authentication tag.
+    /// - The authentication tag is not valid for the ciphertext, the nonce, and the associated
+    ///   data under the key of this instance.
+    pub fn open_elements(
+        &self,
+        nonce: Word,
+        associated_data: &[Felt],
+        ciphertext: &[Felt],
+    ) -> Result<Vec<Felt>, CipherError> {
+        self.open(nonce, associated_data, ciphertext, ELEMENTS_ENCODING)
+    }
+
+    // BYTES
+    // --------------------------------------------------------------------------------------------
+
+    /// Encrypts and authenticates the provided plaintext, and authenticates the associated data.
+    ///
+    /// Both the plaintext and the associated data are packed into field elements 7 bytes at a
+    /// time, after appending a 1 byte followed by as many 0 bytes as necessary to make their
+    /// length a multiple of 7. The returned ciphertext consists of the canonical little-endian
+    /// encodings of the ciphertext elements and of the authentication tag, i.e., it has 8 bytes
+    /// per element.
+    ///
+    /// Ciphertexts produced by this method are separated from the ciphertexts produced by
+    /// [RpoAead::seal_elements()], i.e., one cannot be opened as the other.
+    pub fn seal_bytes(&self, nonce: Word, associated_data: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let associated_data = bytes_to_elements(associated_data);
+        let mut sponge = self.init_sponge(nonce, &associated_data, BYTES_ENCODING);
+
+        #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+        let mut plaintext = bytes_to_elements(plaintext);
+        let mut ciphertext = Vec::with_capacity((plaintext.len() + TAG_SIZE) * ELEMENT_BYTES);
+        for &element in plaintext.iter() {
+            ciphertext.extend_from_slice(&sponge.encrypt(element).as_int().to_le_bytes());
+        }
+        for element in sponge.finalize() {
+            ciphertext.extend_from_slice(&element.as_int().to_le_bytes());
+        }
+
+        #[cfg(feature = "zeroize")]
+        zeroize_elements(&mut plaintext);
+
+        ciphertext
+    }
+
+    /// Verifies and decrypts the provided ciphertext, and verifies the associated data.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The length of the ciphertext is not a multiple of 8, or the ciphertext is too short to
+    ///   contain the encryption of any plaintext.
+    /// - The ciphertext contains a non-canonical encoding of a field element.
+    /// - The authentication tag is not valid for the ciphertext, the nonce, and the associated
+    ///   data under the key of this instance.
+    /// - The decrypted plaintext is not correctly padded, which can happen only if the ciphertext
+    ///   was not produced by [RpoAead::seal_bytes()].
+    pub fn open_bytes(
+        &self,
+        nonce: Word,
+        associated_data: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, CipherError> {
+        if ciphertext.len() % ELEMENT_BYTES != 0
+            || ciphertext.len() < (TAG_SIZE + 1) * ELEMENT_BYTES
+        {
+            return Err(CipherError::InvalidCiphertextLength(ciphertext.len()));
+        }
+
+        let ciphertext = ciphertext
+            .chunks_exact(ELEMENT_BYTES)
+            .map(|chunk| {
+                Felt::try_from(u64::from_le_bytes(chunk.try_into().unwrap()))
+                    .map_err(|_| CipherError::NonCanonicalElement)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let associated_data = bytes_to_elements(associated_data);
+        #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+        let mut plaintext = self.open(nonce, &associated_data, &ciphertext, BYTES_ENCODING)?;
+        let result = elements_to_bytes(&plaintext);
+
+        #[cfg(feature = "zeroize")]
+        zeroize_elements(&mut plaintext);
+
+        result
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a sponge which has absorbed the key, the nonce, the plaintext encoding, and the
+    /// associated data, and is thus ready to encrypt or decrypt the plaintext.
+    fn init_sponge(&self, nonce: Word, associated_data: &[Felt], encoding: Felt) -> AeadSponge {
+        let mut state = [ZERO; STATE_WIDTH];
+        state[Rpo256::CAPACITY_RANGE].copy_from_slice(&self.key);
+        state[RATE_START..RATE_START + WORD_SIZE].copy_from_slice(&nonce);
+        state[ENCODING_IDX] = encoding;
+        Rpo256::apply_permutation(&mut state);
+
+        let mut sponge = AeadSponge { state, rate_idx: 0 };
+
+        #[cfg(feature = "zeroize")]
+        zeroize_elements(&mut state);
+
+        for &element in associated_data {
+            sponge.absorb(element);
+        }
+        sponge.pad_and_permute();
+        sponge.state[PHASE_IDX] += ONE;
+        sponge
+    }
+
+    /// Verifies and decrypts the provided ciphertext elements.
+    fn open(
+        &self,
+        nonce: Word,
+        associated_data: &[Felt],
+        ciphertext: &[Felt],
+        encoding: Felt,
+    ) -> Result<Vec<Felt>, CipherError> {
+        if ciphertext.len() < TAG_SIZE {
+            return Err(CipherError::InvalidCiphertextLength(ciphertext.len()));
+        }
+        let (ciphertext, tag) = ciphertext.split_at(ciphertext.len() - TAG_SIZE);
+
+        let mut sponge = self.init_sponge(nonce, associated_data, encoding);
+        #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+        let mut plaintext: Vec<Felt> =
+            ciphertext.iter().map(|&element| sponge.decrypt(element)).collect();
+        let expected_tag = sponge.finalize();
+
+        if ct_eq(tag, &expected_tag) {
+            Ok(plaintext)
+        } else {
+            // the plaintext of an unauthenticated ciphertext must not be released, and thus it is
+            // wiped before being dropped
+            #[cfg(feature = "zeroize")]
+            zeroize_elements(&mut plaintext);
+            Err(CipherError::AuthenticationFailed)
+        }
+    }
+}
+
+impl fmt::Debug for RpoAead {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpoAead").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for RpoAead {
+    fn drop(&mut self) {
+        zeroize_elements(&mut self.key);
+    }
+}
+
+// AEAD SPONGE
+// ================================================================================================
+
+/// State of the duplex sponge used by [RpoAead].
+struct AeadSponge {
+    state: [Felt; STATE_WIDTH],
+    /// Index of the next element to be absorbed, encrypted, or decrypted within the rate.
+    rate_idx: usize,
+}
+
+impl AeadSponge {
+    /// Adds the provided element to the next element of the rate.
+    fn absorb(&mut self, element: Felt) {
+        self.state[RATE_START + self.rate_idx] += element;
+        self.advance();
+    }
+
+    /// Adds the provided plaintext element to the next element of the rate, and returns the
+    /// result as the ciphertext element.
+    fn encrypt(&mut self, element: Felt) -> Felt {
+        let idx = RATE_START + self.rate_idx;
+        self.state[idx] += element;
+        let ciphertext = self.state[idx];
+        self.advance();
+        ciphertext
+    }
+
+    /// Returns the plaintext element for the provided ciphertext element, and overwrites the
+    /// next element of the rate with the ciphertext element.
+    fn decrypt(&mut self, element: Felt) -> Felt {
+        let idx = RATE_START + self.rate_idx;
+        let plaintext = element - self.state[idx];
+        self.state[idx] = element;
+        self.advance();
+        plaintext
+    }
+
+    /// Pads the processed plaintext and returns the authentication tag.
+    fn finalize(mut self) -> Word {
+        self.pad_and_permute();
+        self.state[Rpo256::DIGEST_RANGE].try_into().unwrap()
+    }
+
+    /// Moves to the next element of the rate, applying the permutation if the rate is full.
+    fn advance(&mut self) {
+        self.rate_idx += 1;
+        if self.rate_idx == RATE_WIDTH {
+            Rpo256::apply_permutation(&mut self.state);
+            self.rate_idx = 0;
+        }
+    }
+
+    /// Adds a 1 to the next element of the rate and applies the permutation.
+    fn pad_and_permute(&mut self) {
+        self.state[RATE_START + self.rate_idx] += ONE;
+        Rpo256::apply_permutation(&mut self.state);
+        self.rate_idx = 0;
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for AeadSponge {
+    fn drop(&mut self) {
+        zeroize_elements(&mut self.state);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Packs the provided bytes into field elements 7 bytes at a time, after padding them with a 1
+/// byte followed by as many 0 bytes as necessary to make their length a multiple of 7.
+fn bytes_to_elements(bytes: &[u8]) -> Vec<Felt> {
+    let mut elements = Vec::with_capacity(bytes.len() / BINARY_CHUNK_SIZE + 1);
+    let mut chunks = bytes.chunks_exact(BINARY_CHUNK_SIZE);
+    let mut buf = [0_u8; 8];
+    for chunk in chunks.by_ref() {
+        buf[..BINARY_CHUNK_SIZE].copy_from_slice(chunk);
+        elements.push(Felt::new(u64::from_le_bytes(buf)));
+    }
+
+    let remainder = chunks.remainder();
+    buf.fill(0);
+    buf[..remainder.len()].copy_from_slice(remainder);
+    buf[remainder.len()] = 1;
+    elements.push(Felt::new(u64::from_le_bytes(buf)));
+
+    #[cfg(feature = "zeroize")]
+    zeroize::Zeroize::zeroize(&mut buf);
+
+    elements
+}
+
+/// Unpacks the bytes packed by [bytes_to_elements()] and removes their padding.
+fn elements_to_bytes(elements: &[Felt]) -> Result<Vec<u8>, CipherError> {
+    let mut bytes = Vec::with_capacity(elements.len() * BINARY_CHUNK_SIZE);
+    for element in elements {
+        let value = element.as_int().to_le_bytes();
+        if value[BINARY_CHUNK_SIZE] != 0 {
+            #[cfg(feature = "zeroize")]
+            zeroize::Zeroize::zeroize(&mut bytes);
+
+            return Err(CipherError::InvalidPadding);
+        }
+        bytes.extend_from_slice(&value[..BINARY_CHUNK_SIZE]);
+    }
+
+    // the padding occupies between 1 and 7 bytes at the end of the last element
+    let padding_start = bytes.iter().rposition(|&byte| byte != 0);
+    match padding_start {
+        Some(idx) if bytes[idx] == 1 && bytes.len() - idx <= BINARY_CHUNK_SIZE => {
+            bytes.truncate(idx);
+            Ok(bytes)
+        }
+        _ => {
+            #[cfg(feature = "zeroize")]
+            zeroize::Zeroize::zeroize(&mut bytes);
+
+            Err(CipherError::InvalidPadding)
+        }
+    }
+}
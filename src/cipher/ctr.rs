@@ -0,0 +1,211 @@
+use core::fmt;
+
+#[cfg(feature = "zeroize")]
+use crate::utils::zeroize_elements;
+use crate::{hash::rpo::Rpo256, Felt, Word, ONE, WORD_SIZE, ZERO};
+
+// CONSTANTS
+// ================================================================================================
+
+const STATE_WIDTH: usize = Rpo256::STATE_WIDTH;
+const RATE_START: usize = Rpo256::RATE_RANGE.start;
+const RATE_WIDTH: usize = Rpo256::RATE_RANGE.end - Rpo256::RATE_RANGE.start;
+
+/// Index of the rate element which holds the lower 32 bits of the block counter.
+const COUNTER_IDX: usize = RATE_START + WORD_SIZE;
+
+/// Index of the capacity element to which the domain separator of the keystream is added.
+///
+/// The domain separator is placed into the capacity rather than into the rate, since inputs
+/// absorbed into the rate by [Rpo256::mac()] and [RpoAead] can take any value, including the
+/// value of the separator (e.g., the 1 of the padding of an input of 7 elements).
+///
+/// [RpoAead]: super::RpoAead
+const DOMAIN_IDX: usize = Rpo256::CAPACITY_RANGE.start;
+
+/// The number of keystream blocks computed at a time.
+const BATCH_BLOCKS: usize = 8;
+
+/// The number of keystream elements computed at a time.
+const BUFFER_SIZE: usize = BATCH_BLOCKS * RATE_WIDTH;
+
+// RPO CTR
+// ================================================================================================
+
+/// A keystream generator based on the RPO permutation used in counter mode.
+///
+/// The `i`-th block of the keystream is computed by placing the key into the capacity portion of
+/// the state and adding 1 to its first element, placing the nonce and the lower and the upper
+/// 32 bits of `i` into the rate portion of the state, applying the permutation, and reading the
+/// full rate portion of the result. Thus, every block consists of 8 field elements, and blocks can
+/// be computed independently of each other.
+///
+/// The 1 added to the capacity separates the keystream from [Rpo256::mac()] (and thus
+/// [RpoPrf](crate::hash::prf::RpoPrf)) and from [RpoAead](super::RpoAead) under the same key:
+/// both of them start from a capacity equal to the key, and thus no input to them results in the
+/// state of a keystream block.
+///
+/// Blocks are computed in batches of 8 via [Rpo256::apply_permutation_batch()], and thus use the
+/// same (possibly vectorized) implementation of the permutation as batch hashing.
+///
+/// The keystream does not authenticate the masked data, and it must never be reused: masking two
+/// payloads with the same key and nonce reveals the difference of the payloads.
+///
+/// When the `zeroize` feature is enabled, the key and the buffered keystream are wiped from
+/// memory when the generator is dropped.
+#[derive(Clone)]
+pub struct RpoCtr {
+    key: Word,
+    nonce: Word,
+    /// Index of the first block of the next batch.
+    counter: u64,
+    buffer: [Felt; BUFFER_SIZE],
+    buffer_idx: usize,
+    /// Bytes of the last element which have not been returned by `fill_bytes()` yet.
+    bytes: [u8; 8],
+    bytes_idx: usize,
+}
+
+impl RpoCtr {
+    /// Returns a new keystream generator for the provided key and nonce, positioned at the start
+    /// of the keystream.
+    pub fn new(key: Word, nonce: Word) -> Self {
+        Self {
+            key,
+            nonce,
+            counter: 0,
+            buffer: [ZERO; BUFFER_SIZE],
+            buffer_idx: BUFFER_SIZE,
+            bytes: [0; 8],
+            bytes_idx: 8,
+        }
+    }
+
+    // KEYSTREAM
+    // --------------------------------------------------------------------------------------------
+
+    /// Fills the provided slice with the next field elements of the keystream.
+    ///
+    /// Any bytes of a field element which have been partially returned by
+    /// [RpoCtr::fill_bytes()] are discarded.
+    pub fn fill_elements(&mut self, output: &mut [Felt]) {
+        self.bytes_idx = self.bytes.len();
+        for element in output.iter_mut() {
+            *element = self.next_element();
+        }
+    }
+
+    /// Fills the provided slice with the next bytes of the keystream.
+    ///
+    /// Each field element of the keystream is converted into 8 bytes in little-endian order.
+    /// Since the elements are uniformly distributed over the field rather than over all 64-bit
+    /// values, the resulting bytes deviate from the uniform distribution by about 2^-32.
+    pub fn fill_bytes(&mut self, output: &mut [u8]) {
+        for byte in output.iter_mut() {
+            if self.bytes_idx == self.bytes.len() {
+                self.bytes = self.next_element().as_int().to_le_bytes();
+                self.bytes_idx = 0;
+            }
+            *byte = self.bytes[self.bytes_idx];
+            self.bytes_idx += 1;
+        }
+    }
+
+    // MASKING
+    // --------------------------------------------------------------------------------------------
+
+    /// Masks the provided field elements by adding the next elements of the keystream to them.
+    pub fn mask_elements(&mut self, elements: &mut [Felt]) {
+        self.bytes_idx = self.bytes.len();
+        for element in elements.iter_mut() {
+            *element += self.next_element();
+        }
+    }
+
+    /// Removes the mask applied by [RpoCtr::mask_elements()] by subtracting the next elements of
+    /// the keystream from the provided field elements.
+    pub fn unmask_elements(&mut self, elements: &mut [Felt]) {
+        self.bytes_idx = self.bytes.len();
+        for element in elements.iter_mut() {
+            *element -= self.next_element();
+        }
+    }
+
+    /// XORs the provided bytes with the next bytes of the keystream, which both masks and
+    /// unmasks them.
+    pub fn apply_keystream(&mut self, bytes: &mut [u8]) {
+        for byte in bytes.iter_mut() {
+            if self.bytes_idx == self.bytes.len() {
+                self.bytes = self.next_element().as_int().to_le_bytes();
+                self.bytes_idx = 0;
+            }
+            *byte ^= self.bytes[self.bytes_idx];
+            self.bytes_idx += 1;
+        }
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the next field element of the keystream, computing the next batch of blocks once
+    /// the current one has been read.
+    fn next_element(&mut self) -> Felt {
+        if self.buffer_idx == BUFFER_SIZE {
+            self.refill_buffer();
+        }
+        let element = self.buffer[self.buffer_idx];
+        self.buffer_idx += 1;
+        element
+    }
+
+    /// Computes the next [BATCH_BLOCKS] blocks of the keystream.
+    fn refill_buffer(&mut self) {
+        let mut states = [[ZERO; STATE_WIDTH]; BATCH_BLOCKS];
+        for (i, state) in states.iter_mut().enumerate() {
+            let counter = self.counter + i as u64;
+            state[Rpo256::CAPACITY_RANGE].copy_from_slice(&self.key);
+            state[DOMAIN_IDX] += ONE;
+            state[RATE_START..RATE_START + WORD_SIZE].copy_from_slice(&self.nonce);
+            state[COUNTER_IDX] = Felt::new(counter & u32::MAX as u64);
+            state[COUNTER_IDX + 1] = Felt::new(counter >> 32);
+        }
+        Rpo256::apply_permutation_batch(&mut states);
+
+        for (block, state) in self.buffer.chunks_exact_mut(RATE_WIDTH).zip(states.iter()) {
+            block.copy_from_slice(&state[Rpo256::RATE_RANGE]);
+        }
+
+        #[cfg(feature = "zeroize")]
+        states.iter_mut().for_each(|state| zeroize_elements(state));
+        self.counter += BATCH_BLOCKS as u64;
+        self.buffer_idx = 0;
+    }
+}
+
+impl Iterator for RpoCtr {
+    type Item = Felt;
+
+    /// Returns the next field element of the keystream, discarding any bytes of a field element
+    /// which have been partially returned by [RpoCtr::fill_bytes()].
+    fn next(&mut self) -> Option<Felt> {
+        self.bytes_idx = self.bytes.len();
+        Some(self.next_element())
+    }
+}
+
+impl fmt::Debug for RpoCtr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpoCtr").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for RpoCtr {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+
+        zeroize_elements(&mut self.key);
+        zeroize_elements(&mut self.buffer);
+        self.bytes.zeroize();
+    }
+}
@@ -0,0 +1,31 @@
+use core::fmt;
+
+// CIPHER ERROR
+// ================================================================================================
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CipherError {
+    AuthenticationFailed,
+    InvalidCiphertextLength(usize),
+    InvalidPadding,
+    NonCanonicalElement,
+}
+
+impl fmt::Display for CipherError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use CipherError::*;
+        match self {
+            AuthenticationFailed => write!(f, "Authentication tag of the ciphertext is not valid"),
+            InvalidCiphertextLength(len) => {
+                write!(f, "Ciphertext length {len} is not valid for the encryption scheme")
+            }
+            InvalidPadding => write!(f, "Decrypted plaintext is not padded correctly"),
+            NonCanonicalElement => {
+                write!(f, "Ciphertext contains a non-canonical encoding of a field element")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CipherError {}
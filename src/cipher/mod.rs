@@ -0,0 +1,23 @@
+//! Symmetric encryption schemes based on the RPO permutation.
+//!
+//! The schemes in this module operate natively over field elements, and thus data encrypted with
+//! them can be decrypted (and authenticated) inside the Miden VM at the cost of a few RPO
+//! permutations.
+//!
+//! Currently, the module provides:
+//! * [RpoAead]: an authenticated encryption scheme with associated data (AEAD) built from a
+//!   keyed duplex sponge over the RPO permutation.
+//! * [RpoCtr]: a keystream generator based on the RPO permutation in counter mode, which can be
+//!   used to mask field elements or bytes.
+
+mod aead;
+pub use aead::{RpoAead, TAG_SIZE};
+
+mod ctr;
+pub use ctr::RpoCtr;
+
+mod error;
+pub use error::CipherError;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,267 @@
+use alloc::vec::Vec;
+
+use proptest::prelude::*;
+use rand_utils::{rand_array, rand_vector};
+
+use super::{CipherError, RpoAead, RpoCtr, TAG_SIZE};
+use crate::{hash::rpo::Rpo256, Felt, Word, ONE, ZERO};
+
+// RPO AEAD
+// ================================================================================================
+
+#[test]
+fn seal_open_elements() {
+    let aead = RpoAead::new(rand_array());
+    let nonce: Word = rand_array();
+    let associated_data = rand_vector::<Felt>(5);
+
+    for len in [0, 1, 7, 8, 9, 16, 17] {
+        let plaintext: Vec<Felt> = (0..len).map(|i| Felt::new(i as u64)).collect();
+        let ciphertext = aead.seal_elements(nonce, &associated_data, &plaintext);
+        assert_eq!(ciphertext.len(), len + TAG_SIZE);
+        assert_eq!(aead.open_elements(nonce, &associated_data, &ciphertext), Ok(plaintext));
+    }
+}
+
+#[test]
+fn seal_open_bytes() {
+    let aead = RpoAead::new(rand_array());
+    let nonce: Word = rand_array();
+    let bytes = rand_vector::<u8>(64);
+
+    for len in [0, 1, 6, 7, 8, 14, 55, 56, 64] {
+        let plaintext = &bytes[..len];
+        let ciphertext = aead.seal_bytes(nonce, &bytes[..3], plaintext);
+        assert_eq!(ciphertext.len(), (len / 7 + 1 + TAG_SIZE) * 8);
+        assert_eq!(aead.open_bytes(nonce, &bytes[..3], &ciphertext).unwrap(), plaintext);
+    }
+}
+
+#[test]
+fn seal_is_deterministic_and_nonce_dependent() {
+    let aead = RpoAead::new(rand_array());
+    let plaintext = rand_vector::<Felt>(10);
+
+    let nonce: Word = rand_array();
+    let ciphertext = aead.seal_elements(nonce, &[], &plaintext);
+    assert_eq!(ciphertext, aead.seal_elements(nonce, &[], &plaintext));
+
+    let mut other_nonce = nonce;
+    other_nonce[3] += ONE;
+    let other_ciphertext = aead.seal_elements(other_nonce, &[], &plaintext);
+    assert_ne!(ciphertext[..plaintext.len()], other_ciphertext[..plaintext.len()]);
+}
+
+#[test]
+fn open_rejects_modified_inputs() {
+    let key: Word = rand_array();
+    let aead = RpoAead::new(key);
+    let nonce: Word = rand_array();
+    let associated_data = rand_vector::<Felt>(3);
+    let plaintext = rand_vector::<Felt>(12);
+    let ciphertext = aead.seal_elements(nonce, &associated_data, &plaintext);
+
+    // modified ciphertext and tag elements
+    for idx in [0, 11, 12, 15] {
+        let mut modified = ciphertext.clone();
+        modified[idx] += ONE;
+        assert_eq!(
+            aead.open_elements(nonce, &associated_data, &modified),
+            Err(CipherError::AuthenticationFailed)
+        );
+    }
+
+    // truncated ciphertext
+    assert_eq!(
+        aead.open_elements(nonce, &associated_data, &ciphertext[1..]),
+        Err(CipherError::AuthenticationFailed)
+    );
+
+    // modified associated data
+    assert_eq!(
+        aead.open_elements(nonce, &associated_data[..2], &ciphertext),
+        Err(CipherError::AuthenticationFailed)
+    );
+
+    // modified nonce
+    let mut other_nonce = nonce;
+    other_nonce[0] += ONE;
+    assert_eq!(
+        aead.open_elements(other_nonce, &associated_data, &ciphertext),
+        Err(CipherError::AuthenticationFailed)
+    );
+
+    // modified key
+    let mut other_key = key;
+    other_key[2] += ONE;
+    assert_eq!(
+        RpoAead::new(other_key).open_elements(nonce, &associated_data, &ciphertext),
+        Err(CipherError::AuthenticationFailed)
+    );
+
+    // moving elements from the plaintext to the associated data
+    let mut extended_data = associated_data.clone();
+    extended_data.push(plaintext[0]);
+    let other_ciphertext = aead.seal_elements(nonce, &extended_data, &plaintext[1..]);
+    assert_ne!(ciphertext[plaintext.len()..], other_ciphertext[plaintext.len() - 1..]);
+}
+
+#[test]
+fn open_rejects_invalid_ciphertexts() {
+    let aead = RpoAead::new(rand_array());
+    let nonce: Word = rand_array();
+
+    assert_eq!(
+        aead.open_elements(nonce, &[], &[ONE; TAG_SIZE - 1]),
+        Err(CipherError::InvalidCiphertextLength(TAG_SIZE - 1))
+    );
+
+    let ciphertext = aead.seal_bytes(nonce, &[], b"some data");
+    assert_eq!(
+        aead.open_bytes(nonce, &[], &ciphertext[1..]),
+        Err(CipherError::InvalidCiphertextLength(ciphertext.len() - 1))
+    );
+    assert_eq!(
+        aead.open_bytes(nonce, &[], &ciphertext[..TAG_SIZE * 8]),
+        Err(CipherError::InvalidCiphertextLength(TAG_SIZE * 8))
+    );
+
+    let mut non_canonical = ciphertext.clone();
+    non_canonical[..8].fill(0xff);
+    assert_eq!(
+        aead.open_bytes(nonce, &[], &non_canonical),
+        Err(CipherError::NonCanonicalElement)
+    );
+
+    // ciphertexts of field elements cannot be opened as ciphertexts of bytes and vice versa
+    let elements = aead.seal_elements(nonce, &[], &[Felt::new(1)]);
+    let bytes: Vec<u8> = elements.iter().flat_map(|e| e.as_int().to_le_bytes()).collect();
+    assert_eq!(aead.open_bytes(nonce, &[], &bytes), Err(CipherError::AuthenticationFailed));
+
+    let elements: Vec<Felt> = ciphertext
+        .chunks(8)
+        .map(|chunk| Felt::new(u64::from_le_bytes(chunk.try_into().unwrap())))
+        .collect();
+    assert_eq!(
+        aead.open_elements(nonce, &[], &elements),
+        Err(CipherError::AuthenticationFailed)
+    );
+}
+
+// RPO CTR
+// ================================================================================================
+
+#[test]
+fn ctr_keystream_blocks() {
+    let key: Word = rand_array();
+    let nonce: Word = rand_array();
+    let keystream: Vec<Felt> = RpoCtr::new(key, nonce).take(100).collect();
+
+    // every block is the rate portion of the permuted state for the block counter; blocks 8
+    // and 9 belong to the second batch of blocks
+    for (i, block) in keystream.chunks(8).enumerate() {
+        let mut state = [ZERO; Rpo256::STATE_WIDTH];
+        state[..4].copy_from_slice(&key);
+        state[4..8].copy_from_slice(&nonce);
+        state[0] += ONE;
+        state[8] = Felt::new(i as u64);
+        Rpo256::apply_permutation(&mut state);
+        assert_eq!(block, &state[4..4 + block.len()]);
+    }
+
+    let mut other_nonce = nonce;
+    other_nonce[0] += ONE;
+    let other_keystream: Vec<Felt> = RpoCtr::new(key, other_nonce).take(100).collect();
+    assert_ne!(keystream, other_keystream);
+}
+
+#[test]
+fn ctr_keystream_is_separated_from_mac() {
+    // the rate of a keystream block consists of the nonce, the two halves of the block counter
+    // and two zeros; a MAC of the nonce, the counter and a zero under the same key would produce
+    // the same state if the domain separator were the 1 of the padding, and thus the first half
+    // of the block would be equal to the MAC.
+    let key: Word = rand_array();
+    let nonce: Word = rand_array();
+    let keystream: Vec<Felt> = RpoCtr::new(key, nonce).take(80).collect();
+
+    for (i, block) in keystream.chunks(8).enumerate() {
+        for len in 6..=8 {
+            let mut input = [ZERO; 8];
+            input[..4].copy_from_slice(&nonce);
+            input[4] = Felt::new(i as u64);
+            let mac = Rpo256::mac(key, &input[..len]);
+            assert_ne!(&block[..4], mac.as_elements());
+            assert!(block.iter().all(|element| !mac.contains(element)));
+        }
+    }
+}
+
+#[test]
+fn ctr_split_reads() {
+    let key: Word = rand_array();
+    let nonce: Word = rand_array();
+
+    let mut expected = [ZERO; 150];
+    RpoCtr::new(key, nonce).fill_elements(&mut expected);
+
+    let mut ctr = RpoCtr::new(key, nonce);
+    let mut actual = [ZERO; 150];
+    ctr.fill_elements(&mut actual[..3]);
+    ctr.fill_elements(&mut actual[3..70]);
+    ctr.fill_elements(&mut actual[70..]);
+    assert_eq!(expected, actual);
+
+    // bytes are the little-endian encodings of the elements
+    let mut bytes = [0_u8; 150 * 8];
+    let mut ctr = RpoCtr::new(key, nonce);
+    ctr.fill_bytes(&mut bytes[..5]);
+    ctr.fill_bytes(&mut bytes[5..]);
+    let expected_bytes: Vec<u8> = expected.iter().flat_map(|e| e.as_int().to_le_bytes()).collect();
+    assert_eq!(bytes.to_vec(), expected_bytes);
+
+    // reading elements discards partially read bytes
+    let mut ctr = RpoCtr::new(key, nonce);
+    ctr.fill_bytes(&mut [0_u8; 3]);
+    assert_eq!(ctr.next(), Some(expected[1]));
+}
+
+#[test]
+fn ctr_mask_unmask() {
+    let key: Word = rand_array();
+    let nonce: Word = rand_array();
+
+    let payload = rand_vector::<Felt>(77);
+    let mut masked = payload.clone();
+    RpoCtr::new(key, nonce).mask_elements(&mut masked);
+    assert_ne!(masked, payload);
+
+    let keystream: Vec<Felt> = RpoCtr::new(key, nonce).take(payload.len()).collect();
+    for ((m, p), k) in masked.iter().zip(payload.iter()).zip(keystream.iter()) {
+        assert_eq!(*m, *p + *k);
+    }
+
+    RpoCtr::new(key, nonce).unmask_elements(&mut masked);
+    assert_eq!(masked, payload);
+
+    let payload = rand_vector::<u8>(100);
+    let mut masked = payload.clone();
+    RpoCtr::new(key, nonce).apply_keystream(&mut masked);
+    assert_ne!(masked, payload);
+    RpoCtr::new(key, nonce).apply_keystream(&mut masked);
+    assert_eq!(masked, payload);
+}
+
+proptest! {
+    #[test]
+    fn seal_open_bytes_roundtrip(
+        ref associated_data in any::<Vec<u8>>(),
+        ref plaintext in any::<Vec<u8>>(),
+        key in any::<[u64; 4]>(),
+    ) {
+        let aead = RpoAead::new(key.map(Felt::new));
+        let nonce = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+        let ciphertext = aead.seal_bytes(nonce, associated_data, plaintext);
+        prop_assert_eq!(&aead.open_bytes(nonce, associated_data, &ciphertext).unwrap(), plaintext);
+    }
+}
@@ -0,0 +1,176 @@
+use alloc::string::ToString;
+use core::{
+    fmt,
+    iter::Sum,
+    ops::{Add, Neg, Sub},
+};
+
+use curve25519_dalek::Scalar;
+use rand::{CryptoRng, RngCore};
+
+use super::{PedersenError, BLINDING_FACTOR_LEN};
+use crate::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// BLINDING FACTOR
+// ================================================================================================
+
+/// A blinding factor of a [PedersenCommitment](super::PedersenCommitment), i.e., a scalar of the
+/// Ristretto group.
+///
+/// Blinding factors add and subtract in the same way as the commitments they blind, which makes
+/// it possible to compute the blinding factor of a sum or a difference of commitments. A fresh
+/// blinding factor must be sampled uniformly at random for every commitment via
+/// [BlindingFactor::with_rng()], since the commitment hides the committed value only if its
+/// blinding factor is unknown.
+///
+/// When the `zeroize` feature is enabled, the blinding factor is wiped from memory when it is
+/// dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BlindingFactor(pub(super) Scalar);
+
+impl BlindingFactor {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Generates a blinding factor from OS-provided randomness.
+    #[cfg(feature = "std")]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self::with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a blinding factor using the provided random number generator.
+    pub fn with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self(Scalar::random(rng))
+    }
+
+    /// Returns the blinding factor which is equal to zero.
+    ///
+    /// A commitment with this blinding factor does not hide the committed value, and thus this
+    /// is intended only as the starting point of sums of blinding factors.
+    pub fn zero() -> Self {
+        Self(Scalar::ZERO)
+    }
+
+    /// Returns a new [BlindingFactor] decoded from the provided canonical little-endian encoding
+    /// of a scalar.
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes are not a canonical encoding of a scalar.
+    pub fn from_bytes(bytes: &[u8; BLINDING_FACTOR_LEN]) -> Result<Self, PedersenError> {
+        Option::from(Scalar::from_canonical_bytes(*bytes))
+            .map(Self)
+            .ok_or(PedersenError::BlindingFactorDecodingFailed)
+    }
+
+    /// Returns the blinding factor which balances the provided blinding factors, i.e., the sum of
+    /// the blinding factors of the inputs minus the sum of the blinding factors of the outputs.
+    ///
+    /// If the committed values of the inputs and of the outputs have equal sums, then the sum of
+    /// the input commitments minus the sum of the output commitments is a commitment to zero with
+    /// this blinding factor. Alternatively, using this blinding factor for an additional output
+    /// makes the commitments of the inputs and the outputs sum to the same point.
+    pub fn balancing(inputs: &[BlindingFactor], outputs: &[BlindingFactor]) -> Self {
+        inputs.iter().sum::<Self>() - outputs.iter().sum::<Self>()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the canonical little-endian encoding of this blinding factor.
+    pub fn to_bytes(&self) -> [u8; BLINDING_FACTOR_LEN] {
+        self.0.to_bytes()
+    }
+}
+
+impl fmt::Debug for BlindingFactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlindingFactor").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for BlindingFactor {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0);
+    }
+}
+
+// ARITHMETIC
+// ================================================================================================
+
+impl Add<&BlindingFactor> for &BlindingFactor {
+    type Output = BlindingFactor;
+
+    fn add(self, rhs: &BlindingFactor) -> BlindingFactor {
+        BlindingFactor(self.0 + rhs.0)
+    }
+}
+
+impl Add for BlindingFactor {
+    type Output = BlindingFactor;
+
+    fn add(self, rhs: BlindingFactor) -> BlindingFactor {
+        &self + &rhs
+    }
+}
+
+impl Sub<&BlindingFactor> for &BlindingFactor {
+    type Output = BlindingFactor;
+
+    fn sub(self, rhs: &BlindingFactor) -> BlindingFactor {
+        BlindingFactor(self.0 - rhs.0)
+    }
+}
+
+impl Sub for BlindingFactor {
+    type Output = BlindingFactor;
+
+    fn sub(self, rhs: BlindingFactor) -> BlindingFactor {
+        &self - &rhs
+    }
+}
+
+impl Neg for &BlindingFactor {
+    type Output = BlindingFactor;
+
+    fn neg(self) -> BlindingFactor {
+        BlindingFactor(-self.0)
+    }
+}
+
+impl Neg for BlindingFactor {
+    type Output = BlindingFactor;
+
+    fn neg(self) -> BlindingFactor {
+        -&self
+    }
+}
+
+impl<'a> Sum<&'a BlindingFactor> for BlindingFactor {
+    fn sum<I: Iterator<Item = &'a BlindingFactor>>(iter: I) -> Self {
+        BlindingFactor(iter.map(|blinding| blinding.0).sum())
+    }
+}
+
+impl Sum for BlindingFactor {
+    fn sum<I: Iterator<Item = BlindingFactor>>(iter: I) -> Self {
+        BlindingFactor(iter.map(|blinding| blinding.0).sum())
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for BlindingFactor {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_bytes());
+    }
+}
+
+impl Deserializable for BlindingFactor {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes: [u8; BLINDING_FACTOR_LEN] = source.read_array()?;
+        Self::from_bytes(&bytes).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
@@ -0,0 +1,27 @@
+use core::fmt;
+
+// PEDERSEN ERROR
+// ================================================================================================
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PedersenError {
+    BlindingFactorDecodingFailed,
+    CommitmentDecodingFailed,
+}
+
+impl fmt::Display for PedersenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use PedersenError::*;
+        match self {
+            BlindingFactorDecodingFailed => {
+                write!(f, "Failed to decode blinding factor: not a canonical scalar encoding")
+            }
+            CommitmentDecodingFailed => {
+                write!(f, "Failed to decode commitment: not a valid compressed Ristretto point")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for PedersenError {}
@@ -0,0 +1,201 @@
+//! An additively homomorphic Pedersen commitment scheme over the Ristretto group.
+//!
+//! A value `v` is committed to with a blinding factor `r` as `C = v * G + r * H`, where `G` is
+//! the Ristretto basepoint and `H` is a second generator whose discrete logarithm with respect to
+//! `G` is unknown. The commitment perfectly hides `v` (as long as `r` is uniformly random and
+//! secret), and computationally binds the committer to `v` and `r` under the discrete logarithm
+//! assumption.
+//!
+//! Commitments are additively homomorphic: the sum of commitments to `v1` and `v2` with blinding
+//! factors `r1` and `r2` is a commitment to `v1 + v2` with blinding factor `r1 + r2`. Thus, a
+//! party can show that the committed values of a set of inputs and a set of outputs have equal
+//! sums (e.g., in a confidential transfer) by revealing only the balancing blinding factor (see
+//! [BlindingFactor::balancing()]). Note that the commitments alone do not prove that the
+//! committed values are in any particular range.
+//!
+//! The generator `H` is derived by hashing the compressed encoding of `G` to the group with
+//! SHA3-512, which matches the generators used by the `bulletproofs` crate. It is computed on
+//! first use and cached afterwards.
+
+use alloc::{boxed::Box, string::ToString};
+use core::{
+    iter::Sum,
+    ops::{Add, Neg, Sub},
+};
+
+use curve25519_dalek::{
+    constants::{RISTRETTO_BASEPOINT_COMPRESSED, RISTRETTO_BASEPOINT_POINT},
+    ristretto::{CompressedRistretto, RistrettoPoint},
+    traits::Identity,
+    Scalar,
+};
+use once_cell::race::OnceBox;
+
+use crate::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+mod blinding;
+mod error;
+
+pub use blinding::BlindingFactor;
+pub use error::PedersenError;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Commitment length as a u8 vector (compressed Ristretto point).
+pub const COMMITMENT_LEN: usize = 32;
+
+/// Blinding factor length as a u8 vector (canonical scalar encoding).
+pub const BLINDING_FACTOR_LEN: usize = 32;
+
+// GENERATORS
+// ================================================================================================
+
+/// Returns the generator `G` by which committed values are multiplied, i.e., the Ristretto
+/// basepoint.
+pub fn value_generator() -> RistrettoPoint {
+    RISTRETTO_BASEPOINT_POINT
+}
+
+/// Returns the generator `H` by which blinding factors are multiplied.
+///
+/// The generator is hashed to the group only on the first call; subsequent calls return the
+/// cached point.
+pub fn blinding_generator() -> RistrettoPoint {
+    *BLINDING_GENERATOR.get_or_init(|| {
+        Box::new(RistrettoPoint::hash_from_bytes::<sha3::Sha3_512>(
+            RISTRETTO_BASEPOINT_COMPRESSED.as_bytes(),
+        ))
+    })
+}
+
+/// The cached generator `H`, see [blinding_generator()].
+static BLINDING_GENERATOR: OnceBox<RistrettoPoint> = OnceBox::new();
+
+// PEDERSEN COMMITMENT
+// ================================================================================================
+
+/// A Pedersen commitment to a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PedersenCommitment(RistrettoPoint);
+
+impl PedersenCommitment {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a commitment to the provided value with the provided blinding factor.
+    pub fn new(value: u64, blinding: &BlindingFactor) -> Self {
+        Self::from_scalar(Scalar::from(value), blinding)
+    }
+
+    /// Returns the commitment to zero with a zero blinding factor, i.e., the identity element of
+    /// the group of commitments.
+    pub fn identity() -> Self {
+        Self(RistrettoPoint::identity())
+    }
+
+    /// Returns a new [PedersenCommitment] decoded from the provided compressed Ristretto point.
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes are not a valid encoding of a Ristretto point.
+    pub fn from_bytes(bytes: &[u8; COMMITMENT_LEN]) -> Result<Self, PedersenError> {
+        CompressedRistretto(*bytes)
+            .decompress()
+            .map(Self)
+            .ok_or(PedersenError::CommitmentDecodingFailed)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the compressed Ristretto point encoding of this commitment.
+    pub fn to_bytes(&self) -> [u8; COMMITMENT_LEN] {
+        self.0.compress().to_bytes()
+    }
+
+    // VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this is a commitment to the provided value with the provided blinding
+    /// factor.
+    ///
+    /// Since values are added modulo the order of the group, a sum of commitments can be opened
+    /// via this method only if the sum of the committed values fits into a u64. Otherwise,
+    /// [PedersenCommitment::verify_sum()] can be used.
+    pub fn verify(&self, value: u64, blinding: &BlindingFactor) -> bool {
+        *self == Self::new(value, blinding)
+    }
+
+    /// Returns true if this is a commitment to the sum of the provided values with the provided
+    /// blinding factor.
+    pub fn verify_sum(&self, values: &[u64], blinding: &BlindingFactor) -> bool {
+        let value: Scalar = values.iter().map(|&value| Scalar::from(value)).sum();
+        *self == Self::from_scalar(value, blinding)
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a commitment to the provided scalar with the provided blinding factor.
+    fn from_scalar(value: Scalar, blinding: &BlindingFactor) -> Self {
+        Self(RistrettoPoint::mul_base(&value) + blinding.0 * blinding_generator())
+    }
+}
+
+// ARITHMETIC
+// ================================================================================================
+
+impl Add for PedersenCommitment {
+    type Output = PedersenCommitment;
+
+    fn add(self, rhs: PedersenCommitment) -> PedersenCommitment {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl Sub for PedersenCommitment {
+    type Output = PedersenCommitment;
+
+    fn sub(self, rhs: PedersenCommitment) -> PedersenCommitment {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl Neg for PedersenCommitment {
+    type Output = PedersenCommitment;
+
+    fn neg(self) -> PedersenCommitment {
+        Self(-self.0)
+    }
+}
+
+impl<'a> Sum<&'a PedersenCommitment> for PedersenCommitment {
+    fn sum<I: Iterator<Item = &'a PedersenCommitment>>(iter: I) -> Self {
+        Self(iter.map(|commitment| commitment.0).sum())
+    }
+}
+
+impl Sum for PedersenCommitment {
+    fn sum<I: Iterator<Item = PedersenCommitment>>(iter: I) -> Self {
+        Self(iter.map(|commitment| commitment.0).sum())
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for PedersenCommitment {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_bytes());
+    }
+}
+
+impl Deserializable for PedersenCommitment {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes: [u8; COMMITMENT_LEN] = source.read_array()?;
+        Self::from_bytes(&bytes).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
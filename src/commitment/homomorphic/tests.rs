@@ -0,0 +1,114 @@
+use alloc::vec::Vec;
+
+use curve25519_dalek::{constants::RISTRETTO_BASEPOINT_COMPRESSED, ristretto::RistrettoPoint};
+use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+use super::{
+    blinding_generator, value_generator, BlindingFactor, PedersenCommitment, PedersenError,
+    COMMITMENT_LEN,
+};
+use crate::utils::{Deserializable, Serializable};
+
+// PEDERSEN COMMITMENT
+// ================================================================================================
+
+#[test]
+fn commit_and_verify() {
+    let mut rng = ChaCha20Rng::from_seed([0_u8; 32]);
+    let blinding = BlindingFactor::with_rng(&mut rng);
+    let commitment = PedersenCommitment::new(42, &blinding);
+
+    assert!(commitment.verify(42, &blinding));
+    assert!(!commitment.verify(43, &blinding));
+    assert!(!commitment.verify(42, &BlindingFactor::with_rng(&mut rng)));
+
+    // commitments to the same value with different blinding factors are different
+    let other_commitment = PedersenCommitment::new(42, &BlindingFactor::with_rng(&mut rng));
+    assert_ne!(commitment, other_commitment);
+
+    // the generators are independent of each other
+    assert_ne!(value_generator(), blinding_generator());
+    assert_eq!(
+        PedersenCommitment::new(0, &BlindingFactor::zero()),
+        PedersenCommitment::identity()
+    );
+}
+
+#[test]
+fn blinding_generator_matches_hash_to_group() {
+    let expected = RistrettoPoint::hash_from_bytes::<sha3::Sha3_512>(
+        RISTRETTO_BASEPOINT_COMPRESSED.as_bytes(),
+    );
+    assert_eq!(blinding_generator(), expected);
+    // the second call returns the cached generator
+    assert_eq!(blinding_generator(), expected);
+}
+
+#[test]
+fn commitments_are_homomorphic() {
+    let mut rng = ChaCha20Rng::from_seed([1_u8; 32]);
+    let r1 = BlindingFactor::with_rng(&mut rng);
+    let r2 = BlindingFactor::with_rng(&mut rng);
+    let c1 = PedersenCommitment::new(100, &r1);
+    let c2 = PedersenCommitment::new(30, &r2);
+
+    assert!((c1 + c2).verify(130, &(&r1 + &r2)));
+    assert!((c1 - c2).verify(70, &(&r1 - &r2)));
+    assert_eq!(c1 + (-c1), PedersenCommitment::identity());
+    assert_eq!(-(-&r1), r1);
+
+    // sums of values may exceed u64
+    let max = PedersenCommitment::new(u64::MAX, &r1) + PedersenCommitment::new(u64::MAX, &r2);
+    assert!(max.verify_sum(&[u64::MAX, u64::MAX], &(r1 + r2)));
+}
+
+#[test]
+fn balancing_blinding_factor() {
+    let mut rng = ChaCha20Rng::from_seed([2_u8; 32]);
+    let inputs = [
+        (100, BlindingFactor::with_rng(&mut rng)),
+        (50, BlindingFactor::with_rng(&mut rng)),
+    ];
+    let outputs = [
+        (120, BlindingFactor::with_rng(&mut rng)),
+        (30, BlindingFactor::with_rng(&mut rng)),
+    ];
+    let input_commitments: PedersenCommitment =
+        inputs.iter().map(|(v, r)| PedersenCommitment::new(*v, r)).sum();
+    let output_commitments: PedersenCommitment =
+        outputs.iter().map(|(v, r)| PedersenCommitment::new(*v, r)).sum();
+
+    let input_blindings: Vec<_> = inputs.iter().map(|(_, r)| r.clone()).collect();
+    let output_blindings: Vec<_> = outputs.iter().map(|(_, r)| r.clone()).collect();
+    let excess = BlindingFactor::balancing(&input_blindings, &output_blindings);
+    assert!((input_commitments - output_commitments).verify(0, &excess));
+
+    // the check fails if the values do not balance
+    let extra = PedersenCommitment::new(1, &BlindingFactor::zero());
+    assert!(!(input_commitments - output_commitments - extra).verify(0, &excess));
+}
+
+#[test]
+fn serialization() {
+    let mut rng = ChaCha20Rng::from_seed([3_u8; 32]);
+    let blinding = BlindingFactor::with_rng(&mut rng);
+    let commitment = PedersenCommitment::new(7, &blinding);
+
+    let bytes = commitment.to_bytes();
+    assert_eq!(bytes.len(), COMMITMENT_LEN);
+    assert_eq!(PedersenCommitment::from_bytes(&bytes), Ok(commitment));
+    assert_eq!(PedersenCommitment::read_from_bytes(&commitment.to_bytes()).unwrap(), commitment);
+    assert_eq!(BlindingFactor::from_bytes(&blinding.to_bytes()), Ok(blinding.clone()));
+    assert_eq!(BlindingFactor::read_from_bytes(&blinding.to_bytes()).unwrap(), blinding);
+    assert_eq!(commitment.to_bytes().to_vec(), Serializable::to_bytes(&commitment));
+
+    assert_eq!(
+        PedersenCommitment::from_bytes(&[0xff; COMMITMENT_LEN]),
+        Err(PedersenError::CommitmentDecodingFailed)
+    );
+    assert_eq!(
+        BlindingFactor::from_bytes(&[0xff; 32]),
+        Err(PedersenError::BlindingFactorDecodingFailed)
+    );
+    assert!(PedersenCommitment::read_from_bytes(&[0xff; COMMITMENT_LEN]).is_err());
+}
@@ -0,0 +1,189 @@
+//! A vector commitment scheme based on RPO Merkle trees.
+//!
+//! A vector of words is committed to as follows:
+//! 1. The words are padded with [EMPTY_WORD]s until their number is a power of two which is at
+//!    least two, and a [MerkleTree] is built with the padded words as its leaves.
+//! 2. The commitment is computed as `Rpo256::merge(&[root, [len, 0, 0, 0]])`, where `root` is the
+//!    root of the tree and `len` is the number of words in the vector (before padding).
+//!
+//! Binding the length of the vector into the commitment ensures that vectors which differ only by
+//! trailing [EMPTY_WORD]s have different commitments. The scheme is fixed, i.e., commitments
+//! computed by this module will not change between releases of this crate.
+//!
+//! A word of the vector is opened via an [OpeningProof], which consists of the length of the
+//! vector and the Merkle path from the word to the root of the tree.
+
+use alloc::vec::Vec;
+
+use crate::{
+    hash::rpo::{Rpo256, RpoDigest},
+    merkle::{MerkleError, MerklePath, MerkleTree, NodeIndex},
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    Felt, Word, EMPTY_WORD, ZERO,
+};
+
+pub mod homomorphic;
+
+#[cfg(test)]
+mod tests;
+
+// COMMITMENT
+// ================================================================================================
+
+/// Returns a commitment to the provided vector of words.
+///
+/// This is equivalent to `VectorCommitment::new(values).commitment()`, but avoids keeping the
+/// underlying tree around.
+pub fn commit(values: &[Word]) -> Word {
+    VectorCommitment::new(values).commitment()
+}
+
+/// Returns true if `proof` proves that `value` is the word at position `index` of the vector
+/// committed to by `commitment`.
+pub fn verify(commitment: Word, index: usize, value: Word, proof: &OpeningProof) -> bool {
+    if index as u64 >= proof.len || tree_depth(proof.len) != Some(proof.path.depth()) {
+        return false;
+    }
+
+    match proof.path.compute_root(index as u64, value.into()) {
+        Ok(root) => commitment_from_root(root, proof.len) == commitment,
+        Err(_) => false,
+    }
+}
+
+// VECTOR COMMITMENT
+// ================================================================================================
+
+/// A commitment to a vector of words, together with the data needed to open it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorCommitment {
+    tree: MerkleTree,
+    len: usize,
+    commitment: Word,
+}
+
+impl VectorCommitment {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a commitment to the provided vector of words.
+    pub fn new(values: &[Word]) -> Self {
+        let num_leaves = values.len().next_power_of_two().max(2);
+        let mut leaves = Vec::with_capacity(num_leaves);
+        leaves.extend_from_slice(values);
+        leaves.resize(num_leaves, EMPTY_WORD);
+
+        let tree = MerkleTree::new(leaves).expect("number of leaves is a power of two");
+        let commitment = commitment_from_root(tree.root(), values.len() as u64);
+        Self { tree, len: values.len(), commitment }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the commitment to the vector.
+    pub fn commitment(&self) -> Word {
+        self.commitment
+    }
+
+    /// Returns the number of words in the committed vector.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the committed vector is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the word at the specified position of the committed vector, or `None` if the
+    /// position is out of bounds.
+    pub fn get(&self, index: usize) -> Option<Word> {
+        if index >= self.len {
+            return None;
+        }
+        let index = NodeIndex::new(self.tree.depth(), index as u64).ok()?;
+        self.tree.get_node(index).ok().map(Word::from)
+    }
+
+    /// Returns a proof of the word at the specified position of the committed vector.
+    ///
+    /// # Errors
+    /// Returns an error if `index` is not smaller than the length of the vector.
+    pub fn open(&self, index: usize) -> Result<OpeningProof, MerkleError> {
+        if index >= self.len {
+            return Err(MerkleError::InvalidIndex {
+                depth: self.tree.depth(),
+                value: index as u64,
+            });
+        }
+
+        let path = self.tree.get_path(NodeIndex::new(self.tree.depth(), index as u64)?)?;
+        Ok(OpeningProof { len: self.len as u64, path })
+    }
+}
+
+// OPENING PROOF
+// ================================================================================================
+
+/// A proof that a word is located at a given position of a committed vector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpeningProof {
+    len: u64,
+    path: MerklePath,
+}
+
+impl OpeningProof {
+    /// Returns the number of words in the committed vector.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if the committed vector is empty.
+    ///
+    /// A proof for an empty vector never verifies, since an empty vector has no words to open.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the Merkle path from the opened word to the root of the tree.
+    pub fn path(&self) -> &MerklePath {
+        &self.path
+    }
+}
+
+impl Serializable for OpeningProof {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.len);
+        self.path.write_into(target);
+    }
+}
+
+impl Deserializable for OpeningProof {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let len = source.read_u64()?;
+        if tree_depth(len).is_none() {
+            return Err(DeserializationError::InvalidValue(format!(
+                "vector length {len} exceeds the maximum of 2^63"
+            )));
+        }
+        let path = MerklePath::read_from(source)?;
+        Ok(Self { len, path })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the depth of the tree which holds a vector of `len` words, or `None` if the number of
+/// words is greater than 2^63, in which case it cannot be padded to a power of two.
+fn tree_depth(len: u64) -> Option<u8> {
+    len.checked_next_power_of_two()
+        .map(|num_leaves| num_leaves.max(2).trailing_zeros() as u8)
+}
+
+/// Computes the commitment from the root of the tree and the length of the vector.
+fn commitment_from_root(root: RpoDigest, len: u64) -> Word {
+    let len = RpoDigest::new([Felt::new(len), ZERO, ZERO, ZERO]);
+    Rpo256::merge(&[root, len]).into()
+}
@@ -0,0 +1,103 @@
+use rand_utils::rand_value;
+
+use super::*;
+use crate::{utils::SliceReader, ONE};
+
+fn rand_words(n: usize) -> Vec<Word> {
+    (0..n).map(|_| rand_value::<RpoDigest>().into()).collect()
+}
+
+#[test]
+fn commitment_scheme() {
+    // the commitment is the Merkle root of the padded vector merged with its length
+    let values = rand_words(3);
+    let tree = MerkleTree::new([values[0], values[1], values[2], EMPTY_WORD]).unwrap();
+    let len = RpoDigest::new([Felt::new(3), ZERO, ZERO, ZERO]);
+    let expected: Word = Rpo256::merge(&[tree.root(), len]).into();
+    assert_eq!(commit(&values), expected);
+    assert_eq!(VectorCommitment::new(&values).commitment(), expected);
+
+    // trailing empty words change the commitment
+    let mut padded = values.clone();
+    padded.push(EMPTY_WORD);
+    assert_ne!(commit(&padded), commit(&values));
+
+    // vectors of zero or one words are committed to with a tree of two leaves
+    let tree = MerkleTree::new([EMPTY_WORD, EMPTY_WORD]).unwrap();
+    assert_eq!(commit(&[]), commitment_from_root(tree.root(), 0));
+    let tree = MerkleTree::new([values[0], EMPTY_WORD]).unwrap();
+    assert_eq!(commit(&values[..1]), commitment_from_root(tree.root(), 1));
+}
+
+#[test]
+fn commitment_openings() {
+    for n in [1, 2, 5, 8, 13] {
+        let values = rand_words(n);
+        let vc = VectorCommitment::new(&values);
+        assert_eq!(vc.len(), n);
+
+        for (i, &value) in values.iter().enumerate() {
+            assert_eq!(vc.get(i), Some(value));
+            let proof = vc.open(i).unwrap();
+            assert_eq!(proof.len(), n as u64);
+            assert!(verify(vc.commitment(), i, value, &proof));
+
+            // the proof does not verify for other values, positions or commitments
+            assert!(!verify(vc.commitment(), i, [ONE; 4], &proof));
+            assert!(!verify(vc.commitment(), i + 1, value, &proof));
+            assert!(!verify(commit(&values[..n - 1]), i, value, &proof));
+        }
+
+        assert_eq!(vc.get(n), None);
+        assert!(vc.open(n).is_err());
+    }
+}
+
+#[test]
+fn commitment_padding_cannot_be_opened() {
+    // a position in the padding holds an empty word in the tree, but it is not part of the vector
+    let values = rand_words(3);
+    let vc = VectorCommitment::new(&values);
+    let tree = MerkleTree::new([values[0], values[1], values[2], EMPTY_WORD]).unwrap();
+    let path = tree.get_path(NodeIndex::new(2, 3).unwrap()).unwrap();
+
+    let proof = OpeningProof { len: 3, path: path.clone() };
+    assert!(!verify(vc.commitment(), 3, EMPTY_WORD, &proof));
+    let proof = OpeningProof { len: 4, path };
+    assert!(!verify(vc.commitment(), 3, EMPTY_WORD, &proof));
+}
+
+#[test]
+fn commitment_empty_vector() {
+    let vc = VectorCommitment::new(&[]);
+    assert!(vc.is_empty());
+    assert_eq!(vc.get(0), None);
+    assert!(vc.open(0).is_err());
+}
+
+#[test]
+fn opening_proof_serialization() {
+    let values = rand_words(6);
+    let proof = VectorCommitment::new(&values).open(4).unwrap();
+
+    let bytes = proof.to_bytes();
+    let mut reader = SliceReader::new(&bytes);
+    assert_eq!(OpeningProof::read_from(&mut reader).unwrap(), proof);
+}
+
+#[test]
+fn opening_proof_oversized_length() {
+    let values = rand_words(6);
+    let vc = VectorCommitment::new(&values);
+    let proof = vc.open(4).unwrap();
+
+    // lengths which cannot be padded to a power of two are rejected instead of overflowing
+    assert_eq!(tree_depth(1 << 63), Some(63));
+    assert_eq!(tree_depth((1 << 63) + 1), None);
+    let oversized = OpeningProof {
+        len: u64::MAX,
+        path: proof.path().clone(),
+    };
+    assert!(!verify(vc.commitment(), 4, values[4], &oversized));
+    assert!(OpeningProof::read_from_bytes(&oversized.to_bytes()).is_err());
+}
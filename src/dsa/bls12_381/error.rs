@@ -0,0 +1,33 @@
+use core::fmt;
+
+// BLS ERROR
+// ================================================================================================
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlsError {
+    AggregationOfEmptySet,
+    PubKeyDecodingFailed,
+    SecKeyDecodingFailed,
+    SigDecodingFailed,
+}
+
+impl fmt::Display for BlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use BlsError::*;
+        match self {
+            AggregationOfEmptySet => write!(f, "Failed to aggregate: no elements were provided"),
+            PubKeyDecodingFailed => {
+                write!(f, "Failed to decode public key: not a valid non-identity point in G2")
+            }
+            SecKeyDecodingFailed => {
+                write!(f, "Failed to decode secret key: not a valid non-zero scalar")
+            }
+            SigDecodingFailed => {
+                write!(f, "Failed to decode signature: not a valid point in G1")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BlsError {}
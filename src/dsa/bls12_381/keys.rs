@@ -0,0 +1,315 @@
+use ::bls12_381::{G2Affine, G2Projective, Scalar};
+use alloc::string::ToString;
+use core::fmt;
+
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::{
+    hash_to_g1, message_to_bytes, pairing_check, BlsError, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, Serializable, Signature, Word, PK_LEN, POP_DST, SIG_DST, SK_LEN,
+};
+
+// PUBLIC KEY
+// ================================================================================================
+
+/// A BLS12-381 public key, i.e., a point in G2.
+///
+/// The public key is serialized as a 96-byte compressed G2 point. Decoding a public key checks
+/// that the point is in the prime-order subgroup and is not the identity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(G2Affine);
+
+impl PublicKey {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [PublicKey] decoded from the provided compressed G2 point.
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes are not a valid encoding of a point in G2, or if
+    /// the point is the identity.
+    pub fn from_bytes(bytes: &[u8; PK_LEN]) -> Result<Self, BlsError> {
+        let point: Option<G2Affine> = G2Affine::from_compressed(bytes).into();
+        match point {
+            Some(point) if !bool::from(point.is_identity()) => Ok(Self(point)),
+            _ => Err(BlsError::PubKeyDecodingFailed),
+        }
+    }
+
+    /// Aggregates the provided public keys into a single public key.
+    ///
+    /// The aggregated public key can be used to verify an aggregated signature of the same
+    /// message via [PublicKey::verify()]. To prevent rogue key attacks, possession of the secret
+    /// key corresponding to each of the aggregated public keys must be verified beforehand (see
+    /// [PublicKey::verify_possession()]).
+    ///
+    /// # Errors
+    /// Returns an error if no public keys were provided.
+    pub fn aggregate(public_keys: &[PublicKey]) -> Result<Self, BlsError> {
+        if public_keys.is_empty() {
+            return Err(BlsError::AggregationOfEmptySet);
+        }
+        let sum = public_keys.iter().fold(G2Projective::identity(), |acc, pk| acc + pk.0);
+        Ok(Self(sum.into()))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the compressed encoding of this public key.
+    pub fn to_bytes(&self) -> [u8; PK_LEN] {
+        self.0.to_compressed()
+    }
+
+    /// Returns the point in G2 representing this public key.
+    pub(super) fn point(&self) -> G2Affine {
+        self.0
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the provided signature is a valid signature of the specified message
+    /// against this public key.
+    pub fn verify(&self, message: Word, signature: &Signature) -> bool {
+        self.verify_bytes(&message_to_bytes(message), signature)
+    }
+
+    /// Returns true if the provided signature is a valid signature of the specified arbitrary
+    /// message against this public key.
+    pub fn verify_bytes(&self, message: &[u8], signature: &Signature) -> bool {
+        pairing_check(&signature.point(), &[(hash_to_g1(message, SIG_DST), self.0)])
+    }
+
+    /// Returns true if the provided proof of possession was generated with the secret key
+    /// corresponding to this public key.
+    pub fn verify_possession(&self, proof: &Signature) -> bool {
+        pairing_check(&proof.point(), &[(hash_to_g1(&self.to_bytes(), POP_DST), self.0)])
+    }
+}
+
+// SECRET KEY
+// ================================================================================================
+
+/// A BLS12-381 secret key for the minimal signature size variant of the BLS signature scheme,
+/// i.e., signatures are points in G1 and public keys are points in G2.
+///
+/// Messages are hashed to G1 using the proof of possession ciphersuite of the IETF BLS signature
+/// draft, and thus signatures of the same message can be aggregated securely as long as
+/// possession of the secret keys has been proven.
+///
+/// When the `zeroize` feature is enabled, the secret key is wiped from memory when it is dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretKey(Scalar);
+
+impl SecretKey {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Generates a secret key from OS-provided randomness.
+    #[cfg(feature = "std")]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self::with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a secret key using the provided random number generator.
+    pub fn with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        loop {
+            let mut bytes = [0_u8; 64];
+            rng.fill_bytes(&mut bytes);
+            let scalar = Scalar::from_bytes_wide(&bytes);
+
+            #[cfg(feature = "zeroize")]
+            bytes.zeroize();
+
+            if scalar != Scalar::zero() {
+                return Self(scalar);
+            }
+        }
+    }
+
+    /// Returns a new [SecretKey] decoded from the provided big-endian encoding of a scalar.
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes do not encode a non-zero scalar smaller than the
+    /// order of the curve.
+    pub fn from_bytes(bytes: &[u8; SK_LEN]) -> Result<Self, BlsError> {
+        let mut le_bytes = *bytes;
+        le_bytes.reverse();
+        let scalar: Option<Scalar> = Scalar::from_bytes(&le_bytes).into();
+
+        #[cfg(feature = "zeroize")]
+        le_bytes.zeroize();
+
+        match scalar {
+            Some(scalar) if scalar != Scalar::zero() => Ok(Self(scalar)),
+            _ => Err(BlsError::SecKeyDecodingFailed),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public key corresponding to this secret key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey((G2Affine::generator() * self.0).into())
+    }
+
+    /// Returns the big-endian encoding of this secret key.
+    pub fn to_bytes(&self) -> [u8; SK_LEN] {
+        let mut bytes = self.0.to_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    // SIGNATURE GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Signs the specified message with this secret key.
+    pub fn sign(&self, message: Word) -> Signature {
+        self.sign_bytes(&message_to_bytes(message))
+    }
+
+    /// Signs the specified arbitrary message with this secret key.
+    pub fn sign_bytes(&self, message: &[u8]) -> Signature {
+        Signature::new((hash_to_g1(message, SIG_DST) * self.0).into())
+    }
+
+    /// Returns a proof of possession of this secret key, i.e., a signature of the public key
+    /// under a dedicated domain separation tag.
+    pub fn prove_possession(&self) -> Signature {
+        let pk = self.public_key().to_bytes();
+        Signature::new((hash_to_g1(&pk, POP_DST) * self.0).into())
+    }
+}
+
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretKey")
+            .field("public_key", &self.public_key())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for SecretKey {}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for PublicKey {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_bytes());
+    }
+}
+
+impl Deserializable for PublicKey {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes: [u8; PK_LEN] = source.read_array()?;
+        Self::from_bytes(&bytes).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+impl Serializable for SecretKey {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_bytes());
+    }
+}
+
+impl Deserializable for SecretKey {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes: [u8; SK_LEN] = source.read_array()?;
+        Self::from_bytes(&bytes).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use rand_utils::rand_array;
+
+    use super::{BlsError, Deserializable, PublicKey, SecretKey, Serializable, Word};
+    use crate::Felt;
+
+    #[test]
+    fn test_bls_verification() {
+        let mut rng = ChaCha20Rng::from_seed([9_u8; 32]);
+        let sk = SecretKey::with_rng(&mut rng);
+        let pk = sk.public_key();
+
+        let message: Word = rand_array::<Felt, 4>();
+        let signature = sk.sign(message);
+        assert!(pk.verify(message, &signature));
+
+        // the signature should not verify against a different message or public key
+        let mut other_message = message;
+        other_message[1] += Felt::new(1);
+        assert!(!pk.verify(other_message, &signature));
+        assert!(!SecretKey::with_rng(&mut rng).public_key().verify(message, &signature));
+    }
+
+    #[test]
+    fn test_bls_proof_of_possession() {
+        let mut rng = ChaCha20Rng::from_seed([10_u8; 32]);
+        let sk = SecretKey::with_rng(&mut rng);
+        let pk = sk.public_key();
+
+        let proof = sk.prove_possession();
+        assert!(pk.verify_possession(&proof));
+
+        // a proof of possession is not a signature of the public key bytes
+        assert!(!pk.verify_bytes(&pk.to_bytes(), &proof));
+        assert!(!SecretKey::with_rng(&mut rng).public_key().verify_possession(&proof));
+    }
+
+    #[test]
+    fn test_bls_key_serialization() {
+        let mut rng = ChaCha20Rng::from_seed([11_u8; 32]);
+        let sk = SecretKey::with_rng(&mut rng);
+        let pk = sk.public_key();
+
+        let serialized = Serializable::to_bytes(&sk);
+        assert_eq!(SecretKey::read_from_bytes(&serialized).unwrap(), sk);
+        let serialized = Serializable::to_bytes(&pk);
+        assert_eq!(serialized.len(), 96);
+        assert_eq!(PublicKey::read_from_bytes(&serialized).unwrap(), pk);
+
+        // zero secret keys and identity public keys are rejected
+        assert_eq!(SecretKey::from_bytes(&[0_u8; 32]), Err(BlsError::SecKeyDecodingFailed));
+        let mut identity = [0_u8; 96];
+        identity[0] = 0xc0;
+        assert_eq!(PublicKey::from_bytes(&identity), Err(BlsError::PubKeyDecodingFailed));
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_bls_secret_key_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut sk = SecretKey::with_rng(&mut ChaCha20Rng::from_seed([12_u8; 32]));
+        assert!(sk.to_bytes().iter().any(|&b| b != 0));
+
+        sk.zeroize();
+        assert!(sk.to_bytes().iter().all(|&b| b == 0));
+    }
+}
@@ -0,0 +1,97 @@
+use ::bls12_381::{
+    hash_to_curve::{ExpandMsgXmd, HashToCurve},
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, Gt,
+};
+use alloc::vec::Vec;
+use core::convert::Infallible;
+
+use crate::{
+    dsa::SignatureScheme,
+    hash::rpo::RpoDigest,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    Word,
+};
+
+mod error;
+mod keys;
+mod signature;
+
+pub use error::BlsError;
+pub use keys::{PublicKey, SecretKey};
+pub use signature::Signature;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Secret key length as a u8 vector.
+pub const SK_LEN: usize = 32;
+
+/// Public key length as a u8 vector (compressed G2 point).
+pub const PK_LEN: usize = 96;
+
+/// Signature length as a u8 vector (compressed G1 point).
+pub const SIG_LEN: usize = 48;
+
+/// Domain separation tag used for hashing messages to G1, as specified for the proof of
+/// possession scheme of the minimal signature size variant in the IETF BLS signature draft.
+const SIG_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+/// Domain separation tag used for hashing public keys to G1 when proving possession of the
+/// corresponding secret key.
+const POP_DST: &[u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+// SIGNATURE SCHEME
+// ================================================================================================
+
+/// The BLS signature scheme over the BLS12-381 curve (minimal signature size variant).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bls12381;
+
+impl SignatureScheme for Bls12381 {
+    type SecretKey = SecretKey;
+    type PublicKey = PublicKey;
+    type Signature = Signature;
+    type Error = Infallible;
+
+    fn public_key(secret_key: &SecretKey) -> PublicKey {
+        secret_key.public_key()
+    }
+
+    fn sign(secret_key: &SecretKey, message: Word) -> Result<Signature, Infallible> {
+        Ok(secret_key.sign(message))
+    }
+
+    fn verify(public_key: &PublicKey, message: Word, signature: &Signature) -> bool {
+        public_key.verify(message, signature)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts a message represented as a [Word] into bytes by encoding each of its field elements
+/// as 8 little-endian bytes.
+fn message_to_bytes(message: Word) -> [u8; 32] {
+    RpoDigest::from(message).as_bytes()
+}
+
+/// Hashes the provided message to a point in G1 using the specified domain separation tag.
+fn hash_to_g1(message: &[u8], dst: &[u8]) -> G1Affine {
+    let point =
+        <G1Projective as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(message, dst);
+    point.into()
+}
+
+/// Returns true if e(signature, g2) is equal to the product of e(H(m_i), pk_i) over all
+/// (H(m_i), pk_i) pairs in `terms`.
+fn pairing_check(signature: &G1Affine, terms: &[(G1Affine, G2Affine)]) -> bool {
+    let minus_g2 = G2Prepared::from(-G2Affine::generator());
+    let prepared: Vec<(G1Affine, G2Prepared)> =
+        terms.iter().map(|(hm, pk)| (*hm, G2Prepared::from(*pk))).collect();
+
+    let mut loop_terms: Vec<(&G1Affine, &G2Prepared)> = Vec::with_capacity(terms.len() + 1);
+    loop_terms.push((signature, &minus_g2));
+    loop_terms.extend(prepared.iter().map(|(hm, pk)| (hm, pk)));
+
+    multi_miller_loop(&loop_terms).final_exponentiation() == Gt::identity()
+}
@@ -0,0 +1,191 @@
+use ::bls12_381::{G1Affine, G1Projective};
+use alloc::{string::ToString, vec::Vec};
+
+use super::{
+    hash_to_g1, message_to_bytes, pairing_check, BlsError, ByteReader, ByteWriter, Deserializable,
+    DeserializationError, PublicKey, Serializable, Word, SIG_DST, SIG_LEN,
+};
+
+// BLS SIGNATURE
+// ================================================================================================
+
+/// A BLS12-381 signature, i.e., a point in G1.
+///
+/// The signature is serialized as a 48-byte compressed G1 point. Decoding a signature checks that
+/// the point is in the prime-order subgroup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature(G1Affine);
+
+impl Signature {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [Signature] instantiated from the provided point in G1.
+    pub(super) fn new(point: G1Affine) -> Self {
+        Self(point)
+    }
+
+    /// Returns a new [Signature] decoded from the provided compressed G1 point.
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes are not a valid encoding of a point in G1.
+    pub fn from_bytes(bytes: &[u8; SIG_LEN]) -> Result<Self, BlsError> {
+        let point: Option<G1Affine> = G1Affine::from_compressed(bytes).into();
+        point.map(Self).ok_or(BlsError::SigDecodingFailed)
+    }
+
+    /// Aggregates the provided signatures into a single signature.
+    ///
+    /// # Errors
+    /// Returns an error if no signatures were provided.
+    pub fn aggregate(signatures: &[Signature]) -> Result<Self, BlsError> {
+        if signatures.is_empty() {
+            return Err(BlsError::AggregationOfEmptySet);
+        }
+        let sum = signatures.iter().fold(G1Projective::identity(), |acc, sig| acc + sig.0);
+        Ok(Self(sum.into()))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the compressed encoding of this signature.
+    pub fn to_bytes(&self) -> [u8; SIG_LEN] {
+        self.0.to_compressed()
+    }
+
+    /// Returns the point in G1 representing this signature.
+    pub(super) fn point(&self) -> G1Affine {
+        self.0
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this is a valid aggregated signature of the specified (public key,
+    /// message) pairs, i.e., if it is the aggregation of signatures of each message against the
+    /// corresponding public key.
+    ///
+    /// Returns false if no pairs were provided.
+    pub fn verify_aggregate(&self, pairs: &[(PublicKey, Word)]) -> bool {
+        if pairs.is_empty() {
+            return false;
+        }
+        let terms: Vec<_> = pairs
+            .iter()
+            .map(|(pk, message)| (hash_to_g1(&message_to_bytes(*message), SIG_DST), pk.point()))
+            .collect();
+        pairing_check(&self.0, &terms)
+    }
+
+    /// Returns true if this is a valid aggregated signature of the specified message against all
+    /// of the provided public keys.
+    ///
+    /// This is much faster than [Signature::verify_aggregate()] as only two pairings are computed
+    /// regardless of the number of public keys. As with [PublicKey::aggregate()], possession of
+    /// the secret keys corresponding to the provided public keys must be verified beforehand.
+    ///
+    /// Returns false if no public keys were provided.
+    pub fn fast_aggregate_verify(&self, public_keys: &[PublicKey], message: Word) -> bool {
+        match PublicKey::aggregate(public_keys) {
+            Ok(pk) => pk.verify(message, self),
+            Err(_) => false,
+        }
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for Signature {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_bytes());
+    }
+
+    fn get_size_hint(&self) -> usize {
+        SIG_LEN
+    }
+}
+
+impl Deserializable for Signature {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes: [u8; SIG_LEN] = source.read_array()?;
+        Self::from_bytes(&bytes).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use rand_utils::rand_array;
+
+    use super::{
+        super::{BlsError, SecretKey},
+        Deserializable, PublicKey, Serializable, Signature, Word,
+    };
+    use crate::Felt;
+
+    #[test]
+    fn test_bls_aggregation_same_message() {
+        let mut rng = ChaCha20Rng::from_seed([12_u8; 32]);
+        let keys: Vec<SecretKey> = (0..5).map(|_| SecretKey::with_rng(&mut rng)).collect();
+        let public_keys: Vec<PublicKey> = keys.iter().map(|sk| sk.public_key()).collect();
+
+        let message: Word = rand_array::<Felt, 4>();
+        let signatures: Vec<Signature> = keys.iter().map(|sk| sk.sign(message)).collect();
+        let aggregated = Signature::aggregate(&signatures).unwrap();
+
+        assert!(aggregated.fast_aggregate_verify(&public_keys, message));
+        assert!(!aggregated.fast_aggregate_verify(&public_keys[1..], message));
+        assert!(!aggregated.fast_aggregate_verify(&[], message));
+        assert!(!signatures[0].fast_aggregate_verify(&public_keys, message));
+
+        assert_eq!(Signature::aggregate(&[]), Err(BlsError::AggregationOfEmptySet));
+    }
+
+    #[test]
+    fn test_bls_aggregation_distinct_messages() {
+        let mut rng = ChaCha20Rng::from_seed([13_u8; 32]);
+        let pairs: Vec<(SecretKey, Word)> = (0..4)
+            .map(|_| (SecretKey::with_rng(&mut rng), rand_array::<Felt, 4>()))
+            .collect();
+
+        let signatures: Vec<Signature> = pairs.iter().map(|(sk, m)| sk.sign(*m)).collect();
+        let aggregated = Signature::aggregate(&signatures).unwrap();
+
+        let mut pairs: Vec<(PublicKey, Word)> =
+            pairs.iter().map(|(sk, m)| (sk.public_key(), *m)).collect();
+        assert!(aggregated.verify_aggregate(&pairs));
+        assert!(!aggregated.verify_aggregate(&pairs[1..]));
+        assert!(!aggregated.verify_aggregate(&[]));
+
+        // swapping the messages of two signers invalidates the signature
+        let message = pairs[0].1;
+        pairs[0].1 = pairs[1].1;
+        pairs[1].1 = message;
+        assert!(!aggregated.verify_aggregate(&pairs));
+    }
+
+    #[test]
+    fn test_bls_signature_serialization() {
+        let mut rng = ChaCha20Rng::from_seed([14_u8; 32]);
+        let sk = SecretKey::with_rng(&mut rng);
+        let signature = sk.sign(Word::default());
+
+        let serialized = Serializable::to_bytes(&signature);
+        assert_eq!(serialized.len(), 48);
+        let deserialized = Signature::read_from_bytes(&serialized).unwrap();
+        assert_eq!(deserialized, signature);
+        assert!(sk.public_key().verify(Word::default(), &deserialized));
+
+        // a point which is not on the curve is rejected
+        let mut invalid = signature.to_bytes();
+        invalid[47] ^= 1;
+        invalid[20] ^= 1;
+        assert!(Signature::from_bytes(&invalid).is_err());
+    }
+}
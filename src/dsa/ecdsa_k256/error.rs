@@ -0,0 +1,56 @@
+use core::fmt;
+
+use super::{PK_LEN, SIG_LEN, SK_LEN};
+
+// ECDSA ERROR
+// ================================================================================================
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EcdsaError {
+    PubKeyDecodingFailed,
+    PubKeyDecodingInvalidLength(usize),
+    PubKeyRecoveryFailed,
+    SecKeyDecodingFailed,
+    SecKeyDecodingInvalidLength(usize),
+    SigDecodingFailed,
+    SigDecodingInvalidLength(usize),
+    SigDecodingInvalidRecoveryId(u8),
+    SigGenerationFailed,
+}
+
+impl fmt::Display for EcdsaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use EcdsaError::*;
+        match self {
+            PubKeyDecodingFailed => {
+                write!(f, "Failed to decode public key: not a valid curve point")
+            }
+            PubKeyDecodingInvalidLength(len) => {
+                write!(f, "Failed to decode public key: expected {PK_LEN} bytes but received {len}")
+            }
+            PubKeyRecoveryFailed => write!(f, "Failed to recover public key from signature"),
+            SecKeyDecodingFailed => {
+                write!(f, "Failed to decode secret key: not a valid non-zero scalar")
+            }
+            SecKeyDecodingInvalidLength(len) => {
+                write!(f, "Failed to decode secret key: expected {SK_LEN} bytes but received {len}")
+            }
+            SigDecodingFailed => {
+                write!(f, "Failed to decode signature: r or s is not a valid non-zero scalar")
+            }
+            SigDecodingInvalidLength(len) => {
+                write!(f, "Failed to decode signature: expected {SIG_LEN} bytes but received {len}")
+            }
+            SigDecodingInvalidRecoveryId(byte) => {
+                write!(
+                    f,
+                    "Failed to decode signature: recovery id must be at most 3 but was {byte}"
+                )
+            }
+            SigGenerationFailed => write!(f, "Failed to generate a signature"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EcdsaError {}
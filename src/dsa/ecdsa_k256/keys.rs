@@ -0,0 +1,271 @@
+use alloc::string::ToString;
+
+use k256::ecdsa::{signature::hazmat::PrehashVerifier, SigningKey, VerifyingKey};
+use rand::{CryptoRng, RngCore};
+
+use super::{
+    message_to_prehash, ByteReader, ByteWriter, Deserializable, DeserializationError, EcdsaError,
+    Serializable, Signature, Word, PK_LEN, PREHASH_LEN, SK_LEN,
+};
+
+// PUBLIC KEY
+// ================================================================================================
+
+/// A secp256k1 ECDSA public key.
+///
+/// The public key is serialized as a 33-byte SEC1 compressed point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(VerifyingKey);
+
+impl PublicKey {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [PublicKey] decoded from the provided SEC1 encoding of a curve point. Both
+    /// compressed and uncompressed encodings are accepted.
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes are not a valid encoding of a curve point.
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Result<Self, EcdsaError> {
+        VerifyingKey::from_sec1_bytes(bytes)
+            .map(Self)
+            .map_err(|_| EcdsaError::PubKeyDecodingFailed)
+    }
+
+    /// Recovers the public key which produced the provided signature over the specified message.
+    ///
+    /// # Errors
+    /// Returns an error if no public key can be recovered from the signature.
+    pub fn recover(message: Word, signature: &Signature) -> Result<Self, EcdsaError> {
+        Self::recover_from_prehash(&message_to_prehash(message), signature)
+    }
+
+    /// Recovers the public key which produced the provided signature over the specified message
+    /// prehash (e.g., the Keccak256 digest of an Ethereum transaction).
+    ///
+    /// # Errors
+    /// Returns an error if no public key can be recovered from the signature.
+    pub fn recover_from_prehash(
+        prehash: &[u8; PREHASH_LEN],
+        signature: &Signature,
+    ) -> Result<Self, EcdsaError> {
+        VerifyingKey::recover_from_prehash(prehash, signature.inner(), signature.recovery_id())
+            .map(Self)
+            .map_err(|_| EcdsaError::PubKeyRecoveryFailed)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the SEC1 compressed encoding of this public key.
+    pub fn to_sec1_bytes(&self) -> [u8; PK_LEN] {
+        self.0
+            .to_encoded_point(true)
+            .as_bytes()
+            .try_into()
+            .expect("invalid compressed point length")
+    }
+
+    /// Returns the SEC1 uncompressed encoding of this public key, i.e., 0x04 followed by the
+    /// x and y coordinates of the point.
+    ///
+    /// An Ethereum address is the last 20 bytes of the Keccak256 digest of this encoding without
+    /// the leading 0x04 byte.
+    pub fn to_uncompressed_sec1_bytes(&self) -> [u8; 2 * SK_LEN + 1] {
+        self.0
+            .to_encoded_point(false)
+            .as_bytes()
+            .try_into()
+            .expect("invalid uncompressed point length")
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the provided signature is a valid signature of the specified message
+    /// against this public key.
+    pub fn verify(&self, message: Word, signature: &Signature) -> bool {
+        self.verify_prehash(&message_to_prehash(message), signature)
+    }
+
+    /// Returns true if the provided signature is a valid signature of the specified message
+    /// prehash against this public key.
+    ///
+    /// Only signatures with a normalized (i.e., low) s value are accepted.
+    pub fn verify_prehash(&self, prehash: &[u8; PREHASH_LEN], signature: &Signature) -> bool {
+        self.0.verify_prehash(prehash, signature.inner()).is_ok()
+    }
+}
+
+// SECRET KEY
+// ================================================================================================
+
+/// A secp256k1 ECDSA secret key.
+///
+/// Signatures are generated deterministically as specified in RFC 6979, and always have a
+/// normalized (i.e., low) s value. The secret key is wiped from memory when it is dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretKey(SigningKey);
+
+impl SecretKey {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Generates a secret key from OS-provided randomness.
+    #[cfg(feature = "std")]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self::with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a secret key using the provided random number generator.
+    pub fn with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self(SigningKey::random(rng))
+    }
+
+    /// Returns a new [SecretKey] decoded from the provided big-endian encoding of a scalar.
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes do not encode a non-zero scalar smaller than the
+    /// order of the curve.
+    pub fn from_bytes(bytes: &[u8; SK_LEN]) -> Result<Self, EcdsaError> {
+        SigningKey::from_slice(bytes)
+            .map(Self)
+            .map_err(|_| EcdsaError::SecKeyDecodingFailed)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public key corresponding to this secret key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(*self.0.verifying_key())
+    }
+
+    /// Returns the big-endian encoding of this secret key.
+    pub fn to_bytes(&self) -> [u8; SK_LEN] {
+        self.0.to_bytes().into()
+    }
+
+    // SIGNATURE GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Signs the specified message with this secret key.
+    pub fn sign(&self, message: Word) -> Signature {
+        self.sign_prehash(&message_to_prehash(message))
+    }
+
+    /// Signs the specified message prehash (e.g., the Keccak256 digest of an Ethereum
+    /// transaction) with this secret key.
+    pub fn sign_prehash(&self, prehash: &[u8; PREHASH_LEN]) -> Signature {
+        // signing can fail only if the prehash is shorter than 16 bytes, or with negligible
+        // probability for a specific secret key and prehash; thus, expect() is OK here.
+        let (signature, recovery_id) =
+            self.0.sign_prehash_recoverable(prehash).expect("failed to generate signature");
+        Signature::new(signature, recovery_id)
+    }
+}
+
+impl core::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SecretKey")
+            .field("public_key", &self.public_key())
+            .finish_non_exhaustive()
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for PublicKey {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_sec1_bytes());
+    }
+}
+
+impl Deserializable for PublicKey {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes: [u8; PK_LEN] = source.read_array()?;
+        Self::from_sec1_bytes(&bytes)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+impl Serializable for SecretKey {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_bytes());
+    }
+}
+
+impl Deserializable for SecretKey {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes: [u8; SK_LEN] = source.read_array()?;
+        Self::from_bytes(&bytes).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use rand_utils::rand_array;
+
+    use super::{Deserializable, PublicKey, SecretKey, Serializable, Word};
+    use crate::Felt;
+
+    #[test]
+    fn test_ecdsa_verification() {
+        let mut rng = ChaCha20Rng::from_seed([3_u8; 32]);
+        let sk = SecretKey::with_rng(&mut rng);
+        let pk = sk.public_key();
+
+        let message: Word = rand_array::<Felt, 4>();
+        let signature = sk.sign(message);
+        assert!(pk.verify(message, &signature));
+
+        // signing is deterministic
+        assert_eq!(sk.sign(message), signature);
+
+        // the signature should not verify against a different message or public key
+        let mut other_message = message;
+        other_message[0] += Felt::new(1);
+        assert!(!pk.verify(other_message, &signature));
+        assert!(!SecretKey::with_rng(&mut rng).public_key().verify(message, &signature));
+    }
+
+    #[test]
+    fn test_ecdsa_recovery() {
+        let mut rng = ChaCha20Rng::from_seed([4_u8; 32]);
+        let sk = SecretKey::with_rng(&mut rng);
+
+        let prehash: [u8; 32] = rand_array();
+        let signature = sk.sign_prehash(&prehash);
+        assert_eq!(PublicKey::recover_from_prehash(&prehash, &signature), Ok(sk.public_key()));
+
+        let message: Word = rand_array::<Felt, 4>();
+        let signature = sk.sign(message);
+        assert_eq!(PublicKey::recover(message, &signature), Ok(sk.public_key()));
+    }
+
+    #[test]
+    fn test_ecdsa_key_serialization() {
+        let mut rng = ChaCha20Rng::from_seed([5_u8; 32]);
+        let sk = SecretKey::with_rng(&mut rng);
+        let pk = sk.public_key();
+
+        let restored = SecretKey::read_from_bytes(&sk.to_bytes()).unwrap();
+        assert_eq!(restored, sk);
+
+        let serialized = pk.to_bytes();
+        assert_eq!(serialized.len(), 33);
+        assert_eq!(PublicKey::read_from_bytes(&serialized).unwrap(), pk);
+
+        let uncompressed = pk.to_uncompressed_sec1_bytes();
+        assert_eq!(PublicKey::from_sec1_bytes(&uncompressed).unwrap(), pk);
+
+        // zero is not a valid secret key
+        assert!(SecretKey::read_from_bytes(&[0_u8; 32]).is_err());
+    }
+}
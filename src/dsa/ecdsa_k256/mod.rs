@@ -0,0 +1,67 @@
+use core::convert::Infallible;
+
+use crate::{
+    dsa::SignatureScheme,
+    hash::rpo::RpoDigest,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    Word,
+};
+
+mod error;
+mod keys;
+mod signature;
+
+pub use error::EcdsaError;
+pub use keys::{PublicKey, SecretKey};
+pub use signature::Signature;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Secret key length as a u8 vector.
+pub const SK_LEN: usize = 32;
+
+/// Public key length as a u8 vector (SEC1 compressed encoding).
+pub const PK_LEN: usize = 33;
+
+/// Signature length as a u8 vector, i.e., 32 bytes for r, 32 bytes for s and 1 byte for the
+/// recovery id.
+pub const SIG_LEN: usize = 65;
+
+/// Length of the message prehash signed by ECDSA.
+pub const PREHASH_LEN: usize = 32;
+
+// SIGNATURE SCHEME
+// ================================================================================================
+
+/// The ECDSA signature scheme over the secp256k1 curve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EcdsaK256;
+
+impl SignatureScheme for EcdsaK256 {
+    type SecretKey = SecretKey;
+    type PublicKey = PublicKey;
+    type Signature = Signature;
+    type Error = Infallible;
+
+    fn public_key(secret_key: &SecretKey) -> PublicKey {
+        secret_key.public_key()
+    }
+
+    fn sign(secret_key: &SecretKey, message: Word) -> Result<Signature, Infallible> {
+        Ok(secret_key.sign(message))
+    }
+
+    fn verify(public_key: &PublicKey, message: Word, signature: &Signature) -> bool {
+        public_key.verify(message, signature)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts a message represented as a [Word] into a 32-byte prehash by encoding each of its
+/// field elements as 8 little-endian bytes.
+fn message_to_prehash(message: Word) -> [u8; PREHASH_LEN] {
+    RpoDigest::from(message).as_bytes()
+}
@@ -0,0 +1,138 @@
+use alloc::string::ToString;
+
+use k256::ecdsa::{self, RecoveryId};
+
+use super::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, EcdsaError, Serializable, SIG_LEN,
+};
+
+// ECDSA SIGNATURE
+// ================================================================================================
+
+/// A secp256k1 ECDSA signature together with its recovery id.
+///
+/// The signature is serialized as:
+/// 1. 32 bytes for the big-endian encoding of r.
+/// 2. 32 bytes for the big-endian encoding of s.
+/// 3. 1 byte for the recovery id, which is in the range [0, 3].
+///
+/// Note that Ethereum transactions encode the recovery id as v := 27 + recovery id (or according
+/// to EIP-155); the conversion is left to the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature {
+    sig: ecdsa::Signature,
+    recovery_id: RecoveryId,
+}
+
+impl Signature {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [Signature] instantiated from the provided signature and recovery id.
+    pub(super) fn new(sig: ecdsa::Signature, recovery_id: RecoveryId) -> Self {
+        Self { sig, recovery_id }
+    }
+
+    /// Returns a new [Signature] decoded from the provided bytes in the format described above.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The length of the provided bytes is not [SIG_LEN].
+    /// - r or s is not a valid non-zero scalar.
+    /// - The recovery id is greater than 3.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EcdsaError> {
+        if bytes.len() != SIG_LEN {
+            return Err(EcdsaError::SigDecodingInvalidLength(bytes.len()));
+        }
+
+        let sig = ecdsa::Signature::from_slice(&bytes[..SIG_LEN - 1])
+            .map_err(|_| EcdsaError::SigDecodingFailed)?;
+        let recovery_id = RecoveryId::from_byte(bytes[SIG_LEN - 1])
+            .ok_or(EcdsaError::SigDecodingInvalidRecoveryId(bytes[SIG_LEN - 1]))?;
+
+        Ok(Self { sig, recovery_id })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the encoding of this signature in the format described above.
+    pub fn to_bytes(&self) -> [u8; SIG_LEN] {
+        let mut bytes = [0_u8; SIG_LEN];
+        bytes[..SIG_LEN - 1].copy_from_slice(&self.sig.to_bytes());
+        bytes[SIG_LEN - 1] = self.recovery_id.to_byte();
+        bytes
+    }
+
+    /// Returns the recovery id of this signature.
+    pub fn recovery_id(&self) -> RecoveryId {
+        self.recovery_id
+    }
+
+    /// Returns the underlying (r, s) signature.
+    pub(super) fn inner(&self) -> &ecdsa::Signature {
+        &self.sig
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for Signature {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_bytes());
+    }
+
+    fn get_size_hint(&self) -> usize {
+        SIG_LEN
+    }
+}
+
+impl Deserializable for Signature {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes: [u8; SIG_LEN] = source.read_array()?;
+        Self::from_bytes(&bytes).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+    use super::{
+        super::{EcdsaError, SecretKey},
+        Deserializable, Signature,
+    };
+    use crate::Word;
+
+    #[test]
+    fn test_signature_serialization() {
+        let mut rng = ChaCha20Rng::from_seed([6_u8; 32]);
+        let sk = SecretKey::with_rng(&mut rng);
+        let message = Word::default();
+        let signature = sk.sign(message);
+
+        let serialized = signature.to_bytes();
+        assert_eq!(serialized.len(), 65);
+        let deserialized = Signature::read_from_bytes(&serialized).unwrap();
+        assert_eq!(deserialized, signature);
+        assert!(sk.public_key().verify(message, &deserialized));
+
+        // an invalid recovery id should be rejected
+        let mut serialized = serialized;
+        serialized[64] = 4;
+        assert_eq!(
+            Signature::from_bytes(&serialized),
+            Err(EcdsaError::SigDecodingInvalidRecoveryId(4))
+        );
+
+        // a signature with r = 0 should be rejected
+        serialized[..32].fill(0);
+        serialized[64] = 0;
+        assert_eq!(Signature::from_bytes(&serialized), Err(EcdsaError::SigDecodingFailed));
+        assert!(Signature::from_bytes(&serialized[1..]).is_err());
+    }
+}
@@ -0,0 +1,23 @@
+use core::fmt;
+
+// ED25519 ERROR
+// ================================================================================================
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Ed25519Error {
+    PubKeyDecodingFailed,
+}
+
+impl fmt::Display for Ed25519Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Ed25519Error::*;
+        match self {
+            PubKeyDecodingFailed => {
+                write!(f, "Failed to decode public key: not a valid compressed Edwards point")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Ed25519Error {}
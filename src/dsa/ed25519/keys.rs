@@ -0,0 +1,230 @@
+use alloc::string::ToString;
+
+use ed25519_dalek::{Signer, SigningKey, VerifyingKey};
+use rand::{CryptoRng, RngCore};
+
+use super::{
+    message_to_bytes, ByteReader, ByteWriter, Deserializable, DeserializationError, Ed25519Error,
+    Serializable, Signature, Word, PK_LEN, SK_LEN,
+};
+
+// PUBLIC KEY
+// ================================================================================================
+
+/// An Ed25519 public key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey(VerifyingKey);
+
+impl PublicKey {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [PublicKey] decoded from the provided compressed Edwards point.
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes are not a valid encoding of a curve point.
+    pub fn from_bytes(bytes: &[u8; PK_LEN]) -> Result<Self, Ed25519Error> {
+        VerifyingKey::from_bytes(bytes)
+            .map(Self)
+            .map_err(|_| Ed25519Error::PubKeyDecodingFailed)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the compressed Edwards point encoding of this public key.
+    pub fn to_bytes(&self) -> [u8; PK_LEN] {
+        self.0.to_bytes()
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the provided signature is a valid signature of the specified message
+    /// against this public key.
+    pub fn verify(&self, message: Word, signature: &Signature) -> bool {
+        self.verify_bytes(&message_to_bytes(message), signature)
+    }
+
+    /// Returns true if the provided signature is a valid signature of the specified arbitrary
+    /// message against this public key.
+    ///
+    /// Verification is strict: signatures with a non-canonical scalar s and signatures under
+    /// public keys of small order are rejected, and thus signatures are not malleable.
+    pub fn verify_bytes(&self, message: &[u8], signature: &Signature) -> bool {
+        self.0.verify_strict(message, &signature.0).is_ok()
+    }
+}
+
+// SECRET KEY
+// ================================================================================================
+
+/// An Ed25519 secret key.
+///
+/// Signatures are generated deterministically as specified in RFC 8032. The secret key is wiped
+/// from memory when it is dropped. The [Debug](core::fmt::Debug) output of the secret key only
+/// includes the corresponding public key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SecretKey(SigningKey);
+
+impl SecretKey {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Generates a secret key from OS-provided randomness.
+    #[cfg(feature = "std")]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self::with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a secret key using the provided random number generator.
+    pub fn with_rng<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        Self(SigningKey::generate(rng))
+    }
+
+    /// Returns a new [SecretKey] instantiated from the provided 32-byte seed as specified in
+    /// RFC 8032.
+    pub fn from_bytes(bytes: &[u8; SK_LEN]) -> Self {
+        Self(SigningKey::from_bytes(bytes))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public key corresponding to this secret key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey(self.0.verifying_key())
+    }
+
+    /// Returns the 32-byte seed of this secret key.
+    pub fn to_bytes(&self) -> [u8; SK_LEN] {
+        self.0.to_bytes()
+    }
+
+    // SIGNATURE GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Signs the specified message with this secret key.
+    pub fn sign(&self, message: Word) -> Signature {
+        self.sign_bytes(&message_to_bytes(message))
+    }
+
+    /// Signs the specified arbitrary message with this secret key.
+    pub fn sign_bytes(&self, message: &[u8]) -> Signature {
+        Signature(self.0.sign(message))
+    }
+}
+
+impl core::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SecretKey")
+            .field("public_key", &self.public_key())
+            .finish_non_exhaustive()
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for PublicKey {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_bytes());
+    }
+}
+
+impl Deserializable for PublicKey {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes: [u8; PK_LEN] = source.read_array()?;
+        Self::from_bytes(&bytes).map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+impl Serializable for SecretKey {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_bytes());
+    }
+}
+
+impl Deserializable for SecretKey {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes: [u8; SK_LEN] = source.read_array()?;
+        Ok(Self::from_bytes(&bytes))
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use rand_utils::rand_array;
+
+    use super::{super::Signature, Deserializable, PublicKey, SecretKey, Serializable, Word};
+    use crate::{utils::hex_to_bytes, Felt};
+
+    #[test]
+    fn test_ed25519_verification() {
+        let mut rng = ChaCha20Rng::from_seed([7_u8; 32]);
+        let sk = SecretKey::with_rng(&mut rng);
+        let pk = sk.public_key();
+
+        let message: Word = rand_array::<Felt, 4>();
+        let signature = sk.sign(message);
+        assert!(pk.verify(message, &signature));
+
+        // the signature should not verify against a different message or public key
+        let mut other_message = message;
+        other_message[3] += Felt::new(1);
+        assert!(!pk.verify(other_message, &signature));
+        assert!(!SecretKey::with_rng(&mut rng).public_key().verify(message, &signature));
+    }
+
+    #[test]
+    fn test_ed25519_rfc8032_vector() {
+        // test 1 from RFC 8032, section 7.1
+        let sk: [u8; 32] =
+            hex_to_bytes("0x9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+                .unwrap();
+        let pk: [u8; 32] =
+            hex_to_bytes("0xd75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+                .unwrap();
+        let sig: [u8; 64] = hex_to_bytes(
+            "0xe5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bac\
+             c61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+        )
+        .unwrap();
+
+        let sk = SecretKey::from_bytes(&sk);
+        assert_eq!(sk.public_key(), PublicKey::from_bytes(&pk).unwrap());
+        assert_eq!(sk.sign_bytes(&[]), Signature::from_bytes(&sig));
+        assert!(sk.public_key().verify_bytes(&[], &Signature::from_bytes(&sig)));
+    }
+
+    #[test]
+    fn test_ed25519_secret_key_debug() {
+        let sk = SecretKey::from_bytes(&[0xab_u8; 32]);
+        // the seed must not be included in the debug output
+        let expected = format!("SecretKey {{ public_key: {:?}, .. }}", sk.public_key());
+        assert_eq!(format!("{sk:?}"), expected);
+    }
+
+    #[test]
+    fn test_ed25519_serialization() {
+        let mut rng = ChaCha20Rng::from_seed([8_u8; 32]);
+        let sk = SecretKey::with_rng(&mut rng);
+        let pk = sk.public_key();
+        let signature = sk.sign(Word::default());
+
+        let serialized = Serializable::to_bytes(&sk);
+        assert_eq!(SecretKey::read_from_bytes(&serialized).unwrap(), sk);
+        let serialized = Serializable::to_bytes(&pk);
+        assert_eq!(PublicKey::read_from_bytes(&serialized).unwrap(), pk);
+
+        let serialized = Serializable::to_bytes(&signature);
+        let deserialized = Signature::read_from_bytes(&serialized).unwrap();
+        assert_eq!(deserialized, signature);
+        assert!(pk.verify(Word::default(), &deserialized));
+    }
+}
@@ -0,0 +1,63 @@
+use core::convert::Infallible;
+
+use crate::{
+    dsa::SignatureScheme,
+    hash::rpo::RpoDigest,
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    Word,
+};
+
+mod error;
+mod keys;
+mod signature;
+
+pub use error::Ed25519Error;
+pub use keys::{PublicKey, SecretKey};
+pub use signature::Signature;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Secret key length as a u8 vector.
+pub const SK_LEN: usize = 32;
+
+/// Public key length as a u8 vector.
+pub const PK_LEN: usize = 32;
+
+/// Signature length as a u8 vector.
+pub const SIG_LEN: usize = 64;
+
+// SIGNATURE SCHEME
+// ================================================================================================
+
+/// The Ed25519 signature scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ed25519;
+
+impl SignatureScheme for Ed25519 {
+    type SecretKey = SecretKey;
+    type PublicKey = PublicKey;
+    type Signature = Signature;
+    type Error = Infallible;
+
+    fn public_key(secret_key: &SecretKey) -> PublicKey {
+        secret_key.public_key()
+    }
+
+    fn sign(secret_key: &SecretKey, message: Word) -> Result<Signature, Infallible> {
+        Ok(secret_key.sign(message))
+    }
+
+    fn verify(public_key: &PublicKey, message: Word, signature: &Signature) -> bool {
+        public_key.verify(message, signature)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts a message represented as a [Word] into bytes by encoding each of its field elements
+/// as 8 little-endian bytes.
+fn message_to_bytes(message: Word) -> [u8; 32] {
+    RpoDigest::from(message).as_bytes()
+}
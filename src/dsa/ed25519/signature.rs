@@ -0,0 +1,52 @@
+use ed25519_dalek as dalek;
+
+use super::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable, SIG_LEN};
+
+// ED25519 SIGNATURE
+// ================================================================================================
+
+/// An Ed25519 signature.
+///
+/// The signature is serialized as specified in RFC 8032, i.e., 32 bytes for the compressed point
+/// R followed by 32 bytes for the little-endian encoding of the scalar s. The validity of the
+/// encoding is checked only when the signature is verified.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signature(pub(super) dalek::Signature);
+
+impl Signature {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [Signature] instantiated from the provided bytes.
+    pub fn from_bytes(bytes: &[u8; SIG_LEN]) -> Self {
+        Self(dalek::Signature::from_bytes(bytes))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the encoding of this signature.
+    pub fn to_bytes(&self) -> [u8; SIG_LEN] {
+        self.0.to_bytes()
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for Signature {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_bytes());
+    }
+
+    fn get_size_hint(&self) -> usize {
+        SIG_LEN
+    }
+}
+
+impl Deserializable for Signature {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let bytes: [u8; SIG_LEN] = source.read_array()?;
+        Ok(Self::from_bytes(&bytes))
+    }
+}
@@ -1,3 +1,100 @@
 //! Digital signature schemes supported by default in the Miden VM.
+//!
+//! Note that this module intentionally does not provide a Schnorr signature scheme over the
+//! base field: the multiplicative group of the field has order p - 1 = 2^64 - 2^32, which factors
+//! into small primes, and thus discrete logarithms in it can be computed efficiently via the
+//! Pohlig-Hellman algorithm. A secure Schnorr-like scheme with cheap in-VM verification would
+//! require a prime-order group defined over an extension of the base field (e.g., the ecGFp5
+//! curve over GF(p^5)).
 
+use crate::Word;
+
+pub mod bls12_381;
+pub mod ecdsa_k256;
+pub mod ed25519;
 pub mod rpo_falcon512;
+pub mod rpo_falcon512_det;
+pub mod xmss;
+
+// SIGNATURE SCHEME
+// ================================================================================================
+
+/// A digital signature scheme over messages represented as [Word]s.
+///
+/// This allows code to be generic over the signature scheme instead of depending on the concrete
+/// key and signature types of each scheme.
+pub trait SignatureScheme {
+    /// Secret key used for generating signatures.
+    type SecretKey;
+
+    /// Public key used for verifying signatures.
+    type PublicKey;
+
+    /// Signature over a message.
+    type Signature;
+
+    /// Error which can occur during signature generation.
+    type Error;
+
+    /// Returns the public key corresponding to the provided secret key.
+    fn public_key(secret_key: &Self::SecretKey) -> Self::PublicKey;
+
+    /// Signs the provided message with the provided secret key.
+    fn sign(secret_key: &Self::SecretKey, message: Word) -> Result<Self::Signature, Self::Error>;
+
+    /// Returns true if the provided signature is a valid signature of the provided message
+    /// against the provided public key.
+    fn verify(public_key: &Self::PublicKey, message: Word, signature: &Self::Signature) -> bool;
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use core::fmt::Debug;
+
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use rand_utils::rand_array;
+
+    use super::{
+        bls12_381, ecdsa_k256, ed25519, rpo_falcon512, rpo_falcon512_det, SignatureScheme,
+    };
+    use crate::{Felt, Word};
+
+    fn sign_and_verify<S: SignatureScheme>(secret_key: &S::SecretKey, other_key: &S::SecretKey)
+    where
+        S::Error: Debug,
+    {
+        let message: Word = rand_array::<Felt, 4>();
+        let signature = S::sign(secret_key, message).unwrap();
+        assert!(S::verify(&S::public_key(secret_key), message, &signature));
+        assert!(!S::verify(&S::public_key(other_key), message, &signature));
+    }
+
+    #[test]
+    fn test_signature_schemes() {
+        let mut rng = ChaCha20Rng::from_seed([0_u8; 32]);
+
+        sign_and_verify::<rpo_falcon512::RpoFalcon512>(
+            &rpo_falcon512::KeyPair::new().unwrap(),
+            &rpo_falcon512::KeyPair::new().unwrap(),
+        );
+        sign_and_verify::<rpo_falcon512_det::RpoFalcon512Det>(
+            &rpo_falcon512_det::KeyPair::new().unwrap(),
+            &rpo_falcon512_det::KeyPair::new().unwrap(),
+        );
+        sign_and_verify::<ecdsa_k256::EcdsaK256>(
+            &ecdsa_k256::SecretKey::with_rng(&mut rng),
+            &ecdsa_k256::SecretKey::with_rng(&mut rng),
+        );
+        sign_and_verify::<ed25519::Ed25519>(
+            &ed25519::SecretKey::with_rng(&mut rng),
+            &ed25519::SecretKey::with_rng(&mut rng),
+        );
+        sign_and_verify::<bls12_381::Bls12381>(
+            &bls12_381::SecretKey::with_rng(&mut rng),
+            &bls12_381::SecretKey::with_rng(&mut rng),
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
+
+use super::{
+    ct_eq, ByteReader, ByteWriter, Deserializable, DeserializationError, PublicKey, PublicKeyBytes,
+    RpoDigest, Serializable, Signature, SignatureWithoutPk, VerificationError, Word,
+};
+
+// AGGREGATE SIGNATURE
+// ================================================================================================
+
+/// A list of RPO Falcon512 signatures, each over a message and against a public key commitment,
+/// which can be stored and verified together.
+///
+/// This is not a cryptographic aggregation: the size of an [AggregateSignature] grows linearly
+/// with the number of signatures. However, the expanded public keys embedded in the signatures
+/// are stored only once per distinct public key, which cuts the size of each additional
+/// signature under an already present key from 1563 bytes down to 698 bytes (i.e., 666 bytes for
+/// the signature and 32 bytes for the message). Nonces are unique per signature and thus are not
+/// deduplicated.
+///
+/// The aggregate signature is serialized as:
+/// 1. 8 bytes for the number of distinct public keys, followed by each expanded public key (897
+///    bytes). The commitments to the public keys are recomputed when deserializing.
+/// 2. 8 bytes for the number of signatures, followed by each signature encoded as the index of
+///    its public key (4 bytes), the message (32 bytes) and the signature without the expanded
+///    public key as described in [SignatureWithoutPk].
+#[derive(Debug, Clone, Default)]
+pub struct AggregateSignature {
+    /// The distinct public keys, in the order in which they were added.
+    keys: Vec<PublicKey>,
+    /// The indexes of the public keys in `keys`, keyed by their expanded public keys.
+    key_indexes: BTreeMap<PublicKeyBytes, u32>,
+    entries: Vec<(u32, Word, SignatureWithoutPk)>,
+}
+
+impl AggregateSignature {
+    /// Returns a new empty [AggregateSignature].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new [AggregateSignature] containing the provided `(pubkey_com, message,
+    /// signature)` tuples.
+    ///
+    /// # Errors
+    /// Returns an error if the expanded public key embedded in any of the signatures cannot be
+    /// decoded or does not match the provided public key commitment.
+    pub fn from_signatures<I>(signatures: I) -> Result<Self, VerificationError>
+    where
+        I: IntoIterator<Item = (Word, Word, Signature)>,
+    {
+        let mut aggregate = Self::new();
+        for (pubkey_com, message, signature) in signatures {
+            aggregate.push(pubkey_com, message, signature)?;
+        }
+        Ok(aggregate)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of signatures in this aggregate signature.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if this aggregate signature contains no signatures.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of distinct public keys in this aggregate signature.
+    pub fn num_keys(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns an iterator over the `(pubkey_com, message, signature)` tuples in this aggregate
+    /// signature, in the order in which they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (Word, Word, &SignatureWithoutPk)> {
+        self.entries.iter().map(|(key_idx, message, sig)| {
+            (self.keys[*key_idx as usize].to_commitment(), *message, sig)
+        })
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the provided signature of the specified message against the specified public key
+    /// commitment to this aggregate signature.
+    ///
+    /// The expanded public key embedded in the signature is checked against the commitment when
+    /// it is added, and thus each distinct public key is decoded and hashed only once.
+    ///
+    /// # Errors
+    /// Returns an error if the expanded public key embedded in the signature cannot be decoded or
+    /// does not match the provided public key commitment.
+    ///
+    /// # Panics
+    /// Panics if the aggregate signature already contains 2^32 distinct public keys.
+    pub fn push(
+        &mut self,
+        pubkey_com: Word,
+        message: Word,
+        signature: Signature,
+    ) -> Result<(), VerificationError> {
+        let key_idx = match self.key_indexes.get(&signature.pk) {
+            Some(&key_idx) => {
+                if !ct_eq(&self.keys[key_idx as usize].to_commitment(), &pubkey_com) {
+                    return Err(VerificationError::PubKeyCommitmentMismatch);
+                }
+                key_idx
+            }
+            None => {
+                let pub_key =
+                    PublicKey::new(signature.pk).map_err(VerificationError::MalformedEncoding)?;
+                if !ct_eq(&pub_key.to_commitment(), &pubkey_com) {
+                    return Err(VerificationError::PubKeyCommitmentMismatch);
+                }
+                self.insert_key(pub_key)
+            }
+        };
+        self.entries.push((key_idx, message, signature.sig));
+        Ok(())
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if all signatures in this aggregate signature are valid.
+    ///
+    /// This is equivalent to calling [Signature::verify()] for every `(pubkey_com, message,
+    /// signature)` tuple. The public keys were already checked against their commitments when
+    /// they were added.
+    pub fn verify_all(&self) -> bool {
+        self.entries.iter().all(|(key_idx, message, sig)| {
+            sig.verify_with_key(*message, &self.keys[*key_idx as usize])
+        })
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Adds the provided public key, which must not be present yet, and returns its index.
+    fn insert_key(&mut self, pub_key: PublicKey) -> u32 {
+        let key_idx = u32::try_from(self.keys.len()).expect("too many public keys");
+        self.key_indexes.insert(pub_key.expanded_public_key(), key_idx);
+        self.keys.push(pub_key);
+        key_idx
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for AggregateSignature {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.keys.len() as u64);
+        for pub_key in self.keys.iter() {
+            target.write_bytes(&pub_key.expanded_public_key());
+        }
+
+        target.write_u64(self.entries.len() as u64);
+        for (key_idx, message, sig) in self.entries.iter() {
+            target.write_u32(*key_idx);
+            RpoDigest::from(*message).write_into(target);
+            sig.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for AggregateSignature {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let mut aggregate = Self::new();
+
+        let num_keys = source.read_u64()?;
+        for _ in 0..num_keys {
+            let pk: PublicKeyBytes = source.read_array()?;
+            if aggregate.key_indexes.contains_key(&pk) {
+                return Err(DeserializationError::InvalidValue("duplicate public key".to_string()));
+            }
+            let pub_key = PublicKey::new(pk)?;
+            aggregate.insert_key(pub_key);
+        }
+
+        let num_entries = source.read_u64()?;
+        for _ in 0..num_entries {
+            let key_idx = source.read_u32()?;
+            if key_idx as usize >= aggregate.keys.len() {
+                return Err(DeserializationError::InvalidValue(
+                    "public key index out of bounds".to_string(),
+                ));
+            }
+            let message = RpoDigest::read_from(source)?.into();
+            let sig = SignatureWithoutPk::read_from(source)?;
+            aggregate.entries.push((key_idx, message, sig));
+        }
+
+        Ok(aggregate)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use rand_utils::rand_vector;
+
+    use super::{
+        super::{Felt, KeyPair},
+        AggregateSignature, Deserializable, Serializable, Vec, VerificationError, Word,
+    };
+
+    #[test]
+    fn test_aggregate_signature() {
+        let keys = KeyPair::new_batch(2).unwrap();
+        let tuples: Vec<(Word, Word, _)> = (0..6)
+            .map(|i| {
+                let key = &keys[i % 2];
+                let message: Word = rand_vector::<Felt>(4).try_into().unwrap();
+                (key.commitment(), message, key.sign(message).unwrap())
+            })
+            .collect();
+
+        let aggregate = AggregateSignature::from_signatures(tuples.iter().cloned()).unwrap();
+        assert_eq!(aggregate.len(), 6);
+        assert_eq!(aggregate.num_keys(), 2);
+        assert!(aggregate.verify_all());
+        assert!(AggregateSignature::new().verify_all());
+
+        // the public keys should be stored only once
+        let serialized = aggregate.to_bytes();
+        let individual: usize = tuples.iter().map(|(_, _, sig)| sig.to_bytes().len() + 64).sum();
+        assert!(serialized.len() < individual);
+
+        let deserialized = AggregateSignature::read_from_bytes(&serialized).unwrap();
+        assert_eq!(deserialized.to_bytes(), serialized);
+        assert!(deserialized.verify_all());
+
+        // a signature embedding a public key which does not match the commitment is rejected
+        let mut aggregate = aggregate;
+        let (_, message, signature) = tuples[0].clone();
+        assert_eq!(
+            aggregate.push(keys[1].commitment(), message, signature),
+            Err(VerificationError::PubKeyCommitmentMismatch)
+        );
+        assert_eq!(aggregate.len(), 6);
+
+        // a single invalid signature should invalidate the aggregate
+        let (pubkey_com, mut message, signature) = tuples[1].clone();
+        message[0] += Felt::new(1);
+        aggregate.push(pubkey_com, message, signature).unwrap();
+        assert!(!aggregate.verify_all());
+
+        // duplicate public keys are rejected when deserializing
+        let mut duplicated = Vec::new();
+        duplicated.extend_from_slice(&2_u64.to_le_bytes());
+        duplicated.extend_from_slice(&keys[0].public_key().expanded_public_key());
+        duplicated.extend_from_slice(&keys[0].public_key().expanded_public_key());
+        duplicated.extend_from_slice(&0_u64.to_le_bytes());
+        assert!(AggregateSignature::read_from_bytes(&duplicated).is_err());
+    }
+}
@@ -0,0 +1,151 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
+use super::{FalconError, Felt, SecretKeyBytes, Word, PK_LEN, SK_LEN};
+#[cfg(feature = "zeroize")]
+use crate::utils::zeroize_elements;
+use crate::{
+    cipher::{RpoAead, TAG_SIZE},
+    WORD_SIZE,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The base-2 logarithm of the cost parameter N of scrypt.
+///
+/// Together with [SCRYPT_R], this makes deriving the key from a password take 32 MiB of memory,
+/// which corresponds to the parameters recommended for interactive logins.
+const SCRYPT_LOG_N: u8 = 15;
+
+/// The block size parameter r of scrypt.
+const SCRYPT_R: u32 = 8;
+
+/// The parallelization parameter p of scrypt.
+const SCRYPT_P: u32 = 1;
+
+/// Length of the salt used by the key derivation function.
+pub const SALT_LEN: usize = 32;
+
+/// Length of the nonce used for encryption, i.e., of the canonical encodings of the elements of a
+/// [Word].
+pub const ENC_NONCE_LEN: usize = WORD_SIZE * 8;
+
+/// Length of the key derived from the password, from which the elements of the encryption key
+/// are read.
+const DERIVED_KEY_LEN: usize = WORD_SIZE * 8;
+
+/// Length of an encrypted secret key, including the authentication tag.
+///
+/// [RpoAead::seal_bytes()] packs the secret key into field elements 7 bytes at a time after
+/// appending at least one byte of padding, and every ciphertext element and tag element is encoded
+/// into 8 bytes.
+pub const CIPHERTEXT_LEN: usize = (SK_LEN / 7 + 1 + TAG_SIZE) * 8;
+
+/// Length of a password-encrypted key pair, i.e., salt, nonce, public key and encrypted secret
+/// key.
+pub const ENCRYPTED_KEY_PAIR_LEN: usize = SALT_LEN + ENC_NONCE_LEN + PK_LEN + CIPHERTEXT_LEN;
+
+// AUTHENTICATED ENCRYPTION
+// ================================================================================================
+
+/// Encrypts the provided secret key under a key derived from `password` and `salt`, and returns
+/// the ciphertext, which includes an authentication tag over the ciphertext and
+/// `associated_data`.
+///
+/// The encryption key is derived from the password and the salt via scrypt, and the secret key is
+/// encrypted with [RpoAead] under the provided nonce.
+///
+/// scrypt is computed by the [scrypt](https://docs.rs/scrypt) crate rather than by
+/// [RpoKdf](crate::hash::kdf::RpoKdf): RpoKdf is fast by design, and thus would let an adversary
+/// who obtained the encrypted key try many passwords cheaply, while scrypt makes every guess take
+/// 32 MiB of memory.
+pub fn encrypt(
+    secret_key: &SecretKeyBytes,
+    password: &[u8],
+    salt: &[u8; SALT_LEN],
+    nonce: Word,
+    associated_data: &[u8],
+) -> Vec<u8> {
+    derive_cipher(password, salt).seal_bytes(nonce, associated_data, secret_key)
+}
+
+/// Decrypts a secret key encrypted via [encrypt()].
+///
+/// # Errors
+/// Returns an error if the authentication tag does not match the ciphertext and the associated
+/// data (e.g., because the password is wrong), or if the ciphertext is malformed.
+pub fn decrypt(
+    ciphertext: &[u8],
+    password: &[u8],
+    salt: &[u8; SALT_LEN],
+    nonce: Word,
+    associated_data: &[u8],
+) -> Result<SecretKeyBytes, FalconError> {
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let mut plaintext = derive_cipher(password, salt)
+        .open_bytes(nonce, associated_data, ciphertext)
+        .map_err(|_| FalconError::SecKeyDecryptionFailed)?;
+
+    let secret_key = SecretKeyBytes::try_from(plaintext.as_slice())
+        .map_err(|_| FalconError::SecKeyDecryptionFailed);
+
+    #[cfg(feature = "zeroize")]
+    plaintext.zeroize();
+
+    secret_key
+}
+
+/// Returns the canonical encoding of the provided nonce.
+pub fn encode_nonce(nonce: Word) -> [u8; ENC_NONCE_LEN] {
+    let mut result = [0_u8; ENC_NONCE_LEN];
+    for (bytes, element) in result.chunks_exact_mut(8).zip(nonce) {
+        bytes.copy_from_slice(&element.as_int().to_le_bytes());
+    }
+    result
+}
+
+/// Returns the nonce decoded from the provided bytes.
+///
+/// # Errors
+/// Returns an error if the bytes are not the canonical encoding of a nonce.
+pub fn decode_nonce(bytes: &[u8; ENC_NONCE_LEN]) -> Result<Word, FalconError> {
+    let mut nonce = [Felt::new(0); WORD_SIZE];
+    for (element, bytes) in nonce.iter_mut().zip(bytes.chunks_exact(8)) {
+        let value = u64::from_le_bytes(bytes.try_into().expect("invalid chunk length"));
+        *element = Felt::try_from(value).map_err(|_| FalconError::SecKeyDecryptionFailed)?;
+    }
+    Ok(nonce)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the cipher keyed by the key derived from the provided password and salt.
+///
+/// The elements of the key are read from consecutive 8-byte chunks of the scrypt output, reduced
+/// modulo the field modulus, and thus deviate from the uniform distribution by about 2^-32. When
+/// the `zeroize` feature is enabled, the derived key material is wiped from memory; the internal
+/// buffers of scrypt are not.
+fn derive_cipher(password: &[u8], salt: &[u8; SALT_LEN]) -> RpoAead {
+    let params = scrypt::Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P, DERIVED_KEY_LEN)
+        .expect("invalid scrypt parameters");
+    let mut derived_key = [0_u8; DERIVED_KEY_LEN];
+    scrypt::scrypt(password, salt, &params, &mut derived_key).expect("invalid output length");
+
+    let mut key = [Felt::new(0); WORD_SIZE];
+    for (element, bytes) in key.iter_mut().zip(derived_key.chunks_exact(8)) {
+        *element = Felt::new(u64::from_le_bytes(bytes.try_into().expect("invalid chunk length")));
+    }
+    let cipher = RpoAead::new(key);
+
+    #[cfg(feature = "zeroize")]
+    {
+        derived_key.zeroize();
+        zeroize_elements(&mut key);
+    }
+
+    cipher
+}
@@ -1,20 +1,37 @@
+use alloc::string::ToString;
 use core::fmt;
 
-use super::{LOG_N, MODULUS, PK_LEN};
+use super::{DeserializationError, LOG_N, MODULUS, PK_LEN, SIG_L2_BOUND, SK_LEN};
+
+// FALCON ERROR
+// ================================================================================================
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum FalconError {
+    KeyGenerationCancelled,
     KeyGenerationFailed,
+    NonceDecodingInvalidElement(u64),
+    NonceGenerationExhausted,
     PubKeyDecodingExtraData,
     PubKeyDecodingInvalidCoefficient(u32),
     PubKeyDecodingInvalidLength(usize),
     PubKeyDecodingInvalidTag(u8),
-    SigDecodingTooBigHighBits(u32),
+    SecKeyDecodingFailed,
+    SecKeyDecodingInvalidLength(usize),
+    SecKeyDecryptionFailed,
+    SigDecodingTooBigHighBits { index: usize, high_bits: u32 },
     SigDecodingInvalidRemainder,
+    SigDecodingTruncated { index: usize },
     SigDecodingNonZeroUnusedBitsLastByte,
-    SigDecodingMinusZero,
+    SigDecodingNonZeroPadding,
+    SigDecodingMinusZero { index: usize },
     SigDecodingIncorrectEncodingAlgorithm,
     SigDecodingNotSupportedDegree(u8),
+    SigDecodingUnsupportedSaltVersion(u8),
+    SigDecodingCoefficientOutOfRange { index: usize },
+    SigEncodingCoefficientOutOfRange,
+    SigEncodingTooLong,
+    SigGenerationEmptyMessage,
     SigGenerationFailed,
 }
 
@@ -22,7 +39,14 @@ impl fmt::Display for FalconError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use FalconError::*;
         match self {
+            KeyGenerationCancelled => write!(f, "Key generation has been cancelled"),
             KeyGenerationFailed => write!(f, "Failed to generate a private-public key pair"),
+            NonceDecodingInvalidElement(val) => {
+                write!(f, "Failed to decode nonce: element {val} does not fit into 5 bytes")
+            }
+            NonceGenerationExhausted => {
+                write!(f, "Failed to generate a nonce: the nonce generator is exhausted")
+            }
             PubKeyDecodingExtraData => {
                 write!(f, "Failed to decode public key: input not fully consumed")
             }
@@ -35,18 +59,38 @@ impl fmt::Display for FalconError {
             PubKeyDecodingInvalidTag(byte) => {
                 write!(f, "Failed to decode public key: expected the first byte to be {LOG_N} but was {byte}")
             }
-            SigDecodingTooBigHighBits(m) => {
-                write!(f, "Failed to decode signature: high bits {m} exceed 2048")
+            SecKeyDecodingFailed => write!(f, "Failed to decode secret key"),
+            SecKeyDecodingInvalidLength(len) => {
+                write!(f, "Failed to decode secret key: expected {SK_LEN} bytes but received {len}")
+            }
+            SecKeyDecryptionFailed => {
+                write!(f, "Failed to decrypt secret key: wrong password or corrupted data")
+            }
+            SigDecodingTooBigHighBits { index, high_bits } => {
+                write!(f, "Failed to decode signature: high bits {high_bits} of coefficient {index} exceed 2048")
             }
             SigDecodingInvalidRemainder => {
                 write!(f, "Failed to decode signature: incorrect remaining data")
             }
+            SigDecodingTruncated { index } => {
+                write!(f, "Failed to decode signature: input ended while decoding coefficient {index}")
+            }
             SigDecodingNonZeroUnusedBitsLastByte => {
                 write!(f, "Failed to decode signature: Non-zero unused bits in the last byte")
             }
-            SigDecodingMinusZero => write!(f, "Failed to decode signature: -0 is forbidden"),
+            SigDecodingNonZeroPadding => {
+                write!(f, "Failed to decode signature: Non-zero padding after the encoded coefficients")
+            }
+            SigDecodingMinusZero { index } => write!(f, "Failed to decode signature: coefficient {index} is -0 which is forbidden"),
             SigDecodingIncorrectEncodingAlgorithm => write!(f, "Failed to decode signature: not supported encoding algorithm"),
             SigDecodingNotSupportedDegree(log_n) => write!(f, "Failed to decode signature: only supported irreducible polynomial degree is 512, 2^{log_n} was provided"),
+            SigDecodingUnsupportedSaltVersion(version) => write!(f, "Failed to decode signature: salt version {version} is not supported"),
+            SigDecodingCoefficientOutOfRange { index } => write!(f, "Failed to decode signature: coefficient {index} is -2048 which is forbidden"),
+            SigEncodingCoefficientOutOfRange => write!(f, "Failed to encode signature: coefficient is outside of [-2047, 2047]"),
+            SigEncodingTooLong => write!(f, "Failed to encode signature: encoded coefficients exceed the maximum signature length"),
+            SigGenerationEmptyMessage => {
+                write!(f, "Failed to generate a signature: the message must not be empty")
+            }
             SigGenerationFailed => write!(f, "Failed to generate a signature"),
         }
     }
@@ -54,3 +98,47 @@ impl fmt::Display for FalconError {
 
 #[cfg(feature = "std")]
 impl std::error::Error for FalconError {}
+
+impl From<FalconError> for DeserializationError {
+    /// Returns a [DeserializationError::InvalidValue] describing the provided error.
+    ///
+    /// [DeserializationError] can only carry a message, and thus callers which need to inspect
+    /// the structured error (e.g., the index of a malformed coefficient) should decode via
+    /// [Polynomial::from_signature()](super::Polynomial::from_signature()) or
+    /// [SignatureWithoutPk::from_falcon_bytes()](super::SignatureWithoutPk::from_falcon_bytes()).
+    fn from(err: FalconError) -> Self {
+        DeserializationError::InvalidValue(err.to_string())
+    }
+}
+
+// VERIFICATION ERROR
+// ================================================================================================
+
+/// Reasons for which a signature may fail verification.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VerificationError {
+    /// The expanded public key or the signature could not be decoded.
+    MalformedEncoding(FalconError),
+    /// The squared norm of the signature exceeds the allowed bound.
+    NormBoundExceeded(u64),
+    /// The expanded public key does not match the provided public key commitment.
+    PubKeyCommitmentMismatch,
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use VerificationError::*;
+        match self {
+            MalformedEncoding(err) => write!(f, "Failed to verify signature: {err}"),
+            NormBoundExceeded(sq_norm) => {
+                write!(f, "Failed to verify signature: squared norm {sq_norm} exceeds the bound {SIG_L2_BOUND}")
+            }
+            PubKeyCommitmentMismatch => {
+                write!(f, "Failed to verify signature: public key does not match the commitment")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VerificationError {}
@@ -3,7 +3,9 @@
  */
 
 #include <string.h>
+#ifdef RPO_FALCON_RANDOMBYTES
 #include "randombytes.h"
+#endif
 #include "falcon.h"
 #include "inner.h"
 #include "rpo.h"
@@ -37,11 +39,137 @@
  *      (signature length is 1+len(value), not counting the nonce)
  */
 
+/* ==== Start: Deviation from the reference implementation ===================================== */
+
+/*
+ * When RPO_FALCON_ZEROIZE is defined, secret intermediate values (key polynomials, sampler state
+ * and seeds) are wiped from the stack before returning. The writes go through a volatile pointer
+ * so that they are not optimized away.
+ */
+#ifdef RPO_FALCON_ZEROIZE
+static void
+wipe(void *ptr, size_t len)
+{
+    volatile uint8_t *p = (volatile uint8_t *)ptr;
+    while (len-- > 0)
+    {
+        *p++ = 0;
+    }
+}
+#define WIPE(ptr, len) wipe((ptr), (len))
+#else
+#define WIPE(ptr, len) ((void)0)
+#endif
+
+/*
+ * The functions which draw their randomness from randombytes() are compiled only when
+ * RPO_FALCON_RANDOMBYTES is defined, i.e., when an OS entropy source is available. All other
+ * functions take their randomness from the caller and do not depend on the OS.
+ */
+
+/* ==== End: Deviation from the reference implementation ======================================= */
+
+/*
+ * Decode the private key elements f, g and F from the encoded private key
+ * sk[] (of size PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES).
+ *
+ * Return value: 0 on success, -1 on error.
+ */
+static int decode_private_key(
+    int8_t *f,
+    int8_t *g,
+    int8_t *F,
+    const uint8_t *sk
+) {
+    size_t u, v;
+
+    if (sk[0] != 0x50 + 9)
+    {
+        return -1;
+    }
+    u = 1;
+    v = PQCLEAN_FALCON512_CLEAN_trim_i8_decode(
+        f, 9, PQCLEAN_FALCON512_CLEAN_max_fg_bits[9],
+        sk + u, PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES - u);
+    if (v == 0)
+    {
+        return -1;
+    }
+    u += v;
+    v = PQCLEAN_FALCON512_CLEAN_trim_i8_decode(
+        g, 9, PQCLEAN_FALCON512_CLEAN_max_fg_bits[9],
+        sk + u, PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES - u);
+    if (v == 0)
+    {
+        return -1;
+    }
+    u += v;
+    v = PQCLEAN_FALCON512_CLEAN_trim_i8_decode(
+        F, 9, PQCLEAN_FALCON512_CLEAN_max_FG_bits[9],
+        sk + u, PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES - u);
+    if (v == 0)
+    {
+        return -1;
+    }
+    u += v;
+    if (u != PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES)
+    {
+        return -1;
+    }
+    return 0;
+}
+
+/*
+ * Hash the nonce (of size NONCELEN bytes) followed by the message (m, mlen)
+ * into a point hm[] using the RPO-based hash-to-point algorithm.
+ */
+static void hash_message_rpo(
+    uint16_t *hm,
+    const uint8_t *nonce,
+    const uint8_t *m,
+    size_t mlen
+) {
+    /* ==== Start: Deviation from the reference implementation ================================= */
+
+    rpo128_context rc;
+
+    // Transform the nonce into 8 chunks each of size 5 bytes. We do this in order to be sure that
+    // the conversion to field elements succeeds
+    uint8_t buffer[64];
+    memset(buffer, 0, 64);
+    for (size_t i = 0; i < 8; i++)
+    {
+        buffer[8 * i] = nonce[5 * i];
+        buffer[8 * i + 1] = nonce[5 * i + 1];
+        buffer[8 * i + 2] = nonce[5 * i + 2];
+        buffer[8 * i + 3] = nonce[5 * i + 3];
+        buffer[8 * i + 4] = nonce[5 * i + 4];
+    }
+
+    rpo128_init(&rc);
+
+    // Messages longer than a single word (i.e., 32 bytes) are domain separated by writing the
+    // number of words into the first capacity element. Single-word messages are hashed as before.
+    if (mlen > 32)
+    {
+        rc.st.A[0] = (uint64_t)((mlen + 31) / 32);
+    }
+
+    rpo128_absorb(&rc, buffer, NONCELEN + 24);
+    rpo128_absorb(&rc, m, mlen);
+    rpo128_finalize(&rc);
+    PQCLEAN_FALCON512_CLEAN_hash_to_point_rpo(&rc, hm, 9);
+    rpo128_release(&rc);
+
+    /* ==== End: Deviation from the reference implementation =================================== */
+}
+
 /* see falcon.h */
 int PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_from_seed_rpo(
     uint8_t *pk,
     uint8_t *sk,
-    unsigned char *seed
+    const unsigned char *seed,
+    size_t seed_len
 ) {
     union
     {
@@ -53,12 +181,13 @@ int PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_from_seed_rpo(
     uint16_t h[512];
     inner_shake256_context rng;
     size_t u, v;
+    int ret = -1;
 
     /*
      * Generate key pair.
      */
     inner_shake256_init(&rng);
-    inner_shake256_inject(&rng, seed, sizeof seed);
+    inner_shake256_inject(&rng, seed, seed_len);
     inner_shake256_flip(&rng);
     PQCLEAN_FALCON512_CLEAN_keygen(&rng, f, g, F, NULL, h, 9, tmp.b);
     inner_shake256_ctx_release(&rng);
@@ -73,7 +202,7 @@ int PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_from_seed_rpo(
         f, 9, PQCLEAN_FALCON512_CLEAN_max_fg_bits[9]);
     if (v == 0)
     {
-        return -1;
+        goto out;
     }
     u += v;
     v = PQCLEAN_FALCON512_CLEAN_trim_i8_encode(
@@ -81,7 +210,7 @@ int PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_from_seed_rpo(
         g, 9, PQCLEAN_FALCON512_CLEAN_max_fg_bits[9]);
     if (v == 0)
     {
-        return -1;
+        goto out;
     }
     u += v;
     v = PQCLEAN_FALCON512_CLEAN_trim_i8_encode(
@@ -89,12 +218,12 @@ int PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_from_seed_rpo(
         F, 9, PQCLEAN_FALCON512_CLEAN_max_FG_bits[9]);
     if (v == 0)
     {
-        return -1;
+        goto out;
     }
     u += v;
     if (u != PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES)
     {
-        return -1;
+        goto out;
     }
 
     /*
@@ -106,31 +235,93 @@ int PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_from_seed_rpo(
         h, 9);
     if (v != PQCLEAN_FALCON512_CLEAN_CRYPTO_PUBLICKEYBYTES - 1)
     {
-        return -1;
+        goto out;
     }
-
-    return 0;
+    ret = 0;
+
+out:
+    WIPE(&tmp, sizeof tmp);
+    WIPE(f, sizeof f);
+    WIPE(g, sizeof g);
+    WIPE(F, sizeof F);
+    WIPE(&rng, sizeof rng);
+    return ret;
 }
 
+#ifdef RPO_FALCON_RANDOMBYTES
 int PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_rpo(
     uint8_t *pk,
     uint8_t *sk
 ) {
     unsigned char seed[48];
+    int ret;
 
     /*
      * Generate a random seed.
      */
     randombytes(seed, sizeof seed);
 
-    return PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_from_seed_rpo(pk, sk, seed);
+    ret = PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_from_seed_rpo(pk, sk, seed, sizeof seed);
+    WIPE(seed, sizeof seed);
+    return ret;
+}
+#endif
+
+/* see falcon.h */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_public_key_rpo(
+    uint8_t *pk,
+    const uint8_t *sk
+) {
+    union
+    {
+        uint8_t b[2 * 512];
+        uint16_t dummy_u16;
+    } tmp;
+    int8_t f[512], g[512], F[512];
+    uint16_t h[512];
+    size_t v;
+    int ret = -1;
+
+    /*
+     * Decode the private key.
+     */
+    if (decode_private_key(f, g, F, sk) < 0)
+    {
+        goto out;
+    }
+
+    /*
+     * Compute and encode the public key h = g/f mod phi mod q.
+     */
+    if (!PQCLEAN_FALCON512_CLEAN_compute_public(h, f, g, 9, tmp.b))
+    {
+        goto out;
+    }
+    pk[0] = 0x00 + 9;
+    v = PQCLEAN_FALCON512_CLEAN_modq_encode(
+        pk + 1, PQCLEAN_FALCON512_CLEAN_CRYPTO_PUBLICKEYBYTES - 1,
+        h, 9);
+    if (v != PQCLEAN_FALCON512_CLEAN_CRYPTO_PUBLICKEYBYTES - 1)
+    {
+        goto out;
+    }
+    ret = 0;
+
+out:
+    WIPE(&tmp, sizeof tmp);
+    WIPE(f, sizeof f);
+    WIPE(g, sizeof g);
+    WIPE(F, sizeof F);
+    return ret;
 }
 
 /*
- * Compute the signature. nonce[] receives the nonce and must have length
- * NONCELEN bytes. sigbuf[] receives the signature value (without nonce
- * or header byte), with *sigbuflen providing the maximum value length and
- * receiving the actual value length.
+ * Compute the signature. hm[] contains the hash-to-point of the nonce and
+ * the message, and must have length 512 elements, each lower than q.
+ * seed[] contains the seed used to initialize the sampler
+ * RNG and must have length 48 bytes. sigbuf[] receives the signature value
+ * (without nonce or header byte), with *sigbuflen providing the maximum
+ * value length and receiving the actual value length.
  *
  * If a signature could be computed but not encoded because it would
  * exceed the output buffer size, then a new signature is computed. If
@@ -141,11 +332,10 @@ int PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_rpo(
  * Return value: 0 on success, -1 on error.
  */
 static int do_sign(
-    uint8_t *nonce,
+    const uint8_t *seed,
     uint8_t *sigbuf,
     size_t *sigbuflen,
-    const uint8_t *m,
-    size_t mlen,
+    const uint16_t *hm,
     const uint8_t *sk
 ) {
     union
@@ -158,92 +348,88 @@ static int do_sign(
     struct
     {
         int16_t sig[512];
-        uint16_t hm[512];
     } r;
-    unsigned char seed[48];
     inner_shake256_context sc;
-    rpo128_context rc;
-    size_t u, v;
+    size_t v;
+    int ret = -1;
 
     /*
      * Decode the private key.
      */
-    if (sk[0] != 0x50 + 9)
-    {
-        return -1;
-    }
-    u = 1;
-    v = PQCLEAN_FALCON512_CLEAN_trim_i8_decode(
-        f, 9, PQCLEAN_FALCON512_CLEAN_max_fg_bits[9],
-        sk + u, PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES - u);
-    if (v == 0)
-    {
-        return -1;
-    }
-    u += v;
-    v = PQCLEAN_FALCON512_CLEAN_trim_i8_decode(
-        g, 9, PQCLEAN_FALCON512_CLEAN_max_fg_bits[9],
-        sk + u, PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES - u);
-    if (v == 0)
+    if (decode_private_key(f, g, F, sk) < 0)
     {
-        return -1;
-    }
-    u += v;
-    v = PQCLEAN_FALCON512_CLEAN_trim_i8_decode(
-        F, 9, PQCLEAN_FALCON512_CLEAN_max_FG_bits[9],
-        sk + u, PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES - u);
-    if (v == 0)
-    {
-        return -1;
-    }
-    u += v;
-    if (u != PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES)
-    {
-        return -1;
+        goto out;
     }
     if (!PQCLEAN_FALCON512_CLEAN_complete_private(G, f, g, F, 9, tmp.b))
     {
-        return -1;
+        goto out;
     }
 
     /*
-     * Create a random nonce (40 bytes).
+     * Initialize a RNG.
      */
-    randombytes(nonce, NONCELEN);
-
-    /* ==== Start: Deviation from the reference implementation ================================= */
+    inner_shake256_init(&sc);
+    inner_shake256_inject(&sc, seed, 48);
+    inner_shake256_flip(&sc);
 
-    // Transform the nonce into 8 chunks each of size 5 bytes. We do this in order to be sure that
-    // the conversion to field elements succeeds
-    uint8_t buffer[64];
-    memset(buffer, 0, 64);
-    for (size_t i = 0; i < 8; i++)
+    /*
+     * Compute and return the signature. This loops until a signature
+     * value is found that fits in the provided buffer.
+     */
+    for (;;)
     {
-        buffer[8 * i] = nonce[5 * i];
-        buffer[8 * i + 1] = nonce[5 * i + 1];
-        buffer[8 * i + 2] = nonce[5 * i + 2];
-        buffer[8 * i + 3] = nonce[5 * i + 3];
-        buffer[8 * i + 4] = nonce[5 * i + 4];
+        PQCLEAN_FALCON512_CLEAN_sign_dyn(r.sig, &sc, f, g, F, G, hm, 9, tmp.b);
+        v = PQCLEAN_FALCON512_CLEAN_comp_encode(sigbuf, *sigbuflen, r.sig, 9);
+        if (v != 0)
+        {
+            inner_shake256_ctx_release(&sc);
+            *sigbuflen = v;
+            ret = 0;
+            goto out;
+        }
     }
 
-    /*
-     * Hash message nonce + message into a vector.
-     */
-    rpo128_init(&rc);
-    rpo128_absorb(&rc, buffer, NONCELEN + 24);
-    rpo128_absorb(&rc, m, mlen);
-    rpo128_finalize(&rc);
-    PQCLEAN_FALCON512_CLEAN_hash_to_point_rpo(&rc, r.hm, 9);
-    rpo128_release(&rc);
+out:
+    WIPE(&tmp, sizeof tmp);
+    WIPE(f, sizeof f);
+    WIPE(g, sizeof g);
+    WIPE(F, sizeof F);
+    WIPE(G, sizeof G);
+    WIPE(&r, sizeof r);
+    WIPE(&sc, sizeof sc);
+    return ret;
+}
 
-    /* ==== End: Deviation from the reference implementation =================================== */
+/*
+ * Compute the signature using an expanded private key (as produced by
+ * PQCLEAN_FALCON512_CLEAN_crypto_sign_expand_private_key_rpo()). The
+ * parameters and return value are the same as for do_sign().
+ */
+static int do_sign_expanded(
+    const uint8_t *seed,
+    uint8_t *sigbuf,
+    size_t *sigbuflen,
+    const uint16_t *hm,
+    const fpr *expanded_key
+) {
+    union
+    {
+        uint8_t b[48 * 512];
+        uint64_t dummy_u64;
+        fpr dummy_fpr;
+    } tmp;
+    struct
+    {
+        int16_t sig[512];
+    } r;
+    inner_shake256_context sc;
+    size_t v;
 
     /*
      * Initialize a RNG.
      */
-    randombytes(seed, sizeof seed);
     inner_shake256_init(&sc);
-    inner_shake256_inject(&sc, seed, sizeof seed);
+    inner_shake256_inject(&sc, seed, 48);
     inner_shake256_flip(&sc);
 
     /*
@@ -252,15 +438,20 @@ static int do_sign(
      */
     for (;;)
     {
-        PQCLEAN_FALCON512_CLEAN_sign_dyn(r.sig, &sc, f, g, F, G, r.hm, 9, tmp.b);
+        PQCLEAN_FALCON512_CLEAN_sign_tree(r.sig, &sc, expanded_key, hm, 9, tmp.b);
         v = PQCLEAN_FALCON512_CLEAN_comp_encode(sigbuf, *sigbuflen, r.sig, 9);
         if (v != 0)
         {
             inner_shake256_ctx_release(&sc);
             *sigbuflen = v;
-            return 0;
+            break;
         }
     }
+
+    WIPE(&tmp, sizeof tmp);
+    WIPE(&r, sizeof r);
+    WIPE(&sc, sizeof sc);
+    return 0;
 }
 
 /*
@@ -284,7 +475,6 @@ static int do_verify(
     } tmp;
     uint16_t h[512], hm[512];
     int16_t sig[512];
-    rpo128_context rc;
 
     /*
      * Decode public key.
@@ -313,33 +503,10 @@ static int do_verify(
         return -1;
     }
 
-    /* ==== Start: Deviation from the reference implementation ================================= */
-
     /*
      * Hash nonce + message into a vector.
      */
-
-    // Transform the nonce into 8 chunks each of size 5 bytes. We do this in order to be sure that
-    // the conversion to field elements succeeds
-    uint8_t buffer[64];
-    memset(buffer, 0, 64);
-    for (size_t i = 0; i < 8; i++)
-    {
-        buffer[8 * i] = nonce[5 * i];
-        buffer[8 * i + 1] = nonce[5 * i + 1];
-        buffer[8 * i + 2] = nonce[5 * i + 2];
-        buffer[8 * i + 3] = nonce[5 * i + 3];
-        buffer[8 * i + 4] = nonce[5 * i + 4];
-    }
-
-    rpo128_init(&rc);
-    rpo128_absorb(&rc, buffer, NONCELEN + 24);
-    rpo128_absorb(&rc, m, mlen);
-    rpo128_finalize(&rc);
-    PQCLEAN_FALCON512_CLEAN_hash_to_point_rpo(&rc, hm, 9);
-    rpo128_release(&rc);
-
-    /* === End: Deviation from the reference implementation ==================================== */
+    hash_message_rpo(hm, nonce, m, mlen);
 
     /*
      * Verify signature.
@@ -351,6 +518,7 @@ static int do_verify(
     return 0;
 }
 
+#ifdef RPO_FALCON_RANDOMBYTES
 /* see falcon.h */
 int PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_rpo(
     uint8_t *sig,
@@ -370,13 +538,172 @@ int PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_rpo(
      * and using the same output from randombytes() (this is for
      * reproducibility of tests).
      */
+    uint8_t nonce[NONCELEN];
+    unsigned char seed[48];
+    int ret;
+
+    /*
+     * Create a random nonce (40 bytes) and a random seed for the sampler RNG.
+     */
+    randombytes(nonce, NONCELEN);
+    randombytes(seed, sizeof seed);
+
+    ret = PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_from_seed_rpo(
+        sig, siglen, m, mlen, sk, nonce, seed);
+    WIPE(seed, sizeof seed);
+    return ret;
+}
+#endif
+
+/* see falcon.h */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_from_seed_rpo(
+    uint8_t *sig,
+    size_t *siglen,
+    const uint8_t *m,
+    size_t mlen,
+    const uint8_t *sk,
+    const uint8_t *nonce,
+    const uint8_t *seed
+) {
+    uint16_t hm[512];
+    int ret;
+
+    /*
+     * Hash message nonce + message into a vector.
+     */
+    hash_message_rpo(hm, nonce, m, mlen);
+
+    ret = PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_prehashed_from_seed_rpo(
+        sig, siglen, hm, sk, nonce, seed);
+    WIPE(hm, sizeof hm);
+    return ret;
+}
+
+/* see falcon.h */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_prehashed_from_seed_rpo(
+    uint8_t *sig,
+    size_t *siglen,
+    const uint16_t *hm,
+    const uint8_t *sk,
+    const uint8_t *nonce,
+    const uint8_t *seed
+) {
+    size_t vlen;
+    size_t u;
+
+    for (u = 0; u < 512; u++)
+    {
+        if (hm[u] >= 12289)
+        {
+            return -1;
+        }
+    }
+
+    vlen = PQCLEAN_FALCON512_CLEAN_CRYPTO_BYTES - NONCELEN - 3;
+    memmove(sig + 1, nonce, NONCELEN);
+    if (do_sign(seed, sig + 1 + NONCELEN, &vlen, hm, sk) < 0)
+    {
+        return -1;
+    }
+    sig[0] = 0x30 + 9;
+    *siglen = 1 + NONCELEN + vlen;
+    return 0;
+}
+
+/* see falcon.h */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_expand_private_key_rpo(
+    uint64_t *expanded_key,
+    const uint8_t *sk
+) {
+    union
+    {
+        uint8_t b[72 * 512];
+        uint64_t dummy_u64;
+        fpr dummy_fpr;
+    } tmp;
+    int8_t f[512], g[512], F[512], G[512];
+    int ret = -1;
+
+    /*
+     * Decode the private key and recompute G.
+     */
+    if (decode_private_key(f, g, F, sk) < 0)
+    {
+        goto out;
+    }
+    if (!PQCLEAN_FALCON512_CLEAN_complete_private(G, f, g, F, 9, tmp.b))
+    {
+        goto out;
+    }
+
+    /*
+     * Compute the B0 matrix and the LDL tree.
+     */
+    PQCLEAN_FALCON512_CLEAN_expand_privkey((fpr *)expanded_key, f, g, F, G, 9, tmp.b);
+    ret = 0;
+
+out:
+    WIPE(&tmp, sizeof tmp);
+    WIPE(f, sizeof f);
+    WIPE(g, sizeof g);
+    WIPE(F, sizeof F);
+    WIPE(G, sizeof G);
+    return ret;
+}
+
+#ifdef RPO_FALCON_RANDOMBYTES
+/* see falcon.h */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_expanded_rpo(
+    uint8_t *sig,
+    size_t *siglen,
+    const uint8_t *m,
+    size_t mlen,
+    const uint64_t *expanded_key
+) {
+    uint8_t nonce[NONCELEN];
+    unsigned char seed[48];
+    int ret;
+
+    /*
+     * Create a random nonce (40 bytes) and a random seed for the sampler RNG.
+     */
+    randombytes(nonce, NONCELEN);
+    randombytes(seed, sizeof seed);
+
+    ret = PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_expanded_from_seed_rpo(
+        sig, siglen, m, mlen, expanded_key, nonce, seed);
+    WIPE(seed, sizeof seed);
+    return ret;
+}
+#endif
+
+/* see falcon.h */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_expanded_from_seed_rpo(
+    uint8_t *sig,
+    size_t *siglen,
+    const uint8_t *m,
+    size_t mlen,
+    const uint64_t *expanded_key,
+    const uint8_t *nonce,
+    const uint8_t *seed
+) {
+    uint16_t hm[512];
     size_t vlen;
 
+    /*
+     * Hash message nonce + message into a vector.
+     */
+    hash_message_rpo(hm, nonce, m, mlen);
+
     vlen = PQCLEAN_FALCON512_CLEAN_CRYPTO_BYTES - NONCELEN - 3;
-    if (do_sign(sig + 1, sig + 1 + NONCELEN, &vlen, m, mlen, sk) < 0)
+    memmove(sig + 1, nonce, NONCELEN);
+    if (do_sign_expanded(seed, sig + 1 + NONCELEN, &vlen, hm,
+                         (const fpr *)expanded_key) < 0)
     {
+        WIPE(hm, sizeof hm);
         return -1;
     }
+    WIPE(hm, sizeof hm);
     sig[0] = 0x30 + 9;
     *siglen = 1 + NONCELEN + vlen;
     return 0;
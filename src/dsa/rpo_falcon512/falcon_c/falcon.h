@@ -5,6 +5,12 @@
 #define PQCLEAN_FALCON512_CLEAN_CRYPTO_PUBLICKEYBYTES 897
 #define PQCLEAN_FALCON512_CLEAN_CRYPTO_BYTES 666
 
+/*
+ * Size of an expanded private key in 64-bit words, i.e., (8*logn+40)*2^logn
+ * bytes for logn = 9.
+ */
+#define PQCLEAN_FALCON512_CLEAN_EXPANDED_KEY_WORDS 7168
+
 /*
  * Generate a new key pair. Public key goes into pk[], private key in sk[].
  * Key sizes are exact (in bytes):
@@ -21,7 +27,9 @@ int PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_rpo(
     uint8_t *pk, uint8_t *sk);
 
 /*
- * Generate a new key pair from  seed. Public key goes into pk[], private key in sk[].
+ * Generate a new key pair from a seed (seed, seed_len). The seed is used to
+ * initialize the SHAKE256-based RNG from which the key pair is sampled.
+ * Public key goes into pk[], private key in sk[].
  * Key sizes are exact (in bytes):
  *   public (pk): PQCLEAN_FALCON512_CLEAN_CRYPTO_PUBLICKEYBYTES
  *   private (sk): PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES
@@ -29,7 +37,19 @@ int PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_rpo(
  * Return value: 0 on success, -1 on error.
  */
 int PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_from_seed_rpo(
-    uint8_t *pk, uint8_t *sk, unsigned char *seed);
+    uint8_t *pk, uint8_t *sk, const unsigned char *seed, size_t seed_len);
+
+/*
+ * Compute the public key corresponding to a given private key (sk). The
+ * public key goes into pk[]. Key sizes are exact (in bytes):
+ *   public (pk): PQCLEAN_FALCON512_CLEAN_CRYPTO_PUBLICKEYBYTES
+ *   private (sk): PQCLEAN_FALCON512_CLEAN_CRYPTO_SECRETKEYBYTES
+ *
+ * Return value: 0 on success, -1 on error (e.g., if the private key
+ * cannot be decoded).
+ */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_public_key_rpo(
+    uint8_t *pk, const uint8_t *sk);
 
 /*
  * Compute a signature on a provided message (m, mlen), with a given
@@ -49,6 +69,77 @@ int PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_rpo(
     uint8_t *sig, size_t *siglen,
     const uint8_t *m, size_t mlen, const uint8_t *sk);
 
+/*
+ * Compute a signature on a provided message (m, mlen), with a given
+ * private key (sk), nonce (nonce) and seed used to initialize the sampler
+ * RNG (seed). Signature is written in sig[], with length written into
+ * *siglen. Signature length is variable; maximum signature length
+ * (in bytes) is PQCLEAN_FALCON512_CLEAN_CRYPTO_BYTES.
+ *
+ * The nonce must have length 40 bytes and the seed must have length
+ * 48 bytes. Signing is fully deterministic given these inputs; the caller
+ * must ensure that they are generated with a cryptographically secure RNG
+ * and that a nonce is never reused.
+ *
+ * Return value: 0 on success, -1 on error.
+ */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_from_seed_rpo(
+    uint8_t *sig, size_t *siglen,
+    const uint8_t *m, size_t mlen, const uint8_t *sk,
+    const uint8_t *nonce, const uint8_t *seed);
+
+/*
+ * Compute a signature on a message which was already hashed to a point
+ * (hm, 512 elements each lower than q), with a given private key (sk),
+ * nonce (nonce) and seed used to initialize the sampler RNG (seed). The
+ * nonce is only copied into the signature; the caller is responsible
+ * for hm being the hash-to-point of the nonce and the message.
+ * Signature is written in sig[], with length written into *siglen.
+ *
+ * Return value: 0 on success, -1 on error (e.g., if an element of hm[]
+ * is not lower than q).
+ */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_prehashed_from_seed_rpo(
+    uint8_t *sig, size_t *siglen,
+    const uint16_t *hm, const uint8_t *sk,
+    const uint8_t *nonce, const uint8_t *seed);
+
+/*
+ * Expand a private key (sk) into the B0 matrix in FFT representation and
+ * the LDL tree. The expanded key is written in expanded_key[], which must
+ * have room for PQCLEAN_FALCON512_CLEAN_EXPANDED_KEY_WORDS elements.
+ *
+ * Return value: 0 on success, -1 on error.
+ */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_expand_private_key_rpo(
+    uint64_t *expanded_key, const uint8_t *sk);
+
+/*
+ * Compute a signature on a provided message (m, mlen), with a given
+ * expanded private key (expanded_key). This is equivalent to
+ * PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_rpo() but avoids
+ * recomputing the B0 matrix and the LDL tree for every signature.
+ *
+ * Return value: 0 on success, -1 on error.
+ */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_expanded_rpo(
+    uint8_t *sig, size_t *siglen,
+    const uint8_t *m, size_t mlen, const uint64_t *expanded_key);
+
+/*
+ * Compute a signature on a provided message (m, mlen), with a given
+ * expanded private key (expanded_key), nonce (nonce) and seed used to
+ * initialize the sampler RNG (seed). This is equivalent to
+ * PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_from_seed_rpo() but avoids
+ * recomputing the B0 matrix and the LDL tree for every signature.
+ *
+ * Return value: 0 on success, -1 on error.
+ */
+int PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_expanded_from_seed_rpo(
+    uint8_t *sig, size_t *siglen,
+    const uint8_t *m, size_t mlen, const uint64_t *expanded_key,
+    const uint8_t *nonce, const uint8_t *seed);
+
 /*
  * Verify a signature (sig, siglen) on a message (m, mlen) with a given
  * public key (pk).
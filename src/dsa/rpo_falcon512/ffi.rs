@@ -11,9 +11,11 @@ extern "C" {
     /// - private (sk): 1281
     ///
     /// Return value: 0 on success, -1 on error.
+    #[cfg(feature = "std")]
     pub fn PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_rpo(pk: *mut u8, sk: *mut u8) -> c_int;
 
-    /// Generate a new key pair from  seed. Public key goes into pk[], private key in sk[].
+    /// Generate a new key pair from a seed (seed, seed_len). Public key goes into pk[], private
+    /// key in sk[].
     /// Key sizes are exact (in bytes):
     /// - public (pk): 897
     /// - private (sk): 1281
@@ -23,8 +25,17 @@ extern "C" {
         pk: *mut u8,
         sk: *mut u8,
         seed: *const u8,
+        seed_len: usize,
     ) -> c_int;
 
+    /// Compute the public key corresponding to a given private key (sk). Public key goes into
+    /// pk[]. Key sizes are exact (in bytes):
+    /// - public (pk): 897
+    /// - private (sk): 1281
+    ///
+    /// Return value: 0 on success, -1 on error.
+    pub fn PQCLEAN_FALCON512_CLEAN_crypto_sign_public_key_rpo(pk: *mut u8, sk: *const u8) -> c_int;
+
     /// Compute a signature on a provided message (m, mlen), with a given private key (sk).
     /// Signature is written in sig[], with length written into *siglen. Signature length is
     /// variable; maximum signature length (in bytes) is 666.
@@ -32,6 +43,7 @@ extern "C" {
     /// sig[], m[] and sk[] may overlap each other arbitrarily.
     ///
     /// Return value: 0 on success, -1 on error.
+    #[cfg(feature = "std")]
     pub fn PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_rpo(
         sig: *mut u8,
         siglen: *mut usize,
@@ -40,6 +52,77 @@ extern "C" {
         sk: *const u8,
     ) -> c_int;
 
+    /// Compute a signature on a provided message (m, mlen), with a given private key (sk), using
+    /// the provided nonce (40 bytes) and seed for the sampler RNG (48 bytes). Signature is
+    /// written in sig[], with length written into *siglen. Signature length is variable; maximum
+    /// signature length (in bytes) is 666.
+    ///
+    /// Return value: 0 on success, -1 on error.
+    pub fn PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_from_seed_rpo(
+        sig: *mut u8,
+        siglen: *mut usize,
+        m: *const u8,
+        mlen: usize,
+        sk: *const u8,
+        nonce: *const u8,
+        seed: *const u8,
+    ) -> c_int;
+
+    /// Compute a signature on a message which was already hashed to a point (hm, 512 elements
+    /// each lower than 12289), with a given private key (sk), using the provided nonce (40 bytes)
+    /// and seed for the sampler RNG (48 bytes). The nonce is only copied into the signature.
+    /// Signature is written in sig[], with length written into *siglen. Signature length is
+    /// variable; maximum signature length (in bytes) is 666.
+    ///
+    /// Return value: 0 on success, -1 on error.
+    pub fn PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_prehashed_from_seed_rpo(
+        sig: *mut u8,
+        siglen: *mut usize,
+        hm: *const u16,
+        sk: *const u8,
+        nonce: *const u8,
+        seed: *const u8,
+    ) -> c_int;
+
+    /// Expand a private key (sk) into the B0 matrix in FFT representation and the LDL tree. The
+    /// expanded key is written in expanded_key[], which must have room for 7168 elements.
+    ///
+    /// Return value: 0 on success, -1 on error.
+    pub fn PQCLEAN_FALCON512_CLEAN_crypto_sign_expand_private_key_rpo(
+        expanded_key: *mut u64,
+        sk: *const u8,
+    ) -> c_int;
+
+    /// Compute a signature on a provided message (m, mlen), with a given expanded private key
+    /// (expanded_key). Signature is written in sig[], with length written into *siglen.
+    /// Signature length is variable; maximum signature length (in bytes) is 666.
+    ///
+    /// Return value: 0 on success, -1 on error.
+    #[cfg(feature = "std")]
+    pub fn PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_expanded_rpo(
+        sig: *mut u8,
+        siglen: *mut usize,
+        m: *const u8,
+        mlen: usize,
+        expanded_key: *const u64,
+    ) -> c_int;
+
+    /// Compute a signature on a provided message (m, mlen), with a given expanded private key
+    /// (expanded_key), using the provided nonce (40 bytes) and seed for the sampler RNG
+    /// (48 bytes). Signature is written in sig[], with length written into *siglen. Signature
+    /// length is variable; maximum signature length (in bytes) is 666.
+    ///
+    /// Return value: 0 on success, -1 on error.
+    pub fn PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_expanded_from_seed_rpo(
+        sig: *mut u8,
+        siglen: *mut usize,
+        m: *const u8,
+        mlen: usize,
+        expanded_key: *const u64,
+        nonce: *const u8,
+        seed: *const u8,
+    ) -> c_int;
+
     // TEST HELPERS
     // --------------------------------------------------------------------------------------------
 
@@ -48,7 +131,7 @@ extern "C" {
     /// sig[], m[] and pk[] may overlap each other arbitrarily.
     ///
     /// Return value: 0 on success, -1 on error.
-    #[cfg(test)]
+    #[cfg(all(test, feature = "std"))]
     pub fn PQCLEAN_FALCON512_CLEAN_crypto_sign_verify_rpo(
         sig: *const u8,
         siglen: usize,
@@ -57,6 +140,25 @@ extern "C" {
         pk: *const u8,
     ) -> c_int;
 
+    /// Generate a new key pair using the reference Falcon-512 implementation.
+    ///
+    /// Return value: 0 on success, -1 on error.
+    #[cfg(all(test, feature = "std"))]
+    pub fn PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair(pk: *mut u8, sk: *mut u8) -> c_int;
+
+    /// Compute a signature on a provided message (m, mlen) using the reference Falcon-512
+    /// implementation, i.e., using SHAKE256 in the hash-to-point algorithm.
+    ///
+    /// Return value: 0 on success, -1 on error.
+    #[cfg(all(test, feature = "std"))]
+    pub fn PQCLEAN_FALCON512_CLEAN_crypto_sign_signature(
+        sig: *mut u8,
+        siglen: *mut usize,
+        m: *const u8,
+        mlen: usize,
+        sk: *const u8,
+    ) -> c_int;
+
     /// Hash-to-Point algorithm.
     ///
     /// This function generates a point in Z_q[x]/(phi) from a given message.
@@ -64,17 +166,17 @@ extern "C" {
     /// It takes a finalized rpo128_context as input and it generates the coefficients of the polynomial
     /// representing the point. The coefficients are stored in the array 'x'. The number of coefficients
     /// is given by 'logn', which must in our case is 512.
-    #[cfg(test)]
+    #[cfg(all(test, feature = "std"))]
     pub fn PQCLEAN_FALCON512_CLEAN_hash_to_point_rpo(
         rc: *mut Rpo128Context,
         x: *mut u16,
         logn: usize,
     );
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "std"))]
     pub fn rpo128_init(sc: *mut Rpo128Context);
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "std"))]
     pub fn rpo128_absorb(
         sc: *mut Rpo128Context,
         data: *const core::ffi::c_void,
@@ -84,12 +186,12 @@ extern "C" {
         len: usize,
     );
 
-    #[cfg(test)]
+    #[cfg(all(test, feature = "std"))]
     pub fn rpo128_finalize(sc: *mut Rpo128Context);
 }
 
 #[repr(C)]
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 pub struct Rpo128Context {
     pub content: [u64; 13usize],
 }
@@ -121,7 +223,8 @@ mod tests {
                 PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair_from_seed_rpo(
                     pk.as_mut_ptr(),
                     sk.as_mut_ptr(),
-                    seed.as_ptr()
+                    seed.as_ptr(),
+                    seed.len()
                 )
             );
 
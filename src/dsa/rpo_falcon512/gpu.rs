@@ -0,0 +1,136 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use std::sync::OnceLock;
+
+use super::{
+    ntt::{N_INV, ZETAS},
+    Polynomial, MODULUS, N,
+};
+use crate::utils::gpu::{GpuContext, Kernel};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The smallest number of signatures for which [mul_ntt_many()] is used by
+/// [Signature::verify_batch()](super::Signature::verify_batch); for smaller batches, the cost of
+/// the transfers to and from the GPU outweighs the gains.
+pub(super) const MIN_BATCH_SIZE: usize = 256;
+
+/// The source of the kernel, without the constants prepended by [kernel_source()].
+const KERNEL_SOURCE: &str = include_str!("ntt.wgsl");
+
+// POLYNOMIAL MULTIPLICATION
+// ================================================================================================
+
+/// Returns the products `polys[i] * h_i` where `h_i` is the polynomial with the transform
+/// `h_ntts[keys[i]]`, i.e., the i-th element of the result is equal to
+/// `polys[i].mul_ntt(&h_ntts[keys[i]])`.
+///
+/// The products are computed on the GPU, one workgroup per polynomial. Returns `None` if no GPU
+/// is available or the computation fails, in which case the products should be computed on the
+/// CPU instead.
+pub(super) fn mul_ntt_many(
+    polys: &[Polynomial],
+    h_ntts: &[[u16; N]],
+    keys: &[usize],
+) -> Option<Vec<Polynomial>> {
+    debug_assert_eq!(polys.len(), keys.len());
+    let context = GpuContext::get()?;
+    let kernel = kernel(context)?;
+
+    // each dispatch uploads at most as many key transforms as there are polynomials in it, and
+    // thus the input and output buffers of a dispatch are of the same size
+    let chunk_size = (context.max_buffer_words() / N).min(context.max_workgroups() as usize);
+    if chunk_size == 0 {
+        return None;
+    }
+
+    let mut result = Vec::with_capacity(polys.len());
+    for (polys, keys) in polys.chunks(chunk_size).zip(keys.chunks(chunk_size)) {
+        // only the transforms of the keys used in this chunk are uploaded
+        let mut positions = BTreeMap::new();
+        let mut chunk_h_ntts = Vec::new();
+        let mut chunk_keys = Vec::with_capacity(keys.len());
+        for &key in keys {
+            let position = *positions.entry(key).or_insert_with(|| {
+                chunk_h_ntts.extend(h_ntts[key].iter().map(|&e| e as u32));
+                (chunk_h_ntts.len() / N - 1) as u32
+            });
+            chunk_keys.push(position);
+        }
+        let chunk_polys: Vec<u32> =
+            polys.iter().flat_map(|poly| poly.inner().map(|e| e as u32)).collect();
+
+        let products = context.run(
+            kernel,
+            &[&chunk_polys, &chunk_h_ntts, &chunk_keys],
+            chunk_polys.len(),
+            polys.len() as u32,
+        )?;
+
+        for product in products.chunks_exact(N) {
+            let mut coefficients = [0_u16; N];
+            for (c, &e) in coefficients.iter_mut().zip(product) {
+                // a faulty device must not produce an invalid polynomial
+                if e >= MODULUS as u32 {
+                    return None;
+                }
+                *c = e as u16;
+            }
+            // using the raw constructor is OK here because the coefficients are checked above
+            result.push(unsafe { Polynomial::new(coefficients) });
+        }
+    }
+
+    Some(result)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the kernel computing the products, compiling it on the first call.
+fn kernel(context: &GpuContext) -> Option<&'static Kernel> {
+    static KERNEL: OnceLock<Option<Kernel>> = OnceLock::new();
+    KERNEL
+        .get_or_init(|| context.create_kernel("rpo_falcon512_ntt", kernel_source(), "main"))
+        .as_ref()
+}
+
+/// Returns the source of the kernel with the constants of the transform prepended to it.
+fn kernel_source() -> String {
+    let zetas: Vec<String> = ZETAS.iter().map(|zeta| format!("{zeta}u")).collect();
+    format!(
+        "const MODULUS: u32 = {MODULUS}u;\nconst N_INV: u32 = {N_INV}u;\n\
+         var<private> ZETAS: array<u32, {N}> = array<u32, {N}>({});\n\n{KERNEL_SOURCE}",
+        zetas.join(", ")
+    )
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rand_utils::rand_array;
+
+    use super::{mul_ntt_many, Polynomial, MODULUS, N};
+
+    #[test]
+    fn test_mul_ntt_many() {
+        let random_poly =
+            || unsafe { Polynomial::new(rand_array::<u16, N>().map(|e| e % MODULUS)) };
+        let h_ntts: Vec<[u16; N]> = (0..3).map(|_| random_poly().ntt()).collect();
+        let polys: Vec<Polynomial> = (0..10).map(|_| random_poly()).collect();
+        let keys: Vec<usize> = (0..polys.len()).map(|i| i * i % h_ntts.len()).collect();
+
+        // without a GPU, there is nothing to compare against the CPU implementation
+        let Some(products) = mul_ntt_many(&polys, &h_ntts, &keys) else {
+            return;
+        };
+        assert_eq!(products.len(), polys.len());
+        for ((poly, &key), product) in polys.iter().zip(&keys).zip(products) {
+            assert_eq!(product, poly.mul_ntt(&h_ntts[key]));
+        }
+    }
+}
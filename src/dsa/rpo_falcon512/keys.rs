@@ -1,46 +1,321 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::fmt;
+
+use subtle::ConstantTimeEq;
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
 use super::{
-    ByteReader, ByteWriter, Deserializable, DeserializationError, FalconError, Polynomial,
-    PublicKeyBytes, Rpo256, SecretKeyBytes, Serializable, Signature, Word,
+    encryption::{self, ENCRYPTED_KEY_PAIR_LEN, ENC_NONCE_LEN, SALT_LEN},
+    ByteReader, ByteWriter, Deserializable, DeserializationError, DeterministicNonceGenerator,
+    FalconError, Felt, Polynomial, PublicKeyBytes, Rpo256, RpoDigest, SecretKeyBytes, Serializable,
+    Signature, SignatureWithoutPk, VerificationError, Word, N, PK_LEN,
+};
+#[cfg(falcon_signing)]
+use {
+    super::{
+        ffi, nonce::FixedNonceGenerator, signature::SIG_BUFFER_LEN, Nonce, NonceGenerator,
+        RandomNonceGenerator, SignatureBytes, NONCE_LEN, SIGNING_SEED_LEN, SIG_LEN, SK_LEN,
+    },
+    alloc::{boxed::Box, vec},
+    core::ffi::c_int,
+    rand::{CryptoRng, Rng, RngCore},
 };
 #[cfg(feature = "std")]
 use {
-    super::{ffi, NonceBytes, NONCE_LEN, PK_LEN, SIG_LEN, SK_LEN},
-    alloc::vec::Vec,
+    alloc::sync::Arc,
+    core::sync::atomic::{AtomicBool, Ordering},
+    std::{
+        sync::mpsc::{self, RecvTimeoutError},
+        thread,
+        time::Duration,
+    },
 };
 
+/// Length of the seed from which a key pair can be derived.
+#[cfg(falcon_signing)]
+const KEYGEN_SEED_LEN: usize = 32;
+
+/// Length of an expanded secret key in 64-bit words.
+#[cfg(falcon_signing)]
+const EXPANDED_KEY_WORDS: usize = 7168;
+
 // PUBLIC KEY
 // ================================================================================================
 
 /// A public key for verifying signatures.
 ///
-/// The public key is a [Word] (i.e., 4 field elements) that is the hash of the coefficients of
-/// the polynomial representing the raw bytes of the expanded public key.
+/// The public key wraps the raw bytes of the expanded public key together with the polynomial `h`
+/// they decode into. The commitment to the public key is a [Word] (i.e., 4 field elements) that
+/// is the hash of the coefficients of `h`.
 ///
 /// For Falcon-512, the first byte of the expanded public key is always equal to log2(512) i.e., 9.
 #[derive(Debug, Clone, Copy, PartialEq)]
-pub struct PublicKey(Word);
+pub struct PublicKey {
+    pk: PublicKeyBytes,
+    h: Polynomial,
+    commitment: Word,
+}
 
 impl PublicKey {
-    /// Returns a new [PublicKey] which is a commitment to the provided expanded public key.
+    /// Returns a new [PublicKey] instantiated from the provided expanded public key.
     ///
     /// # Errors
     /// Returns an error if the decoding of the public key fails.
     pub fn new(pk: PublicKeyBytes) -> Result<Self, FalconError> {
         let h = Polynomial::from_pub_key(&pk)?;
         let pk_felts = h.to_elements();
-        let pk_digest = Rpo256::hash_elements(&pk_felts).into();
-        Ok(Self(pk_digest))
+        let commitment = Rpo256::hash_elements(&pk_felts).into();
+        Ok(Self { pk, h, commitment })
+    }
+
+    /// Returns a new [PublicKey] decoded from a public key in the encoding used by the reference
+    /// Falcon-512 implementation (e.g., as produced by `pqcrypto` or liboqs).
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes are not a valid encoding of a public key.
+    pub fn from_falcon_bytes(bytes: &[u8]) -> Result<Self, FalconError> {
+        let pk: PublicKeyBytes = bytes
+            .try_into()
+            .map_err(|_| FalconError::PubKeyDecodingInvalidLength(bytes.len()))?;
+        Self::new(pk)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public key polynomial h.
+    pub fn pub_key_poly(&self) -> Polynomial {
+        self.h
     }
 
+    /// Returns the expanded public key from which this public key was instantiated.
+    pub fn expanded_public_key(&self) -> PublicKeyBytes {
+        self.pk
+    }
+
+    /// Returns this public key in the encoding used by the reference Falcon-512 implementation.
+    ///
+    /// This is the same as the expanded public key.
+    pub fn to_falcon_bytes(&self) -> PublicKeyBytes {
+        self.pk
+    }
+
+    /// Returns the commitment to this public key, i.e., the RPO256 hash of the coefficients of
+    /// the public key polynomial h.
+    ///
+    /// This is the value against which [Signature::verify()] checks the expanded public key
+    /// embedded in a signature.
+    pub fn to_commitment(self) -> Word {
+        self.commitment
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
     /// Verifies the provided signature against provided message and this public key.
     pub fn verify(&self, message: Word, signature: &Signature) -> bool {
-        signature.verify(message, self.0)
+        signature.verify(message, self.commitment)
+    }
+
+    /// Checks whether the provided signature is valid against provided message and this public
+    /// key, returning the reason of the failure otherwise.
+    ///
+    /// # Errors
+    /// See [Signature::verify_checked()].
+    pub fn verify_checked(
+        &self,
+        message: Word,
+        signature: &Signature,
+    ) -> Result<(), VerificationError> {
+        signature.verify_checked(message, self.commitment)
+    }
+
+    /// Verifies the provided signature against provided byte message and this public key.
+    ///
+    /// The message is first hashed into a [Word] using [Rpo256::hash()].
+    pub fn verify_bytes(&self, message: &[u8], signature: &Signature) -> bool {
+        signature.verify_bytes(message, self.commitment)
+    }
+
+    /// Verifies the provided signature against provided message consisting of field elements and
+    /// this public key.
+    ///
+    /// The message is first hashed into a [Word] using [Rpo256::hash_elements()].
+    pub fn verify_elements(&self, message: &[Felt], signature: &Signature) -> bool {
+        signature.verify_elements(message, self.commitment)
+    }
+
+    /// Verifies the provided signature against provided message consisting of multiple words
+    /// and this public key.
+    pub fn verify_words(&self, message: &[Word], signature: &Signature) -> bool {
+        signature.verify_words(message, self.commitment)
+    }
+
+    /// Verifies the provided signature against a message which was already hashed to the point
+    /// `c` and this public key.
+    ///
+    /// See [Signature::verify_prehashed()].
+    pub fn verify_prehashed(&self, c: Polynomial, signature: &Signature) -> bool {
+        signature.verify_prehashed(c, self.commitment)
+    }
+
+    /// Verifies the provided signature, which does not embed the expanded public key, against
+    /// provided message and this public key.
+    pub fn verify_without_pk(&self, message: Word, signature: &SignatureWithoutPk) -> bool {
+        signature.verify_with_key(message, self)
     }
 }
 
 impl From<PublicKey> for Word {
     fn from(key: PublicKey) -> Self {
-        key.0
+        key.commitment
+    }
+}
+
+// VERIFIER KEY
+// ================================================================================================
+
+/// A public key prepared for verifying many signatures.
+///
+/// In addition to the [PublicKey], the verifier key caches the number theoretic transform of the
+/// public key polynomial `h`. This avoids recomputing the transform of `h` when computing the
+/// product `s2 * h` during verification of every signature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifierKey {
+    pub_key: PublicKey,
+    h_ntt: [u16; N],
+}
+
+impl VerifierKey {
+    /// Returns a new [VerifierKey] instantiated from the provided public key.
+    pub fn new(pub_key: PublicKey) -> Self {
+        let h_ntt = pub_key.pub_key_poly().ntt();
+        Self { pub_key, h_ntt }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public key from which this verifier key was instantiated.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.pub_key
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies the provided signature against provided message and this verifier key.
+    ///
+    /// Since the expanded public key is known, the expanded public key embedded in the signature
+    /// is compared against it directly, without hashing it into a commitment.
+    pub fn verify(&self, message: Word, signature: &Signature) -> bool {
+        if signature.pk != self.pub_key.expanded_public_key() {
+            return false;
+        }
+        self.verify_without_pk(message, signature.without_pk())
+    }
+
+    /// Verifies the provided signature, which does not embed the expanded public key, against
+    /// provided message and this verifier key.
+    pub fn verify_without_pk(&self, message: Word, signature: &SignatureWithoutPk) -> bool {
+        signature
+            .verify_against_ntt(signature.hash_to_point(message), &self.h_ntt)
+            .is_ok()
+    }
+}
+
+impl From<PublicKey> for VerifierKey {
+    fn from(pub_key: PublicKey) -> Self {
+        Self::new(pub_key)
+    }
+}
+
+// PUBLIC KEY TABLE
+// ================================================================================================
+
+/// A table of public keys indexed by their commitments.
+///
+/// The table allows resolving the full public key for a commitment (e.g., one stored on chain),
+/// and thus verifying signatures which do not embed the expanded public key.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PubKeyTable {
+    keys: BTreeMap<RpoDigest, PublicKey>,
+}
+
+impl PubKeyTable {
+    /// Returns a new empty [PubKeyTable].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of public keys in this table.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true if this table contains no public keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the public key with the specified commitment, if it is in this table.
+    pub fn get(&self, commitment: Word) -> Option<&PublicKey> {
+        self.keys.get(&RpoDigest::from(commitment))
+    }
+
+    /// Returns true if this table contains a public key with the specified commitment.
+    pub fn contains(&self, commitment: Word) -> bool {
+        self.keys.contains_key(&RpoDigest::from(commitment))
+    }
+
+    /// Returns an iterator over the public keys in this table ordered by their commitments.
+    pub fn iter(&self) -> impl Iterator<Item = &PublicKey> {
+        self.keys.values()
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Inserts the provided public key into this table and returns its commitment.
+    pub fn insert(&mut self, pub_key: PublicKey) -> Word {
+        let commitment = pub_key.to_commitment();
+        self.keys.insert(commitment.into(), pub_key);
+        commitment
+    }
+
+    /// Removes the public key with the specified commitment from this table and returns it, if
+    /// it was in the table.
+    pub fn remove(&mut self, commitment: Word) -> Option<PublicKey> {
+        self.keys.remove(&RpoDigest::from(commitment))
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the provided signature, which does not embed the expanded public key, is
+    /// a valid signature for the specified message against the public key with the specified
+    /// commitment.
+    ///
+    /// Returns false if this table does not contain a public key with the specified commitment.
+    pub fn verify(&self, message: Word, commitment: Word, signature: &SignatureWithoutPk) -> bool {
+        match self.get(commitment) {
+            Some(pub_key) => signature.verify_with_key(message, pub_key),
+            None => false,
+        }
+    }
+}
+
+impl FromIterator<PublicKey> for PubKeyTable {
+    fn from_iter<I: IntoIterator<Item = PublicKey>>(iter: I) -> Self {
+        let mut table = Self::new();
+        for pub_key in iter {
+            table.insert(pub_key);
+        }
+        table
     }
 }
 
@@ -49,12 +324,28 @@ impl From<PublicKey> for Word {
 
 /// A key pair (public and secret keys) for signing messages.
 ///
-/// The secret key is a byte array of length [PK_LEN].
-/// The public key is a byte array of length [SK_LEN].
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// The secret key is a byte array of length [SK_LEN].
+/// The public key is a [PublicKey] decoded from a byte array of length [PK_LEN]; thus, the public
+/// key polynomial and the commitment to the public key are computed only once when the key pair
+/// is instantiated.
+///
+/// Key generation and signing are performed by the PQClean implementation of Falcon-512 which
+/// runs in constant time with respect to the secret key: floating-point operations are emulated
+/// using integer arithmetic, and both the base Gaussian sampler and the rejection step of the
+/// sampler are implemented without secret-dependent branches or memory accesses.
+///
+/// A key pair can optionally carry the expansion of its secret key (see
+/// [KeyPair::with_expanded_key()]), which is then reused by the signing methods.
+///
+/// When the `zeroize` feature is enabled, the secret key (and its expansion) is wiped from memory
+/// when the key pair is dropped. The secret key is omitted from the [Debug](fmt::Debug) output,
+/// and key pairs are compared in constant time with respect to their secret keys.
+#[derive(Clone)]
 pub struct KeyPair {
-    public_key: PublicKeyBytes,
+    public_key: PublicKey,
     secret_key: SecretKeyBytes,
+    #[cfg(falcon_signing)]
+    expanded_key: Option<Box<[u64]>>,
 }
 
 #[allow(clippy::new_without_default)]
@@ -79,18 +370,62 @@ impl KeyPair {
         };
 
         if res == 0 {
-            Ok(Self { public_key, secret_key })
+            Self::from_raw_parts(public_key, secret_key)
         } else {
             Err(FalconError::KeyGenerationFailed)
         }
     }
 
-    /// Generates a (public_key, secret_key) key pair from the provided seed.
+    /// Generates `n` (public_key, secret_key) key pairs from OS-provided randomness.
+    ///
+    /// When the `parallel` feature is enabled, the key pairs are generated concurrently using
+    /// all available threads.
     ///
     /// # Errors
-    /// Returns an error if key generation fails.
+    /// Returns an error if generation of any of the key pairs fails.
     #[cfg(feature = "std")]
-    pub fn from_seed(seed: &NonceBytes) -> Result<Self, FalconError> {
+    pub fn new_batch(n: usize) -> Result<Vec<Self>, FalconError> {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            (0..n).into_par_iter().map(|_| Self::new()).collect()
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        (0..n).map(|_| Self::new()).collect()
+    }
+
+    /// Generates a (public_key, secret_key) key pair using the provided random number generator.
+    ///
+    /// A 32-byte seed is drawn from `rng` and the key pair is derived from it as in
+    /// [KeyPair::from_seed()]. Unlike [KeyPair::new()], this method does not rely on an OS
+    /// entropy source, and thus it is available in the `no_std` context as well, except on wasm32
+    /// targets for which the C implementation of Falcon-512 is not compiled.
+    ///
+    /// # Errors
+    /// Returns an error if key generation fails.
+    #[cfg(falcon_signing)]
+    pub fn with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Result<Self, FalconError> {
+        let mut seed = [0_u8; KEYGEN_SEED_LEN];
+        rng.fill_bytes(&mut seed);
+        let key_pair = Self::from_seed(&seed);
+
+        #[cfg(feature = "zeroize")]
+        seed.zeroize();
+
+        key_pair
+    }
+
+    /// Deterministically derives a (public_key, secret_key) key pair from the provided seed.
+    ///
+    /// The seed is used to initialize a SHAKE256-based DRBG from which the key pair is sampled,
+    /// and thus the same seed always results in the same key pair. This allows restoring a key
+    /// pair from a 32-byte seed instead of storing the full secret key.
+    ///
+    /// # Errors
+    /// Returns an error if key generation fails.
+    #[cfg(falcon_signing)]
+    pub fn from_seed(seed: &[u8; KEYGEN_SEED_LEN]) -> Result<Self, FalconError> {
         let mut public_key = [0u8; PK_LEN];
         let mut secret_key = [0u8; SK_LEN];
 
@@ -99,29 +434,162 @@ impl KeyPair {
                 public_key.as_mut_ptr(),
                 secret_key.as_mut_ptr(),
                 seed.as_ptr(),
+                seed.len(),
             )
         };
 
         if res == 0 {
-            Ok(Self { public_key, secret_key })
+            Self::from_raw_parts(public_key, secret_key)
         } else {
             Err(FalconError::KeyGenerationFailed)
         }
     }
 
+    /// Returns a key pair instantiated from a secret key in the encoding used by the reference
+    /// Falcon-512 implementation (e.g., as produced by `pqcrypto` or liboqs). The public key is
+    /// derived from the secret key.
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes are not a valid encoding of a secret key.
+    #[cfg(falcon_signing)]
+    pub fn from_falcon_bytes(bytes: &[u8]) -> Result<Self, FalconError> {
+        let secret_key: SecretKeyBytes = bytes
+            .try_into()
+            .map_err(|_| FalconError::SecKeyDecodingInvalidLength(bytes.len()))?;
+        let mut public_key = [0u8; PK_LEN];
+
+        let res = unsafe {
+            ffi::PQCLEAN_FALCON512_CLEAN_crypto_sign_public_key_rpo(
+                public_key.as_mut_ptr(),
+                secret_key.as_ptr(),
+            )
+        };
+
+        if res == 0 {
+            Self::from_raw_parts(public_key, secret_key)
+        } else {
+            Err(FalconError::SecKeyDecodingFailed)
+        }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
     /// Returns the public key corresponding to this key pair.
     pub fn public_key(&self) -> PublicKey {
-        // TODO: memoize public key commitment as computing it requires quite a bit of hashing.
-        // expect() is fine here because we assume that the key pair was constructed correctly.
-        PublicKey::new(self.public_key).expect("invalid key pair")
+        self.public_key
+    }
+
+    /// Returns the commitment to the public key of this key pair.
+    pub fn commitment(&self) -> Word {
+        self.public_key.into()
     }
 
     /// Returns the expanded public key corresponding to this key pair.
     pub fn expanded_public_key(&self) -> PublicKeyBytes {
-        self.public_key
+        self.public_key.expanded_public_key()
+    }
+
+    /// Returns the secret key of this key pair in the encoding used by the reference Falcon-512
+    /// implementation.
+    pub fn to_falcon_bytes(&self) -> SecretKeyBytes {
+        self.secret_key
+    }
+
+    /// Returns a [NonceGenerator](super::NonceGenerator) which deterministically derives the nonce and the seed of the
+    /// sampler from the secret key of this key pair and the message being signed.
+    pub fn nonce_generator(&self) -> DeterministicNonceGenerator {
+        DeterministicNonceGenerator::new(&self.secret_key)
+    }
+
+    // SECRET KEY EXPANSION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns this key pair with the expansion of its secret key cached in it.
+    ///
+    /// Producing a signature requires expanding the secret key into the B0 matrix in FFT
+    /// representation and the LDL tree of the key. Without a cached expansion, this is done for
+    /// every signature. With a cached expansion, it is reused by all signing methods except
+    /// [KeyPair::sign_prehashed()], at the cost of 56 KB of memory. The signatures do not depend
+    /// on whether the expansion is cached, and the expansion is not serialized.
+    ///
+    /// # Errors
+    /// Returns an error if the secret key cannot be expanded.
+    #[cfg(falcon_signing)]
+    pub fn with_expanded_key(mut self) -> Result<Self, FalconError> {
+        if self.expanded_key.is_none() {
+            self.expanded_key = Some(expand_secret_key(&self.secret_key)?);
+        }
+        Ok(self)
+    }
+
+    /// Returns true if the expansion of the secret key is cached in this key pair.
+    #[cfg(falcon_signing)]
+    pub fn has_expanded_key(&self) -> bool {
+        self.expanded_key.is_some()
+    }
+
+    // PASSWORD-BASED ENCRYPTION
+    // --------------------------------------------------------------------------------------------
+
+    /// Serializes this key pair into a byte vector of length [ENCRYPTED_KEY_PAIR_LEN] in which
+    /// the secret key is encrypted under a key derived from the provided password.
+    ///
+    /// The encryption key is derived from the password and a random salt using scrypt. The secret
+    /// key is then encrypted and authenticated with [RpoAead](crate::cipher::RpoAead); the public
+    /// key is stored in plaintext but is covered by the authentication tag.
+    ///
+    /// The salt and the encryption nonce are sampled from the provided random number generator.
+    /// Deriving the key is deliberately expensive and takes 32 MiB of memory.
+    pub fn to_encrypted_bytes<R: rand::Rng>(&self, password: &[u8], rng: &mut R) -> Vec<u8> {
+        let mut salt = [0_u8; SALT_LEN];
+        rng.fill_bytes(&mut salt);
+        let nonce: Word = core::array::from_fn(|_| Felt::new(rng.next_u64()));
+
+        let pk = self.expanded_public_key();
+        let ciphertext = encryption::encrypt(&self.secret_key, password, &salt, nonce, &pk);
+
+        let mut result = Vec::with_capacity(ENCRYPTED_KEY_PAIR_LEN);
+        result.extend_from_slice(&salt);
+        result.extend_from_slice(&encryption::encode_nonce(nonce));
+        result.extend_from_slice(&pk);
+        result.extend_from_slice(&ciphertext);
+        result
+    }
+
+    /// Returns a key pair decrypted from bytes produced by [KeyPair::to_encrypted_bytes()] using
+    /// the provided password.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The length of the provided bytes is not [ENCRYPTED_KEY_PAIR_LEN].
+    /// - The public key cannot be decoded.
+    /// - The password is wrong or the encrypted data was tampered with.
+    pub fn from_encrypted_bytes(bytes: &[u8], password: &[u8]) -> Result<Self, FalconError> {
+        if bytes.len() != ENCRYPTED_KEY_PAIR_LEN {
+            return Err(FalconError::SecKeyDecodingInvalidLength(bytes.len()));
+        }
+
+        let (salt, rest) = bytes.split_at(SALT_LEN);
+        let (nonce, rest) = rest.split_at(ENC_NONCE_LEN);
+        let (pk, ciphertext) = rest.split_at(PK_LEN);
+
+        // the lengths were checked above, and thus the conversions below cannot fail
+        let salt: &[u8; SALT_LEN] = salt.try_into().expect("invalid salt length");
+        let nonce = encryption::decode_nonce(nonce.try_into().expect("invalid nonce length"))?;
+
+        let secret_key = encryption::decrypt(ciphertext, password, salt, nonce, pk)?;
+        let public_key = PublicKey::new(pk.try_into().expect("invalid public key length"))?;
+        Ok(Self::from_keys(public_key, secret_key))
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Verifies the provided signature against provided message and the public key of this key
+    /// pair.
+    pub fn verify(&self, message: Word, signature: &Signature) -> bool {
+        self.public_key.verify(message, signature)
     }
 
     // SIGNATURE GENERATION
@@ -130,63 +598,756 @@ impl KeyPair {
     /// Signs a message with a secret key and a seed.
     ///
     /// # Errors
-    /// Returns an error of signature generation fails.
+    /// Returns an error if signature generation fails.
     #[cfg(feature = "std")]
     pub fn sign(&self, message: Word) -> Result<Signature, FalconError> {
-        let msg = message.iter().flat_map(|e| e.as_int().to_le_bytes()).collect::<Vec<_>>();
-        let msg_len = msg.len();
+        self.sign_message(&[message])
+    }
+
+    /// Signs a byte message with a secret key and a seed.
+    ///
+    /// The message is first hashed into a [Word] using [Rpo256::hash()], and the resulting
+    /// signature can be verified via [PublicKey::verify_bytes()].
+    ///
+    /// # Errors
+    /// Returns an error if signature generation fails.
+    #[cfg(feature = "std")]
+    pub fn sign_bytes(&self, message: &[u8]) -> Result<Signature, FalconError> {
+        self.sign(Rpo256::hash(message).into())
+    }
+
+    /// Signs a message consisting of field elements with a secret key and a seed.
+    ///
+    /// The message is first hashed into a [Word] using [Rpo256::hash_elements()], and the
+    /// resulting signature can be verified via [PublicKey::verify_elements()].
+    ///
+    /// # Errors
+    /// Returns an error if signature generation fails.
+    #[cfg(feature = "std")]
+    pub fn sign_elements(&self, message: &[Felt]) -> Result<Signature, FalconError> {
+        self.sign(Rpo256::hash_elements(message).into())
+    }
+
+    /// Signs a message consisting of multiple words with a secret key and a seed.
+    ///
+    /// The words are absorbed into the hash-to-point sponge sequentially rather than being
+    /// hashed into a single [Word] first, and the resulting signature can be verified via
+    /// [PublicKey::verify_words()]. Signing a message consisting of a single word is equivalent
+    /// to [KeyPair::sign()].
+    ///
+    /// # Errors
+    /// Returns an error if the message is empty or if signature generation fails.
+    #[cfg(feature = "std")]
+    pub fn sign_words(&self, message: &[Word]) -> Result<Signature, FalconError> {
+        if message.is_empty() {
+            return Err(FalconError::SigGenerationEmptyMessage);
+        }
+        self.sign_message(message)
+    }
+
+    /// Signs a message with a secret key, drawing the nonce and the seed of the sampler from the
+    /// provided random number generator.
+    ///
+    /// Signing is deterministic given the state of `rng`, which makes this method suitable for
+    /// producing reproducible signatures in tests. Outside of tests, `rng` must be a
+    /// cryptographically secure random number generator.
+    ///
+    /// Unlike [KeyPair::sign()], this method does not rely on an OS entropy source, and thus it is
+    /// available in the `no_std` context as well, except on wasm32 targets for which the C
+    /// implementation of Falcon-512 is not compiled.
+    ///
+    /// # Errors
+    /// Returns an error if signature generation fails.
+    #[cfg(falcon_signing)]
+    pub fn sign_with_rng<R: Rng>(
+        &self,
+        message: Word,
+        rng: &mut R,
+    ) -> Result<Signature, FalconError> {
+        self.sign_with_nonce_generator(message, &mut RandomNonceGenerator::new(rng))
+    }
+
+    /// Signs a message with a secret key using the provided nonce.
+    ///
+    /// This allows fixing the nonce explicitly, e.g., for commit-reveal protocols or for test
+    /// vectors. The seed of the sampler is derived from the secret key, the nonce and the
+    /// message, and thus signing the same message with the same nonce always produces the same
+    /// signature.
+    ///
+    /// The nonce should still be unique per message: while reusing a nonce for distinct messages
+    /// does not leak the secret key, it allows an adversary who can choose the messages to mount
+    /// collision attacks against the hash-to-point function and makes signatures linkable. Nonces
+    /// must never be chosen by an untrusted party.
+    ///
+    /// # Errors
+    /// Returns an error if signature generation fails.
+    #[cfg(falcon_signing)]
+    pub fn sign_with_nonce(&self, message: Word, nonce: Nonce) -> Result<Signature, FalconError> {
+        let mut generator = FixedNonceGenerator::new(&self.secret_key, nonce);
+        self.sign_with_nonce_generator(message, &mut generator)
+    }
+
+    /// Signs a message which was already hashed to a point `c` using the provided nonce.
+    ///
+    /// This allows the hash-to-point to be computed by a party other than the one holding the
+    /// secret key. The caller is responsible for `c` being the hash-to-point of the message and
+    /// `nonce` (e.g., as computed by [Nonce::hash_to_point()](super::Nonce::hash_to_point())),
+    /// as the nonce is only embedded into the resulting signature.
+    ///
+    /// The seed of the sampler is derived from the secret key, `c` and `nonce`, and thus signing
+    /// the same point with the same nonce always produces the same signature. This ensures that
+    /// two distinct signatures of the same point, which would leak information about the secret
+    /// key, are never produced.
+    ///
+    /// # Errors
+    /// Returns an error if signature generation fails.
+    #[cfg(falcon_signing)]
+    pub fn sign_prehashed(&self, c: Polynomial, nonce: Nonce) -> Result<Signature, FalconError> {
         let mut sig = [0_u8; SIG_LEN + NONCE_LEN];
         let mut sig_len: usize = 0;
 
+        #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+        let mut seed = FixedNonceGenerator::new(&self.secret_key, nonce).prehashed_seed(&c);
+
         let res = unsafe {
-            ffi::PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_rpo(
+            ffi::PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_prehashed_from_seed_rpo(
                 sig.as_mut_ptr(),
                 &mut sig_len as *mut usize,
-                msg.as_ptr(),
-                msg_len,
+                c.inner().as_ptr(),
                 self.secret_key.as_ptr(),
+                nonce.as_bytes().as_ptr(),
+                seed.as_ptr(),
             )
         };
 
+        #[cfg(feature = "zeroize")]
+        seed.zeroize();
+
         if res == 0 {
-            Ok(Signature {
-                sig,
-                pk: self.public_key,
-                pk_polynomial: Default::default(),
-                sig_polynomial: Default::default(),
-            })
+            Ok(build_signature(&self.public_key, sig))
         } else {
             Err(FalconError::SigGenerationFailed)
         }
     }
-}
 
-// SERIALIZATION / DESERIALIZATION
-// ================================================================================================
+    /// Signs a message with a secret key, deriving the nonce and the seed of the sampler from the
+    /// secret key and the message.
+    ///
+    /// Signing the same message with the same key pair always produces the same signature. This
+    /// is equivalent to signing with the generator returned by [KeyPair::nonce_generator()].
+    ///
+    /// # Errors
+    /// Returns an error if signature generation fails.
+    #[cfg(falcon_signing)]
+    pub fn sign_deterministic(&self, message: Word) -> Result<Signature, FalconError> {
+        self.sign_with_nonce_generator(message, &mut self.nonce_generator())
+    }
 
-impl Serializable for KeyPair {
-    fn write_into<W: ByteWriter>(&self, target: &mut W) {
-        target.write_bytes(&self.public_key);
-        target.write_bytes(&self.secret_key);
+    /// Signs a message with a secret key, using the nonce and the seed of the sampler produced by
+    /// the provided [NonceGenerator].
+    ///
+    /// # Errors
+    /// Returns an error if the generator cannot produce a nonce, or if signature generation
+    /// fails.
+    #[cfg(falcon_signing)]
+    pub fn sign_with_nonce_generator<G: NonceGenerator>(
+        &self,
+        message: Word,
+        generator: &mut G,
+    ) -> Result<Signature, FalconError> {
+        sign_message_with_generator(
+            &self.public_key,
+            message,
+            generator,
+            |sig, sig_len, msg, nonce, seed| unsafe {
+                match &self.expanded_key {
+                    Some(expanded_key) => {
+                        ffi::PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_expanded_from_seed_rpo(
+                            sig.as_mut_ptr(),
+                            sig_len as *mut usize,
+                            msg.as_ptr(),
+                            msg.len(),
+                            expanded_key.as_ptr(),
+                            nonce.as_bytes().as_ptr(),
+                            seed.as_ptr(),
+                        )
+                    }
+                    None => ffi::PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_from_seed_rpo(
+                        sig.as_mut_ptr(),
+                        sig_len as *mut usize,
+                        msg.as_ptr(),
+                        msg.len(),
+                        self.secret_key.as_ptr(),
+                        nonce.as_bytes().as_ptr(),
+                        seed.as_ptr(),
+                    ),
+                }
+            },
+        )
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Signs a message consisting of one or more words with a secret key and a seed.
+    #[cfg(feature = "std")]
+    fn sign_message(&self, message: &[Word]) -> Result<Signature, FalconError> {
+        sign_message(&self.public_key, message, |sig, sig_len, msg| unsafe {
+            match &self.expanded_key {
+                Some(expanded_key) => {
+                    ffi::PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_expanded_rpo(
+                        sig.as_mut_ptr(),
+                        sig_len as *mut usize,
+                        msg.as_ptr(),
+                        msg.len(),
+                        expanded_key.as_ptr(),
+                    )
+                }
+                None => ffi::PQCLEAN_FALCON512_CLEAN_crypto_sign_signature_rpo(
+                    sig.as_mut_ptr(),
+                    sig_len as *mut usize,
+                    msg.as_ptr(),
+                    msg.len(),
+                    self.secret_key.as_ptr(),
+                ),
+            }
+        })
+    }
+
+    /// Returns a key pair instantiated from the provided expanded public key and secret key.
+    ///
+    /// # Errors
+    /// Returns an error if the expanded public key cannot be decoded.
+    #[cfg(falcon_signing)]
+    fn from_raw_parts(
+        public_key: PublicKeyBytes,
+        secret_key: SecretKeyBytes,
+    ) -> Result<Self, FalconError> {
+        let public_key = PublicKey::new(public_key)?;
+        Ok(Self::from_keys(public_key, secret_key))
+    }
+
+    /// Returns a key pair consisting of the provided keys, without a cached expansion of the
+    /// secret key.
+    fn from_keys(public_key: PublicKey, secret_key: SecretKeyBytes) -> Self {
+        Self {
+            public_key,
+            secret_key,
+            #[cfg(falcon_signing)]
+            expanded_key: None,
+        }
     }
 }
 
-impl Deserializable for KeyPair {
-    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
-        let public_key: PublicKeyBytes = source.read_array()?;
-        let secret_key: SecretKeyBytes = source.read_array()?;
-        Ok(Self { public_key, secret_key })
+impl PartialEq for KeyPair {
+    fn eq(&self, other: &Self) -> bool {
+        // the public keys are not secret, and thus only the secret keys are compared in constant
+        // time
+        self.public_key == other.public_key && bool::from(self.secret_key.ct_eq(&other.secret_key))
     }
 }
 
-// TESTS
+impl fmt::Debug for KeyPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the secret key is not included in the output
+        f.debug_struct("KeyPair")
+            .field("public_key", &self.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+// KEY GENERATION BUILDER
 // ================================================================================================
 
-#[cfg(all(test, feature = "std"))]
-mod tests {
-    use rand_utils::{rand_array, rand_vector};
+/// A token which can be used to cancel key generation performed by a [KeygenBuilder] from
+/// another thread.
+///
+/// Clones of a token share the same state, i.e., cancelling a clone cancels the original token.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+#[cfg(feature = "std")]
+impl CancellationToken {
+    /// Returns a new [CancellationToken] which has not been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels key generation using this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    /// Returns true if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+}
+
+/// A builder for generating one or more key pairs with progress reporting and cancellation.
+///
+/// Progress is reported per key pair: the callback is invoked before the generation of the first
+/// key pair starts and after each key pair is generated.
+///
+/// Generation of a single key pair is performed by the C implementation of Falcon-512, which
+/// cannot be interrupted. Thus, when a cancellation token is set, every key pair is generated on
+/// a worker thread while the token is polled every few milliseconds, and [KeygenBuilder::generate()]
+/// returns as soon as the token is cancelled. The key pair which was being generated at that
+/// point is discarded once its worker thread completes.
+#[cfg(feature = "std")]
+pub struct KeygenBuilder<'a> {
+    count: usize,
+    on_progress: Option<Box<dyn FnMut(usize, usize) + 'a>>,
+    cancellation_token: Option<CancellationToken>,
+}
+
+#[cfg(feature = "std")]
+impl<'a> KeygenBuilder<'a> {
+    /// Returns a new [KeygenBuilder] for generating a single key pair.
+    pub fn new() -> Self {
+        Self {
+            count: 1,
+            on_progress: None,
+            cancellation_token: None,
+        }
+    }
+
+    /// Sets the number of key pairs to generate.
+    pub fn count(mut self, count: usize) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Sets the callback to be invoked with the number of generated key pairs and the total
+    /// number of key pairs to generate, first before any key pair is generated and then after
+    /// each generated key pair.
+    pub fn on_progress<F: FnMut(usize, usize) + 'a>(mut self, on_progress: F) -> Self {
+        self.on_progress = Some(Box::new(on_progress));
+        self
+    }
+
+    /// Sets the token which can be used to cancel key generation.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Generates the key pairs from OS-provided randomness.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Key generation has been cancelled; key pairs generated before the cancellation are
+    ///   discarded.
+    /// - Generation of any of the key pairs fails.
+    pub fn generate(mut self) -> Result<Vec<KeyPair>, FalconError> {
+        let mut keys = Vec::with_capacity(self.count);
+        loop {
+            if let Some(on_progress) = self.on_progress.as_mut() {
+                on_progress(keys.len(), self.count);
+            }
+            if keys.len() == self.count {
+                return Ok(keys);
+            }
+            if self.cancellation_token.as_ref().is_some_and(|token| token.is_cancelled()) {
+                return Err(FalconError::KeyGenerationCancelled);
+            }
+            keys.push(self.generate_key_pair()?);
+        }
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Interval at which the cancellation token is polled while a key pair is being generated.
+    const CANCELLATION_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+    /// Generates a single key pair, returning early if the cancellation token is cancelled while
+    /// the key pair is being generated.
+    fn generate_key_pair(&self) -> Result<KeyPair, FalconError> {
+        let token = match self.cancellation_token.as_ref() {
+            Some(token) => token,
+            None => return KeyPair::new(),
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            // sending fails only if generation has been cancelled, in which case the key pair
+            // is dropped here
+            let _ = sender.send(KeyPair::new());
+        });
+
+        loop {
+            match receiver.recv_timeout(Self::CANCELLATION_POLL_INTERVAL) {
+                Ok(result) => return result,
+                Err(RecvTimeoutError::Timeout) if token.is_cancelled() => {
+                    return Err(FalconError::KeyGenerationCancelled)
+                }
+                Err(RecvTimeoutError::Timeout) => continue,
+                // the worker thread panicked before producing a key pair
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(FalconError::KeyGenerationFailed)
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for KeygenBuilder<'_> {
+    /// Returns a new [KeygenBuilder] for generating a single key pair, i.e., the same builder as
+    /// [KeygenBuilder::new()].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for KeygenBuilder<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeygenBuilder")
+            .field("count", &self.count)
+            .field("cancellation_token", &self.cancellation_token)
+            .finish_non_exhaustive()
+    }
+}
+
+// SIGNING CONTEXT
+// ================================================================================================
+
+/// A context for signing many messages with the same secret key.
+///
+/// Producing a signature requires expanding the secret key into the B0 matrix in FFT
+/// representation and the LDL tree of the key. [KeyPair::sign()] performs this expansion for
+/// every signature, while the signing context performs it only once when it is instantiated. The
+/// expanded key occupies 56 KB of memory.
+///
+/// A signing context is a key pair which always carries the expansion of its secret key (see
+/// [KeyPair::with_expanded_key()]). When the `zeroize` feature is enabled, the secret key and its
+/// expansion are wiped from memory when the context is dropped.
+#[cfg(falcon_signing)]
+#[derive(Clone)]
+pub struct SigningContext {
+    key_pair: KeyPair,
+}
+
+#[cfg(falcon_signing)]
+impl SigningContext {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [SigningContext] for signing messages with the secret key of the provided
+    /// key pair.
+    ///
+    /// # Errors
+    /// Returns an error if the secret key cannot be expanded.
+    pub fn new(key_pair: &KeyPair) -> Result<Self, FalconError> {
+        Ok(Self {
+            key_pair: key_pair.clone().with_expanded_key()?,
+        })
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public key corresponding to the secret key of this context.
+    pub fn public_key(&self) -> PublicKey {
+        self.key_pair.public_key
+    }
+
+    /// Returns the key pair of this context, which carries the expansion of its secret key.
+    pub fn key_pair(&self) -> &KeyPair {
+        &self.key_pair
+    }
+
+    // SIGNATURE GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Signs a message using the expanded secret key of this context.
+    ///
+    /// # Errors
+    /// Returns an error if signature generation fails.
+    #[cfg(feature = "std")]
+    pub fn sign(&self, message: Word) -> Result<Signature, FalconError> {
+        self.key_pair.sign(message)
+    }
+
+    /// Signs a message using the expanded secret key of this context, drawing the nonce and the
+    /// seed of the sampler from the provided random number generator.
+    ///
+    /// Given the same state of `rng`, this produces the same signature as
+    /// [KeyPair::sign_with_rng()].
+    ///
+    /// # Errors
+    /// Returns an error if signature generation fails.
+    pub fn sign_with_rng<R: Rng>(
+        &self,
+        message: Word,
+        rng: &mut R,
+    ) -> Result<Signature, FalconError> {
+        self.key_pair.sign_with_rng(message, rng)
+    }
+
+    /// Signs a message using the expanded secret key of this context, using the nonce and the
+    /// seed of the sampler produced by the provided [NonceGenerator].
+    ///
+    /// Given the same generator state, this produces the same signature as
+    /// [KeyPair::sign_with_nonce_generator()].
+    ///
+    /// # Errors
+    /// Returns an error if the generator cannot produce a nonce, or if signature generation
+    /// fails.
+    pub fn sign_with_nonce_generator<G: NonceGenerator>(
+        &self,
+        message: Word,
+        generator: &mut G,
+    ) -> Result<Signature, FalconError> {
+        self.key_pair.sign_with_nonce_generator(message, generator)
+    }
+}
+
+#[cfg(falcon_signing)]
+impl fmt::Debug for SigningContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the expanded key is secret and thus is not included in the output
+        f.debug_struct("SigningContext")
+            .field("public_key", &self.key_pair.public_key)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(all(falcon_signing, feature = "zeroize"))]
+impl ZeroizeOnDrop for SigningContext {}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for KeyPair {
+    fn zeroize(&mut self) {
+        self.secret_key.zeroize();
+        #[cfg(falcon_signing)]
+        self.expanded_key.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for KeyPair {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for KeyPair {}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for PublicKey {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.pk);
+    }
+}
+
+impl Deserializable for PublicKey {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let pk: PublicKeyBytes = source.read_array()?;
+        Self::new(pk).map_err(DeserializationError::from)
+    }
+}
+
+impl Serializable for PubKeyTable {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.keys.len() as u64);
+        for pub_key in self.keys.values() {
+            pub_key.write_into(target);
+        }
+    }
+}
+
+impl Deserializable for PubKeyTable {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let len = source.read_u64()?;
+        let mut table = Self::new();
+        for _ in 0..len {
+            table.insert(PublicKey::read_from(source)?);
+        }
+        Ok(table)
+    }
+}
+
+impl Serializable for KeyPair {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.public_key.write_into(target);
+        target.write_bytes(&self.secret_key);
+    }
+}
+
+impl Deserializable for KeyPair {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let public_key = PublicKey::read_from(source)?;
+        let secret_key: SecretKeyBytes = source.read_array()?;
+        Ok(Self::from_keys(public_key, secret_key))
+    }
+}
+
+/// The context is serialized as its [KeyPair] followed by the expanded secret key, which is a
+/// sequence of 7168 little-endian 64-bit words, i.e., the serialized context is about 59 KB in
+/// size compared to about 2 KB for a [KeyPair].
+///
+/// When a context is deserialized, the public key is derived from the secret key and the secret
+/// key is expanded again; the context is rejected unless both match the deserialized values.
+#[cfg(falcon_signing)]
+impl Serializable for SigningContext {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.key_pair.write_into(target);
+        let expanded_key = self.key_pair.expanded_key.as_ref().expect("key is not expanded");
+        for &word in expanded_key.iter() {
+            target.write_u64(word);
+        }
+    }
+
+    fn get_size_hint(&self) -> usize {
+        PK_LEN + SK_LEN + EXPANDED_KEY_WORDS * 8
+    }
+}
+
+#[cfg(falcon_signing)]
+impl Deserializable for SigningContext {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let key_pair = KeyPair::read_from(source)?;
+        let mut expanded_key = vec![0_u64; EXPANDED_KEY_WORDS].into_boxed_slice();
+        for word in expanded_key.iter_mut() {
+            *word = source.read_u64()?;
+        }
+
+        let mut public_key = [0_u8; PK_LEN];
+        let res = unsafe {
+            ffi::PQCLEAN_FALCON512_CLEAN_crypto_sign_public_key_rpo(
+                public_key.as_mut_ptr(),
+                key_pair.secret_key.as_ptr(),
+            )
+        };
+        if res != 0 || public_key != key_pair.expanded_public_key() {
+            return Err(DeserializationError::InvalidValue(
+                "public key does not match the secret key".into(),
+            ));
+        }
+
+        let key_pair = key_pair.with_expanded_key().map_err(DeserializationError::from)?;
+        let expected = key_pair.expanded_key.as_ref().expect("key is not expanded");
+        if !bool::from(expected.ct_eq(&expanded_key)) {
+            return Err(DeserializationError::InvalidValue(
+                "expanded secret key does not match the secret key".into(),
+            ));
+        }
+
+        #[cfg(feature = "zeroize")]
+        expanded_key.zeroize();
+
+        Ok(Self { key_pair })
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Signs the provided message consisting of multiple words using the provided signing routine of
+/// the C implementation, and wraps the result into a [Signature] embedding the provided public
+/// key.
+///
+/// The signing routine is invoked with the buffer for the signature, the length of the signature
+/// to be written, and the encoded message, and returns 0 if signing succeeded.
+#[cfg(falcon_signing)]
+fn sign_message(
+    public_key: &PublicKey,
+    message: &[Word],
+    sign: impl FnOnce(&mut SignatureBytes, &mut usize, &[u8]) -> c_int,
+) -> Result<Signature, FalconError> {
+    let msg = message
+        .iter()
+        .flatten()
+        .flat_map(|e| e.as_int().to_le_bytes())
+        .collect::<Vec<_>>();
+    let mut sig = [0_u8; SIG_LEN + NONCE_LEN];
+    let mut sig_len: usize = 0;
+
+    if sign(&mut sig, &mut sig_len, &msg) == 0 {
+        Ok(build_signature(public_key, sig))
+    } else {
+        Err(FalconError::SigGenerationFailed)
+    }
+}
+
+/// Signs the provided message as in [sign_message()], additionally passing the nonce and the seed
+/// of the sampler produced by the provided [NonceGenerator] to the signing routine.
+///
+/// The seed is zeroized once the signature has been generated.
+#[cfg(falcon_signing)]
+fn sign_message_with_generator<G: NonceGenerator>(
+    public_key: &PublicKey,
+    message: Word,
+    generator: &mut G,
+    sign: impl FnOnce(&mut SignatureBytes, &mut usize, &[u8], &Nonce, &[u8; SIGNING_SEED_LEN]) -> c_int,
+) -> Result<Signature, FalconError> {
+    #[cfg_attr(not(feature = "zeroize"), allow(unused_mut))]
+    let (nonce, mut seed) = generator.generate(&[message])?;
+
+    let signature = sign_message(public_key, &[message], |sig, sig_len, msg| {
+        sign(sig, sig_len, msg, &nonce, &seed)
+    });
+
+    #[cfg(feature = "zeroize")]
+    seed.zeroize();
+
+    signature
+}
+
+/// Expands the provided secret key into the B0 matrix in FFT representation and the LDL tree of
+/// the key.
+#[cfg(falcon_signing)]
+fn expand_secret_key(secret_key: &SecretKeyBytes) -> Result<Box<[u64]>, FalconError> {
+    let mut expanded_key = vec![0_u64; EXPANDED_KEY_WORDS].into_boxed_slice();
+
+    let res = unsafe {
+        ffi::PQCLEAN_FALCON512_CLEAN_crypto_sign_expand_private_key_rpo(
+            expanded_key.as_mut_ptr(),
+            secret_key.as_ptr(),
+        )
+    };
+
+    if res == 0 {
+        Ok(expanded_key)
+    } else {
+        Err(FalconError::SecKeyDecodingFailed)
+    }
+}
+
+/// Wraps the raw signature bytes produced with the secret key corresponding to the provided
+/// public key into a [Signature].
+#[cfg(falcon_signing)]
+fn build_signature(public_key: &PublicKey, sig: SignatureBytes) -> Signature {
+    let mut buffer = [0_u8; SIG_BUFFER_LEN];
+    buffer[..sig.len()].copy_from_slice(&sig);
+    SignatureWithoutPk {
+        sig: buffer,
+        sig_polynomial: Default::default(),
+    }
+    .with_pk(public_key)
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use alloc::vec::Vec;
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+    use rand_utils::{rand_array, rand_vector};
 
-    use super::{super::Felt, KeyPair, NonceBytes, Word};
+    use super::{
+        super::{CounterNonceGenerator, Felt, Nonce, Signature, SignatureWithoutPk},
+        CancellationToken, Deserializable, FalconError, KeyPair, KeygenBuilder, PubKeyTable,
+        PublicKey, Serializable, SigningContext, VerifierKey, Word, ENCRYPTED_KEY_PAIR_LEN,
+    };
 
     #[test]
     fn test_falcon_verification() {
@@ -213,10 +1374,18 @@ mod tests {
     #[test]
     fn test_falcon_verification_from_seed() {
         // generate keys from a random seed
-        let seed: NonceBytes = rand_array();
+        let seed: [u8; 32] = rand_array();
         let keys = KeyPair::from_seed(&seed).unwrap();
         let pk = keys.public_key();
 
+        // the same seed should always produce the same keys
+        assert_eq!(keys, KeyPair::from_seed(&seed).unwrap());
+
+        // seeds which differ only in their last byte should produce different keys
+        let mut seed2 = seed;
+        seed2[31] ^= 1;
+        assert_ne!(keys, KeyPair::from_seed(&seed2).unwrap());
+
         // sign a random message
         let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
         let signature = keys.sign(message);
@@ -232,4 +1401,493 @@ mod tests {
         let keys2 = KeyPair::new().unwrap();
         assert!(!keys2.public_key().verify(message, signature.as_ref().unwrap()))
     }
+
+    #[test]
+    fn test_falcon_verification_without_pk() {
+        let keys = KeyPair::new().unwrap();
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        let signature = keys.sign(message).unwrap();
+
+        // serialize the public key and the signature separately
+        let pk_bytes = keys.public_key().to_bytes();
+        let sig_bytes = signature.without_pk().to_bytes();
+        assert_eq!(signature.to_bytes().len(), pk_bytes.len() + sig_bytes.len());
+        assert_eq!(sig_bytes.len(), 666);
+
+        let pk = PublicKey::read_from_bytes(&pk_bytes).unwrap();
+        let sig = SignatureWithoutPk::read_from_bytes(&sig_bytes).unwrap();
+        assert_eq!(pk, keys.public_key());
+
+        // make sure the signature verifies correctly
+        assert!(sig.verify_with_key(message, &pk));
+        assert!(pk.verify_without_pk(message, &sig));
+        assert!(pk.verify(message, &Signature::read_from_bytes(&signature.to_bytes()).unwrap()));
+
+        // a signature should not verify against a wrong message
+        let message2: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        assert!(!sig.verify_with_key(message2, &pk));
+
+        // a signature should not verify against a wrong public key
+        let keys2 = KeyPair::new().unwrap();
+        assert!(!sig.verify_with_key(message, &keys2.public_key()));
+
+        // re-attaching the public key should restore the full signature
+        let restored = sig.clone().with_pk(&pk);
+        assert_eq!(restored.to_bytes(), signature.to_bytes());
+        assert!(pk.verify(message, &restored));
+        assert!(!keys2.public_key().verify(message, &sig.with_pk(&keys2.public_key())));
+        assert_eq!(SignatureWithoutPk::from(signature).to_bytes(), sig_bytes);
+    }
+
+    #[test]
+    fn test_falcon_multi_word_messages() {
+        let keys = KeyPair::new().unwrap();
+        let pk = keys.public_key();
+        let words: Vec<Word> = (0..5).map(|_| rand_array::<Felt, 4>()).collect();
+
+        for len in 1..=words.len() {
+            let message = &words[..len];
+            let signature = keys.sign_words(message).unwrap();
+            assert!(pk.verify_words(message, &signature));
+            assert!(signature.without_pk().verify_words_with_key(message, &pk));
+
+            // the signature should not verify against a prefix or an extension of the message
+            assert!(!pk.verify_words(&words[..len - 1], &signature));
+            if len < words.len() {
+                assert!(!pk.verify_words(&words[..len + 1], &signature));
+            }
+        }
+
+        // a single-word message is equivalent to signing the word directly
+        let signature = keys.sign_words(&words[..1]).unwrap();
+        assert!(pk.verify(words[0], &signature));
+
+        assert_eq!(keys.sign_words(&[]).unwrap_err(), FalconError::SigGenerationEmptyMessage);
+    }
+
+    #[test]
+    fn test_falcon_sign_with_rng() {
+        let keys = KeyPair::new().unwrap();
+        let pk = keys.public_key();
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+
+        // signing with identically seeded RNGs should produce identical signatures
+        let seed: [u8; 32] = rand_array();
+        let sig1 = keys.sign_with_rng(message, &mut ChaCha20Rng::from_seed(seed)).unwrap();
+        let sig2 = keys.sign_with_rng(message, &mut ChaCha20Rng::from_seed(seed)).unwrap();
+        assert_eq!(sig1.to_bytes(), sig2.to_bytes());
+        assert!(pk.verify(message, &sig1));
+
+        // signing with a differently seeded RNG should produce a different signature
+        let mut rng = ChaCha20Rng::from_seed(rand_array());
+        let sig3 = keys.sign_with_rng(message, &mut rng).unwrap();
+        assert_ne!(sig1.to_bytes(), sig3.to_bytes());
+        assert!(pk.verify(message, &sig3));
+    }
+
+    #[test]
+    fn test_falcon_sign_with_nonce_generator() {
+        let keys = KeyPair::new().unwrap();
+        let pk = keys.public_key();
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+
+        // deterministic signing should be reproducible and depend on the message
+        let sig1 = keys.sign_deterministic(message).unwrap();
+        let sig2 = keys.sign_with_nonce_generator(message, &mut keys.nonce_generator()).unwrap();
+        assert_eq!(sig1.to_bytes(), sig2.to_bytes());
+        assert!(pk.verify(message, &sig1));
+
+        let mut other_message = message;
+        other_message[0] += Felt::new(1);
+        let sig3 = keys.sign_deterministic(other_message).unwrap();
+        assert_ne!(sig1.nonce(), sig3.nonce());
+        assert!(pk.verify(other_message, &sig3));
+
+        // a signature produced with a counter generator should embed the counter as its nonce
+        let mut generator = CounterNonceGenerator::new(7, ChaCha20Rng::from_seed(rand_array()));
+        let sig4 = keys.sign_with_nonce_generator(message, &mut generator).unwrap();
+        assert_eq!(generator.counter(), 8);
+        let mut nonce = [0_u8; 40];
+        nonce[0] = 7;
+        assert_eq!(sig4.nonce(), Nonce::new(nonce).to_elements());
+        assert!(pk.verify(message, &sig4));
+
+        // an exhausted counter generator should be reported as an error
+        let mut generator =
+            CounterNonceGenerator::new(u64::MAX, ChaCha20Rng::from_seed(rand_array()));
+        assert_eq!(
+            keys.sign_with_nonce_generator(message, &mut generator).unwrap_err(),
+            FalconError::NonceGenerationExhausted
+        );
+        assert_eq!(generator.counter(), u64::MAX);
+    }
+
+    #[test]
+    fn test_falcon_sign_with_nonce() {
+        let keys = KeyPair::new().unwrap();
+        let pk = keys.public_key();
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+
+        // signing with a fixed nonce should embed the nonce and be reproducible
+        let nonce = Nonce::new(rand_array());
+        let sig1 = keys.sign_with_nonce(message, nonce).unwrap();
+        let sig2 = keys.sign_with_nonce(message, nonce).unwrap();
+        assert_eq!(sig1.nonce(), nonce.to_elements());
+        assert_eq!(sig1.to_bytes(), sig2.to_bytes());
+        assert!(pk.verify(message, &sig1));
+
+        // a different nonce should result in a different signature
+        let sig3 = keys.sign_with_nonce(message, Nonce::new(rand_array())).unwrap();
+        assert_ne!(sig1.to_bytes(), sig3.to_bytes());
+        assert!(pk.verify(message, &sig3));
+    }
+
+    #[test]
+    fn test_falcon_sign_prehashed() {
+        let keys = KeyPair::new().unwrap();
+        let pk = keys.public_key();
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+
+        // a signature of a pre-hashed message should verify against the original message
+        let nonce = Nonce::new(rand_array());
+        let c = nonce.hash_to_point(message);
+        let signature = keys.sign_prehashed(c, nonce).unwrap();
+        assert_eq!(signature.nonce(), nonce.to_elements());
+        assert!(pk.verify(message, &signature));
+        assert!(pk.verify_prehashed(c, &signature));
+
+        // signing the same point with the same nonce should be deterministic
+        assert_eq!(keys.sign_prehashed(c, nonce).unwrap().to_bytes(), signature.to_bytes());
+
+        // the signature should not verify against a point computed with another nonce
+        let c2 = Nonce::new(rand_array()).hash_to_point(message);
+        assert!(!pk.verify_prehashed(c2, &signature));
+    }
+
+    #[test]
+    fn test_falcon_key_generation_with_rng() {
+        // identically seeded RNGs should produce identical key pairs
+        let seed: [u8; 32] = rand_array();
+        let keys1 = KeyPair::with_rng(&mut ChaCha20Rng::from_seed(seed)).unwrap();
+        let keys2 = KeyPair::with_rng(&mut ChaCha20Rng::from_seed(seed)).unwrap();
+        assert_eq!(keys1, keys2);
+
+        let keys3 = KeyPair::with_rng(&mut ChaCha20Rng::from_seed(rand_array())).unwrap();
+        assert_ne!(keys1.public_key(), keys3.public_key());
+
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        assert!(keys1.verify(message, &keys1.sign(message).unwrap()));
+    }
+
+    #[test]
+    fn test_keygen_builder() {
+        let mut progress = Vec::new();
+        let keys = KeygenBuilder::new()
+            .count(2)
+            .on_progress(|done, total| progress.push((done, total)))
+            .generate()
+            .unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(progress, [(0, 2), (1, 2), (2, 2)]);
+
+        // a cancelled token should stop key generation
+        let token = CancellationToken::new();
+        let mut progress = Vec::new();
+        let result = KeygenBuilder::new()
+            .count(3)
+            .cancellation_token(token.clone())
+            .on_progress(|done, _| {
+                progress.push(done);
+                if done == 1 {
+                    token.cancel();
+                }
+            })
+            .generate();
+        assert_eq!(result, Err(FalconError::KeyGenerationCancelled));
+        assert_eq!(progress, [0, 1]);
+
+        // cancelling the token while a key pair is being generated should stop key generation
+        // without waiting for the key pair
+        let token = CancellationToken::new();
+        let canceller = {
+            let token = token.clone();
+            std::thread::spawn(move || token.cancel())
+        };
+        let result = KeygenBuilder::new().count(10_000).cancellation_token(token).generate();
+        canceller.join().unwrap();
+        assert_eq!(result, Err(FalconError::KeyGenerationCancelled));
+
+        // the default builder should generate a single key pair
+        assert_eq!(KeygenBuilder::default().generate().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_falcon_new_batch() {
+        let keys = KeyPair::new_batch(4).unwrap();
+        assert_eq!(keys.len(), 4);
+
+        // all key pairs should be distinct and usable for signing
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        for (i, key) in keys.iter().enumerate() {
+            assert!(keys[i + 1..].iter().all(|other| other.public_key() != key.public_key()));
+            assert!(key.public_key().verify(message, &key.sign(message).unwrap()));
+        }
+        assert!(KeyPair::new_batch(0).unwrap().is_empty());
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_falcon_key_pair_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut keys = KeyPair::new().unwrap();
+        assert!(keys.secret_key.iter().any(|&b| b != 0));
+
+        keys.zeroize();
+        assert!(keys.secret_key.iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_falcon_verification_of_arbitrary_messages() {
+        let keys = KeyPair::new().unwrap();
+        let pk = keys.public_key();
+
+        // sign and verify a byte message
+        let message: Vec<u8> = rand_vector(100);
+        let signature = keys.sign_bytes(&message).unwrap();
+        assert!(pk.verify_bytes(&message, &signature));
+        assert!(!pk.verify_bytes(&message[1..], &signature));
+
+        // sign and verify a message consisting of field elements
+        let message: Vec<Felt> = rand_vector(10);
+        let signature = keys.sign_elements(&message).unwrap();
+        assert!(pk.verify_elements(&message, &signature));
+        assert!(!pk.verify_elements(&message[1..], &signature));
+    }
+
+    #[test]
+    fn test_falcon_bytes_round_trip() {
+        let keys = KeyPair::new().unwrap();
+
+        // the public key should be derived correctly from the secret key
+        let restored = KeyPair::from_falcon_bytes(&keys.to_falcon_bytes()).unwrap();
+        assert_eq!(keys, restored);
+
+        let pk = PublicKey::from_falcon_bytes(&keys.public_key().to_falcon_bytes()).unwrap();
+        assert_eq!(pk, keys.public_key());
+
+        // invalid encodings should be rejected
+        let sk = keys.to_falcon_bytes();
+        assert!(KeyPair::from_falcon_bytes(&sk[1..]).is_err());
+        let mut invalid_sk = sk;
+        invalid_sk[0] = 0;
+        assert!(KeyPair::from_falcon_bytes(&invalid_sk).is_err());
+        assert!(PublicKey::from_falcon_bytes(&pk.to_falcon_bytes()[1..]).is_err());
+    }
+
+    #[test]
+    fn test_falcon_verification_with_verifier_key() {
+        let keys = KeyPair::new().unwrap();
+        let verifier_key = VerifierKey::new(keys.public_key());
+
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        let signature = keys.sign(message).unwrap();
+
+        // make sure the signature verifies correctly
+        assert!(verifier_key.verify(message, &signature));
+        assert!(verifier_key.verify_without_pk(message, signature.without_pk()));
+
+        // a signature should not verify against a wrong message
+        let message2: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        assert!(!verifier_key.verify(message2, &signature));
+        assert!(!verifier_key.verify_without_pk(message2, signature.without_pk()));
+
+        // a signature should not verify against a wrong public key
+        let verifier_key2 = VerifierKey::from(KeyPair::new().unwrap().public_key());
+        assert!(!verifier_key2.verify(message, &signature));
+        assert!(!verifier_key2.verify_without_pk(message, signature.without_pk()));
+    }
+
+    #[test]
+    fn test_pub_key_table() {
+        let keys = KeyPair::new_batch(3).unwrap();
+        let mut table: PubKeyTable = keys.iter().map(|k| k.public_key()).collect();
+        assert_eq!(table.len(), 3);
+
+        // keys should be resolved by their commitments
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        let signature = keys[1].sign(message).unwrap();
+        let commitment = keys[1].public_key().to_commitment();
+        assert_eq!(commitment, keys[1].commitment());
+        assert_eq!(table.get(commitment), Some(&keys[1].public_key()));
+        assert!(table.verify(message, commitment, signature.without_pk()));
+        assert!(!table.verify(message, keys[0].commitment(), signature.without_pk()));
+
+        // the table should survive a serialization round trip
+        let serialized = Serializable::to_bytes(&table);
+        assert_eq!(PubKeyTable::read_from_bytes(&serialized).unwrap(), table);
+
+        // a removed key can no longer be used for verification
+        assert_eq!(table.remove(commitment), Some(keys[1].public_key()));
+        assert!(!table.contains(commitment));
+        assert!(!table.verify(message, commitment, signature.without_pk()));
+    }
+
+    #[test]
+    fn test_key_pair_serialization() {
+        let keys = KeyPair::new().unwrap();
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        let signature = keys.sign(message).unwrap();
+        assert!(keys.verify(message, &signature));
+        assert_eq!(keys.commitment(), Word::from(keys.public_key()));
+
+        let restored = KeyPair::read_from_bytes(&keys.to_bytes()).unwrap();
+        assert_eq!(restored, keys);
+        assert!(restored.verify(message, &signature));
+        assert_eq!(restored.commitment(), keys.commitment());
+    }
+
+    #[test]
+    fn test_key_pair_debug_and_equality() {
+        let keys = KeyPair::new().unwrap();
+
+        // the secret key should not be printed
+        let expected = format!("KeyPair {{ public_key: {:?}, .. }}", keys.public_key());
+        assert_eq!(format!("{keys:?}"), expected);
+
+        // key pairs which differ only in their secret keys should not be equal
+        let mut other = keys.clone();
+        assert_eq!(other, keys);
+        other.secret_key[100] ^= 1;
+        assert_ne!(other, keys);
+    }
+
+    #[test]
+    fn test_key_pair_encryption() {
+        let keys = KeyPair::new().unwrap();
+        let mut rng = ChaCha20Rng::from_seed([7_u8; 32]);
+        let password = b"correct horse battery staple";
+
+        let encrypted = keys.to_encrypted_bytes(password, &mut rng);
+        assert_eq!(encrypted.len(), ENCRYPTED_KEY_PAIR_LEN);
+
+        let restored = KeyPair::from_encrypted_bytes(&encrypted, password).unwrap();
+        assert_eq!(restored, keys);
+
+        // a wrong password must be rejected
+        assert_eq!(
+            KeyPair::from_encrypted_bytes(&encrypted, b"wrong password"),
+            Err(FalconError::SecKeyDecryptionFailed)
+        );
+
+        // tampering with any part of the encrypted key pair must be detected
+        for i in [0, 40, 100, 1000, encrypted.len() - 1] {
+            let mut tampered = encrypted.clone();
+            tampered[i] ^= 1;
+            assert!(KeyPair::from_encrypted_bytes(&tampered, password).is_err());
+        }
+
+        // invalid lengths must be rejected
+        assert_eq!(
+            KeyPair::from_encrypted_bytes(&encrypted[1..], password),
+            Err(FalconError::SecKeyDecodingInvalidLength(ENCRYPTED_KEY_PAIR_LEN - 1))
+        );
+    }
+
+    #[test]
+    fn test_falcon_signing_context() {
+        let keys = KeyPair::new().unwrap();
+        let context = SigningContext::new(&keys).unwrap();
+        assert_eq!(context.public_key(), keys.public_key());
+
+        // signatures produced by the context should verify correctly
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        let signature = context.sign(message).unwrap();
+        assert!(keys.verify(message, &signature));
+
+        let message2: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        assert!(!keys.verify(message2, &signature));
+
+        // signing with the context should produce the same signature as signing with the key pair
+        let seed: [u8; 32] = rand_array();
+        let sig1 = context.sign_with_rng(message, &mut ChaCha20Rng::from_seed(seed)).unwrap();
+        let sig2 = keys.sign_with_rng(message, &mut ChaCha20Rng::from_seed(seed)).unwrap();
+        assert_eq!(sig1.to_bytes(), sig2.to_bytes());
+    }
+
+    #[test]
+    fn test_signing_context_serialization() {
+        let keys = KeyPair::new().unwrap();
+        let context = SigningContext::new(&keys).unwrap();
+
+        let serialized = context.to_bytes();
+        assert_eq!(serialized.len(), context.get_size_hint());
+        let deserialized = SigningContext::read_from_bytes(&serialized).unwrap();
+        assert_eq!(deserialized.public_key(), keys.public_key());
+
+        // the deserialized context should produce the same signatures as the original one
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        let seed: [u8; 32] = rand_array();
+        let sig1 = context.sign_with_rng(message, &mut ChaCha20Rng::from_seed(seed)).unwrap();
+        let sig2 = deserialized.sign_with_rng(message, &mut ChaCha20Rng::from_seed(seed)).unwrap();
+        assert_eq!(sig1.to_bytes(), sig2.to_bytes());
+        assert!(keys.verify(message, &sig2));
+
+        // truncated contexts should be rejected
+        assert!(SigningContext::read_from_bytes(&serialized[..serialized.len() - 1]).is_err());
+
+        // contexts whose expanded key was tampered with should be rejected
+        let mut tampered = serialized.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        assert!(SigningContext::read_from_bytes(&tampered).is_err());
+
+        // contexts with a public key not matching the secret key should be rejected
+        let other_keys = KeyPair::new().unwrap();
+        let mut tampered = other_keys.public_key().to_bytes();
+        tampered.extend_from_slice(&serialized[tampered.len()..]);
+        assert!(SigningContext::read_from_bytes(&tampered).is_err());
+    }
+
+    #[test]
+    fn test_key_pair_expanded_key() {
+        let keys = KeyPair::new().unwrap();
+        assert!(!keys.has_expanded_key());
+        let expanded = keys.clone().with_expanded_key().unwrap();
+        assert!(expanded.has_expanded_key());
+        assert_eq!(expanded, keys);
+
+        // the cached expansion should not change the signatures
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        let seed: [u8; 32] = rand_array();
+        let sig1 = keys.sign_with_rng(message, &mut ChaCha20Rng::from_seed(seed)).unwrap();
+        let sig2 = expanded.sign_with_rng(message, &mut ChaCha20Rng::from_seed(seed)).unwrap();
+        assert_eq!(sig1.to_bytes(), sig2.to_bytes());
+        assert!(keys.verify(message, &expanded.sign(message).unwrap()));
+
+        // the cached expansion should not be serialized
+        let deserialized = KeyPair::read_from_bytes(&expanded.to_bytes()).unwrap();
+        assert!(!deserialized.has_expanded_key());
+    }
+}
+
+#[cfg(all(test, falcon_signing, not(feature = "std")))]
+mod no_std_tests {
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+    use super::{Felt, KeyPair, SigningContext, Word};
+
+    #[test]
+    fn test_falcon_signing_with_injected_entropy() {
+        let mut rng = ChaCha20Rng::from_seed([7_u8; 32]);
+        let keys = KeyPair::with_rng(&mut rng).unwrap();
+        let message: Word = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+        let signature = keys.sign_with_rng(message, &mut rng).unwrap();
+        assert!(keys.verify(message, &signature));
+
+        let context = SigningContext::new(&keys).unwrap();
+        assert!(keys.verify(message, &context.sign_with_rng(message, &mut rng).unwrap()));
+        assert!(keys.verify(message, &keys.sign_deterministic(message).unwrap()));
+    }
 }
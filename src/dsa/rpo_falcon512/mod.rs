@@ -1,21 +1,40 @@
 use crate::{
-    hash::rpo::Rpo256,
-    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    hash::rpo::{Rpo256, RpoDigest},
+    utils::{ct_eq, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
     Felt, Word, ZERO,
 };
 
 #[cfg(feature = "std")]
+use crate::dsa::SignatureScheme;
+
+#[cfg(falcon_signing)]
 mod ffi;
 
+mod aggregate;
+mod encryption;
 mod error;
+#[cfg(feature = "gpu")]
+mod gpu;
 mod keys;
+mod nonce;
+mod ntt;
 mod polynomial;
 mod signature;
 
-pub use error::FalconError;
-pub use keys::{KeyPair, PublicKey};
+pub use aggregate::AggregateSignature;
+pub use encryption::ENCRYPTED_KEY_PAIR_LEN;
+pub use error::{FalconError, VerificationError};
+#[cfg(falcon_signing)]
+pub use keys::SigningContext;
+#[cfg(feature = "std")]
+pub use keys::{CancellationToken, KeygenBuilder};
+pub use keys::{KeyPair, PubKeyTable, PublicKey, VerifierKey};
+pub use nonce::{
+    CounterNonceGenerator, DeterministicNonceGenerator, Nonce, NonceGenerator, RandomNonceGenerator,
+};
 pub use polynomial::Polynomial;
-pub use signature::Signature;
+pub(crate) use signature::SIG_HEADER_COMPRESSED;
+pub use signature::{SigEncoding, Signature, SignatureWithoutPk};
 
 // CONSTANTS
 // ================================================================================================
@@ -27,10 +46,10 @@ const MODULUS_MINUS_1_OVER_TWO: u16 = 6144;
 // The Falcon parameters for Falcon-512. This is the degree of the polynomial `phi := x^N + 1`
 // defining the ring Z_p[x]/(phi).
 const N: usize = 512;
-const LOG_N: usize = 9;
+pub(crate) const LOG_N: usize = 9;
 
 /// Length of nonce used for key-pair generation.
-const NONCE_LEN: usize = 40;
+pub(crate) const NONCE_LEN: usize = 40;
 
 /// Number of filed elements used to encode a nonce.
 const NONCE_ELEMENTS: usize = 8;
@@ -44,14 +63,51 @@ pub const SK_LEN: usize = 1281;
 /// Signature length as a u8 vector.
 const SIG_LEN: usize = 626;
 
+/// Signature length as a u8 vector when the uncompressed encoding is used.
+const SIG_UNCOMPRESSED_LEN: usize = 769;
+
+/// Length of the seed used to initialize the RNG of the sampler during signing.
+pub const SIGNING_SEED_LEN: usize = 48;
+
 /// Bound on the squared-norm of the signature.
 const SIG_L2_BOUND: u64 = 34034726;
 
 // TYPE ALIASES
 // ================================================================================================
 
+#[cfg(falcon_signing)]
 type SignatureBytes = [u8; NONCE_LEN + SIG_LEN];
 type PublicKeyBytes = [u8; PK_LEN];
 type SecretKeyBytes = [u8; SK_LEN];
 type NonceBytes = [u8; NONCE_LEN];
 type NonceElements = [Felt; NONCE_ELEMENTS];
+
+// SIGNATURE SCHEME
+// ================================================================================================
+
+/// The RPO Falcon512 signature scheme.
+///
+/// Since [KeyPair::sign()] draws its randomness from the OS, [SignatureScheme] is implemented for
+/// this scheme only when the `std` feature is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpoFalcon512;
+
+#[cfg(feature = "std")]
+impl SignatureScheme for RpoFalcon512 {
+    type SecretKey = KeyPair;
+    type PublicKey = PublicKey;
+    type Signature = Signature;
+    type Error = FalconError;
+
+    fn public_key(secret_key: &KeyPair) -> PublicKey {
+        secret_key.public_key()
+    }
+
+    fn sign(secret_key: &KeyPair, message: Word) -> Result<Signature, FalconError> {
+        secret_key.sign(message)
+    }
+
+    fn verify(public_key: &PublicKey, message: Word, signature: &Signature) -> bool {
+        public_key.verify(message, signature)
+    }
+}
@@ -0,0 +1,324 @@
+use rand::Rng;
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Shake256,
+};
+
+use super::{
+    signature::{decode_nonce, hash_to_point_words},
+    FalconError, NonceBytes, NonceElements, Polynomial, SecretKeyBytes, Word, NONCE_LEN,
+    SIGNING_SEED_LEN,
+};
+
+// NONCE
+// ================================================================================================
+
+/// The nonce (i.e., salt) hashed together with the message in the hash-to-point algorithm.
+///
+/// A nonce can be represented either as 40 bytes, or as 8 field elements each of which encodes
+/// a consecutive 5-byte chunk of the nonce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nonce(NonceBytes);
+
+impl Nonce {
+    /// Returns a new [Nonce] instantiated from the provided bytes.
+    pub fn new(bytes: NonceBytes) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the byte representation of this nonce.
+    pub fn as_bytes(&self) -> &NonceBytes {
+        &self.0
+    }
+
+    /// Returns the field element representation of this nonce.
+    pub fn to_elements(&self) -> NonceElements {
+        decode_nonce(&self.0)
+    }
+
+    /// Returns a polynomial in Z_p\[x\]/(phi) representing the hash of the provided message and
+    /// this nonce.
+    pub fn hash_to_point(&self, message: Word) -> Polynomial {
+        self.hash_to_point_words(&[message])
+    }
+
+    /// Returns a polynomial in Z_p\[x\]/(phi) representing the hash of the provided message
+    /// consisting of multiple words and this nonce.
+    pub fn hash_to_point_words(&self, message: &[Word]) -> Polynomial {
+        hash_to_point_words(message, &self.to_elements())
+    }
+}
+
+impl From<NonceBytes> for Nonce {
+    fn from(bytes: NonceBytes) -> Self {
+        Self(bytes)
+    }
+}
+
+impl TryFrom<NonceElements> for Nonce {
+    type Error = FalconError;
+
+    /// Returns a nonce encoded by the provided field elements.
+    ///
+    /// # Errors
+    /// Returns an error if any of the elements does not fit into 5 bytes.
+    fn try_from(elements: NonceElements) -> Result<Self, Self::Error> {
+        let mut bytes = [0_u8; NONCE_LEN];
+        for (chunk, element) in bytes.chunks_mut(5).zip(elements) {
+            let value = element.as_int();
+            if value >= 1 << 40 {
+                return Err(FalconError::NonceDecodingInvalidElement(value));
+            }
+            chunk.copy_from_slice(&value.to_le_bytes()[..5]);
+        }
+        Ok(Self(bytes))
+    }
+}
+
+// NONCE GENERATOR
+// ================================================================================================
+
+/// A source of the per-signature randomness used for signing, i.e., the nonce hashed together
+/// with the message and the seed of the sampler.
+///
+/// A generator must never return the same nonce twice for the same message unless it also
+/// returns the same seed of the sampler: signing the same hash-to-point output twice with
+/// different randomness leaks information about the secret key.
+pub trait NonceGenerator {
+    /// Returns the nonce and the seed of the sampler to be used for signing the provided message.
+    ///
+    /// # Errors
+    /// Returns an error if the generator cannot produce any more nonces.
+    fn generate(
+        &mut self,
+        message: &[Word],
+    ) -> Result<(Nonce, [u8; SIGNING_SEED_LEN]), FalconError>;
+}
+
+/// A [NonceGenerator] which draws both the nonce and the seed of the sampler from a random
+/// number generator.
+///
+/// This is the strategy used by [KeyPair::sign_with_rng()](super::KeyPair::sign_with_rng()).
+#[derive(Debug, Clone)]
+pub struct RandomNonceGenerator<R: Rng> {
+    rng: R,
+}
+
+impl<R: Rng> RandomNonceGenerator<R> {
+    /// Returns a new [RandomNonceGenerator] drawing randomness from the provided generator, which
+    /// must be cryptographically secure.
+    pub fn new(rng: R) -> Self {
+        Self { rng }
+    }
+}
+
+impl<R: Rng> NonceGenerator for RandomNonceGenerator<R> {
+    fn generate(
+        &mut self,
+        _message: &[Word],
+    ) -> Result<(Nonce, [u8; SIGNING_SEED_LEN]), FalconError> {
+        let mut nonce = [0_u8; NONCE_LEN];
+        let mut seed = [0_u8; SIGNING_SEED_LEN];
+        self.rng.fill_bytes(&mut nonce);
+        self.rng.fill_bytes(&mut seed);
+        Ok((Nonce(nonce), seed))
+    }
+}
+
+/// A [NonceGenerator] which uses an incrementing counter as the nonce, and draws the seed of the
+/// sampler from a random number generator.
+///
+/// The counter is encoded into the first 8 bytes of the nonce in little-endian order. Once the
+/// counter reaches [u64::MAX], the generator refuses to produce further nonces. The caller
+/// is responsible for never reusing a counter value with the same key (e.g., by persisting the
+/// counter across restarts).
+#[derive(Debug, Clone)]
+pub struct CounterNonceGenerator<R: Rng> {
+    counter: u64,
+    rng: R,
+}
+
+impl<R: Rng> CounterNonceGenerator<R> {
+    /// Returns a new [CounterNonceGenerator] starting at the provided counter value and drawing
+    /// the seeds of the sampler from the provided generator.
+    pub fn new(counter: u64, rng: R) -> Self {
+        Self { counter, rng }
+    }
+
+    /// Returns the counter value which will be used for the next signature.
+    pub fn counter(&self) -> u64 {
+        self.counter
+    }
+}
+
+impl<R: Rng> NonceGenerator for CounterNonceGenerator<R> {
+    fn generate(
+        &mut self,
+        _message: &[Word],
+    ) -> Result<(Nonce, [u8; SIGNING_SEED_LEN]), FalconError> {
+        let mut nonce = [0_u8; NONCE_LEN];
+        nonce[..8].copy_from_slice(&self.counter.to_le_bytes());
+        self.counter = self.counter.checked_add(1).ok_or(FalconError::NonceGenerationExhausted)?;
+
+        let mut seed = [0_u8; SIGNING_SEED_LEN];
+        self.rng.fill_bytes(&mut seed);
+        Ok((Nonce(nonce), seed))
+    }
+}
+
+/// A [NonceGenerator] which derives both the nonce and the seed of the sampler from the secret
+/// key and the message, similarly to RFC 6979.
+///
+/// Signing the same message with the same key always produces the same signature, which makes
+/// signing reproducible without a source of randomness. The derivation uses SHAKE256 keyed with
+/// a digest of the secret key.
+#[derive(Clone)]
+pub struct DeterministicNonceGenerator {
+    key: [u8; 32],
+}
+
+impl DeterministicNonceGenerator {
+    /// Domain separator for deriving the key of the generator from the secret key.
+    const KEY_DOMAIN: &'static [u8] = b"RPO-FALCON512-DETERMINISTIC-NONCE-KEY";
+
+    /// Domain separator for deriving the nonce and the seed from the key and the message.
+    const NONCE_DOMAIN: &'static [u8] = b"RPO-FALCON512-DETERMINISTIC-NONCE";
+
+    /// Returns a new [DeterministicNonceGenerator] for the provided secret key.
+    pub(super) fn new(secret_key: &SecretKeyBytes) -> Self {
+        let mut hasher = Shake256::default();
+        hasher.update(Self::KEY_DOMAIN);
+        hasher.update(secret_key);
+        let mut key = [0_u8; 32];
+        hasher.finalize_xof().read(&mut key);
+        Self { key }
+    }
+}
+
+impl NonceGenerator for DeterministicNonceGenerator {
+    fn generate(
+        &mut self,
+        message: &[Word],
+    ) -> Result<(Nonce, [u8; SIGNING_SEED_LEN]), FalconError> {
+        let mut hasher = Shake256::default();
+        hasher.update(Self::NONCE_DOMAIN);
+        hasher.update(&self.key);
+        hasher.update(&(message.len() as u64).to_le_bytes());
+        for element in message.iter().flatten() {
+            hasher.update(&element.as_int().to_le_bytes());
+        }
+        let mut reader = hasher.finalize_xof();
+
+        let mut nonce = [0_u8; NONCE_LEN];
+        let mut seed = [0_u8; SIGNING_SEED_LEN];
+        reader.read(&mut nonce);
+        reader.read(&mut seed);
+        Ok((Nonce(nonce), seed))
+    }
+}
+
+/// A [NonceGenerator] which always returns the same nonce, and derives the seed of the sampler
+/// from the secret key, the nonce and the message.
+///
+/// Deriving the seed ensures that signing the same message with the same nonce always produces
+/// the same signature, and thus that the same hash-to-point output is never signed twice with
+/// different randomness.
+#[cfg(falcon_signing)]
+pub(super) struct FixedNonceGenerator {
+    nonce: Nonce,
+    key: DeterministicNonceGenerator,
+}
+
+#[cfg(falcon_signing)]
+impl FixedNonceGenerator {
+    /// Domain separator for deriving the seed from the key, the nonce and the message.
+    const SEED_DOMAIN: &'static [u8] = b"RPO-FALCON512-FIXED-NONCE-SEED";
+
+    /// Domain separator for deriving the seed from the key, the nonce and a pre-hashed message.
+    const PREHASHED_SEED_DOMAIN: &'static [u8] = b"RPO-FALCON512-FIXED-NONCE-PREHASHED-SEED";
+
+    /// Returns a new [FixedNonceGenerator] for the provided secret key and nonce.
+    pub(super) fn new(secret_key: &SecretKeyBytes, nonce: Nonce) -> Self {
+        Self {
+            nonce,
+            key: DeterministicNonceGenerator::new(secret_key),
+        }
+    }
+
+    /// Returns the seed of the sampler for signing the message which hashes to the point `c`
+    /// together with the nonce of this generator.
+    pub(super) fn prehashed_seed(&self, c: &Polynomial) -> [u8; SIGNING_SEED_LEN] {
+        let mut hasher = Shake256::default();
+        hasher.update(Self::PREHASHED_SEED_DOMAIN);
+        hasher.update(&self.key.key);
+        hasher.update(self.nonce.as_bytes());
+        for coefficient in c.inner() {
+            hasher.update(&coefficient.to_le_bytes());
+        }
+
+        let mut seed = [0_u8; SIGNING_SEED_LEN];
+        hasher.finalize_xof().read(&mut seed);
+        seed
+    }
+}
+
+#[cfg(falcon_signing)]
+impl NonceGenerator for FixedNonceGenerator {
+    fn generate(
+        &mut self,
+        message: &[Word],
+    ) -> Result<(Nonce, [u8; SIGNING_SEED_LEN]), FalconError> {
+        let mut hasher = Shake256::default();
+        hasher.update(Self::SEED_DOMAIN);
+        hasher.update(&self.key.key);
+        hasher.update(self.nonce.as_bytes());
+        hasher.update(&(message.len() as u64).to_le_bytes());
+        for element in message.iter().flatten() {
+            hasher.update(&element.as_int().to_le_bytes());
+        }
+
+        let mut seed = [0_u8; SIGNING_SEED_LEN];
+        hasher.finalize_xof().read(&mut seed);
+        Ok((self.nonce, seed))
+    }
+}
+
+impl core::fmt::Debug for DeterministicNonceGenerator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DeterministicNonceGenerator").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for DeterministicNonceGenerator {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.key);
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use rand_utils::rand_array;
+
+    use super::{FalconError, Nonce, NONCE_LEN};
+    use crate::Felt;
+
+    #[test]
+    fn test_nonce_representations() {
+        let bytes: [u8; NONCE_LEN] = rand_array();
+        let nonce = Nonce::from(bytes);
+
+        let elements = nonce.to_elements();
+        assert_eq!(Nonce::try_from(elements), Ok(nonce));
+
+        let mut elements = elements;
+        elements[3] = Felt::new(1 << 40);
+        assert_eq!(
+            Nonce::try_from(elements),
+            Err(FalconError::NonceDecodingInvalidElement(1 << 40))
+        );
+    }
+}
@@ -0,0 +1,117 @@
+use super::{LOG_N, MODULUS, N};
+
+// CONSTANTS
+// ================================================================================================
+
+/// A primitive 1024-th root of unity modulo p, i.e., 11^((p - 1) / 1024) where 11 is a generator
+/// of the multiplicative group of Z_p.
+const PSI: u32 = 10302;
+
+/// The inverse of N modulo p.
+pub(super) const N_INV: u32 = 12265;
+
+/// Powers of [PSI] in bit-reversed order, i.e., ZETAS[k] = PSI^brv(k) where brv() reverses the
+/// lower log2(N) bits of its argument.
+pub(super) const ZETAS: [u16; N] = compute_zetas();
+
+// NUMBER THEORETIC TRANSFORM
+// ================================================================================================
+
+/// Computes the negacyclic number theoretic transform of the polynomial with the provided
+/// coefficients in place. The result is in bit-reversed order.
+///
+/// All coefficients are expected to be smaller than the modulus p.
+pub fn ntt(a: &mut [u16; N]) {
+    let q = MODULUS as u32;
+    let mut k = 0;
+    let mut len = N / 2;
+    while len > 0 {
+        for start in (0..N).step_by(2 * len) {
+            k += 1;
+            let zeta = ZETAS[k] as u32;
+            for j in start..start + len {
+                let t = zeta * a[j + len] as u32 % q;
+                a[j + len] = ((a[j] as u32 + q - t) % q) as u16;
+                a[j] = ((a[j] as u32 + t) % q) as u16;
+            }
+        }
+        len >>= 1;
+    }
+}
+
+/// Computes the inverse of [ntt()] in place.
+pub fn intt(a: &mut [u16; N]) {
+    let q = MODULUS as u32;
+    let mut k = N;
+    let mut len = 1;
+    while len < N {
+        for start in (0..N).step_by(2 * len) {
+            k -= 1;
+            let zeta = q - ZETAS[k] as u32;
+            for j in start..start + len {
+                let t = a[j] as u32;
+                a[j] = ((t + a[j + len] as u32) % q) as u16;
+                a[j + len] = (zeta * ((t + q - a[j + len] as u32) % q) % q) as u16;
+            }
+        }
+        len <<= 1;
+    }
+
+    for e in a.iter_mut() {
+        *e = (*e as u32 * N_INV % q) as u16;
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Computes the powers of [PSI] in bit-reversed order.
+const fn compute_zetas() -> [u16; N] {
+    let q = MODULUS as u32;
+    let mut powers = [0_u16; N];
+    let mut acc = 1_u32;
+    let mut i = 0;
+    while i < N {
+        powers[i] = acc as u16;
+        acc = acc * PSI % q;
+        i += 1;
+    }
+
+    let mut zetas = [0_u16; N];
+    let mut k = 0;
+    while k < N {
+        zetas[k] = powers[k.reverse_bits() >> (usize::BITS as usize - LOG_N)];
+        k += 1;
+    }
+    zetas
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{intt, ntt, MODULUS, N, N_INV, PSI};
+
+    #[test]
+    fn test_constants() {
+        let q = MODULUS as u64;
+        let psi = PSI as u64;
+
+        // PSI must be a primitive 1024-th root of unity
+        let psi_512 = (0..512).fold(1, |acc, _| acc * psi % q);
+        assert_eq!(psi_512, q - 1);
+
+        assert_eq!(N as u64 * N_INV as u64 % q, 1);
+    }
+
+    #[test]
+    fn test_ntt_round_trip() {
+        let coefficients: [u16; N] = rand_utils::rand_array::<u16, N>().map(|e| e % MODULUS);
+
+        let mut a = coefficients;
+        ntt(&mut a);
+        intt(&mut a);
+        assert_eq!(a, coefficients);
+    }
+}
@@ -0,0 +1,61 @@
+// Multiplication of polynomials in Z_p[x]/(x^512 + 1) by public key polynomials given in NTT form.
+//
+// Each workgroup multiplies one polynomial: its 256 invocations perform the butterflies of a
+// single layer of the transform at a time, following the ntt() and intt() functions of ntt.rs
+// exactly. The constants MODULUS, N_INV and ZETAS are prepended to this source at runtime.
+
+// Coefficients of the polynomials s2, N per polynomial.
+@group(0) @binding(0) var<storage, read> polys: array<u32>;
+// NTTs of the public key polynomials, N per key.
+@group(0) @binding(1) var<storage, read> h_ntts: array<u32>;
+// The position of the public key in `h_ntts` for each polynomial.
+@group(0) @binding(2) var<storage, read> keys: array<u32>;
+// Coefficients of the products s2 * h, N per polynomial.
+@group(0) @binding(3) var<storage, read_write> products: array<u32>;
+
+var<workgroup> a: array<u32, 512>;
+
+@compute @workgroup_size(256)
+fn main(@builtin(workgroup_id) workgroup: vec3<u32>, @builtin(local_invocation_index) t: u32) {
+    let base = workgroup.x * 512u;
+    a[t] = polys[base + t];
+    a[t + 256u] = polys[base + t + 256u];
+    workgroupBarrier();
+
+    // forward transform; a layer with half-length `len` consists of 256 / len blocks with
+    // consecutive entries of ZETAS starting at 256 / len
+    for (var len = 256u; len > 0u; len = len >> 1u) {
+        let block = t / len;
+        let j = 2u * len * block + t % len;
+        let zeta = ZETAS[256u / len + block];
+        let x = a[j];
+        let y = zeta * a[j + len] % MODULUS;
+        a[j + len] = (x + MODULUS - y) % MODULUS;
+        a[j] = (x + y) % MODULUS;
+        workgroupBarrier();
+    }
+
+    // pointwise multiplication by the transform of the public key polynomial
+    let h_base = keys[workgroup.x] * 512u;
+    for (var i = t; i < 512u; i = i + 256u) {
+        a[i] = a[i] * (h_ntts[h_base + i] % MODULUS) % MODULUS;
+    }
+    workgroupBarrier();
+
+    // inverse transform; the entries of ZETAS are used in the reverse order of the forward
+    // transform, i.e., a layer with half-length `len` uses entries 512 / len - 1 down to 256 / len
+    for (var len = 1u; len < 512u; len = len << 1u) {
+        let block = t / len;
+        let j = 2u * len * block + t % len;
+        let zeta = MODULUS - ZETAS[512u / len - 1u - block];
+        let x = a[j];
+        let y = a[j + len];
+        a[j] = (x + y) % MODULUS;
+        a[j + len] = zeta * ((x + MODULUS - y) % MODULUS) % MODULUS;
+        workgroupBarrier();
+    }
+
+    for (var i = t; i < 512u; i = i + 256u) {
+        products[base + i] = a[i] * N_INV % MODULUS;
+    }
+}
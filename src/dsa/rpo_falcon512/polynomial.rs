@@ -1,7 +1,13 @@
 use alloc::vec::Vec;
 use core::ops::{Add, Mul, Sub};
 
-use super::{FalconError, Felt, LOG_N, MODULUS, MODULUS_MINUS_1_OVER_TWO, N, PK_LEN};
+use super::{
+    ntt, FalconError, Felt, LOG_N, MODULUS, MODULUS_MINUS_1_OVER_TWO, N, PK_LEN, SIG_LEN,
+    SIG_UNCOMPRESSED_LEN,
+};
+
+/// Number of bits used to encode a single coefficient under the uncompressed signature encoding.
+const SIG_COEF_BITS: u32 = 12;
 
 // FALCON POLYNOMIAL
 // ================================================================================================
@@ -71,22 +77,25 @@ impl Polynomial {
         }
     }
 
-    /// Decodes the signature into the coefficients of a polynomial in Z_p\[x\]/(phi). It assumes
-    /// that the signature has been encoded using the uncompressed format.
+    /// Decodes the signature into the coefficients of a polynomial in Z_p\[x\]/(phi). The
+    /// encoding algorithm used for the coefficients is determined by the header byte of the
+    /// signature, and can be either the compressed or the uncompressed encoding.
     ///
     /// # Errors
     /// Returns an error if:
+    /// - The signature is too short to contain the header byte, the nonce and the coefficients.
     /// - The signature has been encoded using a different algorithm than the reference compressed
-    ///   encoding algorithm.
+    ///   or uncompressed encoding algorithms.
     /// - The encoded signature polynomial is in Z_p\[x\]/(phi') where phi' = x^N' + 1 and N' != 512.
-    /// - While decoding the high bits of a coefficient, the current accumulated value of its
-    ///  high bits is larger than 2048.
-    /// - The decoded  coefficient is -0.
-    /// - The remaining unused bits in the last byte of `input` are non-zero.
+    /// - The coefficients cannot be decoded with the algorithm specified in the header byte (see
+    ///   [Polynomial::from_compressed()] and [Polynomial::from_uncompressed()]).
     pub fn from_signature(input: &[u8]) -> Result<Self, FalconError> {
+        if input.len() <= 41 {
+            return Err(FalconError::SigDecodingInvalidRemainder);
+        }
         let (encoding, log_n) = (input[0] >> 4, input[0] & 0b00001111);
 
-        if encoding != 0b0011 {
+        if encoding != 0b0011 && encoding != 0b0101 {
             return Err(FalconError::SigDecodingIncorrectEncodingAlgorithm);
         }
         if log_n != 0b1001 {
@@ -94,13 +103,41 @@ impl Polynomial {
         }
 
         let input = &input[41..];
+        if encoding == 0b0011 {
+            Self::from_compressed(input)
+        } else {
+            Self::from_uncompressed(input)
+        }
+    }
+
+    /// Decodes coefficients encoded using the reference compressed encoding algorithm into a
+    /// polynomial in Z_p\[x\]/(phi).
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - While decoding the high bits of a coefficient, the current accumulated value of its
+    ///  high bits is larger than 2048.
+    /// - The decoded  coefficient is -0.
+    /// - The input ends before all coefficients have been decoded.
+    /// - The remaining unused bits in the last byte of `input` are non-zero.
+    /// - Any of the padding bytes following the encoded coefficients is non-zero.
+    ///
+    /// Errors concerning a specific coefficient report the index of that coefficient. Thus, every polynomial has exactly one valid encoding of a given length.
+    pub fn from_compressed(input: &[u8]) -> Result<Self, FalconError> {
         let mut input_idx = 0;
         let mut acc = 0u32;
         let mut acc_len = 0;
         let mut output = [0_u16; N];
 
-        for e in output.iter_mut() {
-            acc = (acc << 8) | (input[input_idx] as u32);
+        let next_byte = |input_idx: usize, coef_idx: usize| {
+            input
+                .get(input_idx)
+                .map(|&byte| byte as u32)
+                .ok_or(FalconError::SigDecodingTruncated { index: coef_idx })
+        };
+
+        for (i, e) in output.iter_mut().enumerate() {
+            acc = (acc << 8) | next_byte(input_idx, i)?;
             input_idx += 1;
             let b = acc >> acc_len;
             let s = b & 128;
@@ -108,7 +145,7 @@ impl Polynomial {
 
             loop {
                 if acc_len == 0 {
-                    acc = (acc << 8) | (input[input_idx] as u32);
+                    acc = (acc << 8) | next_byte(input_idx, i)?;
                     input_idx += 1;
                     acc_len = 8;
                 }
@@ -118,11 +155,11 @@ impl Polynomial {
                 }
                 m += 128;
                 if m >= 2048 {
-                    return Err(FalconError::SigDecodingTooBigHighBits(m));
+                    return Err(FalconError::SigDecodingTooBigHighBits { index: i, high_bits: m });
                 }
             }
             if s != 0 && m == 0 {
-                return Err(FalconError::SigDecodingMinusZero);
+                return Err(FalconError::SigDecodingMinusZero { index: i });
             }
 
             *e = if s != 0 { (MODULUS as u32 - m) as u16 } else { m as u16 };
@@ -131,10 +168,139 @@ impl Polynomial {
         if (acc & ((1 << acc_len) - 1)) != 0 {
             return Err(FalconError::SigDecodingNonZeroUnusedBitsLastByte);
         }
+        if input[input_idx..].iter().any(|&byte| byte != 0) {
+            return Err(FalconError::SigDecodingNonZeroPadding);
+        }
+
+        Ok(Self(output))
+    }
+
+    /// Decodes coefficients encoded using the reference uncompressed encoding algorithm into a
+    /// polynomial in Z_p\[x\]/(phi). Under this encoding, each coefficient is encoded as a 12-bit
+    /// signed integer in two's complement representation.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The provided input is not exactly 768 bytes long.
+    /// - Any of the decoded coefficients is equal to -2048.
+    pub fn from_uncompressed(input: &[u8]) -> Result<Self, FalconError> {
+        if input.len() != SIG_UNCOMPRESSED_LEN - 1 {
+            return Err(FalconError::SigDecodingInvalidRemainder);
+        }
+
+        let mut acc = 0_u32;
+        let mut acc_len = 0;
+
+        let mut output = [0_u16; N];
+        let mut output_idx = 0;
+
+        for &byte in input {
+            acc = (acc << 8) | (byte as u32);
+            acc_len += 8;
+
+            if acc_len >= SIG_COEF_BITS {
+                acc_len -= SIG_COEF_BITS;
+                let w = (acc >> acc_len) & 0xFFF;
+                if w == 0x800 {
+                    return Err(FalconError::SigDecodingCoefficientOutOfRange {
+                        index: output_idx,
+                    });
+                }
+                output[output_idx] = if w & 0x800 != 0 {
+                    MODULUS - (0x1000 - w) as u16
+                } else {
+                    w as u16
+                };
+                output_idx += 1;
+            }
+        }
 
         Ok(Self(output))
     }
 
+    // ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Encodes the coefficients of this polynomial using the reference compressed encoding
+    /// algorithm. The result is padded with zeros up to 625 bytes.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Any of the coefficients, normalized to the interval (-p/2, p/2], is not in the interval
+    ///   [-2047, 2047].
+    /// - The encoded coefficients do not fit into 625 bytes.
+    pub fn to_compressed(&self) -> Result<[u8; SIG_LEN - 1], FalconError> {
+        let mut output = [0_u8; SIG_LEN - 1];
+        let mut output_idx = 0;
+
+        let mut acc = 0_u32;
+        let mut acc_len = 0;
+
+        for x in self.signed_coefficients()? {
+            // encode the sign bit and the 7 low bits of the absolute value
+            acc <<= 1;
+            if x < 0 {
+                acc |= 1;
+            }
+            let mut w = x.unsigned_abs() as u32;
+            acc <<= 7;
+            acc |= w & 127;
+            w >>= 7;
+            acc_len += 8;
+
+            // encode the high bits of the absolute value in unary
+            acc <<= w + 1;
+            acc |= 1;
+            acc_len += w + 1;
+
+            while acc_len >= 8 {
+                acc_len -= 8;
+                if output_idx >= output.len() {
+                    return Err(FalconError::SigEncodingTooLong);
+                }
+                output[output_idx] = (acc >> acc_len) as u8;
+                output_idx += 1;
+            }
+        }
+
+        if acc_len > 0 {
+            if output_idx >= output.len() {
+                return Err(FalconError::SigEncodingTooLong);
+            }
+            output[output_idx] = (acc << (8 - acc_len)) as u8;
+        }
+
+        Ok(output)
+    }
+
+    /// Encodes the coefficients of this polynomial using the reference uncompressed encoding
+    /// algorithm, i.e., each coefficient is encoded as a 12-bit signed integer in two's
+    /// complement representation.
+    ///
+    /// # Errors
+    /// Returns an error if any of the coefficients, normalized to the interval (-p/2, p/2], is not
+    /// in the interval [-2047, 2047].
+    pub fn to_uncompressed(&self) -> Result<[u8; SIG_UNCOMPRESSED_LEN - 1], FalconError> {
+        let mut output = [0_u8; SIG_UNCOMPRESSED_LEN - 1];
+        let mut output_idx = 0;
+
+        let mut acc = 0_u32;
+        let mut acc_len = 0;
+
+        for x in self.signed_coefficients()? {
+            acc = (acc << SIG_COEF_BITS) | (x as u32 & 0xFFF);
+            acc_len += SIG_COEF_BITS;
+
+            while acc_len >= 8 {
+                acc_len -= 8;
+                output[output_idx] = (acc >> acc_len) as u8;
+                output_idx += 1;
+            }
+        }
+
+        Ok(output)
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
@@ -183,6 +349,38 @@ impl Polynomial {
         Self(c)
     }
 
+    /// Returns the number theoretic transform of this polynomial, i.e., the evaluations of this
+    /// polynomial at the primitive 1024-th roots of unity modulo p (in bit-reversed order).
+    ///
+    /// Multiplication over Z_p\[x\]/(phi) becomes coefficient-wise multiplication in this
+    /// representation.
+    pub fn ntt(&self) -> [u16; N] {
+        let mut res = self.0;
+        ntt::ntt(&mut res);
+        res
+    }
+
+    /// Returns the polynomial with the provided number theoretic transform.
+    pub fn from_ntt(a: &[u16; N]) -> Self {
+        let mut res = a.map(|e| e % MODULUS);
+        ntt::intt(&mut res);
+        Self(res)
+    }
+
+    /// Multiplies this polynomial by a polynomial over Z_p\[x\]/(phi) represented by its number
+    /// theoretic transform (see [Polynomial::ntt()]).
+    ///
+    /// This is equivalent to `self * Polynomial::from_ntt(other_ntt)`, but avoids computing the
+    /// transform of the other polynomial.
+    pub fn mul_ntt(&self, other_ntt: &[u16; N]) -> Self {
+        let mut res = self.ntt();
+        for (r, &b) in res.iter_mut().zip(other_ntt.iter()) {
+            *r = (*r as u32 * (b % MODULUS) as u32 % MODULUS as u32) as u16;
+        }
+        ntt::intt(&mut res);
+        Self(res)
+    }
+
     /// Computes the norm squared of a polynomial in Z_p\[x\]/(phi) after normalizing its
     /// coefficients to be in the interval (-p/2, p/2].
     pub fn sq_norm(&self) -> u64 {
@@ -196,6 +394,30 @@ impl Polynomial {
         }
         res
     }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the coefficients of this polynomial normalized to the interval (-p/2, p/2].
+    ///
+    /// # Errors
+    /// Returns an error if any of the normalized coefficients is not in the interval
+    /// [-2047, 2047], as such coefficients cannot be encoded in a signature.
+    fn signed_coefficients(&self) -> Result<[i16; N], FalconError> {
+        let mut res = [0_i16; N];
+        for (r, &e) in res.iter_mut().zip(self.0.iter()) {
+            let x = if e > MODULUS_MINUS_1_OVER_TWO {
+                -((MODULUS - e) as i16)
+            } else {
+                e as i16
+            };
+            if !(-2047..=2047).contains(&x) {
+                return Err(FalconError::SigEncodingCoefficientOutOfRange);
+            }
+            *r = x;
+        }
+        Ok(res)
+    }
 }
 
 // Returns a polynomial representing the zero polynomial i.e. default element.
@@ -206,26 +428,16 @@ impl Default for Polynomial {
 }
 
 /// Multiplication over Z_p\[x\]/(phi)
+///
+/// The product is computed exactly using the number theoretic transform (see
+/// [Polynomial::mul_ntt()]).
 impl Mul for Polynomial {
     type Output = Self;
 
     fn mul(self, other: Self) -> <Self as Mul<Self>>::Output {
-        let mut result = [0_u16; N];
-        for j in 0..N {
-            for k in 0..N {
-                let i = (j + k) % N;
-                let a = self.0[j] as usize;
-                let b = other.0[k] as usize;
-                let q = MODULUS as usize;
-                let mut prod = a * b % q;
-                if (N - 1) < (j + k) {
-                    prod = (q - prod) % q;
-                }
-                result[i] = ((result[i] as usize + prod) % q) as u16;
-            }
-        }
-
-        Polynomial(result)
+        let lhs = Polynomial(self.0.map(|e| e % MODULUS));
+        let rhs = Polynomial(other.0.map(|e| e % MODULUS));
+        lhs.mul_ntt(&rhs.ntt())
     }
 }
 
@@ -261,7 +473,7 @@ impl Sub for Polynomial {
 
 #[cfg(test)]
 mod tests {
-    use super::{Polynomial, N};
+    use super::{Polynomial, MODULUS, N};
 
     #[test]
     fn test_negacyclic_reduction() {
@@ -276,4 +488,22 @@ mod tests {
             Polynomial::reduce_negacyclic(&Polynomial::mul_modulo_p(&poly1, &poly2))
         );
     }
+
+    #[test]
+    fn test_ntt_multiplication() {
+        let coef1: [u16; N] = rand_utils::rand_array::<u16, N>().map(|e| e % MODULUS);
+        let coef2: [u16; N] = rand_utils::rand_array::<u16, N>().map(|e| e % MODULUS);
+
+        let poly1 = Polynomial(coef1);
+        let poly2 = Polynomial(coef2);
+
+        assert_eq!(poly1 * poly2, poly1.mul_ntt(&poly2.ntt()));
+        assert_eq!(Polynomial::from_ntt(&poly1.ntt()), poly1);
+
+        // the product should match the schoolbook multiplication
+        assert_eq!(
+            poly1 * poly2,
+            Polynomial::reduce_negacyclic(&Polynomial::mul_modulo_p(&poly1, &poly2))
+        );
+    }
 }
@@ -1,11 +1,13 @@
-use alloc::string::ToString;
+use alloc::{collections::BTreeMap, vec::Vec};
 use core::cell::OnceCell;
 
 use super::{
-    ByteReader, ByteWriter, Deserializable, DeserializationError, Felt, NonceBytes, NonceElements,
-    Polynomial, PublicKeyBytes, Rpo256, Serializable, SignatureBytes, Word, MODULUS, N,
-    SIG_L2_BOUND, ZERO,
+    ct_eq, ByteReader, ByteWriter, Deserializable, DeserializationError, FalconError, Felt,
+    NonceBytes, NonceElements, Polynomial, PublicKey, PublicKeyBytes, Rpo256, RpoDigest,
+    Serializable, VerificationError, Word, LOG_N, MODULUS, N, NONCE_ELEMENTS, NONCE_LEN, PK_LEN,
+    SIG_L2_BOUND, SIG_LEN, SIG_UNCOMPRESSED_LEN, ZERO,
 };
+use crate::hash::{sha3::Shake256, Xof, XofReader};
 
 // FALCON SIGNATURE
 // ================================================================================================
@@ -29,42 +31,420 @@ use super::{
 /// 1. 1 byte representing the log2(512) i.e., 9.
 /// 2. 896 bytes for the public key. This is decoded into the `h` polynomial above.
 ///
-/// The actual signature is serialized as:
+/// The actual signature is serialized as described in [SignatureWithoutPk].
+///
+/// The total size of the signature (including the extended public key) is 1563 bytes when the
+/// compressed encoding is used.
+#[derive(Debug, Clone)]
+pub struct Signature {
+    pub(super) pk: PublicKeyBytes,
+    pub(super) sig: SignatureWithoutPk,
+
+    // Cached polynomial decoding for public key
+    pub(super) pk_polynomial: OnceCell<Polynomial>,
+}
+
+impl Signature {
+    /// The maximum size of a serialized signature, i.e., the size of a signature using the
+    /// uncompressed encoding.
+    pub const SERIALIZED_SIZE: usize = PK_LEN + SignatureWithoutPk::SERIALIZED_SIZE;
+
+    /// The size of a serialized signature using the compressed encoding.
+    pub const SERIALIZED_SIZE_COMPRESSED: usize =
+        PK_LEN + SignatureWithoutPk::SERIALIZED_SIZE_COMPRESSED;
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public key polynomial h.
+    pub fn pub_key_poly(&self) -> Polynomial {
+        // we assume that the signature was constructed with a valid public key, and thus
+        // expect() is OK here.
+        self.try_pub_key_poly().expect("invalid public key")
+    }
+
+    /// Returns the nonce component of the signature represented as field elements.
+    ///
+    /// Nonce bytes are converted to field elements by taking consecutive 5 byte chunks
+    /// of the nonce and interpreting them as field elements.
+    pub fn nonce(&self) -> NonceElements {
+        self.sig.nonce()
+    }
+
+    // Returns the polynomial representation of the signature in Z_p[x]/(phi).
+    pub fn sig_poly(&self) -> Polynomial {
+        self.sig.sig_poly()
+    }
+
+    /// Returns this signature without the embedded expanded public key.
+    pub fn without_pk(&self) -> &SignatureWithoutPk {
+        &self.sig
+    }
+
+    /// Consumes this signature and returns it without the embedded expanded public key.
+    pub fn into_without_pk(self) -> SignatureWithoutPk {
+        self.sig
+    }
+
+    /// Returns the polynomial s1 = c - s2 * h recovered from this signature for the specified
+    /// message, where c is the hash-to-point of the message and h is the embedded public key.
+    pub fn s1(&self, message: Word) -> Polynomial {
+        self.hash_to_point(message) - self.sig_poly() * self.pub_key_poly()
+    }
+
+    /// Returns the squared norm of the polynomial s1 recovered from this signature for the
+    /// specified message.
+    pub fn s1_sq_norm(&self, message: Word) -> u64 {
+        self.s1(message).sq_norm()
+    }
+
+    /// Returns the squared norm of the polynomial s2 of this signature.
+    pub fn s2_sq_norm(&self) -> u64 {
+        self.sig.s2_sq_norm()
+    }
+
+    /// Returns the field elements which the Miden VM's RPO Falcon512 verification procedure
+    /// expects to find on the advice stack when verifying this signature.
+    ///
+    /// The elements are returned in the order in which the procedure reads them, i.e., the first
+    /// element must be at the top of the advice stack:
+    /// 1. The 8 elements of the nonce.
+    /// 2. The 512 coefficients of the public key polynomial h.
+    /// 3. The 512 coefficients of the signature polynomial s2.
+    /// 4. The 1023 coefficients of the product h * s2 over Z_p\[x\] (i.e., not reduced modulo
+    ///    phi), padded with a zero to 1024 elements, which the procedure uses to check the
+    ///    multiplication probabilistically.
+    ///
+    /// The procedure computes the hash-to-point c of the message and the polynomial s1 = c - s2 * h
+    /// itself, and thus these are not part of the advice inputs.
+    pub fn to_advice_inputs(&self) -> Vec<Felt> {
+        let h = self.pub_key_poly();
+        let s2 = self.sig_poly();
+        let pi = Polynomial::mul_modulo_p(&h, &s2);
+
+        let mut result = Vec::with_capacity(NONCE_ELEMENTS + 4 * N);
+        result.extend_from_slice(&self.nonce());
+        result.extend(h.to_elements());
+        result.extend(s2.to_elements());
+        result.extend(pi.iter().map(|&a| Felt::new(a)));
+        result
+    }
+
+    // HASH-TO-POINT
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a polynomial in Z_p\[x\]/(phi) representing the hash of the provided message.
+    pub fn hash_to_point(&self, message: Word) -> Polynomial {
+        self.sig.hash_to_point(message)
+    }
+
+    /// Returns a polynomial in Z_p\[x\]/(phi) representing the hash of the provided message
+    /// consisting of multiple words.
+    pub fn hash_to_point_words(&self, message: &[Word]) -> Polynomial {
+        self.sig.hash_to_point_words(message)
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+    /// Returns true if this signature is a valid signature for the specified message generated
+    /// against key pair matching the specified public key commitment.
+    pub fn verify(&self, message: Word, pubkey_com: Word) -> bool {
+        self.verify_checked(message, pubkey_com).is_ok()
+    }
+
+    /// Checks whether this signature is a valid signature for the specified message generated
+    /// against key pair matching the specified public key commitment.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The expanded public key or the signature cannot be decoded.
+    /// - The expanded public key does not match the provided public key commitment.
+    /// - The squared norm of the signature exceeds the allowed bound.
+    pub fn verify_checked(&self, message: Word, pubkey_com: Word) -> Result<(), VerificationError> {
+        let h = self.checked_pub_key_poly(pubkey_com)?;
+        self.sig.verify_against_poly(self.hash_to_point(message), h)
+    }
+
+    /// Returns true if this signature is a valid signature for the specified message generated
+    /// against key pair with the specified public key polynomial `h`.
+    ///
+    /// Unlike [Signature::verify()], this neither hashes the coefficients of the public key
+    /// polynomial to compare it against a commitment nor uses the expanded public key embedded
+    /// in this signature. Thus, the caller is responsible for `h` being the (already validated)
+    /// public key of the expected signer.
+    pub fn verify_with_pubkey(&self, message: Word, h: &Polynomial) -> bool {
+        self.sig.verify_against_poly(self.hash_to_point(message), *h).is_ok()
+    }
+
+    /// Returns true if this signature is a valid signature for a message which hashes to the
+    /// point `c` generated against key pair matching the specified public key commitment.
+    ///
+    /// This allows the hash-to-point of the message to be computed separately, e.g., via
+    /// [Signature::hash_to_point()]. Note that the nonce of this signature is not checked against
+    /// `c`, and thus the caller is responsible for `c` being computed with this nonce.
+    pub fn verify_prehashed(&self, c: Polynomial, pubkey_com: Word) -> bool {
+        match self.checked_pub_key_poly(pubkey_com) {
+            Ok(h) => self.sig.verify_against_poly(c, h).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns true if this signature is a valid signature for the specified message consisting
+    /// of multiple words generated against key pair matching the specified public key
+    /// commitment.
+    ///
+    /// The words are absorbed into the hash-to-point sponge sequentially, and thus a message
+    /// consisting of a single word is equivalent to the same message passed to
+    /// [Signature::verify()]. Returns false if the message is empty.
+    pub fn verify_words(&self, message: &[Word], pubkey_com: Word) -> bool {
+        if message.is_empty() {
+            return false;
+        }
+        match self.checked_pub_key_poly(pubkey_com) {
+            Ok(h) => self.sig.verify_against_poly(self.hash_to_point_words(message), h).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns true if this signature is a valid signature for the specified byte message
+    /// generated against key pair matching the specified public key commitment.
+    ///
+    /// The message is first hashed into a [Word] using [Rpo256::hash()].
+    pub fn verify_bytes(&self, message: &[u8], pubkey_com: Word) -> bool {
+        self.verify(Rpo256::hash(message).into(), pubkey_com)
+    }
+
+    /// Returns true if this signature is a valid signature for the specified message consisting
+    /// of field elements generated against key pair matching the specified public key commitment.
+    ///
+    /// The message is first hashed into a [Word] using [Rpo256::hash_elements()].
+    pub fn verify_elements(&self, message: &[Felt], pubkey_com: Word) -> bool {
+        self.verify(Rpo256::hash_elements(message).into(), pubkey_com)
+    }
+
+    /// Returns true if all signatures in the provided list are valid.
+    ///
+    /// Each item in the list is a tuple `(message, pubkey_com, signature)` with the same meaning
+    /// as the arguments of [Signature::verify()].
+    ///
+    /// Checking that the expanded public key of a signature matches the provided public key
+    /// commitment requires hashing all coefficients of the public key polynomial, and the
+    /// multiplication by the public key polynomial requires its number theoretic transform. When a
+    /// batch contains many signatures generated with the same key, both are computed only once
+    /// per distinct expanded public key.
+    ///
+    /// When the `gpu` feature is enabled and the batch contains at least 256 signatures, the
+    /// products s2 * h (which dominate the cost of verification) are computed on the GPU. If no
+    /// GPU is available or the computation fails, they are computed on the CPU; when the
+    /// `parallel` feature is enabled, the norm checks of the signatures (including the products)
+    /// are then performed concurrently using all available CPU threads. The result does not
+    /// depend on where the products are computed.
+    pub fn verify_batch(items: &[(Word, Word, &Signature)]) -> bool {
+        // expanded public keys which have already been checked against their commitments, and the
+        // positions of the NTTs of their polynomials in `h_ntts`
+        let mut checked_keys: BTreeMap<RpoDigest, (&PublicKeyBytes, usize)> = BTreeMap::new();
+        let mut h_ntts: Vec<[u16; N]> = Vec::new();
+        // polynomials (c, s2) of the signatures for which the norm is yet to be checked, together
+        // with the position of the NTT of the public key polynomial
+        let mut checks = Vec::with_capacity(items.len());
+
+        for &(message, pubkey_com, signature) in items {
+            let pubkey_com = RpoDigest::from(pubkey_com);
+            let key_position = match checked_keys.get(&pubkey_com) {
+                Some(&(pk, position)) if *pk == signature.pk => position,
+                _ => {
+                    let h = match signature.try_pub_key_poly() {
+                        Ok(h) => h,
+                        Err(_) => return false,
+                    };
+                    let h_digest = Rpo256::hash_elements(&h.to_elements());
+                    if !h_digest.ct_eq(&pubkey_com) {
+                        return false;
+                    }
+                    h_ntts.push(h.ntt());
+                    checked_keys.insert(pubkey_com, (&signature.pk, h_ntts.len() - 1));
+                    h_ntts.len() - 1
+                }
+            };
+
+            let s2 = match signature.sig.try_sig_poly() {
+                Ok(s2) => s2,
+                Err(_) => return false,
+            };
+            checks.push((signature.hash_to_point(message), s2, key_position));
+        }
+
+        #[cfg(feature = "gpu")]
+        if checks.len() >= super::gpu::MIN_BATCH_SIZE {
+            let s2s: Vec<Polynomial> = checks.iter().map(|&(_, s2, _)| s2).collect();
+            let keys: Vec<usize> =
+                checks.iter().map(|&(_, _, key_position)| key_position).collect();
+            if let Some(products) = super::gpu::mul_ntt_many(&s2s, &h_ntts, &keys) {
+                return checks
+                    .iter()
+                    .zip(products)
+                    .all(|(&(c, s2, _), product)| check_norm(c - product, s2).is_ok());
+            }
+        }
+
+        let check = |&(c, s2, key_position): &(Polynomial, Polynomial, usize)| {
+            check_norm(c - s2.mul_ntt(&h_ntts[key_position]), s2).is_ok()
+        };
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            checks.par_iter().all(check)
+        }
+
+        #[cfg(not(feature = "parallel"))]
+        checks.iter().all(check)
+    }
+
+    // SERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes this signature into the provided buffer without allocating, and returns the number
+    /// of bytes written.
+    ///
+    /// The written bytes are identical to the output of [Serializable::to_bytes()]. The buffer is
+    /// large enough to hold signatures in either encoding; when the compressed encoding is used,
+    /// only the first [Signature::SERIALIZED_SIZE_COMPRESSED] bytes are written.
+    pub fn write_into_slice(&self, target: &mut [u8; Self::SERIALIZED_SIZE]) -> usize {
+        let (pk, sig) = target.split_at_mut(PK_LEN);
+        pk.copy_from_slice(&self.pk);
+        let sig: &mut [u8; SignatureWithoutPk::SERIALIZED_SIZE] =
+            sig.try_into().expect("invalid buffer length");
+        PK_LEN + self.sig.write_into_slice(sig)
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public key polynomial h after making sure that the expanded public key
+    /// matches the provided public key commitment.
+    fn checked_pub_key_poly(&self, pubkey_com: Word) -> Result<Polynomial, VerificationError> {
+        let h = self.try_pub_key_poly().map_err(VerificationError::MalformedEncoding)?;
+        let h_digest: Word = Rpo256::hash_elements(&h.to_elements()).into();
+        if !ct_eq(&h_digest, &pubkey_com) {
+            return Err(VerificationError::PubKeyCommitmentMismatch);
+        }
+        Ok(h)
+    }
+
+    /// Returns the public key polynomial h, or an error if the expanded public key cannot be
+    /// decoded.
+    fn try_pub_key_poly(&self) -> Result<Polynomial, FalconError> {
+        if let Some(&h) = self.pk_polynomial.get() {
+            return Ok(h);
+        }
+        let h = Polynomial::from_pub_key(&self.pk)?;
+        Ok(*self.pk_polynomial.get_or_init(|| h))
+    }
+}
+
+// FALCON SIGNATURE WITHOUT PUBLIC KEY
+// ================================================================================================
+
+/// An RPO Falcon512 signature over a message which does not embed the expanded public key.
+///
+/// This is useful in contexts where the verifier already knows the [PublicKey] of the signer, as
+/// it cuts the size of the serialized signature from 1563 bytes down to 666 bytes.
+///
+/// The signature is serialized as:
 /// 1. A header byte specifying the algorithm used to encode the coefficients of the `s2` polynomial
 ///    together with the degree of the irreducible polynomial phi.
 ///    The general format of this byte is 0b0cc1nnnn where:
 ///     a. cc is either 01 when the compressed encoding algorithm is used and 10 when the
 ///     uncompressed algorithm is used.
 ///     b. nnnn is log2(N) where N is the degree of the irreducible polynomial phi.
-///    The current implementation works always with cc equal to 0b01 and nnnn equal to 0b1001 and
-///    thus the header byte is always equal to 0b00111001.
+///    Signatures generated by this implementation always use cc equal to 0b01 and nnnn equal to
+///    0b1001 and thus the header byte is equal to 0b00111001. Signatures using the uncompressed
+///    encoding (i.e., with the header byte equal to 0b01011001) are accepted as well.
 /// 2. 40 bytes for the nonce.
-/// 3. 625 bytes encoding the `s2` polynomial above.
+/// 3. 625 bytes encoding the `s2` polynomial above when the compressed encoding is used, or 768
+///    bytes when the uncompressed encoding is used.
 ///
-/// The total size of the signature (including the extended public key) is 1563 bytes.
+/// The signature is stored in a fixed-size buffer large enough for either encoding, and thus
+/// signatures can be created, serialized and verified without allocating on the heap.
 #[derive(Debug, Clone)]
-pub struct Signature {
-    pub(super) pk: PublicKeyBytes,
-    pub(super) sig: SignatureBytes,
+pub struct SignatureWithoutPk {
+    /// The bytes of the signature; signatures using the compressed encoding are padded with
+    /// zeros.
+    pub(super) sig: SignatureBuffer,
 
-    // Cached polynomial decoding for public key and signatures
-    pub(super) pk_polynomial: OnceCell<Polynomial>,
+    // Cached polynomial decoding for the signature
     pub(super) sig_polynomial: OnceCell<Polynomial>,
 }
 
-impl Signature {
-    // PUBLIC ACCESSORS
+impl SignatureWithoutPk {
+    /// The maximum size of a serialized signature, i.e., the size of a signature using the
+    /// uncompressed encoding.
+    pub const SERIALIZED_SIZE: usize = SIG_BUFFER_LEN;
+
+    /// The size of a serialized signature using the compressed encoding.
+    pub const SERIALIZED_SIZE_COMPRESSED: usize = NONCE_LEN + SIG_LEN;
+
+    // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
 
-    /// Returns the public key polynomial h.
-    pub fn pub_key_poly(&self) -> Polynomial {
-        *self.pk_polynomial.get_or_init(|| {
-            // we assume that the signature was constructed with a valid public key, and thus
-            // expect() is OK here.
-            Polynomial::from_pub_key(&self.pk).expect("invalid public key")
-        })
+    /// Returns a new [SignatureWithoutPk] decoded from a signature in the format produced by the
+    /// reference Falcon-512 implementation.
+    ///
+    /// Unlike the serialized form of [SignatureWithoutPk], compressed signatures produced by the
+    /// reference implementation have variable length (up to 666 bytes), and end with the last
+    /// byte of the encoded coefficients. Signatures using the uncompressed encoding have a fixed
+    /// length of 809 bytes.
+    ///
+    /// # Errors
+    /// Returns an error if the provided bytes do not represent a valid signature, including when:
+    /// - A signature using the uncompressed encoding is not exactly 809 bytes long.
+    /// - A signature using the compressed encoding is followed by any trailing bytes.
+    pub fn from_falcon_bytes(bytes: &[u8]) -> Result<Self, FalconError> {
+        let encoding = match bytes.first() {
+            Some(&SIG_HEADER_COMPRESSED) => SigEncoding::Compressed,
+            Some(&SIG_HEADER_UNCOMPRESSED) => SigEncoding::Uncompressed,
+            _ => return Err(FalconError::SigDecodingIncorrectEncodingAlgorithm),
+        };
+
+        let sig_len = encoding.sig_len();
+        let valid_len = match encoding {
+            // the last byte of a compressed encoding holds the terminating bit of the last
+            // coefficient and is thus non-zero, while non-zero trailing bytes are rejected when
+            // decoding the padded signature below
+            SigEncoding::Compressed => {
+                bytes.len() > NONCE_LEN + 1 && bytes.len() <= sig_len && bytes[bytes.len() - 1] != 0
+            }
+            SigEncoding::Uncompressed => bytes.len() == sig_len,
+        };
+        if !valid_len {
+            return Err(FalconError::SigDecodingInvalidRemainder);
+        }
+
+        // compressed signatures are padded with zeros to the maximum signature length
+        let mut sig = [0_u8; SIG_BUFFER_LEN];
+        sig[..bytes.len()].copy_from_slice(bytes);
+
+        let sig_polynomial = Polynomial::from_signature(&sig[..sig_len])?.into();
+        Ok(Self { sig, sig_polynomial })
     }
 
+    /// Returns a [Signature] embedding the expanded public key of the provided public key into
+    /// this signature.
+    ///
+    /// The public key is not checked against the signature; a signature embedding a wrong public
+    /// key fails verification against the commitment to the signer's key.
+    pub fn with_pk(self, pub_key: &PublicKey) -> Signature {
+        Signature {
+            pk: pub_key.expanded_public_key(),
+            sig: self,
+            pk_polynomial: pub_key.pub_key_poly().into(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
     /// Returns the nonce component of the signature represented as field elements.
     ///
     /// Nonce bytes are converted to field elements by taking consecutive 5 byte chunks
@@ -76,13 +456,69 @@ impl Signature {
         decode_nonce(nonce)
     }
 
+    /// Returns the algorithm used to encode the coefficients of the `s2` polynomial.
+    pub fn encoding(&self) -> SigEncoding {
+        if self.sig[0] == SIG_HEADER_UNCOMPRESSED {
+            SigEncoding::Uncompressed
+        } else {
+            SigEncoding::Compressed
+        }
+    }
+
     // Returns the polynomial representation of the signature in Z_p[x]/(phi).
     pub fn sig_poly(&self) -> Polynomial {
-        *self.sig_polynomial.get_or_init(|| {
-            // we assume that the signature was constructed with a valid signature, and thus
-            // expect() is OK here.
-            Polynomial::from_signature(&self.sig).expect("invalid signature")
-        })
+        // we assume that the signature was constructed with a valid signature, and thus
+        // expect() is OK here.
+        self.try_sig_poly().expect("invalid signature")
+    }
+
+    /// Returns the polynomial s1 = c - s2 * h recovered from this signature for the specified
+    /// message and public key, where c is the hash-to-point of the message.
+    pub fn s1(&self, message: Word, pub_key: &PublicKey) -> Polynomial {
+        self.hash_to_point(message) - self.sig_poly() * pub_key.pub_key_poly()
+    }
+
+    /// Returns the squared norm of the polynomial s1 recovered from this signature for the
+    /// specified message and public key.
+    pub fn s1_sq_norm(&self, message: Word, pub_key: &PublicKey) -> u64 {
+        self.s1(message, pub_key).sq_norm()
+    }
+
+    /// Returns the squared norm of the polynomial s2 of this signature.
+    pub fn s2_sq_norm(&self) -> u64 {
+        self.sig_poly().sq_norm()
+    }
+
+    // ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns this signature with the coefficients of the `s2` polynomial re-encoded using the
+    /// specified encoding algorithm.
+    ///
+    /// # Errors
+    /// Returns an error if the `s2` polynomial cannot be encoded using the specified algorithm.
+    /// This may happen when converting a signature to the compressed encoding as the size of the
+    /// compressed encoding is bounded.
+    pub fn to_encoding(&self, encoding: SigEncoding) -> Result<Self, FalconError> {
+        if self.encoding() == encoding {
+            return Ok(self.clone());
+        }
+
+        let s2 = self.sig_poly();
+        let mut sig = [0_u8; SIG_BUFFER_LEN];
+        sig[1..NONCE_LEN + 1].copy_from_slice(&self.sig[1..NONCE_LEN + 1]);
+        match encoding {
+            SigEncoding::Compressed => {
+                sig[0] = SIG_HEADER_COMPRESSED;
+                sig[NONCE_LEN + 1..encoding.sig_len()].copy_from_slice(&s2.to_compressed()?);
+            }
+            SigEncoding::Uncompressed => {
+                sig[0] = SIG_HEADER_UNCOMPRESSED;
+                sig[NONCE_LEN + 1..encoding.sig_len()].copy_from_slice(&s2.to_uncompressed()?);
+            }
+        }
+
+        Ok(Self { sig, sig_polynomial: s2.into() })
     }
 
     // HASH-TO-POINT
@@ -93,26 +529,124 @@ impl Signature {
         hash_to_point(message, &self.nonce())
     }
 
+    /// Returns a polynomial in Z_p\[x\]/(phi) representing the hash of the provided message
+    /// consisting of multiple words.
+    pub fn hash_to_point_words(&self, message: &[Word]) -> Polynomial {
+        hash_to_point_words(message, &self.nonce())
+    }
+
+    /// Returns a polynomial in Z_p\[x\]/(phi) representing the hash of the provided message
+    /// computed using the SHAKE256-based hash-to-point algorithm from the Falcon specification.
+    pub fn hash_to_point_shake256(&self, message: &[u8]) -> Polynomial {
+        // we assume that the signature was constructed with a valid signature, and thus
+        // expect() is OK here.
+        let nonce = self.sig[1..NONCE_LEN + 1].try_into().expect("invalid signature");
+        hash_to_point_shake256(message, nonce)
+    }
+
     // SIGNATURE VERIFICATION
     // --------------------------------------------------------------------------------------------
+
     /// Returns true if this signature is a valid signature for the specified message generated
-    /// against key pair matching the specified public key commitment.
-    pub fn verify(&self, message: Word, pubkey_com: Word) -> bool {
-        // Make sure the expanded public key matches the provided public key commitment
-        let h = self.pub_key_poly();
-        let h_digest: Word = Rpo256::hash_elements(&h.to_elements()).into();
-        if h_digest != pubkey_com {
-            return false;
-        }
+    /// against key pair matching the specified public key.
+    pub fn verify_with_key(&self, message: Word, pub_key: &PublicKey) -> bool {
+        self.verify_with_key_checked(message, pub_key).is_ok()
+    }
 
-        // Make sure the signature is valid
-        let s2 = self.sig_poly();
-        let c = self.hash_to_point(message);
+    /// Checks whether this signature is a valid signature for the specified message generated
+    /// against key pair matching the specified public key.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The signature cannot be decoded.
+    /// - The squared norm of the signature exceeds the allowed bound.
+    pub fn verify_with_key_checked(
+        &self,
+        message: Word,
+        pub_key: &PublicKey,
+    ) -> Result<(), VerificationError> {
+        self.verify_against_poly(self.hash_to_point(message), pub_key.pub_key_poly())
+    }
+
+    /// Returns true if this signature is a valid signature for the specified message consisting
+    /// of multiple words generated against key pair matching the specified public key.
+    ///
+    /// Returns false if the message is empty.
+    pub fn verify_words_with_key(&self, message: &[Word], pub_key: &PublicKey) -> bool {
+        !message.is_empty()
+            && self
+                .verify_against_poly(self.hash_to_point_words(message), pub_key.pub_key_poly())
+                .is_ok()
+    }
+
+    /// Returns true if this signature is a valid signature for the specified message generated
+    /// by the reference Falcon-512 implementation against key pair matching the specified public
+    /// key.
+    ///
+    /// Unlike [SignatureWithoutPk::verify_with_key()], the message is mapped to a polynomial
+    /// using the SHAKE256-based hash-to-point algorithm from the Falcon specification. This allows
+    /// verifying signatures produced by other Falcon implementations (e.g., the C reference
+    /// implementation or liboqs).
+    pub fn verify_shake256(&self, message: &[u8], pub_key: &PublicKey) -> bool {
+        self.verify_against_poly(self.hash_to_point_shake256(message), pub_key.pub_key_poly())
+            .is_ok()
+    }
+
+    // SERIALIZATION
+    // --------------------------------------------------------------------------------------------
 
-        let s1 = c - s2 * h;
+    /// Writes this signature into the provided buffer without allocating, and returns the number
+    /// of bytes written.
+    ///
+    /// The written bytes are identical to the output of [Serializable::to_bytes()]. The buffer is
+    /// large enough to hold signatures in either encoding; when the compressed encoding is used,
+    /// only the first [SignatureWithoutPk::SERIALIZED_SIZE_COMPRESSED] bytes are written.
+    pub fn write_into_slice(&self, target: &mut [u8; Self::SERIALIZED_SIZE]) -> usize {
+        let bytes = self.as_bytes();
+        target[..bytes.len()].copy_from_slice(bytes);
+        bytes.len()
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks whether this signature is a valid signature for a message which hashes to the
+    /// polynomial `c` against the provided public key polynomial `h`.
+    fn verify_against_poly(&self, c: Polynomial, h: Polynomial) -> Result<(), VerificationError> {
+        let s2 = self.try_sig_poly().map_err(VerificationError::MalformedEncoding)?;
+        check_norm(c - s2 * h, s2)
+    }
 
-        let sq_norm = s1.sq_norm() + s2.sq_norm();
-        sq_norm <= SIG_L2_BOUND
+    /// Checks whether this signature is a valid signature for a message which hashes to the
+    /// polynomial `c` against the public key polynomial `h` provided in NTT form.
+    pub(super) fn verify_against_ntt(
+        &self,
+        c: Polynomial,
+        h_ntt: &[u16; N],
+    ) -> Result<(), VerificationError> {
+        let s2 = self.try_sig_poly().map_err(VerificationError::MalformedEncoding)?;
+        check_norm(c - s2.mul_ntt(h_ntt), s2)
+    }
+
+    /// Returns the polynomial representation of the signature, or an error if the signature
+    /// cannot be decoded.
+    fn try_sig_poly(&self) -> Result<Polynomial, FalconError> {
+        if let Some(&s2) = self.sig_polynomial.get() {
+            return Ok(s2);
+        }
+        let s2 = Polynomial::from_signature(self.as_bytes())?;
+        Ok(*self.sig_polynomial.get_or_init(|| s2))
+    }
+
+    /// Returns the bytes of this signature without the zero padding of the buffer.
+    fn as_bytes(&self) -> &[u8] {
+        &self.sig[..self.encoding().sig_len()]
+    }
+}
+
+impl From<Signature> for SignatureWithoutPk {
+    fn from(signature: Signature) -> Self {
+        signature.into_without_pk()
     }
 }
 
@@ -122,24 +656,92 @@ impl Signature {
 impl Serializable for Signature {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         target.write_bytes(&self.pk);
-        target.write_bytes(&self.sig);
+        self.sig.write_into(target);
+    }
+
+    fn get_size_hint(&self) -> usize {
+        PK_LEN + self.sig.get_size_hint()
     }
 }
 
 impl Deserializable for Signature {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         let pk: PublicKeyBytes = source.read_array()?;
-        let sig: SignatureBytes = source.read_array()?;
+        let sig = SignatureWithoutPk::read_from(source)?;
+
+        // make sure public key can be decoded correctly
+        let pk_polynomial = Polynomial::from_pub_key(&pk)?.into();
+
+        Ok(Self { pk, sig, pk_polynomial })
+    }
+}
+
+impl Serializable for SignatureWithoutPk {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(self.as_bytes());
+    }
+
+    fn get_size_hint(&self) -> usize {
+        self.encoding().sig_len()
+    }
+}
+
+impl Deserializable for SignatureWithoutPk {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        // the length of the signature depends on the encoding specified by the header byte
+        let header = source.read_u8()?;
+        let encoding = match header {
+            SIG_HEADER_COMPRESSED => SigEncoding::Compressed,
+            SIG_HEADER_UNCOMPRESSED => SigEncoding::Uncompressed,
+            _ => return Err(FalconError::SigDecodingIncorrectEncodingAlgorithm.into()),
+        };
+
+        let sig_len = encoding.sig_len();
+        let mut sig = [0_u8; SIG_BUFFER_LEN];
+        sig[0] = header;
+        sig[1..sig_len].copy_from_slice(source.read_slice(sig_len - 1)?);
+
+        // make sure signature can be decoded correctly
+        let sig_polynomial = Polynomial::from_signature(&sig[..sig_len])?.into();
 
-        // make sure public key and signature can be decoded correctly
-        let pk_polynomial = Polynomial::from_pub_key(&pk)
-            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?
-            .into();
-        let sig_polynomial = Polynomial::from_signature(&sig)
-            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?
-            .into();
+        Ok(Self { sig, sig_polynomial })
+    }
+}
+
+// SIGNATURE ENCODING
+// ================================================================================================
+
+/// Length of the buffer holding the bytes of a signature, i.e., the length of a signature using
+/// the uncompressed encoding.
+pub(super) const SIG_BUFFER_LEN: usize = NONCE_LEN + SIG_UNCOMPRESSED_LEN;
+
+/// Fixed-size buffer holding the bytes of a signature in either encoding.
+type SignatureBuffer = [u8; SIG_BUFFER_LEN];
+
+/// Header byte of signatures using the compressed encoding.
+pub(crate) const SIG_HEADER_COMPRESSED: u8 = 0x30 + LOG_N as u8;
+
+/// Header byte of signatures using the uncompressed encoding.
+const SIG_HEADER_UNCOMPRESSED: u8 = 0x50 + LOG_N as u8;
+
+/// Algorithm used to encode the coefficients of the `s2` polynomial of a signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigEncoding {
+    /// Reference compressed encoding; the serialized signature is 666 bytes long.
+    Compressed,
+    /// Reference uncompressed encoding where each coefficient is encoded using 12 bits; the
+    /// serialized signature is 809 bytes long.
+    Uncompressed,
+}
 
-        Ok(Self { pk, sig, pk_polynomial, sig_polynomial })
+impl SigEncoding {
+    /// Returns the length of a signature (including the header byte and the nonce) using this
+    /// encoding.
+    fn sig_len(&self) -> usize {
+        match self {
+            SigEncoding::Compressed => NONCE_LEN + SIG_LEN,
+            SigEncoding::Uncompressed => NONCE_LEN + SIG_UNCOMPRESSED_LEN,
+        }
     }
 }
 
@@ -149,7 +751,21 @@ impl Deserializable for Signature {
 /// Returns a polynomial in Z_p[x]/(phi) representing the hash of the provided message and
 /// nonce.
 fn hash_to_point(message: Word, nonce: &NonceElements) -> Polynomial {
+    hash_to_point_words(&[message], nonce)
+}
+
+/// Returns a polynomial in Z_p[x]/(phi) representing the hash of the provided multi-word message
+/// and nonce.
+///
+/// The words of the message are absorbed into the sponge sequentially, two words per
+/// permutation. Messages consisting of more than one word are domain separated by setting the
+/// first capacity element to the number of words; thus, hashing a single-word message is
+/// equivalent to [hash_to_point()].
+pub(super) fn hash_to_point_words(message: &[Word], nonce: &NonceElements) -> Polynomial {
     let mut state = [ZERO; Rpo256::STATE_WIDTH];
+    if message.len() > 1 {
+        state[Rpo256::CAPACITY_RANGE.start] = Felt::new(message.len() as u64);
+    }
 
     // absorb the nonce into the state
     for (&n, s) in nonce.iter().zip(state[Rpo256::RATE_RANGE].iter_mut()) {
@@ -157,9 +773,15 @@ fn hash_to_point(message: Word, nonce: &NonceElements) -> Polynomial {
     }
     Rpo256::apply_permutation(&mut state);
 
-    // absorb message into the state
-    for (&m, s) in message.iter().zip(state[Rpo256::RATE_RANGE].iter_mut()) {
-        *s = m;
+    // absorb message into the state, applying the permutation whenever the rate is full
+    let mut rate_idx = 0;
+    for &m in message.iter().flatten() {
+        state[Rpo256::RATE_RANGE.start + rate_idx] = m;
+        rate_idx += 1;
+        if rate_idx == Rpo256::RATE_RANGE.len() {
+            Rpo256::apply_permutation(&mut state);
+            rate_idx = 0;
+        }
     }
 
     // squeeze the coefficients of the polynomial
@@ -177,8 +799,41 @@ fn hash_to_point(message: Word, nonce: &NonceElements) -> Polynomial {
     unsafe { Polynomial::new(res) }
 }
 
+/// Returns a polynomial in Z_p[x]/(phi) representing the hash of the provided message and
+/// nonce computed as specified in the Falcon reference, i.e., the coefficients are sampled from
+/// the output of SHAKE256 over the nonce followed by the message.
+fn hash_to_point_shake256(message: &[u8], nonce: &NonceBytes) -> Polynomial {
+    let mut reader = Shake256::hash_xof_many(&[nonce, message]);
+
+    // rejection-sample 16-bit values smaller than 5 * p to keep the distribution uniform
+    let mut i = 0;
+    let mut res = [0_u16; N];
+    let mut buffer = [0_u8; 2];
+    while i < N {
+        reader.squeeze_bytes(&mut buffer);
+        let w = u16::from_be_bytes(buffer);
+        if w < 5 * MODULUS {
+            res[i] = w % MODULUS;
+            i += 1;
+        }
+    }
+
+    // using the raw constructor is OK here because we reduce all coefficients by the modulus above
+    unsafe { Polynomial::new(res) }
+}
+
+/// Returns an error if the squared norm of the pair (s1, s2) exceeds [SIG_L2_BOUND].
+fn check_norm(s1: Polynomial, s2: Polynomial) -> Result<(), VerificationError> {
+    let sq_norm = s1.sq_norm() + s2.sq_norm();
+    if sq_norm <= SIG_L2_BOUND {
+        Ok(())
+    } else {
+        Err(VerificationError::NormBoundExceeded(sq_norm))
+    }
+}
+
 /// Converts byte representation of the nonce into field element representation.
-fn decode_nonce(nonce: &NonceBytes) -> NonceElements {
+pub(super) fn decode_nonce(nonce: &NonceBytes) -> NonceElements {
     let mut buffer = [0_u8; 8];
     let mut result = [ZERO; 8];
     for (i, bytes) in nonce.chunks(5).enumerate() {
@@ -196,11 +851,13 @@ fn decode_nonce(nonce: &NonceBytes) -> NonceElements {
 
 #[cfg(all(test, feature = "std"))]
 mod tests {
+    use alloc::vec::Vec;
     use core::ffi::c_void;
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
     use rand_utils::rand_vector;
 
     use super::{
-        super::{ffi::*, KeyPair},
+        super::{ffi::*, KeyPair, SK_LEN},
         *,
     };
 
@@ -277,6 +934,56 @@ mod tests {
         assert_eq!(res, hash_to_point(msg_felts, &nonce).inner());
     }
 
+    #[test]
+    fn test_verify_batch() {
+        let keys1 = KeyPair::new().unwrap();
+        let keys2 = KeyPair::new().unwrap();
+        let pk1: Word = keys1.public_key().into();
+        let pk2: Word = keys2.public_key().into();
+
+        // sign random messages with both keys
+        let messages: Vec<Word> =
+            (0..4).map(|_| rand_vector::<Felt>(4).try_into().unwrap()).collect();
+        let signatures: Vec<Signature> = messages
+            .iter()
+            .enumerate()
+            .map(|(i, &m)| if i % 2 == 0 { keys1.sign(m) } else { keys2.sign(m) }.unwrap())
+            .collect();
+        let mut items: Vec<(Word, Word, &Signature)> = messages
+            .iter()
+            .zip(signatures.iter())
+            .enumerate()
+            .map(|(i, (&m, s))| (m, if i % 2 == 0 { pk1 } else { pk2 }, s))
+            .collect();
+
+        assert!(Signature::verify_batch(&[]));
+        assert!(Signature::verify_batch(&items));
+
+        // a batch should not verify if one of the signatures is for a different message
+        items[3].0 = messages[0];
+        assert!(!Signature::verify_batch(&items));
+
+        // a batch should not verify if one of the signatures is under a different key
+        items[3] = (messages[3], pk1, &signatures[3]);
+        assert!(!Signature::verify_batch(&items));
+    }
+
+    #[test]
+    fn test_verify_large_batch() {
+        // the batch is large enough to be verified on the GPU when the `gpu` feature is enabled
+        let keys = KeyPair::new().unwrap();
+        let pk: Word = keys.public_key().into();
+        let messages: Vec<Word> =
+            (0..2).map(|_| rand_vector::<Felt>(4).try_into().unwrap()).collect();
+        let signatures: Vec<Signature> = messages.iter().map(|&m| keys.sign(m).unwrap()).collect();
+        let mut items: Vec<(Word, Word, &Signature)> =
+            (0..300).map(|i| (messages[i % 2], pk, &signatures[i % 2])).collect();
+        assert!(Signature::verify_batch(&items));
+
+        items[299].0 = messages[0];
+        assert!(!Signature::verify_batch(&items));
+    }
+
     #[test]
     fn test_serialization_round_trip() {
         let key = KeyPair::new().unwrap();
@@ -286,4 +993,257 @@ mod tests {
         assert_eq!(signature.sig_poly(), deserialized.sig_poly());
         assert_eq!(signature.pub_key_poly(), deserialized.pub_key_poly());
     }
+
+    #[test]
+    fn test_non_canonical_encodings() {
+        // use a deterministic signature whose encoded coefficients are followed by padding
+        let key = KeyPair::from_seed(&[1_u8; 32]).unwrap();
+        let mut rng = ChaCha20Rng::from_seed([2_u8; 32]);
+        let signature = key.sign_with_rng(Word::default(), &mut rng).unwrap();
+        let serialized = signature.without_pk().to_bytes();
+        let s2_start = 1 + NONCE_LEN;
+        assert_eq!(serialized.last(), Some(&0));
+
+        // non-zero padding after the encoded coefficients must be rejected
+        let mut malleated = serialized.clone();
+        *malleated.last_mut().unwrap() = 1;
+        assert_eq!(
+            Polynomial::from_signature(&malleated),
+            Err(FalconError::SigDecodingNonZeroPadding)
+        );
+        let err = SignatureWithoutPk::read_from_bytes(&malleated).unwrap_err();
+        assert_eq!(err, DeserializationError::from(FalconError::SigDecodingNonZeroPadding));
+
+        // -0 must be rejected: encode the first coefficient as sign bit 1, low bits 0, a single
+        // terminating high bit and fill the remaining bits with zeros
+        let mut malleated = serialized.clone();
+        malleated[s2_start..].fill(0);
+        malleated[s2_start] = 0b1000_0000;
+        malleated[s2_start + 1] = 0b1000_0000;
+        assert_eq!(
+            Polynomial::from_signature(&malleated),
+            Err(FalconError::SigDecodingMinusZero { index: 0 })
+        );
+
+        // over-long runs of high bits must be rejected
+        let mut malleated = serialized.clone();
+        malleated[s2_start..].fill(0);
+        assert_eq!(
+            Polynomial::from_signature(&malleated),
+            Err(FalconError::SigDecodingTooBigHighBits { index: 0, high_bits: 2048 })
+        );
+
+        // truncated signatures must be rejected without panicking
+        for len in [0, 1, s2_start] {
+            assert_eq!(
+                Polynomial::from_signature(&serialized[..len]),
+                Err(FalconError::SigDecodingInvalidRemainder)
+            );
+        }
+        assert!(matches!(
+            Polynomial::from_signature(&serialized[..s2_start + 10]),
+            Err(FalconError::SigDecodingTruncated { index }) if index > 0 && index < 512
+        ));
+    }
+
+    #[test]
+    fn test_uncompressed_encoding() {
+        let key = KeyPair::new().unwrap();
+        let pk = key.public_key();
+        let message: Word = rand_vector::<Felt>(4).try_into().unwrap();
+        let signature = key.sign(message).unwrap();
+        let compressed = signature.without_pk();
+        assert_eq!(compressed.encoding(), SigEncoding::Compressed);
+
+        // convert the signature to the uncompressed encoding and make sure it still verifies
+        let uncompressed = compressed.to_encoding(SigEncoding::Uncompressed).unwrap();
+        assert_eq!(uncompressed.encoding(), SigEncoding::Uncompressed);
+        let serialized = uncompressed.to_bytes();
+        assert_eq!(serialized.len(), 809);
+        assert_eq!(serialized[0], 0x59);
+
+        let deserialized = SignatureWithoutPk::read_from_bytes(&serialized).unwrap();
+        assert_eq!(deserialized.encoding(), SigEncoding::Uncompressed);
+        assert_eq!(deserialized.nonce(), compressed.nonce());
+        assert_eq!(deserialized.sig_poly(), compressed.sig_poly());
+        assert!(deserialized.verify_with_key(message, &pk));
+
+        // converting back to the compressed encoding should produce the original signature
+        let recompressed = deserialized.to_encoding(SigEncoding::Compressed).unwrap();
+        assert_eq!(recompressed.to_bytes(), compressed.to_bytes());
+
+        // the coefficient -2048 should be rejected and reported with its index
+        let mut malleated = serialized.clone();
+        malleated[42] = (malleated[42] & 0xf0) | 0x08;
+        malleated[43] = 0;
+        assert_eq!(
+            Polynomial::from_signature(&malleated),
+            Err(FalconError::SigDecodingCoefficientOutOfRange { index: 1 })
+        );
+
+        // an unknown header byte should be rejected
+        let mut serialized = serialized;
+        serialized[0] = 0x49;
+        assert!(SignatureWithoutPk::read_from_bytes(&serialized).is_err());
+    }
+
+    #[test]
+    fn test_write_into_slice() {
+        let keys = KeyPair::new().unwrap();
+        let message: Word = rand_vector::<Felt>(4).try_into().expect("Should not fail.");
+        let signature = keys.sign(message).unwrap();
+
+        let mut buffer = [0_u8; Signature::SERIALIZED_SIZE];
+        let len = signature.write_into_slice(&mut buffer);
+        assert_eq!(len, Signature::SERIALIZED_SIZE_COMPRESSED);
+        assert_eq!(&buffer[..len], signature.to_bytes().as_slice());
+        assert_eq!(signature.get_size_hint(), len);
+
+        // signatures using the uncompressed encoding fill the whole buffer
+        let uncompressed = signature.without_pk().to_encoding(SigEncoding::Uncompressed).unwrap();
+        let mut buffer = [0_u8; SignatureWithoutPk::SERIALIZED_SIZE];
+        let len = uncompressed.write_into_slice(&mut buffer);
+        assert_eq!(len, SignatureWithoutPk::SERIALIZED_SIZE);
+        assert_eq!(buffer.as_slice(), uncompressed.to_bytes().as_slice());
+    }
+
+    #[test]
+    fn test_reference_falcon_verification() {
+        let mut pk = [0_u8; PK_LEN];
+        let mut sk = [0_u8; SK_LEN];
+        assert_eq!(0, unsafe {
+            PQCLEAN_FALCON512_CLEAN_crypto_sign_keypair(pk.as_mut_ptr(), sk.as_mut_ptr())
+        });
+        let pub_key = PublicKey::new(pk).unwrap();
+
+        // sign a random message using the reference implementation
+        let message: Vec<u8> = rand_vector(100);
+        let mut sig = [0_u8; NONCE_LEN + SIG_LEN];
+        let mut sig_len = 0;
+        assert_eq!(0, unsafe {
+            PQCLEAN_FALCON512_CLEAN_crypto_sign_signature(
+                sig.as_mut_ptr(),
+                &mut sig_len as *mut usize,
+                message.as_ptr(),
+                message.len(),
+                sk.as_ptr(),
+            )
+        });
+
+        // make sure the signature verifies correctly
+        let signature = SignatureWithoutPk::from_falcon_bytes(&sig[..sig_len]).unwrap();
+        assert!(signature.verify_shake256(&message, &pub_key));
+
+        // trailing bytes after a compressed signature should be rejected
+        let mut padded = sig[..sig_len].to_vec();
+        padded.push(0);
+        assert!(SignatureWithoutPk::from_falcon_bytes(&padded).is_err());
+        *padded.last_mut().unwrap() = 1;
+        assert!(SignatureWithoutPk::from_falcon_bytes(&padded).is_err());
+        assert!(SignatureWithoutPk::from_falcon_bytes(&sig[..sig_len - 1]).is_err());
+
+        // uncompressed signatures should have the exact length of the encoding
+        let uncompressed = signature.to_encoding(SigEncoding::Uncompressed).unwrap().to_bytes();
+        let restored = SignatureWithoutPk::from_falcon_bytes(&uncompressed).unwrap();
+        assert!(restored.verify_shake256(&message, &pub_key));
+        let truncated = &uncompressed[..uncompressed.len() - 1];
+        assert!(SignatureWithoutPk::from_falcon_bytes(truncated).is_err());
+
+        // a signature should not verify against a wrong message or using the RPO hash-to-point
+        assert!(!signature.verify_shake256(&message[1..], &pub_key));
+        assert!(!signature.verify_with_key(Rpo256::hash(&message).into(), &pub_key));
+    }
+
+    #[test]
+    fn test_verify_checked() {
+        let key = KeyPair::new().unwrap();
+        let pk: Word = key.public_key().into();
+        let message: Word = rand_vector::<Felt>(4).try_into().unwrap();
+        let signature = key.sign(message).unwrap();
+        assert_eq!(signature.verify_checked(message, pk), Ok(()));
+
+        // a signature should not verify against a wrong public key commitment
+        let pk2: Word = KeyPair::new().unwrap().public_key().into();
+        assert_eq!(
+            signature.verify_checked(message, pk2),
+            Err(VerificationError::PubKeyCommitmentMismatch)
+        );
+
+        // a signature for a different message results in a large norm
+        let message2: Word = rand_vector::<Felt>(4).try_into().unwrap();
+        assert!(matches!(
+            signature.verify_checked(message2, pk),
+            Err(VerificationError::NormBoundExceeded(_))
+        ));
+
+        // a signature which cannot be decoded should be reported as malformed
+        let mut sig = signature.without_pk().sig;
+        sig[0] = 0x49;
+        let malformed = SignatureWithoutPk { sig, sig_polynomial: OnceCell::new() };
+        assert!(matches!(
+            malformed.verify_with_key_checked(message, &key.public_key()),
+            Err(VerificationError::MalformedEncoding(_))
+        ));
+        assert!(!malformed.verify_with_key(message, &key.public_key()));
+    }
+
+    #[test]
+    fn test_verify_with_pubkey() {
+        let key = KeyPair::new().unwrap();
+        let h = key.public_key().pub_key_poly();
+        let message: Word = rand_vector::<Felt>(4).try_into().unwrap();
+        let signature = key.sign(message).unwrap();
+        assert!(signature.verify_with_pubkey(message, &h));
+
+        // the signature should not verify against a different message or public key polynomial
+        let message2: Word = rand_vector::<Felt>(4).try_into().unwrap();
+        assert!(!signature.verify_with_pubkey(message2, &h));
+        let h2 = KeyPair::new().unwrap().public_key().pub_key_poly();
+        assert!(!signature.verify_with_pubkey(message, &h2));
+    }
+
+    #[test]
+    fn test_signature_components() {
+        let key = KeyPair::new().unwrap();
+        let pub_key = key.public_key();
+        let message: Word = rand_vector::<Felt>(4).try_into().unwrap();
+        let signature = key.sign(message).unwrap();
+
+        // s1 should satisfy s1 + s2 * h = c
+        let s1 = signature.s1(message);
+        let s2 = signature.sig_poly();
+        assert_eq!(s1 + s2 * pub_key.pub_key_poly(), signature.hash_to_point(message));
+        assert_eq!(signature.without_pk().s1(message, &pub_key), s1);
+
+        // the norms of the components should add up to the norm of the signature
+        let sq_norm = signature.s1_sq_norm(message) + signature.s2_sq_norm();
+        assert_eq!(sq_norm, s1.sq_norm() + s2.sq_norm());
+        assert_eq!(signature.without_pk().s1_sq_norm(message, &pub_key), s1.sq_norm());
+        assert!(sq_norm <= SIG_L2_BOUND);
+    }
+
+    #[test]
+    fn test_advice_inputs() {
+        let key = KeyPair::new().unwrap();
+        let message: Word = rand_vector::<Felt>(4).try_into().unwrap();
+        let signature = key.sign(message).unwrap();
+
+        let inputs = signature.to_advice_inputs();
+        assert_eq!(inputs.len(), 8 + 4 * 512);
+
+        let (nonce, rest) = inputs.split_at(8);
+        let (h, rest) = rest.split_at(512);
+        let (s2, pi) = rest.split_at(512);
+        assert_eq!(nonce, signature.nonce());
+        assert_eq!(h, key.public_key().pub_key_poly().to_elements());
+        assert_eq!(s2, signature.sig_poly().to_elements());
+        assert_eq!(pi[1023], Felt::new(0));
+
+        // the product should reduce to h * s2 in Z_p[x]/(phi)
+        let pi: [u64; 1024] = pi.iter().map(|a| a.as_int()).collect::<Vec<_>>().try_into().unwrap();
+        assert_eq!(
+            Polynomial::reduce_negacyclic(&pi),
+            signature.pub_key_poly() * signature.sig_poly()
+        );
+    }
 }
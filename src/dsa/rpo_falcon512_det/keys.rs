@@ -0,0 +1,214 @@
+#[cfg(falcon_signing)]
+use rand::{CryptoRng, RngCore};
+
+use super::{
+    rpo_falcon512, ByteReader, ByteWriter, Deserializable, DeserializationError, PublicKey,
+    Serializable, Signature, Word,
+};
+#[cfg(falcon_signing)]
+use super::{salt, FalconError, SALT_VERSION};
+
+// KEY PAIR
+// ================================================================================================
+
+/// A key pair (public and secret keys) for signing messages with the deterministic variant of
+/// RPO Falcon512.
+///
+/// The key pair wraps a regular [rpo_falcon512::KeyPair], and the two can be converted into each
+/// other freely: only the way in which the key pair signs messages differs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyPair(rpo_falcon512::KeyPair);
+
+#[allow(clippy::new_without_default)]
+impl KeyPair {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Generates a (public_key, secret_key) key pair from OS-provided randomness.
+    ///
+    /// # Errors
+    /// Returns an error if key generation fails.
+    #[cfg(feature = "std")]
+    pub fn new() -> Result<Self, FalconError> {
+        rpo_falcon512::KeyPair::new().map(Self)
+    }
+
+    /// Generates a (public_key, secret_key) key pair using the provided random number generator.
+    ///
+    /// # Errors
+    /// Returns an error if key generation fails.
+    #[cfg(falcon_signing)]
+    pub fn with_rng<R: CryptoRng + RngCore>(rng: &mut R) -> Result<Self, FalconError> {
+        rpo_falcon512::KeyPair::with_rng(rng).map(Self)
+    }
+
+    /// Deterministically derives a (public_key, secret_key) key pair from the provided seed.
+    ///
+    /// # Errors
+    /// Returns an error if key generation fails.
+    #[cfg(falcon_signing)]
+    pub fn from_seed(seed: &[u8; 32]) -> Result<Self, FalconError> {
+        rpo_falcon512::KeyPair::from_seed(seed).map(Self)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public key corresponding to this key pair.
+    pub fn public_key(&self) -> PublicKey {
+        self.0.public_key()
+    }
+
+    /// Returns the commitment to the public key of this key pair.
+    pub fn commitment(&self) -> Word {
+        self.0.commitment()
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the provided signature is a valid signature of the provided message
+    /// against the public key of this key pair.
+    pub fn verify(&self, message: Word, signature: &Signature) -> bool {
+        signature.verify(message, &self.public_key())
+    }
+
+    // SIGNATURE GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Signs a message using the salt with version [SALT_VERSION], deriving the seed of the
+    /// sampler from the secret key, the salt and the message.
+    ///
+    /// Signing the same message with the same key pair always produces the same signature. Since
+    /// no randomness is needed, this method is available in the `no_std` context as well, except
+    /// on wasm32 targets for which the C implementation of Falcon-512 is not compiled.
+    ///
+    /// # Errors
+    /// Returns an error if signature generation fails.
+    #[cfg(falcon_signing)]
+    pub fn sign(&self, message: Word) -> Result<Signature, FalconError> {
+        let salt = salt(SALT_VERSION)?;
+        let sig = self.0.sign_with_nonce(message, salt)?;
+        Ok(Signature::new(SALT_VERSION, sig.into_without_pk()))
+    }
+}
+
+impl From<rpo_falcon512::KeyPair> for KeyPair {
+    fn from(key_pair: rpo_falcon512::KeyPair) -> Self {
+        Self(key_pair)
+    }
+}
+
+impl From<KeyPair> for rpo_falcon512::KeyPair {
+    fn from(key_pair: KeyPair) -> Self {
+        key_pair.0
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+/// The key pair is serialized in the same way as [rpo_falcon512::KeyPair].
+impl Serializable for KeyPair {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.0.write_into(target);
+    }
+}
+
+impl Deserializable for KeyPair {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        rpo_falcon512::KeyPair::read_from(source).map(Self)
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(all(test, falcon_signing))]
+mod tests {
+    use rand_chacha::{rand_core::SeedableRng, ChaCha20Rng};
+
+    use super::{
+        rpo_falcon512, salt, Deserializable, KeyPair, Serializable, Signature, Word, SALT_VERSION,
+    };
+    use crate::{
+        dsa::rpo_falcon512_det::{SALT_DOMAIN, SIG_HEADER},
+        Felt,
+    };
+
+    #[test]
+    fn test_deterministic_signing() {
+        let mut rng = ChaCha20Rng::from_seed([1_u8; 32]);
+        let keys = KeyPair::with_rng(&mut rng).unwrap();
+        let message: Word = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+
+        let signature = keys.sign(message).unwrap();
+        assert_eq!(signature.salt_version(), SALT_VERSION);
+        assert!(keys.verify(message, &signature));
+        assert!(signature.verify(message, &keys.public_key()));
+        assert_eq!(signature.to_bytes(), keys.sign(message).unwrap().to_bytes());
+
+        // signatures of different messages should differ
+        let mut other_message = message;
+        other_message[0] += Felt::new(1);
+        let other_signature = keys.sign(other_message).unwrap();
+        assert!(!keys.verify(message, &other_signature));
+        assert_ne!(signature.to_bytes(), other_signature.to_bytes());
+
+        // signatures should not verify against other keys
+        let other_keys = KeyPair::with_rng(&mut rng).unwrap();
+        assert!(!other_keys.verify(message, &signature));
+
+        // the signature should be an RPO Falcon512 signature with the fixed salt as its nonce
+        let inner: rpo_falcon512::KeyPair = keys.clone().into();
+        let expected = inner.sign_with_nonce(message, salt(SALT_VERSION).unwrap()).unwrap();
+        let sig = signature.to_signature_without_pk();
+        assert_eq!(sig.to_bytes(), expected.without_pk().to_bytes());
+        assert!(sig.verify_with_key(message, &keys.public_key()));
+
+        // deserialized key pairs should produce the same signatures
+        let deserialized = KeyPair::read_from_bytes(&keys.to_bytes()).unwrap();
+        assert_eq!(signature.to_bytes(), deserialized.sign(message).unwrap().to_bytes());
+    }
+
+    #[test]
+    fn test_salt() {
+        let nonce = salt(SALT_VERSION).unwrap();
+        let bytes = nonce.as_bytes();
+        assert_eq!(bytes[0], SALT_VERSION);
+        assert_eq!(bytes[1], 9);
+        assert_eq!(&bytes[2..12], SALT_DOMAIN);
+        assert!(bytes[12..].iter().all(|&byte| byte == 0));
+
+        assert!(salt(SALT_VERSION + 1).is_err());
+    }
+
+    #[test]
+    fn test_signature_serialization() {
+        let keys = KeyPair::from_seed(&[2_u8; 32]).unwrap();
+        let message: Word = [Felt::new(5), Felt::new(6), Felt::new(7), Felt::new(8)];
+        let signature = keys.sign(message).unwrap();
+
+        let bytes = signature.to_bytes();
+        assert_eq!(bytes.len(), Signature::SERIALIZED_SIZE);
+        assert_eq!(bytes.len(), signature.get_size_hint());
+        assert_eq!(bytes[0], SIG_HEADER);
+        assert_eq!(bytes[0], 0b10111001);
+        assert_eq!(bytes[1], SALT_VERSION);
+
+        let deserialized = Signature::read_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized.to_bytes(), bytes);
+        assert!(keys.verify(message, &deserialized));
+
+        // signatures with other headers or salt versions should be rejected
+        let mut invalid = bytes.clone();
+        invalid[0] = 0x39;
+        assert!(Signature::read_from_bytes(&invalid).is_err());
+        let mut invalid = bytes.clone();
+        invalid[1] = SALT_VERSION + 1;
+        assert!(Signature::read_from_bytes(&invalid).is_err());
+
+        // truncated signatures should be rejected
+        assert!(Signature::read_from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+}
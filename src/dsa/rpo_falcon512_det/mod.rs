@@ -0,0 +1,100 @@
+//! Deterministic variant of the RPO Falcon512 signature scheme, following the construction of
+//! deterministic Falcon (Falcon-Det).
+//!
+//! In this variant, the 40-byte salt (i.e., the nonce hashed together with the message) is a
+//! fixed, versioned constant instead of a random value, and the seed of the sampler is derived
+//! from the secret key, the salt and the message. Signing the same message with the same key thus
+//! always produces the same signature, which removes the dependency on a source of randomness
+//! during signing, and with it the risk of leaking the secret key by signing the same
+//! hash-to-point output with different randomness (e.g., due to a faulty or reused random number
+//! generator).
+//!
+//! As in Falcon-Det:
+//! - The salt with version `v` is the byte `v`, followed by the byte log2(512), i.e., 9, followed
+//!   by the ASCII string `FALCON_DET`, padded with zeros to 40 bytes. The only salt version
+//!   defined so far is [SALT_VERSION], i.e., 0.
+//! - Since the salt is fixed, it is not included in the signature; a signature consists of the
+//!   header byte, the salt version byte and the compressed encoding of the `s2` polynomial, and
+//!   thus is 627 bytes long.
+//! - The header byte is the header byte of a compressed Falcon-512 signature with the most
+//!   significant bit set, i.e., 0b10111001.
+//!
+//! The key pairs, the public keys and the polynomial arithmetic are shared with [rpo_falcon512],
+//! and messages are hashed to points using RPO as in that scheme (rather than with SHAKE256 as in
+//! the Falcon-Det specification). A deterministic signature is thus equivalent to an RPO Falcon512 signature whose nonce is the fixed salt (see
+//! [Signature::to_signature_without_pk()]).
+
+#[cfg(falcon_signing)]
+use crate::dsa::SignatureScheme;
+use crate::{
+    dsa::rpo_falcon512::{self, Nonce, LOG_N, NONCE_LEN, SIG_HEADER_COMPRESSED},
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    Word,
+};
+
+mod keys;
+mod signature;
+
+pub use keys::KeyPair;
+pub use rpo_falcon512::{FalconError, PublicKey, VerificationError};
+pub use signature::Signature;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The salt version used for generating signatures.
+pub const SALT_VERSION: u8 = 0;
+
+/// Header byte of deterministic signatures, i.e., the header byte of compressed signatures with
+/// the most significant bit set.
+const SIG_HEADER: u8 = 0x80 | SIG_HEADER_COMPRESSED;
+
+/// The domain separator following the salt version and log2(512) in the salt.
+const SALT_DOMAIN: &[u8] = b"FALCON_DET";
+
+// SIGNATURE SCHEME
+// ================================================================================================
+
+/// The deterministic RPO Falcon512 signature scheme.
+///
+/// Since deterministic signing does not need a source of randomness, [SignatureScheme] is
+/// implemented for this scheme whenever signing is available, i.e., also in the `no_std` context
+/// on all targets but `wasm32`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RpoFalcon512Det;
+
+#[cfg(falcon_signing)]
+impl SignatureScheme for RpoFalcon512Det {
+    type SecretKey = KeyPair;
+    type PublicKey = PublicKey;
+    type Signature = Signature;
+    type Error = FalconError;
+
+    fn public_key(secret_key: &KeyPair) -> PublicKey {
+        secret_key.public_key()
+    }
+
+    fn sign(secret_key: &KeyPair, message: Word) -> Result<Signature, FalconError> {
+        secret_key.sign(message)
+    }
+
+    fn verify(public_key: &PublicKey, message: Word, signature: &Signature) -> bool {
+        signature.verify(message, public_key)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the salt with the provided version, or an error if the version is not supported.
+fn salt(version: u8) -> Result<Nonce, FalconError> {
+    if version != SALT_VERSION {
+        return Err(FalconError::SigDecodingUnsupportedSaltVersion(version));
+    }
+
+    let mut salt = [0_u8; NONCE_LEN];
+    salt[0] = version;
+    salt[1] = LOG_N as u8;
+    salt[2..2 + SALT_DOMAIN.len()].copy_from_slice(SALT_DOMAIN);
+    Ok(Nonce::new(salt))
+}
@@ -0,0 +1,133 @@
+use super::{
+    rpo_falcon512::{Polynomial, SignatureWithoutPk},
+    salt, ByteReader, ByteWriter, Deserializable, DeserializationError, FalconError, PublicKey,
+    Serializable, VerificationError, Word, NONCE_LEN, SIG_HEADER, SIG_HEADER_COMPRESSED,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Length of the compressed encoding of the `s2` polynomial, padded with zeros.
+const S2_LEN: usize = SignatureWithoutPk::SERIALIZED_SIZE_COMPRESSED - NONCE_LEN - 1;
+
+// SIGNATURE
+// ================================================================================================
+
+/// A deterministic RPO Falcon512 signature over a message.
+///
+/// The signature is serialized as:
+/// 1. 1 header byte equal to 0b10111001.
+/// 2. 1 byte for the salt version.
+/// 3. 625 bytes encoding the `s2` polynomial using the compressed encoding, padded with zeros.
+///
+/// The salt is determined by its version, and thus is not serialized.
+#[derive(Debug, Clone)]
+pub struct Signature {
+    salt_version: u8,
+    sig: SignatureWithoutPk,
+}
+
+impl Signature {
+    /// The size of a serialized signature.
+    pub const SERIALIZED_SIZE: usize = 2 + S2_LEN;
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a deterministic signature consisting of the provided RPO Falcon512 signature,
+    /// whose nonce must be the salt with the provided version.
+    pub(super) fn new(salt_version: u8, sig: SignatureWithoutPk) -> Self {
+        Self { salt_version, sig }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the version of the salt used for generating this signature.
+    pub fn salt_version(&self) -> u8 {
+        self.salt_version
+    }
+
+    /// Returns the polynomial representation of the signature in Z_p\[x\]/(phi).
+    pub fn sig_poly(&self) -> Polynomial {
+        self.sig.sig_poly()
+    }
+
+    /// Returns this signature as an RPO Falcon512 signature whose nonce is the salt of this
+    /// signature.
+    ///
+    /// The returned signature verifies against the same messages and public keys as this one,
+    /// which allows deterministic signatures to be verified wherever RPO Falcon512 signatures are
+    /// (e.g., by the Miden VM).
+    pub fn to_signature_without_pk(&self) -> SignatureWithoutPk {
+        self.sig.clone()
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this signature is a valid signature for the specified message generated
+    /// against the key pair matching the specified public key.
+    pub fn verify(&self, message: Word, pub_key: &PublicKey) -> bool {
+        self.verify_checked(message, pub_key).is_ok()
+    }
+
+    /// Checks whether this signature is a valid signature for the specified message generated
+    /// against the key pair matching the specified public key.
+    ///
+    /// # Errors
+    /// Returns an error if the squared norm of the signature exceeds the allowed bound.
+    pub fn verify_checked(
+        &self,
+        message: Word,
+        pub_key: &PublicKey,
+    ) -> Result<(), VerificationError> {
+        self.sig.verify_with_key_checked(message, pub_key)
+    }
+}
+
+impl From<Signature> for SignatureWithoutPk {
+    fn from(signature: Signature) -> Self {
+        signature.sig
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for Signature {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        // the encoded s2 polynomial follows the header byte and the salt of the RPO Falcon512
+        // signature
+        let mut bytes = [0_u8; SignatureWithoutPk::SERIALIZED_SIZE];
+        let len = self.sig.write_into_slice(&mut bytes);
+        debug_assert_eq!(len, SignatureWithoutPk::SERIALIZED_SIZE_COMPRESSED);
+
+        target.write_u8(SIG_HEADER);
+        target.write_u8(self.salt_version);
+        target.write_bytes(&bytes[1 + NONCE_LEN..len]);
+    }
+
+    fn get_size_hint(&self) -> usize {
+        Self::SERIALIZED_SIZE
+    }
+}
+
+impl Deserializable for Signature {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        if source.read_u8()? != SIG_HEADER {
+            return Err(FalconError::SigDecodingIncorrectEncodingAlgorithm.into());
+        }
+        let salt_version = source.read_u8()?;
+        let salt = salt(salt_version)?;
+
+        // the signature is decoded as an RPO Falcon512 signature whose nonce is the salt
+        let mut bytes = [0_u8; SignatureWithoutPk::SERIALIZED_SIZE_COMPRESSED];
+        bytes[0] = SIG_HEADER_COMPRESSED;
+        bytes[1..1 + NONCE_LEN].copy_from_slice(salt.as_bytes());
+        bytes[1 + NONCE_LEN..].copy_from_slice(source.read_slice(S2_LEN)?);
+        let sig = SignatureWithoutPk::read_from_bytes(&bytes)?;
+
+        Ok(Self { salt_version, sig })
+    }
+}
@@ -0,0 +1,37 @@
+use core::fmt;
+
+// XMSS ERROR
+// ================================================================================================
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum XmssError {
+    IndexAlreadyUsed(u64),
+    IndexOutOfRange(u64),
+    InvalidHeight(u8),
+    KeyExhausted,
+    NotEnoughOneTimeKeys { requested: u64, remaining: u64 },
+}
+
+impl fmt::Display for XmssError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use XmssError::*;
+        match self {
+            IndexAlreadyUsed(index) => {
+                write!(f, "Leaf index {index} is behind the next unused leaf index of the key")
+            }
+            IndexOutOfRange(index) => {
+                write!(f, "Leaf index {index} exceeds the number of leaves of the key")
+            }
+            InvalidHeight(height) => {
+                write!(f, "Tree height {height} is outside of the supported range [1, 20]")
+            }
+            KeyExhausted => write!(f, "All one-time keys of the key have already been used"),
+            NotEnoughOneTimeKeys { requested, remaining } => {
+                write!(f, "Cannot reserve {requested} one-time keys since only {remaining} remain")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for XmssError {}
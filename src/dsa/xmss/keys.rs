@@ -0,0 +1,515 @@
+use alloc::{string::ToString, vec::Vec};
+
+use rand::{CryptoRng, RngCore};
+#[cfg(feature = "zeroize")]
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::{
+    wots, ByteReader, ByteWriter, Deserializable, DeserializationError, MerkleTree, NodeIndex,
+    Rpo256, RpoDigest, Serializable, Signature, Word, XmssError, MAX_HEIGHT, MIN_HEIGHT, SEED_LEN,
+};
+
+// PUBLIC KEY
+// ================================================================================================
+
+/// An XMSS public key, i.e., the root of the Merkle tree of one-time keys together with the
+/// public seed used to tweak the hash function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublicKey {
+    root: RpoDigest,
+    pub_seed: Word,
+    height: u8,
+}
+
+impl PublicKey {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the Merkle tree of one-time keys.
+    pub fn root(&self) -> RpoDigest {
+        self.root
+    }
+
+    /// Returns the height of the Merkle tree of one-time keys.
+    pub fn height(&self) -> u8 {
+        self.height
+    }
+
+    // SIGNATURE VERIFICATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the provided signature is a valid signature of the specified message
+    /// against this public key.
+    pub fn verify(&self, message: Word, signature: &Signature) -> bool {
+        if signature.path.depth() != self.height || signature.index >> self.height != 0 {
+            return false;
+        }
+
+        let digits = wots::message_digits(&self.root, signature.index, message);
+        let leaf =
+            wots::leaf_from_signature(&signature.wots, &self.pub_seed, signature.index, &digits);
+        signature.path.verify(signature.index, leaf.into(), &self.root)
+    }
+}
+
+// SECRET KEY
+// ================================================================================================
+
+/// An XMSS secret key.
+///
+/// The secret key holds the Merkle tree of one-time keys together with the range of one-time keys
+/// it may still use, i.e., the index of the next unused one-time key and the end of the range.
+/// Generating a key requires computing all 2^height one-time public keys,
+/// i.e., about 1000 * 2^height hash invocations; the same cost is incurred when a secret key is
+/// deserialized, since the tree is recomputed from the seeds rather than stored.
+///
+/// Each successful call to [SecretKey::sign()] consumes one one-time key. After signing, the
+/// updated key must be persisted before the signature is released.
+///
+/// A secret key cannot be cloned, since two copies of a key would sign with the same one-time
+/// keys. To sign from several devices (or threads), the range of one-time keys can instead be
+/// partitioned via [SecretKey::reserve()].
+///
+/// The [Debug](core::fmt::Debug) output of the secret key does not include the secret seed. When
+/// the `zeroize` feature is enabled, the secret seed is wiped from memory when the key is dropped.
+pub struct SecretKey {
+    seed: Word,
+    pub_seed: Word,
+    tree: MerkleTree,
+    next_index: u64,
+    end_index: u64,
+}
+
+impl SecretKey {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Generates a secret key of the specified height from OS-provided randomness.
+    ///
+    /// # Errors
+    /// Returns an error if the height is smaller than 1 or greater than 20.
+    #[cfg(feature = "std")]
+    pub fn new(height: u8) -> Result<Self, XmssError> {
+        Self::with_rng(height, &mut rand::thread_rng())
+    }
+
+    /// Generates a secret key of the specified height using the provided random number generator.
+    ///
+    /// # Errors
+    /// Returns an error if the height is smaller than 1 or greater than 20.
+    pub fn with_rng<R: RngCore + CryptoRng>(height: u8, rng: &mut R) -> Result<Self, XmssError> {
+        let mut seed = [0_u8; SEED_LEN];
+        rng.fill_bytes(&mut seed);
+        let secret_key = Self::from_seed(height, &seed);
+
+        #[cfg(feature = "zeroize")]
+        seed.zeroize();
+
+        secret_key
+    }
+
+    /// Generates a secret key of the specified height deterministically from the provided seed.
+    ///
+    /// # Errors
+    /// Returns an error if the height is smaller than 1 or greater than 20.
+    pub fn from_seed(height: u8, seed: &[u8; SEED_LEN]) -> Result<Self, XmssError> {
+        let seed: Word = Rpo256::hash(seed).into();
+        let pub_seed: Word = Rpo256::hash_elements(&seed).into();
+        Self::from_parts(height, seed, pub_seed, 0, 1 << height)
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the public key corresponding to this secret key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey {
+            root: self.tree.root(),
+            pub_seed: self.pub_seed,
+            height: self.tree.depth(),
+        }
+    }
+
+    /// Returns the total number of one-time keys of this secret key, i.e., 2^height.
+    pub fn num_leaves(&self) -> u64 {
+        1 << self.tree.depth()
+    }
+
+    /// Returns the index of the one-time key which will be used for the next signature.
+    pub fn next_index(&self) -> u64 {
+        self.next_index
+    }
+
+    /// Returns the index following the last one-time key which may be used by this secret key.
+    ///
+    /// This is equal to the number of leaves unless the key was returned by
+    /// [SecretKey::reserve()].
+    pub fn end_index(&self) -> u64 {
+        self.end_index
+    }
+
+    /// Returns the number of signatures which can still be generated with this secret key.
+    pub fn remaining_signatures(&self) -> u64 {
+        self.end_index - self.next_index
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Sets the index of the one-time key to be used for the next signature, skipping all
+    /// one-time keys before it.
+    ///
+    /// This is useful for making sure that no one-time key is reused, e.g., after restoring a
+    /// secret key from a backup which might be behind the latest state. Setting the index to the
+    /// [end index](SecretKey::end_index()) exhausts the key.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The index is smaller than the current index, as this would allow reusing one-time keys.
+    /// - The index is greater than the end index of this secret key.
+    pub fn advance_index(&mut self, index: u64) -> Result<(), XmssError> {
+        if index < self.next_index {
+            return Err(XmssError::IndexAlreadyUsed(index));
+        }
+        if index > self.end_index {
+            return Err(XmssError::IndexOutOfRange(index));
+        }
+        self.next_index = index;
+        Ok(())
+    }
+
+    /// Splits off the next `count` unused one-time keys of this secret key into a new secret key,
+    /// and advances this key past them.
+    ///
+    /// The returned key signs with the one-time keys in the range
+    /// `[self.next_index(), self.next_index() + count)`, and this key continues after the end of
+    /// that range; the two keys thus never sign with the same one-time key, and share the same
+    /// public key. As with signing, this key must be persisted before the returned key is used.
+    ///
+    /// # Errors
+    /// Returns an error if fewer than `count` one-time keys remain in this secret key.
+    pub fn reserve(&mut self, count: u64) -> Result<SecretKey, XmssError> {
+        let remaining = self.remaining_signatures();
+        if count > remaining {
+            return Err(XmssError::NotEnoughOneTimeKeys { requested: count, remaining });
+        }
+
+        let reserved = SecretKey {
+            seed: self.seed,
+            pub_seed: self.pub_seed,
+            tree: self.tree.clone(),
+            next_index: self.next_index,
+            end_index: self.next_index + count,
+        };
+        self.next_index += count;
+        Ok(reserved)
+    }
+
+    // SIGNATURE GENERATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Signs the specified message with the next unused one-time key, and marks that key as used.
+    ///
+    /// # Errors
+    /// Returns an error if all one-time keys of this secret key have already been used.
+    pub fn sign(&mut self, message: Word) -> Result<Signature, XmssError> {
+        let index = self.next_index;
+        if index == self.end_index {
+            return Err(XmssError::KeyExhausted);
+        }
+        self.next_index += 1;
+
+        let path = self
+            .tree
+            .get_path(NodeIndex::new(self.tree.depth(), index).expect("invalid leaf index"))
+            .expect("leaf index out of bounds");
+        let digits = wots::message_digits(&self.tree.root(), index, message);
+        let wots = wots::sign(&self.seed, &self.pub_seed, index, &digits);
+        Ok(Signature { index, wots, path })
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a secret key instantiated from the provided seeds and range of one-time keys,
+    /// computing the Merkle tree of one-time keys.
+    fn from_parts(
+        height: u8,
+        seed: Word,
+        pub_seed: Word,
+        next_index: u64,
+        end_index: u64,
+    ) -> Result<Self, XmssError> {
+        if !(MIN_HEIGHT..=MAX_HEIGHT).contains(&height) {
+            return Err(XmssError::InvalidHeight(height));
+        }
+        check_range(height, next_index, end_index)?;
+
+        let leaves: Vec<Word> =
+            (0..1_u64 << height).map(|leaf| wots::leaf(&seed, &pub_seed, leaf)).collect();
+        let tree = MerkleTree::new(leaves).expect("invalid number of leaves");
+        Ok(Self {
+            seed,
+            pub_seed,
+            tree,
+            next_index,
+            end_index,
+        })
+    }
+}
+
+impl core::fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SecretKey")
+            .field("public_key", &self.public_key())
+            .field("next_index", &self.next_index)
+            .field("end_index", &self.end_index)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        crate::utils::zeroize_elements(&mut self.seed);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl ZeroizeOnDrop for SecretKey {}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for PublicKey {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.height);
+        self.root.write_into(target);
+        RpoDigest::from(self.pub_seed).write_into(target);
+    }
+}
+
+impl Deserializable for PublicKey {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let height = source.read_u8()?;
+        if !(MIN_HEIGHT..=MAX_HEIGHT).contains(&height) {
+            return Err(DeserializationError::InvalidValue(
+                XmssError::InvalidHeight(height).to_string(),
+            ));
+        }
+        let root = RpoDigest::read_from(source)?;
+        let pub_seed = RpoDigest::read_from(source)?.into();
+        Ok(Self { root, pub_seed, height })
+    }
+}
+
+/// The secret key is serialized as the height of the tree (1 byte), the secret and public seeds
+/// (32 bytes each), the index of the next unused one-time key and the end index (8 bytes each).
+///
+/// The Merkle tree is recomputed on deserialization, which takes about 1000 * 2^height hash
+/// invocations. The height is checked against [MAX_HEIGHT] before anything else is read, but
+/// deserializing a key of the maximum height still takes about 10^9 hash invocations, and thus
+/// serialized secret keys must come from trusted storage.
+impl Serializable for SecretKey {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u8(self.tree.depth());
+        RpoDigest::from(self.seed).write_into(target);
+        RpoDigest::from(self.pub_seed).write_into(target);
+        target.write_u64(self.next_index);
+        target.write_u64(self.end_index);
+    }
+}
+
+impl Deserializable for SecretKey {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let height = source.read_u8()?;
+        if !(MIN_HEIGHT..=MAX_HEIGHT).contains(&height) {
+            return Err(DeserializationError::InvalidValue(
+                XmssError::InvalidHeight(height).to_string(),
+            ));
+        }
+        let seed = RpoDigest::read_from(source)?.into();
+        let pub_seed = RpoDigest::read_from(source)?.into();
+        let next_index = source.read_u64()?;
+        let end_index = source.read_u64()?;
+        check_range(height, next_index, end_index)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
+        Self::from_parts(height, seed, pub_seed, next_index, end_index)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Checks that the range of one-time keys `[next_index, end_index)` is within a tree of the
+/// specified height.
+fn check_range(height: u8, next_index: u64, end_index: u64) -> Result<(), XmssError> {
+    if end_index > 1 << height {
+        return Err(XmssError::IndexOutOfRange(end_index));
+    }
+    if next_index > end_index {
+        return Err(XmssError::IndexOutOfRange(next_index));
+    }
+    Ok(())
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use rand_utils::rand_array;
+
+    use super::{Deserializable, PublicKey, SecretKey, Serializable, Signature, Word, XmssError};
+    use crate::Felt;
+
+    #[test]
+    fn test_xmss_sign_and_verify() {
+        let mut secret_key = SecretKey::from_seed(3, &[1_u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        assert_eq!(secret_key.num_leaves(), 8);
+        assert_eq!(secret_key.remaining_signatures(), 8);
+
+        let message: Word = rand_array::<Felt, 4>();
+        let signature = secret_key.sign(message).unwrap();
+        assert_eq!(signature.index(), 0);
+        assert_eq!(secret_key.next_index(), 1);
+        assert!(public_key.verify(message, &signature));
+
+        // a signature should not verify against a different message
+        let mut other_message = message;
+        other_message[0] += Felt::new(1);
+        assert!(!public_key.verify(other_message, &signature));
+
+        // a signature should not verify with a tampered leaf index
+        let mut tampered = signature.clone();
+        tampered.index = 1;
+        assert!(!public_key.verify(message, &tampered));
+
+        // each signature should use a fresh one-time key
+        let other_signature = secret_key.sign(message).unwrap();
+        assert_eq!(other_signature.index(), 1);
+        assert!(public_key.verify(message, &other_signature));
+        assert_ne!(signature, other_signature);
+
+        // a signature should not verify against a different public key
+        let other_key = SecretKey::from_seed(3, &[2_u8; 32]).unwrap().public_key();
+        assert!(!other_key.verify(message, &signature));
+
+        // serialization should round trip
+        let bytes = signature.to_bytes();
+        assert_eq!(bytes.len(), 8 + 67 * 32 + 1 + 3 * 32);
+        assert_eq!(Signature::read_from_bytes(&bytes).unwrap(), signature);
+        let bytes = public_key.to_bytes();
+        assert_eq!(PublicKey::read_from_bytes(&bytes).unwrap(), public_key);
+        let bytes = secret_key.to_bytes();
+        let deserialized = SecretKey::read_from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized.public_key(), public_key);
+        assert_eq!(deserialized.to_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_xmss_state_management() {
+        assert_eq!(SecretKey::from_seed(0, &[0_u8; 32]).err(), Some(XmssError::InvalidHeight(0)));
+        assert_eq!(SecretKey::from_seed(21, &[0_u8; 32]).err(), Some(XmssError::InvalidHeight(21)));
+
+        let mut secret_key = SecretKey::from_seed(2, &[0_u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let message: Word = rand_array::<Felt, 4>();
+
+        assert_eq!(secret_key.advance_index(5), Err(XmssError::IndexOutOfRange(5)));
+        secret_key.advance_index(3).unwrap();
+        assert_eq!(secret_key.advance_index(2), Err(XmssError::IndexAlreadyUsed(2)));
+
+        let signature = secret_key.sign(message).unwrap();
+        assert_eq!(signature.index(), 3);
+        assert!(public_key.verify(message, &signature));
+        assert_eq!(secret_key.remaining_signatures(), 0);
+        assert_eq!(secret_key.sign(message), Err(XmssError::KeyExhausted));
+
+        // the state should be preserved by serialization
+        let deserialized = SecretKey::read_from_bytes(&secret_key.to_bytes()).unwrap();
+        assert_eq!(deserialized.next_index(), 4);
+
+        // invalid heights and indexes should be rejected before the tree is recomputed
+        let mut bytes = secret_key.to_bytes();
+        bytes[0] = 255;
+        assert!(SecretKey::read_from_bytes(&bytes[..1]).is_err());
+        bytes[0] = 2;
+        bytes[65..73].copy_from_slice(&5_u64.to_le_bytes());
+        assert!(SecretKey::read_from_bytes(&bytes).is_err());
+        bytes[65..73].copy_from_slice(&4_u64.to_le_bytes());
+        bytes[73..].copy_from_slice(&5_u64.to_le_bytes());
+        assert!(SecretKey::read_from_bytes(&bytes).is_err());
+        bytes[65..73].copy_from_slice(&3_u64.to_le_bytes());
+        bytes[73..].copy_from_slice(&2_u64.to_le_bytes());
+        assert!(SecretKey::read_from_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_xmss_reserve() {
+        let mut secret_key = SecretKey::from_seed(3, &[5_u8; 32]).unwrap();
+        let public_key = secret_key.public_key();
+        let message: Word = rand_array::<Felt, 4>();
+        secret_key.sign(message).unwrap();
+
+        // the reserved key should use the next one-time keys, and the original key the ones
+        // following them
+        let mut reserved = secret_key.reserve(3).unwrap();
+        assert_eq!((reserved.next_index(), reserved.end_index()), (1, 4));
+        assert_eq!((secret_key.next_index(), secret_key.end_index()), (4, 8));
+        assert_eq!(reserved.public_key(), public_key);
+
+        let signatures: Vec<Signature> = (0..3).map(|_| reserved.sign(message).unwrap()).collect();
+        assert_eq!(reserved.sign(message), Err(XmssError::KeyExhausted));
+        assert_eq!(reserved.advance_index(5), Err(XmssError::IndexOutOfRange(5)));
+        for (index, signature) in (1..4).zip(&signatures) {
+            assert_eq!(signature.index(), index);
+            assert!(public_key.verify(message, signature));
+        }
+        assert_eq!(secret_key.sign(message).unwrap().index(), 4);
+
+        // the range should be preserved by serialization
+        let deserialized = SecretKey::read_from_bytes(&reserved.to_bytes()).unwrap();
+        assert_eq!((deserialized.next_index(), deserialized.end_index()), (4, 4));
+        assert_eq!(deserialized.remaining_signatures(), 0);
+
+        assert_eq!(
+            secret_key.reserve(4).err(),
+            Some(XmssError::NotEnoughOneTimeKeys { requested: 4, remaining: 3 })
+        );
+        assert_eq!(secret_key.reserve(3).unwrap().remaining_signatures(), 3);
+        assert_eq!(secret_key.remaining_signatures(), 0);
+        assert_eq!(secret_key.reserve(0).unwrap().remaining_signatures(), 0);
+    }
+
+    #[test]
+    fn test_xmss_secret_key_debug() {
+        let secret_key = SecretKey::from_seed(1, &[3_u8; 32]).unwrap();
+        let expected = format!(
+            "SecretKey {{ public_key: {:?}, next_index: 0, end_index: 2, .. }}",
+            secret_key.public_key()
+        );
+        assert_eq!(format!("{secret_key:?}"), expected);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_xmss_secret_key_zeroize() {
+        use zeroize::Zeroize;
+
+        let mut secret_key = SecretKey::from_seed(1, &[4_u8; 32]).unwrap();
+        secret_key.zeroize();
+        assert_eq!(secret_key.seed, [crate::ZERO; 4]);
+    }
+}
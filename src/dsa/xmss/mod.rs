@@ -0,0 +1,63 @@
+//! An XMSS-style stateful hash-based signature scheme built on [Rpo256].
+//!
+//! A secret key consists of 2^height one-time WOTS+ key pairs, and the public key is the root of
+//! a Merkle tree whose leaves are commitments to the one-time public keys. A signature consists
+//! of a WOTS+ signature under one of the one-time keys together with the Merkle path which
+//! authenticates that key against the root.
+//!
+//! The scheme is stateful: each one-time key must be used to sign at most one message, and thus
+//! the secret key keeps track of the index of the next unused leaf. Signing with a stale copy of
+//! a secret key (e.g., one restored from a backup) reuses one-time keys and can allow forging
+//! signatures. The state must be persisted (e.g., via [Serializable]) before a signature is
+//! released, and [SecretKey::advance_index()] can be used to skip leaves which might have been
+//! used. For the same reason, [SignatureScheme](super::SignatureScheme) is not implemented for
+//! this scheme, and secret keys cannot be cloned; instead, [SecretKey::reserve()] splits off a
+//! range of unused leaves into a separate key.
+//!
+//! WOTS+ is instantiated with the Winternitz parameter w = 16 over 256-bit message digests,
+//! which results in 64 message chains and 3 checksum chains per one-time key.
+
+use crate::{
+    hash::rpo::{Rpo256, RpoDigest},
+    merkle::{MerklePath, MerkleTree, NodeIndex},
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    Felt, Word,
+};
+
+mod error;
+mod keys;
+mod signature;
+mod wots;
+
+pub use error::XmssError;
+pub use keys::{PublicKey, SecretKey};
+pub use signature::Signature;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The minimum height of the Merkle tree of a key, i.e., a key can be used to sign at least 2
+/// messages.
+pub const MIN_HEIGHT: u8 = 1;
+
+/// The maximum height of the Merkle tree of a key, i.e., a key can be used to sign at most 2^20
+/// messages.
+pub const MAX_HEIGHT: u8 = 20;
+
+/// Length of the seed from which a secret key is derived.
+pub const SEED_LEN: usize = 32;
+
+/// The number of bits of a message digest encoded by each WOTS+ chain.
+const LOG_W: usize = 4;
+
+/// The Winternitz parameter, i.e., the length of each WOTS+ chain.
+const W: usize = 1 << LOG_W;
+
+/// The number of WOTS+ chains encoding the message digest.
+const LEN1: usize = 64;
+
+/// The number of WOTS+ chains encoding the checksum of the message digest.
+const LEN2: usize = 3;
+
+/// The total number of WOTS+ chains in a one-time key.
+const WOTS_LEN: usize = LEN1 + LEN2;
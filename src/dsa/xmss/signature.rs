@@ -0,0 +1,61 @@
+use super::{
+    ByteReader, ByteWriter, Deserializable, DeserializationError, MerklePath, RpoDigest,
+    Serializable, WOTS_LEN,
+};
+
+// SIGNATURE
+// ================================================================================================
+
+/// An XMSS signature.
+///
+/// A signature consists of the index of the one-time key used for signing, the WOTS+ signature
+/// under that key, and the Merkle path authenticating the one-time key against the root of the
+/// tree.
+///
+/// The signature is serialized as the leaf index (8 bytes), followed by the 67 elements of the
+/// WOTS+ signature (32 bytes each) and the Merkle path (1 byte for the height of the tree and 32
+/// bytes per node). For example, a signature under a key of height 10 is 2473 bytes in size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature {
+    pub(super) index: u64,
+    pub(super) wots: [RpoDigest; WOTS_LEN],
+    pub(super) path: MerklePath,
+}
+
+impl Signature {
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the index of the one-time key used to generate this signature.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+
+    /// Returns the Merkle path authenticating the one-time key used to generate this signature.
+    pub fn path(&self) -> &MerklePath {
+        &self.path
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for Signature {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_u64(self.index);
+        target.write_many(self.wots);
+        self.path.write_into(target);
+    }
+}
+
+impl Deserializable for Signature {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let index = source.read_u64()?;
+        let mut wots = [RpoDigest::default(); WOTS_LEN];
+        for element in wots.iter_mut() {
+            *element = RpoDigest::read_from(source)?;
+        }
+        let path = MerklePath::read_from(source)?;
+        Ok(Self { index, wots, path })
+    }
+}
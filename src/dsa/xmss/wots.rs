@@ -0,0 +1,142 @@
+use alloc::vec::Vec;
+
+use super::{Felt, Rpo256, RpoDigest, Word, LEN1, LEN2, LOG_W, W, WOTS_LEN};
+
+// DOMAIN SEPARATORS
+// ================================================================================================
+
+/// Domain separator for deriving the secret elements of the one-time keys from the secret seed.
+const SECRET_DOMAIN: Felt = Felt::new(1);
+
+/// Domain separator for the hash function iterated along the WOTS+ chains.
+const CHAIN_DOMAIN: Felt = Felt::new(2);
+
+/// Domain separator for hashing messages into the digests signed by the one-time keys.
+const MESSAGE_DOMAIN: Felt = Felt::new(3);
+
+/// Domain separator for compressing one-time public keys into the leaves of the Merkle tree.
+const LEAF_DOMAIN: Felt = Felt::new(4);
+
+// ONE-TIME SIGNATURES
+// ================================================================================================
+
+/// Returns the leaf of the Merkle tree corresponding to the one-time key at the specified leaf
+/// index.
+pub(super) fn leaf(seed: &Word, pub_seed: &Word, leaf: u64) -> Word {
+    let public_key: Vec<RpoDigest> = (0..WOTS_LEN)
+        .map(|chain| {
+            let secret = secret_element(seed, leaf, chain);
+            chain_hash(secret, pub_seed, leaf, chain, 0, W - 1)
+        })
+        .collect();
+    compress(&public_key)
+}
+
+/// Signs the provided digits of a message digest with the one-time key at the specified leaf
+/// index.
+pub(super) fn sign(
+    seed: &Word,
+    pub_seed: &Word,
+    leaf: u64,
+    digits: &[usize; WOTS_LEN],
+) -> [RpoDigest; WOTS_LEN] {
+    let mut signature = [RpoDigest::default(); WOTS_LEN];
+    for (chain, (element, &digit)) in signature.iter_mut().zip(digits).enumerate() {
+        *element = chain_hash(secret_element(seed, leaf, chain), pub_seed, leaf, chain, 0, digit);
+    }
+    signature
+}
+
+/// Returns the leaf of the Merkle tree corresponding to the one-time public key recovered from
+/// the provided signature of the provided digits of a message digest.
+pub(super) fn leaf_from_signature(
+    signature: &[RpoDigest; WOTS_LEN],
+    pub_seed: &Word,
+    leaf: u64,
+    digits: &[usize; WOTS_LEN],
+) -> Word {
+    let public_key: Vec<RpoDigest> = signature
+        .iter()
+        .zip(digits)
+        .enumerate()
+        .map(|(chain, (&element, &digit))| {
+            chain_hash(element, pub_seed, leaf, chain, digit, W - 1 - digit)
+        })
+        .collect();
+    compress(&public_key)
+}
+
+/// Returns the base-w digits of the digest of the provided message, followed by the base-w
+/// digits of their checksum.
+///
+/// The message is hashed together with the root of the tree and the leaf index so that a forger
+/// cannot target all keys at once.
+pub(super) fn message_digits(root: &RpoDigest, leaf: u64, message: Word) -> [usize; WOTS_LEN] {
+    let mut elements = Vec::with_capacity(10);
+    elements.push(MESSAGE_DOMAIN);
+    elements.extend_from_slice(root.as_elements());
+    elements.push(Felt::new(leaf));
+    elements.extend_from_slice(&message);
+    let digest = Rpo256::hash_elements(&elements);
+
+    let mut digits = [0_usize; WOTS_LEN];
+    let chunks_per_element = 64 / LOG_W;
+    for (i, element) in digest.iter().enumerate() {
+        let value = element.as_int();
+        for j in 0..chunks_per_element {
+            digits[i * chunks_per_element + j] = ((value >> (j * LOG_W)) as usize) & (W - 1);
+        }
+    }
+
+    let checksum: usize = digits[..LEN1].iter().map(|digit| W - 1 - digit).sum();
+    for j in 0..LEN2 {
+        digits[LEN1 + j] = (checksum >> (j * LOG_W)) & (W - 1);
+    }
+    digits
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the secret starting element of the specified chain of the one-time key at the
+/// specified leaf index.
+fn secret_element(seed: &Word, leaf: u64, chain: usize) -> RpoDigest {
+    let mut elements = [SECRET_DOMAIN; 7];
+    elements[1..5].copy_from_slice(seed);
+    elements[5] = Felt::new(leaf);
+    elements[6] = Felt::new(chain as u64);
+    Rpo256::hash_elements(&elements)
+}
+
+/// Applies `steps` iterations of the chain hash function to the provided value, starting at
+/// position `start` of the specified chain.
+///
+/// Each iteration is tweaked with the public seed and the position within the chain so that
+/// each hash invocation in the scheme is distinct.
+fn chain_hash(
+    mut value: RpoDigest,
+    pub_seed: &Word,
+    leaf: u64,
+    chain: usize,
+    start: usize,
+    steps: usize,
+) -> RpoDigest {
+    let mut elements = [CHAIN_DOMAIN; 12];
+    elements[1..5].copy_from_slice(pub_seed);
+    elements[5] = Felt::new(leaf);
+    elements[6] = Felt::new(chain as u64);
+    for step in start..start + steps {
+        elements[7] = Felt::new(step as u64);
+        elements[8..].copy_from_slice(value.as_elements());
+        value = Rpo256::hash_elements(&elements);
+    }
+    value
+}
+
+/// Compresses a one-time public key into a leaf of the Merkle tree.
+fn compress(public_key: &[RpoDigest]) -> Word {
+    let mut elements = Vec::with_capacity(1 + public_key.len() * 4);
+    elements.push(LEAF_DOMAIN);
+    elements.extend(RpoDigest::digests_as_elements(public_key.iter()).copied());
+    Rpo256::hash_elements(&elements).into()
+}
@@ -0,0 +1,536 @@
+use core::ops::Range;
+
+use super::{rpo::RpoDigest, ElementHasher, Felt, FieldElement, Hasher, StarkField, ONE, ZERO};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The number of rounds is set to 11, which is the number of rounds required by the
+/// [specifications](https://eprint.iacr.org/2022/840) for 128-bit security with a state of 4
+/// columns and an S-Box of degree 7.
+const NUM_ROUNDS: usize = 11;
+
+/// Sponge state is set to 8 field elements; 4 elements are reserved for rate and the remaining 4
+/// elements are reserved for capacity.
+const STATE_WIDTH: usize = 8;
+
+/// The number of columns of the state. The state is viewed as two vectors of `NUM_COLUMNS`
+/// elements: `x` (elements 0 through 3) and `y` (elements 4 through 7).
+const NUM_COLUMNS: usize = STATE_WIDTH / 2;
+
+/// The rate portion of the state is located in elements 0 through 3.
+const RATE_RANGE: Range<usize> = 0..4;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+/// The capacity portion of the state is located in elements 4, 5, 6, and 7.
+const CAPACITY_RANGE: Range<usize> = 4..8;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 0, 1, 2, and 3 (the rate portion).
+const DIGEST_RANGE: Range<usize> = 0..4;
+
+/// The number of bytes needed to encoded a field element when hashing a byte string.
+const BINARY_CHUNK_SIZE: usize = 7;
+
+/// S-Box and inverse S-Box powers. The S-Box of Anemoi is the Flystel, which uses both the power
+/// map x^ALPHA (in its closed form) and its inverse x^INV_ALPHA (in its open form, used to
+/// evaluate the permutation).
+const ALPHA: u64 = 7;
+const INV_ALPHA: u64 = 10540996611094048183;
+
+/// The multiplicative generator of the field; this is the constant `β` of the quadratic functions
+/// of the Flystel.
+const GENERATOR: Felt = Felt::new(7);
+
+/// The inverse of the multiplicative generator of the field; this is the constant `δ` of the
+/// quadratic functions of the Flystel.
+const GENERATOR_INV: Felt = Felt::new(2635249152773512046);
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of the Anemoi hash function with 256-bit output.
+///
+/// The hash function is instantiated as described in the [specifications](https://eprint.iacr.org/2022/840).
+/// The parameters used to instantiate the function are:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 8 field elements, viewed as 4 columns of `(x, y)` pairs.
+/// * Capacity size: 4 field elements.
+/// * S-Box: the open Flystel with `Q(y) = 7 * y^2` and `E(x) = x^7`, i.e.,
+///   `x -= 7 * y^2`, `y -= x^(1/7)`, `x += 7 * y^2 + 7^(-1)`.
+/// * Number of rounds: 11.
+/// * Round: `add_constants` → `apply_linear_layer` → `apply_sbox`, followed by a final
+///   `apply_linear_layer` after the last round.
+///
+/// The above parameters target a 128-bit security level. The digest consists of four field elements
+/// and it can be serialized into 32 bytes (256 bits). Digests are returned as [RpoDigest] values, since
+/// they have the same shape, so that they can be stored and serialized using the same tooling as RPO
+/// digests.
+///
+/// ## 2-to-1 compression
+/// [merge()](Anemoi256::merge) does not use the sponge construction; instead, it compresses two
+/// digests using the Jive mode from the specifications: the two digests are used as the initial
+/// state of the permutation, and the result is the sum of the two digests and of the two halves of
+/// the permuted state. This requires a single permutation over a state of 8 elements, which makes
+/// Merkle tree hashing considerably cheaper to arithmetize than with RPO.
+///
+/// As a consequence, unlike [Rpo256](super::rpo::Rpo256), [merge()](Anemoi256::merge) is not
+/// consistent with [hash_elements()](Anemoi256::hash_elements): merging two digests produces a
+/// different result than hashing the 8 elements which make up these digests.
+///
+/// ## Hash output consistency
+/// Similarly to [Rpo256](super::rpo::Rpo256), [hash()](Anemoi256::hash) function is not
+/// consistent with [hash_elements()](Anemoi256::hash_elements), since it needs to be able to
+/// handle arbitrary binary strings, which may or may not encode valid field elements.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Anemoi256();
+
+impl Hasher for Anemoi256 {
+    /// Anemoi256 collision resistance is 128-bits.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = RpoDigest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // initialize the state with zeroes
+        let mut state = [ZERO; STATE_WIDTH];
+
+        // determine the number of field elements needed to encode `bytes` when each field element
+        // represents at most 7 bytes; an empty byte string is encoded as a single padded empty
+        // chunk, so that its hash is not the state which results from absorbing nothing.
+        let num_field_elem = bytes.len().div_ceil(BINARY_CHUNK_SIZE).max(1);
+
+        // set the first capacity element to `RATE_WIDTH + (num_field_elem % RATE_WIDTH)`. We do
+        // this to achieve:
+        // 1. Domain separating hashing of `[u8]` from hashing of `[Felt]`.
+        // 2. Avoiding collisions at the `[Felt]` representation of the encoded bytes.
+        state[CAPACITY_RANGE.start] =
+            Felt::from((RATE_WIDTH + (num_field_elem % RATE_WIDTH)) as u8);
+
+        // initialize a buffer to receive the little-endian elements.
+        let mut buf = [0_u8; 8];
+
+        // iterate the chunks of bytes, creating a field element from each chunk and adding it
+        // into the rate portion of the state; every time the rate range is filled, a permutation
+        // is performed.
+        let empty_chunk = core::iter::once(&[][..]).filter(|_| bytes.is_empty());
+        let chunks = empty_chunk.chain(bytes.chunks(BINARY_CHUNK_SIZE)).enumerate();
+        let i = chunks.fold(0, |i, (chunk_idx, chunk)| {
+            // copy the chunk into the buffer
+            if chunk_idx != num_field_elem - 1 {
+                buf[..BINARY_CHUNK_SIZE].copy_from_slice(chunk);
+            } else {
+                // on the last iteration, we pad `buf` with a 1 followed by as many 0's as are
+                // needed to fill it
+                buf.fill(0);
+                buf[..chunk.len()].copy_from_slice(chunk);
+                buf[chunk.len()] = 1;
+            }
+
+            // since we take at most 7 bytes, we are guaranteed that the inputs data will fit
+            // into a single field element.
+            state[RATE_RANGE.start + i] += Felt::new(u64::from_le_bytes(buf));
+
+            if i == RATE_WIDTH - 1 {
+                Self::apply_permutation(&mut state);
+                0
+            } else {
+                i + 1
+            }
+        });
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the permutation. we don't
+        // need to apply any extra padding because the first capacity element contains a flag
+        // indicating the number of field elements constituting the last block.
+        if i != 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        Self::compress(values)
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // hash the seed followed by the value; if the value doesn't fit into a single field
+        // element, it is split into two field elements. inputs of 5 and 6 elements are told apart
+        // by the padding applied by hash_elements().
+        let mut elements = [ZERO; 6];
+        elements[..4].copy_from_slice(seed.as_elements());
+        elements[4] = Felt::new(value);
+        if value < Felt::MODULUS {
+            Self::hash_elements(&elements[..5])
+        } else {
+            elements[5] = Felt::new(value / Felt::MODULUS);
+            Self::hash_elements(&elements)
+        }
+    }
+}
+
+impl ElementHasher for Anemoi256 {
+    type BaseField = Felt;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::slice_as_base_elements(elements);
+
+        // the input is padded by appending a 1 followed by as many 0 as necessary to make its length
+        // a multiple of RATE_WIDTH, unless its length already is a non-zero multiple of RATE_WIDTH;
+        // an empty input is thus padded into a single block.
+        let is_padded = elements.is_empty() || elements.len() % RATE_WIDTH != 0;
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to 1 if the input is padded.
+        let mut state = [ZERO; STATE_WIDTH];
+        if is_padded {
+            state[CAPACITY_RANGE.start] = ONE;
+        }
+
+        // absorb elements into the state one by one until the rate portion of the state is filled
+        // up; then apply the permutation and start absorbing again; repeat until all elements have
+        // been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] += element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // absorb the padding and apply the final permutation; since elements are absorbed by
+        // addition, the zeros of the padding do not need to be added.
+        if is_padded {
+            state[RATE_RANGE.start + i] += ONE;
+            Self::apply_permutation(&mut state);
+        }
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Anemoi256 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds is set to 11 to target 128-bit security level.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 8 field elements; 4 elements are reserved for rate and the remaining
+    /// 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 0 through 3 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 4, 5, 6, and 7.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 0, 1, 2, and 3.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// MDS matrix used for mixing the columns of `x` and `y` in the linear layer.
+    pub const MDS: [[Felt; NUM_COLUMNS]; NUM_COLUMNS] = MDS;
+
+    /// Round constants added to the `x` part of the state.
+    pub const ROUND_CONSTANTS_C: [[Felt; NUM_COLUMNS]; NUM_ROUNDS] = ROUND_CONSTANTS_C;
+
+    /// Round constants added to the `y` part of the state.
+    pub const ROUND_CONSTANTS_D: [[Felt; NUM_COLUMNS]; NUM_ROUNDS] = ROUND_CONSTANTS_D;
+
+    /// The power map used by the closed Flystel, i.e., the degree of the relations which the
+    /// S-Box satisfies.
+    pub const ALPHA: u64 = ALPHA;
+
+    /// The power map used by the open Flystel to evaluate the S-Box, i.e., the inverse of
+    /// [Self::ALPHA] modulo `p - 1`.
+    pub const INV_ALPHA: u64 = INV_ALPHA;
+
+    // TRAIT PASS-THROUGH FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a hash of the provided sequence of bytes.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> RpoDigest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    ///
+    /// The digests are compressed using the Jive mode; see [Anemoi256] for details.
+    #[inline(always)]
+    pub fn merge(values: &[RpoDigest; 2]) -> RpoDigest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E: FieldElement<BaseField = Felt>>(elements: &[E]) -> RpoDigest {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+
+    // JIVE COMPRESSION
+    // --------------------------------------------------------------------------------------------
+
+    /// Compresses two digests into one using the Jive mode.
+    ///
+    /// The two digests are copied into the state, the permutation is applied, and the result is
+    /// computed as `a + b + P(a, b)[0..4] + P(a, b)[4..8]`.
+    #[inline(always)]
+    pub fn compress(values: &[RpoDigest; 2]) -> RpoDigest {
+        let mut state = [ZERO; STATE_WIDTH];
+        state[..NUM_COLUMNS].copy_from_slice(values[0].as_elements());
+        state[NUM_COLUMNS..].copy_from_slice(values[1].as_elements());
+        let input = state;
+
+        Self::apply_permutation(&mut state);
+
+        let mut result = [ZERO; NUM_COLUMNS];
+        for (i, r) in result.iter_mut().enumerate() {
+            *r = input[i] + input[NUM_COLUMNS + i] + state[i] + state[NUM_COLUMNS + i];
+        }
+        RpoDigest::new(result)
+    }
+
+    // ANEMOI PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies the Anemoi permutation to the provided state.
+    #[inline(always)]
+    pub fn apply_permutation(state: &mut [Felt; STATE_WIDTH]) {
+        #[cfg(feature = "metrics")]
+        crate::hash::metrics::record(&crate::hash::metrics::ANEMOI256_PERMUTATIONS, 1);
+        for i in 0..NUM_ROUNDS {
+            Self::apply_round(state, i);
+        }
+        apply_linear_layer(state);
+    }
+
+    /// Anemoi round function.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [Felt; STATE_WIDTH], round: usize) {
+        add_constants(state, round);
+        apply_linear_layer(state);
+        apply_sbox(state);
+    }
+}
+
+// ROUND FUNCTIONS
+// ================================================================================================
+
+#[inline(always)]
+fn add_constants(state: &mut [Felt; STATE_WIDTH], round: usize) {
+    let (x, y) = state.split_at_mut(NUM_COLUMNS);
+    x.iter_mut().zip(ROUND_CONSTANTS_C[round]).for_each(|(s, c)| *s += c);
+    y.iter_mut().zip(ROUND_CONSTANTS_D[round]).for_each(|(s, d)| *s += d);
+}
+
+/// Applies the linear layer: `x` is multiplied by the MDS matrix, `y` is rotated to the left by
+/// one element and multiplied by the MDS matrix, and the two halves are mixed using the
+/// Pseudo-Hadamard transform `y += x; x += y`.
+#[inline(always)]
+fn apply_linear_layer(state: &mut [Felt; STATE_WIDTH]) {
+    let mut x = [ZERO; NUM_COLUMNS];
+    let mut y = [ZERO; NUM_COLUMNS];
+    for (i, row) in MDS.iter().enumerate() {
+        for (j, &m) in row.iter().enumerate() {
+            x[i] += m * state[j];
+            y[i] += m * state[NUM_COLUMNS + (j + 1) % NUM_COLUMNS];
+        }
+    }
+
+    for i in 0..NUM_COLUMNS {
+        y[i] += x[i];
+        x[i] += y[i];
+    }
+
+    state[..NUM_COLUMNS].copy_from_slice(&x);
+    state[NUM_COLUMNS..].copy_from_slice(&y);
+}
+
+/// Applies the open Flystel to each `(x, y)` column of the state.
+#[inline(always)]
+fn apply_sbox(state: &mut [Felt; STATE_WIDTH]) {
+    let (x, y) = state.split_at_mut(NUM_COLUMNS);
+    for (x, y) in x.iter_mut().zip(y.iter_mut()) {
+        *x -= GENERATOR * y.square();
+        *y -= x.exp(INV_ALPHA);
+        *x += GENERATOR * y.square() + GENERATOR_INV;
+    }
+}
+
+// MDS
+// ================================================================================================
+
+/// The MDS matrix for 4 columns from the specifications, instantiated with the generator `g = 7`:
+///
+/// ```text
+/// | 1     1 + g    g      g      |
+/// | g^2   g + g^2  1 + g  1 + 2g |
+/// | g^2   g^2      1      1 + g  |
+/// | 1 + g 1 + 2g   g      1 + g  |
+/// ```
+const MDS: [[Felt; NUM_COLUMNS]; NUM_COLUMNS] = [
+    [Felt::new(1), Felt::new(8), Felt::new(7), Felt::new(7)],
+    [Felt::new(49), Felt::new(56), Felt::new(8), Felt::new(15)],
+    [Felt::new(49), Felt::new(49), Felt::new(1), Felt::new(8)],
+    [Felt::new(8), Felt::new(15), Felt::new(7), Felt::new(8)],
+];
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Anemoi round constants; computed as in the [specifications](https://eprint.iacr.org/2022/840)
+/// from the first 200 decimal digits of π (split into `π_0` and `π_1`, reduced modulo p) as:
+///
+/// * `C[r][i] = g * (π_0^r)^2 + (π_0^r + π_1^i)^ALPHA`
+/// * `D[r][i] = g * (π_1^i)^2 + (π_0^r + π_1^i)^ALPHA + g^(-1)`
+const ROUND_CONSTANTS_C: [[Felt; NUM_COLUMNS]; NUM_ROUNDS] = [
+    [
+        Felt::new(135),
+        Felt::new(11838461599927962280),
+        Felt::new(6695425721971157745),
+        Felt::new(5019557931754813304),
+    ],
+    [
+        Felt::new(2495927434367559449),
+        Felt::new(13492968121013574904),
+        Felt::new(10098914474676720416),
+        Felt::new(4843826562618834151),
+    ],
+    [
+        Felt::new(6426088485207136111),
+        Felt::new(5831914601985236665),
+        Felt::new(3131753839109558676),
+        Felt::new(3931787235364236402),
+    ],
+    [
+        Felt::new(15630856188639316257),
+        Felt::new(1985318265374982878),
+        Felt::new(2310933915074061319),
+        Felt::new(82460173033028635),
+    ],
+    [
+        Felt::new(35751876663066701),
+        Felt::new(12218846398244870934),
+        Felt::new(12158495329324236791),
+        Felt::new(14193345342118923896),
+    ],
+    [
+        Felt::new(10762311532495310736),
+        Felt::new(1602790612750887683),
+        Felt::new(10558955589715575707),
+        Felt::new(4268644503140521285),
+    ],
+    [
+        Felt::new(13776040199363511613),
+        Felt::new(2645507812358398727),
+        Felt::new(11189896514172536637),
+        Felt::new(2832927452123329016),
+    ],
+    [
+        Felt::new(7954822014402935159),
+        Felt::new(4221275062851635374),
+        Felt::new(4146348742796636803),
+        Felt::new(11984179805579125028),
+    ],
+    [
+        Felt::new(16207264356032897204),
+        Felt::new(14947325633951015074),
+        Felt::new(12682075457336921621),
+        Felt::new(3428661900037965523),
+    ],
+    [
+        Felt::new(11395204924706988113),
+        Felt::new(1445298492587981366),
+        Felt::new(1648821176782737788),
+        Felt::new(15250803325479081487),
+    ],
+    [
+        Felt::new(16536231370017439012),
+        Felt::new(7360252512197717923),
+        Felt::new(2705763861182069829),
+        Felt::new(10717289112464514560),
+    ],
+];
+
+const ROUND_CONSTANTS_D: [[Felt; NUM_COLUMNS]; NUM_ROUNDS] = [
+    [
+        Felt::new(2635249152773512181),
+        Felt::new(6157005413239783064),
+        Felt::new(68462008871283793),
+        Felt::new(1116237429717582885),
+    ],
+    [
+        Felt::new(5762606100895777574),
+        Felt::new(8442941448080101767),
+        Felt::new(4103380275331552543),
+        Felt::new(1571935574336309811),
+    ],
+    [
+        Felt::new(13733991889676916174),
+        Felt::new(4823112666993325466),
+        Felt::new(1177444377705952741),
+        Felt::new(4701120985023274000),
+    ],
+    [
+        Felt::new(8470567618884092005),
+        Felt::new(4955068425572651685),
+        Felt::new(4335176548860035390),
+        Felt::new(4830346017881646239),
+    ],
+    [
+        Felt::new(1762538920950224372),
+        Felt::new(5628928103070337343),
+        Felt::new(4623069507738008464),
+        Felt::new(9381562731595339102),
+    ],
+    [
+        Felt::new(7562502807542755678),
+        Felt::new(8533020617751225684),
+        Felt::new(16543678068304218972),
+        Felt::new(12977010192791808083),
+    ],
+    [
+        Felt::new(12515775684509917073),
+        Felt::new(11515282027457697246),
+        Felt::new(667419133445556099),
+        Felt::new(13480837351873576332),
+    ],
+    [
+        Felt::new(4309793999236275086),
+        Felt::new(10706285777637868360),
+        Felt::new(9685851931171175053),
+        Felt::new(1800582135601722490),
+    ],
+    [
+        Felt::new(168687491574439534),
+        Felt::new(9038787499445450463),
+        Felt::new(5828029796419662274),
+        Felt::new(17745003519597934030),
+    ],
+    [
+        Felt::new(16776936635835678651),
+        Felt::new(16957068933669564963),
+        Felt::new(16215084091452626649),
+        Felt::new(14093965381797029560),
+    ],
+    [
+        Felt::new(14988442143969286076),
+        Felt::new(15942502016102458046),
+        Felt::new(10342505838675115216),
+        Felt::new(2630930231605619159),
+    ],
+];
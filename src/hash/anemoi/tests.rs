@@ -0,0 +1,217 @@
+use proptest::prelude::*;
+use rand_utils::rand_value;
+
+use super::*;
+use crate::{hash::rpo::Rpo256, ONE};
+use alloc::vec::Vec;
+
+const PI_0: &str =
+    "1415926535897932384626433832795028841971693993751058209749445923078164062862089986280348253421170679";
+const PI_1: &str =
+    "8214808651328230664709384460955058223172535940812848111745028410270193852110555964462294895493038196";
+
+fn decimal_to_felt(digits: &str) -> Felt {
+    digits.bytes().fold(ZERO, |acc, d| acc * Felt::new(10) + Felt::from(d - b'0'))
+}
+
+#[test]
+fn constants() {
+    let x: Felt = rand_value();
+    assert_eq!(x.exp(ALPHA).exp(INV_ALPHA), x);
+    assert_eq!(GENERATOR * GENERATOR_INV, ONE);
+
+    let pi_0 = decimal_to_felt(PI_0);
+    let pi_1 = decimal_to_felt(PI_1);
+    for r in 0..NUM_ROUNDS {
+        let pi_0_r = pi_0.exp(r as u64);
+        for i in 0..NUM_COLUMNS {
+            let pi_1_i = pi_1.exp(i as u64);
+            let sum_alpha = (pi_0_r + pi_1_i).exp(ALPHA);
+            let c = GENERATOR * pi_0_r.square() + sum_alpha;
+            let d = GENERATOR * pi_1_i.square() + sum_alpha + GENERATOR_INV;
+            assert_eq!(c, ROUND_CONSTANTS_C[r][i]);
+            assert_eq!(d, ROUND_CONSTANTS_D[r][i]);
+        }
+    }
+}
+
+#[test]
+fn sbox_satisfies_closed_flystel() {
+    // the open Flystel (x, y) -> (u, v) is verified in-circuit by checking the low-degree
+    // relations of the closed Flystel:
+    // x = (y - v)^ALPHA + g * y^2 and u = (y - v)^ALPHA + g * v^2 + g^(-1)
+    let input: [Felt; STATE_WIDTH] = core::array::from_fn(|_| rand_value());
+    let mut output = input;
+    apply_sbox(&mut output);
+
+    for i in 0..NUM_COLUMNS {
+        let (x, y) = (input[i], input[NUM_COLUMNS + i]);
+        let (u, v) = (output[i], output[NUM_COLUMNS + i]);
+        let t = (y - v).exp(ALPHA);
+        assert_eq!(x, t + GENERATOR * y.square());
+        assert_eq!(u, t + GENERATOR * v.square() + GENERATOR_INV);
+    }
+}
+
+#[test]
+fn anemoi_merge() {
+    let digests = [
+        RpoDigest::new(core::array::from_fn(|i| Felt::new(i as u64))),
+        RpoDigest::new(core::array::from_fn(|i| Felt::new(i as u64 + 4))),
+    ];
+
+    // merging is the Jive compression of the two digests
+    let mut state: [Felt; STATE_WIDTH] = core::array::from_fn(|i| Felt::new(i as u64));
+    Anemoi256::apply_permutation(&mut state);
+    let expected: [Felt; 4] =
+        core::array::from_fn(|i| Felt::new((2 * i + 4) as u64) + state[i] + state[i + 4]);
+    assert_eq!(Anemoi256::merge(&digests), RpoDigest::new(expected));
+    assert_eq!(Anemoi256::merge(&digests), Anemoi256::compress(&digests));
+
+    // merging is not consistent with hashing the elements, and differs from RPO
+    let elements: Vec<Felt> = RpoDigest::digests_as_elements(digests.iter()).copied().collect();
+    assert_ne!(Anemoi256::merge(&digests), Anemoi256::hash_elements(&elements));
+    assert_ne!(Anemoi256::merge(&digests), Rpo256::merge(&digests));
+
+    // the order of the digests matters
+    assert_ne!(Anemoi256::merge(&digests), Anemoi256::merge(&[digests[1], digests[0]]));
+}
+
+#[test]
+fn anemoi_merge_with_int() {
+    let seed = Anemoi256::hash(b"seed");
+    let elements: Vec<Felt> = seed.as_elements().iter().copied().chain([Felt::new(42)]).collect();
+    assert_eq!(Anemoi256::merge_with_int(seed, 42), Anemoi256::hash_elements(&elements));
+
+    let value = Felt::MODULUS + 5;
+    let elements: Vec<Felt> =
+        seed.as_elements().iter().copied().chain([Felt::new(value), ONE]).collect();
+    assert_eq!(Anemoi256::merge_with_int(seed, value), Anemoi256::hash_elements(&elements));
+}
+
+#[test]
+fn anemoi_hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Anemoi256::hash(&[1_u8, 2, 3]);
+    let r2 = Anemoi256::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // same as above but with bigger inputs
+    let r1 = Anemoi256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18]);
+    let r2 =
+        Anemoi256::hash(&[1_u8, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 0]);
+    assert_ne!(r1, r2);
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let elements = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4), Felt::new(5)];
+    let r1 = Anemoi256::hash_elements(&elements);
+    let r2 = Anemoi256::hash_elements(&[elements.as_slice(), &[ZERO]].concat());
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn anemoi_hash_elements_vs_hash() {
+    // hashing bytes is domain separated from hashing the field elements the bytes decode into
+    let bytes = [1_u8, 2, 3, 4, 5, 6, 7];
+    let mut buf = [0_u8; 8];
+    buf[..7].copy_from_slice(&bytes);
+    buf[7] = 1;
+    let element = Felt::new(u64::from_le_bytes(buf));
+    assert_ne!(Anemoi256::hash(&bytes), Anemoi256::hash_elements(&[element]));
+}
+
+#[test]
+fn anemoi_hash_empty_input() {
+    // empty inputs are padded into a single block, and thus their hashes are not the zero digest
+    let zero = RpoDigest::default();
+    assert_ne!(Anemoi256::hash(&[]), zero);
+    assert_ne!(Anemoi256::hash_elements::<Felt>(&[]), zero);
+
+    // the padded empty input is domain separated from a block which looks like the padding
+    let empty = Anemoi256::hash_elements::<Felt>(&[]);
+    assert_ne!(empty, Anemoi256::hash_elements(&[ZERO; RATE_WIDTH]));
+    assert_ne!(empty, Anemoi256::hash_elements(&[ONE, ZERO, ZERO, ZERO]));
+    assert_ne!(Anemoi256::hash(&[]), Anemoi256::hash(&[0]));
+    assert_ne!(Anemoi256::hash(&[]), empty);
+}
+
+#[test]
+fn num_rounds_matches_specifications() {
+    // the specifications require max(8, min(5, l + 1) + 2 + r) rounds for 128-bit security, where
+    // l is the number of columns and r is the smallest integer such that
+    // binomial(4 * l * r + k, 2 * l * r)^2 >= 2^128, with k = 4 for ALPHA = 7
+    let binomial = |n: u128, k: u128| (0..k).fold(1_u128, |acc, i| acc * (n - i) / (i + 1));
+    let l = NUM_COLUMNS as u128;
+    let r = (1..).find(|&r| binomial(4 * l * r + 4, 2 * l * r) >= 1 << 64).unwrap();
+    assert_eq!(ALPHA, 7);
+    assert_eq!(NUM_ROUNDS as u128, 8.max(5.min(l + 1) + 2 + r));
+}
+
+#[test]
+fn anemoi_regression_vectors() {
+    // no reference test vectors are available for this instance of Anemoi (64-bit field, 4
+    // columns); these vectors were computed by this implementation and guard against accidental
+    // changes to the permutation, the sponge and the Jive compression
+    let mut state: [Felt; STATE_WIDTH] = core::array::from_fn(|i| Felt::new(i as u64));
+    Anemoi256::apply_permutation(&mut state);
+    let expected = [
+        15954903169837751377,
+        6397089886364990261,
+        8911563813348774342,
+        2822055177728336360,
+        9124185198208516278,
+        16630780401420581036,
+        6506191262375123273,
+        17830597779046807035,
+    ];
+    assert_eq!(state, expected.map(Felt::new));
+
+    let elements: Vec<Felt> = (0..8).map(Felt::new).collect();
+    let expected = [
+        10570892970427305641,
+        3706821331378291296,
+        6467965155953772118,
+        13238971434910861492,
+    ];
+    assert_eq!(Anemoi256::hash_elements(&elements), RpoDigest::new(expected.map(Felt::new)));
+
+    let expected = [
+        3701096464864082814,
+        9942257868189689392,
+        7821930145189090852,
+        11831231710108752142,
+    ];
+    assert_eq!(Anemoi256::hash(b"abc"), RpoDigest::new(expected.map(Felt::new)));
+
+    let digests = [
+        RpoDigest::new(core::array::from_fn(|i| Felt::new(i as u64))),
+        RpoDigest::new(core::array::from_fn(|i| Felt::new(i as u64 + 4))),
+    ];
+    let expected = [
+        6632344298631683338,
+        4581126218370986982,
+        15417755075723897623,
+        2205908887360559084,
+    ];
+    assert_eq!(Anemoi256::merge(&digests), RpoDigest::new(expected.map(Felt::new)));
+}
+
+proptest! {
+    #[test]
+    fn anemoi_hash_elements_padding(ref values in any::<Vec<u64>>(), extra in any::<u64>()) {
+        let elements: Vec<Felt> = values.iter().map(|&v| Felt::new(v)).collect();
+        let digest = Anemoi256::hash_elements(&elements);
+        prop_assert_eq!(Anemoi256::hash_elements(&elements), digest);
+        prop_assert_ne!(digest, Rpo256::hash_elements(&elements));
+
+        // appending any element, including a zero, changes the hash
+        let extended: Vec<Felt> = elements.iter().copied().chain([Felt::new(extra)]).collect();
+        prop_assert_ne!(Anemoi256::hash_elements(&extended), digest);
+    }
+
+    #[test]
+    fn anemoi_hash_bytes_padding(ref bytes in any::<Vec<u8>>(), extra in any::<u8>()) {
+        let extended: Vec<u8> = bytes.iter().copied().chain([extra]).collect();
+        prop_assert_ne!(Anemoi256::hash(&extended), Anemoi256::hash(bytes));
+    }
+}
@@ -1,11 +1,15 @@
-use alloc::string::String;
+use alloc::{string::String, vec, vec::Vec};
 use core::{
     mem::{size_of, transmute, transmute_copy},
     ops::Deref,
     slice::from_raw_parts,
 };
 
-use super::{Digest, ElementHasher, Felt, FieldElement, Hasher};
+use subtle::ConstantTimeEq;
+
+#[cfg(feature = "std")]
+use super::{read_chunk, READER_BUFFER_SIZE};
+use super::{Digest, ElementHasher, Felt, FieldElement, Hasher, Xof, XofReader};
 use crate::utils::{
     bytes_to_hex_string, hex_to_bytes, ByteReader, ByteWriter, Deserializable,
     DeserializationError, HexParseError, Serializable,
@@ -21,6 +25,25 @@ const DIGEST32_BYTES: usize = 32;
 const DIGEST24_BYTES: usize = 24;
 const DIGEST20_BYTES: usize = 20;
 
+/// The number of bytes in a key for the keyed mode of BLAKE3.
+pub const KEY_BYTES: usize = 32;
+
+/// The number of bytes in a BLAKE3 block.
+const BLOCK_LEN: usize = 64;
+
+/// The number of bytes in a BLAKE3 chunk.
+const CHUNK_LEN: usize = 1024;
+
+/// BLAKE3 initialization vector (the same as the one of SHA-256).
+const IV: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+/// Domain separation flags of the BLAKE3 compression function.
+const CHUNK_START: u8 = 1 << 0;
+const CHUNK_END: u8 = 1 << 1;
+const ROOT: u8 = 1 << 3;
+
 // BLAKE3 N-BIT OUTPUT
 // ================================================================================================
 
@@ -28,11 +51,21 @@ const DIGEST20_BYTES: usize = 20;
 ///
 /// Note: `N` can't be greater than `32` because [`Digest::as_bytes`] currently supports only 32
 /// bytes.
-#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(into = "String", try_from = "&str"))]
 pub struct Blake3Digest<const N: usize>([u8; N]);
 
+impl<const N: usize> Blake3Digest<N> {
+    /// Returns true if this digest is equal to the other digest.
+    ///
+    /// Unlike `==`, the running time of the comparison does not depend on the contents of the
+    /// digests, which makes it suitable for comparing digests derived from secret data.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&other.0).into()
+    }
+}
+
 impl<const N: usize> Default for Blake3Digest<N> {
     fn default() -> Self {
         Self([0; N])
@@ -133,6 +166,18 @@ impl ElementHasher for Blake3_256 {
     }
 }
 
+impl Xof for Blake3_256 {
+    type Reader = Blake3XofReader;
+
+    fn hash_xof_many(inputs: &[&[u8]]) -> Self::Reader {
+        let mut hasher = blake3::Hasher::new();
+        for input in inputs {
+            hasher.update(input);
+        }
+        Blake3XofReader(hasher.finalize_xof())
+    }
+}
+
 impl Blake3_256 {
     /// Returns a hash of the provided sequence of bytes.
     #[inline(always)]
@@ -155,6 +200,105 @@ impl Blake3_256 {
     {
         <Self as ElementHasher>::hash_elements(elements)
     }
+
+    /// Returns a hash of the provided sequence of bytes computed using the keyed mode of BLAKE3,
+    /// which can be used as a MAC or a PRF.
+    #[inline(always)]
+    pub fn keyed_hash(key: &[u8; KEY_BYTES], bytes: &[u8]) -> Blake3Digest<DIGEST32_BYTES> {
+        Blake3Digest(blake3::keyed_hash(key, bytes).into())
+    }
+
+    /// Returns a hash of all bytes read from the provided reader until EOF.
+    ///
+    /// The input is read in fixed-size chunks, and the result is the same as the result of
+    /// [Self::hash()] over the entire input.
+    ///
+    /// # Errors
+    /// Returns an error if reading from `reader` fails with an error other than
+    /// [ErrorKind::Interrupted](std::io::ErrorKind::Interrupted).
+    #[cfg(feature = "std")]
+    pub fn hash_reader<R: std::io::Read>(
+        mut reader: R,
+    ) -> std::io::Result<Blake3Digest<DIGEST32_BYTES>> {
+        let mut hasher = blake3::Hasher::new();
+        let mut buf = [0_u8; READER_BUFFER_SIZE];
+        loop {
+            let len = read_chunk(&mut reader, &mut buf)?;
+            hasher.update(&buf[..len]);
+            if len < buf.len() {
+                return Ok(Blake3Digest(hasher.finalize().into()));
+            }
+        }
+    }
+
+    /// Returns a reader for an output of arbitrary length for the provided sequence of bytes,
+    /// the first 32 bytes of which are equal to [Self::hash()].
+    #[inline(always)]
+    pub fn hash_xof(bytes: &[u8]) -> Blake3XofReader {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(bytes);
+        Blake3XofReader(hasher.finalize_xof())
+    }
+
+    /// Returns a reader for an output of arbitrary length computed using the keyed mode of
+    /// BLAKE3, the first 32 bytes of which are equal to [Self::keyed_hash()].
+    #[inline(always)]
+    pub fn keyed_hash_xof(key: &[u8; KEY_BYTES], bytes: &[u8]) -> Blake3XofReader {
+        let mut hasher = blake3::Hasher::new_keyed(key);
+        hasher.update(bytes);
+        Blake3XofReader(hasher.finalize_xof())
+    }
+
+    /// Returns hashes of the provided inputs, each of which is `N` bytes long.
+    ///
+    /// The result is the same as the result of calling [Self::hash()] on every input, but when
+    /// `N` is a non-zero multiple of 64 and is at most 1024 (i.e., every input fits into a single
+    /// BLAKE3 chunk), the inputs are hashed together using the widest SIMD implementation
+    /// available on the platform. This makes hashing many small leaves (e.g., of a Merkle tree)
+    /// considerably faster than hashing them one by one.
+    pub fn hash_many<const N: usize>(inputs: &[&[u8; N]]) -> Vec<Blake3Digest<DIGEST32_BYTES>> {
+        if N == 0 || N % BLOCK_LEN != 0 || N > CHUNK_LEN {
+            return inputs.iter().map(|input| Self::hash(input.as_slice())).collect();
+        }
+
+        // every input is a single chunk which is also the root of its tree, and thus its hash is
+        // the chaining value of the chunk computed with the ROOT flag set on its last block.
+        //
+        // note: `blake3::platform` is a hidden module of the blake3 crate which may change in any
+        // release, and thus the blake3 dependency is pinned to an exact version; the tests of
+        // this method make sure that its results remain consistent with `blake3::hash()`.
+        let mut out = vec![0_u8; inputs.len() * DIGEST32_BYTES];
+        blake3::platform::Platform::detect().hash_many(
+            inputs,
+            &IV,
+            0,
+            blake3::IncrementCounter::No,
+            0,
+            CHUNK_START,
+            CHUNK_END | ROOT,
+            &mut out,
+        );
+
+        out.chunks_exact(DIGEST32_BYTES)
+            .map(|digest| Blake3Digest(digest.try_into().expect("invalid digest length")))
+            .collect()
+    }
+
+    /// Returns hashes of the provided pairs of digests, each of which is the same as the result
+    /// of [Self::merge()] on the pair.
+    ///
+    /// The pairs are merged together using [Self::hash_many()], and thus this method is intended
+    /// for computing one level of a Merkle tree at a time.
+    pub fn merge_many(
+        pairs: &[[Blake3Digest<DIGEST32_BYTES>; 2]],
+    ) -> Vec<Blake3Digest<DIGEST32_BYTES>> {
+        let inputs: Vec<[u8; 2 * DIGEST32_BYTES]> = pairs
+            .iter()
+            .map(|pair| prepare_merge(pair).try_into().expect("invalid merge input length"))
+            .collect();
+        let inputs: Vec<&[u8; 2 * DIGEST32_BYTES]> = inputs.iter().collect();
+        Self::hash_many(&inputs)
+    }
 }
 
 // BLAKE3 192-BIT OUTPUT
@@ -219,6 +363,13 @@ impl Blake3_192 {
     {
         <Self as ElementHasher>::hash_elements(elements)
     }
+
+    /// Returns a hash of the provided sequence of bytes computed using the keyed mode of BLAKE3,
+    /// which can be used as a MAC or a PRF.
+    #[inline(always)]
+    pub fn keyed_hash(key: &[u8; KEY_BYTES], bytes: &[u8]) -> Blake3Digest<DIGEST24_BYTES> {
+        Blake3Digest(*shrink_bytes(&blake3::keyed_hash(key, bytes).into()))
+    }
 }
 
 // BLAKE3 160-BIT OUTPUT
@@ -283,6 +434,36 @@ impl Blake3_160 {
     {
         <Self as ElementHasher>::hash_elements(elements)
     }
+
+    /// Returns a hash of the provided sequence of bytes computed using the keyed mode of BLAKE3,
+    /// which can be used as a MAC or a PRF.
+    #[inline(always)]
+    pub fn keyed_hash(key: &[u8; KEY_BYTES], bytes: &[u8]) -> Blake3Digest<DIGEST20_BYTES> {
+        Blake3Digest(*shrink_bytes(&blake3::keyed_hash(key, bytes).into()))
+    }
+}
+
+// BLAKE3 EXTENDABLE OUTPUT
+// ================================================================================================
+
+/// Reader for the extendable output of BLAKE3.
+///
+/// Consecutive reads return consecutive parts of the output, and thus the output does not depend
+/// on how it is split into reads.
+#[derive(Debug, Clone)]
+pub struct Blake3XofReader(blake3::OutputReader);
+
+impl Blake3XofReader {
+    /// Fills the provided buffer with the next bytes of the output.
+    pub fn squeeze_bytes(&mut self, output: &mut [u8]) {
+        self.0.fill(output);
+    }
+}
+
+impl XofReader for Blake3XofReader {
+    fn squeeze_bytes(&mut self, output: &mut [u8]) {
+        self.0.fill(output);
+    }
 }
 
 // HELPER FUNCTIONS
@@ -19,6 +19,96 @@ fn blake3_hash_elements() {
     assert_eq!(&expected, &actual);
 }
 
+#[test]
+fn blake3_digest_ct_eq() {
+    let digest = Blake3_256::hash(b"a");
+    assert!(digest.ct_eq(&Blake3_256::hash(b"a")));
+    assert!(!digest.ct_eq(&Blake3_256::hash(b"b")));
+}
+
+#[test]
+fn blake3_keyed_hash() {
+    let key = [7_u8; KEY_BYTES];
+    let data = b"some data";
+
+    let expected: [u8; 32] = blake3::keyed_hash(&key, data).into();
+    assert_eq!(Blake3_256::keyed_hash(&key, data), Blake3Digest(expected));
+    assert_eq!(Blake3_192::keyed_hash(&key, data), Blake3Digest(*shrink_bytes(&expected)));
+    assert_eq!(Blake3_160::keyed_hash(&key, data), Blake3Digest(*shrink_bytes(&expected)));
+
+    // the keyed mode should be separated from the unkeyed one
+    assert_ne!(Blake3_256::keyed_hash(&[0; KEY_BYTES], data), Blake3_256::hash(data));
+}
+
+#[test]
+fn blake3_xof() {
+    let key = [7_u8; KEY_BYTES];
+    let data = b"some data";
+
+    let mut output = [0_u8; 100];
+    Blake3_256::hash_xof(data).squeeze_bytes(&mut output);
+    assert_eq!(output[..32], *Blake3_256::hash(data));
+
+    // splitting reads should not change the output
+    let mut reader = Blake3_256::hash_xof(data);
+    let mut split_output = [0_u8; 100];
+    reader.squeeze_bytes(&mut split_output[..7]);
+    reader.squeeze_bytes(&mut split_output[7..]);
+    assert_eq!(output, split_output);
+
+    let mut keyed_output = [0_u8; 100];
+    Blake3_256::keyed_hash_xof(&key, data).squeeze_bytes(&mut keyed_output);
+    assert_eq!(keyed_output[..32], *Blake3_256::keyed_hash(&key, data));
+
+    // hashing multiple inputs via the Xof trait is the same as hashing their concatenation
+    let mut parts_output = [0_u8; 100];
+    <Blake3_256 as Xof>::hash_xof_many(&[b"some ", b"data"]).squeeze_bytes(&mut parts_output);
+    assert_eq!(output, parts_output);
+}
+
+#[test]
+fn blake3_hash_many() {
+    fn check<const N: usize>() {
+        let inputs: Vec<[u8; N]> =
+            (0..17).map(|_| rand_vector::<u8>(N.max(1))[..N].try_into().unwrap()).collect();
+        let inputs: Vec<&[u8; N]> = inputs.iter().collect();
+        let expected: Vec<_> = inputs.iter().map(|input| Blake3_256::hash(*input)).collect();
+        assert_eq!(Blake3_256::hash_many(&inputs), expected);
+    }
+
+    // inputs which are hashed in a batch
+    check::<64>();
+    check::<128>();
+    check::<1024>();
+
+    // inputs which are hashed one by one
+    check::<0>();
+    check::<32>();
+    check::<65>();
+    check::<1088>();
+
+    assert!(Blake3_256::hash_many::<64>(&[]).is_empty());
+}
+
+#[test]
+fn blake3_merge_many() {
+    let pairs: Vec<[Blake3Digest<32>; 2]> = (0..9)
+        .map(|i: u8| [Blake3_256::hash(&[i]), Blake3_256::hash(&[i, i])])
+        .collect();
+    let expected: Vec<_> = pairs.iter().map(Blake3_256::merge).collect();
+    assert_eq!(Blake3_256::merge_many(&pairs), expected);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn blake3_hash_reader() {
+    let bytes: Vec<u8> = rand_vector(3 * READER_BUFFER_SIZE + 5);
+    for len in [0, 1, READER_BUFFER_SIZE, bytes.len()] {
+        let digest = Blake3_256::hash_reader(&bytes[..len]).unwrap();
+        assert_eq!(digest, Blake3_256::hash(&bytes[..len]));
+    }
+}
+
 proptest! {
     #[test]
     fn blake160_wont_panic_with_arbitrary_input(ref vec in any::<Vec<u8>>()) {
@@ -0,0 +1,182 @@
+use ::blake2::{digest::consts::U32, Blake2b};
+
+use super::{byte_digest::byte_digest, ElementHasher, Felt, FieldElement, Hasher};
+
+#[cfg(test)]
+mod tests;
+
+// BLAKE2 DIGEST
+// ================================================================================================
+
+byte_digest! {
+    /// 32-bytes output of the BLAKE2b-256 and BLAKE2s-256 hash functions.
+    Blake2Digest
+}
+
+// BLAKE2b-256 HASHER
+// ================================================================================================
+
+/// BLAKE2b hasher with 256-bit output, as used by Substrate-based chains.
+///
+/// Two digests are merged by hashing their concatenation. Field elements are encoded as the
+/// little-endian 8-byte representations of their canonical values, i.e., a sequence of n elements
+/// is hashed as the BLAKE2b-256 digest of the corresponding 8n bytes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Blake2b256;
+
+impl Hasher for Blake2b256 {
+    /// BLAKE2b-256 collision resistance is 128-bits for 32-bytes output.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = Blake2Digest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        hash_bytes::<Blake2b<U32>>(bytes)
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        merge_digests::<Blake2b<U32>>(values)
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        merge_digest_with_int::<Blake2b<U32>>(seed, value)
+    }
+}
+
+impl ElementHasher for Blake2b256 {
+    type BaseField = Felt;
+
+    fn hash_elements<E>(elements: &[E]) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        hash_field_elements::<Blake2b<U32>, E>(elements)
+    }
+}
+
+impl Blake2b256 {
+    /// Returns a hash of the provided sequence of bytes.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> Blake2Digest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    #[inline(always)]
+    pub fn merge(values: &[Blake2Digest; 2]) -> Blake2Digest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E>(elements: &[E]) -> Blake2Digest
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+}
+
+// BLAKE2s-256 HASHER
+// ================================================================================================
+
+/// BLAKE2s hasher with 256-bit output.
+///
+/// Two digests are merged by hashing their concatenation. Field elements are encoded as the
+/// little-endian 8-byte representations of their canonical values, i.e., a sequence of n elements
+/// is hashed as the BLAKE2s-256 digest of the corresponding 8n bytes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Blake2s256;
+
+impl Hasher for Blake2s256 {
+    /// BLAKE2s-256 collision resistance is 128-bits for 32-bytes output.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = Blake2Digest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        hash_bytes::<::blake2::Blake2s256>(bytes)
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        merge_digests::<::blake2::Blake2s256>(values)
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        merge_digest_with_int::<::blake2::Blake2s256>(seed, value)
+    }
+}
+
+impl ElementHasher for Blake2s256 {
+    type BaseField = Felt;
+
+    fn hash_elements<E>(elements: &[E]) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        hash_field_elements::<::blake2::Blake2s256, E>(elements)
+    }
+}
+
+impl Blake2s256 {
+    /// Returns a hash of the provided sequence of bytes.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> Blake2Digest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    #[inline(always)]
+    pub fn merge(values: &[Blake2Digest; 2]) -> Blake2Digest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E>(elements: &[E]) -> Blake2Digest
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+fn hash_bytes<D: ::blake2::Digest<OutputSize = U32>>(bytes: &[u8]) -> Blake2Digest {
+    Blake2Digest(D::digest(bytes).into())
+}
+
+fn merge_digests<D: ::blake2::Digest<OutputSize = U32>>(
+    values: &[Blake2Digest; 2],
+) -> Blake2Digest {
+    let mut hasher = D::new();
+    hasher.update(values[0].0);
+    hasher.update(values[1].0);
+    Blake2Digest(hasher.finalize().into())
+}
+
+fn merge_digest_with_int<D: ::blake2::Digest<OutputSize = U32>>(
+    seed: Blake2Digest,
+    value: u64,
+) -> Blake2Digest {
+    let mut hasher = D::new();
+    hasher.update(seed.0);
+    hasher.update(value.to_le_bytes());
+    Blake2Digest(hasher.finalize().into())
+}
+
+fn hash_field_elements<D, E>(elements: &[E]) -> Blake2Digest
+where
+    D: ::blake2::Digest<OutputSize = U32>,
+    E: FieldElement<BaseField = Felt>,
+{
+    let mut hasher = D::new();
+    for element in E::slice_as_base_elements(elements) {
+        hasher.update(element.as_int().to_le_bytes());
+    }
+    Blake2Digest(hasher.finalize().into())
+}
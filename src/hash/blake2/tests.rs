@@ -0,0 +1,32 @@
+use rand_utils::rand_vector;
+
+use super::*;
+use crate::utils::hex_to_bytes;
+
+#[test]
+fn blake2b256_test_vectors() {
+    let expected: [u8; 32] =
+        hex_to_bytes("0x0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8").unwrap();
+    assert_eq!(Blake2b256::hash(b""), Blake2Digest::from(expected));
+
+    let expected: [u8; 32] =
+        hex_to_bytes("0xbddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319").unwrap();
+    assert_eq!(Blake2b256::hash(b"abc"), Blake2Digest::from(expected));
+}
+
+#[test]
+fn blake2s256_test_vectors() {
+    let expected: [u8; 32] =
+        hex_to_bytes("0x69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9").unwrap();
+    assert_eq!(Blake2s256::hash(b""), Blake2Digest::from(expected));
+
+    let expected: [u8; 32] =
+        hex_to_bytes("0x508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982").unwrap();
+    assert_eq!(Blake2s256::hash(b"abc"), Blake2Digest::from(expected));
+}
+
+#[test]
+fn blake2_variants_differ() {
+    let elements = rand_vector::<Felt>(17);
+    assert_ne!(Blake2b256::hash_elements(&elements), Blake2s256::hash_elements(&elements));
+}
@@ -0,0 +1,206 @@
+// BYTE DIGEST
+// ================================================================================================
+
+/// Defines a 32-byte digest type for a hash function over bytes.
+///
+/// The digest dereferences to its bytes, converts to and from `[u8; 32]`, hex strings and
+/// [Word](crate::Word)s (as the concatenation of the little-endian encodings of the elements), is
+/// serialized as its 32 bytes, and implements [Digest](crate::hash::Digest).
+macro_rules! byte_digest {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+        #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+        #[cfg_attr(
+            feature = "serde",
+            serde(into = "alloc::string::String", try_from = "&str")
+        )]
+        pub struct $name([u8; 32]);
+
+        impl core::ops::Deref for $name {
+            type Target = [u8];
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl From<$name> for [u8; 32] {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl From<[u8; 32]> for $name {
+            fn from(value: [u8; 32]) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$crate::Word> for $name {
+            /// Encodes the word as the concatenation of the little-endian encodings of its
+            /// elements.
+            fn from(value: $crate::Word) -> Self {
+                Self($crate::hash::rpo::RpoDigest::from(value).into())
+            }
+        }
+
+        impl TryFrom<$name> for $crate::Word {
+            type Error = $crate::utils::HexParseError;
+
+            /// Decodes the digest as four little-endian 8-byte elements.
+            ///
+            /// Returns an error if any of the elements is not smaller than the field modulus.
+            fn try_from(value: $name) -> Result<Self, Self::Error> {
+                $crate::hash::rpo::RpoDigest::try_from(value.0).map($crate::Word::from)
+            }
+        }
+
+        impl From<$name> for alloc::string::String {
+            fn from(value: $name) -> Self {
+                $crate::utils::bytes_to_hex_string(value.0)
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = $crate::utils::HexParseError;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                $crate::utils::hex_to_bytes(value).map(Self)
+            }
+        }
+
+        impl $crate::utils::Serializable for $name {
+            fn write_into<W: $crate::utils::ByteWriter>(&self, target: &mut W) {
+                target.write_bytes(&self.0);
+            }
+        }
+
+        impl $crate::utils::Deserializable for $name {
+            fn read_from<R: $crate::utils::ByteReader>(
+                source: &mut R,
+            ) -> Result<Self, $crate::utils::DeserializationError> {
+                source.read_array().map(Self)
+            }
+        }
+
+        impl $crate::hash::Digest for $name {
+            fn as_bytes(&self) -> [u8; 32] {
+                self.0
+            }
+        }
+    };
+}
+
+pub(super) use byte_digest;
+
+// TESTS
+// ================================================================================================
+
+/// Checks shared by all hashers with a digest defined via `byte_digest!`, i.e., hashers which
+/// merge digests by hashing their concatenation and hash field elements as the concatenation of
+/// the little-endian encodings of their canonical values.
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use proptest::prelude::*;
+    use rand_utils::rand_vector;
+
+    use crate::{
+        hash::{
+            blake2::{Blake2Digest, Blake2b256, Blake2s256},
+            keccak::{Keccak256, Keccak256Digest},
+            sha2::{Sha256, Sha256Digest},
+            sha3::{Sha3Digest, Sha3_256, Shake128, Shake256},
+            Digest, ElementHasher,
+        },
+        utils::{hex_to_bytes, HexParseError},
+        Felt, Word, ONE, ZERO,
+    };
+
+    #[test]
+    fn byte_hashers_merge() {
+        check_merge::<Keccak256>();
+        check_merge::<Sha256>();
+        check_merge::<Blake2b256>();
+        check_merge::<Blake2s256>();
+        check_merge::<Sha3_256>();
+        check_merge::<Shake128>();
+        check_merge::<Shake256>();
+    }
+
+    #[test]
+    fn byte_hashers_hash_elements() {
+        check_hash_elements::<Keccak256>();
+        check_hash_elements::<Sha256>();
+        check_hash_elements::<Blake2b256>();
+        check_hash_elements::<Blake2s256>();
+        check_hash_elements::<Sha3_256>();
+        check_hash_elements::<Shake128>();
+        check_hash_elements::<Shake256>();
+    }
+
+    #[test]
+    fn byte_digests_conversions() {
+        check_conversions::<Keccak256Digest>();
+        check_conversions::<Sha256Digest>();
+        check_conversions::<Blake2Digest>();
+        check_conversions::<Sha3Digest>();
+    }
+
+    proptest! {
+        #[test]
+        fn byte_hashers_wont_panic_with_arbitrary_input(ref vec in any::<Vec<u8>>()) {
+            Keccak256::hash(vec);
+            Sha256::hash(vec);
+            Blake2b256::hash(vec);
+            Blake2s256::hash(vec);
+            Sha3_256::hash(vec);
+            Shake128::hash(vec);
+            Shake256::hash(vec);
+        }
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    fn check_merge<H: ElementHasher>() {
+        let a = H::hash(b"a");
+        let b = H::hash(b"b");
+        let concatenated: Vec<u8> = a.as_bytes().into_iter().chain(b.as_bytes()).collect();
+        assert_eq!(H::merge(&[a, b]), H::hash(&concatenated));
+
+        let seed = H::hash(b"seed");
+        let bytes: Vec<u8> = seed.as_bytes().into_iter().chain(42_u64.to_le_bytes()).collect();
+        assert_eq!(H::merge_with_int(seed, 42), H::hash(&bytes));
+    }
+
+    fn check_hash_elements<H: ElementHasher<BaseField = Felt>>() {
+        let elements = rand_vector::<Felt>(17);
+        let bytes: Vec<u8> = elements.iter().flat_map(|e| e.as_int().to_le_bytes()).collect();
+        assert_eq!(H::hash_elements(&elements), H::hash(&bytes));
+    }
+
+    fn check_conversions<D>()
+    where
+        D: Digest + From<Word> + From<[u8; 32]> + TryInto<Word, Error = HexParseError>,
+        D: Into<alloc::string::String> + for<'a> TryFrom<&'a str, Error = HexParseError>,
+    {
+        let word: Word = [ONE, ZERO, Felt::new(u64::MAX), Felt::new(7)];
+        let digest = D::from(word);
+        assert_eq!(TryInto::<Word>::try_into(digest).unwrap(), word);
+
+        let digest = D::from([0xff; 32]);
+        assert!(matches!(TryInto::<Word>::try_into(digest), Err(HexParseError::OutOfRange)));
+
+        let bytes: [u8; 32] =
+            hex_to_bytes("0x000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f")
+                .unwrap();
+        let digest = D::from(bytes);
+        let hex: alloc::string::String = digest.into();
+        assert_eq!(D::try_from(hex.as_str()).unwrap(), digest);
+        assert_eq!(D::read_from_bytes(&digest.to_bytes()).unwrap(), digest);
+        assert_eq!(digest.as_bytes(), bytes);
+    }
+}
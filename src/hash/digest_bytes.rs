@@ -0,0 +1,191 @@
+use core::fmt;
+
+use super::{
+    blake::Blake3Digest, blake2::Blake2Digest, keccak::Keccak256Digest, rpo::RpoDigest,
+    rpx::RpxDigest, sha2::Sha256Digest, sha3::Sha3Digest, Digest,
+};
+
+// DIGEST BYTES
+// ================================================================================================
+
+/// Defines the canonical byte representation of a digest, which allows converting digests of one
+/// hash function into digests of another.
+///
+/// The byte representation of a digest consists of the first [DigestBytes::NUM_BYTES] bytes
+/// returned by [Digest::as_bytes()]. For digests of field elements (e.g., [RpoDigest]), these are
+/// the little-endian encodings of the canonical values of the elements.
+pub trait DigestBytes: Digest {
+    /// The number of bytes in the byte representation of the digest.
+    const NUM_BYTES: usize;
+
+    /// Returns a digest with the provided byte representation.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The length of `bytes` is not equal to [Self::NUM_BYTES].
+    /// - The bytes do not encode a valid digest, e.g., because they encode a value which is not
+    ///   a canonical field element.
+    fn try_from_digest_bytes(bytes: &[u8]) -> Result<Self, DigestConversionError>;
+
+    /// Converts this digest into a digest of type `D` with the same byte representation.
+    ///
+    /// # Errors
+    /// Returns an error if the byte representation of this digest is not a valid byte
+    /// representation of `D`; see [DigestBytes::try_from_digest_bytes()].
+    fn try_into_digest<D: DigestBytes>(&self) -> Result<D, DigestConversionError> {
+        D::try_from_digest_bytes(&self.as_bytes()[..Self::NUM_BYTES])
+    }
+}
+
+// DIGEST CONVERSION ERROR
+// ================================================================================================
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DigestConversionError {
+    InvalidLength { expected: usize, actual: usize },
+    NonCanonicalElement,
+}
+
+impl fmt::Display for DigestConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use DigestConversionError::*;
+        match self {
+            InvalidLength { expected, actual } => {
+                write!(
+                    f,
+                    "Digest must consist of {expected} bytes, but {actual} bytes were provided"
+                )
+            }
+            NonCanonicalElement => {
+                write!(f, "Digest bytes encode a value which is not a canonical field element")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DigestConversionError {}
+
+// DIGEST BYTES IMPLEMENTATIONS
+// ================================================================================================
+
+impl DigestBytes for RpoDigest {
+    const NUM_BYTES: usize = 32;
+
+    fn try_from_digest_bytes(bytes: &[u8]) -> Result<Self, DigestConversionError> {
+        let bytes: [u8; 32] = to_array(bytes)?;
+        Self::try_from(bytes).map_err(|_| DigestConversionError::NonCanonicalElement)
+    }
+}
+
+impl DigestBytes for RpxDigest {
+    const NUM_BYTES: usize = 32;
+
+    fn try_from_digest_bytes(bytes: &[u8]) -> Result<Self, DigestConversionError> {
+        let bytes: [u8; 32] = to_array(bytes)?;
+        Self::try_from(bytes).map_err(|_| DigestConversionError::NonCanonicalElement)
+    }
+}
+
+impl<const N: usize> DigestBytes for Blake3Digest<N> {
+    const NUM_BYTES: usize = N;
+
+    fn try_from_digest_bytes(bytes: &[u8]) -> Result<Self, DigestConversionError> {
+        to_array(bytes).map(Self::from)
+    }
+}
+
+/// Implements [DigestBytes] for digests which consist of 32 arbitrary bytes.
+macro_rules! impl_byte_digest_bytes {
+    ($($digest:ty),+) => {
+        $(
+            impl DigestBytes for $digest {
+                const NUM_BYTES: usize = 32;
+
+                fn try_from_digest_bytes(bytes: &[u8]) -> Result<Self, DigestConversionError> {
+                    to_array(bytes).map(Self::from)
+                }
+            }
+        )+
+    };
+}
+
+impl_byte_digest_bytes!(Blake2Digest, Keccak256Digest, Sha256Digest, Sha3Digest);
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Converts the provided slice into an array, returning an error if the slice has a different
+/// length.
+pub(super) fn to_array<const N: usize>(bytes: &[u8]) -> Result<[u8; N], DigestConversionError> {
+    bytes
+        .try_into()
+        .map_err(|_| DigestConversionError::InvalidLength { expected: N, actual: bytes.len() })
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{DigestBytes, DigestConversionError};
+    use crate::{
+        hash::{
+            blake::{Blake3Digest, Blake3_160},
+            rpo::{Rpo256, RpoDigest},
+            rpx::RpxDigest,
+            sha2::Sha256Digest,
+            Digest,
+        },
+        Felt, StarkField, ZERO,
+    };
+
+    #[test]
+    fn digest_bytes_round_trip() {
+        // digests of field elements can be converted into byte digests and back
+        let digest = Rpo256::hash(b"digest");
+        let sha: Sha256Digest = digest.try_into_digest().unwrap();
+        assert_eq!(sha.as_bytes(), digest.as_bytes());
+        assert_eq!(sha.try_into_digest::<RpoDigest>().unwrap(), digest);
+
+        let rpx: RpxDigest = digest.try_into_digest().unwrap();
+        assert_eq!(rpx.as_elements(), digest.as_elements());
+    }
+
+    #[test]
+    fn digest_bytes_errors() {
+        // a byte digest which does not encode canonical field elements cannot be converted
+        let digest = Sha256Digest::from([0xff; 32]);
+        assert_eq!(
+            digest.try_into_digest::<RpoDigest>(),
+            Err(DigestConversionError::NonCanonicalElement)
+        );
+
+        // digests of different sizes cannot be converted
+        let digest = Blake3_160::hash(b"digest");
+        assert_eq!(
+            digest.try_into_digest::<Sha256Digest>(),
+            Err(DigestConversionError::InvalidLength { expected: 32, actual: 20 })
+        );
+        assert_eq!(
+            RpoDigest::from_digest_reduced(&digest),
+            Err(DigestConversionError::InvalidLength { expected: 32, actual: 20 })
+        );
+    }
+
+    #[test]
+    fn digest_reduction() {
+        let digest = Blake3Digest::<32>::from([0xff; 32]);
+        let expected = Felt::new(u64::MAX - Felt::MODULUS);
+        let reduced = RpoDigest::from_digest_reduced(&digest).unwrap();
+        assert_eq!(reduced, RpoDigest::new([expected; 4]));
+        assert_eq!(RpxDigest::from_digest_reduced(&digest).unwrap().as_elements(), &[expected; 4]);
+
+        // canonical digests are not changed by the reduction
+        let digest = Rpo256::hash(b"digest");
+        let bytes = Blake3Digest::<32>::from(digest.as_bytes());
+        assert_eq!(RpoDigest::from_digest_reduced(&bytes).unwrap(), digest);
+        let zero = Blake3Digest::<32>::from([0; 32]);
+        assert_eq!(RpoDigest::from_digest_reduced(&zero).unwrap(), RpoDigest::new([ZERO; 4]));
+    }
+}
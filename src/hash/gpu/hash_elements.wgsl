@@ -0,0 +1,52 @@
+// Hashing of sequences of field elements with RPO, following Rpo256::hash_elements() exactly.
+//
+// Each invocation hashes one sequence. This source is appended to rpo.wgsl at runtime.
+
+// The offset (in elements) and the number of elements of each sequence.
+@group(0) @binding(0) var<storage, read> sequences: array<u32>;
+// The elements of all sequences, two words per element.
+@group(0) @binding(1) var<storage, read> elements: array<u32>;
+// The digests of the sequences, eight words per digest.
+@group(0) @binding(2) var<storage, read_write> digests: array<u32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if index >= arrayLength(&digests) / 8u {
+        return;
+    }
+    let offset = sequences[2u * index];
+    let len = sequences[2u * index + 1u];
+
+    // the first capacity element is set to 1 if the number of elements is not a multiple of the
+    // rate width
+    var state: array<vec2<u32>, 12>;
+    if len % 8u != 0u {
+        state[0] = ONE;
+    }
+
+    var i = 0u;
+    for (var k = 0u; k < len; k = k + 1u) {
+        let e = 2u * (offset + k);
+        state[4u + i] = vec2<u32>(elements[e], elements[e + 1u]);
+        i = i + 1u;
+        if i == 8u {
+            apply_permutation(&state);
+            i = 0u;
+        }
+    }
+
+    // pad a partially filled rate with a 1 followed by zeros
+    if i > 0u {
+        state[4u + i] = ONE;
+        for (var j = i + 1u; j < 8u; j = j + 1u) {
+            state[4u + j] = ZERO;
+        }
+        apply_permutation(&state);
+    }
+
+    for (var j = 0u; j < 4u; j = j + 1u) {
+        digests[8u * index + 2u * j] = state[4u + j].x;
+        digests[8u * index + 2u * j + 1u] = state[4u + j].y;
+    }
+}
@@ -0,0 +1,28 @@
+// 2-to-1 hashing of digests with RPO, following Rpo256::merge() exactly.
+//
+// Each invocation merges one pair of digests. This source is appended to rpo.wgsl at runtime.
+
+// The pairs of digests, sixteen words per pair.
+@group(0) @binding(0) var<storage, read> pairs: array<u32>;
+// The digests of the pairs, eight words per digest.
+@group(0) @binding(1) var<storage, read_write> digests: array<u32>;
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if index >= arrayLength(&digests) / 8u {
+        return;
+    }
+
+    // the digests are copied into the rate portion of the state, and the capacity is zero
+    var state: array<vec2<u32>, 12>;
+    for (var j = 0u; j < 8u; j = j + 1u) {
+        state[4u + j] = vec2<u32>(pairs[16u * index + 2u * j], pairs[16u * index + 2u * j + 1u]);
+    }
+    apply_permutation(&state);
+
+    for (var j = 0u; j < 4u; j = j + 1u) {
+        digests[8u * index + 2u * j] = state[4u + j].x;
+        digests[8u * index + 2u * j + 1u] = state[4u + j].y;
+    }
+}
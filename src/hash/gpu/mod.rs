@@ -0,0 +1,254 @@
+//! Batch hashing with [Rpo256] on a GPU.
+//!
+//! The functions in this module compute the same digests as [Rpo256::hash_elements()] and
+//! [Rpo256::merge()] for many independent inputs, e.g., all nodes in a level of a large Merkle
+//! tree. The permutations are computed on the GPU via [wgpu](https://wgpu.rs), with one GPU
+//! thread per input; the field arithmetic is emulated with 32-bit integers, since WGSL has no
+//! 64-bit integer type.
+//!
+//! The fallback to the CPU is transparent: if no GPU adapter is available, or the computation on
+//! the GPU fails, the inputs are hashed on the CPU (concurrently when the `parallel` feature is
+//! enabled). Batches smaller than [MIN_BATCH_SIZE] are always hashed on the CPU, since for them
+//! the cost of the transfers to and from the GPU outweighs the gains. The digests are identical
+//! either way.
+
+use alloc::{string::String, vec::Vec};
+use std::sync::OnceLock;
+
+use super::rpo::{Rpo256, RpoDigest};
+use crate::{
+    utils::gpu::{GpuContext, Kernel},
+    Felt, FieldElement, ZERO,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The smallest number of inputs which [hash_elements_many()] and [merge_many()] hash on the GPU.
+pub const MIN_BATCH_SIZE: usize = 1024;
+
+/// The number of 32-bit words in the GPU representation of a field element.
+const ELEMENT_WORDS: usize = 2;
+
+/// The number of 32-bit words in the GPU representation of a digest.
+const DIGEST_WORDS: usize = 4 * ELEMENT_WORDS;
+
+/// The number of invocations in a workgroup of the kernels.
+const WORKGROUP_SIZE: usize = 64;
+
+/// The largest number of 32-bit words uploaded to a single buffer in one dispatch, which bounds
+/// the memory used for staging the inputs of a dispatch.
+const MAX_BUFFER_WORDS: usize = 1 << 24;
+
+/// The source of the field arithmetic and the RPO permutation shared by the kernels, without the
+/// constants prepended by [kernel_source()].
+const RPO_SOURCE: &str = include_str!("rpo.wgsl");
+
+/// The source of the entry point of the kernel hashing sequences of elements.
+const HASH_ELEMENTS_SOURCE: &str = include_str!("hash_elements.wgsl");
+
+/// The source of the entry point of the kernel merging pairs of digests.
+const MERGE_SOURCE: &str = include_str!("merge.wgsl");
+
+// BATCH HASHING
+// ================================================================================================
+
+/// Returns true if a GPU adapter is available for hashing.
+pub fn is_available() -> bool {
+    GpuContext::get().is_some()
+}
+
+/// Returns hashes of the provided sequences of field elements, i.e., the i-th element of the
+/// result is equal to `Rpo256::hash_elements(inputs[i])`.
+///
+/// The sequences are hashed on the GPU if there are at least [MIN_BATCH_SIZE] of them and a GPU
+/// adapter is available, and on the CPU otherwise. Sequences which are too long to fit into a
+/// single GPU buffer are always hashed on the CPU.
+pub fn hash_elements_many<E: FieldElement<BaseField = Felt>>(inputs: &[&[E]]) -> Vec<RpoDigest> {
+    let inputs: Vec<&[Felt]> =
+        inputs.iter().map(|input| E::slice_as_base_elements(input)).collect();
+    if inputs.len() >= MIN_BATCH_SIZE {
+        if let Some(digests) = hash_elements_on_gpu(&inputs) {
+            return digests;
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    {
+        Rpo256::hash_elements_many_parallel(&inputs)
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    inputs.iter().map(|input| Rpo256::hash_elements(input)).collect()
+}
+
+/// Returns hashes of the provided pairs of digests, i.e., the i-th element of the result is
+/// equal to `Rpo256::merge(&pairs[i])`.
+///
+/// The pairs are merged on the GPU if there are at least [MIN_BATCH_SIZE] of them and a GPU
+/// adapter is available, and on the CPU otherwise.
+pub fn merge_many(pairs: &[[RpoDigest; 2]]) -> Vec<RpoDigest> {
+    if pairs.len() >= MIN_BATCH_SIZE {
+        if let Some(digests) = merge_on_gpu(pairs) {
+            return digests;
+        }
+    }
+
+    #[cfg(feature = "parallel")]
+    {
+        Rpo256::merge_many_parallel(pairs)
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    Rpo256::merge_in_domain_many(pairs, ZERO)
+}
+
+// GPU DISPATCH
+// ================================================================================================
+
+/// Hashes the provided sequences on the GPU, or returns `None` if no GPU is available or the
+/// computation fails.
+fn hash_elements_on_gpu(inputs: &[&[Felt]]) -> Option<Vec<RpoDigest>> {
+    static KERNEL: OnceLock<Option<Kernel>> = OnceLock::new();
+    let context = GpuContext::get()?;
+    let kernel = KERNEL
+        .get_or_init(|| {
+            context.create_kernel(
+                "rpo256_hash_elements",
+                kernel_source(HASH_ELEMENTS_SOURCE),
+                "main",
+            )
+        })
+        .as_ref()?;
+
+    let max_words = context.max_buffer_words().min(MAX_BUFFER_WORDS);
+    let max_inputs =
+        (max_words / DIGEST_WORDS).min(context.max_workgroups() as usize * WORKGROUP_SIZE);
+
+    let mut result = Vec::with_capacity(inputs.len());
+    let mut start = 0;
+    while start < inputs.len() {
+        // take as many sequences as fit into the buffers of a single dispatch
+        let mut end = start;
+        let mut words = 0;
+        while end < inputs.len()
+            && end - start < max_inputs
+            && words + ELEMENT_WORDS * inputs[end].len() <= max_words
+        {
+            words += ELEMENT_WORDS * inputs[end].len();
+            end += 1;
+        }
+
+        // a sequence which does not fit into a buffer on its own is hashed on the CPU
+        if end == start {
+            result.push(Rpo256::hash_elements(inputs[start]));
+            start += 1;
+            continue;
+        }
+
+        let chunk = &inputs[start..end];
+        let mut sequences = Vec::with_capacity(2 * chunk.len());
+        let mut elements = Vec::with_capacity(words);
+        for input in chunk {
+            sequences.push((elements.len() / ELEMENT_WORDS) as u32);
+            sequences.push(input.len() as u32);
+            elements.extend(input.iter().flat_map(|&element| element_to_words(element)));
+        }
+
+        let digests = context.run(
+            kernel,
+            &[&sequences, &elements],
+            DIGEST_WORDS * chunk.len(),
+            chunk.len().div_ceil(WORKGROUP_SIZE) as u32,
+        )?;
+        result.extend(digests.chunks_exact(DIGEST_WORDS).map(words_to_digest));
+        start = end;
+    }
+
+    #[cfg(feature = "metrics")]
+    crate::hash::metrics::record(
+        &crate::hash::metrics::RPO256_PERMUTATIONS,
+        inputs.iter().map(|input| input.len().div_ceil(8) as u64).sum(),
+    );
+    Some(result)
+}
+
+/// Merges the provided pairs on the GPU, or returns `None` if no GPU is available or the
+/// computation fails.
+fn merge_on_gpu(pairs: &[[RpoDigest; 2]]) -> Option<Vec<RpoDigest>> {
+    static KERNEL: OnceLock<Option<Kernel>> = OnceLock::new();
+    let context = GpuContext::get()?;
+    let kernel = KERNEL
+        .get_or_init(|| context.create_kernel("rpo256_merge", kernel_source(MERGE_SOURCE), "main"))
+        .as_ref()?;
+
+    let max_words = context.max_buffer_words().min(MAX_BUFFER_WORDS);
+    let max_pairs =
+        (max_words / (2 * DIGEST_WORDS)).min(context.max_workgroups() as usize * WORKGROUP_SIZE);
+
+    let mut result = Vec::with_capacity(pairs.len());
+    for chunk in pairs.chunks(max_pairs.max(1)) {
+        let words: Vec<u32> = RpoDigest::digests_as_elements(chunk.iter().flatten())
+            .flat_map(|&element| element_to_words(element))
+            .collect();
+        let digests = context.run(
+            kernel,
+            &[&words],
+            DIGEST_WORDS * chunk.len(),
+            chunk.len().div_ceil(WORKGROUP_SIZE) as u32,
+        )?;
+        result.extend(digests.chunks_exact(DIGEST_WORDS).map(words_to_digest));
+    }
+
+    #[cfg(feature = "metrics")]
+    crate::hash::metrics::record(&crate::hash::metrics::RPO256_PERMUTATIONS, pairs.len() as u64);
+    Some(result)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the source of a kernel with the provided entry point, i.e., the constants of RPO,
+/// followed by the field arithmetic and the permutation, followed by the entry point.
+fn kernel_source(entry_point: &str) -> String {
+    let array = |rows: &[[Felt; Rpo256::STATE_WIDTH]]| {
+        let literals: Vec<String> =
+            rows.iter().flatten().map(|&element| element_literal(element)).collect();
+        let array_type = format!("array<vec2<u32>, {}>", literals.len());
+        format!("{array_type} = {array_type}({})", literals.join(", "))
+    };
+    let mds = array(&Rpo256::MDS);
+    let ark1 = array(&Rpo256::ARK1);
+    let ark2 = array(&Rpo256::ARK2);
+    let inv_alpha =
+        format!("vec2<u32>({}u, {}u)", Rpo256::INV_ALPHA as u32, Rpo256::INV_ALPHA >> 32);
+
+    format!(
+        "var<private> MDS: {mds};\nvar<private> ARK1: {ark1};\nvar<private> ARK2: {ark2};\n\
+         const INV_ALPHA: vec2<u32> = {inv_alpha};\n\n{RPO_SOURCE}\n{entry_point}"
+    )
+}
+
+/// Returns the WGSL literal of the provided field element.
+fn element_literal(element: Felt) -> String {
+    let [lo, hi] = element_to_words(element);
+    format!("vec2<u32>({lo}u, {hi}u)")
+}
+
+/// Splits the canonical value of the provided field element into its lower and upper 32 bits.
+fn element_to_words(element: Felt) -> [u32; ELEMENT_WORDS] {
+    let value = element.as_int();
+    [value as u32, (value >> 32) as u32]
+}
+
+/// Returns the digest with the provided GPU representation.
+fn words_to_digest(words: &[u32]) -> RpoDigest {
+    let mut digest = [ZERO; 4];
+    for (element, words) in digest.iter_mut().zip(words.chunks_exact(ELEMENT_WORDS)) {
+        *element = Felt::new(words[0] as u64 | (words[1] as u64) << 32);
+    }
+    RpoDigest::new(digest)
+}
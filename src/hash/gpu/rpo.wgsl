@@ -0,0 +1,164 @@
+// Arithmetic in the field with modulus p = 2^64 - 2^32 + 1 and the RPO permutation.
+//
+// WGSL has no 64-bit integers, and thus a field element is represented by a vec2<u32> holding the
+// lower and the upper 32 bits of its canonical value. The constants MDS, ARK1, ARK2 and INV_ALPHA
+// are prepended to this source at runtime.
+
+const ZERO: vec2<u32> = vec2<u32>(0u, 0u);
+const ONE: vec2<u32> = vec2<u32>(1u, 0u);
+
+// 2^64 mod p
+const EPSILON: vec2<u32> = vec2<u32>(0xffffffffu, 0u);
+
+// 64-BIT INTEGER ARITHMETIC
+// ================================================================================================
+
+// Returns a + b mod 2^64 in the first two components and the carry in the third one.
+fn adc(a: vec2<u32>, b: vec2<u32>) -> vec3<u32> {
+    let lo = a.x + b.x;
+    let carry = select(0u, 1u, lo < a.x);
+    let hi = a.y + b.y;
+    let hi_carry = select(0u, 1u, hi < a.y);
+    let result = hi + carry;
+    return vec3<u32>(lo, result, hi_carry | select(0u, 1u, result < hi));
+}
+
+// Returns a - b mod 2^64 in the first two components and the borrow in the third one.
+fn sbb(a: vec2<u32>, b: vec2<u32>) -> vec3<u32> {
+    let lo = a.x - b.x;
+    let borrow = select(0u, 1u, a.x < b.x);
+    let hi = a.y - b.y;
+    let hi_borrow = select(0u, 1u, a.y < b.y);
+    return vec3<u32>(lo, hi - borrow, hi_borrow | select(0u, 1u, hi < borrow));
+}
+
+// Returns the 64-bit product of two 32-bit integers.
+fn mul_wide(a: u32, b: u32) -> vec2<u32> {
+    let a0 = a & 0xffffu;
+    let a1 = a >> 16u;
+    let b0 = b & 0xffffu;
+    let b1 = b >> 16u;
+
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let mid = p01 + p10;
+    let mid_carry = select(0u, 0x10000u, mid < p01);
+    let lo = p00 + (mid << 16u);
+    let lo_carry = select(0u, 1u, lo < p00);
+    return vec2<u32>(lo, p11 + (mid >> 16u) + mid_carry + lo_carry);
+}
+
+// FIELD ARITHMETIC
+// ================================================================================================
+
+// Reduces a value smaller than 2^64 to its canonical representation.
+fn canonicalize(a: vec2<u32>) -> vec2<u32> {
+    // a >= p if and only if the upper half is 2^32 - 1 and the lower half is not zero; in that
+    // case, a - p = a - 2^64 + EPSILON
+    if a.y == 0xffffffffu && a.x != 0u {
+        return vec2<u32>(a.x - 1u, 0u);
+    }
+    return a;
+}
+
+fn add(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> {
+    let sum = adc(a, b);
+    if sum.z != 0u {
+        // a + b = 2^64 + s, and a + b - p = s + EPSILON is smaller than p
+        return adc(sum.xy, EPSILON).xy;
+    }
+    return canonicalize(sum.xy);
+}
+
+fn mul(a: vec2<u32>, b: vec2<u32>) -> vec2<u32> {
+    // the 128-bit product as four 32-bit limbs r0..r3
+    let t00 = mul_wide(a.x, b.x);
+    let t01 = mul_wide(a.x, b.y);
+    let t10 = mul_wide(a.y, b.x);
+    let t11 = mul_wide(a.y, b.y);
+
+    let s1 = t00.y + t01.x;
+    var c1 = select(0u, 1u, s1 < t00.y);
+    let r1 = s1 + t10.x;
+    c1 = c1 + select(0u, 1u, r1 < s1);
+
+    let s2 = t01.y + t10.y;
+    var c2 = select(0u, 1u, s2 < t01.y);
+    let s2b = s2 + t11.x;
+    c2 = c2 + select(0u, 1u, s2b < s2);
+    let r2 = s2b + c1;
+    c2 = c2 + select(0u, 1u, r2 < s2b);
+
+    let r3 = t11.y + c2;
+
+    // reduce r0 + 2^32 r1 + 2^64 r2 + 2^96 r3 using 2^64 = 2^32 - 1 and 2^96 = -1 mod p
+    var t0 = sbb(vec2<u32>(t00.x, r1), vec2<u32>(r3, 0u));
+    if t0.z != 0u {
+        t0 = sbb(t0.xy, EPSILON);
+    }
+    // r2 * (2^32 - 1)
+    var t1 = ZERO;
+    if r2 != 0u {
+        t1 = vec2<u32>(0u - r2, r2 - 1u);
+    }
+    var t2 = adc(t0.xy, t1);
+    if t2.z != 0u {
+        t2 = adc(t2.xy, EPSILON);
+    }
+    return canonicalize(t2.xy);
+}
+
+// RPO PERMUTATION
+// ================================================================================================
+
+fn apply_mds(state: ptr<function, array<vec2<u32>, 12>>) {
+    var result: array<vec2<u32>, 12>;
+    for (var i = 0u; i < 12u; i = i + 1u) {
+        var acc = ZERO;
+        for (var j = 0u; j < 12u; j = j + 1u) {
+            acc = add(acc, mul(MDS[12u * i + j], (*state)[j]));
+        }
+        result[i] = acc;
+    }
+    *state = result;
+}
+
+fn apply_sbox(x: vec2<u32>) -> vec2<u32> {
+    let x2 = mul(x, x);
+    let x4 = mul(x2, x2);
+    return mul(x4, mul(x2, x));
+}
+
+fn apply_inv_sbox(x: vec2<u32>) -> vec2<u32> {
+    // square-and-multiply over the bits of INV_ALPHA, from the most significant one
+    var result = ONE;
+    for (var i = 0u; i < 64u; i = i + 1u) {
+        result = mul(result, result);
+        let bit = 63u - i;
+        var limb = INV_ALPHA.x;
+        if bit >= 32u {
+            limb = INV_ALPHA.y;
+        }
+        if ((limb >> (bit % 32u)) & 1u) != 0u {
+            result = mul(result, x);
+        }
+    }
+    return result;
+}
+
+fn apply_permutation(state: ptr<function, array<vec2<u32>, 12>>) {
+    for (var round = 0u; round < 7u; round = round + 1u) {
+        apply_mds(state);
+        for (var i = 0u; i < 12u; i = i + 1u) {
+            (*state)[i] = apply_sbox(add((*state)[i], ARK1[12u * round + i]));
+        }
+
+        apply_mds(state);
+        for (var i = 0u; i < 12u; i = i + 1u) {
+            (*state)[i] = apply_inv_sbox(add((*state)[i], ARK2[12u * round + i]));
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use alloc::vec::Vec;
+
+use rand_utils::rand_vector;
+
+use super::{
+    hash_elements_many, hash_elements_on_gpu, merge_many, merge_on_gpu, Felt, Rpo256, RpoDigest,
+    MIN_BATCH_SIZE,
+};
+use crate::{rand::Randomizable, QuadExtension, StarkField};
+
+type QuadFelt = QuadExtension<Felt>;
+
+/// Returns sequences of random elements of all lengths up to three times the rate width, with
+/// elements close to the modulus included.
+fn sequences() -> Vec<Vec<Felt>> {
+    let mut sequences: Vec<Vec<Felt>> = (0..25).map(random_elements).collect();
+    sequences.push(vec![Felt::new(Felt::MODULUS - 1); 9]);
+    sequences.push(vec![Felt::new(u32::MAX as u64); 8]);
+    sequences
+}
+
+/// Returns a vector of `n` random elements, which may be empty.
+fn random_elements<E: Randomizable>(n: usize) -> Vec<E> {
+    if n == 0 {
+        Vec::new()
+    } else {
+        rand_vector(n)
+    }
+}
+
+#[test]
+fn test_hash_elements_on_gpu() {
+    let sequences = sequences();
+    let inputs: Vec<&[Felt]> = sequences.iter().map(Vec::as_slice).collect();
+
+    // without a GPU, there is nothing to compare against the CPU implementation
+    let Some(digests) = hash_elements_on_gpu(&inputs) else {
+        return;
+    };
+    let expected: Vec<RpoDigest> =
+        inputs.iter().map(|input| Rpo256::hash_elements(input)).collect();
+    assert_eq!(digests, expected);
+}
+
+#[test]
+fn test_merge_on_gpu() {
+    let pairs: Vec<[RpoDigest; 2]> = (0..100)
+        .map(|_| {
+            let elements = rand_vector::<Felt>(8);
+            [
+                RpoDigest::new(elements[..4].try_into().unwrap()),
+                RpoDigest::new(elements[4..].try_into().unwrap()),
+            ]
+        })
+        .collect();
+
+    let Some(digests) = merge_on_gpu(&pairs) else {
+        return;
+    };
+    let expected: Vec<RpoDigest> = pairs.iter().map(Rpo256::merge).collect();
+    assert_eq!(digests, expected);
+}
+
+#[test]
+fn test_batch_hashing() {
+    // the batches are large enough to be hashed on the GPU if one is available
+    let sequences: Vec<Vec<QuadFelt>> =
+        (0..MIN_BATCH_SIZE).map(|i| random_elements(i % 7)).collect();
+    let inputs: Vec<&[QuadFelt]> = sequences.iter().map(Vec::as_slice).collect();
+    let expected: Vec<RpoDigest> =
+        inputs.iter().map(|input| Rpo256::hash_elements(input)).collect();
+    assert_eq!(hash_elements_many(&inputs), expected);
+
+    let pairs: Vec<[RpoDigest; 2]> =
+        expected.chunks_exact(2).map(|pair| [pair[0], pair[1]]).collect();
+    let pairs = [pairs.as_slice(), pairs.as_slice()].concat();
+    let expected: Vec<RpoDigest> = pairs.iter().map(Rpo256::merge).collect();
+    assert_eq!(merge_many(&pairs), expected);
+
+    // small batches are hashed on the CPU
+    assert_eq!(merge_many(&pairs[..3]), expected[..3]);
+    assert!(hash_elements_many::<Felt>(&[]).is_empty());
+}
@@ -0,0 +1,88 @@
+use alloc::vec::Vec;
+
+use super::{rescue::Rpo256Xof, Felt, ZERO};
+use crate::{Word, WORD_SIZE};
+
+// CONSTANTS
+// ================================================================================================
+
+/// Number of bytes packed into each field element when absorbing byte strings.
+///
+/// Any 7-byte value is smaller than the field modulus, and thus the packing is injective.
+const BYTES_PER_ELEMENT: usize = 7;
+
+// HASH-TO-FIELD
+// ================================================================================================
+
+/// Hashes the provided bytes into `n` field elements which are uniformly distributed over the
+/// field.
+///
+/// The `domain` separates the outputs of different protocols (or different uses within the same
+/// protocol) and should be unique for each use.
+///
+/// The lengths of `domain` and `bytes` followed by their contents (packed into field elements 7
+/// bytes at a time in little-endian order, with the last element padded with zeros) are absorbed
+/// into an [Rpo256Xof](super::rpo::Rpo256Xof), and the output is squeezed from it. Since the
+/// elements are read directly from the RPO state rather than reduced from integers, they do not
+/// carry the bias of reducing a random 64-bit value modulo the field modulus.
+pub fn hash_to_felts(domain: &[u8], bytes: &[u8], n: usize) -> Vec<Felt> {
+    let mut xof = Rpo256Xof::new();
+    xof.update(&[Felt::new(domain.len() as u64), Felt::new(bytes.len() as u64)]);
+    absorb_bytes(&mut xof, domain);
+    absorb_bytes(&mut xof, bytes);
+
+    let mut result = vec![ZERO; n];
+    xof.finalize().squeeze(&mut result);
+    result
+}
+
+/// Hashes the provided bytes into a word whose elements are uniformly distributed over the field.
+///
+/// This is equivalent to the first 4 elements returned by [hash_to_felts()] for the same inputs.
+pub fn hash_to_word(domain: &[u8], bytes: &[u8]) -> Word {
+    let mut word = [ZERO; WORD_SIZE];
+    word.copy_from_slice(&hash_to_felts(domain, bytes, WORD_SIZE));
+    word
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Packs the provided bytes into field elements and absorbs them into the XOF.
+fn absorb_bytes(xof: &mut Rpo256Xof, bytes: &[u8]) {
+    let elements: Vec<Felt> = bytes
+        .chunks(BYTES_PER_ELEMENT)
+        .map(|chunk| {
+            let mut buf = [0_u8; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            Felt::new(u64::from_le_bytes(buf))
+        })
+        .collect();
+    xof.update(&elements);
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_to_felts, hash_to_word};
+
+    #[test]
+    fn hash_to_felts_separation() {
+        let output = hash_to_felts(b"domain", b"message", 10);
+        assert_eq!(output.len(), 10);
+
+        // shorter outputs should be prefixes of longer ones
+        assert_eq!(hash_to_felts(b"domain", b"message", 3), output[..3]);
+        assert_eq!(hash_to_word(b"domain", b"message"), output[..4]);
+
+        // moving bytes between the domain and the message should change the output
+        assert_ne!(hash_to_felts(b"domainm", b"essage", 10), output);
+
+        // trailing zeros should not be lost to packing
+        assert_ne!(hash_to_felts(b"domain", b"message\0", 10), output);
+        assert_ne!(hash_to_felts(b"", b"", 4), hash_to_felts(b"", b"\0", 4));
+        assert_ne!(hash_to_felts(b"", b"", 4), hash_to_felts(b"\0", b"", 4));
+    }
+}
@@ -0,0 +1,103 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use super::prf::RpoPrf;
+use crate::{Felt, Word, WORD_SIZE};
+
+#[cfg(test)]
+mod tests;
+
+// RPO KDF
+// ================================================================================================
+
+/// A key derivation function (KDF) following the extract-then-expand design of HKDF
+/// ([RFC 5869](https://datatracker.ietf.org/doc/html/rfc5869)), with [RpoPrf] in place of HMAC.
+///
+/// - [RpoKdf::extract()] computes a pseudorandom key `prk = RpoPrf::new(salt).eval(ikm)` from the
+///   salt and the input keying material.
+/// - [RpoKdf::expand()] computes the output keying material as the concatenation of the words
+///   `T(1), T(2), ...`, where `T(i) = RpoPrf::new(prk).eval(T(i - 1) || info || [i])` and `T(0)`
+///   is empty, truncated to the requested number of field elements.
+///
+/// The `info` argument separates keys derived from the same input keying material for different
+/// purposes (e.g., different accounts of a wallet), and should be unique for each purpose.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RpoKdf {
+    prk: Word,
+}
+
+impl RpoKdf {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a KDF keyed by the pseudorandom key extracted from the provided salt and input
+    /// keying material.
+    ///
+    /// The salt does not need to be secret, and may be set to [EMPTY_WORD](crate::EMPTY_WORD) if
+    /// no salt is available.
+    pub fn extract(salt: Word, ikm: &[Felt]) -> Self {
+        Self { prk: RpoPrf::new(salt).eval(ikm) }
+    }
+
+    /// Returns a KDF keyed by the provided pseudorandom key, skipping the extraction step.
+    ///
+    /// This must only be used if `prk` is already uniformly distributed, e.g., if it was derived
+    /// by another KDF.
+    pub fn from_prk(prk: Word) -> Self {
+        Self { prk }
+    }
+
+    // EXPANSION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns `len` field elements of output keying material for the provided context.
+    pub fn expand(&self, info: &[Felt], len: usize) -> Vec<Felt> {
+        let prf = RpoPrf::new(self.prk);
+        let mut result = Vec::with_capacity(len.next_multiple_of(WORD_SIZE));
+        let mut block: Option<Word> = None;
+        let mut counter = 1;
+        while result.len() < len {
+            let mut evaluator = prf.evaluator();
+            if let Some(block) = block {
+                evaluator.update(&block);
+            }
+            evaluator.update(info);
+            evaluator.update(&[Felt::new(counter)]);
+
+            let next = evaluator.finalize();
+            result.extend_from_slice(&next);
+            block = Some(next);
+            counter += 1;
+        }
+        result.truncate(len);
+        result
+    }
+
+    /// Returns a word of output keying material for the provided context; this is equal to the
+    /// first 4 elements returned by [RpoKdf::expand()].
+    pub fn expand_word(&self, info: &[Felt]) -> Word {
+        self.expand(info, WORD_SIZE).try_into().unwrap()
+    }
+
+    /// Returns `len` bytes of output keying material for the provided context.
+    ///
+    /// The bytes are obtained by encoding the field elements returned by [RpoKdf::expand()] as
+    /// 8 bytes in little-endian order. Since the elements are uniformly distributed over the field
+    /// rather than over all 64-bit values, the resulting bytes deviate from the uniform
+    /// distribution by about 2^-32.
+    pub fn expand_bytes(&self, info: &[Felt], len: usize) -> Vec<u8> {
+        let mut result: Vec<u8> = self
+            .expand(info, len.div_ceil(8))
+            .iter()
+            .flat_map(|element| element.as_int().to_le_bytes())
+            .collect();
+        result.truncate(len);
+        result
+    }
+}
+
+impl fmt::Debug for RpoKdf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpoKdf").finish_non_exhaustive()
+    }
+}
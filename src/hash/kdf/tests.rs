@@ -0,0 +1,52 @@
+use rand_utils::rand_vector;
+
+use super::*;
+use crate::{EMPTY_WORD, ONE, ZERO};
+
+#[test]
+fn kdf_expand_blocks() {
+    let ikm = rand_vector::<Felt>(12);
+    let salt: Word = [Felt::new(5), ZERO, ONE, ZERO];
+    let info = [Felt::new(42)];
+    let kdf = RpoKdf::extract(salt, &ikm);
+
+    // the output is the concatenation of the chained PRF blocks
+    let prk = RpoPrf::new(salt).eval(&ikm);
+    let prf = RpoPrf::new(prk);
+    let t1 = prf.eval(&[info[0], Felt::new(1)]);
+    let t2 = prf.eval(&[t1[0], t1[1], t1[2], t1[3], info[0], Felt::new(2)]);
+    let t3 = prf.eval(&[t2[0], t2[1], t2[2], t2[3], info[0], Felt::new(3)]);
+    let expected: Vec<Felt> = [t1, t2, t3].concat();
+    assert_eq!(kdf.expand(&info, 11), expected[..11]);
+    assert_eq!(RpoKdf::from_prk(prk).expand(&info, 12), expected);
+    assert_eq!(kdf.expand_word(&info), t1);
+
+    // shorter outputs are prefixes of longer ones
+    assert_eq!(kdf.expand(&info, 3), expected[..3]);
+    assert!(kdf.expand(&info, 0).is_empty());
+}
+
+#[test]
+fn kdf_separation() {
+    let ikm = rand_vector::<Felt>(4);
+    let kdf = RpoKdf::extract(EMPTY_WORD, &ikm);
+
+    // different contexts, salts and keying material produce unrelated keys
+    let key = kdf.expand_word(&[ONE]);
+    assert_ne!(key, kdf.expand_word(&[Felt::new(2)]));
+    assert_ne!(key, kdf.expand_word(&[]));
+    assert_ne!(key, RpoKdf::extract([ONE, ZERO, ZERO, ZERO], &ikm).expand_word(&[ONE]));
+    assert_ne!(key, RpoKdf::extract(EMPTY_WORD, &ikm[..3]).expand_word(&[ONE]));
+}
+
+#[test]
+fn kdf_expand_bytes() {
+    let kdf = RpoKdf::extract(EMPTY_WORD, &rand_vector::<Felt>(4));
+    let info = [Felt::new(7)];
+
+    let elements = kdf.expand(&info, 9);
+    let expected: Vec<u8> = elements.iter().flat_map(|e| e.as_int().to_le_bytes()).collect();
+    assert_eq!(kdf.expand_bytes(&info, 72), expected);
+    assert_eq!(kdf.expand_bytes(&info, 33), expected[..33]);
+    assert!(kdf.expand_bytes(&info, 0).is_empty());
+}
@@ -0,0 +1,90 @@
+use sha3::Digest as _;
+
+use super::{byte_digest::byte_digest, ElementHasher, Felt, FieldElement, Hasher};
+
+#[cfg(test)]
+mod tests;
+
+// KECCAK256 DIGEST
+// ================================================================================================
+
+byte_digest! {
+    /// 32-bytes output of the Keccak-256 hash function.
+    Keccak256Digest
+}
+
+// KECCAK256 HASHER
+// ================================================================================================
+
+/// Keccak-256 hasher, i.e., the variant of SHA3-256 with the original Keccak padding used by
+/// Ethereum.
+///
+/// Two digests are merged by hashing their concatenation, and field elements are hashed as the
+/// concatenation of the little-endian encodings of their canonical values. Thus, Merkle roots
+/// computed with this hasher match those of Ethereum-style Keccak-256 Merkle trees.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Keccak256;
+
+impl Hasher for Keccak256 {
+    /// Keccak-256 collision resistance is 128-bits for 32-bytes output.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = Keccak256Digest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        Keccak256Digest(sha3::Keccak256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        let mut hasher = sha3::Keccak256::new();
+        hasher.update(values[0].0);
+        hasher.update(values[1].0);
+        Keccak256Digest(hasher.finalize().into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut hasher = sha3::Keccak256::new();
+        hasher.update(seed.0);
+        hasher.update(value.to_le_bytes());
+        Keccak256Digest(hasher.finalize().into())
+    }
+}
+
+impl ElementHasher for Keccak256 {
+    type BaseField = Felt;
+
+    fn hash_elements<E>(elements: &[E]) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let mut hasher = sha3::Keccak256::new();
+        for element in E::slice_as_base_elements(elements) {
+            hasher.update(element.as_int().to_le_bytes());
+        }
+        Keccak256Digest(hasher.finalize().into())
+    }
+}
+
+impl Keccak256 {
+    /// Returns a hash of the provided sequence of bytes.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> Keccak256Digest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    #[inline(always)]
+    pub fn merge(values: &[Keccak256Digest; 2]) -> Keccak256Digest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E>(elements: &[E]) -> Keccak256Digest
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+}
@@ -0,0 +1,13 @@
+use super::*;
+use crate::utils::hex_to_bytes;
+
+#[test]
+fn keccak256_test_vectors() {
+    let expected: [u8; 32] =
+        hex_to_bytes("0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470").unwrap();
+    assert_eq!(Keccak256::hash(b""), Keccak256Digest::from(expected));
+
+    let expected: [u8; 32] =
+        hex_to_bytes("0x4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45").unwrap();
+    assert_eq!(Keccak256::hash(b"abc"), Keccak256Digest::from(expected));
+}
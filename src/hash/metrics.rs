@@ -0,0 +1,168 @@
+//! Counters of permutation invocations of the algebraic hash functions.
+//!
+//! When the `metrics` feature is enabled, every invocation of the RPO, RPX, Anemoi, Poseidon2,
+//! Monolith, and Tip5 permutations (including the invocations made internally while hashing,
+//! merging, or building Merkle trees) increments a global counter for the corresponding hash
+//! function. This makes it possible to attribute the hashing cost of an operation by taking a
+//! [snapshot()] before and after it:
+//!
+//! ```
+//! use miden_crypto::hash::{metrics, rpo::Rpo256};
+//!
+//! let before = metrics::snapshot();
+//! Rpo256::hash(b"some data");
+//! let cost = metrics::snapshot() - before;
+//! assert!(cost.rpo256 >= 1);
+//! ```
+//!
+//! The counters are shared by all threads, and thus the difference of two snapshots includes
+//! the permutations invoked by other threads in the meantime. The legacy Rescue Prime hash
+//! function delegates to Winterfell's implementation and is not counted.
+
+use core::{
+    ops::Sub,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+// COUNTERS
+// ================================================================================================
+
+/// The number of RPO permutations invoked since the start of the process (or the last reset).
+pub(crate) static RPO256_PERMUTATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The number of RPX permutations invoked since the start of the process (or the last reset).
+pub(crate) static RPX256_PERMUTATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The number of Anemoi permutations invoked since the start of the process (or the last reset).
+pub(crate) static ANEMOI256_PERMUTATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The number of Poseidon2 permutations invoked since the start of the process (or the last
+/// reset).
+pub(crate) static POSEIDON2_PERMUTATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The number of Monolith permutations invoked since the start of the process (or the last
+/// reset).
+pub(crate) static MONOLITH_PERMUTATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// The number of Tip5 permutations invoked since the start of the process (or the last reset).
+pub(crate) static TIP5_PERMUTATIONS: AtomicU64 = AtomicU64::new(0);
+
+/// Adds `count` permutation invocations to the provided counter.
+#[inline(always)]
+pub(crate) fn record(counter: &AtomicU64, count: u64) {
+    counter.fetch_add(count, Ordering::Relaxed);
+}
+
+/// Returns the current values of the permutation counters.
+pub fn snapshot() -> MetricsSnapshot {
+    MetricsSnapshot {
+        rpo256: RPO256_PERMUTATIONS.load(Ordering::Relaxed),
+        rpx256: RPX256_PERMUTATIONS.load(Ordering::Relaxed),
+        anemoi256: ANEMOI256_PERMUTATIONS.load(Ordering::Relaxed),
+        poseidon2: POSEIDON2_PERMUTATIONS.load(Ordering::Relaxed),
+        monolith: MONOLITH_PERMUTATIONS.load(Ordering::Relaxed),
+        tip5: TIP5_PERMUTATIONS.load(Ordering::Relaxed),
+    }
+}
+
+/// Resets all permutation counters to zero.
+pub fn reset() {
+    RPO256_PERMUTATIONS.store(0, Ordering::Relaxed);
+    RPX256_PERMUTATIONS.store(0, Ordering::Relaxed);
+    ANEMOI256_PERMUTATIONS.store(0, Ordering::Relaxed);
+    POSEIDON2_PERMUTATIONS.store(0, Ordering::Relaxed);
+    MONOLITH_PERMUTATIONS.store(0, Ordering::Relaxed);
+    TIP5_PERMUTATIONS.store(0, Ordering::Relaxed);
+}
+
+// METRICS SNAPSHOT
+// ================================================================================================
+
+/// Values of the permutation counters at a point in time.
+///
+/// Subtracting an earlier snapshot from a later one yields the number of permutations invoked in
+/// between. If the counters were reset in between, the affected values saturate at zero.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    /// The number of RPO permutations.
+    pub rpo256: u64,
+    /// The number of RPX permutations.
+    pub rpx256: u64,
+    /// The number of Anemoi permutations.
+    pub anemoi256: u64,
+    /// The number of Poseidon2 permutations.
+    pub poseidon2: u64,
+    /// The number of Monolith permutations.
+    pub monolith: u64,
+    /// The number of Tip5 permutations.
+    pub tip5: u64,
+}
+
+impl MetricsSnapshot {
+    /// Returns the total number of permutations of all hash functions.
+    pub fn total(&self) -> u64 {
+        self.rpo256 + self.rpx256 + self.anemoi256 + self.poseidon2 + self.monolith + self.tip5
+    }
+}
+
+impl Sub for MetricsSnapshot {
+    type Output = MetricsSnapshot;
+
+    fn sub(self, rhs: MetricsSnapshot) -> MetricsSnapshot {
+        MetricsSnapshot {
+            rpo256: self.rpo256.saturating_sub(rhs.rpo256),
+            rpx256: self.rpx256.saturating_sub(rhs.rpx256),
+            anemoi256: self.anemoi256.saturating_sub(rhs.anemoi256),
+            poseidon2: self.poseidon2.saturating_sub(rhs.poseidon2),
+            monolith: self.monolith.saturating_sub(rhs.monolith),
+            tip5: self.tip5.saturating_sub(rhs.tip5),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::snapshot;
+    use crate::{
+        hash::{
+            anemoi::Anemoi256, monolith::Monolith, poseidon2::Poseidon2, rpo::Rpo256, rpx::Rpx256,
+            tip5::Tip5,
+        },
+        Felt, ZERO,
+    };
+
+    // other tests may invoke permutations concurrently, and thus the counters are checked only
+    // for lower bounds
+    #[test]
+    fn permutations_are_counted() {
+        let before = snapshot();
+        let mut state = [ZERO; Rpo256::STATE_WIDTH];
+        Rpo256::apply_permutation(&mut state);
+        Rpo256::hash_elements(&[Felt::new(1); 16]);
+        let cost = snapshot() - before;
+        assert!(cost.rpo256 >= 3);
+
+        let before = snapshot();
+        let mut states = [[ZERO; Rpo256::STATE_WIDTH]; 11];
+        Rpo256::apply_permutation_batch(&mut states);
+        let cost = snapshot() - before;
+        assert!(cost.rpo256 >= 11);
+
+        let before = snapshot();
+        Rpx256::hash(b"some data");
+        Anemoi256::hash(b"some data");
+        Poseidon2::hash(b"some data");
+        Monolith::hash(b"some data");
+        Tip5::hash(b"some data");
+        let cost = snapshot() - before;
+        assert!(cost.rpx256 >= 1);
+        assert!(cost.anemoi256 >= 1);
+        assert!(cost.poseidon2 >= 1);
+        assert!(cost.monolith >= 1);
+        assert!(cost.tip5 >= 1);
+        assert!(cost.total() >= 5);
+    }
+}
@@ -2,18 +2,113 @@
 
 use super::{CubeExtension, Felt, FieldElement, StarkField, ONE, ZERO};
 
+pub mod anemoi;
+
 pub mod blake;
 
+pub mod blake2;
+
+#[cfg(feature = "gpu")]
+pub mod gpu;
+
+mod byte_digest;
+
+pub mod kdf;
+
+pub mod keccak;
+
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+pub mod monolith;
+
+pub mod poseidon2;
+
+pub mod prf;
+
+pub mod rescue_prime;
+
+pub mod sha2;
+
+pub mod sha3;
+
+pub mod tip5;
+
 mod rescue;
 pub mod rpo {
-    pub use super::rescue::{Rpo256, RpoDigest};
+    pub use super::rescue::{
+        LengthEncoding, Rpo256, Rpo256Stream, Rpo256Xof, Rpo256XofReader, RpoDigest, RpoDuplex,
+    };
 }
 
 pub mod rpx {
     pub use super::rescue::{Rpx256, RpxDigest};
 }
 
+mod digest_bytes;
+pub use digest_bytes::{DigestBytes, DigestConversionError};
+
+mod hash_to_field;
+pub use hash_to_field::{hash_to_felts, hash_to_word};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The number of bytes read from the input at a time by the `hash_reader()` functions, which is
+/// the same as the size of the chunks read by [Rpo256::hash_stream()](rpo::Rpo256::hash_stream).
+#[cfg(feature = "std")]
+const READER_BUFFER_SIZE: usize = 4 * 1024;
+
 // RE-EXPORTS
 // ================================================================================================
 
 pub use winter_crypto::{Digest, ElementHasher, Hasher};
+
+// EXTENDABLE-OUTPUT FUNCTIONS
+// ================================================================================================
+
+/// Defines an extendable-output function (XOF) over byte strings, i.e., a hash function which can
+/// produce an output of arbitrary length.
+pub trait Xof {
+    /// Reader from which the output of the XOF can be squeezed.
+    type Reader: XofReader;
+
+    /// Returns a reader for the output of the XOF over the concatenation of the provided byte
+    /// strings.
+    fn hash_xof_many(inputs: &[&[u8]]) -> Self::Reader;
+
+    /// Returns a reader for the output of the XOF over the provided sequence of bytes.
+    fn hash_xof(bytes: &[u8]) -> Self::Reader {
+        Self::hash_xof_many(&[bytes])
+    }
+}
+
+/// Defines a reader for the output of an extendable-output function.
+///
+/// Consecutive reads return consecutive parts of the output, and thus the output does not depend
+/// on how it is split into reads.
+pub trait XofReader {
+    /// Fills the provided buffer with the next bytes of the output.
+    fn squeeze_bytes(&mut self, output: &mut [u8]);
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Reads bytes into the buffer until it is full or the reader is exhausted, and returns the
+/// number of bytes read.
+///
+/// Reads failing with [ErrorKind::Interrupted](std::io::ErrorKind::Interrupted) are retried.
+#[cfg(feature = "std")]
+fn read_chunk<R: std::io::Read>(reader: &mut R, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => (),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
@@ -0,0 +1,410 @@
+use core::ops::Range;
+
+use super::{
+    rescue::apply_mds, rpo::RpoDigest, ElementHasher, Felt, FieldElement, Hasher, StarkField, ONE,
+    ZERO,
+};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The number of rounds is set to 6, which is the number of rounds recommended by the
+/// [specifications](https://eprint.iacr.org/2023/1025) for the 64-bit prime field.
+const NUM_ROUNDS: usize = 6;
+
+/// Sponge state is set to 12 field elements; 8 elements are reserved for rate and the remaining 4
+/// elements are reserved for capacity.
+const STATE_WIDTH: usize = 12;
+
+/// The number of elements of the state to which the Bars layer is applied.
+const NUM_BARS: usize = 4;
+
+/// The rate portion of the state is located in elements 0 through 7.
+const RATE_RANGE: Range<usize> = 0..8;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+/// The capacity portion of the state is located in elements 8, 9, 10, and 11.
+const CAPACITY_RANGE: Range<usize> = 8..12;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 0, 1, 2, and 3 (the first half of the rate portion).
+const DIGEST_RANGE: Range<usize> = 0..4;
+
+/// The number of bytes needed to encoded a field element when hashing a byte string.
+const BINARY_CHUNK_SIZE: usize = 7;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of the Monolith hash function with 256-bit output.
+///
+/// The hash function is instantiated as described in the [specifications](https://eprint.iacr.org/2023/1025).
+/// The parameters used to instantiate the function are:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 12 field elements.
+/// * Capacity size: 4 field elements.
+/// * Number of rounds: 6.
+/// * Permutation: the Concrete layer, followed by 6 rounds of `Bars` → `Bricks` → `Concrete`
+///   (with round constants added by Concrete in all rounds but the last), where:
+///   - Bars decomposes each of the first 4 elements into 8 bytes and applies the 8-bit S-Box
+///     `y -> (y ^ (!y <<< 1) & (y <<< 2) & (y <<< 3)) <<< 1` to each byte;
+///   - Bricks is the Feistel layer `x_i += x_{i-1}^2` for every element but the first;
+///   - Concrete multiplies the state by the circulant MDS matrix of [Rpo256](super::rpo::Rpo256).
+///
+/// Unlike the S-Boxes of RPO, the Bars layer is not a low-degree map, and thus Monolith is
+/// considerably faster to evaluate outside of circuits (e.g., for transaction IDs), while its
+/// lookup-based arithmetization suits proof systems with lookup arguments.
+///
+/// The round constants are derived from SHAKE128 as in the reference implementation of the
+/// specifications. No reference test vectors are available for this instance of Monolith in this
+/// crate, and thus compatibility with other implementations is not verified.
+///
+/// The above parameters target a 128-bit security level. The digest consists of four field elements
+/// and it can be serialized into 32 bytes (256 bits). Digests are returned as [RpoDigest] values, since
+/// they have the same shape, so that they can be converted from and into [Word](crate::Word) and
+/// stored and serialized using the same tooling as RPO digests.
+///
+/// ## Hash output consistency
+/// As with [Rpo256](super::rpo::Rpo256), [merge()](Monolith::merge) is consistent with
+/// [hash_elements()](Monolith::hash_elements): merging two digests produces the same result as
+/// hashing the 8 elements which make up these digests, i.e., merging is the permutation of the two
+/// digests truncated to the first 4 elements.
+///
+/// Similarly to [Rpo256](super::rpo::Rpo256), [hash()](Monolith::hash) function is not
+/// consistent with [hash_elements()](Monolith::hash_elements), since it needs to be able to
+/// handle arbitrary binary strings, which may or may not encode valid field elements.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Monolith();
+
+impl Hasher for Monolith {
+    /// Monolith collision resistance is 128-bits.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = RpoDigest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // initialize the state with zeroes
+        let mut state = [ZERO; STATE_WIDTH];
+
+        // determine the number of field elements needed to encode `bytes` when each field element
+        // represents at most 7 bytes; an empty byte string is encoded as a single padded empty
+        // chunk, so that its hash is not the state which results from absorbing nothing.
+        let num_field_elem = bytes.len().div_ceil(BINARY_CHUNK_SIZE).max(1);
+
+        // set the first capacity element to `RATE_WIDTH + (num_field_elem % RATE_WIDTH)`. We do
+        // this to achieve:
+        // 1. Domain separating hashing of `[u8]` from hashing of `[Felt]`.
+        // 2. Avoiding collisions at the `[Felt]` representation of the encoded bytes.
+        state[CAPACITY_RANGE.start] =
+            Felt::from((RATE_WIDTH + (num_field_elem % RATE_WIDTH)) as u8);
+
+        // initialize a buffer to receive the little-endian elements.
+        let mut buf = [0_u8; 8];
+
+        // iterate the chunks of bytes, creating a field element from each chunk and adding it
+        // into the rate portion of the state; every time the rate range is filled, a permutation
+        // is performed.
+        let empty_chunk = core::iter::once(&[][..]).filter(|_| bytes.is_empty());
+        let chunks = empty_chunk.chain(bytes.chunks(BINARY_CHUNK_SIZE)).enumerate();
+        let i = chunks.fold(0, |i, (chunk_idx, chunk)| {
+            // copy the chunk into the buffer
+            if chunk_idx != num_field_elem - 1 {
+                buf[..BINARY_CHUNK_SIZE].copy_from_slice(chunk);
+            } else {
+                // on the last iteration, we pad `buf` with a 1 followed by as many 0's as are
+                // needed to fill it
+                buf.fill(0);
+                buf[..chunk.len()].copy_from_slice(chunk);
+                buf[chunk.len()] = 1;
+            }
+
+            // since we take at most 7 bytes, we are guaranteed that the inputs data will fit
+            // into a single field element.
+            state[RATE_RANGE.start + i] += Felt::new(u64::from_le_bytes(buf));
+
+            if i == RATE_WIDTH - 1 {
+                Self::apply_permutation(&mut state);
+                0
+            } else {
+                i + 1
+            }
+        });
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the permutation. we don't
+        // need to apply any extra padding because the first capacity element contains a flag
+        // indicating the number of field elements constituting the last block.
+        if i != 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // the two digests fill up the rate portion of the state, and the capacity is set to
+        // zeros, as when hashing the 8 elements of the digests
+        let mut state = [ZERO; STATE_WIDTH];
+        state[..4].copy_from_slice(values[0].as_elements());
+        state[4..RATE_RANGE.end].copy_from_slice(values[1].as_elements());
+        Self::apply_permutation(&mut state);
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // hash the seed followed by the value; if the value doesn't fit into a single field
+        // element, it is split into two field elements. inputs of 5 and 6 elements are told apart
+        // by the padding applied by hash_elements().
+        let mut elements = [ZERO; 6];
+        elements[..4].copy_from_slice(seed.as_elements());
+        elements[4] = Felt::new(value);
+        if value < Felt::MODULUS {
+            Self::hash_elements(&elements[..5])
+        } else {
+            elements[5] = Felt::new(value / Felt::MODULUS);
+            Self::hash_elements(&elements)
+        }
+    }
+}
+
+impl ElementHasher for Monolith {
+    type BaseField = Felt;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::slice_as_base_elements(elements);
+
+        // the input is padded by appending a 1 followed by as many 0 as necessary to make its length
+        // a multiple of RATE_WIDTH, unless its length already is a non-zero multiple of RATE_WIDTH;
+        // an empty input is thus padded into a single block.
+        let is_padded = elements.is_empty() || elements.len() % RATE_WIDTH != 0;
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to 1 if the input is padded.
+        let mut state = [ZERO; STATE_WIDTH];
+        if is_padded {
+            state[CAPACITY_RANGE.start] = ONE;
+        }
+
+        // absorb elements into the state one by one until the rate portion of the state is filled
+        // up; then apply the permutation and start absorbing again; repeat until all elements have
+        // been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] += element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // absorb the padding and apply the final permutation; since elements are absorbed by
+        // addition, the zeros of the padding do not need to be added.
+        if is_padded {
+            state[RATE_RANGE.start + i] += ONE;
+            Self::apply_permutation(&mut state);
+        }
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Monolith {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds is set to 6 to target 128-bit security level.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 12 field elements; 8 elements are reserved for rate and the
+    /// remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 0 through 7 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 8, 9, 10, and 11.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 0, 1, 2, and 3.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// Round constants added to the state by the Concrete layer of all rounds but the last.
+    pub const ROUND_CONSTANTS: [[Felt; STATE_WIDTH]; NUM_ROUNDS - 1] = ROUND_CONSTANTS;
+
+    // TRAIT PASS-THROUGH FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a hash of the provided sequence of bytes.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> RpoDigest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    #[inline(always)]
+    pub fn merge(values: &[RpoDigest; 2]) -> RpoDigest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E: FieldElement<BaseField = Felt>>(elements: &[E]) -> RpoDigest {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+
+    // MONOLITH PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies the Monolith permutation to the provided state.
+    #[inline(always)]
+    pub fn apply_permutation(state: &mut [Felt; STATE_WIDTH]) {
+        #[cfg(feature = "metrics")]
+        crate::hash::metrics::record(&crate::hash::metrics::MONOLITH_PERMUTATIONS, 1);
+        apply_mds(state);
+        for round in 0..NUM_ROUNDS {
+            Self::apply_round(state, round);
+        }
+    }
+
+    /// Monolith round function.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [Felt; STATE_WIDTH], round: usize) {
+        apply_bars(state);
+        apply_bricks(state);
+        apply_mds(state);
+        if let Some(constants) = ROUND_CONSTANTS.get(round) {
+            state.iter_mut().zip(constants).for_each(|(s, &c)| *s += c);
+        }
+    }
+}
+
+// ROUND FUNCTIONS
+// ================================================================================================
+
+/// Applies the Bars layer, i.e., the bytewise S-Box, to the first [NUM_BARS] elements.
+///
+/// The S-Box maps the bytes 0x00 and 0xff to themselves, and thus the canonical value of an
+/// element (whose upper 4 bytes are all 0xff only if its lower 4 bytes are all 0x00) is mapped to
+/// the canonical value of an element.
+#[inline(always)]
+fn apply_bars(state: &mut [Felt; STATE_WIDTH]) {
+    for s in state[..NUM_BARS].iter_mut() {
+        *s = Felt::new(bar(s.as_int()));
+    }
+}
+
+/// Applies the 8-bit S-Box `y -> (y ^ (!y <<< 1) & (y <<< 2) & (y <<< 3)) <<< 1` to every byte
+/// of the provided value.
+#[inline(always)]
+fn bar(value: u64) -> u64 {
+    // rotates every byte of the value to the left by the provided number of bits
+    let rotl = |x: u64, bits: u32| {
+        let high = u64::from_ne_bytes([0xff_u8 << (8 - bits); 8]);
+        ((x & high) >> (8 - bits)) | ((x & !high) << bits)
+    };
+
+    let t = value ^ (rotl(!value, 1) & rotl(value, 2) & rotl(value, 3));
+    rotl(t, 1)
+}
+
+/// Applies the Bricks layer, i.e., adds the square of each element but the last to the next
+/// element.
+#[inline(always)]
+fn apply_bricks(state: &mut [Felt; STATE_WIDTH]) {
+    for i in (1..STATE_WIDTH).rev() {
+        state[i] += state[i - 1].square();
+    }
+}
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Monolith round constants; derived as in the reference implementation of the specifications:
+/// SHAKE128 is applied to the ASCII string `Monolith`, followed by the state width and the number
+/// of rounds as single bytes, the modulus as 8 little-endian bytes and the bit sizes of the 8
+/// lookups of the Bars layer, i.e., 8 bytes equal to 8. The output is read in chunks of 8 bytes,
+/// which are interpreted as little-endian integers, and integers which are not smaller than the
+/// modulus are rejected. The last round has no round constants.
+const ROUND_CONSTANTS: [[Felt; STATE_WIDTH]; NUM_ROUNDS - 1] = [
+    [
+        Felt::new(13596126580325903823),
+        Felt::new(5676126986831820406),
+        Felt::new(11349149288412960427),
+        Felt::new(3368797843020733411),
+        Felt::new(16240671731749717664),
+        Felt::new(9273190757374900239),
+        Felt::new(14446552112110239438),
+        Felt::new(4033077683985131644),
+        Felt::new(4291229347329361293),
+        Felt::new(13231607645683636062),
+        Felt::new(1383651072186713277),
+        Felt::new(8898815177417587567),
+    ],
+    [
+        Felt::new(2383619671172821638),
+        Felt::new(6065528368924797662),
+        Felt::new(16737578966352303081),
+        Felt::new(2661700069680749654),
+        Felt::new(7414030722730336790),
+        Felt::new(18124970299993404776),
+        Felt::new(9169923000283400738),
+        Felt::new(15832813151034110977),
+        Felt::new(16245117847613094506),
+        Felt::new(11056181639108379773),
+        Felt::new(10546400734398052938),
+        Felt::new(8443860941261719174),
+    ],
+    [
+        Felt::new(15799082741422909885),
+        Felt::new(13421235861052008152),
+        Felt::new(15448208253823605561),
+        Felt::new(2540286744040770964),
+        Felt::new(2895626806801935918),
+        Felt::new(8644593510196221619),
+        Felt::new(17722491003064835823),
+        Felt::new(5166255496419771636),
+        Felt::new(1015740739405252346),
+        Felt::new(4400043467547597488),
+        Felt::new(5176473243271652644),
+        Felt::new(4517904634837939508),
+    ],
+    [
+        Felt::new(18341030605319882173),
+        Felt::new(13366339881666916534),
+        Felt::new(6291492342503367536),
+        Felt::new(10004214885638819819),
+        Felt::new(4748655089269860551),
+        Felt::new(1520762444865670308),
+        Felt::new(8393589389936386108),
+        Felt::new(11025183333304586284),
+        Felt::new(5993305003203422738),
+        Felt::new(458912836931247573),
+        Felt::new(5947003897778655410),
+        Felt::new(17184667486285295106),
+    ],
+    [
+        Felt::new(15710528677110011358),
+        Felt::new(8929476121507374707),
+        Felt::new(2351989866172789037),
+        Felt::new(11264145846854799752),
+        Felt::new(14924075362538455764),
+        Felt::new(10107004551857451916),
+        Felt::new(18325221206052792232),
+        Felt::new(16751515052585522105),
+        Felt::new(15305034267720085905),
+        Felt::new(15639149412312342017),
+        Felt::new(14624541102106656564),
+        Felt::new(3542311898554959098),
+    ],
+];
@@ -0,0 +1,202 @@
+use proptest::prelude::*;
+use rand_utils::rand_value;
+
+use super::*;
+use crate::hash::{rpo::Rpo256, sha3::Shake128, Xof, XofReader};
+use alloc::vec::Vec;
+
+#[test]
+fn constants() {
+    let mut reader = Shake128::hash_xof_many(&[
+        b"Monolith",
+        &[STATE_WIDTH as u8, NUM_ROUNDS as u8],
+        &Felt::MODULUS.to_le_bytes(),
+        &[8; 8],
+    ]);
+    for constants in ROUND_CONSTANTS {
+        for constant in constants {
+            let mut bytes = [0_u8; 8];
+            let value = loop {
+                reader.squeeze_bytes(&mut bytes);
+                let value = u64::from_le_bytes(bytes);
+                if value < Felt::MODULUS {
+                    break value;
+                }
+            };
+            assert_eq!(constant, Felt::new(value));
+        }
+    }
+}
+
+#[test]
+fn bars_are_bytewise_permutations() {
+    // the S-Box is a permutation of the bytes which fixes 0x00 and 0xff
+    let sbox =
+        |y: u8| (y ^ ((!y).rotate_left(1) & y.rotate_left(2) & y.rotate_left(3))).rotate_left(1);
+    let mut images = [false; 256];
+    for byte in 0..=255_u8 {
+        let image = bar(byte as u64);
+        assert_eq!(image, sbox(byte) as u64);
+        assert!(!images[image as usize]);
+        images[image as usize] = true;
+
+        // the S-Box is applied to every byte independently
+        for i in 0..8 {
+            assert_eq!(bar((byte as u64) << (8 * i)), image << (8 * i));
+        }
+    }
+    assert_eq!(bar(0), 0);
+    assert_eq!(bar(0xff), 0xff);
+
+    // canonical values of elements are mapped to canonical values of elements
+    for value in [Felt::MODULUS - 1, 0xffffffff, 0xffffffff00000000] {
+        assert!(bar(value) < Felt::MODULUS);
+    }
+    for _ in 0..100 {
+        let element: Felt = rand_value();
+        assert!(bar(element.as_int()) < Felt::MODULUS);
+    }
+}
+
+#[test]
+fn concrete_is_circulant() {
+    // the Concrete layer multiplies the state by circ(7, 23, 8, 26, 13, 10, 9, 7, 6, 22, 21, 8)
+    let row = [7, 23, 8, 26, 13, 10, 9, 7, 6, 22, 21, 8];
+    let input: [Felt; STATE_WIDTH] = core::array::from_fn(|_| rand_value());
+    let expected: [Felt; STATE_WIDTH] = core::array::from_fn(|i| {
+        (0..STATE_WIDTH).fold(ZERO, |acc, j| {
+            acc + Felt::new(row[(STATE_WIDTH + j - i) % STATE_WIDTH]) * input[j]
+        })
+    });
+    let mut actual = input;
+    apply_mds(&mut actual);
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn monolith_merge() {
+    let digests = [
+        RpoDigest::new(core::array::from_fn(|i| Felt::new(i as u64))),
+        RpoDigest::new(core::array::from_fn(|i| Felt::new(i as u64 + 4))),
+    ];
+
+    // merging truncates the permutation of the two digests, which is the same as hashing the 8
+    // elements of the digests
+    let mut state: [Felt; STATE_WIDTH] = core::array::from_fn(|i| Felt::new(i as u64));
+    state[CAPACITY_RANGE].fill(ZERO);
+    Monolith::apply_permutation(&mut state);
+    assert_eq!(
+        Monolith::merge(&digests),
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    );
+
+    let elements: Vec<Felt> = RpoDigest::digests_as_elements(digests.iter()).copied().collect();
+    assert_eq!(Monolith::merge(&digests), Monolith::hash_elements(&elements));
+    assert_ne!(Monolith::merge(&digests), Rpo256::merge(&digests));
+
+    // the order of the digests matters
+    assert_ne!(Monolith::merge(&digests), Monolith::merge(&[digests[1], digests[0]]));
+}
+
+#[test]
+fn monolith_merge_with_int() {
+    let seed = Monolith::hash(b"seed");
+    let elements: Vec<Felt> = seed.as_elements().iter().copied().chain([Felt::new(42)]).collect();
+    assert_eq!(Monolith::merge_with_int(seed, 42), Monolith::hash_elements(&elements));
+
+    let value = Felt::MODULUS + 5;
+    let elements: Vec<Felt> =
+        seed.as_elements().iter().copied().chain([Felt::new(value), ONE]).collect();
+    assert_eq!(Monolith::merge_with_int(seed, value), Monolith::hash_elements(&elements));
+}
+
+#[test]
+fn monolith_hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Monolith::hash(&[1_u8, 2, 3]);
+    let r2 = Monolith::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let elements = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4), Felt::new(5)];
+    let r1 = Monolith::hash_elements(&elements);
+    let r2 = Monolith::hash_elements(&[elements.as_slice(), &[ZERO]].concat());
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn monolith_hash_empty_input() {
+    // empty inputs are padded into a single block, and thus their hashes are not the zero digest
+    let zero = RpoDigest::default();
+    assert_ne!(Monolith::hash(&[]), zero);
+    assert_ne!(Monolith::hash_elements::<Felt>(&[]), zero);
+
+    // the padded empty input is domain separated from a block which looks like the padding
+    let empty = Monolith::hash_elements::<Felt>(&[]);
+    assert_ne!(empty, Monolith::hash_elements(&[ZERO; RATE_WIDTH]));
+    let mut padding = [ZERO; RATE_WIDTH];
+    padding[0] = ONE;
+    assert_ne!(empty, Monolith::hash_elements(&padding));
+    assert_ne!(Monolith::hash(&[]), Monolith::hash(&[0]));
+    assert_ne!(Monolith::hash(&[]), empty);
+}
+
+#[test]
+fn monolith_regression_vectors() {
+    // no reference test vectors are available for this instance of Monolith in this crate; these
+    // vectors were computed by this implementation and guard against accidental changes to the
+    // permutation and the sponge
+    let mut state: [Felt; STATE_WIDTH] = core::array::from_fn(|i| Felt::new(i as u64));
+    Monolith::apply_permutation(&mut state);
+    let expected = [
+        5867581605548782913,
+        588867029099903233,
+        6043817495575026667,
+        805786589926590032,
+        9919982299747097782,
+        6718641691835914685,
+        7951881005429661950,
+        15453177927755089358,
+        974633365445157727,
+        9654662171963364206,
+        6281307445101925412,
+        13745376999934453119,
+    ];
+    assert_eq!(state, expected.map(Felt::new));
+
+    let elements: Vec<Felt> = (0..8).map(Felt::new).collect();
+    let expected = [
+        8597293992452543654,
+        13251886779405042379,
+        6979248705951146223,
+        15358842403937303290,
+    ];
+    assert_eq!(Monolith::hash_elements(&elements), RpoDigest::new(expected.map(Felt::new)));
+
+    let expected = [
+        2126249551872888891,
+        13977715125751048589,
+        8207426865744467872,
+        3826389397625626185,
+    ];
+    assert_eq!(Monolith::hash(b"abc"), RpoDigest::new(expected.map(Felt::new)));
+}
+
+proptest! {
+    #[test]
+    fn monolith_hash_elements_padding(ref values in any::<Vec<u64>>(), extra in any::<u64>()) {
+        let elements: Vec<Felt> = values.iter().map(|&v| Felt::new(v)).collect();
+        let digest = Monolith::hash_elements(&elements);
+        prop_assert_ne!(digest, Rpo256::hash_elements(&elements));
+
+        // appending any element, including a zero, changes the hash
+        let extended: Vec<Felt> = elements.iter().copied().chain([Felt::new(extra)]).collect();
+        prop_assert_ne!(Monolith::hash_elements(&extended), digest);
+    }
+
+    #[test]
+    fn monolith_hash_bytes_padding(ref bytes in any::<Vec<u8>>(), extra in any::<u8>()) {
+        let extended: Vec<u8> = bytes.iter().copied().chain([extra]).collect();
+        prop_assert_ne!(Monolith::hash(&extended), Monolith::hash(bytes));
+    }
+}
@@ -0,0 +1,537 @@
+use core::ops::Range;
+
+use super::{rpo::RpoDigest, ElementHasher, Felt, FieldElement, Hasher, StarkField, ONE, ZERO};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The number of full rounds is set to 8, half of which are applied before and half after the
+/// partial rounds; this is the number of full rounds required by the
+/// [specifications](https://eprint.iacr.org/2023/323) for 128-bit security.
+const NUM_FULL_ROUNDS: usize = 8;
+
+/// The number of partial rounds is set to 22, which is the number of partial rounds required by
+/// the specifications for 128-bit security with a state of 12 elements and an S-Box of degree 7
+/// over the 64-bit prime field.
+const NUM_PARTIAL_ROUNDS: usize = 22;
+
+/// Sponge state is set to 12 field elements; 8 elements are reserved for rate and the remaining 4
+/// elements are reserved for capacity.
+const STATE_WIDTH: usize = 12;
+
+/// The rate portion of the state is located in elements 0 through 7.
+const RATE_RANGE: Range<usize> = 0..8;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+/// The capacity portion of the state is located in elements 8, 9, 10, and 11.
+const CAPACITY_RANGE: Range<usize> = 8..12;
+
+/// The output of the hash function is a digest which consists of 4 field elements or 32 bytes.
+///
+/// The digest is returned from state elements 0, 1, 2, and 3 (the first half of the rate portion).
+const DIGEST_RANGE: Range<usize> = 0..4;
+
+/// The number of bytes needed to encoded a field element when hashing a byte string.
+const BINARY_CHUNK_SIZE: usize = 7;
+
+/// S-Box power.
+const ALPHA: u64 = 7;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of the Poseidon2 hash function with 256-bit output.
+///
+/// The hash function is instantiated as described in the [specifications](https://eprint.iacr.org/2023/323).
+/// The parameters used to instantiate the function are:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 12 field elements.
+/// * Capacity size: 4 field elements.
+/// * S-Box: x^7.
+/// * Number of rounds: 8 full rounds and 22 partial rounds.
+/// * Permutation: the external linear layer, followed by 4 full rounds, 22 partial rounds and
+///   4 full rounds. A full round adds a constant to every element, applies the S-Box to every
+///   element and applies the external linear layer; a partial round adds a constant to the first
+///   element, applies the S-Box to the first element and applies the internal linear layer.
+///
+/// The round constants are generated with the Grain LFSR as in the reference implementation of
+/// the specifications, and the diagonal of the internal linear layer is chosen such that the
+/// layer does not admit invariant subspace trails. No reference test vectors are available for
+/// this instance of Poseidon2 in this crate, and thus compatibility with other implementations is
+/// not verified.
+///
+/// The above parameters target a 128-bit security level. The digest consists of four field elements
+/// and it can be serialized into 32 bytes (256 bits). Digests are returned as [RpoDigest] values, since
+/// they have the same shape, so that they can be converted from and into [Word](crate::Word) and
+/// stored and serialized using the same tooling as RPO digests.
+///
+/// ## Hash output consistency
+/// As with [Rpo256](super::rpo::Rpo256), [merge()](Poseidon2::merge) is consistent with
+/// [hash_elements()](Poseidon2::hash_elements): merging two digests produces the same result as
+/// hashing the 8 elements which make up these digests, i.e., merging is the permutation of the two
+/// digests truncated to the first 4 elements.
+///
+/// Similarly to [Rpo256](super::rpo::Rpo256), [hash()](Poseidon2::hash) function is not
+/// consistent with [hash_elements()](Poseidon2::hash_elements), since it needs to be able to
+/// handle arbitrary binary strings, which may or may not encode valid field elements.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Poseidon2();
+
+impl Hasher for Poseidon2 {
+    /// Poseidon2 collision resistance is 128-bits.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = RpoDigest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // initialize the state with zeroes
+        let mut state = [ZERO; STATE_WIDTH];
+
+        // determine the number of field elements needed to encode `bytes` when each field element
+        // represents at most 7 bytes; an empty byte string is encoded as a single padded empty
+        // chunk, so that its hash is not the state which results from absorbing nothing.
+        let num_field_elem = bytes.len().div_ceil(BINARY_CHUNK_SIZE).max(1);
+
+        // set the first capacity element to `RATE_WIDTH + (num_field_elem % RATE_WIDTH)`. We do
+        // this to achieve:
+        // 1. Domain separating hashing of `[u8]` from hashing of `[Felt]`.
+        // 2. Avoiding collisions at the `[Felt]` representation of the encoded bytes.
+        state[CAPACITY_RANGE.start] =
+            Felt::from((RATE_WIDTH + (num_field_elem % RATE_WIDTH)) as u8);
+
+        // initialize a buffer to receive the little-endian elements.
+        let mut buf = [0_u8; 8];
+
+        // iterate the chunks of bytes, creating a field element from each chunk and adding it
+        // into the rate portion of the state; every time the rate range is filled, a permutation
+        // is performed.
+        let empty_chunk = core::iter::once(&[][..]).filter(|_| bytes.is_empty());
+        let chunks = empty_chunk.chain(bytes.chunks(BINARY_CHUNK_SIZE)).enumerate();
+        let i = chunks.fold(0, |i, (chunk_idx, chunk)| {
+            // copy the chunk into the buffer
+            if chunk_idx != num_field_elem - 1 {
+                buf[..BINARY_CHUNK_SIZE].copy_from_slice(chunk);
+            } else {
+                // on the last iteration, we pad `buf` with a 1 followed by as many 0's as are
+                // needed to fill it
+                buf.fill(0);
+                buf[..chunk.len()].copy_from_slice(chunk);
+                buf[chunk.len()] = 1;
+            }
+
+            // since we take at most 7 bytes, we are guaranteed that the inputs data will fit
+            // into a single field element.
+            state[RATE_RANGE.start + i] += Felt::new(u64::from_le_bytes(buf));
+
+            if i == RATE_WIDTH - 1 {
+                Self::apply_permutation(&mut state);
+                0
+            } else {
+                i + 1
+            }
+        });
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the permutation. we don't
+        // need to apply any extra padding because the first capacity element contains a flag
+        // indicating the number of field elements constituting the last block.
+        if i != 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // the two digests fill up the rate portion of the state, and the capacity is set to
+        // zeros, as when hashing the 8 elements of the digests
+        let mut state = [ZERO; STATE_WIDTH];
+        state[..4].copy_from_slice(values[0].as_elements());
+        state[4..RATE_RANGE.end].copy_from_slice(values[1].as_elements());
+        Self::apply_permutation(&mut state);
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // hash the seed followed by the value; if the value doesn't fit into a single field
+        // element, it is split into two field elements. inputs of 5 and 6 elements are told apart
+        // by the padding applied by hash_elements().
+        let mut elements = [ZERO; 6];
+        elements[..4].copy_from_slice(seed.as_elements());
+        elements[4] = Felt::new(value);
+        if value < Felt::MODULUS {
+            Self::hash_elements(&elements[..5])
+        } else {
+            elements[5] = Felt::new(value / Felt::MODULUS);
+            Self::hash_elements(&elements)
+        }
+    }
+}
+
+impl ElementHasher for Poseidon2 {
+    type BaseField = Felt;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements
+        let elements = E::slice_as_base_elements(elements);
+
+        // the input is padded by appending a 1 followed by as many 0 as necessary to make its length
+        // a multiple of RATE_WIDTH, unless its length already is a non-zero multiple of RATE_WIDTH;
+        // an empty input is thus padded into a single block.
+        let is_padded = elements.is_empty() || elements.len() % RATE_WIDTH != 0;
+
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to 1 if the input is padded.
+        let mut state = [ZERO; STATE_WIDTH];
+        if is_padded {
+            state[CAPACITY_RANGE.start] = ONE;
+        }
+
+        // absorb elements into the state one by one until the rate portion of the state is filled
+        // up; then apply the permutation and start absorbing again; repeat until all elements have
+        // been absorbed
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] += element;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // absorb the padding and apply the final permutation; since elements are absorbed by
+        // addition, the zeros of the padding do not need to be added.
+        if is_padded {
+            state[RATE_RANGE.start + i] += ONE;
+            Self::apply_permutation(&mut state);
+        }
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Poseidon2 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of full rounds is set to 8 to target 128-bit security level.
+    pub const NUM_FULL_ROUNDS: usize = NUM_FULL_ROUNDS;
+
+    /// The number of partial rounds is set to 22 to target 128-bit security level.
+    pub const NUM_PARTIAL_ROUNDS: usize = NUM_PARTIAL_ROUNDS;
+
+    /// Sponge state is set to 12 field elements; 8 elements are reserved for rate and the
+    /// remaining 4 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 0 through 7 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 8, 9, 10, and 11.
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 0, 1, 2, and 3.
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// Round constants added to the state in the full rounds.
+    pub const EXTERNAL_ROUND_CONSTANTS: [[Felt; STATE_WIDTH]; NUM_FULL_ROUNDS] =
+        EXTERNAL_ROUND_CONSTANTS;
+
+    /// Round constants added to the first element of the state in the partial rounds.
+    pub const INTERNAL_ROUND_CONSTANTS: [Felt; NUM_PARTIAL_ROUNDS] = INTERNAL_ROUND_CONSTANTS;
+
+    /// The 4x4 MDS matrix which the external linear layer is built from.
+    pub const M4: [[Felt; 4]; 4] = M4;
+
+    /// The diagonal of the internal linear layer minus the identity, i.e., the internal linear
+    /// layer maps `x` to `x_i * INTERNAL_DIAGONAL[i] + sum(x)`.
+    pub const INTERNAL_DIAGONAL: [Felt; STATE_WIDTH] = INTERNAL_DIAGONAL;
+
+    /// The S-Box power.
+    pub const ALPHA: u64 = ALPHA;
+
+    // TRAIT PASS-THROUGH FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a hash of the provided sequence of bytes.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> RpoDigest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    #[inline(always)]
+    pub fn merge(values: &[RpoDigest; 2]) -> RpoDigest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E: FieldElement<BaseField = Felt>>(elements: &[E]) -> RpoDigest {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+
+    // POSEIDON2 PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies the Poseidon2 permutation to the provided state.
+    #[inline(always)]
+    pub fn apply_permutation(state: &mut [Felt; STATE_WIDTH]) {
+        #[cfg(feature = "metrics")]
+        crate::hash::metrics::record(&crate::hash::metrics::POSEIDON2_PERMUTATIONS, 1);
+        apply_external_linear_layer(state);
+        for round in 0..NUM_FULL_ROUNDS / 2 {
+            Self::apply_full_round(state, round);
+        }
+        for round in 0..NUM_PARTIAL_ROUNDS {
+            Self::apply_partial_round(state, round);
+        }
+        for round in NUM_FULL_ROUNDS / 2..NUM_FULL_ROUNDS {
+            Self::apply_full_round(state, round);
+        }
+    }
+
+    /// Poseidon2 full round function; `round` is the index of the full round.
+    #[inline(always)]
+    pub fn apply_full_round(state: &mut [Felt; STATE_WIDTH], round: usize) {
+        state.iter_mut().zip(EXTERNAL_ROUND_CONSTANTS[round]).for_each(|(s, c)| *s += c);
+        state.iter_mut().for_each(|s| *s = s.exp7());
+        apply_external_linear_layer(state);
+    }
+
+    /// Poseidon2 partial round function; `round` is the index of the partial round.
+    #[inline(always)]
+    pub fn apply_partial_round(state: &mut [Felt; STATE_WIDTH], round: usize) {
+        state[0] += INTERNAL_ROUND_CONSTANTS[round];
+        state[0] = state[0].exp7();
+        apply_internal_linear_layer(state);
+    }
+}
+
+// LINEAR LAYERS
+// ================================================================================================
+
+/// Applies the external linear layer, i.e., the matrix `circ(2 * M4, M4, M4)`: every chunk of 4
+/// elements is multiplied by [M4], and the sum of the chunks is added to each chunk.
+#[inline(always)]
+fn apply_external_linear_layer(state: &mut [Felt; STATE_WIDTH]) {
+    for chunk in state.chunks_exact_mut(4) {
+        apply_m4(chunk.try_into().unwrap());
+    }
+
+    let mut sums = [ZERO; 4];
+    for chunk in state.chunks_exact(4) {
+        sums.iter_mut().zip(chunk).for_each(|(sum, &s)| *sum += s);
+    }
+    for chunk in state.chunks_exact_mut(4) {
+        chunk.iter_mut().zip(sums).for_each(|(s, sum)| *s += sum);
+    }
+}
+
+/// Multiplies the provided chunk by [M4] using 8 additions and 4 doublings.
+#[inline(always)]
+fn apply_m4(x: &mut [Felt; 4]) {
+    let t0 = x[0] + x[1];
+    let t1 = x[2] + x[3];
+    let t2 = x[1].double() + t1;
+    let t3 = x[3].double() + t0;
+    let t4 = t1.double().double() + t3;
+    let t5 = t0.double().double() + t2;
+    let t6 = t3 + t5;
+    let t7 = t2 + t4;
+    *x = [t6, t5, t7, t4];
+}
+
+/// Applies the internal linear layer, i.e., the matrix `J + diag(INTERNAL_DIAGONAL)`, where `J` is
+/// the matrix of all ones.
+#[inline(always)]
+fn apply_internal_linear_layer(state: &mut [Felt; STATE_WIDTH]) {
+    let sum = state.iter().fold(ZERO, |acc, &s| acc + s);
+    state.iter_mut().zip(INTERNAL_DIAGONAL).for_each(|(s, d)| *s = *s * d + sum);
+}
+
+// MATRICES
+// ================================================================================================
+
+/// The 4x4 MDS matrix from the specifications which the external linear layer is built from.
+const M4: [[Felt; 4]; 4] = [
+    [Felt::new(5), Felt::new(7), Felt::new(1), Felt::new(3)],
+    [Felt::new(4), Felt::new(6), Felt::new(1), Felt::new(1)],
+    [Felt::new(1), Felt::new(3), Felt::new(5), Felt::new(7)],
+    [Felt::new(1), Felt::new(1), Felt::new(4), Felt::new(6)],
+];
+
+/// The diagonal of the internal linear layer minus the identity. The minimal polynomials of the
+/// powers `M_I^i` of the internal linear layer for `1 <= i <= 2 * STATE_WIDTH` are irreducible and
+/// of degree [STATE_WIDTH], which rules out invariant subspace trails over the partial rounds.
+const INTERNAL_DIAGONAL: [Felt; STATE_WIDTH] = [
+    Felt::new(14102670999874605824),
+    Felt::new(15585654191999307702),
+    Felt::new(940187017142450255),
+    Felt::new(8747386241522630711),
+    Felt::new(6750641561540124747),
+    Felt::new(7440998025584530007),
+    Felt::new(6136358134615751536),
+    Felt::new(12413576830284969611),
+    Felt::new(11675438539028694709),
+    Felt::new(17580553691069642926),
+    Felt::new(892707462476851331),
+    Felt::new(15167485180850043744),
+];
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Poseidon2 round constants; generated as by the parameter script of the reference
+/// implementation of the specifications: the Grain LFSR is initialized with the parameters of the
+/// instance (a prime field of 64 bits, the S-Box x^ALPHA, a state of 12 elements, 8 full rounds and
+/// 22 partial rounds), and 12 field elements are sampled for every round. The full rounds use all
+/// constants of their round, while the partial rounds use only the first constant of their round.
+const EXTERNAL_ROUND_CONSTANTS: [[Felt; STATE_WIDTH]; NUM_FULL_ROUNDS] = [
+    [
+        Felt::new(1431286215153372998),
+        Felt::new(3509349009260703107),
+        Felt::new(2289575380984896342),
+        Felt::new(10625215922958251110),
+        Felt::new(17137022507167291684),
+        Felt::new(17143426961497010024),
+        Felt::new(9589775313463224365),
+        Felt::new(7736066733515538648),
+        Felt::new(2217569167061322248),
+        Felt::new(10394930802584583083),
+        Felt::new(4612393375016695705),
+        Felt::new(5332470884919453534),
+    ],
+    [
+        Felt::new(8724526834049581439),
+        Felt::new(17673787971454860688),
+        Felt::new(2519987773101056005),
+        Felt::new(7999687124137420323),
+        Felt::new(18312454652563306701),
+        Felt::new(15136091233824155669),
+        Felt::new(1257110570403430003),
+        Felt::new(5665449074466664773),
+        Felt::new(16178737609685266571),
+        Felt::new(52855143527893348),
+        Felt::new(8084454992943870230),
+        Felt::new(2597062441266647183),
+    ],
+    [
+        Felt::new(3342624911463171251),
+        Felt::new(6781356195391537436),
+        Felt::new(4697929572322733707),
+        Felt::new(4179687232228901671),
+        Felt::new(17841073646522133059),
+        Felt::new(18340176721233187897),
+        Felt::new(13152929999122219197),
+        Felt::new(6306257051437840427),
+        Felt::new(4974451914008050921),
+        Felt::new(11258703678970285201),
+        Felt::new(581736081259960204),
+        Felt::new(18323286026903235604),
+    ],
+    [
+        Felt::new(10250026231324330997),
+        Felt::new(13321947507807660157),
+        Felt::new(13020725208899496943),
+        Felt::new(11416990495425192684),
+        Felt::new(7221795794796219413),
+        Felt::new(2607917872900632985),
+        Felt::new(2591896057192169329),
+        Felt::new(10485489452304998145),
+        Felt::new(9480186048908910015),
+        Felt::new(2645141845409940474),
+        Felt::new(16242299839765162610),
+        Felt::new(12203738590896308135),
+    ],
+    [
+        Felt::new(10552448846206288151),
+        Felt::new(14987673924494666433),
+        Felt::new(18035303280469462414),
+        Felt::new(16595113834715919465),
+        Felt::new(15208661533916677630),
+        Felt::new(4170608138187333497),
+        Felt::new(16304084357983152470),
+        Felt::new(2331503858766652994),
+        Felt::new(8776079357547932587),
+        Felt::new(18299646478835171989),
+        Felt::new(3681263166902989193),
+        Felt::new(12612029705709390274),
+    ],
+    [
+        Felt::new(12014669431902405777),
+        Felt::new(11319504285297576766),
+        Felt::new(5234999940078631477),
+        Felt::new(1125448944938006422),
+        Felt::new(2164405204907480972),
+        Felt::new(6168495504522907053),
+        Felt::new(6250236942243891229),
+        Felt::new(18269902991411124149),
+        Felt::new(9426885685329917236),
+        Felt::new(4521800374915508165),
+        Felt::new(2213719649464492152),
+        Felt::new(9422759956003735939),
+    ],
+    [
+        Felt::new(12723275943377720767),
+        Felt::new(14785736031955679545),
+        Felt::new(15257683393549924851),
+        Felt::new(14586462537439744229),
+        Felt::new(13109892360729616102),
+        Felt::new(18054952537889795742),
+        Felt::new(12589969976105374274),
+        Felt::new(1436163932748701916),
+        Felt::new(14879322534176465619),
+        Felt::new(17580838042056220468),
+        Felt::new(17970300042937392952),
+        Felt::new(1420156878331078790),
+    ],
+    [
+        Felt::new(17310902395782251544),
+        Felt::new(9021117459098865178),
+        Felt::new(9956374953785489337),
+        Felt::new(9283926179170577664),
+        Felt::new(2866744588122882663),
+        Felt::new(12613310502798528952),
+        Felt::new(48642999969593367),
+        Felt::new(5069344854700671784),
+        Felt::new(17704314310866354161),
+        Felt::new(15988800480645163458),
+        Felt::new(5818851986787837003),
+        Felt::new(2578102338873304736),
+    ],
+];
+
+const INTERNAL_ROUND_CONSTANTS: [Felt; NUM_PARTIAL_ROUNDS] = [
+    Felt::new(5395176197344543510),
+    Felt::new(4555032575628627551),
+    Felt::new(10887434669785806501),
+    Felt::new(12415218859476220947),
+    Felt::new(323544930728360053),
+    Felt::new(13821005335130766955),
+    Felt::new(8112988184280322821),
+    Felt::new(16331987863400672412),
+    Felt::new(16093634485870170562),
+    Felt::new(2038954051047328382),
+    Felt::new(17917233579925756683),
+    Felt::new(5480704578777097169),
+    Felt::new(741424706267005090),
+    Felt::new(7872218736019578342),
+    Felt::new(940187017142450256),
+    Felt::new(3242363036477934858),
+    Felt::new(15415646525902701306),
+    Felt::new(10673647621461954174),
+    Felt::new(10043771903993878423),
+    Felt::new(5731056810399963425),
+    Felt::new(12331335364636844807),
+    Felt::new(6376995477333092352),
+];
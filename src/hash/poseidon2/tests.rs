@@ -0,0 +1,353 @@
+use proptest::prelude::*;
+use rand_utils::rand_value;
+
+use super::*;
+use crate::hash::rpo::Rpo256;
+use alloc::{vec, vec::Vec};
+
+/// Samples field elements as the parameter script of the reference implementation does: the
+/// Grain LFSR is initialized with the parameters of the instance, and 64-bit samples which are
+/// not smaller than the modulus are rejected.
+fn grain_field_elements(count: usize) -> Vec<Felt> {
+    // field type (prime field), S-Box type (x^ALPHA), field size, state width, number of full and
+    // partial rounds, followed by 30 ones
+    let params = [(1, 2), (0, 4), (64, 12), (STATE_WIDTH, 12), (NUM_FULL_ROUNDS, 10)];
+    let mut bits: Vec<bool> = params
+        .into_iter()
+        .chain([(NUM_PARTIAL_ROUNDS, 10)])
+        .flat_map(|(value, width)| (0..width).rev().map(move |i| (value >> i) & 1 == 1))
+        .chain([true; 30])
+        .collect();
+    assert_eq!(bits.len(), 80);
+
+    let mut next_bit = || {
+        let bit = bits[62] ^ bits[51] ^ bits[38] ^ bits[23] ^ bits[13] ^ bits[0];
+        bits.remove(0);
+        bits.push(bit);
+        bit
+    };
+    for _ in 0..160 {
+        next_bit();
+    }
+
+    // the output bits are filtered in pairs: the second bit is kept if the first bit is 1
+    let mut next_output_bit = || loop {
+        let (keep, bit) = (next_bit(), next_bit());
+        if keep {
+            return bit;
+        }
+    };
+
+    let mut elements = Vec::with_capacity(count);
+    while elements.len() < count {
+        let sample = (0..64).fold(0_u64, |acc, _| (acc << 1) | next_output_bit() as u64);
+        if sample < Felt::MODULUS {
+            elements.push(Felt::new(sample));
+        }
+    }
+    elements
+}
+
+#[test]
+fn constants() {
+    let elements = grain_field_elements((NUM_FULL_ROUNDS + NUM_PARTIAL_ROUNDS) * STATE_WIDTH);
+    let rounds: Vec<&[Felt]> = elements.chunks_exact(STATE_WIDTH).collect();
+    let (first, rest) = rounds.split_at(NUM_FULL_ROUNDS / 2);
+    let (partial, last) = rest.split_at(NUM_PARTIAL_ROUNDS);
+
+    for (constants, round) in EXTERNAL_ROUND_CONSTANTS.iter().zip(first.iter().chain(last)) {
+        assert_eq!(constants, round);
+    }
+    for (&constant, round) in INTERNAL_ROUND_CONSTANTS.iter().zip(partial) {
+        assert_eq!(constant, round[0]);
+    }
+}
+
+#[test]
+fn linear_layers_match_matrices() {
+    let input: [Felt; STATE_WIDTH] = core::array::from_fn(|_| rand_value());
+
+    // the external linear layer is circ(2 * M4, M4, M4)
+    let mut expected = [ZERO; STATE_WIDTH];
+    for (i, e) in expected.iter_mut().enumerate() {
+        for (j, &x) in input.iter().enumerate() {
+            let m = M4[i % 4][j % 4];
+            *e += if i / 4 == j / 4 { m.double() * x } else { m * x };
+        }
+    }
+    let mut actual = input;
+    apply_external_linear_layer(&mut actual);
+    assert_eq!(actual, expected);
+
+    // the internal linear layer is J + diag(INTERNAL_DIAGONAL)
+    let mut actual = input;
+    apply_internal_linear_layer(&mut actual);
+    assert_eq!(actual, matrix_vector_product(&internal_matrix(), &input));
+}
+
+#[test]
+fn internal_matrix_has_no_invariant_subspaces() {
+    // the minimal polynomials of M_I^i for 1 <= i <= 2 * STATE_WIDTH must be irreducible and of
+    // degree STATE_WIDTH, i.e., the characteristic polynomials of these powers must be irreducible
+    let matrix = internal_matrix();
+    let mut power = matrix.clone();
+    for _ in 0..2 * STATE_WIDTH {
+        assert!(is_irreducible(&characteristic_polynomial(&power)));
+        power = matrix_product(&matrix, &power);
+    }
+}
+
+#[test]
+fn poseidon2_merge() {
+    let digests = [
+        RpoDigest::new(core::array::from_fn(|i| Felt::new(i as u64))),
+        RpoDigest::new(core::array::from_fn(|i| Felt::new(i as u64 + 4))),
+    ];
+
+    // merging truncates the permutation of the two digests, which is the same as hashing the 8
+    // elements of the digests
+    let mut state: [Felt; STATE_WIDTH] = core::array::from_fn(|i| Felt::new(i as u64));
+    state[CAPACITY_RANGE].fill(ZERO);
+    Poseidon2::apply_permutation(&mut state);
+    assert_eq!(
+        Poseidon2::merge(&digests),
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    );
+
+    let elements: Vec<Felt> = RpoDigest::digests_as_elements(digests.iter()).copied().collect();
+    assert_eq!(Poseidon2::merge(&digests), Poseidon2::hash_elements(&elements));
+    assert_ne!(Poseidon2::merge(&digests), Rpo256::merge(&digests));
+
+    // the order of the digests matters
+    assert_ne!(Poseidon2::merge(&digests), Poseidon2::merge(&[digests[1], digests[0]]));
+}
+
+#[test]
+fn poseidon2_merge_with_int() {
+    let seed = Poseidon2::hash(b"seed");
+    let elements: Vec<Felt> = seed.as_elements().iter().copied().chain([Felt::new(42)]).collect();
+    assert_eq!(Poseidon2::merge_with_int(seed, 42), Poseidon2::hash_elements(&elements));
+
+    let value = Felt::MODULUS + 5;
+    let elements: Vec<Felt> =
+        seed.as_elements().iter().copied().chain([Felt::new(value), ONE]).collect();
+    assert_eq!(Poseidon2::merge_with_int(seed, value), Poseidon2::hash_elements(&elements));
+}
+
+#[test]
+fn poseidon2_hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Poseidon2::hash(&[1_u8, 2, 3]);
+    let r2 = Poseidon2::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let elements = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4), Felt::new(5)];
+    let r1 = Poseidon2::hash_elements(&elements);
+    let r2 = Poseidon2::hash_elements(&[elements.as_slice(), &[ZERO]].concat());
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn poseidon2_hash_empty_input() {
+    // empty inputs are padded into a single block, and thus their hashes are not the zero digest
+    let zero = RpoDigest::default();
+    assert_ne!(Poseidon2::hash(&[]), zero);
+    assert_ne!(Poseidon2::hash_elements::<Felt>(&[]), zero);
+
+    // the padded empty input is domain separated from a block which looks like the padding
+    let empty = Poseidon2::hash_elements::<Felt>(&[]);
+    assert_ne!(empty, Poseidon2::hash_elements(&[ZERO; RATE_WIDTH]));
+    let mut padding = [ZERO; RATE_WIDTH];
+    padding[0] = ONE;
+    assert_ne!(empty, Poseidon2::hash_elements(&padding));
+    assert_ne!(Poseidon2::hash(&[]), Poseidon2::hash(&[0]));
+    assert_ne!(Poseidon2::hash(&[]), empty);
+}
+
+#[test]
+fn poseidon2_regression_vectors() {
+    // no reference test vectors are available for this instance of Poseidon2 in this crate; these
+    // vectors were computed by this implementation and guard against accidental changes to the
+    // permutation and the sponge
+    let mut state: [Felt; STATE_WIDTH] = core::array::from_fn(|i| Felt::new(i as u64));
+    Poseidon2::apply_permutation(&mut state);
+    let expected = [
+        9055939365950196236,
+        9146495671805357187,
+        11294722936358926239,
+        6183475495202068751,
+        13956795833109140181,
+        1071210587444983697,
+        6722284916457882859,
+        16903317085068507354,
+        410501482076261030,
+        4545306310947191001,
+        6592395517618663430,
+        6523907342399165115,
+    ];
+    assert_eq!(state, expected.map(Felt::new));
+
+    let elements: Vec<Felt> = (0..8).map(Felt::new).collect();
+    let expected = [
+        12041868112633902895,
+        10614681363924877768,
+        11165560868582500572,
+        14565465112054365391,
+    ];
+    assert_eq!(Poseidon2::hash_elements(&elements), RpoDigest::new(expected.map(Felt::new)));
+
+    let expected = [
+        16687246364708437859,
+        4785063057222904642,
+        10188707984570228712,
+        64784947984626017,
+    ];
+    assert_eq!(Poseidon2::hash(b"abc"), RpoDigest::new(expected.map(Felt::new)));
+}
+
+proptest! {
+    #[test]
+    fn poseidon2_hash_elements_padding(ref values in any::<Vec<u64>>(), extra in any::<u64>()) {
+        let elements: Vec<Felt> = values.iter().map(|&v| Felt::new(v)).collect();
+        let digest = Poseidon2::hash_elements(&elements);
+        prop_assert_ne!(digest, Rpo256::hash_elements(&elements));
+
+        // appending any element, including a zero, changes the hash
+        let extended: Vec<Felt> = elements.iter().copied().chain([Felt::new(extra)]).collect();
+        prop_assert_ne!(Poseidon2::hash_elements(&extended), digest);
+    }
+
+    #[test]
+    fn poseidon2_hash_bytes_padding(ref bytes in any::<Vec<u8>>(), extra in any::<u8>()) {
+        let extended: Vec<u8> = bytes.iter().copied().chain([extra]).collect();
+        prop_assert_ne!(Poseidon2::hash(&extended), Poseidon2::hash(bytes));
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+type Matrix = Vec<Vec<Felt>>;
+
+/// Returns the matrix of the internal linear layer.
+fn internal_matrix() -> Matrix {
+    (0..STATE_WIDTH)
+        .map(|i| {
+            (0..STATE_WIDTH)
+                .map(|j| if i == j { ONE + INTERNAL_DIAGONAL[i] } else { ONE })
+                .collect()
+        })
+        .collect()
+}
+
+fn matrix_vector_product(matrix: &Matrix, vector: &[Felt; STATE_WIDTH]) -> [Felt; STATE_WIDTH] {
+    core::array::from_fn(|i| matrix[i].iter().zip(vector).fold(ZERO, |acc, (&m, &x)| acc + m * x))
+}
+
+fn matrix_product(a: &Matrix, b: &Matrix) -> Matrix {
+    (0..a.len())
+        .map(|i| {
+            (0..b[0].len())
+                .map(|j| (0..b.len()).fold(ZERO, |acc, k| acc + a[i][k] * b[k][j]))
+                .collect()
+        })
+        .collect()
+}
+
+/// Returns the coefficients of the characteristic polynomial of the provided matrix (lowest
+/// degree first), computed with the Faddeev-LeVerrier algorithm.
+fn characteristic_polynomial(matrix: &Matrix) -> Vec<Felt> {
+    let n = matrix.len();
+    let mut coefficients = vec![ZERO; n + 1];
+    coefficients[n] = ONE;
+
+    let mut m = vec![vec![ZERO; n]; n];
+    for k in 1..=n {
+        for (i, row) in m.iter_mut().enumerate() {
+            row[i] += coefficients[n + 1 - k];
+        }
+        m = matrix_product(matrix, &m);
+        let trace = (0..n).fold(ZERO, |acc, i| acc + m[i][i]);
+        coefficients[n - k] = -trace / Felt::new(k as u64);
+    }
+    coefficients
+}
+
+/// Returns true if the provided monic polynomial of degree 12 is irreducible, using Rabin's test:
+/// `f` is irreducible iff `f` divides `x^(p^12) - x`, and `f` is coprime with `x^(p^6) - x` and
+/// `x^(p^4) - x`.
+fn is_irreducible(f: &[Felt]) -> bool {
+    let degree = f.len() - 1;
+    assert_eq!(degree, 12);
+
+    let frobenius = |k: usize| {
+        let mut result = vec![ZERO, ONE];
+        for _ in 0..k {
+            result = poly_pow_mod(&result, Felt::MODULUS, f);
+        }
+        result[1] -= ONE;
+        result
+    };
+
+    poly_trim(poly_rem(&frobenius(degree), f)).is_empty()
+        && [2, 3].iter().all(|q| poly_gcd(f.to_vec(), frobenius(degree / q)).len() == 1)
+}
+
+fn poly_trim(mut a: Vec<Felt>) -> Vec<Felt> {
+    while a.last() == Some(&ZERO) {
+        a.pop();
+    }
+    a
+}
+
+fn poly_rem(a: &[Felt], f: &[Felt]) -> Vec<Felt> {
+    let mut a = poly_trim(a.to_vec());
+    let f = poly_trim(f.to_vec());
+    let lead_inv = f[f.len() - 1].inv();
+    while a.len() >= f.len() {
+        let c = a[a.len() - 1] * lead_inv;
+        let shift = a.len() - f.len();
+        for (i, &fi) in f.iter().enumerate() {
+            a[shift + i] -= c * fi;
+        }
+        a = poly_trim(a);
+    }
+    a
+}
+
+fn poly_mul_mod(a: &[Felt], b: &[Felt], f: &[Felt]) -> Vec<Felt> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut product = vec![ZERO; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            product[i + j] += x * y;
+        }
+    }
+    poly_rem(&product, f)
+}
+
+fn poly_pow_mod(a: &[Felt], mut exp: u64, f: &[Felt]) -> Vec<Felt> {
+    let mut result = vec![ONE];
+    let mut base = poly_rem(a, f);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = poly_mul_mod(&result, &base, f);
+        }
+        base = poly_mul_mod(&base, &base, f);
+        exp >>= 1;
+    }
+    result
+}
+
+fn poly_gcd(a: Vec<Felt>, b: Vec<Felt>) -> Vec<Felt> {
+    let (mut a, mut b) = (poly_trim(a), poly_trim(b));
+    while !b.is_empty() {
+        let r = poly_rem(&a, &b);
+        a = b;
+        b = r;
+    }
+    a
+}
@@ -0,0 +1,101 @@
+use core::fmt;
+
+use super::rpo::Rpo256;
+use crate::{Felt, Word, ONE, ZERO};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+const STATE_WIDTH: usize = Rpo256::STATE_WIDTH;
+const RATE_START: usize = Rpo256::RATE_RANGE.start;
+const RATE_WIDTH: usize = Rpo256::RATE_RANGE.end - Rpo256::RATE_RANGE.start;
+
+// RPO PRF
+// ================================================================================================
+
+/// A pseudorandom function (PRF) over sequences of field elements, based on the RPO permutation.
+///
+/// The PRF is a keyed sponge construction: the key is absorbed into the capacity portion of the
+/// state, the input is absorbed into the rate portion and padded by appending a 1 followed by as
+/// many 0 as necessary to make its length a multiple of the rate, and the output is read from the
+/// digest range of the state. [Rpo256::mac()] is computed via this PRF, and thus for any key and
+/// input, `RpoPrf::new(key).eval(input)` is equal to `Rpo256::mac(key, input)`.
+///
+/// Inputs can also be evaluated incrementally via [RpoPrf::evaluator()], which is useful when the
+/// input is not available as a single slice.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RpoPrf {
+    key: Word,
+}
+
+impl RpoPrf {
+    /// Returns a new PRF instantiated with the provided key.
+    pub fn new(key: Word) -> Self {
+        Self { key }
+    }
+
+    /// Returns the output of the PRF for the provided input.
+    pub fn eval(&self, input: &[Felt]) -> Word {
+        let mut evaluator = self.evaluator();
+        evaluator.update(input);
+        evaluator.finalize()
+    }
+
+    /// Returns an evaluator which computes the output of the PRF for an input supplied in parts.
+    pub fn evaluator(&self) -> RpoPrfEvaluator {
+        let mut state = [ZERO; STATE_WIDTH];
+        state[Rpo256::CAPACITY_RANGE].copy_from_slice(&self.key);
+        RpoPrfEvaluator { state, rate_idx: 0 }
+    }
+}
+
+impl fmt::Debug for RpoPrf {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpoPrf").finish_non_exhaustive()
+    }
+}
+
+// RPO PRF EVALUATOR
+// ================================================================================================
+
+/// Incremental evaluator of an [RpoPrf].
+///
+/// The output does not depend on how the input is split into calls to
+/// [RpoPrfEvaluator::update()], i.e., it is always equal to the output of [RpoPrf::eval()] over
+/// the concatenation of all parts.
+#[derive(Clone)]
+pub struct RpoPrfEvaluator {
+    state: [Felt; STATE_WIDTH],
+    rate_idx: usize,
+}
+
+impl RpoPrfEvaluator {
+    /// Absorbs the provided field elements into the state of the evaluator.
+    pub fn update(&mut self, elements: &[Felt]) {
+        for &element in elements {
+            self.state[RATE_START + self.rate_idx] = element;
+            self.rate_idx += 1;
+            if self.rate_idx == RATE_WIDTH {
+                Rpo256::apply_permutation(&mut self.state);
+                self.rate_idx = 0;
+            }
+        }
+    }
+
+    /// Pads the absorbed input and returns the output of the PRF.
+    pub fn finalize(mut self) -> Word {
+        self.state[RATE_START + self.rate_idx] = ONE;
+        self.state[RATE_START + self.rate_idx + 1..RATE_START + RATE_WIDTH].fill(ZERO);
+        Rpo256::apply_permutation(&mut self.state);
+        self.state[Rpo256::DIGEST_RANGE].try_into().unwrap()
+    }
+}
+
+impl fmt::Debug for RpoPrfEvaluator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpoPrfEvaluator").finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,41 @@
+use proptest::prelude::*;
+use rand_utils::rand_vector;
+
+use super::*;
+use alloc::vec::Vec;
+
+#[test]
+fn prf_matches_mac() {
+    let key: Word = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let prf = RpoPrf::new(key);
+    let data = rand_vector::<Felt>(21);
+    for len in [0, 1, 7, 8, 9, 16, 21] {
+        assert_eq!(prf.eval(&data[..len]), Word::from(Rpo256::mac(key, &data[..len])));
+    }
+}
+
+#[test]
+fn prf_keyed_output() {
+    let input = rand_vector::<Felt>(10);
+    let a = RpoPrf::new([ONE, ZERO, ZERO, ZERO]).eval(&input);
+    let b = RpoPrf::new([ZERO, ONE, ZERO, ZERO]).eval(&input);
+    assert_ne!(a, b);
+}
+
+proptest! {
+    #[test]
+    fn prf_incremental_evaluation(
+        ref values in prop::collection::vec(any::<u64>(), 0..40),
+        split in 0..40_usize,
+    ) {
+        let input: Vec<Felt> = values.iter().map(|&v| Felt::new(v)).collect();
+        let split = split.min(input.len());
+        let prf = RpoPrf::new([Felt::new(7), ONE, ZERO, Felt::new(11)]);
+
+        let mut evaluator = prf.evaluator();
+        evaluator.update(&input[..split]);
+        evaluator.update(&[]);
+        evaluator.update(&input[split..]);
+        prop_assert_eq!(evaluator.finalize(), prf.eval(&input));
+    }
+}
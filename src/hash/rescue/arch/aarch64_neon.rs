@@ -0,0 +1,358 @@
+use core::arch::aarch64::*;
+
+use crate::{
+    hash::rescue::{add_constants, BATCH_SIZE, STATE_WIDTH},
+    Felt,
+};
+
+// The following NEON implementation follows the structure of the SIMD128 implementation. NEON
+// vectors hold two 64-bit lanes, and thus the state is held in six vectors. NEON has no 64-bit
+// lane multiplication, and thus 128-bit products are assembled from widening 32-bit
+// multiplications. Unlike SIMD128, NEON has unsigned 64-bit lane comparisons, which are used for
+// computing the carries and borrows of 64-bit additions and subtractions.
+//
+// As in the other vectorized implementations, the arithmetic operates on the internal
+// (Montgomery) form of the field elements as if it were the canonical form. This is correct for
+// the S-Box and its inverse because 2^192 = 1 modulo p, and thus for the Montgomery factor
+// R = 2^64 we have (aR)^e = a^e * R whenever e = 1 modulo 3, which holds for both 7 and its
+// inverse.
+
+/// 2^32 - 1, i.e., 2^64 modulo p.
+const EPSILON: u64 = 0xffffffff;
+
+/// Field elements held in vectors of two 64-bit lanes.
+type Vectors<const N: usize> = [uint64x2_t; N];
+
+// RUNTIME DISPATCH
+// ================================================================================================
+
+/// Returns true if the CPU supports the instructions required by this implementation.
+///
+/// NEON is part of the baseline of all common aarch64 targets (e.g., Linux, macOS and Windows),
+/// in which case the check is resolved at compile time. On other targets, support for NEON is
+/// detected at runtime when the `std` feature is enabled, and assumed to be missing otherwise.
+#[inline(always)]
+fn is_available() -> bool {
+    #[cfg(target_feature = "neon")]
+    {
+        true
+    }
+
+    #[cfg(all(not(target_feature = "neon"), feature = "std"))]
+    {
+        std::arch::is_aarch64_feature_detected!("neon")
+    }
+
+    #[cfg(all(not(target_feature = "neon"), not(feature = "std")))]
+    false
+}
+
+// S-BOX
+// ================================================================================================
+
+/// Adds the round constants to the state and applies the S-Box using NEON instructions if the
+/// CPU supports them; otherwise, leaves the state unchanged and returns false.
+#[inline(always)]
+pub fn add_constants_and_apply_sbox(
+    state: &mut [Felt; STATE_WIDTH],
+    ark: &[Felt; STATE_WIDTH],
+) -> bool {
+    if !is_available() {
+        return false;
+    }
+    add_constants(state, ark);
+    unsafe { apply_sbox::<{ STATE_WIDTH / 2 }>(state) };
+    true
+}
+
+/// Adds the round constants to the state and applies the inverse S-Box using NEON instructions
+/// if the CPU supports them; otherwise, leaves the state unchanged and returns false.
+#[inline(always)]
+pub fn add_constants_and_apply_inv_sbox(
+    state: &mut [Felt; STATE_WIDTH],
+    ark: &[Felt; STATE_WIDTH],
+) -> bool {
+    if !is_available() {
+        return false;
+    }
+    add_constants(state, ark);
+    unsafe { apply_inv_sbox::<{ STATE_WIDTH / 2 }>(state) };
+    true
+}
+
+/// Applies the S-Box to a batch of states in a structure-of-arrays layout using NEON
+/// instructions if the CPU supports them; otherwise, leaves the states unchanged and returns
+/// false.
+#[inline(always)]
+pub fn apply_sbox_batch(state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+    if !is_available() {
+        return false;
+    }
+    for row in state.iter_mut() {
+        unsafe { apply_sbox::<{ BATCH_SIZE / 2 }>(row) };
+    }
+    true
+}
+
+/// Applies the inverse S-Box to a batch of states in a structure-of-arrays layout using NEON
+/// instructions if the CPU supports them; otherwise, leaves the states unchanged and returns
+/// false.
+#[inline(always)]
+pub fn apply_inv_sbox_batch(state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+    if !is_available() {
+        return false;
+    }
+    for row in state.iter_mut() {
+        unsafe { apply_inv_sbox::<{ BATCH_SIZE / 2 }>(row) };
+    }
+    true
+}
+
+/// Applies the S-Box to `2 * N` field elements held in `N` vectors.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn apply_sbox<const N: usize>(elements: &mut [Felt]) {
+    store(elements, do_apply_sbox(load::<N>(elements)));
+}
+
+/// Applies the inverse S-Box to `2 * N` field elements held in `N` vectors.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn apply_inv_sbox<const N: usize>(elements: &mut [Felt]) {
+    store(elements, do_apply_inv_sbox(load::<N>(elements)));
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn do_apply_sbox<const N: usize>(state: Vectors<N>) -> Vectors<N> {
+    let state2 = square_reduce(state);
+    let state4 = square_reduce(state2);
+    let state3 = mul_reduce(state2, state);
+    mul_reduce(state3, state4)
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn do_apply_inv_sbox<const N: usize>(state: Vectors<N>) -> Vectors<N> {
+    // compute base^10540996611094048183 using 72 multiplications per array element
+    // 10540996611094048183 = b1001001001001001001001001001000110110110110110110110110110110111
+
+    // compute base^10
+    let t1 = square_reduce(state);
+
+    // compute base^100
+    let t2 = square_reduce(t1);
+
+    // compute base^100100
+    let t3 = exp_acc(t2, t2, 3);
+
+    // compute base^100100100100
+    let t4 = exp_acc(t3, t3, 6);
+
+    // compute base^100100100100100100100100
+    let t5 = exp_acc(t4, t4, 12);
+
+    // compute base^100100100100100100100100100100
+    let t6 = exp_acc(t5, t3, 6);
+
+    // compute base^1001001001001001001001001001000100100100100100100100100100100
+    let t7 = exp_acc(t6, t6, 31);
+
+    // compute base^1001001001001001001001001001000110110110110110110110110110110111
+    let a = square_reduce(square_reduce(mul_reduce(square_reduce(t7), t6)));
+    let b = mul_reduce(t1, mul_reduce(t2, state));
+    mul_reduce(a, b)
+}
+
+// FIELD ARITHMETIC
+// ================================================================================================
+
+/// Computes the full 128-bit products of the corresponding lanes of the two vectors.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn mul128(x: uint64x2_t, y: uint64x2_t) -> (uint64x2_t, uint64x2_t) {
+    let (x_lo, x_hi) = (vmovn_u64(x), vshrn_n_u64::<32>(x));
+    let (y_lo, y_hi) = (vmovn_u64(y), vshrn_n_u64::<32>(y));
+
+    // all four pairwise products of the 32-bit halves
+    let mul_ll = vmull_u32(x_lo, y_lo);
+    let mul_lh = vmull_u32(x_lo, y_hi);
+    let mul_hl = vmull_u32(x_hi, y_lo);
+    let mul_hh = vmull_u32(x_hi, y_hi);
+    add_partial_products(mul_ll, mul_lh, mul_hl, mul_hh)
+}
+
+/// Computes the full 128-bit squares of the lanes of the vector.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn square128(x: uint64x2_t) -> (uint64x2_t, uint64x2_t) {
+    // as in mul128(), but the two cross products are equal, and thus only one is computed
+    let (x_lo, x_hi) = (vmovn_u64(x), vshrn_n_u64::<32>(x));
+    let mul_ll = vmull_u32(x_lo, x_lo);
+    let mul_lh = vmull_u32(x_lo, x_hi);
+    let mul_hh = vmull_u32(x_hi, x_hi);
+    add_partial_products(mul_ll, mul_lh, mul_lh, mul_hh)
+}
+
+/// Assembles the 128-bit products from the pairwise products of the 32-bit halves.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn add_partial_products(
+    mul_ll: uint64x2_t,
+    mul_lh: uint64x2_t,
+    mul_hl: uint64x2_t,
+    mul_hh: uint64x2_t,
+) -> (uint64x2_t, uint64x2_t) {
+    let epsilon = vdupq_n_u64(EPSILON);
+
+    // bignum addition; none of the additions below can overflow
+    let t0 = vaddq_u64(mul_hl, vshrq_n_u64::<32>(mul_ll));
+    let t1 = vaddq_u64(mul_lh, vandq_u64(t0, epsilon));
+    let hi = vaddq_u64(vaddq_u64(mul_hh, vshrq_n_u64::<32>(t0)), vshrq_n_u64::<32>(t1));
+    let lo = vorrq_u64(vandq_u64(mul_ll, epsilon), vshlq_n_u64::<32>(t1));
+    (lo, hi)
+}
+
+/// Reduces the 128-bit values given by their low and high halves modulo p.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn reduce128((lo, hi): (uint64x2_t, uint64x2_t)) -> uint64x2_t {
+    let epsilon = vdupq_n_u64(EPSILON);
+
+    // subtract the top 32 bits, accounting for 2^64 = 2^32 - 1 modulo p on underflow
+    let hi_hi = vshrq_n_u64::<32>(hi);
+    let borrow = vcltq_u64(lo, hi_hi);
+    let t0 = vsubq_u64(vsubq_u64(lo, hi_hi), vandq_u64(borrow, epsilon));
+
+    // add the middle 32 bits multiplied by 2^32 - 1, accounting for 2^64 = 2^32 - 1 modulo p on
+    // overflow
+    let t1 = vmull_u32(vmovn_u64(hi), vdup_n_u32(EPSILON as u32));
+    let t2 = vaddq_u64(t0, t1);
+    let carry = vcltq_u64(t2, t1);
+    vaddq_u64(t2, vandq_u64(carry, epsilon))
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn mul_reduce<const N: usize>(a: Vectors<N>, b: Vectors<N>) -> Vectors<N> {
+    let mut result = a;
+    for (r, (&x, &y)) in result.iter_mut().zip(a.iter().zip(b.iter())) {
+        *r = reduce128(mul128(x, y));
+    }
+    result
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn square_reduce<const N: usize>(a: Vectors<N>) -> Vectors<N> {
+    let mut result = a;
+    for (r, &x) in result.iter_mut().zip(a.iter()) {
+        *r = reduce128(square128(x));
+    }
+    result
+}
+
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn exp_acc<const N: usize>(high: Vectors<N>, low: Vectors<N>, exp: usize) -> Vectors<N> {
+    let mut result = high;
+    for _ in 0..exp {
+        result = square_reduce(result);
+    }
+    mul_reduce(result, low)
+}
+
+// LOADING AND STORING
+// ================================================================================================
+
+/// Loads `2 * N` field elements into `N` vectors.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn load<const N: usize>(elements: &[Felt]) -> Vectors<N> {
+    debug_assert_eq!(elements.len(), 2 * N);
+    let mut vectors = [vdupq_n_u64(0); N];
+    for (v, pair) in vectors.iter_mut().zip(elements.chunks_exact(2)) {
+        *v = vld1q_u64([pair[0].inner(), pair[1].inner()].as_ptr());
+    }
+    vectors
+}
+
+/// Stores `N` vectors into `2 * N` field elements.
+#[inline]
+#[target_feature(enable = "neon")]
+unsafe fn store<const N: usize>(elements: &mut [Felt], vectors: Vectors<N>) {
+    debug_assert_eq!(elements.len(), 2 * N);
+    for (v, pair) in vectors.iter().zip(elements.chunks_exact_mut(2)) {
+        pair[0] = Felt::from_mont(vgetq_lane_u64::<0>(*v));
+        pair[1] = Felt::from_mont(vgetq_lane_u64::<1>(*v));
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use rand_utils::rand_array;
+
+    use super::{
+        add_constants_and_apply_inv_sbox, add_constants_and_apply_sbox, apply_inv_sbox_batch,
+        apply_sbox_batch, is_available,
+    };
+    use crate::{
+        hash::rescue::{add_constants, apply_inv_sbox, apply_sbox, ARK1, BATCH_SIZE, STATE_WIDTH},
+        Felt,
+    };
+
+    #[test]
+    fn test_neon_sbox() {
+        if !is_available() {
+            return;
+        }
+
+        for _ in 0..100 {
+            let state: [Felt; STATE_WIDTH] = rand_array();
+            let ark = ARK1[0];
+
+            let mut expected = state;
+            add_constants(&mut expected, &ark);
+            apply_sbox(&mut expected);
+            let mut actual = state;
+            assert!(add_constants_and_apply_sbox(&mut actual, &ark));
+            assert_eq!(expected, actual);
+
+            let mut expected = state;
+            add_constants(&mut expected, &ark);
+            apply_inv_sbox(&mut expected);
+            let mut actual = state;
+            assert!(add_constants_and_apply_inv_sbox(&mut actual, &ark));
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn test_neon_sbox_batch() {
+        if !is_available() {
+            return;
+        }
+
+        let mut state = [[Felt::new(0); BATCH_SIZE]; STATE_WIDTH];
+        for row in state.iter_mut() {
+            *row = rand_array();
+        }
+
+        let mut expected = state;
+        for row in expected.iter_mut() {
+            for element in row.iter_mut() {
+                *element = element.exp7();
+            }
+        }
+        let mut actual = state;
+        assert!(apply_sbox_batch(&mut actual));
+        assert_eq!(expected, actual);
+
+        let mut actual = expected;
+        assert!(apply_inv_sbox_batch(&mut actual));
+        assert_eq!(state, actual);
+    }
+}
@@ -1,6 +1,9 @@
 #[cfg(target_feature = "sve")]
 pub mod optimized {
-    use crate::{hash::rescue::STATE_WIDTH, Felt};
+    use crate::{
+        hash::rescue::{BATCH_SIZE, STATE_WIDTH},
+        Felt,
+    };
 
     mod ffi {
         #[link(name = "rpo_sve", kind = "static")]
@@ -41,16 +44,38 @@ pub mod optimized {
             )
         }
     }
+
+    #[inline(always)]
+    pub fn apply_sbox_batch(_state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    pub fn apply_inv_sbox_batch(_state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+        false
+    }
 }
 
-#[cfg(target_feature = "avx2")]
+#[cfg(target_arch = "x86_64")]
 mod x86_64_avx2;
 
-#[cfg(target_feature = "avx2")]
+// AVX-512 intrinsics are stable since Rust 1.89, and thus the `avx512` feature requires it.
+#[cfg(all(feature = "avx512", target_arch = "x86_64"))]
+#[clippy::msrv = "1.89"]
+mod x86_64_avx512;
+
+#[cfg(all(target_arch = "aarch64", not(target_feature = "sve")))]
+mod aarch64_neon;
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod wasm32_simd128;
+
+/// On x86_64, support for AVX-512 (when the `avx512` feature is enabled) and AVX2 is detected at
+/// runtime, with AVX-512 taking precedence.
+#[cfg(target_arch = "x86_64")]
 pub mod optimized {
-    use super::x86_64_avx2::{apply_inv_sbox, apply_sbox};
     use crate::{
-        hash::rescue::{add_constants, STATE_WIDTH},
+        hash::rescue::{BATCH_SIZE, STATE_WIDTH},
         Felt,
     };
 
@@ -59,11 +84,11 @@ pub mod optimized {
         state: &mut [Felt; STATE_WIDTH],
         ark: &[Felt; STATE_WIDTH],
     ) -> bool {
-        add_constants(state, ark);
-        unsafe {
-            apply_sbox(std::mem::transmute(state));
+        #[cfg(feature = "avx512")]
+        if super::x86_64_avx512::add_constants_and_apply_sbox(state, ark) {
+            return true;
         }
-        true
+        super::x86_64_avx2::add_constants_and_apply_sbox(state, ark)
     }
 
     #[inline(always)]
@@ -71,17 +96,63 @@ pub mod optimized {
         state: &mut [Felt; STATE_WIDTH],
         ark: &[Felt; STATE_WIDTH],
     ) -> bool {
-        add_constants(state, ark);
-        unsafe {
-            apply_inv_sbox(std::mem::transmute(state));
+        #[cfg(feature = "avx512")]
+        if super::x86_64_avx512::add_constants_and_apply_inv_sbox(state, ark) {
+            return true;
         }
-        true
+        super::x86_64_avx2::add_constants_and_apply_inv_sbox(state, ark)
     }
+
+    #[inline(always)]
+    pub fn apply_sbox_batch(_state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+        #[cfg(feature = "avx512")]
+        if super::x86_64_avx512::apply_sbox_batch(_state) {
+            return true;
+        }
+        false
+    }
+
+    #[inline(always)]
+    pub fn apply_inv_sbox_batch(_state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+        #[cfg(feature = "avx512")]
+        if super::x86_64_avx512::apply_inv_sbox_batch(_state) {
+            return true;
+        }
+        false
+    }
+}
+
+/// WebAssembly has no runtime feature detection, and thus SIMD128 is used whenever the code is
+/// compiled with the `simd128` target feature enabled.
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub mod optimized {
+    pub use super::wasm32_simd128::{
+        add_constants_and_apply_inv_sbox, add_constants_and_apply_sbox, apply_inv_sbox_batch,
+        apply_sbox_batch,
+    };
+}
+
+/// On aarch64 without SVE, support for NEON is determined at compile time when it is part of the
+/// target's baseline, and detected at runtime otherwise (when the `std` feature is enabled).
+#[cfg(all(target_arch = "aarch64", not(target_feature = "sve")))]
+pub mod optimized {
+    pub use super::aarch64_neon::{
+        add_constants_and_apply_inv_sbox, add_constants_and_apply_sbox, apply_inv_sbox_batch,
+        apply_sbox_batch,
+    };
 }
 
-#[cfg(not(any(target_feature = "avx2", target_feature = "sve")))]
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_feature = "sve",
+    all(target_arch = "wasm32", target_feature = "simd128")
+)))]
 pub mod optimized {
-    use crate::{hash::rescue::STATE_WIDTH, Felt};
+    use crate::{
+        hash::rescue::{BATCH_SIZE, STATE_WIDTH},
+        Felt,
+    };
 
     #[inline(always)]
     pub fn add_constants_and_apply_sbox(
@@ -98,4 +169,14 @@ pub mod optimized {
     ) -> bool {
         false
     }
+
+    #[inline(always)]
+    pub fn apply_sbox_batch(_state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    pub fn apply_inv_sbox_batch(_state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+        false
+    }
 }
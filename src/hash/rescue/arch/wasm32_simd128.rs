@@ -0,0 +1,310 @@
+use core::arch::wasm32::*;
+
+use crate::{
+    hash::rescue::{add_constants, BATCH_SIZE, STATE_WIDTH},
+    Felt,
+};
+
+// The following SIMD128 implementation follows the structure of the AVX-512 implementation.
+// SIMD128 vectors hold two 64-bit lanes, and thus the state is held in six vectors. SIMD128 has no
+// widening 64-bit lane multiplication, and thus 128-bit products are assembled from 32-bit
+// multiplications. The carries and borrows of 64-bit additions and subtractions are computed
+// from the top bits of the operands and the result, which is faster than the (signed-only) 64-bit
+// lane comparisons.
+//
+// As in the other vectorized implementations, the arithmetic operates on the internal
+// (Montgomery) form of the field elements as if it were the canonical form. This is correct for
+// the S-Box and its inverse because 2^192 = 1 modulo p, and thus for the Montgomery factor
+// R = 2^64 we have (aR)^e = a^e * R whenever e = 1 modulo 3, which holds for both 7 and its
+// inverse.
+//
+// WebAssembly has no runtime feature detection, and thus this implementation is used whenever
+// the code is compiled with the `simd128` target feature enabled.
+
+/// 2^32 - 1, i.e., 2^64 modulo p.
+const EPSILON: u64 = 0xffffffff;
+
+/// Field elements held in vectors of two 64-bit lanes.
+type Vectors<const N: usize> = [v128; N];
+
+// S-BOX
+// ================================================================================================
+
+/// Adds the round constants to the state and applies the S-Box using SIMD128 instructions.
+#[inline(always)]
+pub fn add_constants_and_apply_sbox(
+    state: &mut [Felt; STATE_WIDTH],
+    ark: &[Felt; STATE_WIDTH],
+) -> bool {
+    add_constants(state, ark);
+    apply_sbox::<{ STATE_WIDTH / 2 }>(state);
+    true
+}
+
+/// Adds the round constants to the state and applies the inverse S-Box using SIMD128
+/// instructions.
+#[inline(always)]
+pub fn add_constants_and_apply_inv_sbox(
+    state: &mut [Felt; STATE_WIDTH],
+    ark: &[Felt; STATE_WIDTH],
+) -> bool {
+    add_constants(state, ark);
+    apply_inv_sbox::<{ STATE_WIDTH / 2 }>(state);
+    true
+}
+
+/// Applies the S-Box to a batch of states in a structure-of-arrays layout using SIMD128
+/// instructions.
+#[inline(always)]
+pub fn apply_sbox_batch(state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+    for row in state.iter_mut() {
+        apply_sbox::<{ BATCH_SIZE / 2 }>(row);
+    }
+    true
+}
+
+/// Applies the inverse S-Box to a batch of states in a structure-of-arrays layout using SIMD128
+/// instructions.
+#[inline(always)]
+pub fn apply_inv_sbox_batch(state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+    for row in state.iter_mut() {
+        apply_inv_sbox::<{ BATCH_SIZE / 2 }>(row);
+    }
+    true
+}
+
+/// Applies the S-Box to `2 * N` field elements held in `N` vectors.
+#[inline(always)]
+fn apply_sbox<const N: usize>(elements: &mut [Felt]) {
+    store(elements, do_apply_sbox(load::<N>(elements)));
+}
+
+/// Applies the inverse S-Box to `2 * N` field elements held in `N` vectors.
+#[inline(always)]
+fn apply_inv_sbox<const N: usize>(elements: &mut [Felt]) {
+    store(elements, do_apply_inv_sbox(load::<N>(elements)));
+}
+
+#[inline(always)]
+fn do_apply_sbox<const N: usize>(state: Vectors<N>) -> Vectors<N> {
+    let state2 = square_reduce(state);
+    let state4 = square_reduce(state2);
+    let state3 = mul_reduce(state2, state);
+    mul_reduce(state3, state4)
+}
+
+#[inline(always)]
+fn do_apply_inv_sbox<const N: usize>(state: Vectors<N>) -> Vectors<N> {
+    // compute base^10540996611094048183 using 72 multiplications per array element
+    // 10540996611094048183 = b1001001001001001001001001001000110110110110110110110110110110111
+
+    // compute base^10
+    let t1 = square_reduce(state);
+
+    // compute base^100
+    let t2 = square_reduce(t1);
+
+    // compute base^100100
+    let t3 = exp_acc(t2, t2, 3);
+
+    // compute base^100100100100
+    let t4 = exp_acc(t3, t3, 6);
+
+    // compute base^100100100100100100100100
+    let t5 = exp_acc(t4, t4, 12);
+
+    // compute base^100100100100100100100100100100
+    let t6 = exp_acc(t5, t3, 6);
+
+    // compute base^1001001001001001001001001001000100100100100100100100100100100
+    let t7 = exp_acc(t6, t6, 31);
+
+    // compute base^1001001001001001001001001001000110110110110110110110110110110111
+    let a = square_reduce(square_reduce(mul_reduce(square_reduce(t7), t6)));
+    let b = mul_reduce(t1, mul_reduce(t2, state));
+    mul_reduce(a, b)
+}
+
+// FIELD ARITHMETIC
+// ================================================================================================
+
+/// Computes the full 128-bit products of the corresponding lanes of the two vectors.
+#[inline(always)]
+fn mul128(x: v128, y: v128) -> (v128, v128) {
+    // arrange the 32-bit halves of the lanes of `x` as [lo, lo, hi, hi], and the halves of the
+    // lanes of `y` both in the same order and swapped, so that the four pairwise products of the
+    // halves are computed by widening multiplications of the low and the high 32-bit lanes
+    let x = i32x4_shuffle::<0, 2, 1, 3>(x, x);
+    let y_lo_hi = i32x4_shuffle::<0, 2, 1, 3>(y, y);
+    let y_hi_lo = i32x4_shuffle::<1, 3, 0, 2>(y, y);
+    let mul_ll = u64x2_extmul_low_u32x4(x, y_lo_hi);
+    let mul_hh = u64x2_extmul_high_u32x4(x, y_lo_hi);
+    let mul_lh = u64x2_extmul_low_u32x4(x, y_hi_lo);
+    let mul_hl = u64x2_extmul_high_u32x4(x, y_hi_lo);
+    add_partial_products(mul_ll, mul_lh, mul_hl, mul_hh)
+}
+
+/// Computes the full 128-bit squares of the lanes of the vector.
+#[inline(always)]
+fn square128(x: v128) -> (v128, v128) {
+    // as in mul128(), but the two cross products are equal, and thus only one is computed
+    let x_lo_hi = i32x4_shuffle::<0, 2, 1, 3>(x, x);
+    let x_hi_lo = i32x4_shuffle::<1, 3, 0, 2>(x, x);
+    let mul_ll = u64x2_extmul_low_u32x4(x_lo_hi, x_lo_hi);
+    let mul_hh = u64x2_extmul_high_u32x4(x_lo_hi, x_lo_hi);
+    let mul_lh = u64x2_extmul_low_u32x4(x_lo_hi, x_hi_lo);
+    add_partial_products(mul_ll, mul_lh, mul_lh, mul_hh)
+}
+
+/// Assembles the 128-bit products from the pairwise products of the 32-bit halves.
+#[inline(always)]
+fn add_partial_products(mul_ll: v128, mul_lh: v128, mul_hl: v128, mul_hh: v128) -> (v128, v128) {
+    let epsilon = u64x2_splat(EPSILON);
+
+    // bignum addition; none of the additions below can overflow
+    let t0 = u64x2_add(mul_hl, u64x2_shr(mul_ll, 32));
+    let t1 = u64x2_add(mul_lh, v128_and(t0, epsilon));
+    let hi = u64x2_add(u64x2_add(mul_hh, u64x2_shr(t0, 32)), u64x2_shr(t1, 32));
+    let lo = v128_or(v128_and(mul_ll, epsilon), u64x2_shl(t1, 32));
+    (lo, hi)
+}
+
+/// Reduces the 128-bit values given by their low and high halves modulo p.
+#[inline(always)]
+fn reduce128((lo, hi): (v128, v128)) -> v128 {
+    let epsilon = u64x2_splat(EPSILON);
+
+    // subtract the top 32 bits, accounting for 2^64 = 2^32 - 1 modulo p on underflow
+    let hi_hi = u64x2_shr(hi, 32);
+    let t0 = u64x2_sub(lo, hi_hi);
+    // the top bit of `borrow` is set iff `lo < hi_hi`
+    let borrow = v128_or(v128_andnot(hi_hi, lo), v128_andnot(t0, v128_xor(lo, hi_hi)));
+    let t0 = u64x2_sub(t0, times_epsilon(u64x2_shr(borrow, 63)));
+
+    // add the middle 32 bits multiplied by 2^32 - 1, accounting for 2^64 = 2^32 - 1 modulo p on
+    // overflow
+    let hi_lo = v128_and(hi, epsilon);
+    let t1 = times_epsilon(hi_lo);
+    let t2 = u64x2_add(t0, t1);
+    // the top bit of `carry` is set iff `t0 + t1` overflows
+    let carry = v128_or(v128_and(t0, t1), v128_andnot(v128_or(t0, t1), t2));
+    u64x2_add(t2, times_epsilon(u64x2_shr(carry, 63)))
+}
+
+/// Multiplies the lanes of the vector, which must be smaller than 2^32, by 2^32 - 1.
+#[inline(always)]
+fn times_epsilon(x: v128) -> v128 {
+    u64x2_sub(u64x2_shl(x, 32), x)
+}
+
+#[inline(always)]
+fn mul_reduce<const N: usize>(a: Vectors<N>, b: Vectors<N>) -> Vectors<N> {
+    let mut result = a;
+    for (r, (&x, &y)) in result.iter_mut().zip(a.iter().zip(b.iter())) {
+        *r = reduce128(mul128(x, y));
+    }
+    result
+}
+
+#[inline(always)]
+fn square_reduce<const N: usize>(a: Vectors<N>) -> Vectors<N> {
+    let mut result = a;
+    for (r, &x) in result.iter_mut().zip(a.iter()) {
+        *r = reduce128(square128(x));
+    }
+    result
+}
+
+#[inline(always)]
+fn exp_acc<const N: usize>(high: Vectors<N>, low: Vectors<N>, exp: usize) -> Vectors<N> {
+    let mut result = high;
+    for _ in 0..exp {
+        result = square_reduce(result);
+    }
+    mul_reduce(result, low)
+}
+
+// LOADING AND STORING
+// ================================================================================================
+
+/// Loads `2 * N` field elements into `N` vectors.
+#[inline(always)]
+fn load<const N: usize>(elements: &[Felt]) -> Vectors<N> {
+    debug_assert_eq!(elements.len(), 2 * N);
+    let mut vectors = [u64x2_splat(0); N];
+    for (v, pair) in vectors.iter_mut().zip(elements.chunks_exact(2)) {
+        *v = u64x2(pair[0].inner(), pair[1].inner());
+    }
+    vectors
+}
+
+/// Stores `N` vectors into `2 * N` field elements.
+#[inline(always)]
+fn store<const N: usize>(elements: &mut [Felt], vectors: Vectors<N>) {
+    debug_assert_eq!(elements.len(), 2 * N);
+    for (v, pair) in vectors.iter().zip(elements.chunks_exact_mut(2)) {
+        pair[0] = Felt::from_mont(u64x2_extract_lane::<0>(*v));
+        pair[1] = Felt::from_mont(u64x2_extract_lane::<1>(*v));
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use rand_utils::rand_array;
+
+    use super::{
+        add_constants_and_apply_inv_sbox, add_constants_and_apply_sbox, apply_inv_sbox_batch,
+        apply_sbox_batch,
+    };
+    use crate::{
+        hash::rescue::{add_constants, apply_inv_sbox, apply_sbox, ARK1, BATCH_SIZE, STATE_WIDTH},
+        Felt,
+    };
+
+    #[test]
+    fn test_simd128_sbox() {
+        for _ in 0..100 {
+            let state: [Felt; STATE_WIDTH] = rand_array();
+            let ark = ARK1[0];
+
+            let mut expected = state;
+            add_constants(&mut expected, &ark);
+            apply_sbox(&mut expected);
+            let mut actual = state;
+            assert!(add_constants_and_apply_sbox(&mut actual, &ark));
+            assert_eq!(expected, actual);
+
+            let mut expected = state;
+            add_constants(&mut expected, &ark);
+            apply_inv_sbox(&mut expected);
+            let mut actual = state;
+            assert!(add_constants_and_apply_inv_sbox(&mut actual, &ark));
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn test_simd128_sbox_batch() {
+        let mut state = [[Felt::new(0); BATCH_SIZE]; STATE_WIDTH];
+        for row in state.iter_mut() {
+            *row = rand_array();
+        }
+
+        let mut expected = state;
+        for row in expected.iter_mut() {
+            for element in row.iter_mut() {
+                *element = element.exp7();
+            }
+        }
+        let mut actual = state;
+        assert!(apply_sbox_batch(&mut actual));
+        assert_eq!(expected, actual);
+
+        let mut actual = expected;
+        assert!(apply_inv_sbox_batch(&mut actual));
+        assert_eq!(state, actual);
+    }
+}
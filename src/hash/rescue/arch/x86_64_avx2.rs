@@ -1,5 +1,10 @@
 use core::arch::x86_64::*;
 
+use crate::{
+    hash::rescue::{add_constants, STATE_WIDTH},
+    Felt,
+};
+
 // The following AVX2 implementation has been copied from plonky2:
 // https://github.com/0xPolygonZero/plonky2/blob/main/plonky2/src/hash/arch/x86_64/poseidon_goldilocks_avx2_bmi2.rs
 
@@ -41,8 +46,54 @@ use core::arch::x86_64::*;
 //    Notice that the above 3-value addition still only requires two calls to shift, just like our
 //    2-value addition.
 
+// RUNTIME DISPATCH
+// ================================================================================================
+
+// Support for AVX2 is detected when the functions below are first called; if the code is compiled
+// with the `avx2` target feature enabled, the detection is resolved at compile time.
+cpufeatures::new!(avx2_cpuid, "avx2");
+
+/// Adds the round constants to the state and applies the S-Box using AVX2 instructions if the CPU
+/// supports them; otherwise, leaves the state unchanged and returns false.
 #[inline(always)]
-pub fn branch_hint() {
+pub fn add_constants_and_apply_sbox(
+    state: &mut [Felt; STATE_WIDTH],
+    ark: &[Felt; STATE_WIDTH],
+) -> bool {
+    if !avx2_cpuid::get() {
+        return false;
+    }
+    add_constants(state, ark);
+    unsafe {
+        apply_sbox(core::mem::transmute::<&mut [Felt; STATE_WIDTH], &mut [u64; STATE_WIDTH]>(state))
+    };
+    true
+}
+
+/// Adds the round constants to the state and applies the inverse S-Box using AVX2 instructions if
+/// the CPU supports them; otherwise, leaves the state unchanged and returns false.
+#[inline(always)]
+pub fn add_constants_and_apply_inv_sbox(
+    state: &mut [Felt; STATE_WIDTH],
+    ark: &[Felt; STATE_WIDTH],
+) -> bool {
+    if !avx2_cpuid::get() {
+        return false;
+    }
+    add_constants(state, ark);
+    unsafe {
+        apply_inv_sbox(core::mem::transmute::<&mut [Felt; STATE_WIDTH], &mut [u64; STATE_WIDTH]>(
+            state,
+        ))
+    };
+    true
+}
+
+// FIELD ARITHMETIC
+// ================================================================================================
+
+#[inline(always)]
+fn branch_hint() {
     // NOTE: These are the currently supported assembly architectures. See the
     // [nightly reference](https://doc.rust-lang.org/nightly/reference/inline-assembly.html) for
     // the most up-to-date list.
@@ -174,8 +225,7 @@ unsafe fn add_small(
     let res_wrapped_s = map3!(_mm256_add_epi64, x_s, y);
     let mask = map3!(_mm256_cmpgt_epi32, x_s, res_wrapped_s);
     let wrapback_amt = map3!(_mm256_srli_epi64::<32>, mask); // EPSILON if overflowed else 0.
-    let res_s = map3!(_mm256_add_epi64, res_wrapped_s, wrapback_amt);
-    res_s
+    map3!(_mm256_add_epi64, res_wrapped_s, wrapback_amt)
 }
 
 #[inline(always)]
@@ -205,8 +255,7 @@ unsafe fn sub_tiny(
 ) -> (__m256i, __m256i, __m256i) {
     let res_wrapped_s = map3!(_mm256_sub_epi64, x_s, y);
     let mask = map3!(_mm256_cmpgt_epi32, res_wrapped_s, x_s);
-    let res_s = map3!(maybe_adj_sub, res_wrapped_s, mask);
-    res_s
+    map3!(maybe_adj_sub, res_wrapped_s, mask)
 }
 
 #[inline(always)]
@@ -220,8 +269,7 @@ unsafe fn reduce3(
     let lo1_s = sub_tiny(lo0_s, hi_hi0);
     let t1 = map3!(_mm256_mul_epu32, hi0, rep epsilon);
     let lo2_s = add_small(lo1_s, t1);
-    let lo2 = map3!(_mm256_xor_si256, lo2_s, rep sign_bit);
-    lo2
+    map3!(_mm256_xor_si256, lo2_s, rep sign_bit)
 }
 
 #[inline(always)]
@@ -258,8 +306,7 @@ unsafe fn do_apply_sbox(state: (__m256i, __m256i, __m256i)) -> (__m256i, __m256i
     let state4 = reduce3(state4_unreduced);
     let state3 = reduce3(state3_unreduced);
     let state7_unreduced = mul3(state3, state4);
-    let state7 = reduce3(state7_unreduced);
-    state7
+    reduce3(state7_unreduced)
 }
 
 #[inline(always)]
@@ -297,29 +344,71 @@ unsafe fn do_apply_inv_sbox(state: (__m256i, __m256i, __m256i)) -> (__m256i, __m
 #[inline(always)]
 unsafe fn avx2_load(state: &[u64; 12]) -> (__m256i, __m256i, __m256i) {
     (
-        _mm256_loadu_si256((&state[0..4]).as_ptr().cast::<__m256i>()),
-        _mm256_loadu_si256((&state[4..8]).as_ptr().cast::<__m256i>()),
-        _mm256_loadu_si256((&state[8..12]).as_ptr().cast::<__m256i>()),
+        _mm256_loadu_si256(state[0..4].as_ptr().cast::<__m256i>()),
+        _mm256_loadu_si256(state[4..8].as_ptr().cast::<__m256i>()),
+        _mm256_loadu_si256(state[8..12].as_ptr().cast::<__m256i>()),
     )
 }
 
 #[inline(always)]
 unsafe fn avx2_store(buf: &mut [u64; 12], state: (__m256i, __m256i, __m256i)) {
-    _mm256_storeu_si256((&mut buf[0..4]).as_mut_ptr().cast::<__m256i>(), state.0);
-    _mm256_storeu_si256((&mut buf[4..8]).as_mut_ptr().cast::<__m256i>(), state.1);
-    _mm256_storeu_si256((&mut buf[8..12]).as_mut_ptr().cast::<__m256i>(), state.2);
+    _mm256_storeu_si256(buf[0..4].as_mut_ptr().cast::<__m256i>(), state.0);
+    _mm256_storeu_si256(buf[4..8].as_mut_ptr().cast::<__m256i>(), state.1);
+    _mm256_storeu_si256(buf[8..12].as_mut_ptr().cast::<__m256i>(), state.2);
 }
 
-#[inline(always)]
-pub unsafe fn apply_sbox(buffer: &mut [u64; 12]) {
-    let mut state = avx2_load(&buffer);
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn apply_sbox(buffer: &mut [u64; 12]) {
+    let mut state = avx2_load(buffer);
     state = do_apply_sbox(state);
     avx2_store(buffer, state);
 }
 
-#[inline(always)]
-pub unsafe fn apply_inv_sbox(buffer: &mut [u64; 12]) {
-    let mut state = avx2_load(&buffer);
+#[inline]
+#[target_feature(enable = "avx2")]
+unsafe fn apply_inv_sbox(buffer: &mut [u64; 12]) {
+    let mut state = avx2_load(buffer);
     state = do_apply_inv_sbox(state);
     avx2_store(buffer, state);
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use rand_utils::rand_array;
+
+    use super::{add_constants_and_apply_inv_sbox, add_constants_and_apply_sbox, avx2_cpuid};
+    use crate::{
+        hash::rescue::{add_constants, apply_inv_sbox, apply_sbox, ARK1, STATE_WIDTH},
+        Felt,
+    };
+
+    #[test]
+    fn test_avx2_sbox() {
+        if !avx2_cpuid::get() {
+            return;
+        }
+
+        for _ in 0..100 {
+            let state: [Felt; STATE_WIDTH] = rand_array();
+            let ark = ARK1[0];
+
+            let mut expected = state;
+            add_constants(&mut expected, &ark);
+            apply_sbox(&mut expected);
+            let mut actual = state;
+            assert!(add_constants_and_apply_sbox(&mut actual, &ark));
+            assert_eq!(expected, actual);
+
+            let mut expected = state;
+            add_constants(&mut expected, &ark);
+            apply_inv_sbox(&mut expected);
+            let mut actual = state;
+            assert!(add_constants_and_apply_inv_sbox(&mut actual, &ark));
+            assert_eq!(expected, actual);
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use core::arch::x86_64::*;
+
+use crate::{
+    hash::rescue::{add_constants, BATCH_SIZE, STATE_WIDTH},
+    Felt,
+};
+
+// The following AVX-512 implementation follows the structure of the AVX2 implementation, but takes
+// advantage of the unsigned 64-bit comparisons and masked operations available in AVX-512F, which
+// remove the need for the shifting tricks used there.
+//
+// The state is held in a 512-bit vector containing elements 0 through 7 and a 256-bit vector
+// containing elements 8 through 11; operations on the latter rely on AVX-512VL.
+//
+// As in the AVX2 implementation, the arithmetic operates on the internal (Montgomery) form of the
+// field elements as if it were the canonical form. This is correct for the S-Box and its inverse
+// because 2^192 = 1 modulo p, and thus for the Montgomery factor R = 2^64 we have (aR)^e = a^e * R
+// whenever e = 1 modulo 3, which holds for both 7 and its inverse.
+//
+// AVX-512 IFMA is not used since its 52-bit multipliers would need to split each 64-bit element
+// into two limbs, which does not beat the 32-bit multipliers used below.
+
+/// 2^32 - 1, i.e., 2^64 modulo p.
+const EPSILON: i64 = 0xffffffff;
+
+type State = (__m512i, __m256i);
+
+/// The state of 8 permutations in a structure-of-arrays layout, i.e., the j-th lane of the i-th
+/// vector holds the i-th element of the j-th state.
+type BatchState = [__m512i; STATE_WIDTH];
+
+// each lane of a 512-bit vector holds an element of a different state
+const _: () = assert!(BATCH_SIZE == 8);
+
+// RUNTIME DISPATCH
+// ================================================================================================
+
+// Support for AVX-512F and AVX-512VL is detected when the functions below are first called, in
+// the same way as support for AVX2; if the code is compiled with both target features enabled, the
+// detection is resolved at compile time.
+cpufeatures::new!(avx512_cpuid, "avx512f", "avx512vl");
+
+/// Returns true if the CPU supports the instructions required by this implementation.
+#[inline(always)]
+fn is_available() -> bool {
+    avx512_cpuid::get()
+}
+
+/// Adds the round constants to the state and applies the S-Box using AVX-512 instructions if the
+/// CPU supports them; otherwise, leaves the state unchanged and returns false.
+#[inline(always)]
+pub fn add_constants_and_apply_sbox(
+    state: &mut [Felt; STATE_WIDTH],
+    ark: &[Felt; STATE_WIDTH],
+) -> bool {
+    if !is_available() {
+        return false;
+    }
+    add_constants(state, ark);
+    unsafe {
+        apply_sbox(core::mem::transmute::<&mut [Felt; STATE_WIDTH], &mut [u64; STATE_WIDTH]>(state))
+    };
+    true
+}
+
+/// Adds the round constants to the state and applies the inverse S-Box using AVX-512
+/// instructions if the CPU supports them; otherwise, leaves the state unchanged and returns
+/// false.
+#[inline(always)]
+pub fn add_constants_and_apply_inv_sbox(
+    state: &mut [Felt; STATE_WIDTH],
+    ark: &[Felt; STATE_WIDTH],
+) -> bool {
+    if !is_available() {
+        return false;
+    }
+    add_constants(state, ark);
+    unsafe {
+        apply_inv_sbox(core::mem::transmute::<&mut [Felt; STATE_WIDTH], &mut [u64; STATE_WIDTH]>(
+            state,
+        ))
+    };
+    true
+}
+
+/// Applies the S-Box to a batch of 8 states in a structure-of-arrays layout using AVX-512
+/// instructions if the CPU supports them; otherwise, leaves the states unchanged and returns
+/// false.
+#[inline(always)]
+pub fn apply_sbox_batch(state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+    if !is_available() {
+        return false;
+    }
+    unsafe { apply_sbox_soa(state) };
+    true
+}
+
+/// Applies the inverse S-Box to a batch of 8 states in a structure-of-arrays layout using
+/// AVX-512 instructions if the CPU supports them; otherwise, leaves the states unchanged and
+/// returns false.
+#[inline(always)]
+pub fn apply_inv_sbox_batch(state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) -> bool {
+    if !is_available() {
+        return false;
+    }
+    unsafe { apply_inv_sbox_soa(state) };
+    true
+}
+
+// FIELD ARITHMETIC
+// ================================================================================================
+
+/// Computes the full 128-bit products of the corresponding elements of the two vectors.
+#[inline]
+#[target_feature(enable = "avx512f")]
+unsafe fn mul128(x: __m512i, y: __m512i) -> (__m512i, __m512i) {
+    let epsilon = _mm512_set1_epi64(EPSILON);
+    let x_hi = _mm512_srli_epi64::<32>(x);
+    let y_hi = _mm512_srli_epi64::<32>(y);
+
+    // all four pairwise products of the 32-bit halves
+    let mul_ll = _mm512_mul_epu32(x, y);
+    let mul_lh = _mm512_mul_epu32(x, y_hi);
+    let mul_hl = _mm512_mul_epu32(x_hi, y);
+    let mul_hh = _mm512_mul_epu32(x_hi, y_hi);
+
+    // bignum addition; none of the additions below can overflow
+    let t0 = _mm512_add_epi64(mul_hl, _mm512_srli_epi64::<32>(mul_ll));
+    let t1 = _mm512_add_epi64(mul_lh, _mm512_and_si512(t0, epsilon));
+    let hi = _mm512_add_epi64(
+        _mm512_add_epi64(mul_hh, _mm512_srli_epi64::<32>(t0)),
+        _mm512_srli_epi64::<32>(t1),
+    );
+    let lo = _mm512_or_si512(_mm512_and_si512(mul_ll, epsilon), _mm512_slli_epi64::<32>(t1));
+    (lo, hi)
+}
+
+/// Reduces the 128-bit values given by their low and high halves modulo p.
+#[inline]
+#[target_feature(enable = "avx512f")]
+unsafe fn reduce128((lo, hi): (__m512i, __m512i)) -> __m512i {
+    let epsilon = _mm512_set1_epi64(EPSILON);
+
+    // subtract the top 32 bits, accounting for 2^64 = 2^32 - 1 modulo p on underflow
+    let hi_hi = _mm512_srli_epi64::<32>(hi);
+    let borrow = _mm512_cmplt_epu64_mask(lo, hi_hi);
+    let t0 = _mm512_sub_epi64(lo, hi_hi);
+    let t0 = _mm512_mask_sub_epi64(t0, borrow, t0, epsilon);
+
+    // add the middle 32 bits multiplied by 2^32 - 1, accounting for 2^64 = 2^32 - 1 modulo p on
+    // overflow
+    let t1 = _mm512_mul_epu32(hi, epsilon);
+    let t2 = _mm512_add_epi64(t0, t1);
+    let carry = _mm512_cmplt_epu64_mask(t2, t1);
+    _mm512_mask_add_epi64(t2, carry, t2, epsilon)
+}
+
+/// Same as [mul128()] but for 256-bit vectors.
+#[inline]
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn mul128_256(x: __m256i, y: __m256i) -> (__m256i, __m256i) {
+    let epsilon = _mm256_set1_epi64x(EPSILON);
+    let x_hi = _mm256_srli_epi64::<32>(x);
+    let y_hi = _mm256_srli_epi64::<32>(y);
+
+    let mul_ll = _mm256_mul_epu32(x, y);
+    let mul_lh = _mm256_mul_epu32(x, y_hi);
+    let mul_hl = _mm256_mul_epu32(x_hi, y);
+    let mul_hh = _mm256_mul_epu32(x_hi, y_hi);
+
+    let t0 = _mm256_add_epi64(mul_hl, _mm256_srli_epi64::<32>(mul_ll));
+    let t1 = _mm256_add_epi64(mul_lh, _mm256_and_si256(t0, epsilon));
+    let hi = _mm256_add_epi64(
+        _mm256_add_epi64(mul_hh, _mm256_srli_epi64::<32>(t0)),
+        _mm256_srli_epi64::<32>(t1),
+    );
+    let lo = _mm256_or_si256(_mm256_and_si256(mul_ll, epsilon), _mm256_slli_epi64::<32>(t1));
+    (lo, hi)
+}
+
+/// Same as [reduce128()] but for 256-bit vectors.
+#[inline]
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn reduce128_256((lo, hi): (__m256i, __m256i)) -> __m256i {
+    let epsilon = _mm256_set1_epi64x(EPSILON);
+
+    let hi_hi = _mm256_srli_epi64::<32>(hi);
+    let borrow = _mm256_cmplt_epu64_mask(lo, hi_hi);
+    let t0 = _mm256_sub_epi64(lo, hi_hi);
+    let t0 = _mm256_mask_sub_epi64(t0, borrow, t0, epsilon);
+
+    let t1 = _mm256_mul_epu32(hi, epsilon);
+    let t2 = _mm256_add_epi64(t0, t1);
+    let carry = _mm256_cmplt_epu64_mask(t2, t1);
+    _mm256_mask_add_epi64(t2, carry, t2, epsilon)
+}
+
+/// A vector of field elements on which the S-Box and its inverse can be computed element-wise.
+trait FieldVector: Copy {
+    /// Multiplies the corresponding elements of the two vectors modulo p.
+    ///
+    /// # Safety
+    /// The CPU must support the avx512f and avx512vl extensions.
+    unsafe fn mul_reduce(self, other: Self) -> Self;
+}
+
+impl FieldVector for State {
+    #[inline(always)]
+    unsafe fn mul_reduce(self, other: Self) -> Self {
+        (reduce128(mul128(self.0, other.0)), reduce128_256(mul128_256(self.1, other.1)))
+    }
+}
+
+impl FieldVector for BatchState {
+    #[inline(always)]
+    unsafe fn mul_reduce(self, other: Self) -> Self {
+        let mut result = self;
+        for (r, (&x, &y)) in result.iter_mut().zip(self.iter().zip(other.iter())) {
+            *r = reduce128(mul128(x, y));
+        }
+        result
+    }
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn mul_reduce<V: FieldVector>(a: V, b: V) -> V {
+    a.mul_reduce(b)
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn square_reduce<V: FieldVector>(a: V) -> V {
+    mul_reduce(a, a)
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn exp_acc<V: FieldVector>(high: V, low: V, exp: usize) -> V {
+    let mut result = high;
+    for _ in 0..exp {
+        result = square_reduce(result);
+    }
+    mul_reduce(result, low)
+}
+
+// S-BOX
+// ================================================================================================
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn do_apply_sbox<V: FieldVector>(state: V) -> V {
+    let state2 = square_reduce(state);
+    let state4 = square_reduce(state2);
+    let state3 = mul_reduce(state2, state);
+    mul_reduce(state3, state4)
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn do_apply_inv_sbox<V: FieldVector>(state: V) -> V {
+    // compute base^10540996611094048183 using 72 multiplications per array element
+    // 10540996611094048183 = b1001001001001001001001001001000110110110110110110110110110110111
+
+    // compute base^10
+    let t1 = square_reduce(state);
+
+    // compute base^100
+    let t2 = square_reduce(t1);
+
+    // compute base^100100
+    let t3 = exp_acc(t2, t2, 3);
+
+    // compute base^100100100100
+    let t4 = exp_acc(t3, t3, 6);
+
+    // compute base^100100100100100100100100
+    let t5 = exp_acc(t4, t4, 12);
+
+    // compute base^100100100100100100100100100100
+    let t6 = exp_acc(t5, t3, 6);
+
+    // compute base^1001001001001001001001001001000100100100100100100100100100100
+    let t7 = exp_acc(t6, t6, 31);
+
+    // compute base^1001001001001001001001001001000110110110110110110110110110110111
+    let a = square_reduce(square_reduce(mul_reduce(square_reduce(t7), t6)));
+    let b = mul_reduce(t1, mul_reduce(t2, state));
+    mul_reduce(a, b)
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn avx512_load(state: &[u64; 12]) -> State {
+    (
+        _mm512_loadu_si512(state[0..8].as_ptr().cast()),
+        _mm256_loadu_si256(state[8..12].as_ptr().cast()),
+    )
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn avx512_store(buf: &mut [u64; 12], state: State) {
+    _mm512_storeu_si512(buf[0..8].as_mut_ptr().cast(), state.0);
+    _mm256_storeu_si256(buf[8..12].as_mut_ptr().cast(), state.1);
+}
+
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn apply_sbox(buffer: &mut [u64; 12]) {
+    let state = avx512_load(buffer);
+    avx512_store(buffer, do_apply_sbox(state));
+}
+
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn apply_inv_sbox(buffer: &mut [u64; 12]) {
+    let state = avx512_load(buffer);
+    avx512_store(buffer, do_apply_inv_sbox(state));
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn batch_load(state: &[[Felt; BATCH_SIZE]; STATE_WIDTH]) -> BatchState {
+    let mut result = [_mm512_setzero_si512(); STATE_WIDTH];
+    for (r, s) in result.iter_mut().zip(state.iter()) {
+        *r = _mm512_loadu_si512(s.as_ptr().cast());
+    }
+    result
+}
+
+#[inline]
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn batch_store(buf: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH], state: BatchState) {
+    for (b, s) in buf.iter_mut().zip(state.iter()) {
+        _mm512_storeu_si512(b.as_mut_ptr().cast(), *s);
+    }
+}
+
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn apply_sbox_soa(buffer: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) {
+    let state = batch_load(buffer);
+    batch_store(buffer, do_apply_sbox(state));
+}
+
+#[target_feature(enable = "avx512f,avx512vl")]
+unsafe fn apply_inv_sbox_soa(buffer: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) {
+    let state = batch_load(buffer);
+    batch_store(buffer, do_apply_inv_sbox(state));
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use rand_utils::rand_array;
+
+    use super::{
+        add_constants_and_apply_inv_sbox, add_constants_and_apply_sbox, apply_inv_sbox_batch,
+        apply_sbox_batch, is_available,
+    };
+    use crate::{
+        hash::rescue::{add_constants, apply_inv_sbox, apply_sbox, ARK1, BATCH_SIZE, STATE_WIDTH},
+        Felt,
+    };
+
+    #[test]
+    fn test_avx512_sbox() {
+        if !is_available() {
+            return;
+        }
+
+        for _ in 0..100 {
+            let state: [Felt; STATE_WIDTH] = rand_array();
+            let ark = ARK1[0];
+
+            let mut expected = state;
+            add_constants(&mut expected, &ark);
+            apply_sbox(&mut expected);
+            let mut actual = state;
+            assert!(add_constants_and_apply_sbox(&mut actual, &ark));
+            assert_eq!(expected, actual);
+
+            let mut expected = state;
+            add_constants(&mut expected, &ark);
+            apply_inv_sbox(&mut expected);
+            let mut actual = state;
+            assert!(add_constants_and_apply_inv_sbox(&mut actual, &ark));
+            assert_eq!(expected, actual);
+        }
+    }
+
+    #[test]
+    fn test_avx512_sbox_batch() {
+        if !is_available() {
+            return;
+        }
+
+        let states: [[Felt; BATCH_SIZE]; STATE_WIDTH] =
+            rand_array::<Felt, STATE_WIDTH>().map(|_| rand_array::<Felt, BATCH_SIZE>());
+
+        let mut expected = states;
+        expected.iter_mut().flatten().for_each(|s| *s = s.exp7());
+        let mut actual = states;
+        assert!(apply_sbox_batch(&mut actual));
+        assert_eq!(expected, actual);
+
+        let mut expected = states;
+        expected.iter_mut().for_each(apply_inv_sbox);
+        let mut actual = states;
+        assert!(apply_inv_sbox_batch(&mut actual));
+        assert_eq!(expected, actual);
+    }
+}
@@ -159,7 +159,7 @@ const fn block3(x: [i64; 3], y: [i64; 3]) -> [i64; 3] {
 mod tests {
     use proptest::prelude::*;
 
-    use super::super::{apply_mds, Felt, MDS, ZERO};
+    use super::super::{apply_mds, apply_mds_batch, Felt, MDS, ZERO};
 
     const STATE_WIDTH: usize = 12;
 
@@ -191,5 +191,26 @@ mod tests {
 
             prop_assert_eq!(v1, v2);
         }
+
+        #[test]
+        fn mds_batch_proptest(a in any::<[[u64; STATE_WIDTH]; 4]>()) {
+            let states = a.map(|s| s.map(Felt::new));
+
+            let mut batch = [[ZERO; 4]; STATE_WIDTH];
+            for (j, state) in states.iter().enumerate() {
+                for i in 0..STATE_WIDTH {
+                    batch[i][j] = state[i];
+                }
+            }
+            apply_mds_batch(&mut batch);
+
+            for (j, state) in states.iter().enumerate() {
+                let mut expected = *state;
+                apply_mds(&mut expected);
+                for i in 0..STATE_WIDTH {
+                    prop_assert_eq!(batch[i][j], expected[i]);
+                }
+            }
+        }
     }
 }
@@ -38,6 +38,19 @@ pub fn apply_mds(state: &mut [Felt; STATE_WIDTH]) {
     *state = result;
 }
 
+/// Applies the MDS matrix to N states held in a structure-of-arrays layout, i.e., with
+/// `state[i][j]` being the i-th element of the j-th state.
+#[inline(always)]
+pub fn apply_mds_batch<const N: usize>(state: &mut [[Felt; N]; STATE_WIDTH]) {
+    // the FFT-based multiplication is cheaper than a matrix-vector product vectorized across the
+    // states, and thus the states are multiplied one by one
+    for j in 0..N {
+        let mut column = state.map(|row| row[j]);
+        apply_mds(&mut column);
+        state.iter_mut().zip(column).for_each(|(row, s)| row[j] = s);
+    }
+}
+
 // MDS MATRIX
 // ================================================================================================
 
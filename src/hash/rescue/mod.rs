@@ -8,10 +8,13 @@ mod arch;
 pub use arch::optimized::{add_constants_and_apply_inv_sbox, add_constants_and_apply_sbox};
 
 mod mds;
-use mds::{apply_mds, MDS};
+pub(crate) use mds::apply_mds;
+use mds::{apply_mds_batch, MDS};
 
 mod rpo;
-pub use rpo::{Rpo256, RpoDigest};
+pub use rpo::{
+    LengthEncoding, Rpo256, Rpo256Stream, Rpo256Xof, Rpo256XofReader, RpoDigest, RpoDuplex,
+};
 
 mod rpx;
 pub use rpx::{Rpx256, RpxDigest};
@@ -53,13 +56,14 @@ const DIGEST_BYTES: usize = 32;
 /// The number of byte chunks defining a field element when hashing a sequence of bytes
 const BINARY_CHUNK_SIZE: usize = 7;
 
+/// The number of states permuted together by the batched permutation functions.
+const BATCH_SIZE: usize = 8;
+
 /// S-Box and Inverse S-Box powers;
 ///
-/// The constants are defined for tests only because the exponentiations in the code are unrolled
-/// for efficiency reasons.
-#[cfg(test)]
+/// The constants are not used by the permutation itself because the exponentiations in the code
+/// are unrolled for efficiency reasons.
 const ALPHA: u64 = 7;
-#[cfg(test)]
 const INV_ALPHA: u64 = 10540996611094048183;
 
 // SBOX FUNCTION
@@ -85,7 +89,7 @@ fn apply_sbox(state: &mut [Felt; STATE_WIDTH]) {
 // ================================================================================================
 
 #[inline(always)]
-fn apply_inv_sbox(state: &mut [Felt; STATE_WIDTH]) {
+fn apply_inv_sbox<const N: usize>(state: &mut [Felt; N]) {
     // compute base^10540996611094048183 using 72 multiplications per array element
     // 10540996611094048183 = b1001001001001001001001001001000110110110110110110110110110110111
 
@@ -98,19 +102,19 @@ fn apply_inv_sbox(state: &mut [Felt; STATE_WIDTH]) {
     t2.iter_mut().for_each(|t| *t = t.square());
 
     // compute base^100100
-    let t3 = exp_acc::<Felt, STATE_WIDTH, 3>(t2, t2);
+    let t3 = exp_acc::<Felt, N, 3>(t2, t2);
 
     // compute base^100100100100
-    let t4 = exp_acc::<Felt, STATE_WIDTH, 6>(t3, t3);
+    let t4 = exp_acc::<Felt, N, 6>(t3, t3);
 
     // compute base^100100100100100100100100
-    let t5 = exp_acc::<Felt, STATE_WIDTH, 12>(t4, t4);
+    let t5 = exp_acc::<Felt, N, 12>(t4, t4);
 
     // compute base^100100100100100100100100100100
-    let t6 = exp_acc::<Felt, STATE_WIDTH, 6>(t5, t3);
+    let t6 = exp_acc::<Felt, N, 6>(t5, t3);
 
     // compute base^1001001001001001001001001001000100100100100100100100100100100
-    let t7 = exp_acc::<Felt, STATE_WIDTH, 31>(t6, t6);
+    let t7 = exp_acc::<Felt, N, 31>(t6, t6);
 
     // compute base^1001001001001001001001001001000110110110110110110110110110110111
     for (i, s) in state.iter_mut().enumerate() {
@@ -138,6 +142,35 @@ fn add_constants(state: &mut [Felt; STATE_WIDTH], ark: &[Felt; STATE_WIDTH]) {
     state.iter_mut().zip(ark).for_each(|(s, &k)| *s += k);
 }
 
+// BATCHED ROUND FUNCTIONS
+// ================================================================================================
+
+// The following functions operate on N states held in a structure-of-arrays layout, i.e., with
+// `state[i][j]` being the i-th element of the j-th state. Applying the same operation to all
+// states at once exposes independent computations which the CPU can execute in parallel.
+
+#[inline(always)]
+fn apply_sbox_batch(state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) {
+    if !arch::optimized::apply_sbox_batch(state) {
+        state.iter_mut().flatten().for_each(|s| *s = s.exp7());
+    }
+}
+
+#[inline(always)]
+fn apply_inv_sbox_batch(state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH]) {
+    if !arch::optimized::apply_inv_sbox_batch(state) {
+        state.iter_mut().for_each(apply_inv_sbox);
+    }
+}
+
+#[inline(always)]
+fn add_constants_batch<const N: usize>(
+    state: &mut [[Felt; N]; STATE_WIDTH],
+    ark: &[Felt; STATE_WIDTH],
+) {
+    state.iter_mut().zip(ark).for_each(|(s, &k)| s.iter_mut().for_each(|s| *s += k));
+}
+
 // ROUND CONSTANTS
 // ================================================================================================
 
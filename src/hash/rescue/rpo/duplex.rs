@@ -0,0 +1,138 @@
+use super::{
+    Felt, FieldElement, Rpo256, RpoDigest, CAPACITY_RANGE, DIGEST_SIZE, ONE, RATE_RANGE,
+    RATE_WIDTH, STATE_WIDTH, ZERO,
+};
+use crate::Word;
+
+// DUPLEX SPONGE
+// ================================================================================================
+
+/// A duplex sponge over the RPO permutation which allows absorbing input and squeezing output in
+/// any interleaved order.
+///
+/// This is intended to serve as a Fiat-Shamir transcript: the prover and the verifier absorb the
+/// same protocol messages and squeeze challenges from the sponge, and every challenge depends on
+/// all input absorbed before it.
+///
+/// The sponge alternates between an absorbing and a squeezing phase. Input is written into the
+/// rate portion of the state, and the permutation is applied whenever the rate is full. Before
+/// the first element of a squeezing phase is read, the absorbed input is padded by appending a 1
+/// followed by as many 0 as necessary to fill the rate, and the permutation is applied. Output
+/// is read from the full rate portion of the state, i.e., 8 elements are emitted per permutation.
+///
+/// Consecutive calls within the same phase behave as a single call on the concatenation of their
+/// arguments. That is, splitting an absorb (or a squeeze) into multiple calls does not change the
+/// output, but interleaving absorbs and squeezes in a different order does.
+///
+/// The domain passed to [RpoDuplex::new()] is placed in the second capacity element, and the
+/// fourth capacity element is set to 1, which separates the outputs of the sponge from the
+/// outputs of [Rpo256::hash_elements()], [Rpo256::merge_in_domain()], and [Rpo256Xof].
+///
+/// [Rpo256Xof]: super::Rpo256Xof
+#[derive(Debug, Clone)]
+pub struct RpoDuplex {
+    state: [Felt; STATE_WIDTH],
+    /// Index of the next element to be absorbed or squeezed within the rate portion of the state.
+    rate_idx: usize,
+    /// True if the sponge is in the squeezing phase.
+    squeezing: bool,
+}
+
+impl RpoDuplex {
+    /// Returns a new [RpoDuplex] for the specified domain which has not absorbed any input.
+    ///
+    /// Different protocols (or different instances of the same protocol) should use different
+    /// domains so that their challenges are independent.
+    pub fn new(domain: Felt) -> Self {
+        let mut state = [ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start + 1] = domain;
+        state[CAPACITY_RANGE.start + 3] = ONE;
+        Self { state, rate_idx: 0, squeezing: false }
+    }
+
+    // ABSORBING
+    // --------------------------------------------------------------------------------------------
+
+    /// Absorbs the provided field elements into the sponge state.
+    ///
+    /// Extension field elements are absorbed as the sequence of their base field coordinates.
+    ///
+    /// If the sponge is in the squeezing phase, a new absorbing phase is started even if
+    /// `elements` is empty.
+    pub fn absorb<E: FieldElement<BaseField = Felt>>(&mut self, elements: &[E]) {
+        let elements = E::slice_as_base_elements(elements);
+        if self.squeezing {
+            self.squeezing = false;
+            self.rate_idx = 0;
+        }
+        for &element in elements {
+            if self.rate_idx == RATE_WIDTH {
+                Rpo256::apply_permutation(&mut self.state);
+                self.rate_idx = 0;
+            }
+            self.state[RATE_RANGE.start + self.rate_idx] = element;
+            self.rate_idx += 1;
+        }
+    }
+
+    /// Absorbs the elements of the provided word into the sponge state.
+    pub fn absorb_word(&mut self, word: Word) {
+        self.absorb(&word);
+    }
+
+    /// Absorbs the elements of the provided digest into the sponge state.
+    pub fn absorb_digest(&mut self, digest: RpoDigest) {
+        self.absorb(digest.as_elements());
+    }
+
+    // SQUEEZING
+    // --------------------------------------------------------------------------------------------
+
+    /// Fills the provided slice with the next field elements of the output.
+    pub fn squeeze(&mut self, output: &mut [Felt]) {
+        for element in output.iter_mut() {
+            *element = self.squeeze_element();
+        }
+    }
+
+    /// Returns the next field element of the output.
+    pub fn squeeze_element(&mut self) -> Felt {
+        if !self.squeezing {
+            self.pad_and_permute();
+        } else if self.rate_idx == RATE_WIDTH {
+            Rpo256::apply_permutation(&mut self.state);
+            self.rate_idx = 0;
+        }
+        let element = self.state[RATE_RANGE.start + self.rate_idx];
+        self.rate_idx += 1;
+        element
+    }
+
+    /// Returns the next word of the output.
+    pub fn squeeze_word(&mut self) -> Word {
+        let mut word = [ZERO; DIGEST_SIZE];
+        self.squeeze(&mut word);
+        word
+    }
+
+    /// Returns the next four elements of the output as a digest.
+    pub fn squeeze_digest(&mut self) -> RpoDigest {
+        RpoDigest::new(self.squeeze_word())
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Ends the absorbing phase by padding the absorbed input and applying the permutation.
+    fn pad_and_permute(&mut self) {
+        if self.rate_idx == RATE_WIDTH {
+            Rpo256::apply_permutation(&mut self.state);
+            self.rate_idx = 0;
+        }
+        self.state[RATE_RANGE.start + self.rate_idx] = ONE;
+        self.state[RATE_RANGE.start + self.rate_idx + 1..RATE_RANGE.end].fill(ZERO);
+        Rpo256::apply_permutation(&mut self.state);
+        self.rate_idx = 0;
+        self.squeezing = true;
+    }
+}
@@ -1,18 +1,69 @@
+use alloc::{vec, vec::Vec};
 use core::ops::Range;
 
+#[cfg(feature = "std")]
+use crate::hash::{read_chunk, READER_BUFFER_SIZE};
+use crate::{hash::prf::RpoPrf, Word};
+
 use super::{
-    add_constants, add_constants_and_apply_inv_sbox, add_constants_and_apply_sbox, apply_inv_sbox,
-    apply_mds, apply_sbox, Digest, ElementHasher, Felt, FieldElement, Hasher, StarkField, ARK1,
-    ARK2, BINARY_CHUNK_SIZE, CAPACITY_RANGE, DIGEST_BYTES, DIGEST_RANGE, DIGEST_SIZE, INPUT1_RANGE,
-    INPUT2_RANGE, MDS, NUM_ROUNDS, ONE, RATE_RANGE, RATE_WIDTH, STATE_WIDTH, ZERO,
+    add_constants, add_constants_and_apply_inv_sbox, add_constants_and_apply_sbox,
+    add_constants_batch, apply_inv_sbox, apply_inv_sbox_batch, apply_mds, apply_mds_batch,
+    apply_sbox, apply_sbox_batch, Digest, ElementHasher, Felt, FieldElement, Hasher, StarkField,
+    ALPHA, ARK1, ARK2, BATCH_SIZE, BINARY_CHUNK_SIZE, CAPACITY_RANGE, DIGEST_BYTES, DIGEST_RANGE,
+    DIGEST_SIZE, INPUT1_RANGE, INPUT2_RANGE, INV_ALPHA, MDS, NUM_ROUNDS, ONE, RATE_RANGE,
+    RATE_WIDTH, STATE_WIDTH, ZERO,
 };
 
 mod digest;
 pub use digest::RpoDigest;
 
+mod duplex;
+pub use duplex::RpoDuplex;
+
+mod stream;
+pub use stream::Rpo256Stream;
+
+mod xof;
+pub use xof::{Rpo256Xof, Rpo256XofReader};
+
+#[cfg(feature = "std")]
+mod tree;
+
 #[cfg(test)]
 mod tests;
 
+// LENGTH ENCODING
+// ================================================================================================
+
+/// Specifies how the length of a variable-length input is bound into the capacity portion of the
+/// sponge state by [Rpo256::hash_with_encoding()] and [Rpo256::hash_elements_with_encoding()].
+///
+/// The digests produced under each encoding are fixed: changing how any encoding handles its
+/// input is a breaking change, and will only happen in a major release (or in a minor release
+/// prior to 1.0). Digests produced under different encodings are unrelated to each other, except
+/// that the empty input is hashed into a word of zeros under all encodings.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum LengthEncoding {
+    /// The encoding used by [Rpo256::hash()] and [Rpo256::hash_elements()], which matches the
+    /// digests computed by Miden VM.
+    ///
+    /// The first capacity element is set to 1 if the length of the input is not a multiple of
+    /// the rate width, and to 0 otherwise (for byte strings, the length is counted in bytes).
+    /// Input elements overwrite the rate portion of the state, and a partially filled rate is
+    /// padded with a 1 followed by as many 0 as necessary. For byte strings, a byte with value 1
+    /// is also appended to the last chunk if it is shorter than 7 bytes.
+    #[default]
+    RateFlag,
+    /// The encoding of the Rescue Prime sponge (`Rp64_256` in winter-crypto), instantiated with
+    /// the RPO permutation.
+    ///
+    /// The first capacity element is set to the number of input elements (for byte strings, the
+    /// number of 7-byte chunks). Input elements are added to the rate portion of the state, and
+    /// no padding elements are absorbed. For byte strings, a byte with value 1 is appended to the
+    /// last chunk.
+    Length,
+}
+
 // HASHER IMPLEMENTATION
 // ================================================================================================
 
@@ -124,17 +175,9 @@ impl Hasher for Rpo256 {
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
-        // initialize the state by copying the digest elements into the rate portion of the state
-        // (8 total elements), and set the capacity elements to 0.
-        let mut state = [ZERO; STATE_WIDTH];
-        let it = Self::Digest::digests_as_elements(values.iter());
-        for (i, v) in it.enumerate() {
-            state[RATE_RANGE.start + i] = *v;
-        }
-
-        // apply the RPO permutation and return the first four elements of the state
-        Self::apply_permutation(&mut state);
-        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+        let mut digest = [ZERO; DIGEST_SIZE];
+        Self::merge_into(values, &mut digest);
+        RpoDigest::new(digest)
     }
 
     fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
@@ -169,45 +212,7 @@ impl ElementHasher for Rpo256 {
     type BaseField = Felt;
 
     fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
-        // convert the elements into a list of base field elements
-        let elements = E::slice_as_base_elements(elements);
-
-        // initialize state to all zeros, except for the first element of the capacity part, which
-        // is set to 1 if the number of elements is not a multiple of RATE_WIDTH.
-        let mut state = [ZERO; STATE_WIDTH];
-        if elements.len() % RATE_WIDTH != 0 {
-            state[CAPACITY_RANGE.start] = ONE;
-        }
-
-        // absorb elements into the state one by one until the rate portion of the state is filled
-        // up; then apply the Rescue permutation and start absorbing again; repeat until all
-        // elements have been absorbed
-        let mut i = 0;
-        for &element in elements.iter() {
-            state[RATE_RANGE.start + i] = element;
-            i += 1;
-            if i % RATE_WIDTH == 0 {
-                Self::apply_permutation(&mut state);
-                i = 0;
-            }
-        }
-
-        // if we absorbed some elements but didn't apply a permutation to them (would happen when
-        // the number of elements is not a multiple of RATE_WIDTH), apply the RPO permutation after
-        // padding by appending a 1 followed by as many 0 as necessary to make the input length a
-        // multiple of the RATE_WIDTH.
-        if i > 0 {
-            state[RATE_RANGE.start + i] = ONE;
-            i += 1;
-            while i != RATE_WIDTH {
-                state[RATE_RANGE.start + i] = ZERO;
-                i += 1;
-            }
-            Self::apply_permutation(&mut state);
-        }
-
-        // return the first 4 elements of the state as hash result
-        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+        Self::hash_elements_in_domain(elements, ZERO)
     }
 }
 
@@ -243,6 +248,13 @@ impl Rpo256 {
     /// Round constants added to the hasher state in the second half of the RPO round.
     pub const ARK2: [[Felt; STATE_WIDTH]; NUM_ROUNDS] = ARK2;
 
+    /// The power of the S-Box applied in the first half of the RPO round.
+    pub const ALPHA: u64 = ALPHA;
+
+    /// The power of the inverse S-Box applied in the second half of the RPO round, i.e., the
+    /// inverse of [Self::ALPHA] modulo `p - 1`.
+    pub const INV_ALPHA: u64 = INV_ALPHA;
+
     // TRAIT PASS-THROUGH FUNCTIONS
     // --------------------------------------------------------------------------------------------
 
@@ -260,11 +272,93 @@ impl Rpo256 {
     }
 
     /// Returns a hash of the provided field elements.
+    ///
+    /// Extension field elements are hashed as the sequence of their base field coordinates; the
+    /// coordinates are read in place, without copying the input into a vector of base elements.
     #[inline(always)]
     pub fn hash_elements<E: FieldElement<BaseField = Felt>>(elements: &[E]) -> RpoDigest {
         <Self as ElementHasher>::hash_elements(elements)
     }
 
+    /// Returns a hash of the field elements produced by the provided iterator, consuming the
+    /// elements one by one without collecting them.
+    ///
+    /// The result is the same as the result of [hash_elements()](Rpo256::hash_elements) over the
+    /// collected elements. Since the length of the input is not known in advance (the size hint
+    /// of the iterator is not relied upon), the elements are absorbed via [Rpo256Stream], which
+    /// is about twice as expensive as [hash_elements()](Rpo256::hash_elements). For iterators of
+    /// known length, [hash_elements_exact_iter()](Rpo256::hash_elements_exact_iter) is as fast as
+    /// [hash_elements()](Rpo256::hash_elements).
+    pub fn hash_elements_iter<I: IntoIterator<Item = Felt>>(elements: I) -> RpoDigest {
+        let mut stream = Rpo256Stream::new();
+        elements.into_iter().for_each(|element| stream.update(&[element]));
+        stream.finalize()
+    }
+
+    /// Returns a hash of the field elements produced by the provided iterator of known length,
+    /// consuming the elements one by one without collecting them.
+    ///
+    /// The result is the same as the result of [hash_elements()](Rpo256::hash_elements) over the
+    /// collected elements. Since the length of the input is known in advance, the elements are
+    /// absorbed into a single sponge state, as in [hash_elements()](Rpo256::hash_elements).
+    ///
+    /// # Panics
+    /// Panics if the iterator produces a different number of elements than reported by
+    /// [ExactSizeIterator::len()].
+    pub fn hash_elements_exact_iter<I>(elements: I) -> RpoDigest
+    where
+        I: IntoIterator<Item = Felt>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        let elements = elements.into_iter();
+        let len = elements.len();
+        let mut digest = [ZERO; DIGEST_SIZE];
+        let num_elements = Self::hash_iter_in_domain_into(elements, len, ZERO, &mut digest);
+        assert_eq!(
+            num_elements, len,
+            "iterator produced a different number of elements than its length"
+        );
+        RpoDigest::new(digest)
+    }
+
+    // INTEGER HASHING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a hash of the provided 64-bit integers.
+    ///
+    /// Since not every 64-bit integer is a valid field element, each integer is split into two
+    /// 32-bit limbs, and the limbs are hashed as field elements via
+    /// [hash_elements()](Rpo256::hash_elements), lower limb first. That is, `[a, b]` is hashed as
+    /// `[a_lo, a_hi, b_lo, b_hi]`.
+    pub fn hash_u64s(values: &[u64]) -> RpoDigest {
+        let limbs: Vec<Felt> = values
+            .iter()
+            .flat_map(|&value| [Felt::new(value as u32 as u64), Felt::new(value >> 32)])
+            .collect();
+        Self::hash_elements(&limbs)
+    }
+
+    /// Returns a hash of the provided bytes packed into field elements.
+    ///
+    /// The bytes are hashed as field elements via [hash_elements()](Rpo256::hash_elements). The
+    /// first element is the number of bytes, and it is followed by the bytes packed 4 at a time
+    /// into 32-bit limbs in little-endian order, with the last limb padded with zeros. Thus, the
+    /// hash of `8 * n` bytes is not consistent with [hash_u64s()](Rpo256::hash_u64s) of the `n`
+    /// little-endian integers they encode, since the latter does not include the length.
+    ///
+    /// Unlike [hash()](Rpo256::hash), where 7 bytes are absorbed per element, the limbs of the
+    /// packed bytes can be handled natively by the u32 operations of Miden VM.
+    pub fn hash_u8s_packed(bytes: &[u8]) -> RpoDigest {
+        let mut elements = Vec::with_capacity(1 + bytes.len().div_ceil(4));
+        elements.push(Felt::new(bytes.len() as u64));
+        elements.extend(bytes.chunks(4).map(|chunk| {
+            let mut buf = [0_u8; 4];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            Felt::new(u32::from_le_bytes(buf) as u64)
+        }));
+        Self::hash_elements(&elements)
+    }
+
     // DOMAIN IDENTIFIER
     // --------------------------------------------------------------------------------------------
 
@@ -287,32 +381,442 @@ impl Rpo256 {
         RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
     }
 
+    /// Returns hashes of the provided pairs of digests and a domain identifier, i.e., the i-th
+    /// element of the result is equal to `merge_in_domain(&pairs[i], domain)`.
+    ///
+    /// The states are permuted using [Self::apply_permutation_batch()], which makes this function
+    /// considerably faster than merging the pairs one by one. This is intended for Merkle trees
+    /// which tag their nodes with a domain (e.g., to distinguish leaves from internal nodes).
+    pub fn merge_in_domain_many(pairs: &[[RpoDigest; 2]], domain: Felt) -> Vec<RpoDigest> {
+        let mut result = vec![RpoDigest::default(); pairs.len()];
+        Self::merge_in_domain_batch(pairs, domain, &mut result);
+        result
+    }
+
+    /// Writes `merge_in_domain(&pairs[i], domain)` into `out[i]` for every pair, permuting the
+    /// states using [Self::apply_permutation_batch()].
+    fn merge_in_domain_batch(pairs: &[[RpoDigest; 2]], domain: Felt, out: &mut [RpoDigest]) {
+        debug_assert_eq!(pairs.len(), out.len());
+
+        // initialize the states in the same way as merge_in_domain() does
+        let mut states: Vec<[Felt; STATE_WIDTH]> = pairs
+            .iter()
+            .map(|pair| {
+                let mut state = [ZERO; STATE_WIDTH];
+                let it = RpoDigest::digests_as_elements(pair.iter());
+                for (i, v) in it.enumerate() {
+                    state[RATE_RANGE.start + i] = *v;
+                }
+                state[CAPACITY_RANGE.start + 1] = domain;
+                state
+            })
+            .collect();
+
+        Self::apply_permutation_batch(&mut states);
+        for (digest, state) in out.iter_mut().zip(states) {
+            *digest = RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap());
+        }
+    }
+
+    /// Returns a hash of the provided field elements and a domain identifier.
+    ///
+    /// The domain identifier is set in the second capacity element, as in
+    /// [merge_in_domain()](Rpo256::merge_in_domain), and thus hashing the same elements in
+    /// different domains produces unrelated digests. Hashing in domain 0 is equivalent to
+    /// [hash_elements()](Rpo256::hash_elements).
+    pub fn hash_elements_in_domain<E: FieldElement<BaseField = Felt>>(
+        elements: &[E],
+        domain: Felt,
+    ) -> RpoDigest {
+        let mut digest = [ZERO; DIGEST_SIZE];
+        Self::hash_elements_in_domain_into(elements, domain, &mut digest);
+        RpoDigest::new(digest)
+    }
+
+    // HASHING INTO AN OUTPUT BUFFER
+    // --------------------------------------------------------------------------------------------
+
+    /// Writes a hash of the provided field elements into `out`.
+    ///
+    /// The result is the same as the result of [hash_elements()](Rpo256::hash_elements), but no
+    /// digest value is constructed and returned, which helps to avoid redundant copies in hot
+    /// loops.
+    #[inline(always)]
+    pub fn hash_elements_into<E: FieldElement<BaseField = Felt>>(elements: &[E], out: &mut Word) {
+        Self::hash_elements_in_domain_into(elements, ZERO, out);
+    }
+
+    /// Writes a 2-to-1 hash of the provided digests into `out`.
+    ///
+    /// The result is the same as the result of [merge()](Rpo256::merge), but no digest value is
+    /// constructed and returned, which helps to avoid redundant copies in hot loops.
+    #[inline(always)]
+    pub fn merge_into(values: &[RpoDigest; 2], out: &mut Word) {
+        // initialize the state by copying the digest elements into the rate portion of the state
+        // (8 total elements), and set the capacity elements to 0.
+        let mut state = [ZERO; STATE_WIDTH];
+        state[INPUT1_RANGE].copy_from_slice(values[0].as_elements());
+        state[INPUT2_RANGE].copy_from_slice(values[1].as_elements());
+
+        // apply the RPO permutation and write the first four elements of the rate into `out`
+        Self::apply_permutation(&mut state);
+        out.copy_from_slice(&state[DIGEST_RANGE]);
+    }
+
+    /// Writes a hash of the provided field elements and a domain identifier into `out`.
+    fn hash_elements_in_domain_into<E: FieldElement<BaseField = Felt>>(
+        elements: &[E],
+        domain: Felt,
+        out: &mut Word,
+    ) {
+        // convert the elements into a list of base field elements
+        let elements = E::slice_as_base_elements(elements);
+        Self::hash_iter_in_domain_into(elements.iter().copied(), elements.len(), domain, out);
+    }
+
+    /// Writes a hash of the provided field elements and a domain identifier into `out`, and
+    /// returns the number of elements produced by the iterator.
+    ///
+    /// The padding flag in the capacity portion of the state is set according to `len`, and thus
+    /// the hash is correct only if the iterator produces exactly `len` elements.
+    fn hash_iter_in_domain_into<I: Iterator<Item = Felt>>(
+        elements: I,
+        len: usize,
+        domain: Felt,
+        out: &mut Word,
+    ) -> usize {
+        // initialize state to all zeros, except for the first element of the capacity part, which
+        // is set to 1 if the number of elements is not a multiple of RATE_WIDTH.
+        let mut state = [ZERO; STATE_WIDTH];
+        if len % RATE_WIDTH != 0 {
+            state[CAPACITY_RANGE.start] = ONE;
+        }
+
+        // set the second capacity element to the domain value
+        state[CAPACITY_RANGE.start + 1] = domain;
+
+        // absorb elements into the state one by one until the rate portion of the state is filled
+        // up; then apply the Rescue permutation and start absorbing again; repeat until all
+        // elements have been absorbed
+        let mut num_elements = 0;
+        let mut i = 0;
+        for element in elements {
+            state[RATE_RANGE.start + i] = element;
+            num_elements += 1;
+            i += 1;
+            if i % RATE_WIDTH == 0 {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // if we absorbed some elements but didn't apply a permutation to them (would happen when
+        // the number of elements is not a multiple of RATE_WIDTH), apply the RPO permutation after
+        // padding by appending a 1 followed by as many 0 as necessary to make the input length a
+        // multiple of the RATE_WIDTH.
+        if i > 0 {
+            state[RATE_RANGE.start + i] = ONE;
+            i += 1;
+            while i != RATE_WIDTH {
+                state[RATE_RANGE.start + i] = ZERO;
+                i += 1;
+            }
+            Self::apply_permutation(&mut state);
+        }
+
+        // write the first 4 elements of the rate into `out` as hash result
+        out.copy_from_slice(&state[DIGEST_RANGE]);
+        num_elements
+    }
+
+    // LENGTH ENCODING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a hash of the provided sequence of bytes, binding the length of the input into the
+    /// capacity portion of the state as specified by `encoding`.
+    ///
+    /// With [LengthEncoding::RateFlag], the result is the same as the result of
+    /// [hash()](Rpo256::hash).
+    pub fn hash_with_encoding(bytes: &[u8], encoding: LengthEncoding) -> RpoDigest {
+        if encoding == LengthEncoding::RateFlag {
+            return Self::hash(bytes);
+        }
+
+        // compute the number of 7-byte chunks (including a potential partial chunk at the end)
+        // and set the first capacity element to it.
+        let num_elements = bytes.len().div_ceil(BINARY_CHUNK_SIZE);
+        let mut state = [ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = Felt::new(num_elements as u64);
+
+        // convert each chunk into a field element and add it to the rate portion of the state.
+        // a byte with value 1 is appended to the last chunk, even if it is a full chunk, so that
+        // adding trailing zeros results in a different hash.
+        let mut i = 0;
+        for (chunk_idx, chunk) in bytes.chunks(BINARY_CHUNK_SIZE).enumerate() {
+            let mut buf = [0_u8; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            if chunk_idx == num_elements - 1 {
+                buf[chunk.len()] = 1;
+            }
+            state[RATE_RANGE.start + i] += Felt::new(u64::from_le_bytes(buf));
+            i += 1;
+            if i == RATE_WIDTH {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // no padding elements are needed since the number of elements is bound in the capacity
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    /// Returns a hash of the provided field elements, binding the length of the input into the
+    /// capacity portion of the state as specified by `encoding`.
+    ///
+    /// With [LengthEncoding::RateFlag], the result is the same as the result of
+    /// [hash_elements()](Rpo256::hash_elements).
+    pub fn hash_elements_with_encoding<E: FieldElement<BaseField = Felt>>(
+        elements: &[E],
+        encoding: LengthEncoding,
+    ) -> RpoDigest {
+        if encoding == LengthEncoding::RateFlag {
+            return Self::hash_elements(elements);
+        }
+
+        // initialize the state to all zeros, except for the first capacity element, which is set
+        // to the number of elements to be hashed.
+        let elements = E::slice_as_base_elements(elements);
+        let mut state = [ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = Felt::new(elements.len() as u64);
+
+        // add the elements to the rate portion of the state, applying the permutation whenever
+        // the rate is filled up.
+        let mut i = 0;
+        for &element in elements.iter() {
+            state[RATE_RANGE.start + i] += element;
+            i += 1;
+            if i == RATE_WIDTH {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // no padding elements are needed since the number of elements is bound in the capacity
+        if i > 0 {
+            Self::apply_permutation(&mut state);
+        }
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    // KEYED HASHING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a message authentication code (MAC) of the provided field elements under the
+    /// provided key.
+    ///
+    /// The key is absorbed into the capacity portion of the state, and the elements are absorbed
+    /// into the rate portion. Since the capacity is occupied by the key, the input is always
+    /// padded by appending a 1 followed by as many 0 as necessary to make its length a multiple
+    /// of the rate. This also makes the function a pseudorandom function (PRF) keyed by `key`.
+    ///
+    /// The keyed sponge is implemented by [RpoPrf](crate::hash::prf::RpoPrf), which can also
+    /// evaluate inputs supplied in parts; this function is equivalent to
+    /// `RpoPrf::new(key).eval(data)`.
+    pub fn mac(key: Word, data: &[Felt]) -> RpoDigest {
+        RpoPrf::new(key).eval(data).into()
+    }
+
+    // READER HASHING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a hash of all bytes read from the provided reader until EOF.
+    ///
+    /// The input is read in fixed-size chunks and absorbed via [Rpo256Stream], and thus the
+    /// result is the same as the result of [hash()](Rpo256::hash) over the entire input, without
+    /// the need to load it into memory at once.
+    ///
+    /// # Errors
+    /// Returns an error if reading from `reader` fails with an error other than
+    /// [ErrorKind::Interrupted](std::io::ErrorKind::Interrupted).
+    #[cfg(feature = "std")]
+    pub fn hash_reader<R: std::io::Read>(mut reader: R) -> std::io::Result<RpoDigest> {
+        let mut stream = Rpo256Stream::new();
+        let mut buf = [0_u8; READER_BUFFER_SIZE];
+        loop {
+            let len = read_chunk(&mut reader, &mut buf)?;
+            stream.update_bytes(&buf[..len]);
+            if len < buf.len() {
+                return Ok(stream.finalize());
+            }
+        }
+    }
+
+    // PARALLEL HASHING
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns hashes of the provided sequences of field elements, i.e., the i-th element of the
+    /// result is equal to `hash_elements(inputs[i])`.
+    ///
+    /// The sequences are hashed concurrently across all available CPU threads using rayon. See
+    /// also the `hash::gpu` module (behind the `gpu` feature), which hashes large batches on a GPU.
+    #[cfg(feature = "parallel")]
+    pub fn hash_elements_many_parallel<E: FieldElement<BaseField = Felt>>(
+        inputs: &[&[E]],
+    ) -> Vec<RpoDigest> {
+        use rayon::prelude::*;
+
+        inputs.par_iter().map(|elements| Self::hash_elements(elements)).collect()
+    }
+
+    /// Returns hashes of the provided pairs of digests, i.e., the i-th element of the result is
+    /// equal to `merge(&pairs[i])`.
+    ///
+    /// The pairs are split into chunks which are merged concurrently using rayon, and the states
+    /// within each chunk are permuted using [Self::apply_permutation_batch()]. This is intended
+    /// for computing entire levels of large Merkle trees.
+    #[cfg(feature = "parallel")]
+    pub fn merge_many_parallel(pairs: &[[RpoDigest; 2]]) -> Vec<RpoDigest> {
+        use rayon::prelude::*;
+
+        /// The number of pairs merged by a single rayon task.
+        const CHUNK_SIZE: usize = 1024;
+
+        let mut result = vec![RpoDigest::default(); pairs.len()];
+        result
+            .par_chunks_mut(CHUNK_SIZE)
+            .zip(pairs.par_chunks(CHUNK_SIZE))
+            .for_each(|(digests, pairs)| Self::merge_in_domain_batch(pairs, ZERO, digests));
+        result
+    }
+
     // RESCUE PERMUTATION
     // --------------------------------------------------------------------------------------------
 
     /// Applies RPO permutation to the provided state.
     #[inline(always)]
     pub fn apply_permutation(state: &mut [Felt; STATE_WIDTH]) {
+        #[cfg(feature = "metrics")]
+        crate::hash::metrics::record(&crate::hash::metrics::RPO256_PERMUTATIONS, 1);
         for i in 0..NUM_ROUNDS {
             Self::apply_round(state, i);
         }
     }
 
+    /// Applies RPO permutation to each of the provided states.
+    ///
+    /// The result is the same as calling [Self::apply_permutation()] on every state, but the
+    /// states are internally processed in groups of 8 using a structure-of-arrays layout, which
+    /// lets the CPU work on independent states in parallel. This makes the function well suited
+    /// for hashing many unrelated inputs at once, e.g., all nodes in a level of a Merkle tree.
+    pub fn apply_permutation_batch(states: &mut [[Felt; STATE_WIDTH]]) {
+        let mut chunks = states.chunks_exact_mut(BATCH_SIZE);
+        for chunk in &mut chunks {
+            #[cfg(feature = "metrics")]
+            crate::hash::metrics::record(
+                &crate::hash::metrics::RPO256_PERMUTATIONS,
+                BATCH_SIZE as u64,
+            );
+            let mut batch = [[ZERO; BATCH_SIZE]; STATE_WIDTH];
+            for (j, state) in chunk.iter().enumerate() {
+                for (i, &s) in state.iter().enumerate() {
+                    batch[i][j] = s;
+                }
+            }
+
+            for i in 0..NUM_ROUNDS {
+                Self::apply_round_batch(&mut batch, i);
+            }
+
+            for (j, state) in chunk.iter_mut().enumerate() {
+                for (i, s) in state.iter_mut().enumerate() {
+                    *s = batch[i][j];
+                }
+            }
+        }
+
+        // the remaining states do not fill a full batch, and thus are permuted one by one
+        for state in chunks.into_remainder() {
+            Self::apply_permutation(state);
+        }
+    }
+
+    /// Returns the states of the RPO permutation applied to the provided state at every round
+    /// boundary.
+    ///
+    /// The first element of the result is the input state, and the `i`-th element (for `i > 0`)
+    /// is the state after `i` rounds; thus, the last element is the result of
+    /// [Self::apply_permutation()]. This is intended for generating execution traces which match
+    /// this implementation exactly.
+    pub fn permutation_trace(
+        mut state: [Felt; STATE_WIDTH],
+    ) -> [[Felt; STATE_WIDTH]; NUM_ROUNDS + 1] {
+        let mut trace = [state; NUM_ROUNDS + 1];
+        for (i, row) in trace.iter_mut().enumerate().skip(1) {
+            Self::apply_round(&mut state, i - 1);
+            *row = state;
+        }
+        trace
+    }
+
     /// RPO round function.
+    ///
+    /// This is equivalent to [Self::apply_first_half_round()] followed by
+    /// [Self::apply_second_half_round()].
+    ///
+    /// # Panics
+    /// Panics if `round` is greater than or equal to [Self::NUM_ROUNDS].
     #[inline(always)]
     pub fn apply_round(state: &mut [Felt; STATE_WIDTH], round: usize) {
-        // apply first half of RPO round
+        Self::apply_first_half_round(state, round);
+        Self::apply_second_half_round(state, round);
+    }
+
+    /// First half of the RPO round function: applies the MDS matrix, adds the round constants
+    /// [Self::ARK1] for the specified round, and raises every element to the power
+    /// [Self::ALPHA].
+    ///
+    /// # Panics
+    /// Panics if `round` is greater than or equal to [Self::NUM_ROUNDS].
+    #[inline(always)]
+    pub fn apply_first_half_round(state: &mut [Felt; STATE_WIDTH], round: usize) {
         apply_mds(state);
         if !add_constants_and_apply_sbox(state, &ARK1[round]) {
             add_constants(state, &ARK1[round]);
             apply_sbox(state);
         }
+    }
 
-        // apply second half of RPO round
+    /// Second half of the RPO round function: applies the MDS matrix, adds the round constants
+    /// [Self::ARK2] for the specified round, and raises every element to the power
+    /// [Self::INV_ALPHA].
+    ///
+    /// # Panics
+    /// Panics if `round` is greater than or equal to [Self::NUM_ROUNDS].
+    #[inline(always)]
+    pub fn apply_second_half_round(state: &mut [Felt; STATE_WIDTH], round: usize) {
         apply_mds(state);
         if !add_constants_and_apply_inv_sbox(state, &ARK2[round]) {
             add_constants(state, &ARK2[round]);
             apply_inv_sbox(state);
         }
     }
+
+    /// RPO round function applied to a batch of states in a structure-of-arrays layout.
+    #[inline(always)]
+    fn apply_round_batch(state: &mut [[Felt; BATCH_SIZE]; STATE_WIDTH], round: usize) {
+        // apply first half of RPO round
+        apply_mds_batch(state);
+        add_constants_batch(state, &ARK1[round]);
+        apply_sbox_batch(state);
+
+        // apply second half of RPO round
+        apply_mds_batch(state);
+        add_constants_batch(state, &ARK2[round]);
+        apply_inv_sbox_batch(state);
+    }
 }
@@ -0,0 +1,155 @@
+use super::{
+    Felt, FieldElement, Rpo256, RpoDigest, BINARY_CHUNK_SIZE, CAPACITY_RANGE, DIGEST_RANGE, ONE,
+    RATE_RANGE, RATE_WIDTH, STATE_WIDTH, ZERO,
+};
+
+// STREAMING HASHER
+// ================================================================================================
+
+/// An incremental hasher which computes RPO256 digests of inputs supplied in multiple pieces.
+///
+/// The digest of a stream fed only via [Rpo256Stream::update()] is equal to the digest computed
+/// by [Rpo256::hash_elements()] over the concatenation of all pieces, and the digest of a stream
+/// fed only via [Rpo256Stream::update_bytes()] is equal to the digest computed by
+/// [Rpo256::hash()] over the concatenation of all pieces, regardless of how the input is split.
+/// When both methods are used on the same stream, each run of bytes is encoded into field
+/// elements in the same way as in [Rpo256::hash()], and the resulting elements are hashed as in
+/// [Rpo256::hash_elements()].
+///
+/// [Rpo256::hash_elements()] sets a flag in the capacity portion of the state depending on
+/// whether the length of the input is a multiple of the rate, which is not known until the stream
+/// is finalized. Thus, the stream keeps track of two sponge states (one for each value of the
+/// flag), which makes streaming hashing about twice as expensive as one-shot hashing.
+#[derive(Debug, Clone)]
+pub struct Rpo256Stream {
+    /// Sponge states without and with the flag set in the capacity portion.
+    states: [[Felt; STATE_WIDTH]; 2],
+    /// Index of the next element to be absorbed within the rate portion of the states.
+    rate_idx: usize,
+    /// Total number of elements absorbed into the states.
+    num_elements: u64,
+    /// Total number of bytes supplied via `update_bytes()`.
+    num_bytes: u64,
+    /// True if all input has been supplied via `update_bytes()`.
+    bytes_only: bool,
+    /// Bytes which have not yet been absorbed because they do not make up a full chunk.
+    chunk: [u8; BINARY_CHUNK_SIZE],
+    /// Number of bytes in `chunk`.
+    chunk_len: usize,
+}
+
+impl Rpo256Stream {
+    /// Returns a new [Rpo256Stream] which has not absorbed any input.
+    pub fn new() -> Self {
+        let mut states = [[ZERO; STATE_WIDTH]; 2];
+        states[1][CAPACITY_RANGE.start] = ONE;
+        Self {
+            states,
+            rate_idx: 0,
+            num_elements: 0,
+            num_bytes: 0,
+            bytes_only: true,
+            chunk: [0; BINARY_CHUNK_SIZE],
+            chunk_len: 0,
+        }
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Absorbs the provided field elements into the hasher state.
+    ///
+    /// Extension field elements are absorbed as the sequence of their base field coordinates, as
+    /// in [Rpo256::hash_elements()].
+    pub fn update<E: FieldElement<BaseField = Felt>>(&mut self, elements: &[E]) {
+        let elements = E::slice_as_base_elements(elements);
+        if elements.is_empty() {
+            return;
+        }
+        self.flush_chunk();
+        self.bytes_only = false;
+        for &element in elements {
+            self.absorb(element);
+        }
+    }
+
+    /// Absorbs the provided bytes into the hasher state.
+    ///
+    /// Bytes are encoded into field elements in chunks of 7 bytes as in [Rpo256::hash()]; a
+    /// trailing partial chunk is kept until more bytes are supplied, or until the stream is
+    /// finalized or [Rpo256Stream::update()] is called.
+    pub fn update_bytes(&mut self, mut bytes: &[u8]) {
+        self.num_bytes += bytes.len() as u64;
+        while !bytes.is_empty() {
+            let n = (BINARY_CHUNK_SIZE - self.chunk_len).min(bytes.len());
+            self.chunk[self.chunk_len..self.chunk_len + n].copy_from_slice(&bytes[..n]);
+            self.chunk_len += n;
+            bytes = &bytes[n..];
+
+            if self.chunk_len == BINARY_CHUNK_SIZE {
+                let mut buf = [0_u8; 8];
+                buf[..BINARY_CHUNK_SIZE].copy_from_slice(&self.chunk);
+                self.absorb(Felt::new(u64::from_le_bytes(buf)));
+                self.chunk_len = 0;
+            }
+        }
+    }
+
+    /// Consumes the hasher and returns the digest of all absorbed input.
+    pub fn finalize(mut self) -> RpoDigest {
+        self.flush_chunk();
+
+        // the flag is set if the input length is not a multiple of the rate; as in the one-shot
+        // functions, the length is measured in bytes for byte inputs and in elements otherwise
+        let input_len = if self.bytes_only {
+            self.num_bytes
+        } else {
+            self.num_elements
+        };
+        let state = &mut self.states[usize::from(input_len % RATE_WIDTH as u64 != 0)];
+
+        // pad the last block by appending a 1 followed by as many 0 as necessary to fill up the
+        // rate, and apply the final permutation
+        if self.rate_idx > 0 {
+            state[RATE_RANGE.start + self.rate_idx] = ONE;
+            state[RATE_RANGE.start + self.rate_idx + 1..RATE_RANGE.end].fill(ZERO);
+            Rpo256::apply_permutation(state);
+        }
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    // HELPER METHODS
+    // --------------------------------------------------------------------------------------------
+
+    /// Absorbs a single element into both states, applying the permutation once the rate is
+    /// filled up.
+    fn absorb(&mut self, element: Felt) {
+        for state in self.states.iter_mut() {
+            state[RATE_RANGE.start + self.rate_idx] = element;
+        }
+        self.rate_idx += 1;
+        self.num_elements += 1;
+        if self.rate_idx == RATE_WIDTH {
+            self.states.iter_mut().for_each(Rpo256::apply_permutation);
+            self.rate_idx = 0;
+        }
+    }
+
+    /// Pads and absorbs the trailing partial chunk of bytes, if any.
+    fn flush_chunk(&mut self) {
+        if self.chunk_len > 0 {
+            let mut buf = [0_u8; 8];
+            buf[..self.chunk_len].copy_from_slice(&self.chunk[..self.chunk_len]);
+            buf[self.chunk_len] = 1;
+            self.absorb(Felt::new(u64::from_le_bytes(buf)));
+            self.chunk_len = 0;
+        }
+    }
+}
+
+impl Default for Rpo256Stream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
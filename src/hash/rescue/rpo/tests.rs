@@ -3,9 +3,10 @@ use rand_utils::rand_value;
 
 use super::{
     super::{apply_inv_sbox, apply_sbox, ALPHA, INV_ALPHA},
-    Felt, FieldElement, Hasher, Rpo256, RpoDigest, StarkField, ONE, STATE_WIDTH, ZERO,
+    Felt, FieldElement, Hasher, LengthEncoding, Rpo256, Rpo256Stream, Rpo256Xof, RpoDigest,
+    RpoDuplex, StarkField, ONE, STATE_WIDTH, ZERO,
 };
-use crate::Word;
+use crate::{CubeExtension, QuadExtension, Word};
 use alloc::{collections::BTreeSet, vec::Vec};
 
 #[test]
@@ -75,6 +76,35 @@ fn merge_vs_merge_in_domain() {
     assert_ne!(merge_result, merge_in_domain_result);
 }
 
+#[test]
+fn hash_elements_vs_hash_elements_in_domain() {
+    let elements = [Felt::new(rand_value()); 11];
+
+    // hashing in domain 0 should be equivalent to hashing without a domain
+    for len in [0, 7, 8, 11] {
+        assert_eq!(
+            Rpo256::hash_elements(&elements[..len]),
+            Rpo256::hash_elements_in_domain(&elements[..len], ZERO)
+        );
+    }
+
+    // hashing in different domains should produce different results
+    let domain = Felt::new(42);
+    let result = Rpo256::hash_elements_in_domain(&elements, domain);
+    assert_ne!(result, Rpo256::hash_elements(&elements));
+    assert_ne!(result, Rpo256::hash_elements_in_domain(&elements, ONE));
+
+    // hashing 8 elements in a domain should be consistent with merging in the same domain
+    let digests: [RpoDigest; 2] = [
+        RpoDigest::new(elements[..4].try_into().unwrap()),
+        RpoDigest::new(elements[4..8].try_into().unwrap()),
+    ];
+    assert_eq!(
+        Rpo256::hash_elements_in_domain(&elements[..8], domain),
+        Rpo256::merge_in_domain(&digests, domain)
+    );
+}
+
 #[test]
 fn hash_elements_vs_merge_with_int() {
     let tmp = [Felt::new(rand_value()); 4];
@@ -219,11 +249,352 @@ fn sponge_zeroes_collision() {
     });
 }
 
+#[test]
+fn stream_mixed_input() {
+    let elements: Vec<Felt> = (0..11).map(Felt::new).collect();
+    let bytes = [7_u8; 10];
+
+    // bytes are encoded as in hash() and the resulting elements are hashed as in hash_elements()
+    let mut encoded = elements.clone();
+    encoded.push(Felt::new(u64::from_le_bytes([7, 7, 7, 7, 7, 7, 7, 0])));
+    encoded.push(Felt::new(u64::from_le_bytes([7, 7, 7, 1, 0, 0, 0, 0])));
+    encoded.extend_from_slice(&elements);
+
+    let mut stream = Rpo256Stream::new();
+    stream.update(&elements);
+    stream.update_bytes(&bytes[..4]);
+    stream.update_bytes(&bytes[4..]);
+    stream.update(&elements);
+    assert_eq!(stream.finalize(), Rpo256::hash_elements(&encoded));
+
+    assert_eq!(Rpo256Stream::new().finalize(), Rpo256::hash_elements::<Felt>(&[]));
+}
+
+#[test]
+#[should_panic(expected = "different number of elements")]
+fn hash_elements_exact_iter_wrong_length() {
+    // an iterator reporting one element more than it produces
+    struct WrongLength(core::ops::Range<u64>);
+
+    impl Iterator for WrongLength {
+        type Item = Felt;
+
+        fn next(&mut self) -> Option<Felt> {
+            self.0.next().map(Felt::new)
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let len = self.0.end as usize - self.0.start as usize + 1;
+            (len, Some(len))
+        }
+    }
+
+    impl ExactSizeIterator for WrongLength {}
+
+    Rpo256::hash_elements_exact_iter(WrongLength(0..8));
+}
+
+#[test]
+fn mac_keyed_output() {
+    let key: Word = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+    let other_key: Word = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(5)];
+    let data: Vec<Felt> = (0..16).map(Felt::new).collect();
+
+    let set: BTreeSet<RpoDigest> = [
+        Rpo256::mac(key, &data),
+        Rpo256::mac(other_key, &data),
+        Rpo256::mac(key, &data[..8]),
+        Rpo256::mac(key, &data[..7]),
+        Rpo256::mac(key, &[]),
+        Rpo256::hash_elements(&data),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(set.len(), 6);
+
+    // padding should separate inputs which differ only by trailing zeros
+    let mut padded = data[..7].to_vec();
+    padded.push(ZERO);
+    assert_ne!(Rpo256::mac(key, &data[..7]), Rpo256::mac(key, &padded));
+    assert_eq!(Rpo256::mac(key, &data), Rpo256::mac(key, &data));
+}
+
+#[test]
+fn merge_in_domain_many() {
+    // 21 pairs exercise both full batches and the remainder
+    let pairs: Vec<[RpoDigest; 2]> = (0..21_u64)
+        .map(|i| {
+            [
+                RpoDigest::new([Felt::new(i), ONE, ZERO, Felt::new(rand_value())]),
+                RpoDigest::new([ZERO, Felt::new(i), ONE, Felt::new(rand_value())]),
+            ]
+        })
+        .collect();
+
+    let domain = Felt::new(3);
+    let expected: Vec<RpoDigest> =
+        pairs.iter().map(|pair| Rpo256::merge_in_domain(pair, domain)).collect();
+    assert_eq!(Rpo256::merge_in_domain_many(&pairs, domain), expected);
+
+    // merging in domain 0 is equivalent to merge()
+    let expected: Vec<RpoDigest> = pairs.iter().map(Rpo256::merge).collect();
+    assert_eq!(Rpo256::merge_in_domain_many(&pairs, ZERO), expected);
+    assert!(Rpo256::merge_in_domain_many(&[], domain).is_empty());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn merge_many_parallel() {
+    let pairs: Vec<[RpoDigest; 2]> = (0..2500_u64)
+        .map(|i| {
+            [
+                RpoDigest::new([Felt::new(i), ONE, ZERO, Felt::new(rand_value())]),
+                RpoDigest::new([ZERO, Felt::new(i), ONE, Felt::new(rand_value())]),
+            ]
+        })
+        .collect();
+
+    let expected: Vec<RpoDigest> = pairs.iter().map(Rpo256::merge).collect();
+    assert_eq!(Rpo256::merge_many_parallel(&pairs), expected);
+    assert!(Rpo256::merge_many_parallel(&[]).is_empty());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn hash_elements_many_parallel() {
+    let elements: Vec<Felt> = (0..100).map(Felt::new).collect();
+    let inputs: Vec<&[Felt]> = (0..elements.len()).map(|i| &elements[..i]).collect();
+
+    let expected: Vec<RpoDigest> = inputs.iter().map(|e| Rpo256::hash_elements(e)).collect();
+    assert_eq!(Rpo256::hash_elements_many_parallel(&inputs), expected);
+}
+
+#[test]
+fn xof_output() {
+    let elements: Vec<Felt> = (0..13).map(Felt::new).collect();
+    let mut xof = Rpo256Xof::new();
+    xof.update(&elements[..5]);
+    xof.update(&elements[5..]);
+
+    // splitting reads should not change the output
+    let mut reader = xof.clone().finalize();
+    let mut output = [ZERO; 20];
+    reader.squeeze(&mut output);
+    let mut split_reader = xof.finalize();
+    let mut split_output = [ZERO; 20];
+    split_reader.squeeze(&mut split_output[..3]);
+    split_reader.squeeze(&mut split_output[3..]);
+    assert_eq!(output, split_output);
+
+    // the output should be separated from hash_elements()
+    assert_ne!(output[..4], *Rpo256::hash_elements(&elements).as_elements());
+
+    // different inputs should produce different outputs
+    let mut xof = Rpo256Xof::new();
+    xof.update(&elements[..12]);
+    let mut other_output = [ZERO; 20];
+    xof.finalize().squeeze(&mut other_output);
+    assert_ne!(output, other_output);
+
+    // bytes should be read from the little-endian encodings of the elements
+    let mut xof = Rpo256Xof::new();
+    xof.update(&elements);
+    let mut reader = xof.finalize();
+    let mut bytes = [0_u8; 20];
+    reader.squeeze_bytes(&mut bytes[..5]);
+    reader.squeeze_bytes(&mut bytes[5..]);
+    let expected: Vec<u8> = output.iter().flat_map(|e| e.as_int().to_le_bytes()).collect();
+    assert_eq!(bytes, expected[..20]);
+}
+
+#[test]
+fn hash_with_length_encoding() {
+    let elements: Vec<Felt> = (1..=11).map(Felt::new).collect();
+    let bytes: Vec<u8> = (1..=20).collect();
+
+    // the default encoding should be consistent with hash() and hash_elements()
+    assert_eq!(LengthEncoding::default(), LengthEncoding::RateFlag);
+    assert_eq!(
+        Rpo256::hash_elements_with_encoding(&elements, LengthEncoding::RateFlag),
+        Rpo256::hash_elements(&elements)
+    );
+    assert_eq!(
+        Rpo256::hash_with_encoding(&bytes, LengthEncoding::RateFlag),
+        Rpo256::hash(&bytes)
+    );
+
+    // the length encoding should bind the number of elements into the first capacity element
+    let mut state = [ZERO; STATE_WIDTH];
+    state[Rpo256::CAPACITY_RANGE.start] = Felt::new(3);
+    state[Rpo256::RATE_RANGE.start..Rpo256::RATE_RANGE.start + 3].copy_from_slice(&elements[..3]);
+    Rpo256::apply_permutation(&mut state);
+    let digest = Rpo256::hash_elements_with_encoding(&elements[..3], LengthEncoding::Length);
+    assert_eq!(*digest, state[Rpo256::DIGEST_RANGE]);
+
+    // byte strings are absorbed as 7-byte chunks, with a 1 appended to the last chunk
+    let chunks = [
+        Felt::new(u64::from_le_bytes([1, 2, 3, 4, 5, 6, 7, 0])),
+        Felt::new(u64::from_le_bytes([8, 9, 10, 11, 12, 13, 14, 1])),
+    ];
+    assert_eq!(
+        Rpo256::hash_with_encoding(&bytes[..14], LengthEncoding::Length),
+        Rpo256::hash_elements_with_encoding(&chunks, LengthEncoding::Length)
+    );
+
+    // different encodings should produce different digests (except for the empty input, which
+    // is not permuted under either encoding), and trailing zeros should matter
+    for len in [0, 3, 8, 11] {
+        let input = &elements[..len];
+        let digest = Rpo256::hash_elements_with_encoding(input, LengthEncoding::Length);
+        if len != 0 {
+            assert_ne!(digest, Rpo256::hash_elements(input));
+        }
+        let mut padded = input.to_vec();
+        padded.push(ZERO);
+        assert_ne!(digest, Rpo256::hash_elements_with_encoding(&padded, LengthEncoding::Length));
+    }
+    for len in [0, 7, 14, 20] {
+        let input = &bytes[..len];
+        let digest = Rpo256::hash_with_encoding(input, LengthEncoding::Length);
+        if len != 0 {
+            assert_ne!(digest, Rpo256::hash(input));
+        }
+        let mut padded = input.to_vec();
+        padded.push(0);
+        assert_ne!(digest, Rpo256::hash_with_encoding(&padded, LengthEncoding::Length));
+    }
+}
+
+#[test]
+fn hash_integers() {
+    let values = [0, 1, u64::MAX, Felt::MODULUS, 0x1234_5678_9abc_def0];
+    let limbs: Vec<Felt> = values
+        .iter()
+        .flat_map(|&v| [Felt::new(v & 0xffff_ffff), Felt::new(v >> 32)])
+        .collect();
+    assert_eq!(Rpo256::hash_u64s(&values), Rpo256::hash_elements(&limbs));
+    assert_ne!(Rpo256::hash_u64s(&values[..4]), Rpo256::hash_u64s(&values));
+
+    let bytes = [1_u8, 2, 3, 4, 5, 6];
+    let expected = [
+        Felt::new(6),
+        Felt::new(u32::from_le_bytes([1, 2, 3, 4]) as u64),
+        Felt::new(0x0605),
+    ];
+    assert_eq!(Rpo256::hash_u8s_packed(&bytes), Rpo256::hash_elements(&expected));
+    assert_ne!(Rpo256::hash_u8s_packed(&bytes), Rpo256::hash_u8s_packed(&[1, 2, 3, 4, 5, 6, 0]));
+    assert_ne!(Rpo256::hash_u8s_packed(&[]), Rpo256::hash_elements::<Felt>(&[]));
+}
+
+#[test]
+fn duplex_transcript() {
+    let elements: Vec<Felt> = (0..13).map(Felt::new).collect();
+    let domain = Felt::new(7);
+
+    // splitting absorbs and squeezes should not change the output
+    let mut duplex = RpoDuplex::new(domain);
+    duplex.absorb(&elements);
+    let mut output = [ZERO; 20];
+    duplex.squeeze(&mut output);
+    let mut split_duplex = RpoDuplex::new(domain);
+    split_duplex.absorb(&elements[..8]);
+    split_duplex.absorb(&elements[8..]);
+    let mut split_output = [ZERO; 20];
+    split_duplex.squeeze(&mut split_output[..3]);
+    split_duplex.squeeze(&mut split_output[3..]);
+    assert_eq!(output, split_output);
+
+    // the output should be separated from hash_elements() and from other domains
+    assert_ne!(output[..4], *Rpo256::hash_elements(&elements).as_elements());
+    let mut other = RpoDuplex::new(ONE);
+    other.absorb(&elements);
+    assert_ne!(output[..4], other.squeeze_word());
+
+    // trailing zeros should not be lost to padding
+    let mut other = RpoDuplex::new(domain);
+    other.absorb(&elements[..8]);
+    let mut padded = RpoDuplex::new(domain);
+    padded.absorb(&elements[..8]);
+    padded.absorb(&[ZERO]);
+    assert_ne!(other.squeeze_digest(), padded.squeeze_digest());
+
+    // challenges should depend on the order of absorbs and squeezes
+    let mut duplex = RpoDuplex::new(domain);
+    duplex.absorb(&elements[..5]);
+    let first = duplex.squeeze_digest();
+    duplex.absorb(&elements[5..]);
+    let second = duplex.squeeze_digest();
+    assert_ne!(*first, output[..4]);
+    assert_ne!(first, second);
+    assert_ne!(*second, output[4..8]);
+
+    // absorbing after squeezing should not be a no-op, even without input
+    let mut other = RpoDuplex::new(domain);
+    other.absorb(&elements[..5]);
+    other.squeeze_digest();
+    other.absorb::<Felt>(&[]);
+    other.absorb(&elements[5..]);
+    assert_eq!(other.squeeze_digest(), second);
+    let mut other = RpoDuplex::new(domain);
+    other.absorb(&elements[..5]);
+    other.squeeze_digest();
+    other.absorb::<Felt>(&[]);
+    assert_ne!(other.squeeze_word()[..], output[4..8]);
+}
+
 proptest! {
     #[test]
     fn rpo256_wont_panic_with_arbitrary_input(ref bytes in any::<Vec<u8>>()) {
         Rpo256::hash(bytes);
     }
+
+    #[test]
+    fn stream_matches_hash_elements(ref values in any::<Vec<u64>>(), split in any::<usize>()) {
+        let elements: Vec<Felt> = values.iter().map(|&v| Felt::new(v)).collect();
+        let split = split % (elements.len() + 1);
+
+        let mut stream = Rpo256Stream::new();
+        stream.update(&elements[..split]);
+        stream.update(&elements[split..]);
+        prop_assert_eq!(stream.finalize(), Rpo256::hash_elements(&elements));
+    }
+
+    #[test]
+    fn hash_elements_iter_matches_hash_elements(ref values in any::<Vec<u64>>()) {
+        let elements: Vec<Felt> = values.iter().map(|&v| Felt::new(v)).collect();
+        let expected = Rpo256::hash_elements(&elements);
+
+        // iterators with exact and inexact size hints
+        prop_assert_eq!(Rpo256::hash_elements_iter(elements.iter().copied()), expected);
+        prop_assert_eq!(
+            Rpo256::hash_elements_iter(elements.iter().copied().filter(|_| true)),
+            expected
+        );
+        prop_assert_eq!(Rpo256::hash_elements_exact_iter(elements.iter().copied()), expected);
+        prop_assert_eq!(Rpo256::hash_elements_exact_iter(elements.clone()), expected);
+    }
+
+    #[test]
+    fn permutation_batch_matches_permutation(ref values in prop::collection::vec(any::<[u64; STATE_WIDTH]>(), 0..20)) {
+        let states: Vec<[Felt; STATE_WIDTH]> = values.iter().map(|s| s.map(Felt::new)).collect();
+
+        let mut batch = states.clone();
+        Rpo256::apply_permutation_batch(&mut batch);
+        for (mut state, permuted) in states.into_iter().zip(batch) {
+            Rpo256::apply_permutation(&mut state);
+            prop_assert_eq!(state, permuted);
+        }
+    }
+
+    #[test]
+    fn stream_matches_hash(ref bytes in any::<Vec<u8>>(), split in any::<usize>()) {
+        let split = split % (bytes.len() + 1);
+
+        let mut stream = Rpo256Stream::new();
+        stream.update_bytes(&bytes[..split]);
+        stream.update_bytes(&bytes[split..]);
+        prop_assert_eq!(stream.finalize(), Rpo256::hash(bytes));
+    }
 }
 
 const EXPECTED: [Word; 19] = [
@@ -342,3 +713,133 @@ const EXPECTED: [Word; 19] = [
         Felt::new(16982398877290254028),
     ],
 ];
+
+#[test]
+fn hash_extension_elements() {
+    let base: Vec<Felt> = (0..18).map(|_| Felt::new(rand_value())).collect();
+    let quad: Vec<QuadExtension<Felt>> =
+        base.chunks(2).map(|c| QuadExtension::new(c[0], c[1])).collect();
+    let cube: Vec<CubeExtension<Felt>> =
+        base.chunks(3).map(|c| CubeExtension::new(c[0], c[1], c[2])).collect();
+
+    // extension elements should be hashed as the sequence of their base field coordinates
+    let expected = Rpo256::hash_elements(&base);
+    assert_eq!(Rpo256::hash_elements(&quad), expected);
+    assert_eq!(Rpo256::hash_elements(&cube), expected);
+
+    let mut stream = Rpo256Stream::new();
+    stream.update(&quad[..3]);
+    stream.update(&cube[2..]);
+    assert_eq!(stream.finalize(), expected);
+
+    let mut duplex = RpoDuplex::new(ZERO);
+    duplex.absorb(&cube);
+    let mut expected_duplex = RpoDuplex::new(ZERO);
+    expected_duplex.absorb(&base);
+    assert_eq!(duplex.squeeze_element(), expected_duplex.squeeze_element());
+}
+
+#[test]
+fn permutation_rounds() {
+    let mut state = [ZERO; STATE_WIDTH];
+    state.iter_mut().for_each(|v| *v = Felt::new(rand_value()));
+
+    // the trace should start with the input state and end with the permuted state
+    let trace = Rpo256::permutation_trace(state);
+    let mut expected = state;
+    Rpo256::apply_permutation(&mut expected);
+    assert_eq!(trace[0], state);
+    assert_eq!(trace[Rpo256::NUM_ROUNDS], expected);
+
+    // every row should be obtained from the previous one by applying one round, and every round
+    // should match a reference computation using the public constants
+    for round in 0..Rpo256::NUM_ROUNDS {
+        let mut next = trace[round];
+        Rpo256::apply_first_half_round(&mut next, round);
+        let mut reference = apply_mds_reference(&trace[round]);
+        for (i, v) in reference.iter_mut().enumerate() {
+            *v = (*v + Rpo256::ARK1[round][i]).exp(Rpo256::ALPHA);
+        }
+        assert_eq!(next, reference);
+
+        Rpo256::apply_second_half_round(&mut next, round);
+        let mut reference = apply_mds_reference(&reference);
+        for (i, v) in reference.iter_mut().enumerate() {
+            *v = (*v + Rpo256::ARK2[round][i]).exp(Rpo256::INV_ALPHA);
+        }
+        assert_eq!(next, reference);
+        assert_eq!(next, trace[round + 1]);
+    }
+}
+
+fn apply_mds_reference(state: &[Felt; STATE_WIDTH]) -> [Felt; STATE_WIDTH] {
+    let mut result = [ZERO; STATE_WIDTH];
+    for (i, r) in result.iter_mut().enumerate() {
+        for (j, &s) in state.iter().enumerate() {
+            *r += Rpo256::MDS[i][j] * s;
+        }
+    }
+    result
+}
+
+#[test]
+fn hash_into_output_buffer() {
+    let elements: Vec<Felt> = (0..19).map(|_| Felt::new(rand_value())).collect();
+    let mut out: Word = [ONE; 4];
+
+    for len in [0, 4, 8, 19] {
+        Rpo256::hash_elements_into(&elements[..len], &mut out);
+        assert_eq!(out, *Rpo256::hash_elements(&elements[..len]));
+    }
+
+    let values = [
+        RpoDigest::new(elements[..4].try_into().unwrap()),
+        RpoDigest::new(elements[4..8].try_into().unwrap()),
+    ];
+    Rpo256::merge_into(&values, &mut out);
+    assert_eq!(out, *Rpo256::merge(&values));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hash_reader() {
+    use std::io::{Error, ErrorKind, Read};
+
+    /// A reader which returns at most 5 bytes at a time and is interrupted before every read.
+    struct ChoppyReader<'a> {
+        bytes: &'a [u8],
+        interrupted: bool,
+    }
+
+    impl Read for ChoppyReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.interrupted = !self.interrupted;
+            if self.interrupted {
+                return Err(Error::from(ErrorKind::Interrupted));
+            }
+            let n = buf.len().min(self.bytes.len()).min(5);
+            buf[..n].copy_from_slice(&self.bytes[..n]);
+            self.bytes = &self.bytes[n..];
+            Ok(n)
+        }
+    }
+
+    let bytes: Vec<u8> = (0..20_000).map(|_| rand_value()).collect();
+    for len in [0, 3, 56, 100, bytes.len()] {
+        let expected = Rpo256::hash(&bytes[..len]);
+        assert_eq!(Rpo256::hash_reader(&bytes[..len]).unwrap(), expected);
+
+        let reader = ChoppyReader { bytes: &bytes[..len], interrupted: false };
+        assert_eq!(Rpo256::hash_reader(reader).unwrap(), expected);
+    }
+
+    // errors other than interruptions should be propagated
+    struct FailingReader;
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(Error::from(ErrorKind::BrokenPipe))
+        }
+    }
+    let err = Rpo256::hash_reader(FailingReader).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::BrokenPipe);
+}
@@ -0,0 +1,196 @@
+use alloc::vec::Vec;
+use std::io::{self, Read};
+
+use super::{Felt, Rpo256, RpoDigest, ZERO};
+use crate::hash::{read_chunk, READER_BUFFER_SIZE};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The number of bytes hashed into a single leaf of the tree, i.e., the size of the chunks read
+/// from the input.
+const TREE_CHUNK_SIZE: usize = READER_BUFFER_SIZE;
+
+/// The number of chunks read and hashed at once when the `parallel` feature is enabled.
+#[cfg(feature = "parallel")]
+const CHUNKS_PER_BATCH: usize = 256;
+
+/// Domain used for merging two child nodes into a parent node.
+const PARENT_DOMAIN: Felt = Felt::new(u64::from_le_bytes(*b"tree_par"));
+
+/// Domain used for binding the root of the tree to the length of the input.
+const ROOT_DOMAIN: Felt = Felt::new(u64::from_le_bytes(*b"tree_rt\0"));
+
+// TREE HASHING
+// ================================================================================================
+
+impl Rpo256 {
+    /// Returns a hash of all bytes produced by the provided reader, computed in a tree mode.
+    ///
+    /// The input is split into chunks of 4096 bytes (the last chunk may be shorter), each of which
+    /// is hashed with [hash()](Rpo256::hash). The chunk digests are the leaves of a binary tree in
+    /// which parent nodes are computed using [merge_in_domain()](Rpo256::merge_in_domain) with a
+    /// dedicated domain. As in BLAKE3, the left subtree of every node is a perfect binary tree
+    /// holding the largest possible power of two leaves. Finally, the root of the tree is merged
+    /// with the length of the input in bytes.
+    ///
+    /// The reader is consumed incrementally and only a logarithmic number of digests is kept in
+    /// memory, and thus inputs of arbitrary size can be hashed. When the `parallel` feature is
+    /// enabled, batches of chunks are hashed concurrently.
+    ///
+    /// Note that the result differs from [hash()](Rpo256::hash) applied to the same bytes.
+    ///
+    /// # Errors
+    /// Returns an error if reading from the reader fails.
+    pub fn hash_stream<R: Read>(mut reader: R) -> io::Result<RpoDigest> {
+        let mut tree = TreeBuilder::default();
+        let mut len = 0_u64;
+
+        #[cfg(not(feature = "parallel"))]
+        {
+            let mut chunk = [0_u8; TREE_CHUNK_SIZE];
+            loop {
+                let chunk_len = read_chunk(&mut reader, &mut chunk)?;
+                if chunk_len == 0 && tree.num_leaves != 0 {
+                    break;
+                }
+                tree.push(Self::hash(&chunk[..chunk_len]));
+                len += chunk_len as u64;
+                if chunk_len < TREE_CHUNK_SIZE {
+                    break;
+                }
+            }
+        }
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            let mut batch = alloc::vec![0_u8; TREE_CHUNK_SIZE * CHUNKS_PER_BATCH];
+            loop {
+                let batch_len = read_chunk(&mut reader, &mut batch)?;
+                if batch_len == 0 && tree.num_leaves != 0 {
+                    break;
+                }
+
+                // an empty input is hashed as a single empty chunk
+                let leaves: Vec<RpoDigest> = if batch_len == 0 {
+                    alloc::vec![Self::hash(&[])]
+                } else {
+                    batch[..batch_len].par_chunks(TREE_CHUNK_SIZE).map(Self::hash).collect()
+                };
+                leaves.into_iter().for_each(|leaf| tree.push(leaf));
+                len += batch_len as u64;
+                if batch_len < batch.len() {
+                    break;
+                }
+            }
+        }
+
+        let len = RpoDigest::new([Felt::new(len), ZERO, ZERO, ZERO]);
+        Ok(Self::merge_in_domain(&[tree.finalize(), len], ROOT_DOMAIN))
+    }
+}
+
+// TREE BUILDER
+// ================================================================================================
+
+/// Incrementally computes the root of a tree from a sequence of leaves.
+///
+/// The builder keeps the roots of perfect subtrees which do not have a sibling yet, ordered from
+/// the largest to the smallest subtree; the sizes of these subtrees correspond to the bits of the
+/// number of leaves pushed so far.
+#[derive(Default)]
+struct TreeBuilder {
+    subtrees: Vec<RpoDigest>,
+    num_leaves: u64,
+}
+
+impl TreeBuilder {
+    fn push(&mut self, leaf: RpoDigest) {
+        let mut node = leaf;
+        self.num_leaves += 1;
+
+        // every trailing zero of the new number of leaves completes a perfect subtree
+        for _ in 0..self.num_leaves.trailing_zeros() {
+            let left = self.subtrees.pop().expect("missing left subtree");
+            node = Rpo256::merge_in_domain(&[left, node], PARENT_DOMAIN);
+        }
+        self.subtrees.push(node);
+    }
+
+    fn finalize(mut self) -> RpoDigest {
+        let mut root = self.subtrees.pop().expect("tree has no leaves");
+        while let Some(left) = self.subtrees.pop() {
+            root = Rpo256::merge_in_domain(&[left, root], PARENT_DOMAIN);
+        }
+        root
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use std::io::{self, Read};
+
+    use super::{Felt, Rpo256, RpoDigest, PARENT_DOMAIN, ROOT_DOMAIN, TREE_CHUNK_SIZE, ZERO};
+
+    /// A reader which returns at most 7 bytes per read.
+    struct ShortReader<'a>(&'a [u8]);
+
+    impl Read for ShortReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = buf.len().min(self.0.len()).min(7);
+            buf[..n].copy_from_slice(&self.0[..n]);
+            self.0 = &self.0[n..];
+            Ok(n)
+        }
+    }
+
+    fn with_len(root: RpoDigest, len: usize) -> RpoDigest {
+        let len = RpoDigest::new([Felt::new(len as u64), ZERO, ZERO, ZERO]);
+        Rpo256::merge_in_domain(&[root, len], ROOT_DOMAIN)
+    }
+
+    #[test]
+    fn hash_stream_tree_shape() {
+        let bytes: Vec<u8> = (0..5 * TREE_CHUNK_SIZE / 2).map(|i| i as u8).collect();
+        let leaves: Vec<RpoDigest> = bytes.chunks(TREE_CHUNK_SIZE).map(Rpo256::hash).collect();
+        assert_eq!(leaves.len(), 3);
+
+        let parent = |l, r| Rpo256::merge_in_domain(&[l, r], PARENT_DOMAIN);
+        let expected = with_len(parent(parent(leaves[0], leaves[1]), leaves[2]), bytes.len());
+        assert_eq!(Rpo256::hash_stream(bytes.as_slice()).unwrap(), expected);
+
+        // the result should not depend on how the reader splits the input
+        assert_eq!(Rpo256::hash_stream(ShortReader(&bytes)).unwrap(), expected);
+
+        // inputs consisting of exactly one chunk or no bytes at all have a single leaf
+        let chunk = &bytes[..TREE_CHUNK_SIZE];
+        let expected = with_len(Rpo256::hash(chunk), TREE_CHUNK_SIZE);
+        assert_eq!(Rpo256::hash_stream(chunk).unwrap(), expected);
+        let expected = with_len(Rpo256::hash(&[]), 0);
+        assert_eq!(Rpo256::hash_stream(io::empty()).unwrap(), expected);
+    }
+
+    #[test]
+    fn hash_stream_large_input() {
+        // the number of chunks spans more than one batch when the `parallel` feature is enabled
+        let bytes: Vec<u8> = (0..300 * TREE_CHUNK_SIZE + 5).map(|i| (i % 251) as u8).collect();
+        let digest = Rpo256::hash_stream(bytes.as_slice()).unwrap();
+        assert_eq!(Rpo256::hash_stream(ShortReader(&bytes)).unwrap(), digest);
+
+        let mut builder = super::TreeBuilder::default();
+        bytes
+            .chunks(TREE_CHUNK_SIZE)
+            .for_each(|chunk| builder.push(Rpo256::hash(chunk)));
+        assert_eq!(digest, with_len(builder.finalize(), bytes.len()));
+
+        // different lengths should produce different digests
+        let digest = Rpo256::hash_stream(&bytes[..bytes.len() - 1]).unwrap();
+        assert_ne!(Rpo256::hash_stream(bytes.as_slice()).unwrap(), digest);
+    }
+}
@@ -0,0 +1,125 @@
+use super::{Felt, Rpo256, CAPACITY_RANGE, ONE, RATE_RANGE, RATE_WIDTH, STATE_WIDTH, ZERO};
+use crate::hash::XofReader;
+
+// EXTENDABLE-OUTPUT FUNCTION
+// ================================================================================================
+
+/// An extendable-output function (XOF) based on the RPO permutation.
+///
+/// The XOF absorbs field elements supplied via [Rpo256Xof::update()], and once finalized via
+/// [Rpo256Xof::finalize()] can emit an arbitrary number of field elements or bytes.
+///
+/// The input is always padded by appending a 1 followed by as many 0 as necessary to make its
+/// length a multiple of the rate, and the third capacity element is set to 1, which separates
+/// the outputs of the XOF from the outputs of [Rpo256::hash_elements()] and
+/// [Rpo256::merge_in_domain()]. Output is read from the full rate portion of the state, i.e.,
+/// 8 elements are emitted per permutation.
+#[derive(Debug, Clone)]
+pub struct Rpo256Xof {
+    state: [Felt; STATE_WIDTH],
+    rate_idx: usize,
+}
+
+impl Rpo256Xof {
+    /// Returns a new [Rpo256Xof] which has not absorbed any input.
+    pub fn new() -> Self {
+        let mut state = [ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start + 2] = ONE;
+        Self { state, rate_idx: 0 }
+    }
+
+    /// Absorbs the provided field elements into the XOF state.
+    pub fn update(&mut self, elements: &[Felt]) {
+        for &element in elements {
+            self.state[RATE_RANGE.start + self.rate_idx] = element;
+            self.rate_idx += 1;
+            if self.rate_idx == RATE_WIDTH {
+                Rpo256::apply_permutation(&mut self.state);
+                self.rate_idx = 0;
+            }
+        }
+    }
+
+    /// Pads the absorbed input and returns a reader from which the output of the XOF can be
+    /// squeezed.
+    pub fn finalize(mut self) -> Rpo256XofReader {
+        self.state[RATE_RANGE.start + self.rate_idx] = ONE;
+        self.state[RATE_RANGE.start + self.rate_idx + 1..RATE_RANGE.end].fill(ZERO);
+        Rpo256::apply_permutation(&mut self.state);
+        Rpo256XofReader {
+            state: self.state,
+            rate_idx: 0,
+            bytes: [0; 8],
+            bytes_idx: 8,
+        }
+    }
+}
+
+impl Default for Rpo256Xof {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// XOF READER
+// ================================================================================================
+
+/// A reader from which the output of an [Rpo256Xof] can be squeezed.
+///
+/// Reads continue where the previous read stopped, and thus splitting a read of field elements
+/// (or of bytes) into multiple reads does not change the output.
+#[derive(Debug, Clone)]
+pub struct Rpo256XofReader {
+    state: [Felt; STATE_WIDTH],
+    rate_idx: usize,
+    /// Bytes of the last element which have not been returned by `squeeze_bytes()` yet.
+    bytes: [u8; 8],
+    bytes_idx: usize,
+}
+
+impl Rpo256XofReader {
+    /// Fills the provided slice with the next field elements of the output.
+    ///
+    /// Any bytes of a field element which have been partially returned by
+    /// [Rpo256XofReader::squeeze_bytes()] are discarded.
+    pub fn squeeze(&mut self, output: &mut [Felt]) {
+        self.bytes_idx = self.bytes.len();
+        for element in output.iter_mut() {
+            *element = self.next_element();
+        }
+    }
+
+    /// Fills the provided slice with the next bytes of the output.
+    ///
+    /// Each field element of the output is converted into 8 bytes in little-endian order. Since
+    /// the elements are uniformly distributed over the field rather than over all 64-bit values,
+    /// the resulting bytes deviate from the uniform distribution by about 2^-32.
+    pub fn squeeze_bytes(&mut self, output: &mut [u8]) {
+        for byte in output.iter_mut() {
+            if self.bytes_idx == self.bytes.len() {
+                self.bytes = self.next_element().as_int().to_le_bytes();
+                self.bytes_idx = 0;
+            }
+            *byte = self.bytes[self.bytes_idx];
+            self.bytes_idx += 1;
+        }
+    }
+
+    /// Returns the next field element of the output, applying the permutation once the rate
+    /// portion of the state has been read.
+    fn next_element(&mut self) -> Felt {
+        if self.rate_idx == RATE_WIDTH {
+            Rpo256::apply_permutation(&mut self.state);
+            self.rate_idx = 0;
+        }
+        let element = self.state[RATE_RANGE.start + self.rate_idx];
+        self.rate_idx += 1;
+        element
+    }
+}
+
+impl XofReader for Rpo256XofReader {
+    fn squeeze_bytes(&mut self, output: &mut [u8]) {
+        Rpo256XofReader::squeeze_bytes(self, output);
+    }
+}
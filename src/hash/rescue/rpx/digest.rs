@@ -1,11 +1,17 @@
 use alloc::string::String;
-use core::{cmp::Ordering, fmt::Display, ops::Deref};
+use core::{
+    cmp::Ordering,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    ops::Deref,
+};
 
 use super::{Digest, Felt, StarkField, DIGEST_BYTES, DIGEST_SIZE, ZERO};
 use crate::{
+    hash::{digest_bytes::to_array, DigestBytes, DigestConversionError},
     rand::Randomizable,
     utils::{
-        bytes_to_hex_string, hex_to_bytes, ByteReader, ByteWriter, Deserializable,
+        bytes_to_hex_string, ct_eq, hex_to_bytes, ByteReader, ByteWriter, Deserializable,
         DeserializationError, HexParseError, Serializable,
     },
 };
@@ -42,6 +48,36 @@ impl RpxDigest {
     pub fn to_hex(&self) -> String {
         bytes_to_hex_string(self.as_bytes())
     }
+
+    /// Returns the first `M` bytes of the byte encoding of this digest.
+    ///
+    /// The byte encoding of a digest consists of the little-endian encodings of its elements,
+    /// each of which is close to uniformly distributed over 8 bytes. Thus, a truncated digest
+    /// provides about `8 * M` bits of preimage resistance and `4 * M` bits of collision
+    /// resistance, capped at the 128-bit collision resistance of the full digest. For example,
+    /// truncating to 20 bytes leaves 80 bits of collision resistance.
+    ///
+    /// # Panics
+    /// Panics if `M` is greater than 32.
+    pub fn truncate<const M: usize>(&self) -> [u8; M] {
+        assert!(M <= DIGEST_BYTES, "a digest cannot be truncated to more than 32 bytes");
+        let mut result = [0_u8; M];
+        result.copy_from_slice(&self.as_bytes()[..M]);
+        result
+    }
+
+    /// Returns the elements of this digest as canonical 64-bit integers.
+    pub fn to_u64s(&self) -> [u64; DIGEST_SIZE] {
+        self.into()
+    }
+
+    /// Returns true if this digest is equal to the other digest.
+    ///
+    /// Unlike `==`, the running time of the comparison does not depend on the contents of the
+    /// digests, which makes it suitable for comparing digests derived from secret data.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq(&self.0, &other.0)
+    }
 }
 
 impl Digest for RpxDigest {
@@ -94,6 +130,16 @@ impl PartialOrd for RpxDigest {
     }
 }
 
+impl Hash for RpxDigest {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // as in `Ord`, we use `Felt::inner` to avoid performing a montgomery reduction for every
+        // limb; this is consistent with `Eq`, which also compares the inner values.
+        for element in self.0.iter() {
+            element.inner().hash(state);
+        }
+    }
+}
+
 impl Display for RpxDigest {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let encoded: String = self.into();
@@ -178,6 +224,28 @@ impl From<&RpxDigest> for String {
     }
 }
 
+// CONVERSIONS: FROM OTHER DIGESTS
+// ================================================================================================
+
+impl RpxDigest {
+    /// Returns a digest built from the byte representation of the provided 32-byte digest of any
+    /// hash function, e.g., a [Blake3Digest](crate::hash::blake::Blake3Digest).
+    ///
+    /// The bytes are split into four 8-byte chunks, and every chunk is read as a little-endian
+    /// integer and reduced modulo the field modulus. Unlike
+    /// [DigestBytes::try_into_digest()], this conversion accepts any 32 bytes, but it is not
+    /// injective.
+    ///
+    /// # Errors
+    /// Returns an error if the byte representation of `digest` does not consist of 32 bytes.
+    pub fn from_digest_reduced<D: DigestBytes>(digest: &D) -> Result<Self, DigestConversionError> {
+        let bytes: [u8; DIGEST_BYTES] = to_array(&digest.as_bytes()[..D::NUM_BYTES])?;
+        Ok(Self(core::array::from_fn(|i| {
+            Felt::new(u64::from_le_bytes(bytes[i * 8..(i + 1) * 8].try_into().unwrap()))
+        })))
+    }
+}
+
 // CONVERSIONS: TO RPX DIGEST
 // ================================================================================================
 
@@ -213,6 +213,10 @@ impl Rpx256 {
     // CONSTANTS
     // --------------------------------------------------------------------------------------------
 
+    /// The number of rounds is set to 7: three (FB) rounds, three (E) rounds and one final (M)
+    /// round.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
     /// Sponge state is set to 12 field elements or 768 bytes; 8 elements are reserved for rate and
     /// the remaining 4 elements are reserved for capacity.
     pub const STATE_WIDTH: usize = STATE_WIDTH;
@@ -252,6 +256,9 @@ impl Rpx256 {
     }
 
     /// Returns a hash of the provided field elements.
+    ///
+    /// Extension field elements are hashed as the sequence of their base field coordinates; the
+    /// coordinates are read in place, without copying the input into a vector of base elements.
     #[inline(always)]
     pub fn hash_elements<E: FieldElement<BaseField = Felt>>(elements: &[E]) -> RpxDigest {
         <Self as ElementHasher>::hash_elements(elements)
@@ -285,6 +292,8 @@ impl Rpx256 {
     /// Applies RPX permutation to the provided state.
     #[inline(always)]
     pub fn apply_permutation(state: &mut [Felt; STATE_WIDTH]) {
+        #[cfg(feature = "metrics")]
+        crate::hash::metrics::record(&crate::hash::metrics::RPX256_PERMUTATIONS, 1);
         Self::apply_fb_round(state, 0);
         Self::apply_ext_round(state, 1);
         Self::apply_fb_round(state, 2);
@@ -0,0 +1,109 @@
+use winter_crypto::hashers::Rp64_256;
+
+use super::{rpo::RpoDigest, ElementHasher, Felt, FieldElement, Hasher};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// Sponge state is set to 12 field elements; 8 elements are reserved for rate and the remaining 4
+/// elements are reserved for capacity.
+pub const STATE_WIDTH: usize = 12;
+
+// RESCUE PRIME HASHER
+// ================================================================================================
+
+/// Implementation of the [Hasher] trait for the original Rescue Prime hash function with 256-bit
+/// output.
+///
+/// This is the hash function which preceded [Rpo256](super::rpo::Rpo256), and which is available
+/// in winter-crypto as `Rp64_256`; it is provided so that digests produced by older systems can
+/// be recomputed and migrated. It uses the Rescue Prime round function and round constants (7
+/// rounds over a state of 12 elements, as in RPO), and binds the number of input elements into the
+/// first capacity element instead of padding the input (see
+/// [LengthEncoding::Length](super::rpo::LengthEncoding::Length)).
+///
+/// Digests are returned as [RpoDigest] values, since they have the same shape, so that they can
+/// be stored, serialized and compared using the same tooling. Note, however, that a Rescue Prime
+/// digest and an RPO digest of the same input are unrelated.
+///
+/// New systems should use [Rpo256](super::rpo::Rpo256) instead.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct RescuePrime256();
+
+impl Hasher for RescuePrime256 {
+    /// Rescue Prime collision resistance is 128-bits.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = RpoDigest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        from_rp_digest(Rp64_256::hash(bytes))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        from_rp_digest(Rp64_256::merge(&[to_rp_digest(values[0]), to_rp_digest(values[1])]))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        from_rp_digest(Rp64_256::merge_with_int(to_rp_digest(seed), value))
+    }
+}
+
+impl ElementHasher for RescuePrime256 {
+    type BaseField = Felt;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        from_rp_digest(Rp64_256::hash_elements(elements))
+    }
+}
+
+impl RescuePrime256 {
+    /// Returns a hash of the provided sequence of bytes.
+    ///
+    /// The bytes are hashed exactly as `Rp64_256` hashes them, including its quirks: inputs of at
+    /// most 56 bytes (8 field elements) are padded with a single `1` byte, while longer inputs are
+    /// not padded at all.
+    ///
+    /// # Panics
+    /// Panics if the input is longer than 56 bytes and its length is not a multiple of 7, since
+    /// `Rp64_256` cannot hash such inputs and thus no legacy digests of them exist.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> RpoDigest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    #[inline(always)]
+    pub fn merge(values: &[RpoDigest; 2]) -> RpoDigest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E: FieldElement<BaseField = Felt>>(elements: &[E]) -> RpoDigest {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+
+    /// Applies the Rescue Prime permutation to the provided state.
+    #[inline(always)]
+    pub fn apply_permutation(state: &mut [Felt; STATE_WIDTH]) {
+        Rp64_256::apply_permutation(state);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+type RpDigest = <Rp64_256 as Hasher>::Digest;
+
+fn to_rp_digest(digest: RpoDigest) -> RpDigest {
+    RpDigest::from(<[Felt; 4]>::from(digest))
+}
+
+fn from_rp_digest(digest: RpDigest) -> RpoDigest {
+    RpoDigest::new(digest.into())
+}
@@ -0,0 +1,68 @@
+use rand_utils::rand_vector;
+
+use super::*;
+use crate::hash::{rpo::Rpo256, Digest};
+use alloc::vec::Vec;
+
+#[test]
+fn rescue_prime_permutation() {
+    let mut state: [Felt; STATE_WIDTH] = core::array::from_fn(|i| Felt::new(i as u64));
+    RescuePrime256::apply_permutation(&mut state);
+
+    let expected = [
+        Felt::new(11084501481526603421),
+        Felt::new(6291559951628160880),
+        Felt::new(13626645864671311919),
+        Felt::new(18397438323058963117),
+        Felt::new(7443014167353970324),
+        Felt::new(17930833023906771425),
+        Felt::new(4275355080008025761),
+        Felt::new(7676681476902901785),
+        Felt::new(3460534574143792217),
+        Felt::new(11912731278641497187),
+        Felt::new(8104899243369883110),
+        Felt::new(674509706691634438),
+    ];
+    assert_eq!(expected, state);
+}
+
+#[test]
+fn rescue_prime_matches_rp64_256() {
+    // Rp64_256 can only hash byte strings longer than 56 bytes if their length is a multiple of 7
+    assert_eq!(RescuePrime256::hash(&[]).as_bytes(), Rp64_256::hash(&[]).as_bytes());
+    for len in [1, 13, 56, 70] {
+        let bytes = rand_vector::<u8>(len);
+        assert_eq!(RescuePrime256::hash(&bytes).as_bytes(), Rp64_256::hash(&bytes).as_bytes());
+    }
+
+    let elements = rand_vector::<Felt>(17);
+    assert_eq!(
+        RescuePrime256::hash_elements(&elements).as_bytes(),
+        Rp64_256::hash_elements(&elements).as_bytes()
+    );
+}
+
+#[test]
+fn rescue_prime_merge() {
+    let elements = rand_vector::<Felt>(8);
+    let digests = [
+        RpoDigest::new(elements[..4].try_into().unwrap()),
+        RpoDigest::new(elements[4..].try_into().unwrap()),
+    ];
+
+    // merging two digests is the same as hashing their 8 elements
+    assert_eq!(RescuePrime256::merge(&digests), RescuePrime256::hash_elements(&elements));
+
+    let seed = RescuePrime256::hash(b"seed");
+    assert_eq!(
+        RescuePrime256::merge_with_int(seed, 42).as_bytes(),
+        Rp64_256::merge_with_int(Rp64_256::hash(b"seed"), 42).as_bytes()
+    );
+}
+
+#[test]
+fn rescue_prime_differs_from_rpo() {
+    let elements: Vec<Felt> = rand_vector::<Felt>(9);
+    assert_ne!(RescuePrime256::hash_elements(&elements), Rpo256::hash_elements(&elements));
+    assert_ne!(RescuePrime256::hash(b"miden"), Rpo256::hash(b"miden"));
+}
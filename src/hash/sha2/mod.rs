@@ -0,0 +1,90 @@
+use ::sha2::Digest as _;
+
+use super::{byte_digest::byte_digest, ElementHasher, Felt, FieldElement, Hasher};
+
+#[cfg(test)]
+mod tests;
+
+// SHA256 DIGEST
+// ================================================================================================
+
+byte_digest! {
+    /// 32-bytes output of the SHA-256 hash function.
+    Sha256Digest
+}
+
+// SHA256 HASHER
+// ================================================================================================
+
+/// SHA-256 hasher.
+///
+/// Two digests are merged by hashing their concatenation. Field elements are encoded as the
+/// little-endian 8-byte representations of their canonical values, i.e., a sequence of n elements
+/// is hashed as the SHA-256 digest of the corresponding 8n bytes. This makes commitments computed
+/// with this hasher reproducible with any standard SHA-256 implementation.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Sha256;
+
+impl Hasher for Sha256 {
+    /// SHA-256 collision resistance is 128-bits for 32-bytes output.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = Sha256Digest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        Sha256Digest(::sha2::Sha256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        let mut hasher = ::sha2::Sha256::new();
+        hasher.update(values[0].0);
+        hasher.update(values[1].0);
+        Sha256Digest(hasher.finalize().into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut hasher = ::sha2::Sha256::new();
+        hasher.update(seed.0);
+        hasher.update(value.to_le_bytes());
+        Sha256Digest(hasher.finalize().into())
+    }
+}
+
+impl ElementHasher for Sha256 {
+    type BaseField = Felt;
+
+    fn hash_elements<E>(elements: &[E]) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let mut hasher = ::sha2::Sha256::new();
+        for element in E::slice_as_base_elements(elements) {
+            hasher.update(element.as_int().to_le_bytes());
+        }
+        Sha256Digest(hasher.finalize().into())
+    }
+}
+
+impl Sha256 {
+    /// Returns a hash of the provided sequence of bytes.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> Sha256Digest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    #[inline(always)]
+    pub fn merge(values: &[Sha256Digest; 2]) -> Sha256Digest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E>(elements: &[E]) -> Sha256Digest
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+}
@@ -0,0 +1,13 @@
+use super::*;
+use crate::utils::hex_to_bytes;
+
+#[test]
+fn sha256_test_vectors() {
+    let expected: [u8; 32] =
+        hex_to_bytes("0xe3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855").unwrap();
+    assert_eq!(Sha256::hash(b""), Sha256Digest::from(expected));
+
+    let expected: [u8; 32] =
+        hex_to_bytes("0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad").unwrap();
+    assert_eq!(Sha256::hash(b"abc"), Sha256Digest::from(expected));
+}
@@ -0,0 +1,313 @@
+use core::fmt;
+
+use ::sha3::{
+    digest::{ExtendableOutput, Update, XofReader as _},
+    Digest as _,
+};
+
+use super::{byte_digest::byte_digest, ElementHasher, Felt, FieldElement, Hasher, Xof, XofReader};
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+const DIGEST_BYTES: usize = 32;
+
+// SHA3 DIGEST
+// ================================================================================================
+
+byte_digest! {
+    /// 32-bytes output of the SHA3-256 hash function, or the first 32 bytes of the output of the
+    /// SHAKE128 and SHAKE256 extendable-output functions.
+    Sha3Digest
+}
+
+// SHA3-256 HASHER
+// ================================================================================================
+
+/// SHA3-256 hasher.
+///
+/// Two digests are merged by hashing their concatenation. Field elements are encoded as the
+/// little-endian 8-byte representations of their canonical values, i.e., a sequence of n elements
+/// is hashed as the SHA3-256 digest of the corresponding 8n bytes.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Sha3_256;
+
+impl Hasher for Sha3_256 {
+    /// SHA3-256 collision resistance is 128-bits for 32-bytes output.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = Sha3Digest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        Sha3Digest(::sha3::Sha3_256::digest(bytes).into())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        let mut hasher = ::sha3::Sha3_256::new();
+        ::sha3::Digest::update(&mut hasher, values[0].0);
+        ::sha3::Digest::update(&mut hasher, values[1].0);
+        Sha3Digest(hasher.finalize().into())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        let mut hasher = ::sha3::Sha3_256::new();
+        ::sha3::Digest::update(&mut hasher, seed.0);
+        ::sha3::Digest::update(&mut hasher, value.to_le_bytes());
+        Sha3Digest(hasher.finalize().into())
+    }
+}
+
+impl ElementHasher for Sha3_256 {
+    type BaseField = Felt;
+
+    fn hash_elements<E>(elements: &[E]) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let mut hasher = ::sha3::Sha3_256::new();
+        for element in E::slice_as_base_elements(elements) {
+            ::sha3::Digest::update(&mut hasher, element.as_int().to_le_bytes());
+        }
+        Sha3Digest(hasher.finalize().into())
+    }
+}
+
+impl Sha3_256 {
+    /// Returns a hash of the provided sequence of bytes.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> Sha3Digest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    #[inline(always)]
+    pub fn merge(values: &[Sha3Digest; 2]) -> Sha3Digest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E>(elements: &[E]) -> Sha3Digest
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+}
+
+// SHAKE128 HASHER
+// ================================================================================================
+
+/// SHAKE128 extendable-output function.
+///
+/// When used as a [Hasher], the digest consists of the first 32 bytes of the output. Two digests
+/// are merged by hashing their concatenation, and field elements are encoded as the little-endian
+/// 8-byte representations of their canonical values. An output of arbitrary length can be read
+/// via [Xof::hash_xof()].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Shake128;
+
+impl Hasher for Shake128 {
+    /// SHAKE128 collision resistance is 128-bits for 32-bytes output.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = Sha3Digest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        shake_digest(Self::hash_xof(bytes))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        shake_digest(Self::hash_xof_many(&[&values[0].0, &values[1].0]))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        shake_digest(Self::hash_xof_many(&[&seed.0, &value.to_le_bytes()]))
+    }
+}
+
+impl ElementHasher for Shake128 {
+    type BaseField = Felt;
+
+    fn hash_elements<E>(elements: &[E]) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let mut hasher = ::sha3::Shake128::default();
+        for element in E::slice_as_base_elements(elements) {
+            hasher.update(&element.as_int().to_le_bytes());
+        }
+        shake_digest(Shake128XofReader(hasher.finalize_xof()))
+    }
+}
+
+impl Xof for Shake128 {
+    type Reader = Shake128XofReader;
+
+    fn hash_xof_many(inputs: &[&[u8]]) -> Self::Reader {
+        let mut hasher = ::sha3::Shake128::default();
+        for input in inputs {
+            hasher.update(input);
+        }
+        Shake128XofReader(hasher.finalize_xof())
+    }
+}
+
+impl Shake128 {
+    /// Returns a hash of the provided sequence of bytes.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> Sha3Digest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    #[inline(always)]
+    pub fn merge(values: &[Sha3Digest; 2]) -> Sha3Digest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E>(elements: &[E]) -> Sha3Digest
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+}
+
+/// Reader for the extendable output of SHAKE128.
+///
+/// Consecutive reads return consecutive parts of the output, and thus the output does not depend
+/// on how it is split into reads.
+#[derive(Clone)]
+pub struct Shake128XofReader(::sha3::Shake128Reader);
+
+impl fmt::Debug for Shake128XofReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shake128XofReader").finish_non_exhaustive()
+    }
+}
+
+impl XofReader for Shake128XofReader {
+    fn squeeze_bytes(&mut self, output: &mut [u8]) {
+        self.0.read(output);
+    }
+}
+
+// SHAKE256 HASHER
+// ================================================================================================
+
+/// SHAKE256 extendable-output function.
+///
+/// When used as a [Hasher], the digest consists of the first 32 bytes of the output. Two digests
+/// are merged by hashing their concatenation, and field elements are encoded as the little-endian
+/// 8-byte representations of their canonical values. An output of arbitrary length can be read
+/// via [Xof::hash_xof()]; this is the XOF used by the Falcon reference to hash messages to
+/// points.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Shake256;
+
+impl Hasher for Shake256 {
+    /// SHAKE256 collision resistance is 128-bits for 32-bytes output.
+    const COLLISION_RESISTANCE: u32 = 128;
+
+    type Digest = Sha3Digest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        shake_digest(Self::hash_xof(bytes))
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        shake_digest(Self::hash_xof_many(&[&values[0].0, &values[1].0]))
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        shake_digest(Self::hash_xof_many(&[&seed.0, &value.to_le_bytes()]))
+    }
+}
+
+impl ElementHasher for Shake256 {
+    type BaseField = Felt;
+
+    fn hash_elements<E>(elements: &[E]) -> Self::Digest
+    where
+        E: FieldElement<BaseField = Self::BaseField>,
+    {
+        let mut hasher = ::sha3::Shake256::default();
+        for element in E::slice_as_base_elements(elements) {
+            hasher.update(&element.as_int().to_le_bytes());
+        }
+        shake_digest(Shake256XofReader(hasher.finalize_xof()))
+    }
+}
+
+impl Xof for Shake256 {
+    type Reader = Shake256XofReader;
+
+    fn hash_xof_many(inputs: &[&[u8]]) -> Self::Reader {
+        let mut hasher = ::sha3::Shake256::default();
+        for input in inputs {
+            hasher.update(input);
+        }
+        Shake256XofReader(hasher.finalize_xof())
+    }
+}
+
+impl Shake256 {
+    /// Returns a hash of the provided sequence of bytes.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> Sha3Digest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    #[inline(always)]
+    pub fn merge(values: &[Sha3Digest; 2]) -> Sha3Digest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E>(elements: &[E]) -> Sha3Digest
+    where
+        E: FieldElement<BaseField = Felt>,
+    {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+}
+
+/// Reader for the extendable output of SHAKE256.
+///
+/// Consecutive reads return consecutive parts of the output, and thus the output does not depend
+/// on how it is split into reads.
+#[derive(Clone)]
+pub struct Shake256XofReader(::sha3::Shake256Reader);
+
+impl fmt::Debug for Shake256XofReader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shake256XofReader").finish_non_exhaustive()
+    }
+}
+
+impl XofReader for Shake256XofReader {
+    fn squeeze_bytes(&mut self, output: &mut [u8]) {
+        self.0.read(output);
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the first 32 bytes of the provided XOF output as a digest.
+fn shake_digest<R: XofReader>(mut reader: R) -> Sha3Digest {
+    let mut digest = [0_u8; DIGEST_BYTES];
+    reader.squeeze_bytes(&mut digest);
+    Sha3Digest(digest)
+}
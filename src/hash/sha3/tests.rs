@@ -0,0 +1,65 @@
+use rand_utils::rand_vector;
+
+use super::*;
+use crate::utils::hex_to_bytes;
+
+#[test]
+fn sha3_256_test_vectors() {
+    let expected: [u8; 32] =
+        hex_to_bytes("0xa7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a").unwrap();
+    assert_eq!(Sha3_256::hash(b""), Sha3Digest::from(expected));
+
+    let expected: [u8; 32] =
+        hex_to_bytes("0x3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532").unwrap();
+    assert_eq!(Sha3_256::hash(b"abc"), Sha3Digest::from(expected));
+}
+
+#[test]
+fn shake_test_vectors() {
+    let expected: [u8; 32] =
+        hex_to_bytes("0x7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26").unwrap();
+    assert_eq!(Shake128::hash(b""), Sha3Digest::from(expected));
+
+    let expected: [u8; 32] =
+        hex_to_bytes("0x5881092dd818bf5cf8a3ddb793fbcba74097d5c526a6d35f97b83351940f2cc8").unwrap();
+    assert_eq!(Shake128::hash(b"abc"), Sha3Digest::from(expected));
+
+    let expected: [u8; 32] =
+        hex_to_bytes("0x46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762f").unwrap();
+    assert_eq!(Shake256::hash(b""), Sha3Digest::from(expected));
+
+    let expected: [u8; 32] =
+        hex_to_bytes("0x483366601360a8771c6863080cc4114d8db44530f8f1e1ee4f94ea37e78b5739").unwrap();
+    assert_eq!(Shake256::hash(b"abc"), Sha3Digest::from(expected));
+}
+
+#[test]
+fn shake_xof() {
+    let bytes = rand_vector::<u8>(100);
+
+    // the first 32 bytes of the output are equal to the digest
+    let mut output = [0_u8; 200];
+    Shake256::hash_xof(&bytes).squeeze_bytes(&mut output);
+    assert_eq!(output[..32], *Shake256::hash(&bytes));
+
+    // splitting the output into multiple reads does not change it
+    let mut reader = Shake256::hash_xof(&bytes);
+    let mut chunked = [0_u8; 200];
+    for chunk in chunked.chunks_mut(7) {
+        reader.squeeze_bytes(chunk);
+    }
+    assert_eq!(chunked, output);
+
+    // hashing multiple inputs is the same as hashing their concatenation
+    let mut parts = [0_u8; 200];
+    Shake256::hash_xof_many(&[&bytes[..30], &[], &bytes[30..]]).squeeze_bytes(&mut parts);
+    assert_eq!(parts, output);
+
+    let mut output = [0_u8; 200];
+    Shake128::hash_xof(&bytes).squeeze_bytes(&mut output);
+    assert_eq!(output[..32], *Shake128::hash(&bytes));
+
+    let mut parts = [0_u8; 200];
+    Shake128::hash_xof_many(&[&bytes[..30], &bytes[30..]]).squeeze_bytes(&mut parts);
+    assert_eq!(parts, output);
+}
@@ -0,0 +1,106 @@
+use alloc::string::String;
+use core::ops::Deref;
+
+use super::{Digest, Felt, StarkField, DIGEST_SIZE, ZERO};
+use crate::utils::{
+    bytes_to_hex_string, ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable,
+};
+
+// CONSTANTS
+// ================================================================================================
+
+/// The number of bytes needed to serialize a digest, i.e., 8 bytes for each of its elements.
+pub const DIGEST_BYTES: usize = DIGEST_SIZE * 8;
+
+// TIP5 DIGEST
+// ================================================================================================
+
+/// A digest of the [Tip5](super::Tip5) hash function, which consists of 5 field elements.
+///
+/// Since the [Digest] trait limits the byte representation of a digest to 32 bytes,
+/// [Digest::as_bytes()] returns only the first 32 of the 40 bytes of a serialized digest, i.e.,
+/// the encodings of the first 4 elements. The full digest is retained by serialization and by
+/// [to_bytes()](Tip5Digest::to_bytes).
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct Tip5Digest([Felt; DIGEST_SIZE]);
+
+impl Tip5Digest {
+    pub const fn new(value: [Felt; DIGEST_SIZE]) -> Self {
+        Self(value)
+    }
+
+    pub fn as_elements(&self) -> &[Felt] {
+        &self.0
+    }
+
+    /// Returns the little-endian encodings of the elements of this digest.
+    pub fn to_bytes(&self) -> [u8; DIGEST_BYTES] {
+        let mut result = [0; DIGEST_BYTES];
+        for (chunk, element) in result.chunks_exact_mut(8).zip(self.0.iter()) {
+            chunk.copy_from_slice(&element.as_int().to_le_bytes());
+        }
+        result
+    }
+
+    /// Returns hexadecimal representation of this digest prefixed with `0x`.
+    pub fn to_hex(&self) -> String {
+        bytes_to_hex_string(self.to_bytes())
+    }
+}
+
+impl Digest for Tip5Digest {
+    fn as_bytes(&self) -> [u8; 32] {
+        let mut result = [0; 32];
+        result.copy_from_slice(&self.to_bytes()[..32]);
+        result
+    }
+}
+
+impl Deref for Tip5Digest {
+    type Target = [Felt; DIGEST_SIZE];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+// CONVERSIONS
+// ================================================================================================
+
+impl From<[Felt; DIGEST_SIZE]> for Tip5Digest {
+    fn from(value: [Felt; DIGEST_SIZE]) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Tip5Digest> for [Felt; DIGEST_SIZE] {
+    fn from(value: Tip5Digest) -> Self {
+        value.0
+    }
+}
+
+// SERIALIZATION / DESERIALIZATION
+// ================================================================================================
+
+impl Serializable for Tip5Digest {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        target.write_bytes(&self.to_bytes());
+    }
+}
+
+impl Deserializable for Tip5Digest {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let mut inner: [Felt; DIGEST_SIZE] = [ZERO; DIGEST_SIZE];
+        for inner in inner.iter_mut() {
+            let e = source.read_u64()?;
+            if e >= Felt::MODULUS {
+                return Err(DeserializationError::InvalidValue(String::from(
+                    "Value not in the appropriate range",
+                )));
+            }
+            *inner = Felt::new(e);
+        }
+
+        Ok(Self(inner))
+    }
+}
@@ -0,0 +1,437 @@
+use core::ops::Range;
+
+use super::{Digest, ElementHasher, Felt, FieldElement, Hasher, StarkField, ONE, ZERO};
+
+mod digest;
+pub use digest::Tip5Digest;
+
+#[cfg(test)]
+mod tests;
+
+// CONSTANTS
+// ================================================================================================
+
+/// The number of rounds is set to 5, which is the number of rounds required by the
+/// [specifications](https://eprint.iacr.org/2023/107) for 160-bit security.
+const NUM_ROUNDS: usize = 5;
+
+/// Sponge state is set to 16 field elements; 10 elements are reserved for rate and the remaining
+/// 6 elements are reserved for capacity.
+const STATE_WIDTH: usize = 16;
+
+/// The number of elements of the state to which the split-and-lookup S-Box is applied; the
+/// power map S-Box is applied to the remaining elements.
+const NUM_SPLIT_AND_LOOKUP: usize = 4;
+
+/// The rate portion of the state is located in elements 0 through 9.
+const RATE_RANGE: Range<usize> = 0..10;
+const RATE_WIDTH: usize = RATE_RANGE.end - RATE_RANGE.start;
+
+/// The capacity portion of the state is located in elements 10 through 15.
+const CAPACITY_RANGE: Range<usize> = 10..16;
+
+/// The output of the hash function is a digest which consists of 5 field elements or 40 bytes.
+///
+/// The digest is returned from state elements 0 through 4 (the first half of the rate portion).
+const DIGEST_RANGE: Range<usize> = 0..5;
+const DIGEST_SIZE: usize = DIGEST_RANGE.end - DIGEST_RANGE.start;
+
+/// The number of bytes needed to encoded a field element when hashing a byte string.
+const BINARY_CHUNK_SIZE: usize = 7;
+
+// HASHER IMPLEMENTATION
+// ================================================================================================
+
+/// Implementation of the Tip5 hash function with 320-bit output.
+///
+/// The hash function is instantiated as described in the [specifications](https://eprint.iacr.org/2023/107).
+/// The parameters used to instantiate the function are:
+/// * Field: 64-bit prime field with modulus 2^64 - 2^32 + 1.
+/// * State width: 16 field elements.
+/// * Capacity size: 6 field elements.
+/// * Number of rounds: 5.
+/// * Round function: the S-Box layer, followed by the multiplication of the state by a circulant
+///   MDS matrix and the addition of the round constants, where the S-Box layer:
+///   - decomposes each of the first 4 elements into the 8 bytes of its Montgomery representation
+///     and maps each byte `x` to `(x + 1)^3 - 1 mod 257` (split-and-lookup);
+///   - raises each of the remaining 12 elements to the power 7.
+///
+/// As in the specifications, variable-length inputs are absorbed by overwriting the rate portion
+/// of the state, starting from a zero capacity, while fixed-length inputs of 10 elements (i.e.,
+/// pairs of digests) are permuted once with a capacity set to all ones.
+///
+/// The round constants are derived from BLAKE3 as described in [Tip5::ROUND_CONSTANTS]. No
+/// reference test vectors are available for this instance of Tip5 in this crate, and thus
+/// compatibility with other implementations is not verified.
+///
+/// The above parameters target a 160-bit security level. The digest consists of five field elements
+/// and it can be serialized into 40 bytes (320 bits), and thus digests are returned as [Tip5Digest]
+/// values rather than RPO digests.
+///
+/// ## Hash output consistency
+/// Unlike [Rpo256](super::rpo::Rpo256), [merge()](Tip5::merge) is not consistent with
+/// [hash_elements()](Tip5::hash_elements): merging two digests is domain separated from hashing
+/// the 10 elements which make up these digests by the capacity, which is set to all ones.
+///
+/// Similarly to [Rpo256](super::rpo::Rpo256), [hash()](Tip5::hash) function is not consistent
+/// with [hash_elements()](Tip5::hash_elements), since it needs to be able to handle arbitrary
+/// binary strings, which may or may not encode valid field elements.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Tip5();
+
+impl Hasher for Tip5 {
+    /// Tip5 collision resistance is 160-bits.
+    const COLLISION_RESISTANCE: u32 = 160;
+
+    type Digest = Tip5Digest;
+
+    fn hash(bytes: &[u8]) -> Self::Digest {
+        // initialize the state with zeroes, except for the first capacity element, which is set
+        // to the number of bytes encoded by a field element to domain separate hashing of `[u8]`
+        // from hashing of `[Felt]` and from merging.
+        let mut state = [ZERO; STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = Felt::new(BINARY_CHUNK_SIZE as u64);
+
+        // determine the number of field elements needed to encode `bytes` when each field element
+        // represents at most 7 bytes; an empty byte string is encoded as a single padded empty
+        // chunk.
+        let num_field_elem = bytes.len().div_ceil(BINARY_CHUNK_SIZE).max(1);
+
+        // create a field element from each chunk of bytes; the last chunk is padded with a 1
+        // followed by as many 0's as are needed to fill an element, so that the encoding is
+        // injective given the number of elements, which is in turn fixed by the padding applied
+        // when absorbing the elements.
+        let empty_chunk = core::iter::once(&[][..]).filter(|_| bytes.is_empty());
+        let chunks = empty_chunk.chain(bytes.chunks(BINARY_CHUNK_SIZE)).enumerate();
+        let elements = chunks.map(|(chunk_idx, chunk)| {
+            let mut buf = [0_u8; 8];
+            buf[..chunk.len()].copy_from_slice(chunk);
+            if chunk_idx == num_field_elem - 1 {
+                buf[chunk.len()] = 1;
+            }
+
+            // since we take at most 7 bytes, we are guaranteed that the inputs data will fit
+            // into a single field element.
+            Felt::new(u64::from_le_bytes(buf))
+        });
+
+        Self::absorb(state, elements)
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // the two digests fill up the rate portion of the state, and the capacity is set to ones
+        // to domain separate merging from hashing of variable-length inputs.
+        let mut state = [ONE; STATE_WIDTH];
+        state[..DIGEST_SIZE].copy_from_slice(values[0].as_elements());
+        state[DIGEST_SIZE..RATE_RANGE.end].copy_from_slice(values[1].as_elements());
+        Self::apply_permutation(&mut state);
+
+        Tip5Digest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge_with_int(seed: Self::Digest, value: u64) -> Self::Digest {
+        // hash the seed followed by the value; if the value doesn't fit into a single field
+        // element, it is split into two field elements. inputs of 6 and 7 elements are told apart
+        // by the padding applied by hash_elements().
+        let mut elements = [ZERO; DIGEST_SIZE + 2];
+        elements[..DIGEST_SIZE].copy_from_slice(seed.as_elements());
+        elements[DIGEST_SIZE] = Felt::new(value);
+        if value < Felt::MODULUS {
+            Self::hash_elements(&elements[..DIGEST_SIZE + 1])
+        } else {
+            elements[DIGEST_SIZE + 1] = Felt::new(value / Felt::MODULUS);
+            Self::hash_elements(&elements)
+        }
+    }
+}
+
+impl ElementHasher for Tip5 {
+    type BaseField = Felt;
+
+    fn hash_elements<E: FieldElement<BaseField = Self::BaseField>>(elements: &[E]) -> Self::Digest {
+        // convert the elements into a list of base field elements and absorb them starting from
+        // the all-zero state
+        let elements = E::slice_as_base_elements(elements);
+        Self::absorb([ZERO; STATE_WIDTH], elements.iter().copied())
+    }
+}
+
+// HASH FUNCTION IMPLEMENTATION
+// ================================================================================================
+
+impl Tip5 {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// The number of rounds is set to 5 to target 160-bit security level.
+    pub const NUM_ROUNDS: usize = NUM_ROUNDS;
+
+    /// Sponge state is set to 16 field elements; 10 elements are reserved for rate and the
+    /// remaining 6 elements are reserved for capacity.
+    pub const STATE_WIDTH: usize = STATE_WIDTH;
+
+    /// The rate portion of the state is located in elements 0 through 9 (inclusive).
+    pub const RATE_RANGE: Range<usize> = RATE_RANGE;
+
+    /// The capacity portion of the state is located in elements 10 through 15 (inclusive).
+    pub const CAPACITY_RANGE: Range<usize> = CAPACITY_RANGE;
+
+    /// The output of the hash function can be read from state elements 0 through 4 (inclusive).
+    pub const DIGEST_RANGE: Range<usize> = DIGEST_RANGE;
+
+    /// The first column of the circulant MDS matrix of the linear layer.
+    pub const MDS_FIRST_COLUMN: [Felt; STATE_WIDTH] = MDS_FIRST_COLUMN;
+
+    /// Round constants added to the state at the end of every round.
+    ///
+    /// The `j`-th constant of round `i` is derived by hashing the ASCII string `Tip5-` followed by
+    /// the decimal representation of `16 * i + j` with BLAKE3, interpreting the first 16 bytes of
+    /// the digest as a little-endian integer and reducing it modulo the field modulus.
+    pub const ROUND_CONSTANTS: [[Felt; STATE_WIDTH]; NUM_ROUNDS] = ROUND_CONSTANTS;
+
+    // TRAIT PASS-THROUGH FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a hash of the provided sequence of bytes.
+    #[inline(always)]
+    pub fn hash(bytes: &[u8]) -> Tip5Digest {
+        <Self as Hasher>::hash(bytes)
+    }
+
+    /// Returns a hash of two digests. This method is intended for use in construction of
+    /// Merkle trees and verification of Merkle paths.
+    #[inline(always)]
+    pub fn merge(values: &[Tip5Digest; 2]) -> Tip5Digest {
+        <Self as Hasher>::merge(values)
+    }
+
+    /// Returns a hash of the provided field elements.
+    #[inline(always)]
+    pub fn hash_elements<E: FieldElement<BaseField = Felt>>(elements: &[E]) -> Tip5Digest {
+        <Self as ElementHasher>::hash_elements(elements)
+    }
+
+    // TIP5 PERMUTATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Applies the Tip5 permutation to the provided state.
+    #[inline(always)]
+    pub fn apply_permutation(state: &mut [Felt; STATE_WIDTH]) {
+        #[cfg(feature = "metrics")]
+        crate::hash::metrics::record(&crate::hash::metrics::TIP5_PERMUTATIONS, 1);
+        for round in 0..NUM_ROUNDS {
+            Self::apply_round(state, round);
+        }
+    }
+
+    /// Tip5 round function.
+    #[inline(always)]
+    pub fn apply_round(state: &mut [Felt; STATE_WIDTH], round: usize) {
+        apply_sbox_layer(state);
+        apply_mds(state);
+        state.iter_mut().zip(ROUND_CONSTANTS[round]).for_each(|(s, c)| *s += c);
+    }
+
+    // HELPER FUNCTIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Absorbs the provided elements into the state by overwriting its rate portion, and returns
+    /// the resulting digest.
+    ///
+    /// The elements are padded by appending a 1 followed by as many 0 as necessary to make their
+    /// number a multiple of RATE_WIDTH; an empty input is thus padded into a single block.
+    fn absorb(
+        mut state: [Felt; STATE_WIDTH],
+        elements: impl IntoIterator<Item = Felt>,
+    ) -> Tip5Digest {
+        let mut i = 0;
+        for element in elements.into_iter().chain([ONE]) {
+            state[RATE_RANGE.start + i] = element;
+            i += 1;
+            if i == RATE_WIDTH {
+                Self::apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // fill the rest of the last block with the zeros of the padding and apply the final
+        // permutation
+        if i != 0 {
+            state[RATE_RANGE.start + i..RATE_RANGE.end].fill(ZERO);
+            Self::apply_permutation(&mut state);
+        }
+
+        Tip5Digest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+// ROUND FUNCTIONS
+// ================================================================================================
+
+/// Applies the split-and-lookup S-Box to the first [NUM_SPLIT_AND_LOOKUP] elements, and the power
+/// map `x -> x^7` to the remaining elements.
+#[inline(always)]
+fn apply_sbox_layer(state: &mut [Felt; STATE_WIDTH]) {
+    let (lookups, powers) = state.split_at_mut(NUM_SPLIT_AND_LOOKUP);
+    lookups.iter_mut().for_each(|s| *s = split_and_lookup(*s));
+    powers.iter_mut().for_each(|s| *s = s.exp7());
+}
+
+/// Maps every byte of the Montgomery representation of the provided element through
+/// [LOOKUP_TABLE].
+///
+/// The table maps the bytes 0x00 and 0xff to themselves, and thus the canonical Montgomery
+/// representation of an element (whose upper 4 bytes are all 0xff only if its lower 4 bytes are
+/// all 0x00) is mapped to the canonical Montgomery representation of an element.
+#[inline(always)]
+fn split_and_lookup(element: Felt) -> Felt {
+    let bytes = element.inner().to_le_bytes().map(|byte| LOOKUP_TABLE[byte as usize]);
+    Felt::from_mont(u64::from_le_bytes(bytes))
+}
+
+/// Multiplies the state by the circulant MDS matrix whose first column is [MDS_FIRST_COLUMN].
+#[inline(always)]
+fn apply_mds(state: &mut [Felt; STATE_WIDTH]) {
+    let input = *state;
+    for (i, s) in state.iter_mut().enumerate() {
+        *s = (0..STATE_WIDTH).fold(ZERO, |acc, j| {
+            acc + MDS_FIRST_COLUMN[(STATE_WIDTH + i - j) % STATE_WIDTH] * input[j]
+        });
+    }
+}
+
+// LOOKUP TABLE
+// ================================================================================================
+
+/// The 8-bit S-Box of the split-and-lookup map, `x -> (x + 1)^3 - 1 mod 257`, which is a
+/// permutation of the bytes since cubing is a permutation of the non-zero residues modulo 257.
+const LOOKUP_TABLE: [u8; 256] = {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let x = i as u32 + 1;
+        table[i] = ((x * x * x - 1) % 257) as u8;
+        i += 1;
+    }
+    table
+};
+
+// MDS
+// ================================================================================================
+
+/// The first column of the circulant MDS matrix of the linear layer.
+const MDS_FIRST_COLUMN: [Felt; STATE_WIDTH] = [
+    Felt::new(61402),
+    Felt::new(1108),
+    Felt::new(28750),
+    Felt::new(33823),
+    Felt::new(7454),
+    Felt::new(43244),
+    Felt::new(53865),
+    Felt::new(12034),
+    Felt::new(56951),
+    Felt::new(27521),
+    Felt::new(41351),
+    Felt::new(40901),
+    Felt::new(12021),
+    Felt::new(59689),
+    Felt::new(26798),
+    Felt::new(17845),
+];
+
+// ROUND CONSTANTS
+// ================================================================================================
+
+/// Tip5 round constants; see [Tip5::ROUND_CONSTANTS] for their derivation.
+const ROUND_CONSTANTS: [[Felt; STATE_WIDTH]; NUM_ROUNDS] = [
+    [
+        Felt::new(13256696083604752469),
+        Felt::new(6996355253477699666),
+        Felt::new(9071515541372186704),
+        Felt::new(6927405237501299190),
+        Felt::new(6853716940624806606),
+        Felt::new(14668259738777772264),
+        Felt::new(8193070512228114254),
+        Felt::new(2584296487727246621),
+        Felt::new(1249141527437075905),
+        Felt::new(2340679490227635347),
+        Felt::new(5250126703174424015),
+        Felt::new(5253845744113751685),
+        Felt::new(7861050888854365180),
+        Felt::new(11015385367923832608),
+        Felt::new(4006836296416020229),
+        Felt::new(13925598476184492002),
+    ],
+    [
+        Felt::new(15310464707976399663),
+        Felt::new(2421348710139844624),
+        Felt::new(5257936591603668157),
+        Felt::new(3664012637234610989),
+        Felt::new(17256355424601855030),
+        Felt::new(8231846883285985164),
+        Felt::new(13956866007301078072),
+        Felt::new(546418158942126258),
+        Felt::new(10251475448938041783),
+        Felt::new(15624703873573977246),
+        Felt::new(11058100872898799603),
+        Felt::new(15752757062335395119),
+        Felt::new(17406089992898789261),
+        Felt::new(13632370219120111688),
+        Felt::new(16016712735572664816),
+        Felt::new(18078160280851533343),
+    ],
+    [
+        Felt::new(14469687462810475958),
+        Felt::new(3953859707957023371),
+        Felt::new(16777177057447719403),
+        Felt::new(11950509880372014014),
+        Felt::new(2474647167162650400),
+        Felt::new(6060300282272369863),
+        Felt::new(15761705805204418069),
+        Felt::new(3165858407334905915),
+        Felt::new(2536793772416098273),
+        Felt::new(1292533655331486913),
+        Felt::new(3018803827372971307),
+        Felt::new(5991625577323954663),
+        Felt::new(12569632450789771842),
+        Felt::new(18175226784988108290),
+        Felt::new(5338707544712562168),
+        Felt::new(16780277607240132196),
+    ],
+    [
+        Felt::new(6940461609298375859),
+        Felt::new(11162418790504099443),
+        Felt::new(14811937084286945082),
+        Felt::new(10210185455507156521),
+        Felt::new(2183012569604731026),
+        Felt::new(17140794130373248786),
+        Felt::new(12910208081766546082),
+        Felt::new(13314542141882637336),
+        Felt::new(9493378506791579003),
+        Felt::new(13980094124551510451),
+        Felt::new(10259563351095421892),
+        Felt::new(12462068802836442232),
+        Felt::new(497236980256524894),
+        Felt::new(13999704055710201964),
+        Felt::new(12355404273549796148),
+        Felt::new(15725820076963714480),
+    ],
+    [
+        Felt::new(897533341503103841),
+        Felt::new(3127206660657379035),
+        Felt::new(2888288100758962797),
+        Felt::new(4549188953298525338),
+        Felt::new(15394685081157569942),
+        Felt::new(953826543152575288),
+        Felt::new(1363701706599808568),
+        Felt::new(9902308452754898263),
+        Felt::new(11322709929209266740),
+        Felt::new(10174567525112800258),
+        Felt::new(9985888252092632901),
+        Felt::new(17952341267343128382),
+        Felt::new(7703173372510397659),
+        Felt::new(15002163860099020375),
+        Felt::new(8502016434380904885),
+        Felt::new(10269755927539984007),
+    ],
+];
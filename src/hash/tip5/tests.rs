@@ -0,0 +1,238 @@
+use proptest::prelude::*;
+use rand_utils::rand_value;
+
+use super::*;
+use crate::{
+    hash::rpo::Rpo256,
+    utils::{Deserializable, Serializable},
+};
+use alloc::{format, vec::Vec};
+
+#[test]
+fn constants() {
+    for (i, constants) in ROUND_CONSTANTS.iter().enumerate() {
+        for (j, &constant) in constants.iter().enumerate() {
+            let digest = blake3::hash(format!("Tip5-{}", STATE_WIDTH * i + j).as_bytes());
+            let value = u128::from_le_bytes(digest.as_bytes()[..16].try_into().unwrap());
+            assert_eq!(constant, Felt::new((value % Felt::MODULUS as u128) as u64));
+        }
+    }
+}
+
+#[test]
+fn lookup_table_is_a_permutation() {
+    // the first entries of the table as listed in the specifications
+    assert_eq!(LOOKUP_TABLE[..8], [0, 7, 26, 63, 124, 215, 85, 254]);
+
+    let mut images = [false; 256];
+    for &image in LOOKUP_TABLE.iter() {
+        assert!(!images[image as usize]);
+        images[image as usize] = true;
+    }
+    assert_eq!(LOOKUP_TABLE[0], 0);
+    assert_eq!(LOOKUP_TABLE[0xff], 0xff);
+
+    // the table is applied to every byte of the Montgomery representation independently
+    for byte in 0..=255_u8 {
+        for i in 0..4 {
+            let element = Felt::from_mont((byte as u64) << (8 * i));
+            let expected = Felt::from_mont((LOOKUP_TABLE[byte as usize] as u64) << (8 * i));
+            assert_eq!(split_and_lookup(element), expected);
+        }
+    }
+
+    // canonical representations of elements are mapped to canonical representations of elements
+    for inner in [Felt::MODULUS - 1, 0xffffffff, 0xffffffff00000000] {
+        assert!(split_and_lookup(Felt::from_mont(inner)).inner() < Felt::MODULUS);
+    }
+    for _ in 0..100 {
+        let element: Felt = rand_value();
+        assert!(split_and_lookup(element).inner() < Felt::MODULUS);
+    }
+}
+
+#[test]
+fn mds_is_circulant() {
+    let input: [Felt; STATE_WIDTH] = core::array::from_fn(|_| rand_value());
+    let mut actual = input;
+    apply_mds(&mut actual);
+
+    // the first column of the matrix is the image of the first unit vector, and every other column
+    // is the rotation of the previous one
+    let mut expected = [ZERO; STATE_WIDTH];
+    for (j, &x) in input.iter().enumerate() {
+        let mut unit = [ZERO; STATE_WIDTH];
+        unit[j] = ONE;
+        apply_mds(&mut unit);
+        for i in 0..STATE_WIDTH {
+            assert_eq!(unit[i], MDS_FIRST_COLUMN[(STATE_WIDTH + i - j) % STATE_WIDTH]);
+            expected[i] += unit[i] * x;
+        }
+    }
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn tip5_merge() {
+    let digests = [
+        Tip5Digest::new(core::array::from_fn(|i| Felt::new(i as u64))),
+        Tip5Digest::new(core::array::from_fn(|i| Felt::new(i as u64 + 5))),
+    ];
+
+    // merging truncates the permutation of the two digests with a capacity set to ones, and is thus
+    // domain separated from hashing the 10 elements of the digests
+    let mut state: [Felt; STATE_WIDTH] = core::array::from_fn(|i| Felt::new(i as u64));
+    state[CAPACITY_RANGE].fill(ONE);
+    Tip5::apply_permutation(&mut state);
+    assert_eq!(Tip5::merge(&digests), Tip5Digest::new(state[DIGEST_RANGE].try_into().unwrap()));
+
+    let elements: Vec<Felt> = digests.iter().flat_map(|d| d.as_elements()).copied().collect();
+    assert_ne!(Tip5::merge(&digests), Tip5::hash_elements(&elements));
+
+    // the order of the digests matters
+    assert_ne!(Tip5::merge(&digests), Tip5::merge(&[digests[1], digests[0]]));
+}
+
+#[test]
+fn tip5_merge_with_int() {
+    let seed = Tip5::hash(b"seed");
+    let elements: Vec<Felt> = seed.as_elements().iter().copied().chain([Felt::new(42)]).collect();
+    assert_eq!(Tip5::merge_with_int(seed, 42), Tip5::hash_elements(&elements));
+
+    let value = Felt::MODULUS + 5;
+    let elements: Vec<Felt> =
+        seed.as_elements().iter().copied().chain([Felt::new(value), ONE]).collect();
+    assert_eq!(Tip5::merge_with_int(seed, value), Tip5::hash_elements(&elements));
+}
+
+#[test]
+fn tip5_hash_elements_overwrites_rate() {
+    // a full block of elements is followed by a block consisting of the padding only, and each
+    // block overwrites the rate portion of the state
+    let elements: [Felt; RATE_WIDTH] = core::array::from_fn(|_| rand_value());
+    let mut state = [ZERO; STATE_WIDTH];
+    state[RATE_RANGE].copy_from_slice(&elements);
+    Tip5::apply_permutation(&mut state);
+    state[RATE_RANGE].fill(ZERO);
+    state[RATE_RANGE.start] = ONE;
+    Tip5::apply_permutation(&mut state);
+    assert_eq!(
+        Tip5::hash_elements(&elements),
+        Tip5Digest::new(state[DIGEST_RANGE].try_into().unwrap())
+    );
+}
+
+#[test]
+fn tip5_hash_padding() {
+    // adding a zero bytes at the end of a byte string should result in a different hash
+    let r1 = Tip5::hash(&[1_u8, 2, 3]);
+    let r2 = Tip5::hash(&[1_u8, 2, 3, 0]);
+    assert_ne!(r1, r2);
+
+    // adding a zero element at the end of a list of elements should result in a different hash
+    let elements = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4), Felt::new(5)];
+    let r1 = Tip5::hash_elements(&elements);
+    let r2 = Tip5::hash_elements(&[elements.as_slice(), &[ZERO]].concat());
+    assert_ne!(r1, r2);
+}
+
+#[test]
+fn tip5_hash_empty_input() {
+    // empty inputs are padded into a single block, and thus their hashes are not the zero digest
+    let zero = Tip5Digest::default();
+    assert_ne!(Tip5::hash(&[]), zero);
+    assert_ne!(Tip5::hash_elements::<Felt>(&[]), zero);
+
+    // the padded empty input is domain separated from a block which looks like the padding
+    let empty = Tip5::hash_elements::<Felt>(&[]);
+    assert_ne!(empty, Tip5::hash_elements(&[ZERO; RATE_WIDTH]));
+    let mut padding = [ZERO; RATE_WIDTH];
+    padding[0] = ONE;
+    assert_ne!(empty, Tip5::hash_elements(&padding));
+    assert_ne!(Tip5::hash(&[]), Tip5::hash(&[0]));
+    assert_ne!(Tip5::hash(&[]), empty);
+}
+
+#[test]
+fn tip5_digest_serialization() {
+    let digest = Tip5::hash(b"digest");
+    let bytes = digest.to_bytes();
+    assert_eq!(digest.as_bytes(), bytes[..32]);
+    assert_eq!(digest.to_hex().len(), 2 + 2 * DIGEST_SIZE * 8);
+
+    // serialization retains all 5 elements of the digest
+    assert_eq!(digest.to_bytes().to_vec(), Serializable::to_bytes(&digest));
+    assert_eq!(Tip5Digest::read_from_bytes(&bytes).unwrap(), digest);
+
+    // non-canonical elements are rejected
+    let mut bytes = bytes;
+    bytes[32..].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert!(Tip5Digest::read_from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn tip5_regression_vectors() {
+    // no reference test vectors are available for this instance of Tip5 in this crate; these
+    // vectors were computed by this implementation and guard against accidental changes to the
+    // permutation and the sponge
+    let mut state: [Felt; STATE_WIDTH] = core::array::from_fn(|i| Felt::new(i as u64));
+    Tip5::apply_permutation(&mut state);
+    let expected = [
+        10541696102761920154,
+        12666498145502556542,
+        16308763728817241566,
+        4356733245237987773,
+        17758409856713742715,
+        744985265460699950,
+        10829185520624776695,
+        3173576851277651783,
+        6358574823155920395,
+        2018872138310410001,
+        4470835483579887263,
+        9300665595290241298,
+        14905001604333285013,
+        11518671792287950360,
+        3887354151752324901,
+        4865760687691151800,
+    ];
+    assert_eq!(state, expected.map(Felt::new));
+
+    let elements: Vec<Felt> = (0..10).map(Felt::new).collect();
+    let expected = [
+        6230403867311065524,
+        4097331043619689855,
+        3945756604070153252,
+        13262898716295639431,
+        8815271171863182529,
+    ];
+    assert_eq!(Tip5::hash_elements(&elements), Tip5Digest::new(expected.map(Felt::new)));
+
+    let expected = [
+        2767290246150690918,
+        3425012450495661588,
+        11270747022142877598,
+        1366026293708808391,
+        15575066817886602440,
+    ];
+    assert_eq!(Tip5::hash(b"abc"), Tip5Digest::new(expected.map(Felt::new)));
+}
+
+proptest! {
+    #[test]
+    fn tip5_hash_elements_padding(ref values in any::<Vec<u64>>(), extra in any::<u64>()) {
+        let elements: Vec<Felt> = values.iter().map(|&v| Felt::new(v)).collect();
+        let digest = Tip5::hash_elements(&elements);
+        let rpo_digest = Rpo256::hash_elements(&elements);
+        prop_assert_ne!(&digest[..4], rpo_digest.as_elements());
+
+        // appending any element, including a zero, changes the hash
+        let extended: Vec<Felt> = elements.iter().copied().chain([Felt::new(extra)]).collect();
+        prop_assert_ne!(Tip5::hash_elements(&extended), digest);
+    }
+
+    #[test]
+    fn tip5_hash_bytes_padding(ref bytes in any::<Vec<u8>>(), extra in any::<u8>()) {
+        let extended: Vec<u8> = bytes.iter().copied().chain([extra]).collect();
+        prop_assert_ne!(Tip5::hash(&extended), Tip5::hash(bytes));
+    }
+}
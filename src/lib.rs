@@ -6,6 +6,8 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod cipher;
+pub mod commitment;
 pub mod dsa;
 pub mod hash;
 pub mod merkle;
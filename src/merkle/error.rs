@@ -2,19 +2,23 @@ use alloc::vec::Vec;
 use core::fmt;
 
 use super::{smt::SmtLeafError, MerklePath, NodeIndex, RpoDigest};
+use crate::utils::bytes_to_hex_string;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum MerkleError {
     ConflictingRoots(Vec<RpoDigest>),
+    ConflictingMutationRoots([u8; 32], [u8; 32]),
     DepthTooSmall(u8),
     DepthTooBig(u64),
     DuplicateValuesForIndex(u64),
     DuplicateValuesForKey(RpoDigest),
     InvalidIndex { depth: u8, value: u64 },
+    InvalidDeltaRoot([u8; 32], [u8; 32]),
     InvalidDepth { expected: u8, provided: u8 },
     InvalidSubtreeDepth { subtree_depth: u8, tree_depth: u8 },
     InvalidPath(MerklePath),
     InvalidNumEntries(usize),
+    NoKeys,
     NodeNotInSet(NodeIndex),
     NodeNotInStore(RpoDigest, NodeIndex),
     NumLeavesNotPowerOfTwo(usize),
@@ -27,6 +31,14 @@ impl fmt::Display for MerkleError {
         use MerkleError::*;
         match self {
             ConflictingRoots(roots) => write!(f, "the merkle paths roots do not match {roots:?}"),
+            ConflictingMutationRoots(mutations_root, tree_root) => {
+                write!(
+                    f,
+                    "the mutations were computed against root {}, but the root of the tree is {}",
+                    bytes_to_hex_string(*mutations_root),
+                    bytes_to_hex_string(*tree_root)
+                )
+            }
             DepthTooSmall(depth) => write!(f, "the provided depth {depth} is too small"),
             DepthTooBig(depth) => write!(f, "the provided depth {depth} is too big"),
             DuplicateValuesForIndex(key) => write!(f, "multiple values provided for key {key}"),
@@ -34,6 +46,14 @@ impl fmt::Display for MerkleError {
             InvalidIndex { depth, value } => {
                 write!(f, "the index value {value} is not valid for the depth {depth}")
             }
+            InvalidDeltaRoot(delta_root, computed_root) => {
+                write!(
+                    f,
+                    "the delta claims the root {}, but applying its key-value pairs yields the root {}",
+                    bytes_to_hex_string(*delta_root),
+                    bytes_to_hex_string(*computed_root)
+                )
+            }
             InvalidDepth { expected, provided } => {
                 write!(f, "the provided depth {provided} is not valid for {expected}")
             }
@@ -42,6 +62,7 @@ impl fmt::Display for MerkleError {
             }
             InvalidPath(_path) => write!(f, "the provided path is not valid"),
             InvalidNumEntries(max) => write!(f, "number of entries exceeded the maximum: {max}"),
+            NoKeys => write!(f, "no keys were provided"),
             NodeNotInSet(index) => write!(f, "the node with index ({index}) is not in the set"),
             NodeNotInStore(hash, index) => {
                 write!(f, "the node {hash:?} with index ({index}) is not in the store")
@@ -50,10 +50,22 @@ impl MerkleTree {
         let ptr = nodes.as_ptr() as *const [RpoDigest; 2];
         let pairs = unsafe { slice::from_raw_parts(ptr, n) };
 
-        // calculate all internal tree nodes
+        // calculate all internal tree nodes; when the `parallel` feature is enabled, the nodes are
+        // computed level by level, with the nodes of each level computed concurrently
+        #[cfg(not(feature = "parallel"))]
         for i in (1..n).rev() {
             nodes[i] = Rpo256::merge(&pairs[i]);
         }
+        #[cfg(feature = "parallel")]
+        {
+            let mut level_start = n / 2;
+            while level_start > 0 {
+                let level_end = 2 * level_start;
+                let level = Rpo256::merge_many_parallel(&pairs[level_start..level_end]);
+                nodes[level_start..level_end].copy_from_slice(&level);
+                level_start /= 2;
+            }
+        }
 
         Ok(Self { nodes })
     }
@@ -22,8 +22,10 @@ pub use path::{MerklePath, RootPath, ValuePath};
 
 mod smt;
 pub use smt::{
-    LeafIndex, SimpleSmt, Smt, SmtLeaf, SmtLeafError, SmtProof, SmtProofError, SMT_DEPTH,
-    SMT_MAX_DEPTH, SMT_MIN_DEPTH,
+    FrozenSmt, InnerNode, KvDatabase, KvDatabaseStore, LeafIndex, MemorySmtStore, MutationSet,
+    Reversion, SimpleSmt, Smt, SmtDelta, SmtHasher, SmtHistoryProof, SmtLeaf, SmtLeafError,
+    SmtMultiProof, SmtProof, SmtProofError, SmtStore, SmtWithHistory, SMT_DEPTH, SMT_MAX_DEPTH,
+    SMT_MIN_DEPTH,
 };
 
 mod mmr;
@@ -11,18 +11,21 @@ use crate::{
 // ================================================================================================
 
 /// A merkle path container, composed of a sequence of nodes of a Merkle tree.
+///
+/// The nodes are [RpoDigest]s by default. Paths of trees built with other hash functions (see
+/// [Smt](super::Smt)) hold the digests of those functions, and are verified by the trees' proofs.
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub struct MerklePath {
-    nodes: Vec<RpoDigest>,
+pub struct MerklePath<D = RpoDigest> {
+    nodes: Vec<D>,
 }
 
-impl MerklePath {
+impl<D> MerklePath<D> {
     // CONSTRUCTORS
     // --------------------------------------------------------------------------------------------
 
     /// Creates a new Merkle path from a list of nodes.
-    pub fn new(nodes: Vec<RpoDigest>) -> Self {
+    pub fn new(nodes: Vec<D>) -> Self {
         assert!(nodes.len() <= u8::MAX.into(), "MerklePath may have at most 256 items");
         Self { nodes }
     }
@@ -36,10 +39,12 @@ impl MerklePath {
     }
 
     /// Returns a reference to the [MerklePath]'s nodes.
-    pub fn nodes(&self) -> &[RpoDigest] {
+    pub fn nodes(&self) -> &[D] {
         &self.nodes
     }
+}
 
+impl MerklePath {
     /// Computes the merkle root for this opening.
     pub fn compute_root(&self, index: u64, node: RpoDigest) -> Result<RpoDigest, MerkleError> {
         let mut index = NodeIndex::new(self.depth(), index)?;
@@ -84,35 +89,35 @@ impl MerklePath {
 // CONVERSIONS
 // ================================================================================================
 
-impl From<MerklePath> for Vec<RpoDigest> {
-    fn from(path: MerklePath) -> Self {
+impl<D> From<MerklePath<D>> for Vec<D> {
+    fn from(path: MerklePath<D>) -> Self {
         path.nodes
     }
 }
 
-impl From<Vec<RpoDigest>> for MerklePath {
-    fn from(path: Vec<RpoDigest>) -> Self {
+impl<D> From<Vec<D>> for MerklePath<D> {
+    fn from(path: Vec<D>) -> Self {
         Self::new(path)
     }
 }
 
-impl From<&[RpoDigest]> for MerklePath {
-    fn from(path: &[RpoDigest]) -> Self {
+impl<D: Clone> From<&[D]> for MerklePath<D> {
+    fn from(path: &[D]) -> Self {
         Self::new(path.to_vec())
     }
 }
 
-impl Deref for MerklePath {
+impl<D> Deref for MerklePath<D> {
     // we use `Vec` here instead of slice so we can call vector mutation methods directly from the
     // merkle path (example: `Vec::remove`).
-    type Target = Vec<RpoDigest>;
+    type Target = Vec<D>;
 
     fn deref(&self) -> &Self::Target {
         &self.nodes
     }
 }
 
-impl DerefMut for MerklePath {
+impl<D> DerefMut for MerklePath<D> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.nodes
     }
@@ -121,15 +126,15 @@ impl DerefMut for MerklePath {
 // ITERATORS
 // ================================================================================================
 
-impl FromIterator<RpoDigest> for MerklePath {
-    fn from_iter<T: IntoIterator<Item = RpoDigest>>(iter: T) -> Self {
+impl<D> FromIterator<D> for MerklePath<D> {
+    fn from_iter<T: IntoIterator<Item = D>>(iter: T) -> Self {
         Self::new(iter.into_iter().collect())
     }
 }
 
-impl IntoIterator for MerklePath {
-    type Item = RpoDigest;
-    type IntoIter = alloc::vec::IntoIter<RpoDigest>;
+impl<D> IntoIterator for MerklePath<D> {
+    type Item = D;
+    type IntoIter = alloc::vec::IntoIter<D>;
 
     fn into_iter(self) -> Self::IntoIter {
         self.nodes.into_iter()
@@ -205,7 +210,7 @@ pub struct RootPath {
 // SERIALIZATION
 // ================================================================================================
 
-impl Serializable for MerklePath {
+impl<D: Serializable> Serializable for MerklePath<D> {
     fn write_into<W: winter_utils::ByteWriter>(&self, target: &mut W) {
         assert!(self.nodes.len() <= u8::MAX.into(), "Length enforced in the constructor");
         target.write_u8(self.nodes.len() as u8);
@@ -213,10 +218,10 @@ impl Serializable for MerklePath {
     }
 }
 
-impl Deserializable for MerklePath {
+impl<D: Deserializable> Deserializable for MerklePath<D> {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         let count = source.read_u8()?.into();
-        let nodes = source.read_many::<RpoDigest>(count)?;
+        let nodes = source.read_many::<D>(count)?;
         Ok(Self { nodes })
     }
 }
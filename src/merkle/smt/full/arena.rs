@@ -0,0 +1,665 @@
+#[cfg(feature = "serde")]
+use alloc::string::{String, ToString};
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::fmt;
+
+use super::{EmptyRoots, InnerNode, NodeIndex, Rpo256, SmtHasher};
+
+// NODE ARENA
+// ================================================================================================
+
+/// Compact storage of the inner nodes of an [Smt](super::Smt) of depth `DEPTH`.
+///
+/// Every inner node is stored as a pair of 32-bit handles to the digests of its children, in a
+/// dense vector of nodes which is indexed by the scalar indices of the nodes (see
+/// [NodeIndex::to_scalar_index()]), such that the nodes are ordered by depth first and by their
+/// position within the level second. The digests themselves are kept in a single arena, except
+/// for the roots of empty subtrees: since the vast majority of the children of the nodes of a
+/// sparse tree are empty subtrees, these are deduplicated into reserved handles, with the handle
+/// `d` referring to the root of an empty subtree at depth `d`. The slots of the nodes and of the
+/// digests which are no longer referenced are reused by subsequent insertions.
+///
+/// Since handles are 32-bit integers, the arena holds at most `u32::MAX` nodes and
+/// `u32::MAX - DEPTH - 1` digests, beyond which insertions fail with [HandlesExhausted].
+///
+/// When deserialized, the arena is checked to be the arena of some tree, i.e., every handle refers
+/// either to the root of an empty subtree at the depth of the child or to a slot which is neither
+/// free nor referred to by any other handle, and every slot is either referred to or free.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(bound(serialize = "H::Digest: serde::Serialize")))]
+pub(super) struct NodeArena<const DEPTH: u8, H: SmtHasher = Rpo256> {
+    /// Handles of the inner nodes, keyed by the scalar indices of the nodes.
+    index: BTreeMap<u64, u32>,
+    /// Handles of the children of the inner nodes, addressed by the handles of the nodes.
+    nodes: Vec<[u32; 2]>,
+    free_nodes: Vec<u32>,
+    digests: Vec<H::Digest>,
+    free: Vec<u32>,
+    /// The number of handles which can be assigned to nodes and to digests, which is lowered only
+    /// to test the exhaustion of handles.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    max_handles: u32,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    empty_roots: EmptyRoots<DEPTH, H>,
+}
+
+impl<const DEPTH: u8, H: SmtHasher> NodeArena<DEPTH, H> {
+    /// The number of handles which are reserved for the roots of empty subtrees, one for every
+    /// depth from 0 to `DEPTH` (inclusive).
+    const NUM_EMPTY_HANDLES: u32 = DEPTH as u32 + 1;
+
+    /// Returns a new arena without any inner nodes.
+    pub fn new() -> Self {
+        Self {
+            index: BTreeMap::new(),
+            nodes: Vec::new(),
+            free_nodes: Vec::new(),
+            digests: Vec::new(),
+            free: Vec::new(),
+            max_handles: u32::MAX,
+            empty_roots: EmptyRoots::default(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of inner nodes in the arena.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// Returns the number of digests stored in the arena, i.e., the number of children of the
+    /// inner nodes which are not roots of empty subtrees.
+    pub fn num_digests(&self) -> usize {
+        self.digests.len() - self.free.len()
+    }
+
+    /// Returns the number of digests which the arena stores for the provided inner node at the
+    /// specified index, i.e., the number of its children which are not roots of empty subtrees.
+    pub fn num_digests_of(&self, index: NodeIndex, node: &InnerNode<H::Digest>) -> usize {
+        let empty_root = self.empty_roots.get(index.depth() + 1);
+        (node.left != empty_root) as usize + (node.right != empty_root) as usize
+    }
+
+    /// Returns the inner node at the specified index, if present.
+    pub fn get(&self, index: NodeIndex) -> Option<InnerNode<H::Digest>> {
+        self.index
+            .get(&index.to_scalar_index())
+            .map(|&handle| self.node(self.nodes[handle as usize]))
+    }
+
+    /// Returns an iterator over the inner nodes in the arena, ordered by depth first and by their
+    /// position within the level second.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeIndex, InnerNode<H::Digest>)> + '_ {
+        self.index.iter().map(move |(&scalar_index, &handle)| {
+            (node_index(scalar_index), self.node(self.nodes[handle as usize]))
+        })
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Inserts or replaces the inner node at the specified index.
+    ///
+    /// The slots of the replaced node and of its digests are reused for the new node and its
+    /// digests.
+    ///
+    /// # Errors
+    /// Returns an error, and leaves the arena unchanged, if the node or its digests need new
+    /// slots while all handles have been assigned.
+    pub fn insert(
+        &mut self,
+        index: NodeIndex,
+        node: InnerNode<H::Digest>,
+    ) -> Result<(), HandlesExhausted> {
+        let child_depth = index.depth() + 1;
+        let empty_root = self.empty_roots.get(child_depth);
+        let scalar_index = index.to_scalar_index();
+        let handle = self.index.get(&scalar_index).copied();
+        let children = handle.map_or([child_depth as u32; 2], |handle| self.nodes[handle as usize]);
+
+        // check that enough handles are available before any slot is assigned, accounting for the
+        // slots released by the children which become roots of empty subtrees
+        if handle.is_none()
+            && self.free_nodes.is_empty()
+            && self.nodes.len() as u32 >= self.max_handles
+        {
+            return Err(HandlesExhausted);
+        }
+        let digests = [node.left, node.right];
+        let is_stored = |handle: u32| handle >= Self::NUM_EMPTY_HANDLES;
+        let needed = children
+            .iter()
+            .zip(digests)
+            .filter(|&(&handle, digest)| !is_stored(handle) && digest != empty_root)
+            .count();
+        let released = children
+            .iter()
+            .zip(digests)
+            .filter(|&(&handle, digest)| is_stored(handle) && digest == empty_root)
+            .count();
+        let available = (self.max_handles - Self::NUM_EMPTY_HANDLES) as usize - self.digests.len()
+            + self.free.len()
+            + released;
+        if needed > available {
+            return Err(HandlesExhausted);
+        }
+
+        let children = [
+            self.assign(children[0], node.left, child_depth),
+            self.assign(children[1], node.right, child_depth),
+        ];
+        match handle {
+            Some(handle) => self.nodes[handle as usize] = children,
+            None => {
+                let handle = match self.free_nodes.pop() {
+                    Some(handle) => {
+                        self.nodes[handle as usize] = children;
+                        handle
+                    }
+                    None => {
+                        self.nodes.push(children);
+                        self.nodes.len() as u32 - 1
+                    }
+                };
+                self.index.insert(scalar_index, handle);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the inner node at the specified index, if present.
+    pub fn remove(&mut self, index: NodeIndex) {
+        if let Some(handle) = self.index.remove(&index.to_scalar_index()) {
+            self.nodes[handle as usize].into_iter().for_each(|child| self.release(child));
+            self.free_nodes.push(handle);
+        }
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the inner node with children referred to by the provided handles.
+    fn node(&self, [left, right]: [u32; 2]) -> InnerNode<H::Digest> {
+        InnerNode {
+            left: self.digest(left),
+            right: self.digest(right),
+        }
+    }
+
+    /// Returns the digest referred to by the provided handle.
+    fn digest(&self, handle: u32) -> H::Digest {
+        if handle < Self::NUM_EMPTY_HANDLES {
+            self.empty_roots.get(handle as u8)
+        } else {
+            self.digests[(handle - Self::NUM_EMPTY_HANDLES) as usize]
+        }
+    }
+
+    /// Returns a handle to the provided digest of a node at the provided depth, reusing the slot
+    /// of the digest currently referred to by `handle` if possible.
+    ///
+    /// The caller must ensure that a slot is available if the digest needs one.
+    fn assign(&mut self, handle: u32, digest: H::Digest, depth: u8) -> u32 {
+        if digest == self.empty_roots.get(depth) {
+            self.release(handle);
+            return depth as u32;
+        }
+        if handle >= Self::NUM_EMPTY_HANDLES {
+            self.digests[(handle - Self::NUM_EMPTY_HANDLES) as usize] = digest;
+            return handle;
+        }
+
+        match self.free.pop() {
+            Some(handle) => {
+                self.digests[(handle - Self::NUM_EMPTY_HANDLES) as usize] = digest;
+                handle
+            }
+            None => {
+                let handle = self.digests.len() as u32 + Self::NUM_EMPTY_HANDLES;
+                self.digests.push(digest);
+                handle
+            }
+        }
+    }
+
+    /// Marks the slot of the digest referred to by the provided handle as free.
+    fn release(&mut self, handle: u32) {
+        if handle >= Self::NUM_EMPTY_HANDLES {
+            self.free.push(handle);
+        }
+    }
+
+    /// Checks that the index, the handles and the free slots of the arena are consistent with
+    /// each other, and returns a description of the first inconsistency otherwise.
+    #[cfg(feature = "serde")]
+    fn validate(&self) -> Result<(), String> {
+        /// Tracks the slots which are referred to by a handle or free.
+        struct Slots {
+            referenced: Vec<bool>,
+            offset: u32,
+            count: usize,
+        }
+
+        impl Slots {
+            fn mark(&mut self, handle: u32) -> Result<(), String> {
+                let slot = handle
+                    .checked_sub(self.offset)
+                    .and_then(|slot| self.referenced.get_mut(slot as usize))
+                    .ok_or_else(|| format!("handle {handle} is out of bounds"))?;
+                if core::mem::replace(slot, true) {
+                    return Err(format!("handle {handle} is used more than once"));
+                }
+                self.count += 1;
+                Ok(())
+            }
+        }
+
+        if self.nodes.len() > self.max_handles as usize
+            || self.digests.len() > (self.max_handles - Self::NUM_EMPTY_HANDLES) as usize
+        {
+            return Err("the number of slots exceeds the number of handles".to_string());
+        }
+
+        // every slot must be referred to by exactly one handle or be free
+        let mut nodes = Slots {
+            referenced: vec![false; self.nodes.len()],
+            offset: 0,
+            count: 0,
+        };
+        let mut digests = Slots {
+            referenced: vec![false; self.digests.len()],
+            offset: Self::NUM_EMPTY_HANDLES,
+            count: 0,
+        };
+
+        for (&scalar_index, &handle) in self.index.iter() {
+            let depth = scalar_index.checked_ilog2().filter(|&depth| depth < DEPTH as u32);
+            let depth = depth.ok_or_else(|| format!("scalar index {scalar_index} is invalid"))?;
+            nodes.mark(handle)?;
+
+            let child_depth = depth + 1;
+            for child in self.nodes[handle as usize] {
+                if child >= Self::NUM_EMPTY_HANDLES {
+                    digests.mark(child)?;
+                } else if child != child_depth {
+                    return Err(format!("handle {child} is invalid at depth {child_depth}"));
+                }
+            }
+        }
+        for &handle in self.free_nodes.iter() {
+            nodes.mark(handle)?;
+        }
+        for &handle in self.free.iter() {
+            digests.mark(handle)?;
+        }
+        if nodes.count != self.nodes.len() || digests.count != self.digests.len() {
+            return Err("a slot is neither referred to nor free".to_string());
+        }
+
+        Ok(())
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Default for NodeArena<DEPTH, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const DEPTH: u8, H: SmtHasher> serde::Deserialize<'de> for NodeArena<DEPTH, H>
+where
+    H::Digest: serde::Deserialize<'de>,
+{
+    fn deserialize<De: serde::Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "NodeArena")]
+        struct Parts<D> {
+            index: BTreeMap<u64, u32>,
+            nodes: Vec<[u32; 2]>,
+            free_nodes: Vec<u32>,
+            digests: Vec<D>,
+            free: Vec<u32>,
+        }
+
+        let Parts { index, nodes, free_nodes, digests, free } = Parts::deserialize(deserializer)?;
+        let arena = Self {
+            index,
+            nodes,
+            free_nodes,
+            digests,
+            free,
+            max_handles: u32::MAX,
+            empty_roots: EmptyRoots::default(),
+        };
+        arena.validate().map_err(serde::de::Error::custom)?;
+        Ok(arena)
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Clone for NodeArena<DEPTH, H> {
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index.clone(),
+            nodes: self.nodes.clone(),
+            free_nodes: self.free_nodes.clone(),
+            digests: self.digests.clone(),
+            free: self.free.clone(),
+            max_handles: self.max_handles,
+            empty_roots: self.empty_roots.clone(),
+        }
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> PartialEq for NodeArena<DEPTH, H> {
+    fn eq(&self, other: &Self) -> bool {
+        // the handles depend on the order of the insertions, and thus only the nodes are compared
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Eq for NodeArena<DEPTH, H> {}
+
+// HANDLES EXHAUSTED
+// ================================================================================================
+
+/// The error returned by [NodeArena::insert()] when a node or a digest needs a new slot, but all
+/// 32-bit handles have been assigned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct HandlesExhausted;
+
+impl fmt::Display for HandlesExhausted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "all 32-bit handles of the inner node arena have been assigned")
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the node index with the provided scalar index, i.e., `2^depth + value`.
+fn node_index(scalar_index: u64) -> NodeIndex {
+    let depth = scalar_index.ilog2() as u8;
+    NodeIndex::new_unchecked(depth, scalar_index - (1 << depth))
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{HandlesExhausted, InnerNode, NodeArena, NodeIndex};
+    use crate::{hash::rpo::Rpo256, merkle::SmtHasher, Felt};
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn node_arena_validation() {
+        let digest = |v: u64| Rpo256::hash_elements(&[Felt::new(v)]);
+        let empty_roots = Rpo256::empty_subtree_roots(8);
+
+        let mut arena = NodeArena::<8, Rpo256>::new();
+        let nodes = [
+            (NodeIndex::new(1, 0).unwrap(), InnerNode { left: digest(1), right: digest(2) }),
+            (
+                NodeIndex::new(2, 3).unwrap(),
+                InnerNode { left: digest(3), right: empty_roots[3] },
+            ),
+            (
+                NodeIndex::new(1, 0).unwrap(),
+                InnerNode { left: digest(4), right: empty_roots[2] },
+            ),
+        ];
+        for (index, node) in nodes.iter().cloned() {
+            arena.insert(index, node).unwrap();
+        }
+        assert_eq!(arena.validate(), Ok(()));
+
+        // arenas with the same nodes are equal regardless of the handles of the nodes
+        let mut other = NodeArena::<8, Rpo256>::new();
+        for (index, node) in nodes.iter().skip(1).cloned() {
+            other.insert(index, node).unwrap();
+        }
+        assert_ne!(arena.nodes, other.nodes);
+        assert_eq!(arena, other);
+
+        // the scalar indices of the nodes must be valid for the depth of the tree
+        let handle = arena.index[&7];
+        for scalar_index in [0, 1 << 8] {
+            let mut invalid = arena.clone();
+            invalid.index.remove(&7);
+            invalid.index.insert(scalar_index, handle);
+            assert!(invalid.validate().is_err());
+        }
+
+        // the handles of the roots of empty subtrees must match the depths of the children
+        let children = arena.nodes[handle as usize];
+        let mut invalid = arena.clone();
+        invalid.nodes[handle as usize] = [children[0], 2];
+        assert!(invalid.validate().is_err());
+
+        // handles must be in bounds and referred to at most once, including by the free lists
+        let mut invalid = arena.clone();
+        invalid.nodes[handle as usize] = [100, 3];
+        assert!(invalid.validate().is_err());
+
+        let other_children = arena.nodes[arena.index[&2] as usize];
+        let mut invalid = arena.clone();
+        invalid.nodes[handle as usize] = [other_children[0], 3];
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = arena.clone();
+        invalid.free.push(children[0]);
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = arena.clone();
+        invalid.index.insert(3, handle);
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = arena.clone();
+        invalid.free_nodes.push(handle);
+        assert!(invalid.validate().is_err());
+
+        // every slot must be either referred to or free
+        let mut invalid = arena.clone();
+        invalid.free.pop();
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = arena.clone();
+        invalid.nodes.push([2, 2]);
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn node_arena_handles_exhausted() {
+        let digest = |v: u64| Rpo256::hash_elements(&[Felt::new(v)]);
+        let empty_roots = Rpo256::empty_subtree_roots(8);
+
+        // 9 handles are reserved for the roots of empty subtrees, which leaves 3 for digests
+        let mut arena = NodeArena::<8, Rpo256>::new();
+        arena.max_handles = 12;
+        let index = NodeIndex::new(1, 0).unwrap();
+        arena.insert(index, InnerNode { left: digest(1), right: digest(2) }).unwrap();
+
+        // a node which needs more digests than are available leaves the arena unchanged
+        let other = NodeIndex::new(1, 1).unwrap();
+        let expected = arena.clone();
+        let node = InnerNode { left: digest(3), right: digest(4) };
+        assert_eq!(arena.insert(other, node.clone()), Err(HandlesExhausted));
+        assert_eq!(arena, expected);
+        assert_eq!(arena.get(other), None);
+
+        // the slots released by a node are available to the node itself and to other nodes
+        arena
+            .insert(index, InnerNode { left: empty_roots[2], right: digest(5) })
+            .unwrap();
+        arena.insert(other, node).unwrap();
+        assert_eq!(arena.num_digests(), 3);
+        let node = InnerNode { left: digest(6), right: digest(7) };
+        assert_eq!(arena.insert(index, node.clone()), Err(HandlesExhausted));
+
+        arena.remove(other);
+        arena.insert(index, node.clone()).unwrap();
+        assert_eq!(arena.get(index), Some(node));
+
+        // the number of nodes is limited by the number of handles as well
+        let mut arena = NodeArena::<8, Rpo256>::new();
+        arena.max_handles = 10;
+        let empty = InnerNode {
+            left: empty_roots[8],
+            right: empty_roots[8],
+        };
+        for value in 0..10 {
+            arena.insert(NodeIndex::new(7, value).unwrap(), empty.clone()).unwrap();
+        }
+        let index = NodeIndex::new(7, 10).unwrap();
+        assert_eq!(arena.insert(index, empty.clone()), Err(HandlesExhausted));
+        assert_eq!(arena.len(), 10);
+
+        // replacing a node does not need a new handle, and removing a node frees its handle
+        arena.insert(NodeIndex::new(7, 0).unwrap(), empty.clone()).unwrap();
+        arena.remove(NodeIndex::new(7, 0).unwrap());
+        arena.insert(index, empty).unwrap();
+        assert_eq!(arena.len(), 10);
+    }
+
+    /// Measures the bytes per leaf of the inner nodes of a sparse tree, for the arena and for the
+    /// previous layout, in which the nodes of every level were kept in a separate map and referred
+    /// to their digests via 64-bit handles.
+    #[cfg(feature = "std")]
+    #[test]
+    fn node_arena_bytes_per_leaf() {
+        use alloc::{collections::BTreeMap, vec::Vec};
+        use std::println;
+
+        use rand_utils::rand_value;
+
+        use crate::{hash::rpo::RpoDigest, merkle::smt::SMT_DEPTH, ZERO};
+
+        const NUM_LEAVES: usize = 4096;
+
+        // the inner nodes on the paths from the root to randomly distributed leaves, with one
+        // distinct digest for every child on a path
+        let mut children = BTreeMap::<(u8, u64), [Option<RpoDigest>; 2]>::new();
+        let mut counter = 0;
+        for _ in 0..NUM_LEAVES {
+            let key: u64 = rand_value();
+            for depth in 0..SMT_DEPTH {
+                let value = key.checked_shr((SMT_DEPTH - depth) as u32).unwrap_or(0);
+                let side = (key >> (SMT_DEPTH - depth - 1)) & 1;
+                counter += 1;
+                let digest = RpoDigest::new([Felt::new(counter), ZERO, ZERO, ZERO]);
+                children.entry((depth, value)).or_default()[side as usize] = Some(digest);
+            }
+        }
+        let empty_roots = Rpo256::empty_subtree_roots(SMT_DEPTH);
+        let nodes: Vec<(NodeIndex, InnerNode<RpoDigest>)> = children
+            .into_iter()
+            .map(|((depth, value), [left, right])| {
+                let empty_root = empty_roots[depth as usize + 1];
+                let node = InnerNode {
+                    left: left.unwrap_or(empty_root),
+                    right: right.unwrap_or(empty_root),
+                };
+                (NodeIndex::new_unchecked(depth, value), node)
+            })
+            .collect();
+
+        let (arena, arena_bytes) = allocated_bytes(|| {
+            let mut arena = NodeArena::<SMT_DEPTH, Rpo256>::new();
+            for (index, node) in nodes.iter() {
+                arena.insert(*index, node.clone()).unwrap();
+            }
+            arena
+        });
+        assert_eq!(arena.len(), nodes.len());
+
+        let (baseline, baseline_bytes) = allocated_bytes(|| {
+            let mut levels = vec![BTreeMap::<u64, [u64; 2]>::new(); SMT_DEPTH as usize];
+            let mut digests = Vec::<RpoDigest>::new();
+            for (index, node) in nodes.iter() {
+                let empty_root = empty_roots[index.depth() as usize + 1];
+                let handles = [node.left, node.right].map(|digest| {
+                    if digest == empty_root {
+                        index.depth() as u64 + 1
+                    } else {
+                        digests.push(digest);
+                        (digests.len() + SMT_DEPTH as usize) as u64
+                    }
+                });
+                levels[index.depth() as usize].insert(index.value(), handles);
+            }
+            (levels, digests)
+        });
+        assert_eq!(baseline.1.len(), arena.num_digests());
+
+        println!(
+            "{} inner nodes and {} digests for {NUM_LEAVES} leaves: {} bytes per leaf for the \
+             arena, {} bytes per leaf for the baseline",
+            nodes.len(),
+            arena.num_digests(),
+            arena_bytes / NUM_LEAVES,
+            baseline_bytes / NUM_LEAVES,
+        );
+        assert!(arena_bytes < baseline_bytes);
+    }
+
+    /// Returns the result of the provided function and the number of bytes allocated by it on the
+    /// current thread which have not been deallocated.
+    #[cfg(feature = "std")]
+    fn allocated_bytes<T>(f: impl FnOnce() -> T) -> (T, usize) {
+        let start = allocator::allocated();
+        let result = f();
+        (result, (allocator::allocated() - start) as usize)
+    }
+
+    /// A global allocator which counts the bytes allocated by every thread, such that the memory
+    /// used by a data structure can be measured while other tests run on other threads.
+    #[cfg(feature = "std")]
+    mod allocator {
+        use core::cell::Cell;
+        use std::{
+            alloc::{GlobalAlloc, Layout, System},
+            thread_local,
+        };
+
+        thread_local! {
+            static ALLOCATED: Cell<isize> = const { Cell::new(0) };
+        }
+
+        /// Returns the number of bytes allocated on the current thread, minus the number of bytes
+        /// deallocated on it.
+        pub fn allocated() -> isize {
+            ALLOCATED.with(Cell::get)
+        }
+
+        fn record(bytes: isize) {
+            // the counter is unavailable while the thread is being torn down
+            let _ = ALLOCATED.try_with(|allocated| allocated.set(allocated.get() + bytes));
+        }
+
+        struct CountingAllocator;
+
+        unsafe impl GlobalAlloc for CountingAllocator {
+            unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+                record(layout.size() as isize);
+                System.alloc(layout)
+            }
+
+            unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+                record(-(layout.size() as isize));
+                System.dealloc(ptr, layout)
+            }
+
+            unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+                record(new_size as isize - layout.size() as isize);
+                System.realloc(ptr, layout, new_size)
+            }
+        }
+
+        #[global_allocator]
+        static GLOBAL: CountingAllocator = CountingAllocator;
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
 // =================================================================================================
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub enum SmtLeafError {
+pub enum SmtLeafError<const DEPTH: u8 = SMT_DEPTH> {
     InconsistentKeys {
         entries: Vec<(RpoDigest, Word)>,
         key_1: RpoDigest,
@@ -20,18 +20,18 @@ pub enum SmtLeafError {
     InvalidNumEntriesForMultiple(usize),
     SingleKeyInconsistentWithLeafIndex {
         key: RpoDigest,
-        leaf_index: LeafIndex<SMT_DEPTH>,
+        leaf_index: LeafIndex<DEPTH>,
     },
     MultipleKeysInconsistentWithLeafIndex {
-        leaf_index_from_keys: LeafIndex<SMT_DEPTH>,
-        leaf_index_supplied: LeafIndex<SMT_DEPTH>,
+        leaf_index_from_keys: LeafIndex<DEPTH>,
+        leaf_index_supplied: LeafIndex<DEPTH>,
     },
 }
 
 #[cfg(feature = "std")]
-impl std::error::Error for SmtLeafError {}
+impl<const DEPTH: u8> std::error::Error for SmtLeafError<DEPTH> {}
 
-impl fmt::Display for SmtLeafError {
+impl<const DEPTH: u8> fmt::Display for SmtLeafError<DEPTH> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use SmtLeafError::*;
         match self {
@@ -69,6 +69,9 @@ impl fmt::Display for SmtLeafError {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SmtProofError {
     InvalidPathLength(usize),
+    InvalidNumSiblings { expected: usize, actual: usize },
+    NoLeaves,
+    UnsortedLeaves,
 }
 
 #[cfg(feature = "std")]
@@ -79,7 +82,20 @@ impl fmt::Display for SmtProofError {
         use SmtProofError::*;
         match self {
             InvalidPathLength(path_length) => {
-                write!(f, "Invalid Merkle path length. Expected {SMT_DEPTH}, got {path_length}")
+                write!(
+                    f,
+                    "Invalid Merkle path length. Expected the depth of the tree, got {path_length}"
+                )
+            }
+            InvalidNumSiblings { expected, actual } => {
+                write!(
+                    f,
+                    "Invalid number of siblings in multiproof. Expected {expected}, got {actual}"
+                )
+            }
+            NoLeaves => write!(f, "Multiproof must contain at least one leaf"),
+            UnsortedLeaves => {
+                write!(f, "Leaves of multiproof must be sorted by their indices without duplicates")
             }
         }
     }
@@ -0,0 +1,122 @@
+use alloc::sync::Arc;
+
+use super::{
+    MemorySmtStore, MutationSet, Rpo256, RpoDigest, Smt, SmtHasher, SmtLeaf, SmtProof, SmtStore,
+    Word, SMT_DEPTH,
+};
+
+// FROZEN SMT
+// ================================================================================================
+
+/// An immutable snapshot of an [Smt] which can be shared between threads.
+///
+/// Cloning a snapshot is cheap, since all clones refer to the same tree. The tree cannot be
+/// modified through a snapshot, and thus any number of threads can read from it concurrently
+/// (e.g., via [FrozenSmt::open()] and [FrozenSmt::get_value()]) without locking.
+///
+/// A writer prepares the next version of the tree by computing the changes against the snapshot
+/// via [FrozenSmt::compute_mutations()], while the readers continue to use the snapshot. The
+/// changes are then applied to a mutable copy of the tree (see [FrozenSmt::into_smt()]), which is
+/// published to the readers as a new snapshot via [Smt::freeze()]. A snapshot of a tree which
+/// remains mutable is taken via [Smt::snapshot()].
+#[derive(Debug, PartialEq, Eq)]
+pub struct FrozenSmt<
+    const DEPTH: u8 = SMT_DEPTH,
+    H: SmtHasher = Rpo256,
+    S: SmtStore<DEPTH, H> = MemorySmtStore<DEPTH, H>,
+> {
+    tree: Arc<Smt<DEPTH, H, S>>,
+}
+
+impl<const DEPTH: u8, H: SmtHasher, S: SmtStore<DEPTH, H>> FrozenSmt<DEPTH, H, S> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new snapshot of the provided tree.
+    pub fn new(tree: Smt<DEPTH, H, S>) -> Self {
+        Self { tree: Arc::new(tree) }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> H::Digest {
+        self.tree.root()
+    }
+
+    /// Returns the leaf to which `key` maps.
+    pub fn get_leaf(&self, key: &RpoDigest) -> SmtLeaf<DEPTH> {
+        self.tree.get_leaf(key)
+    }
+
+    /// Returns the value associated with `key`.
+    pub fn get_value(&self, key: &RpoDigest) -> Word {
+        self.tree.get_value(key)
+    }
+
+    /// Returns an opening of the leaf associated with `key`.
+    pub fn open(&self, key: &RpoDigest) -> SmtProof<DEPTH, H> {
+        self.tree.open(key)
+    }
+
+    /// Computes the changes which inserting the provided key-value pairs would make to the tree,
+    /// without applying them (see [Smt::compute_mutations()]).
+    pub fn compute_mutations(
+        &self,
+        kv_pairs: impl IntoIterator<Item = (RpoDigest, Word)>,
+    ) -> MutationSet<DEPTH, RpoDigest, Word, H::Digest> {
+        self.tree.compute_mutations(kv_pairs)
+    }
+
+    /// Returns a reference to the tree, which provides the remaining read-only methods of [Smt].
+    pub fn as_smt(&self) -> &Smt<DEPTH, H, S> {
+        &self.tree
+    }
+
+    /// Returns the number of snapshots which refer to the same tree as this snapshot (including
+    /// this snapshot).
+    pub fn num_handles(&self) -> usize {
+        Arc::strong_count(&self.tree)
+    }
+
+    // CONVERSIONS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a mutable tree with the contents of this snapshot.
+    ///
+    /// If this is the only snapshot which refers to the tree, the tree is returned without being
+    /// copied. Otherwise, the tree is cloned.
+    pub fn into_smt(self) -> Smt<DEPTH, H, S>
+    where
+        S: Clone,
+    {
+        Arc::try_unwrap(self.tree).unwrap_or_else(|tree| Smt::clone(&tree))
+    }
+
+    /// Returns a mutable tree with the contents of this snapshot, which can be changed without
+    /// affecting the snapshot.
+    ///
+    /// With the default store, the tree shares the nodes of the snapshot instead of copying them,
+    /// and thus any number of forks of a snapshot can be created cheaply.
+    pub fn fork(&self) -> Smt<DEPTH, H, S>
+    where
+        S: Clone,
+    {
+        Smt::clone(&self.tree)
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher, S: SmtStore<DEPTH, H>> Clone for FrozenSmt<DEPTH, H, S> {
+    fn clone(&self) -> Self {
+        Self { tree: Arc::clone(&self.tree) }
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher, S: SmtStore<DEPTH, H>> From<Smt<DEPTH, H, S>>
+    for FrozenSmt<DEPTH, H, S>
+{
+    fn from(tree: Smt<DEPTH, H, S>) -> Self {
+        Self::new(tree)
+    }
+}
@@ -0,0 +1,283 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+
+use super::{
+    EmptySubtreeRoots, InnerNode, LeafIndex, MemorySmtStore, MerkleError, MerklePath, MutationSet,
+    NodeIndex, RpoDigest, Smt, SmtLeaf, SmtProof, SmtStore, Word, EMPTY_WORD, SMT_DEPTH,
+};
+use crate::merkle::{Mmr, MmrPeaks, MmrProof};
+
+// SMT WITH HISTORY
+// ================================================================================================
+
+/// An [Smt] which keeps track of its past versions, such that the values of keys can be proven
+/// against the roots of earlier versions of the tree.
+///
+/// Every update of the tree (via [SmtWithHistory::insert()] or
+/// [SmtWithHistory::apply_mutations()]) creates a new version, with the initial tree being
+/// version 0. The roots of all versions are accumulated in an [Mmr], whose peaks commit to the
+/// entire history of the tree. An opening of a key at some version (see
+/// [SmtWithHistory::open_at()]) thus proves to a light client which knows only the peaks that a
+/// value was associated with the key at the root of that version.
+///
+/// For every version, only the previous states of the inner nodes and leaves changed by the
+/// update are recorded. The memory overhead of a version is thus proportional to the number of
+/// changed leaves times the depth of the tree.
+#[derive(Debug, Clone)]
+pub struct SmtWithHistory<const DEPTH: u8 = SMT_DEPTH> {
+    tree: Smt<DEPTH>,
+    roots: Mmr,
+    /// The versions at which the inner nodes were replaced, together with the nodes they held
+    /// before (with `None` standing for the roots of empty subtrees), sorted by version.
+    nodes: BTreeMap<NodeIndex, Vec<(usize, Option<InnerNode>)>>,
+    /// The versions at which the leaves were replaced, together with the leaves they held
+    /// before, sorted by version.
+    leaves: BTreeMap<u64, Vec<(usize, Option<SmtLeaf<DEPTH>>)>>,
+}
+
+impl<const DEPTH: u8> SmtWithHistory<DEPTH> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [SmtWithHistory] with the provided tree as its initial version.
+    pub fn new(tree: Smt<DEPTH>) -> Self {
+        let mut roots = Mmr::new();
+        roots.add(tree.root());
+
+        Self {
+            tree,
+            roots,
+            nodes: BTreeMap::new(),
+            leaves: BTreeMap::new(),
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the number of the current version of the tree.
+    pub fn version(&self) -> usize {
+        self.roots.forest() - 1
+    }
+
+    /// Returns the root of the current version of the tree.
+    pub fn root(&self) -> RpoDigest {
+        self.tree.root()
+    }
+
+    /// Returns the root of the specified version of the tree, or None if the version does not
+    /// exist yet.
+    pub fn root_at(&self, version: usize) -> Option<RpoDigest> {
+        self.roots.get(version).ok()
+    }
+
+    /// Returns the peaks of the [Mmr] of the roots of all versions of the tree.
+    pub fn peaks(&self) -> MmrPeaks {
+        self.roots.peaks(self.roots.forest()).expect("forest of the MMR is valid")
+    }
+
+    /// Returns a reference to the current version of the tree, which provides the read-only
+    /// methods of [Smt].
+    pub fn as_smt(&self) -> &Smt<DEPTH> {
+        &self.tree
+    }
+
+    /// Returns the value associated with `key` at the specified version of the tree, or None if
+    /// the version does not exist yet.
+    pub fn get_value_at(&self, key: &RpoDigest, version: usize) -> Option<Word> {
+        if version > self.version() {
+            return None;
+        }
+
+        let leaf_index = LeafIndex::<DEPTH>::from(key).value();
+        let value = self.leaf_at(leaf_index, version).and_then(|leaf| leaf.get_value(key));
+        Some(value.unwrap_or(EMPTY_WORD))
+    }
+
+    /// Returns an opening of the leaf associated with `key` at the specified version of the tree,
+    /// or None if the version does not exist yet.
+    ///
+    /// The opening proves the state of the leaf against the root of the version, and the root of
+    /// the version against the current peaks of the tree (see [Self::peaks()]).
+    pub fn open_at(&self, key: &RpoDigest, version: usize) -> Option<SmtHistoryProof<DEPTH>> {
+        let root_proof = self.roots.open(version, self.roots.forest()).ok()?;
+
+        let leaf_index = LeafIndex::<DEPTH>::from(key);
+        let leaf = self
+            .leaf_at(leaf_index.value(), version)
+            .unwrap_or_else(|| SmtLeaf::new_empty(leaf_index));
+
+        let mut index: NodeIndex = leaf_index.into();
+        let mut path = Vec::with_capacity(DEPTH as usize);
+        for _ in 0..DEPTH {
+            let is_right = index.is_value_odd();
+            index.move_up();
+            let InnerNode { left, right } = self.node_at(index, version);
+            path.push(if is_right { left } else { right });
+        }
+
+        Some(SmtHistoryProof {
+            smt_proof: SmtProof::new_unchecked(MerklePath::new(path), leaf),
+            root_proof,
+        })
+    }
+
+    // STATE MUTATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Inserts a value at the specified key as a new version of the tree, returning the previous
+    /// value associated with that key.
+    pub fn insert(&mut self, key: RpoDigest, value: Word) -> Word {
+        let old_value = self.tree.get_value(&key);
+        let mutations = self.tree.compute_mutations([(key, value)]);
+        self.apply_mutations(mutations)
+            .expect("mutations computed against the current tree");
+        old_value
+    }
+
+    /// Applies the provided mutations to the tree as a new version of the tree.
+    ///
+    /// # Errors
+    /// Returns an error if the mutations were computed against a tree with a root different from
+    /// the root of the current version of the tree.
+    pub fn apply_mutations(
+        &mut self,
+        mutations: MutationSet<DEPTH, RpoDigest, Word>,
+    ) -> Result<(), MerkleError> {
+        if mutations.old_root() != self.root() {
+            return Err(MerkleError::ConflictingMutationRoots(
+                mutations.old_root().as_bytes(),
+                self.root().as_bytes(),
+            ));
+        }
+
+        // record the states of the nodes and leaves which are about to be replaced
+        let next_version = self.version() + 1;
+        let store = self.tree.store();
+        for &index in mutations.node_mutations.keys() {
+            let node = store.get_node(index);
+            self.nodes.entry(index).or_default().push((next_version, node));
+        }
+        let leaf_indices: BTreeSet<u64> = mutations
+            .new_pairs()
+            .keys()
+            .map(|key| LeafIndex::<DEPTH>::from(key).value())
+            .collect();
+        for index in leaf_indices {
+            let leaf = store.get_leaf(index);
+            self.leaves.entry(index).or_default().push((next_version, leaf));
+        }
+
+        self.tree.apply_mutations(mutations)?;
+        self.roots.add(self.tree.root());
+
+        Ok(())
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the inner node at the specified index at the specified version of the tree.
+    fn node_at(&self, index: NodeIndex, version: usize) -> InnerNode {
+        let node = match self.nodes.get(&index).and_then(|history| state_at(history, version)) {
+            Some(node) => node.clone(),
+            None => self.tree.store().get_node(index),
+        };
+
+        node.unwrap_or_else(|| {
+            let empty_root = *EmptySubtreeRoots::entry(DEPTH, index.depth() + 1);
+            InnerNode { left: empty_root, right: empty_root }
+        })
+    }
+
+    /// Returns the leaf at the specified index at the specified version of the tree, if the leaf
+    /// is not empty.
+    fn leaf_at(&self, index: u64, version: usize) -> Option<SmtLeaf<DEPTH>> {
+        match self.leaves.get(&index).and_then(|history| state_at(history, version)) {
+            Some(leaf) => leaf.clone(),
+            None => self.tree.store().get_leaf(index),
+        }
+    }
+}
+
+impl<const DEPTH: u8> Default for SmtWithHistory<DEPTH> {
+    fn default() -> Self {
+        Self::new(Smt::with_store(MemorySmtStore::new()))
+    }
+}
+
+impl<const DEPTH: u8> From<Smt<DEPTH>> for SmtWithHistory<DEPTH> {
+    fn from(tree: Smt<DEPTH>) -> Self {
+        Self::new(tree)
+    }
+}
+
+// SMT HISTORY PROOF
+// ================================================================================================
+
+/// A proof which can be used to assert that a key-value pair was in a past version of an
+/// [SmtWithHistory].
+///
+/// The proof consists of an opening of the leaf at the version, and of an opening of the root of
+/// the version in the [Mmr] of the roots of all versions of the tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmtHistoryProof<const DEPTH: u8 = SMT_DEPTH> {
+    smt_proof: SmtProof<DEPTH>,
+    root_proof: MmrProof,
+}
+
+impl<const DEPTH: u8> SmtHistoryProof<DEPTH> {
+    // PROOF VERIFIER
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if the provided key-value pair was in the version of the tree to which this
+    /// proof resolves, and the root of this version is part of the history of the tree committed
+    /// to by the provided peaks.
+    ///
+    /// The peaks must be the peaks of the tree at the time the proof was created. A light client
+    /// which already trusts the root of the version can instead verify the proof of the leaf
+    /// against this root via [SmtProof::verify_membership()].
+    pub fn verify_membership(&self, key: &RpoDigest, value: &Word, peaks: &MmrPeaks) -> bool {
+        let root_proof = &self.root_proof;
+        self.smt_proof.get(key) == Some(*value)
+            && root_proof.position < root_proof.forest
+            && peaks.num_leaves() == root_proof.forest
+            && peaks.verify(self.smt_proof.compute_root(), root_proof.clone())
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the version of the tree to which this proof resolves.
+    pub fn version(&self) -> usize {
+        self.root_proof.position
+    }
+
+    /// Returns the opening of the leaf at the version.
+    pub fn smt_proof(&self) -> &SmtProof<DEPTH> {
+        &self.smt_proof
+    }
+
+    /// Returns the opening of the root of the version in the [Mmr] of the roots.
+    pub fn root_proof(&self) -> &MmrProof {
+        &self.root_proof
+    }
+
+    /// Consume the proof and returns its parts.
+    pub fn into_parts(self) -> (SmtProof<DEPTH>, MmrProof) {
+        (self.smt_proof, self.root_proof)
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the state held at the specified version, given the versions at which the state was
+/// replaced together with the previous states, or None if the state was not replaced after the
+/// version.
+fn state_at<T>(history: &[(usize, T)], version: usize) -> Option<&T> {
+    let position = history.partition_point(|&(replaced_at, _)| replaced_at <= version);
+    history.get(position).map(|(_, state)| state)
+}
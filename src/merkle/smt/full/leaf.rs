@@ -1,18 +1,20 @@
 use alloc::{string::ToString, vec::Vec};
 use core::cmp::Ordering;
 
-use super::{Felt, LeafIndex, Rpo256, RpoDigest, SmtLeafError, Word, EMPTY_WORD, SMT_DEPTH};
+use super::{
+    ElementHasher, Felt, LeafIndex, Rpo256, RpoDigest, SmtLeafError, Word, EMPTY_WORD, SMT_DEPTH,
+};
 use crate::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub enum SmtLeaf {
-    Empty(LeafIndex<SMT_DEPTH>),
+pub enum SmtLeaf<const DEPTH: u8 = SMT_DEPTH> {
+    Empty(LeafIndex<DEPTH>),
     Single((RpoDigest, Word)),
     Multiple(Vec<(RpoDigest, Word)>),
 }
 
-impl SmtLeaf {
+impl<const DEPTH: u8> SmtLeaf<DEPTH> {
     // CONSTRUCTORS
     // ---------------------------------------------------------------------------------------------
 
@@ -24,14 +26,14 @@ impl SmtLeaf {
     ///     different from `leaf_index`
     pub fn new(
         entries: Vec<(RpoDigest, Word)>,
-        leaf_index: LeafIndex<SMT_DEPTH>,
-    ) -> Result<Self, SmtLeafError> {
+        leaf_index: LeafIndex<DEPTH>,
+    ) -> Result<Self, SmtLeafError<DEPTH>> {
         match entries.len() {
             0 => Ok(Self::new_empty(leaf_index)),
             1 => {
                 let (key, value) = entries[0];
 
-                if LeafIndex::<SMT_DEPTH>::from(key) != leaf_index {
+                if LeafIndex::<DEPTH>::from(key) != leaf_index {
                     return Err(SmtLeafError::SingleKeyInconsistentWithLeafIndex {
                         key,
                         leaf_index,
@@ -58,7 +60,7 @@ impl SmtLeaf {
     }
 
     /// Returns a new empty leaf with the specified leaf index
-    pub fn new_empty(leaf_index: LeafIndex<SMT_DEPTH>) -> Self {
+    pub fn new_empty(leaf_index: LeafIndex<DEPTH>) -> Self {
         Self::Empty(leaf_index)
     }
 
@@ -73,7 +75,7 @@ impl SmtLeaf {
     ///
     /// # Errors
     ///   - Returns an error if 2 keys in `entries` map to a different leaf index
-    pub fn new_multiple(entries: Vec<(RpoDigest, Word)>) -> Result<Self, SmtLeafError> {
+    pub fn new_multiple(entries: Vec<(RpoDigest, Word)>) -> Result<Self, SmtLeafError<DEPTH>> {
         if entries.len() < 2 {
             return Err(SmtLeafError::InvalidNumEntriesForMultiple(entries.len()));
         }
@@ -83,10 +85,10 @@ impl SmtLeaf {
             let mut keys = entries.iter().map(|(key, _)| key);
 
             let first_key = *keys.next().expect("ensured at least 2 entries");
-            let first_leaf_index: LeafIndex<SMT_DEPTH> = first_key.into();
+            let first_leaf_index: LeafIndex<DEPTH> = first_key.into();
 
             for &next_key in keys {
-                let next_leaf_index: LeafIndex<SMT_DEPTH> = next_key.into();
+                let next_leaf_index: LeafIndex<DEPTH> = next_key.into();
 
                 if next_leaf_index != first_leaf_index {
                     return Err(SmtLeafError::InconsistentKeys {
@@ -110,7 +112,7 @@ impl SmtLeaf {
     }
 
     /// Returns the leaf's index in the [`super::Smt`]
-    pub fn index(&self) -> LeafIndex<SMT_DEPTH> {
+    pub fn index(&self) -> LeafIndex<DEPTH> {
         match self {
             SmtLeaf::Empty(leaf_index) => *leaf_index,
             SmtLeaf::Single((key, _)) => key.into(),
@@ -145,6 +147,22 @@ impl SmtLeaf {
         }
     }
 
+    /// Computes the hash of the leaf with the hash function `H`.
+    ///
+    /// An empty leaf hashes to the default digest, and any other leaf to the hash of the elements
+    /// of its key-value pairs. For [Rpo256], the result is the same as the result of
+    /// [Self::hash()].
+    pub fn hash_with<H: ElementHasher<BaseField = Felt>>(&self) -> H::Digest {
+        match self {
+            SmtLeaf::Empty(_) => H::Digest::default(),
+            _ => {
+                let elements: Vec<Felt> =
+                    self.entries_slice().iter().copied().flat_map(kv_to_elements).collect();
+                H::hash_elements(&elements)
+            }
+        }
+    }
+
     // ITERATORS
     // ---------------------------------------------------------------------------------------------
 
@@ -157,6 +175,15 @@ impl SmtLeaf {
         }
     }
 
+    /// Returns the key-value pairs in the leaf as a slice, without allocating.
+    pub(super) fn entries_slice(&self) -> &[(RpoDigest, Word)] {
+        match self {
+            SmtLeaf::Empty(_) => &[],
+            SmtLeaf::Single(kv_pair) => core::slice::from_ref(kv_pair),
+            SmtLeaf::Multiple(kv_pairs) => kv_pairs,
+        }
+    }
+
     // CONVERSIONS
     // ---------------------------------------------------------------------------------------------
 
@@ -303,7 +330,7 @@ impl SmtLeaf {
     }
 }
 
-impl Serializable for SmtLeaf {
+impl<const DEPTH: u8> Serializable for SmtLeaf<DEPTH> {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         // Write: num entries
         self.num_entries().write_into(target);
@@ -320,16 +347,14 @@ impl Serializable for SmtLeaf {
     }
 }
 
-impl Deserializable for SmtLeaf {
+impl<const DEPTH: u8> Deserializable for SmtLeaf<DEPTH> {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         // Read: num entries
         let num_entries = source.read_u64()?;
 
         // Read: leaf index
-        let leaf_index: LeafIndex<SMT_DEPTH> = {
-            let value = source.read_u64()?;
-            LeafIndex::new_max_depth(value)
-        };
+        let leaf_index: LeafIndex<DEPTH> = LeafIndex::new(source.read_u64()?)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))?;
 
         // Read: entries
         let mut entries: Vec<(RpoDigest, Word)> = Vec::new();
@@ -358,7 +383,7 @@ fn kv_to_elements((key, value): (RpoDigest, Word)) -> impl Iterator<Item = Felt>
 
 /// Compares two keys, compared element-by-element using their integer representations starting with
 /// the most significant element.
-fn cmp_keys(key_1: RpoDigest, key_2: RpoDigest) -> Ordering {
+pub(super) fn cmp_keys(key_1: RpoDigest, key_2: RpoDigest) -> Ordering {
     for (v1, v2) in key_1.iter().zip(key_2.iter()).rev() {
         let v1 = v1.as_int();
         let v2 = v2.as_int();
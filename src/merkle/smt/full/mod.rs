@@ -1,18 +1,44 @@
 use super::{
-    EmptySubtreeRoots, Felt, InnerNode, InnerNodeInfo, LeafIndex, MerkleError, MerklePath,
-    NodeIndex, Rpo256, RpoDigest, SparseMerkleTree, Word, EMPTY_WORD,
+    Digest, ElementHasher, EmptySubtreeRoots, Felt, Hasher, InnerNode, InnerNodeInfo, LeafIndex,
+    MerkleError, MerklePath, MutationSet, NodeIndex, NodeMutation, Rpo256, RpoDigest, SmtHasher,
+    SparseMerkleTree, Word, EMPTY_WORD, SMT_MAX_DEPTH, SMT_MIN_DEPTH,
+};
+#[cfg(feature = "parallel")]
+use crate::ZERO;
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec::Vec,
+};
+use core::{
+    cmp::Ordering,
+    fmt,
+    ops::{Bound, RangeBounds},
 };
-use alloc::collections::{BTreeMap, BTreeSet};
+
+mod arena;
 
 mod error;
 pub use error::{SmtLeafError, SmtProofError};
 
 mod leaf;
+use leaf::cmp_keys;
 pub use leaf::SmtLeaf;
 
+mod multiproof;
+pub use multiproof::SmtMultiProof;
+
 mod proof;
 pub use proof::SmtProof;
 
+mod frozen;
+pub use frozen::FrozenSmt;
+
+mod history;
+pub use history::{SmtHistoryProof, SmtWithHistory};
+
+mod store;
+pub use store::{KvDatabase, KvDatabaseStore, MemorySmtStore, SmtStore};
+
 #[cfg(test)]
 mod tests;
 
@@ -21,24 +47,77 @@ mod tests;
 
 pub const SMT_DEPTH: u8 = 64;
 
+// SMT DELTA
+// ================================================================================================
+
+/// The changes which transform one [Smt] into another, as computed by [Smt::diff()].
+///
+/// A delta contains the key-value pairs which differ between the two trees (with removed keys
+/// mapped to [EMPTY_WORD]) and the inner nodes of the target tree on the paths to the changed
+/// leaves. Its size is thus proportional to the number of changes rather than to the size of the
+/// trees, and it can be serialized and sent to a replica which then brings its tree up to date via
+/// [Smt::apply_delta()].
+pub type SmtDelta<const DEPTH: u8 = SMT_DEPTH, H = Rpo256> =
+    MutationSet<DEPTH, RpoDigest, Word, <H as Hasher>::Digest>;
+
+// REVERSION
+// ================================================================================================
+
+/// The changes which undo an insertion into an [Smt], as returned by [Smt::insert_with_undo()].
+///
+/// A reversion contains the previous value of the inserted key and the previous inner nodes on
+/// the path from its leaf to the root. Restoring the previous state of the tree via
+/// [Smt::apply_reversion()] thus requires no hashing. The reversions of consecutive insertions
+/// must be applied in the reverse order of the insertions.
+pub type Reversion<const DEPTH: u8 = SMT_DEPTH, H = Rpo256> =
+    MutationSet<DEPTH, RpoDigest, Word, <H as Hasher>::Digest>;
+
 // SMT
 // ================================================================================================
 
 /// Sparse Merkle tree mapping 256-bit keys to 256-bit values. Both keys and values are represented
 /// by 4 field elements.
 ///
-/// All leaves sit at depth 64. The most significant element of the key is used to identify the leaf to
-/// which the key maps.
+/// All leaves sit at depth `DEPTH`, which is 64 by default. The `DEPTH` most significant bits of the
+/// most significant element of the key are used to identify the leaf to which the key maps. A tree
+/// of a smaller depth thus has shorter proofs and hashes fewer nodes per update, at the cost of
+/// more keys sharing a leaf.
 ///
 /// A leaf is either empty, or holds one or more key-value pairs. An empty leaf hashes to the empty
 /// word. Otherwise, a leaf hashes to the hash of its key-value pairs, ordered by key first, value
 /// second.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// The nodes of the tree are computed with the hash function `H`, which is [Rpo256] by default.
+/// Trees computed with other hash functions (e.g., [Keccak256](crate::hash::keccak::Keccak256)
+/// for verification by Ethereum smart contracts) hold the same keys and values, but their roots,
+/// inner nodes and proofs consist of the digests of `H` (see [SmtHasher]).
+///
+/// The leaves and the inner nodes of the tree are kept in an [SmtStore], which is in memory by
+/// default. A tree with a different store, depth other than 64, or hash function other than
+/// [Rpo256], is created via [Smt::with_store()].
+///
+/// With the default store, a tree is cloned without copying its nodes: the clone shares them with
+/// the original tree, and both trees only keep their own copies of the nodes they change
+/// afterwards. [Smt::snapshot()] thus cheaply forks the state of the tree, e.g., to build a
+/// candidate block on top of it.
+#[derive(Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub struct Smt {
-    root: RpoDigest,
-    leaves: BTreeMap<u64, SmtLeaf>,
-    inner_nodes: BTreeMap<NodeIndex, InnerNode>,
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "H::Digest: serde::Serialize, S: serde::Serialize",
+        deserialize = "H::Digest: serde::Deserialize<'de>, S: serde::Deserialize<'de>"
+    ))
+)]
+pub struct Smt<
+    const DEPTH: u8 = SMT_DEPTH,
+    H: SmtHasher = Rpo256,
+    S: SmtStore<DEPTH, H> = MemorySmtStore<DEPTH, H>,
+> {
+    root: H::Digest,
+    store: S,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    empty_roots: EmptyRoots<DEPTH, H>,
 }
 
 impl Smt {
@@ -54,13 +133,7 @@ impl Smt {
     ///
     /// All leaves in the returned tree are set to [Self::EMPTY_VALUE].
     pub fn new() -> Self {
-        let root = *EmptySubtreeRoots::entry(SMT_DEPTH, 0);
-
-        Self {
-            root,
-            leaves: BTreeMap::new(),
-            inner_nodes: BTreeMap::new(),
-        }
+        Self::with_store(MemorySmtStore::new())
     }
 
     /// Returns a new [Smt] instantiated with leaves set as specified by the provided entries.
@@ -95,62 +168,293 @@ impl Smt {
         Ok(tree)
     }
 
+    /// Returns a new [Smt] instantiated with leaves set as specified by the provided entries,
+    /// computing the inner nodes of the tree concurrently.
+    ///
+    /// The result is the same as the result of [Self::with_entries()]. However, instead of
+    /// inserting the entries one by one (and thus hashing every path from a leaf to the root), the
+    /// leaves are partitioned by the top 8 bits of their indices, and the subtrees rooted at
+    /// depth 8 are built concurrently using rayon one level at a time. This way, every inner node
+    /// is hashed exactly once, and the nodes of a level are merged in batches.
+    ///
+    /// # Errors
+    /// Returns an error if the provided entries contain multiple values for the same key.
+    #[cfg(feature = "parallel")]
+    pub fn with_entries_parallel(
+        entries: impl IntoIterator<Item = (RpoDigest, Word)>,
+    ) -> Result<Self, MerkleError> {
+        use rayon::prelude::*;
+
+        // populate the leaves of an empty tree; duplicates are detected in the same way as in
+        // `with_entries()`
+        let mut tree = Self::new();
+        let mut key_set_to_zero = BTreeSet::new();
+        for (key, value) in entries {
+            let old_value = tree.insert_value(key, value).unwrap_or(EMPTY_WORD);
+
+            if old_value != EMPTY_WORD || key_set_to_zero.contains(&key) {
+                return Err(MerkleError::DuplicateValuesForIndex(
+                    LeafIndex::<SMT_DEPTH>::from(key).value(),
+                ));
+            }
+
+            if value == EMPTY_WORD {
+                key_set_to_zero.insert(key);
+            };
+        }
+
+        if tree.store.num_leaves() == 0 {
+            return Ok(tree);
+        }
+
+        let leaves: Vec<(u64, &SmtLeaf)> = tree.store.iter_leaves().collect();
+        let leaf_hashes: NodeHashes =
+            leaves.par_iter().map(|&(index, leaf)| (index, leaf.hash())).collect();
+
+        // build the subtrees rooted at `SUBTREE_DEPTH` concurrently, and then build the top of
+        // the tree from their roots
+        let subtrees = split_by_prefix(&leaf_hashes, SMT_DEPTH - SUBTREE_DEPTH);
+        let (subtree_nodes, subtree_roots): (Vec<_>, Vec<_>) = subtrees
+            .par_iter()
+            .map(|leaves| {
+                let (inner_nodes, roots) = build_subtree(leaves.to_vec(), SMT_DEPTH, SUBTREE_DEPTH);
+                (inner_nodes, roots[0])
+            })
+            .unzip();
+        let (top_nodes, root) = build_subtree(subtree_roots, SUBTREE_DEPTH, 0);
+
+        for (index, node) in subtree_nodes.into_iter().flatten().chain(top_nodes) {
+            tree.store.put_node(index, node);
+        }
+        tree.root = root[0].1;
+        Ok(tree)
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Smt<DEPTH, H> {
+    // ITERATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an iterator over the leaves of this [Smt].
+    pub fn leaves(&self) -> impl Iterator<Item = (LeafIndex<DEPTH>, &SmtLeaf<DEPTH>)> {
+        self.store
+            .iter_leaves()
+            .map(|(leaf_index, leaf)| (LeafIndex::new_unchecked(leaf_index), leaf))
+    }
+
+    /// Returns an iterator over the key-value pairs of this [Smt].
+    pub fn entries(&self) -> impl Iterator<Item = &(RpoDigest, Word)> {
+        self.leaves().flat_map(|(_, leaf)| leaf.entries())
+    }
+
+    /// Returns an iterator over the key-value pairs of this [Smt] in canonical key order.
+    ///
+    /// Keys are ordered by the integer representations of their elements, starting with the most
+    /// significant element. This is the order in which the key-value pairs of a leaf are hashed,
+    /// and thus the output is deterministic for a given set of entries regardless of the order in
+    /// which they were inserted.
+    pub fn entries_sorted(&self) -> impl Iterator<Item = &(RpoDigest, Word)> {
+        self.store.iter_leaves().flat_map(|(_, leaf)| leaf.entries_slice())
+    }
+
+    /// Returns an iterator over the key-value pairs of this [Smt] with keys in the provided range,
+    /// in canonical key order (see [Self::entries_sorted()]).
+    ///
+    /// The bounds of the range are interpreted in canonical key order as well, which differs from
+    /// the [Ord] implementation of [RpoDigest]. Only the leaves which may contain keys in the range
+    /// are visited. If the start of the range is greater than its end, the iterator is empty.
+    pub fn range(
+        &self,
+        range: impl RangeBounds<RpoDigest>,
+    ) -> impl Iterator<Item = &(RpoDigest, Word)> {
+        let start = range.start_bound().cloned();
+        let end = range.end_bound().cloned();
+
+        // the leaves which may contain keys in the range are selected by the most significant
+        // element of the bounds, and the bounds are then checked against every key in those leaves
+        let first_leaf = match start {
+            Bound::Included(key) | Bound::Excluded(key) => LeafIndex::<DEPTH>::from(key).value(),
+            Bound::Unbounded => 0,
+        };
+        let last_leaf = match end {
+            Bound::Included(key) | Bound::Excluded(key) => LeafIndex::<DEPTH>::from(key).value(),
+            Bound::Unbounded => u64::MAX,
+        };
+        let leaves = (first_leaf <= last_leaf)
+            .then(|| self.store.iter_leaves_in(first_leaf..=last_leaf))
+            .into_iter()
+            .flatten();
+
+        leaves.flat_map(|(_, leaf)| leaf.entries_slice()).filter(move |(key, _)| {
+            let after_start = match start {
+                Bound::Included(start) => cmp_keys(*key, start) != Ordering::Less,
+                Bound::Excluded(start) => cmp_keys(*key, start) == Ordering::Greater,
+                Bound::Unbounded => true,
+            };
+            let before_end = match end {
+                Bound::Included(end) => cmp_keys(*key, end) != Ordering::Greater,
+                Bound::Excluded(end) => cmp_keys(*key, end) == Ordering::Less,
+                Bound::Unbounded => true,
+            };
+            after_start && before_end
+        })
+    }
+}
+
+impl<const DEPTH: u8> Smt<DEPTH> {
+    /// Returns an iterator over the inner nodes of this [Smt].
+    pub fn inner_nodes(&self) -> impl Iterator<Item = InnerNodeInfo> + '_ {
+        self.store.iter_inner_nodes().map(|(_, e)| InnerNodeInfo {
+            value: e.hash(),
+            left: e.left,
+            right: e.right,
+        })
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher, S: SmtStore<DEPTH, H>> Smt<DEPTH, H, S> {
+    // CONSTANTS
+    // --------------------------------------------------------------------------------------------
+
+    /// Fails the compilation of any tree with a depth outside of the supported range.
+    const VALID_DEPTH: () = assert!(
+        SMT_MIN_DEPTH <= DEPTH && DEPTH <= SMT_MAX_DEPTH,
+        "depth of the tree must be between 1 and 64"
+    );
+
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new [Smt] which keeps its leaves and inner nodes in the provided store.
+    ///
+    /// The depth and the hash function of the tree are determined by the store, e.g.,
+    /// `MemorySmtStore::<32, Keccak256>::new()` yields an empty in-memory tree of depth 32 whose
+    /// nodes are computed with Keccak-256.
+    ///
+    /// The store may already contain a tree (e.g., if it is backed by a database which was
+    /// populated by a previous instance of the tree), in which case the root of the tree is
+    /// recomputed from the root node in the store.
+    pub fn with_store(store: S) -> Self {
+        let () = Self::VALID_DEPTH;
+
+        let empty_roots = EmptyRoots::default();
+        let root = match store.get_node(NodeIndex::root()) {
+            Some(node) => node.hash_with::<H>(),
+            None => empty_roots.get(0),
+        };
+
+        Self { root, store, empty_roots }
+    }
+
     // PUBLIC ACCESSORS
     // --------------------------------------------------------------------------------------------
 
     /// Returns the depth of the tree
     pub const fn depth(&self) -> u8 {
-        SMT_DEPTH
+        DEPTH
     }
 
     /// Returns the root of the tree
-    pub fn root(&self) -> RpoDigest {
-        <Self as SparseMerkleTree<SMT_DEPTH>>::root(self)
+    pub fn root(&self) -> H::Digest {
+        <Self as SparseMerkleTree<DEPTH>>::root(self)
     }
 
     /// Returns the leaf to which `key` maps
-    pub fn get_leaf(&self, key: &RpoDigest) -> SmtLeaf {
-        <Self as SparseMerkleTree<SMT_DEPTH>>::get_leaf(self, key)
+    pub fn get_leaf(&self, key: &RpoDigest) -> SmtLeaf<DEPTH> {
+        <Self as SparseMerkleTree<DEPTH>>::get_leaf(self, key)
     }
 
     /// Returns the value associated with `key`
     pub fn get_value(&self, key: &RpoDigest) -> Word {
-        let leaf_pos = LeafIndex::<SMT_DEPTH>::from(*key).value();
+        let leaf_pos = LeafIndex::<DEPTH>::from(*key).value();
 
-        match self.leaves.get(&leaf_pos) {
+        match self.store.get_leaf(leaf_pos) {
             Some(leaf) => leaf.get_value(key).unwrap_or_default(),
             None => EMPTY_WORD,
         }
     }
 
-    /// Returns an opening of the leaf associated with `key`. Conceptually, an opening is a Merkle
-    /// path to the leaf, as well as the leaf itself.
-    pub fn open(&self, key: &RpoDigest) -> SmtProof {
-        <Self as SparseMerkleTree<SMT_DEPTH>>::open(self, key)
+    /// Returns a reference to the store of this tree.
+    pub fn store(&self) -> &S {
+        &self.store
     }
 
-    // ITERATORS
-    // --------------------------------------------------------------------------------------------
+    /// Returns the store of this tree.
+    pub fn into_store(self) -> S {
+        self.store
+    }
 
-    /// Returns an iterator over the leaves of this [Smt].
-    pub fn leaves(&self) -> impl Iterator<Item = (LeafIndex<SMT_DEPTH>, &SmtLeaf)> {
-        self.leaves
-            .iter()
-            .map(|(leaf_index, leaf)| (LeafIndex::new_max_depth(*leaf_index), leaf))
+    /// Returns an immutable snapshot of this tree which can be shared between threads.
+    pub fn freeze(self) -> FrozenSmt<DEPTH, H, S> {
+        FrozenSmt::new(self)
     }
 
-    /// Returns an iterator over the key-value pairs of this [Smt].
-    pub fn entries(&self) -> impl Iterator<Item = &(RpoDigest, Word)> {
-        self.leaves().flat_map(|(_, leaf)| leaf.entries())
+    /// Returns an immutable snapshot of the current state of this tree, while the tree itself
+    /// remains mutable.
+    ///
+    /// With the default store, the snapshot shares the nodes of the tree instead of copying them.
+    /// Subsequent changes to the tree do not affect the snapshot, and a mutable fork of the
+    /// snapshot is obtained via [FrozenSmt::fork()].
+    pub fn snapshot(&self) -> FrozenSmt<DEPTH, H, S>
+    where
+        S: Clone,
+    {
+        FrozenSmt::new(self.clone())
     }
 
-    /// Returns an iterator over the inner nodes of this [Smt].
-    pub fn inner_nodes(&self) -> impl Iterator<Item = InnerNodeInfo> + '_ {
-        self.inner_nodes.values().map(|e| InnerNodeInfo {
-            value: e.hash(),
-            left: e.left,
-            right: e.right,
-        })
+    /// Returns an opening of the leaf associated with `key`. Conceptually, an opening is a Merkle
+    /// path to the leaf, as well as the leaf itself.
+    pub fn open(&self, key: &RpoDigest) -> SmtProof<DEPTH, H> {
+        <Self as SparseMerkleTree<DEPTH>>::open(self, key)
+    }
+
+    /// Returns an opening of the leaves associated with the provided keys.
+    ///
+    /// In contrast to opening every key via [Self::open()], the nodes shared by the paths from
+    /// the leaves to the root are included in the returned proof only once, and keys which map to
+    /// the same leaf share the opening of this leaf.
+    ///
+    /// # Errors
+    /// Returns an error if no keys are provided.
+    pub fn open_many<'a>(
+        &self,
+        keys: impl IntoIterator<Item = &'a RpoDigest>,
+    ) -> Result<SmtMultiProof<DEPTH, H>, MerkleError> {
+        let indices: BTreeSet<u64> =
+            keys.into_iter().map(|key| Self::key_to_leaf_index(key).value()).collect();
+        if indices.is_empty() {
+            return Err(MerkleError::NoKeys);
+        }
+
+        let leaves = indices
+            .iter()
+            .map(|&index| {
+                self.store
+                    .get_leaf(index)
+                    .unwrap_or_else(|| SmtLeaf::new_empty(LeafIndex::new_unchecked(index)))
+            })
+            .collect();
+
+        // walk up the tree one level at a time, collecting the siblings of the nodes on the paths
+        // which are not on the paths themselves
+        let mut siblings = Vec::new();
+        let mut nodes: Vec<u64> = indices.into_iter().collect();
+        for depth in (0..DEPTH).rev() {
+            let mut parents = Vec::with_capacity(nodes.len());
+            let mut iter = nodes.iter().peekable();
+            while let Some(&index) = iter.next() {
+                let parent = self.get_inner_node(NodeIndex::new_unchecked(depth, index >> 1));
+                if index & 1 == 1 {
+                    siblings.push(parent.left);
+                } else if iter.next_if(|&&next| next == index + 1).is_none() {
+                    siblings.push(parent.right);
+                }
+                parents.push(index >> 1);
+            }
+            nodes = parents;
+        }
+
+        Ok(SmtMultiProof::new_unchecked(leaves, siblings))
     }
 
     // STATE MUTATORS
@@ -158,12 +462,168 @@ impl Smt {
 
     /// Inserts a value at the specified key, returning the previous value associated with that key.
     /// Recall that by definition, any key that hasn't been updated is associated with
-    /// [`Self::EMPTY_VALUE`].
+    /// [`Smt::EMPTY_VALUE`].
     ///
     /// This also recomputes all hashes between the leaf (associated with the key) and the root,
     /// updating the root itself.
     pub fn insert(&mut self, key: RpoDigest, value: Word) -> Word {
-        <Self as SparseMerkleTree<SMT_DEPTH>>::insert(self, key, value)
+        <Self as SparseMerkleTree<DEPTH>>::insert(self, key, value)
+    }
+
+    /// Inserts a value at the specified key, returning a [Reversion] which restores the state of
+    /// the tree before the insertion when applied via [Self::apply_reversion()].
+    ///
+    /// This allows speculative updates of the tree to be rolled back without cloning the tree.
+    /// The previous value associated with the key is available via [MutationSet::new_pairs()].
+    pub fn insert_with_undo(&mut self, key: RpoDigest, value: Word) -> Reversion<DEPTH, H> {
+        let root = self.root;
+
+        // record the inner nodes on the path from the leaf to the root before they are replaced
+        let mut node_mutations = BTreeMap::new();
+        let mut index: NodeIndex = Self::key_to_leaf_index(&key).into();
+        for _ in 0..DEPTH {
+            index.move_up();
+            let mutation = match self.store.get_node(index) {
+                Some(node) => NodeMutation::Addition(node),
+                None => NodeMutation::Removal,
+            };
+            node_mutations.insert(index, mutation);
+        }
+
+        let old_value = self.insert(key, value);
+
+        MutationSet {
+            old_root: self.root,
+            node_mutations,
+            new_pairs: BTreeMap::from([(key, old_value)]),
+            new_root: root,
+        }
+    }
+
+    /// Computes the changes which inserting the provided key-value pairs would make to this tree,
+    /// without applying them.
+    ///
+    /// The returned [MutationSet] contains the new root of the tree, and thus a batch of updates
+    /// can be validated before it is applied via [Self::apply_mutations()]. Inner nodes shared by
+    /// the paths of multiple updated leaves are hashed only once. If a key appears multiple
+    /// times, the last value associated with it is used.
+    pub fn compute_mutations(
+        &self,
+        kv_pairs: impl IntoIterator<Item = (RpoDigest, Word)>,
+    ) -> MutationSet<DEPTH, RpoDigest, Word, H::Digest> {
+        <Self as SparseMerkleTree<DEPTH>>::compute_mutations(self, kv_pairs)
+    }
+
+    /// Applies the provided mutations to this tree.
+    ///
+    /// The result is the same as inserting all key-value pairs of the mutations one by one.
+    ///
+    /// # Errors
+    /// Returns an error if the mutations were computed against a tree with a root different from
+    /// the root of this tree.
+    pub fn apply_mutations(
+        &mut self,
+        mutations: MutationSet<DEPTH, RpoDigest, Word, H::Digest>,
+    ) -> Result<(), MerkleError> {
+        <Self as SparseMerkleTree<DEPTH>>::apply_mutations(self, mutations)
+    }
+
+    /// Applies the provided delta, computed via [Self::diff()], to this tree.
+    ///
+    /// After the delta is applied, this tree is equal to the tree the delta was computed against.
+    /// The inner nodes of the delta are not trusted: the paths to the changed leaves are re-hashed
+    /// from the key-value pairs of the delta, and the delta is applied only if the resulting root
+    /// matches the root claimed by the delta. Thus, a delta received from an untrusted party
+    /// cannot corrupt this tree.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - The delta was computed from a tree with a root different from the root of this tree.
+    /// - Applying the key-value pairs of the delta does not result in the root of the delta.
+    pub fn apply_delta(&mut self, delta: SmtDelta<DEPTH, H>) -> Result<(), MerkleError> {
+        let MutationSet { old_root, new_pairs, new_root, .. } = delta;
+        if old_root != self.root {
+            return Err(MerkleError::ConflictingMutationRoots(
+                old_root.as_bytes(),
+                self.root.as_bytes(),
+            ));
+        }
+
+        let mutations = self.compute_mutations(new_pairs);
+        if mutations.root() != new_root {
+            return Err(MerkleError::InvalidDeltaRoot(
+                new_root.as_bytes(),
+                mutations.root().as_bytes(),
+            ));
+        }
+        self.apply_mutations(mutations)
+    }
+
+    /// Applies the provided reversion, returned by [Self::insert_with_undo()], to this tree.
+    ///
+    /// After the reversion is applied, this tree is in the state it was in before the insertion
+    /// which returned the reversion.
+    ///
+    /// # Errors
+    /// Returns an error if the root of this tree is different from the root of the tree right
+    /// after the insertion, e.g., if the reversions of multiple insertions are not applied in the
+    /// reverse order of the insertions.
+    pub fn apply_reversion(&mut self, reversion: Reversion<DEPTH, H>) -> Result<(), MerkleError> {
+        self.apply_mutations(reversion)
+    }
+
+    // SYNCHRONIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the changes which transform this tree into `other`.
+    ///
+    /// Only the subtrees whose roots differ between the two trees are visited, and thus the cost
+    /// of computing the delta is proportional to the number of changed leaves rather than to the
+    /// size of the trees. Applying the returned delta to this tree via [Self::apply_delta()] makes
+    /// it equal to `other`.
+    pub fn diff<T: SmtStore<DEPTH, H>>(&self, other: &Smt<DEPTH, H, T>) -> SmtDelta<DEPTH, H> {
+        let mut node_mutations = BTreeMap::new();
+        let mut new_pairs = BTreeMap::new();
+
+        let mut stack = Vec::new();
+        if self.root != other.root {
+            stack.push(NodeIndex::root());
+        }
+        while let Some(index) = stack.pop() {
+            // the node differs between the trees, and thus the node of `other` is taken over
+            let mutation = match other.store.get_node(index) {
+                Some(node) => NodeMutation::Addition(node),
+                None => NodeMutation::Removal,
+            };
+            node_mutations.insert(index, mutation);
+
+            let node = self.get_inner_node(index);
+            let other_node = other.get_inner_node(index);
+            for (child, hash, other_hash) in [
+                (index.left_child(), node.left, other_node.left),
+                (index.right_child(), node.right, other_node.right),
+            ] {
+                if hash == other_hash {
+                    continue;
+                }
+                if child.depth() == DEPTH {
+                    diff_leaves(
+                        self.store.get_leaf(child.value()).as_ref(),
+                        other.store.get_leaf(child.value()).as_ref(),
+                        &mut new_pairs,
+                    );
+                } else {
+                    stack.push(child);
+                }
+            }
+        }
+
+        MutationSet {
+            old_root: self.root,
+            node_mutations,
+            new_pairs,
+            new_root: other.root,
+        }
     }
 
     // HELPERS
@@ -172,14 +632,19 @@ impl Smt {
     /// Inserts `value` at leaf index pointed to by `key`. `value` is guaranteed to not be the empty
     /// value, such that this is indeed an insertion.
     fn perform_insert(&mut self, key: RpoDigest, value: Word) -> Option<Word> {
-        debug_assert_ne!(value, Self::EMPTY_VALUE);
+        debug_assert_ne!(value, EMPTY_WORD);
+
+        let leaf_index: LeafIndex<DEPTH> = Self::key_to_leaf_index(&key);
 
-        let leaf_index: LeafIndex<SMT_DEPTH> = Self::key_to_leaf_index(&key);
+        match self.store.get_leaf(leaf_index.value()) {
+            Some(mut leaf) => {
+                let old_value = leaf.insert(key, value);
+                self.store.put_leaf(leaf_index.value(), leaf);
 
-        match self.leaves.get_mut(&leaf_index.value()) {
-            Some(leaf) => leaf.insert(key, value),
+                old_value
+            }
             None => {
-                self.leaves.insert(leaf_index.value(), SmtLeaf::Single((key, value)));
+                self.store.put_leaf(leaf_index.value(), SmtLeaf::Single((key, value)));
 
                 None
             }
@@ -188,12 +653,14 @@ impl Smt {
 
     /// Removes key-value pair at leaf index pointed to by `key` if it exists.
     fn perform_remove(&mut self, key: RpoDigest) -> Option<Word> {
-        let leaf_index: LeafIndex<SMT_DEPTH> = Self::key_to_leaf_index(&key);
+        let leaf_index: LeafIndex<DEPTH> = Self::key_to_leaf_index(&key);
 
-        if let Some(leaf) = self.leaves.get_mut(&leaf_index.value()) {
+        if let Some(mut leaf) = self.store.get_leaf(leaf_index.value()) {
             let (old_value, is_empty) = leaf.remove(key);
             if is_empty {
-                self.leaves.remove(&leaf_index.value());
+                self.store.remove_leaf(leaf_index.value());
+            } else if old_value.is_some() {
+                self.store.put_leaf(leaf_index.value(), leaf);
             }
             old_value
         } else {
@@ -203,36 +670,43 @@ impl Smt {
     }
 }
 
-impl SparseMerkleTree<SMT_DEPTH> for Smt {
+impl<const DEPTH: u8, H: SmtHasher, S: SmtStore<DEPTH, H>> SparseMerkleTree<DEPTH>
+    for Smt<DEPTH, H, S>
+{
     type Key = RpoDigest;
     type Value = Word;
-    type Leaf = SmtLeaf;
-    type Opening = SmtProof;
+    type Leaf = SmtLeaf<DEPTH>;
+    type Opening = SmtProof<DEPTH, H>;
+    type Hasher = H;
 
     const EMPTY_VALUE: Self::Value = EMPTY_WORD;
 
-    fn root(&self) -> RpoDigest {
+    fn root(&self) -> H::Digest {
         self.root
     }
 
-    fn set_root(&mut self, root: RpoDigest) {
+    fn set_root(&mut self, root: H::Digest) {
         self.root = root;
     }
 
-    fn get_inner_node(&self, index: NodeIndex) -> InnerNode {
-        self.inner_nodes.get(&index).cloned().unwrap_or_else(|| {
-            let node = EmptySubtreeRoots::entry(SMT_DEPTH, index.depth() + 1);
+    fn empty_subtree_root(&self, depth: u8) -> H::Digest {
+        self.empty_roots.get(depth)
+    }
+
+    fn get_inner_node(&self, index: NodeIndex) -> InnerNode<H::Digest> {
+        self.store.get_node(index).unwrap_or_else(|| {
+            let node = self.empty_roots.get(index.depth() + 1);
 
-            InnerNode { left: *node, right: *node }
+            InnerNode { left: node, right: node }
         })
     }
 
-    fn insert_inner_node(&mut self, index: NodeIndex, inner_node: InnerNode) {
-        self.inner_nodes.insert(index, inner_node);
+    fn insert_inner_node(&mut self, index: NodeIndex, inner_node: InnerNode<H::Digest>) {
+        self.store.put_node(index, inner_node);
     }
 
     fn remove_inner_node(&mut self, index: NodeIndex) {
-        let _ = self.inner_nodes.remove(&index);
+        self.store.remove_node(index);
     }
 
     fn insert_value(&mut self, key: Self::Key, value: Self::Value) -> Option<Self::Value> {
@@ -245,51 +719,225 @@ impl SparseMerkleTree<SMT_DEPTH> for Smt {
     }
 
     fn get_leaf(&self, key: &RpoDigest) -> Self::Leaf {
-        let leaf_pos = LeafIndex::<SMT_DEPTH>::from(*key).value();
+        let leaf_pos = LeafIndex::<DEPTH>::from(*key).value();
 
-        match self.leaves.get(&leaf_pos) {
-            Some(leaf) => leaf.clone(),
+        match self.store.get_leaf(leaf_pos) {
+            Some(leaf) => leaf,
             None => SmtLeaf::new_empty(key.into()),
         }
     }
 
-    fn hash_leaf(leaf: &Self::Leaf) -> RpoDigest {
-        leaf.hash()
+    fn hash_leaf(leaf: &Self::Leaf) -> H::Digest {
+        leaf.hash_with::<H>()
     }
 
-    fn key_to_leaf_index(key: &RpoDigest) -> LeafIndex<SMT_DEPTH> {
-        let most_significant_felt = key[3];
-        LeafIndex::new_max_depth(most_significant_felt.as_int())
+    fn construct_prospective_leaf(
+        mut existing_leaf: SmtLeaf<DEPTH>,
+        key: &RpoDigest,
+        value: &Word,
+    ) -> SmtLeaf<DEPTH> {
+        debug_assert_eq!(existing_leaf.index(), Self::key_to_leaf_index(key));
+
+        // inserting an `EMPTY_VALUE` is equivalent to removing any value associated with `key`
+        if *value != Self::EMPTY_VALUE {
+            existing_leaf.insert(*key, *value);
+        } else {
+            existing_leaf.remove(*key);
+        }
+        existing_leaf
     }
 
-    fn path_and_leaf_to_opening(path: MerklePath, leaf: SmtLeaf) -> SmtProof {
+    fn key_to_leaf_index(key: &RpoDigest) -> LeafIndex<DEPTH> {
+        LeafIndex::from(key)
+    }
+
+    fn path_and_leaf_to_opening(
+        path: MerklePath<H::Digest>,
+        leaf: SmtLeaf<DEPTH>,
+    ) -> SmtProof<DEPTH, H> {
         SmtProof::new_unchecked(path, leaf)
     }
 }
 
+impl<const DEPTH: u8, H: SmtHasher, S: SmtStore<DEPTH, H> + Clone> Clone for Smt<DEPTH, H, S> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root,
+            store: self.store.clone(),
+            empty_roots: self.empty_roots.clone(),
+        }
+    }
+}
+
 impl Default for Smt {
     fn default() -> Self {
         Self::new()
     }
 }
 
+// EMPTY SUBTREE ROOTS
+// ================================================================================================
+
+/// The roots of the empty subtrees of a tree of depth `DEPTH` computed with the hash function
+/// `H`, indexed by the depths of the roots.
+///
+/// The roots are taken from [EmptySubtreeRoots] for [Rpo256], and computed once per tree for any
+/// other hash function.
+struct EmptyRoots<const DEPTH: u8, H: SmtHasher>(Vec<H::Digest>);
+
+impl<const DEPTH: u8, H: SmtHasher> EmptyRoots<DEPTH, H> {
+    /// Returns the root of an empty subtree at the specified depth.
+    fn get(&self, depth: u8) -> H::Digest {
+        self.0[depth as usize]
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Default for EmptyRoots<DEPTH, H> {
+    fn default() -> Self {
+        Self(H::empty_subtree_roots(DEPTH))
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Clone for EmptyRoots<DEPTH, H> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> fmt::Debug for EmptyRoots<DEPTH, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EmptyRoots").finish_non_exhaustive()
+    }
+}
+
+// the roots are determined by `DEPTH` and `H`, and thus always equal
+impl<const DEPTH: u8, H: SmtHasher> PartialEq for EmptyRoots<DEPTH, H> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Eq for EmptyRoots<DEPTH, H> {}
+
+// PARALLEL CONSTRUCTION
+// ================================================================================================
+
+/// The depth of the roots of the subtrees which are built concurrently by
+/// [Smt::with_entries_parallel()].
+#[cfg(feature = "parallel")]
+const SUBTREE_DEPTH: u8 = 8;
+
+/// Indices and hashes of the nodes at a given depth of a tree, sorted by their indices.
+#[cfg(feature = "parallel")]
+type NodeHashes = Vec<(u64, RpoDigest)>;
+
+/// Splits the provided nodes (sorted by their indices) into groups of nodes whose indices have
+/// the same value when shifted right by `shift` bits, i.e., which belong to the same subtree.
+#[cfg(feature = "parallel")]
+fn split_by_prefix(nodes: &[(u64, RpoDigest)], shift: u8) -> Vec<&[(u64, RpoDigest)]> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+    for i in 1..=nodes.len() {
+        if i == nodes.len() || nodes[i].0 >> shift != nodes[start].0 >> shift {
+            groups.push(&nodes[start..i]);
+            start = i;
+        }
+    }
+    groups
+}
+
+/// Computes the inner nodes of the subtree containing the provided nodes at `from_depth`,
+/// level by level until `to_depth`.
+///
+/// The nodes must be sorted by their indices, and absent nodes are assumed to be roots of empty
+/// subtrees. Returns the non-empty inner nodes of the subtree together with the nodes at
+/// `to_depth`. Inner nodes which are equal to the roots of empty subtrees are omitted, in the
+/// same way as they are when entries are inserted one by one.
+#[cfg(feature = "parallel")]
+fn build_subtree(
+    mut nodes: NodeHashes,
+    from_depth: u8,
+    to_depth: u8,
+) -> (Vec<(NodeIndex, InnerNode)>, NodeHashes) {
+    let mut inner_nodes = Vec::new();
+    for depth in (to_depth..from_depth).rev() {
+        let empty_child = *EmptySubtreeRoots::entry(SMT_DEPTH, depth + 1);
+
+        // pair every node with its sibling, which may be the root of an empty subtree
+        let mut indices = Vec::with_capacity(nodes.len());
+        let mut pairs = Vec::with_capacity(nodes.len());
+        let mut iter = nodes.iter().peekable();
+        while let Some(&(index, hash)) = iter.next() {
+            let pair = if index & 1 == 1 {
+                [empty_child, hash]
+            } else {
+                match iter.next_if(|(next, _)| *next == index + 1) {
+                    Some(&(_, sibling)) => [hash, sibling],
+                    None => [hash, empty_child],
+                }
+            };
+            indices.push(index >> 1);
+            pairs.push(pair);
+        }
+
+        let hashes = Rpo256::merge_in_domain_many(&pairs, ZERO);
+        let empty_parent = *EmptySubtreeRoots::entry(SMT_DEPTH, depth);
+        for ((&index, [left, right]), &hash) in indices.iter().zip(pairs).zip(hashes.iter()) {
+            if hash != empty_parent {
+                inner_nodes
+                    .push((NodeIndex::new_unchecked(depth, index), InnerNode { left, right }));
+            }
+        }
+        nodes = indices.into_iter().zip(hashes).collect();
+    }
+    (inner_nodes, nodes)
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Records the key-value pairs which transform `leaf` into `other_leaf` into `new_pairs`.
+///
+/// Keys which are present only in `leaf` are mapped to [EMPTY_WORD].
+fn diff_leaves<const DEPTH: u8>(
+    leaf: Option<&SmtLeaf<DEPTH>>,
+    other_leaf: Option<&SmtLeaf<DEPTH>>,
+    new_pairs: &mut BTreeMap<RpoDigest, Word>,
+) {
+    let value_in = |leaf: Option<&SmtLeaf<DEPTH>>, key| {
+        leaf.and_then(|leaf| leaf.get_value(key)).unwrap_or(EMPTY_WORD)
+    };
+
+    for (key, value) in other_leaf.map(SmtLeaf::entries).unwrap_or_default() {
+        if value_in(leaf, key) != *value {
+            new_pairs.insert(*key, *value);
+        }
+    }
+    for (key, _) in leaf.map(SmtLeaf::entries).unwrap_or_default() {
+        if value_in(other_leaf, key) == EMPTY_WORD {
+            new_pairs.insert(*key, EMPTY_WORD);
+        }
+    }
+}
+
 // CONVERSIONS
 // ================================================================================================
 
-impl From<Word> for LeafIndex<SMT_DEPTH> {
+impl<const DEPTH: u8> From<Word> for LeafIndex<DEPTH> {
     fn from(value: Word) -> Self {
-        // We use the most significant `Felt` of a `Word` as the leaf index.
-        Self::new_max_depth(value[3].as_int())
+        // We use the `DEPTH` most significant bits of the most significant `Felt` of a `Word` as
+        // the leaf index.
+        Self::new_unchecked(value[3].as_int() >> (SMT_MAX_DEPTH - DEPTH))
     }
 }
 
-impl From<RpoDigest> for LeafIndex<SMT_DEPTH> {
+impl<const DEPTH: u8> From<RpoDigest> for LeafIndex<DEPTH> {
     fn from(value: RpoDigest) -> Self {
         Word::from(value).into()
     }
 }
 
-impl From<&RpoDigest> for LeafIndex<SMT_DEPTH> {
+impl<const DEPTH: u8> From<&RpoDigest> for LeafIndex<DEPTH> {
     fn from(value: &RpoDigest) -> Self {
         Word::from(value).into()
     }
@@ -0,0 +1,191 @@
+use alloc::{string::ToString, vec::Vec};
+
+use super::{LeafIndex, Rpo256, RpoDigest, SmtHasher, SmtLeaf, SmtProofError, Word, SMT_DEPTH};
+use crate::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
+
+// SMT MULTIPROOF
+// ================================================================================================
+
+/// A proof which can be used to assert membership (or non-membership) of key-value pairs in many
+/// leaves of a [`super::Smt`] at once.
+///
+/// The proof consists of the opened leaves, sorted by their indices, and of the siblings of the
+/// nodes on the paths from these leaves to the root which are not themselves on any of the paths.
+/// In contrast to a set of [`super::SmtProof`]s, every node shared by multiple paths is thus
+/// included only once. The siblings are ordered by depth (starting with the leaves) first, and by
+/// their position within the level second. The siblings are digests of the hash function `H` of
+/// the tree.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SmtMultiProof<const DEPTH: u8 = SMT_DEPTH, H: SmtHasher = Rpo256> {
+    leaves: Vec<SmtLeaf<DEPTH>>,
+    siblings: Vec<H::Digest>,
+}
+
+impl<const DEPTH: u8, H: SmtHasher> SmtMultiProof<DEPTH, H> {
+    // CONSTRUCTOR
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new instance of [`SmtMultiProof`] instantiated from the specified leaves and
+    /// siblings.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - No leaves are provided.
+    /// - The leaves are not sorted by their indices, or multiple leaves have the same index.
+    /// - The number of siblings does not match the number of siblings required by the paths from
+    ///   the leaves to the root.
+    pub fn new(
+        leaves: Vec<SmtLeaf<DEPTH>>,
+        siblings: Vec<H::Digest>,
+    ) -> Result<Self, SmtProofError> {
+        if leaves.is_empty() {
+            return Err(SmtProofError::NoLeaves);
+        }
+        if leaves.windows(2).any(|pair| pair[0].index().value() >= pair[1].index().value()) {
+            return Err(SmtProofError::UnsortedLeaves);
+        }
+
+        let expected =
+            num_siblings::<DEPTH>(leaves.iter().map(|leaf| leaf.index().value()).collect());
+        if siblings.len() != expected {
+            return Err(SmtProofError::InvalidNumSiblings { expected, actual: siblings.len() });
+        }
+
+        Ok(Self { leaves, siblings })
+    }
+
+    /// Returns a new instance of [`SmtMultiProof`] instantiated from the specified leaves and
+    /// siblings.
+    ///
+    /// The leaves and siblings are not checked. Reserved for internal use.
+    pub(super) fn new_unchecked(leaves: Vec<SmtLeaf<DEPTH>>, siblings: Vec<H::Digest>) -> Self {
+        Self { leaves, siblings }
+    }
+
+    // PROOF VERIFIER
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if a [`super::Smt`] with the specified root contains the provided key-value
+    /// pair.
+    ///
+    /// Note: as with [`super::SmtProof::verify_membership()`], if false is returned, it does not
+    /// mean that the provided key-value pair is not in the tree. To check many key-value pairs
+    /// against the same root, the root should be computed once via [Self::compute_root()] and the
+    /// values looked up via [Self::get()].
+    pub fn verify_membership(&self, key: &RpoDigest, value: &Word, root: &H::Digest) -> bool {
+        match self.get(key) {
+            Some(value_in_leaf) => value_in_leaf == *value && self.compute_root() == *root,
+            None => false,
+        }
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the value associated with the specific key according to this proof, or None if
+    /// this proof does not contain the leaf to which the key maps.
+    pub fn get(&self, key: &RpoDigest) -> Option<Word> {
+        let leaf_index = LeafIndex::<DEPTH>::from(key).value();
+        self.leaves
+            .binary_search_by_key(&leaf_index, |leaf| leaf.index().value())
+            .ok()
+            .and_then(|position| self.leaves[position].get_value(key))
+    }
+
+    /// Computes the root of a [`super::Smt`] to which this proof resolves.
+    pub fn compute_root(&self) -> H::Digest {
+        let mut nodes: Vec<(u64, H::Digest)> = self
+            .leaves
+            .iter()
+            .map(|leaf| (leaf.index().value(), leaf.hash_with::<H>()))
+            .collect();
+        let mut siblings = self.siblings.iter().copied();
+        let mut next_sibling = || siblings.next().expect("number of siblings checked on creation");
+
+        for _ in 0..DEPTH {
+            let mut indices = Vec::with_capacity(nodes.len());
+            let mut pairs = Vec::with_capacity(nodes.len());
+            let mut iter = nodes.iter().peekable();
+            while let Some(&(index, hash)) = iter.next() {
+                let pair = if index & 1 == 1 {
+                    [next_sibling(), hash]
+                } else {
+                    match iter.next_if(|(next, _)| *next == index + 1) {
+                        Some(&(_, sibling)) => [hash, sibling],
+                        None => [hash, next_sibling()],
+                    }
+                };
+                indices.push(index >> 1);
+                pairs.push(pair);
+            }
+
+            let hashes = H::merge_many(&pairs);
+            nodes = indices.into_iter().zip(hashes).collect();
+        }
+
+        nodes[0].1
+    }
+
+    /// Returns the leaves of the proof, sorted by their indices.
+    pub fn leaves(&self) -> &[SmtLeaf<DEPTH>] {
+        &self.leaves
+    }
+
+    /// Returns the siblings of the nodes on the paths from the leaves to the root.
+    pub fn siblings(&self) -> &[H::Digest] {
+        &self.siblings
+    }
+
+    /// Consume the proof and returns its parts.
+    pub fn into_parts(self) -> (Vec<SmtLeaf<DEPTH>>, Vec<H::Digest>) {
+        (self.leaves, self.siblings)
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Serializable for SmtMultiProof<DEPTH, H> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        (self.leaves.len() as u64).write_into(target);
+        for leaf in self.leaves.iter() {
+            leaf.write_into(target);
+        }
+
+        (self.siblings.len() as u64).write_into(target);
+        target.write_many(&self.siblings);
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Deserializable for SmtMultiProof<DEPTH, H> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let num_leaves = source.read_u64()?;
+        let mut leaves = Vec::new();
+        for _ in 0..num_leaves {
+            leaves.push(SmtLeaf::<DEPTH>::read_from(source)?);
+        }
+
+        let num_siblings = source.read_u64()?;
+        let mut siblings = Vec::new();
+        for _ in 0..num_siblings {
+            siblings.push(source.read()?);
+        }
+
+        Self::new(leaves, siblings)
+            .map_err(|err| DeserializationError::InvalidValue(err.to_string()))
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Returns the number of siblings required to compute the root from the leaves at the provided
+/// indices, which must be sorted and must not contain duplicates.
+fn num_siblings<const DEPTH: u8>(mut indices: Vec<u64>) -> usize {
+    let mut count = 0;
+    for _ in 0..DEPTH {
+        let num_nodes = indices.len();
+        indices.dedup_by_key(|index| *index >> 1);
+        // every pair of siblings on the paths needs no sibling, and every other node needs one
+        count += 2 * indices.len() - num_nodes;
+        indices.iter_mut().for_each(|index| *index >>= 1);
+    }
+    count
+}
@@ -1,4 +1,4 @@
-use super::{MerklePath, RpoDigest, SmtLeaf, SmtProofError, Word, SMT_DEPTH};
+use super::{MerklePath, Rpo256, RpoDigest, SmtHasher, SmtLeaf, SmtProofError, Word, SMT_DEPTH};
 use crate::utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable};
 use alloc::string::ToString;
 
@@ -6,23 +6,23 @@ use alloc::string::ToString;
 /// [`super::Smt`].
 ///
 /// The proof consists of a Merkle path and leaf which describes the node located at the base of the
-/// path.
+/// path. The nodes of the path are digests of the hash function `H` of the tree.
 #[derive(Clone, Debug, PartialEq, Eq)]
-pub struct SmtProof {
-    path: MerklePath,
-    leaf: SmtLeaf,
+pub struct SmtProof<const DEPTH: u8 = SMT_DEPTH, H: SmtHasher = Rpo256> {
+    path: MerklePath<H::Digest>,
+    leaf: SmtLeaf<DEPTH>,
 }
 
-impl SmtProof {
+impl<const DEPTH: u8, H: SmtHasher> SmtProof<DEPTH, H> {
     // CONSTRUCTOR
     // --------------------------------------------------------------------------------------------
 
     /// Returns a new instance of [`SmtProof`] instantiated from the specified path and leaf.
     ///
     /// # Errors
-    /// Returns an error if the path length is not [`SMT_DEPTH`].
-    pub fn new(path: MerklePath, leaf: SmtLeaf) -> Result<Self, SmtProofError> {
-        let depth: usize = SMT_DEPTH.into();
+    /// Returns an error if the path length is not equal to the depth of the tree.
+    pub fn new(path: MerklePath<H::Digest>, leaf: SmtLeaf<DEPTH>) -> Result<Self, SmtProofError> {
+        let depth: usize = DEPTH.into();
         if path.len() != depth {
             return Err(SmtProofError::InvalidPathLength(path.len()));
         }
@@ -33,7 +33,7 @@ impl SmtProof {
     /// Returns a new instance of [`SmtProof`] instantiated from the specified path and leaf.
     ///
     /// The length of the path is not checked. Reserved for internal use.
-    pub(super) fn new_unchecked(path: MerklePath, leaf: SmtLeaf) -> Self {
+    pub(super) fn new_unchecked(path: MerklePath<H::Digest>, leaf: SmtLeaf<DEPTH>) -> Self {
         Self { path, leaf }
     }
 
@@ -45,7 +45,7 @@ impl SmtProof {
     ///
     /// Note: this method cannot be used to assert non-membership. That is, if false is returned,
     /// it does not mean that the provided key-value pair is not in the tree.
-    pub fn verify_membership(&self, key: &RpoDigest, value: &Word, root: &RpoDigest) -> bool {
+    pub fn verify_membership(&self, key: &RpoDigest, value: &Word, root: &H::Digest) -> bool {
         let maybe_value_in_leaf = self.leaf.get_value(key);
 
         match maybe_value_in_leaf {
@@ -75,36 +75,43 @@ impl SmtProof {
     }
 
     /// Computes the root of a [`super::Smt`] to which this proof resolves.
-    pub fn compute_root(&self) -> RpoDigest {
-        self.path
-            .compute_root(self.leaf.index().value(), self.leaf.hash())
-            .expect("failed to compute Merkle path root")
+    pub fn compute_root(&self) -> H::Digest {
+        let mut index = self.leaf.index().value();
+        self.path.iter().fold(self.leaf.hash_with::<H>(), |node, &sibling| {
+            let pair = if index & 1 == 1 {
+                [sibling, node]
+            } else {
+                [node, sibling]
+            };
+            index >>= 1;
+            H::merge(&pair)
+        })
     }
 
     /// Returns the proof's Merkle path.
-    pub fn path(&self) -> &MerklePath {
+    pub fn path(&self) -> &MerklePath<H::Digest> {
         &self.path
     }
 
     /// Returns the leaf associated with the proof.
-    pub fn leaf(&self) -> &SmtLeaf {
+    pub fn leaf(&self) -> &SmtLeaf<DEPTH> {
         &self.leaf
     }
 
     /// Consume the proof and returns its parts.
-    pub fn into_parts(self) -> (MerklePath, SmtLeaf) {
+    pub fn into_parts(self) -> (MerklePath<H::Digest>, SmtLeaf<DEPTH>) {
         (self.path, self.leaf)
     }
 }
 
-impl Serializable for SmtProof {
+impl<const DEPTH: u8, H: SmtHasher> Serializable for SmtProof<DEPTH, H> {
     fn write_into<W: ByteWriter>(&self, target: &mut W) {
         self.path.write_into(target);
         self.leaf.write_into(target);
     }
 }
 
-impl Deserializable for SmtProof {
+impl<const DEPTH: u8, H: SmtHasher> Deserializable for SmtProof<DEPTH, H> {
     fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
         let path = MerklePath::read_from(source)?;
         let leaf = SmtLeaf::read_from(source)?;
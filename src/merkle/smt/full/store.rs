@@ -0,0 +1,640 @@
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use core::{cmp::Ordering, fmt, mem, ops::RangeInclusive};
+
+use super::{arena::NodeArena, InnerNode, NodeIndex, Rpo256, SmtHasher, SmtLeaf, SMT_DEPTH};
+use crate::utils::{Deserializable, DeserializationError, Serializable};
+
+// SMT STORE
+// ================================================================================================
+
+/// A storage backend for the leaves and the inner nodes of an [Smt](super::Smt) of depth `DEPTH`
+/// whose nodes are computed with the hash function `H`.
+///
+/// Leaves are identified by their index (i.e., the `DEPTH` most significant bits of the most
+/// significant element of the keys they hold), and inner nodes by their position in the tree.
+/// Only the leaves which hold at least one key-value pair, and only the inner nodes which are not
+/// roots of empty subtrees, are put into the store; every missing entry is treated as empty by
+/// the tree.
+///
+/// The methods of the store are infallible. A store backed by a medium which can fail (e.g., a
+/// database on disk) has to handle such failures itself, typically by panicking, since the tree
+/// cannot recover from a partially applied update.
+pub trait SmtStore<const DEPTH: u8 = SMT_DEPTH, H: SmtHasher = Rpo256> {
+    /// Returns the inner node at the specified index, if present.
+    fn get_node(&self, index: NodeIndex) -> Option<InnerNode<H::Digest>>;
+
+    /// Inserts or replaces the inner node at the specified index.
+    fn put_node(&mut self, index: NodeIndex, node: InnerNode<H::Digest>);
+
+    /// Removes the inner node at the specified index, if present.
+    fn remove_node(&mut self, index: NodeIndex);
+
+    /// Returns the leaf at the specified index, if present.
+    fn get_leaf(&self, index: u64) -> Option<SmtLeaf<DEPTH>>;
+
+    /// Inserts or replaces the leaf at the specified index.
+    fn put_leaf(&mut self, index: u64, leaf: SmtLeaf<DEPTH>);
+
+    /// Removes the leaf at the specified index, if present.
+    fn remove_leaf(&mut self, index: u64);
+}
+
+// MEMORY STORE
+// ================================================================================================
+
+/// The maximum ratio of the number of entries of the base layer of a [MemorySmtStore] to the
+/// number of entries of its overlay. Once the overlay grows larger, it is merged into a copy of the
+/// base layer, such that the cost of looking up and iterating over the entries of the store stays
+/// close to the cost for a store without an overlay.
+const MAX_OVERLAY_RATIO: usize = 8;
+
+/// The message of the panic raised when the inner node arena of a [MemorySmtStore] runs out of
+/// handles.
+const HANDLES_EXHAUSTED: &str = "the inner node arena has run out of handles";
+
+/// An [SmtStore] which keeps all leaves and inner nodes in memory.
+///
+/// This is the default store of an [Smt](super::Smt). Inner nodes are kept in a compact arena,
+/// in which they refer to the digests of their children via 32-bit handles, and the roots of
+/// empty subtrees are shared by all nodes.
+///
+/// The store is copy-on-write: cloning it (e.g., via [Smt::snapshot()](super::Smt::snapshot))
+/// shares the leaves and the inner nodes between the clones without copying them. While they are
+/// shared, the changes made to a clone are kept in a separate overlay of this clone. Once the
+/// leaves and the inner nodes are no longer shared, the overlay is merged back into them on the
+/// next change. Should the overlay grow too large in the meantime, it is merged into a copy of
+/// the shared leaves and inner nodes instead.
+///
+/// # Panics
+/// Storing an inner node panics if the arena runs out of 32-bit handles, i.e., if the store holds
+/// about `u32::MAX` inner nodes or non-empty children of inner nodes.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        bound(
+            serialize = "H::Digest: serde::Serialize",
+            deserialize = "H::Digest: serde::Deserialize<'de>"
+        ),
+        from = "MemoryLayer<DEPTH, H>",
+        into = "MemoryLayer<DEPTH, H>"
+    )
+)]
+pub struct MemorySmtStore<const DEPTH: u8 = SMT_DEPTH, H: SmtHasher = Rpo256> {
+    pub(super) base: Arc<MemoryLayer<DEPTH, H>>,
+    pub(super) overlay: Arc<Overlay<DEPTH, H>>,
+}
+
+impl<const DEPTH: u8, H: SmtHasher> MemorySmtStore<DEPTH, H> {
+    /// Returns a new empty [MemorySmtStore].
+    pub fn new() -> Self {
+        Self::from(MemoryLayer::new())
+    }
+
+    /// Returns the number of leaves in the store.
+    pub fn num_leaves(&self) -> usize {
+        let added_or_removed = self.overlay.leaves.iter().map(|(index, leaf)| {
+            (leaf.is_some() as isize) - (self.base.leaves.contains_key(index) as isize)
+        });
+        (self.base.leaves.len() as isize + added_or_removed.sum::<isize>()) as usize
+    }
+
+    /// Returns the number of inner nodes in the store.
+    pub fn num_inner_nodes(&self) -> usize {
+        let added_or_removed = self.overlay.nodes.iter().map(|(&index, node)| {
+            (node.is_some() as isize) - (self.base.inner_nodes.get(index).is_some() as isize)
+        });
+        (self.base.inner_nodes.len() as isize + added_or_removed.sum::<isize>()) as usize
+    }
+
+    /// Returns the number of digests of the children of the inner nodes in the store, excluding
+    /// the roots of empty subtrees which are shared by all nodes.
+    pub fn num_digests(&self) -> usize {
+        let arena = &self.base.inner_nodes;
+        let added_or_removed = self.overlay.nodes.iter().map(|(&index, node)| {
+            let num_digests = |node: Option<&InnerNode<H::Digest>>| {
+                node.map_or(0, |node| arena.num_digests_of(index, node)) as isize
+            };
+            num_digests(node.as_ref()) - num_digests(arena.get(index).as_ref())
+        });
+        (arena.num_digests() as isize + added_or_removed.sum::<isize>()) as usize
+    }
+
+    // ITERATORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns an iterator over the leaves in the store, sorted by their indices.
+    pub(super) fn iter_leaves(&self) -> impl Iterator<Item = (u64, &SmtLeaf<DEPTH>)> {
+        self.iter_leaves_in(0..=u64::MAX)
+    }
+
+    /// Returns an iterator over the leaves in the store with indices in the provided range, sorted
+    /// by their indices.
+    pub(super) fn iter_leaves_in(
+        &self,
+        range: RangeInclusive<u64>,
+    ) -> impl Iterator<Item = (u64, &SmtLeaf<DEPTH>)> {
+        merge_layers(
+            self.base.leaves.range(range.clone()).map(|(&index, leaf)| (index, leaf)),
+            self.overlay.leaves.range(range).map(|(&index, leaf)| (index, leaf.as_ref())),
+        )
+    }
+
+    /// Returns an iterator over the inner nodes in the store, ordered by depth first and by their
+    /// position within the level second.
+    pub(super) fn iter_inner_nodes(
+        &self,
+    ) -> impl Iterator<Item = (NodeIndex, InnerNode<H::Digest>)> + '_ {
+        merge_layers(
+            self.base.inner_nodes.iter(),
+            self.overlay.nodes.iter().map(|(&index, node)| (index, node.clone())),
+        )
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the base layer for modification if it is not shared with any other store, after
+    /// merging the overlay into it.
+    fn unshared_base(&mut self) -> Option<&mut MemoryLayer<DEPTH, H>> {
+        let base = Arc::get_mut(&mut self.base)?;
+        if !self.overlay.is_empty() {
+            let overlay = mem::replace(&mut self.overlay, Arc::new(Overlay::new()));
+            base.apply(Arc::try_unwrap(overlay).unwrap_or_else(|overlay| (*overlay).clone()));
+        }
+        Some(base)
+    }
+
+    /// Applies the provided change to the overlay, and merges the overlay into a copy of the base
+    /// layer if the overlay became too large.
+    fn change_overlay(&mut self, change: impl FnOnce(&mut Overlay<DEPTH, H>)) {
+        change(Arc::make_mut(&mut self.overlay));
+
+        if self.overlay.len() * MAX_OVERLAY_RATIO > self.base.len() {
+            let overlay = mem::replace(&mut self.overlay, Arc::new(Overlay::new()));
+            Arc::make_mut(&mut self.base)
+                .apply(Arc::try_unwrap(overlay).unwrap_or_else(|overlay| (*overlay).clone()));
+        }
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Default for MemorySmtStore<DEPTH, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Clone for MemorySmtStore<DEPTH, H> {
+    fn clone(&self) -> Self {
+        Self {
+            base: Arc::clone(&self.base),
+            overlay: Arc::clone(&self.overlay),
+        }
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> fmt::Debug for MemorySmtStore<DEPTH, H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MemorySmtStore")
+            .field("leaves", &self.iter_leaves().collect::<Vec<_>>())
+            .field("inner_nodes", &self.iter_inner_nodes().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> PartialEq for MemorySmtStore<DEPTH, H> {
+    fn eq(&self, other: &Self) -> bool {
+        // the same entries may be split differently between the base layers and the overlays
+        self.iter_leaves().eq(other.iter_leaves())
+            && self.iter_inner_nodes().eq(other.iter_inner_nodes())
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Eq for MemorySmtStore<DEPTH, H> {}
+
+impl<const DEPTH: u8, H: SmtHasher> SmtStore<DEPTH, H> for MemorySmtStore<DEPTH, H> {
+    fn get_node(&self, index: NodeIndex) -> Option<InnerNode<H::Digest>> {
+        match self.overlay.nodes.get(&index) {
+            Some(node) => node.clone(),
+            None => self.base.inner_nodes.get(index),
+        }
+    }
+
+    fn put_node(&mut self, index: NodeIndex, node: InnerNode<H::Digest>) {
+        match self.unshared_base() {
+            Some(base) => base.inner_nodes.insert(index, node).expect(HANDLES_EXHAUSTED),
+            None => self.change_overlay(|overlay| {
+                overlay.nodes.insert(index, Some(node));
+            }),
+        }
+    }
+
+    fn remove_node(&mut self, index: NodeIndex) {
+        if let Some(base) = self.unshared_base() {
+            base.inner_nodes.remove(index);
+            return;
+        }
+
+        // the removal of a node only needs to be recorded if the node is in the base layer
+        let in_base = self.base.inner_nodes.get(index).is_some();
+        self.change_overlay(|overlay| {
+            if in_base {
+                overlay.nodes.insert(index, None);
+            } else {
+                overlay.nodes.remove(&index);
+            }
+        });
+    }
+
+    fn get_leaf(&self, index: u64) -> Option<SmtLeaf<DEPTH>> {
+        match self.overlay.leaves.get(&index) {
+            Some(leaf) => leaf.clone(),
+            None => self.base.leaves.get(&index).cloned(),
+        }
+    }
+
+    fn put_leaf(&mut self, index: u64, leaf: SmtLeaf<DEPTH>) {
+        match self.unshared_base() {
+            Some(base) => {
+                base.leaves.insert(index, leaf);
+            }
+            None => self.change_overlay(|overlay| {
+                overlay.leaves.insert(index, Some(leaf));
+            }),
+        }
+    }
+
+    fn remove_leaf(&mut self, index: u64) {
+        if let Some(base) = self.unshared_base() {
+            base.leaves.remove(&index);
+            return;
+        }
+
+        // the removal of a leaf only needs to be recorded if the leaf is in the base layer
+        let in_base = self.base.leaves.contains_key(&index);
+        self.change_overlay(|overlay| {
+            if in_base {
+                overlay.leaves.insert(index, None);
+            } else {
+                overlay.leaves.remove(&index);
+            }
+        });
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> From<MemoryLayer<DEPTH, H>> for MemorySmtStore<DEPTH, H> {
+    fn from(base: MemoryLayer<DEPTH, H>) -> Self {
+        Self {
+            base: Arc::new(base),
+            overlay: Arc::new(Overlay::new()),
+        }
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> From<MemorySmtStore<DEPTH, H>> for MemoryLayer<DEPTH, H> {
+    fn from(store: MemorySmtStore<DEPTH, H>) -> Self {
+        let mut base = Arc::try_unwrap(store.base).unwrap_or_else(|base| (*base).clone());
+        base.apply(Arc::try_unwrap(store.overlay).unwrap_or_else(|overlay| (*overlay).clone()));
+        base
+    }
+}
+
+// MEMORY LAYERS
+// ------------------------------------------------------------------------------------------------
+
+/// The leaves and the inner nodes of a [MemorySmtStore], which may be shared by multiple stores.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(
+        serialize = "H::Digest: serde::Serialize",
+        deserialize = "H::Digest: serde::Deserialize<'de>"
+    ))
+)]
+pub(super) struct MemoryLayer<const DEPTH: u8, H: SmtHasher> {
+    leaves: BTreeMap<u64, SmtLeaf<DEPTH>>,
+    inner_nodes: NodeArena<DEPTH, H>,
+}
+
+impl<const DEPTH: u8, H: SmtHasher> MemoryLayer<DEPTH, H> {
+    /// Returns a new layer without any leaves or inner nodes.
+    fn new() -> Self {
+        Self {
+            leaves: BTreeMap::new(),
+            inner_nodes: NodeArena::new(),
+        }
+    }
+
+    /// Returns the number of leaves and inner nodes in the layer.
+    fn len(&self) -> usize {
+        self.leaves.len() + self.inner_nodes.len()
+    }
+
+    /// Applies the changes of the provided overlay to the layer.
+    fn apply(&mut self, overlay: Overlay<DEPTH, H>) {
+        for (index, node) in overlay.nodes {
+            match node {
+                Some(node) => self.inner_nodes.insert(index, node).expect(HANDLES_EXHAUSTED),
+                None => self.inner_nodes.remove(index),
+            }
+        }
+        for (index, leaf) in overlay.leaves {
+            match leaf {
+                Some(leaf) => self.leaves.insert(index, leaf),
+                None => self.leaves.remove(&index),
+            };
+        }
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Clone for MemoryLayer<DEPTH, H> {
+    fn clone(&self) -> Self {
+        Self {
+            leaves: self.leaves.clone(),
+            inner_nodes: self.inner_nodes.clone(),
+        }
+    }
+}
+
+/// The changes made to a [MemorySmtStore] while its base layer is shared with other stores, with
+/// `None` marking removed leaves and inner nodes.
+#[derive(Debug)]
+pub(super) struct Overlay<const DEPTH: u8, H: SmtHasher> {
+    leaves: BTreeMap<u64, Option<SmtLeaf<DEPTH>>>,
+    nodes: BTreeMap<NodeIndex, Option<InnerNode<H::Digest>>>,
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Overlay<DEPTH, H> {
+    /// Returns a new overlay without any changes.
+    fn new() -> Self {
+        Self {
+            leaves: BTreeMap::new(),
+            nodes: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of changed leaves and inner nodes.
+    fn len(&self) -> usize {
+        self.leaves.len() + self.nodes.len()
+    }
+
+    /// Returns true if no leaves or inner nodes were changed.
+    pub(super) fn is_empty(&self) -> bool {
+        self.leaves.is_empty() && self.nodes.is_empty()
+    }
+}
+
+impl<const DEPTH: u8, H: SmtHasher> Clone for Overlay<DEPTH, H> {
+    fn clone(&self) -> Self {
+        Self {
+            leaves: self.leaves.clone(),
+            nodes: self.nodes.clone(),
+        }
+    }
+}
+
+// KEY-VALUE DATABASE STORE
+// ================================================================================================
+
+/// A byte-oriented key-value database, such as RocksDB or sled.
+///
+/// Implementing this trait for a database handle makes it usable as the storage of an
+/// [Smt](super::Smt) via [KvDatabaseStore]. When the `sled` feature is enabled, the trait is
+/// implemented for `sled::Tree`. For other databases (e.g., RocksDB), an implementation is a few
+/// lines of code in the crate which uses the database, along the lines of the one for sled.
+pub trait KvDatabase {
+    /// Returns the value stored under the provided key, if any.
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>>;
+
+    /// Stores the provided value under the provided key, replacing the previous value.
+    fn put(&mut self, key: &[u8], value: Vec<u8>);
+
+    /// Removes the value stored under the provided key, if any.
+    fn delete(&mut self, key: &[u8]);
+}
+
+/// An [SmtStore] which keeps the leaves and the inner nodes of a tree in a [KvDatabase].
+///
+/// Inner nodes are stored under the key `0 || depth || index` and leaves under the key
+/// `1 || index`, with the indices encoded in big-endian order, such that the entries of each kind
+/// are ordered by their position in the tree. Inner nodes are stored as the concatenation of
+/// their children, and leaves in their serialized form. Since the keys do not depend on the depth
+/// of the tree, the store can back a tree of any depth and hash function.
+///
+/// A database which was populated by a previous instance of a tree should be opened via
+/// [KvDatabaseStore::open()], which checks the stored tree and returns an error if it is corrupted.
+///
+/// # Panics
+/// Reading an entry panics if the database contains a value which is not a valid encoding of a
+/// node or a leaf. This cannot happen for a database opened via [KvDatabaseStore::open()] unless
+/// it is modified by something other than the store afterwards.
+#[derive(Debug, Clone)]
+pub struct KvDatabaseStore<D: KvDatabase> {
+    db: D,
+}
+
+impl<D: KvDatabase> KvDatabaseStore<D> {
+    /// Returns a new [KvDatabaseStore] backed by the provided database.
+    ///
+    /// If the database already contains a tree, the tree can be reopened via
+    /// [Smt::with_store()](super::Smt::with_store).
+    pub fn new(db: D) -> Self {
+        Self { db }
+    }
+
+    /// Returns a new [KvDatabaseStore] backed by the provided database, which may contain a tree
+    /// of depth `DEPTH` whose nodes are computed with the hash function `H`.
+    ///
+    /// All entries reachable from the root node of the stored tree are decoded, and the hash of
+    /// every node and leaf is checked against the hash held by its parent.
+    ///
+    /// # Errors
+    /// Returns an error if any entry of the stored tree is not a valid encoding of a node or a
+    /// leaf, is missing, or does not match the hash held by its parent.
+    pub fn open<const DEPTH: u8, H: SmtHasher>(db: D) -> Result<Self, DeserializationError> {
+        let store = Self::new(db);
+        store.validate::<DEPTH, H>()?;
+        Ok(store)
+    }
+
+    /// Returns a reference to the underlying database.
+    pub fn db(&self) -> &D {
+        &self.db
+    }
+
+    /// Returns the underlying database.
+    pub fn into_db(self) -> D {
+        self.db
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Checks that the entries of the stored tree are valid and consistent with each other.
+    fn validate<const DEPTH: u8, H: SmtHasher>(&self) -> Result<(), DeserializationError> {
+        let empty_roots = H::empty_subtree_roots(DEPTH);
+
+        // the hash of the root is not known in advance, and thus the root node is only decoded
+        let mut stack = Vec::new();
+        if let Some(root) = self.read_node::<H>(NodeIndex::root())? {
+            stack.push((NodeIndex::root(), root));
+        }
+        while let Some((index, node)) = stack.pop() {
+            for (child, hash) in
+                [(index.left_child(), node.left), (index.right_child(), node.right)]
+            {
+                if hash == empty_roots[child.depth() as usize] {
+                    continue;
+                }
+                if child.depth() == DEPTH {
+                    let leaf = self.read_leaf::<DEPTH>(child.value())?;
+                    if leaf.map(|leaf| leaf.hash_with::<H>()) != Some(hash) {
+                        return Err(DeserializationError::InvalidValue(format!(
+                            "leaf {} does not match its parent",
+                            child.value()
+                        )));
+                    }
+                } else {
+                    match self.read_node::<H>(child)? {
+                        Some(node) if node.hash_with::<H>() == hash => stack.push((child, node)),
+                        _ => {
+                            return Err(DeserializationError::InvalidValue(format!(
+                                "inner node ({child}) does not match its parent"
+                            )))
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the decoded inner node at the specified index, if present.
+    fn read_node<H: SmtHasher>(
+        &self,
+        index: NodeIndex,
+    ) -> Result<Option<InnerNode<H::Digest>>, DeserializationError> {
+        self.db
+            .get(&Self::node_key(index))
+            .map(|bytes| {
+                let [left, right] = <[H::Digest; 2]>::read_from_bytes(&bytes)?;
+                Ok(InnerNode { left, right })
+            })
+            .transpose()
+    }
+
+    /// Returns the decoded leaf at the specified index, if present.
+    fn read_leaf<const DEPTH: u8>(
+        &self,
+        index: u64,
+    ) -> Result<Option<SmtLeaf<DEPTH>>, DeserializationError> {
+        self.db
+            .get(&Self::leaf_key(index))
+            .map(|bytes| SmtLeaf::read_from_bytes(&bytes))
+            .transpose()
+    }
+
+    fn node_key(index: NodeIndex) -> [u8; 10] {
+        let mut key = [0_u8; 10];
+        key[1] = index.depth();
+        key[2..].copy_from_slice(&index.value().to_be_bytes());
+        key
+    }
+
+    fn leaf_key(index: u64) -> [u8; 9] {
+        let mut key = [1_u8; 9];
+        key[1..].copy_from_slice(&index.to_be_bytes());
+        key
+    }
+}
+
+impl<D: KvDatabase, const DEPTH: u8, H: SmtHasher> SmtStore<DEPTH, H> for KvDatabaseStore<D> {
+    fn get_node(&self, index: NodeIndex) -> Option<InnerNode<H::Digest>> {
+        self.read_node::<H>(index).expect("database contains an invalid inner node")
+    }
+
+    fn put_node(&mut self, index: NodeIndex, node: InnerNode<H::Digest>) {
+        self.db.put(&Self::node_key(index), [node.left, node.right].to_bytes());
+    }
+
+    fn remove_node(&mut self, index: NodeIndex) {
+        self.db.delete(&Self::node_key(index));
+    }
+
+    fn get_leaf(&self, index: u64) -> Option<SmtLeaf<DEPTH>> {
+        self.read_leaf(index).expect("database contains an invalid leaf")
+    }
+
+    fn put_leaf(&mut self, index: u64, leaf: SmtLeaf<DEPTH>) {
+        self.db.put(&Self::leaf_key(index), leaf.to_bytes());
+    }
+
+    fn remove_leaf(&mut self, index: u64) {
+        self.db.delete(&Self::leaf_key(index));
+    }
+}
+
+impl KvDatabase for BTreeMap<Vec<u8>, Vec<u8>> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        BTreeMap::get(self, key).cloned()
+    }
+
+    fn put(&mut self, key: &[u8], value: Vec<u8>) {
+        self.insert(key.to_vec(), value);
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        self.remove(key);
+    }
+}
+
+/// Failures of the database are not recoverable by the tree, and thus reads and writes panic if
+/// sled returns an error.
+#[cfg(feature = "sled")]
+impl KvDatabase for sled::Tree {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        sled::Tree::get(self, key)
+            .expect("database read failed")
+            .map(|value| value.to_vec())
+    }
+
+    fn put(&mut self, key: &[u8], value: Vec<u8>) {
+        self.insert(key, value).expect("database write failed");
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        self.remove(key).expect("database write failed");
+    }
+}
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+/// Merges the entries of a base layer with the entries of an overlay, in which `None` marks the
+/// entries removed from the base layer. Both sets of entries must be sorted by their keys.
+fn merge_layers<K: Ord, V>(
+    base: impl Iterator<Item = (K, V)>,
+    overlay: impl Iterator<Item = (K, Option<V>)>,
+) -> impl Iterator<Item = (K, V)> {
+    let mut base = base.peekable();
+    let mut overlay = overlay.peekable();
+    core::iter::from_fn(move || loop {
+        let order = match (base.peek(), overlay.peek()) {
+            (None, None) => return None,
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some((key, _)), Some((overlay_key, _))) => key.cmp(overlay_key),
+        };
+        match order {
+            Ordering::Less => return base.next(),
+            // the entry of the base layer is replaced by the entry of the overlay
+            Ordering::Equal => drop(base.next()),
+            Ordering::Greater => (),
+        }
+        if let Some((key, Some(value))) = overlay.next() {
+            return Some((key, value));
+        }
+    })
+}
@@ -1,10 +1,16 @@
-use super::{Felt, LeafIndex, NodeIndex, Rpo256, RpoDigest, Smt, SmtLeaf, EMPTY_WORD, SMT_DEPTH};
+use super::{
+    Felt, FrozenSmt, KvDatabaseStore, LeafIndex, MemorySmtStore, MerkleError, MutationSet,
+    NodeIndex, NodeMutation, Rpo256, RpoDigest, Smt, SmtDelta, SmtLeaf, SmtMultiProof,
+    SmtProofError, SmtWithHistory, EMPTY_WORD, SMT_DEPTH,
+};
 use crate::{
-    merkle::{EmptySubtreeRoots, MerkleStore},
+    hash::{blake::Blake3_256, keccak::Keccak256, Digest},
+    merkle::{EmptySubtreeRoots, MerkleStore, SimpleSmt, SmtHasher},
     utils::{Deserializable, Serializable},
     Word, ONE, WORD_SIZE,
 };
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
+use core::ops::Bound;
 
 // SMT
 // --------------------------------------------------------------------------------------------
@@ -176,8 +182,8 @@ fn test_smt_insert_and_remove_multiple_values() {
     assert_eq!(smt.root(), empty_root);
 
     // an empty tree should have no leaves or inner nodes
-    assert!(smt.leaves.is_empty());
-    assert!(smt.inner_nodes.is_empty());
+    assert_eq!(smt.store.num_leaves(), 0);
+    assert_eq!(smt.store.num_inner_nodes(), 0);
 }
 
 /// This tests that inserting the empty value does indeed remove the key-value contained at the
@@ -329,6 +335,767 @@ fn test_smt_entries() {
     assert!(entries.next().is_none());
 }
 
+#[cfg(feature = "parallel")]
+#[test]
+fn test_smt_with_entries_parallel() {
+    use rand_utils::rand_value;
+
+    let random_key = |msb: u64| -> RpoDigest {
+        RpoDigest::from([rand_value(), rand_value(), rand_value(), Felt::new(msb)])
+    };
+    let random_value = || -> Word { rand_value::<RpoDigest>().into() };
+
+    // random leaves, leaves holding multiple keys, leaves with adjacent indices, leaves in the
+    // same subtree of the top levels, and keys set to the empty value
+    let mut entries: Vec<(RpoDigest, Word)> =
+        (0..100).map(|_| (random_key(rand_value()), random_value())).collect();
+    entries.extend((0..3).map(|_| (random_key(42), random_value())));
+    entries.extend((0..4).map(|i| (random_key(1000 + i), random_value())));
+    entries.extend((0..4).map(|i| (random_key(u64::MAX - (i << 50)), random_value())));
+    entries.extend((0..3).map(|i| (random_key(i), EMPTY_WORD)));
+
+    for len in [0, 1, 2, entries.len()] {
+        let expected = Smt::with_entries(entries[..len].iter().copied()).unwrap();
+        let actual = Smt::with_entries_parallel(entries[..len].iter().copied()).unwrap();
+        assert_eq!(actual, expected);
+    }
+
+    // only empty values
+    let empty: Vec<_> = (0..3).map(|i| (random_key(i), EMPTY_WORD)).collect();
+    assert_eq!(Smt::with_entries_parallel(empty).unwrap(), Smt::new());
+
+    // duplicate keys are rejected in the same way as in `with_entries()`
+    let mut duplicates = entries.clone();
+    duplicates.push((entries[10].0, random_value()));
+    duplicates.push((entries[entries.len() - 1].0, random_value()));
+    assert_eq!(
+        Smt::with_entries_parallel(duplicates.iter().copied()),
+        Smt::with_entries(duplicates.iter().copied())
+    );
+    assert!(Smt::with_entries_parallel(duplicates).is_err());
+}
+
+#[test]
+fn test_smt_compute_and_apply_mutations() {
+    let initial_entries = [
+        (key(0, 0), value(1)),
+        (key(1, 0), value(2)),
+        (key(42, 0), value(3)),
+        (key(42, 1), value(4)),
+        (key(u64::MAX, 0), value(5)),
+    ];
+    let smt = Smt::with_entries(initial_entries).unwrap();
+
+    // new keys next to existing ones, updates, removals (including of keys which are not in the
+    // tree), a new key in an existing leaf, and a key updated twice within the batch
+    let mutations = [
+        (key(2, 0), value(6)),
+        (key(1, 0), value(7)),
+        (key(0, 0), EMPTY_WORD),
+        (key(42, 1), EMPTY_WORD),
+        (key(42, 2), value(8)),
+        (key(7, 0), EMPTY_WORD),
+        (key(1 << 63, 0), value(9)),
+        (key(1 << 63, 0), value(10)),
+    ];
+
+    let mut expected = smt.clone();
+    for (key, value) in mutations {
+        expected.insert(key, value);
+    }
+
+    let mutation_set = smt.compute_mutations(mutations);
+    assert_eq!(mutation_set.old_root(), smt.root());
+    assert_eq!(mutation_set.root(), expected.root());
+    assert_eq!(mutation_set.new_pairs()[&key(1 << 63, 0)], value(10));
+
+    let mut actual = smt.clone();
+    actual.apply_mutations(mutation_set.clone()).unwrap();
+    assert_eq!(actual, expected);
+
+    // mutations cannot be applied to a tree with a different root
+    assert!(actual.apply_mutations(mutation_set).is_err());
+    assert_eq!(actual, expected);
+
+    // an empty batch does not change the tree
+    let mutation_set = smt.compute_mutations([]);
+    assert_eq!(mutation_set.root(), smt.root());
+    assert_eq!(mutation_set.num_node_mutations(), 0);
+
+    // removing all entries results in an empty tree
+    let mutation_set = smt.compute_mutations(initial_entries.map(|(key, _)| (key, EMPTY_WORD)));
+    let mut actual = smt.clone();
+    actual.apply_mutations(mutation_set).unwrap();
+    assert_eq!(actual, Smt::new());
+}
+
+#[test]
+fn test_smt_diff_and_apply_delta() {
+    let smt = Smt::with_entries([
+        (key(0, 0), value(1)),
+        (key(1, 0), value(2)),
+        (key(42, 0), value(3)),
+        (key(42, 1), value(4)),
+        (key(u64::MAX, 0), value(5)),
+    ])
+    .unwrap();
+
+    // an updated key, a removed key, a removed leaf, a new key in an existing leaf, and new leaves
+    let mut other = smt.clone();
+    other.insert(key(1, 0), value(6));
+    other.insert(key(42, 1), EMPTY_WORD);
+    other.insert(key(u64::MAX, 0), EMPTY_WORD);
+    other.insert(key(0, 1), value(7));
+    other.insert(key(2, 0), value(8));
+    other.insert(key(1 << 63, 0), value(9));
+
+    let delta = smt.diff(&other);
+    assert_eq!(delta.old_root(), smt.root());
+    assert_eq!(delta.root(), other.root());
+    assert_eq!(delta.new_pairs().len(), 6);
+    assert_eq!(delta.new_pairs()[&key(42, 1)], EMPTY_WORD);
+
+    let mut replica = smt.clone();
+    replica.apply_delta(delta.clone()).unwrap();
+    assert_eq!(replica, other);
+
+    // the delta cannot be applied to a tree with a different root
+    assert!(replica.apply_delta(delta.clone()).is_err());
+
+    // the inner nodes of the delta are not trusted, and the key-value pairs must result in the
+    // root of the delta
+    let tampered_nodes = MutationSet {
+        node_mutations: delta
+            .node_mutations
+            .keys()
+            .map(|&index| (index, NodeMutation::Removal))
+            .collect(),
+        ..delta.clone()
+    };
+    let mut replica = smt.clone();
+    replica.apply_delta(tampered_nodes).unwrap();
+    assert_eq!(replica, other);
+
+    let mut tampered_pairs = delta.clone();
+    tampered_pairs.new_pairs.insert(key(1, 0), value(10));
+    let mut victim = smt.clone();
+    assert!(matches!(
+        victim.apply_delta(tampered_pairs),
+        Err(MerkleError::InvalidDeltaRoot(..))
+    ));
+    assert_eq!(victim, smt);
+
+    // the delta is transmitted in serialized form
+    let bytes = delta.to_bytes();
+    assert_eq!(SmtDelta::<SMT_DEPTH>::read_from_bytes(&bytes).unwrap(), delta);
+
+    // the reverse delta restores the original tree
+    replica.apply_delta(other.diff(&smt)).unwrap();
+    assert_eq!(replica, smt);
+
+    // equal trees have an empty delta, and a delta from an empty tree contains all entries
+    let delta = smt.diff(&smt.clone());
+    assert!(delta.new_pairs().is_empty());
+    assert_eq!(delta.num_node_mutations(), 0);
+
+    let mut replica = Smt::new();
+    let delta = replica.diff(&other);
+    assert_eq!(delta.new_pairs().len(), other.entries().count());
+    replica.apply_delta(delta).unwrap();
+    assert_eq!(replica, other);
+}
+
+#[test]
+fn test_smt_insert_with_undo() {
+    let smt = Smt::with_entries([
+        (key(0, 0), value(1)),
+        (key(42, 0), value(2)),
+        (key(42, 1), value(3)),
+        (key(u64::MAX, 0), value(4)),
+    ])
+    .unwrap();
+
+    // an updated key, a removed key, a new key in an existing leaf, a new leaf, and an unchanged
+    // key; the state of the tree before every insertion is recorded
+    let updates = [
+        (key(0, 0), value(5)),
+        (key(42, 1), EMPTY_WORD),
+        (key(u64::MAX, 1), value(6)),
+        (key(1 << 63, 0), value(7)),
+        (key(42, 0), value(2)),
+    ];
+    let mut tree = smt.clone();
+    let mut states = Vec::new();
+    let mut reversions = Vec::new();
+    for (key, value) in updates {
+        states.push(tree.clone());
+        let old_value = tree.get_value(&key);
+        let reversion = tree.insert_with_undo(key, value);
+        assert_eq!(reversion.old_root(), tree.root());
+        assert_eq!(reversion.new_pairs()[&key], old_value);
+        assert_eq!(tree.get_value(&key), value);
+        reversions.push(reversion);
+    }
+
+    // a reversion cannot be applied out of order
+    assert!(tree.apply_reversion(reversions[0].clone()).is_err());
+
+    // applying the reversions in the reverse order restores every previous state
+    while let Some(reversion) = reversions.pop() {
+        tree.apply_reversion(reversion).unwrap();
+        assert_eq!(tree, states.pop().unwrap());
+    }
+    assert_eq!(tree, smt);
+}
+
+#[test]
+fn test_smt_with_history() {
+    let smt = Smt::with_entries([(key(0, 0), value(1)), (key(42, 0), value(2))]).unwrap();
+    let mut history = SmtWithHistory::new(smt.clone());
+    assert_eq!(history.version(), 0);
+
+    // every update creates a new version; the trees of all versions are recorded for comparison
+    let mut versions = vec![smt];
+    assert_eq!(history.insert(key(0, 0), value(3)), value(1));
+    versions.push(history.as_smt().clone());
+    let mutations = history.as_smt().compute_mutations([
+        (key(42, 1), value(4)),
+        (key(u64::MAX, 0), value(5)),
+        (key(0, 0), EMPTY_WORD),
+    ]);
+    history.apply_mutations(mutations).unwrap();
+    versions.push(history.as_smt().clone());
+    assert_eq!(history.insert(key(42, 0), EMPTY_WORD), value(2));
+    versions.push(history.as_smt().clone());
+    assert_eq!(history.version(), 3);
+
+    // the values and openings of all keys at all versions match those of the recorded trees
+    let keys = [key(0, 0), key(42, 0), key(42, 1), key(u64::MAX, 0), key(7, 0)];
+    let peaks = history.peaks();
+    for (version, tree) in versions.iter().enumerate() {
+        assert_eq!(history.root_at(version), Some(tree.root()));
+        for key in keys.iter() {
+            let value_at_version = tree.get_value(key);
+            assert_eq!(history.get_value_at(key, version), Some(value_at_version));
+
+            let proof = history.open_at(key, version).unwrap();
+            assert_eq!(proof.version(), version);
+            assert_eq!(proof.smt_proof(), &tree.open(key));
+            assert!(proof.verify_membership(key, &value_at_version, &peaks));
+            assert!(!proof.verify_membership(key, &value(99), &peaks));
+        }
+    }
+
+    // proofs are verified against the peaks at the time they were created
+    let proof = history.open_at(&key(0, 0), 0).unwrap();
+    history.insert(key(1, 0), value(6));
+    assert!(!proof.verify_membership(&key(0, 0), &value(1), &history.peaks()));
+    assert!(history.open_at(&key(0, 0), 0).unwrap().verify_membership(
+        &key(0, 0),
+        &value(1),
+        &history.peaks()
+    ));
+
+    // versions which do not exist yet cannot be opened, and stale mutations are rejected
+    assert_eq!(history.get_value_at(&key(0, 0), 5), None);
+    assert!(history.open_at(&key(0, 0), 5).is_none());
+    let stale = versions[0].compute_mutations([(key(2, 0), value(7))]);
+    assert!(history.apply_mutations(stale).is_err());
+    assert_eq!(history.version(), 4);
+}
+
+#[test]
+fn test_smt_snapshot() {
+    let mut entries: BTreeMap<RpoDigest, Word> =
+        (0..64).map(|i| (key(i << 58, i % 2), value(i + 1))).collect();
+    let mut tree = Smt::with_entries(entries.clone()).unwrap();
+    let root = tree.root();
+
+    // the snapshot shares the nodes of the tree, and is not affected by changes to the tree
+    let snapshot = tree.snapshot();
+    assert!(Arc::ptr_eq(&tree.store().base, &snapshot.as_smt().store().base));
+
+    tree.insert(key(1, 0), value(100));
+    tree.insert(key(0, 0), EMPTY_WORD);
+    entries.insert(key(1, 0), value(100));
+    entries.remove(&key(0, 0));
+
+    assert!(Arc::ptr_eq(&tree.store().base, &snapshot.as_smt().store().base));
+    assert!(!tree.store().overlay.is_empty());
+    assert_eq!(snapshot.root(), root);
+    assert_eq!(snapshot.get_value(&key(0, 0)), value(1));
+    assert_eq!(snapshot.get_value(&key(1, 0)), EMPTY_WORD);
+
+    // the tree behaves like a tree built from its entries
+    let expected = Smt::with_entries(entries.clone()).unwrap();
+    assert_eq!(tree, expected);
+    assert_eq!(tree.root(), expected.root());
+    assert!(tree.entries_sorted().eq(expected.entries_sorted()));
+    assert!(tree.range(key(1, 0)..key(5000, 0)).eq(expected.range(key(1, 0)..key(5000, 0))));
+    assert!(tree.inner_nodes().eq(expected.inner_nodes()));
+    assert_eq!(tree.store().num_leaves(), expected.store().num_leaves());
+    assert_eq!(tree.store().num_inner_nodes(), expected.store().num_inner_nodes());
+    assert_eq!(tree.store().num_digests(), expected.store().num_digests());
+
+    // forks of the snapshot diverge independently of the tree and of the snapshot
+    let mut fork = snapshot.fork();
+    fork.insert(key(2, 0), value(200));
+    assert_eq!(fork.get_value(&key(0, 0)), value(1));
+    assert_eq!(fork.get_value(&key(2, 0)), value(200));
+    assert_eq!(tree.get_value(&key(2, 0)), EMPTY_WORD);
+    assert_eq!(snapshot.get_value(&key(2, 0)), EMPTY_WORD);
+    assert_eq!(snapshot.root(), root);
+
+    // once the nodes are no longer shared, the changes are merged back into them
+    drop(snapshot);
+    drop(fork);
+    tree.insert(key(3, 0), value(300));
+    entries.insert(key(3, 0), value(300));
+    assert!(tree.store().overlay.is_empty());
+    assert_eq!(tree, Smt::with_entries(entries).unwrap());
+
+    // changes which are large compared to the shared nodes are made in a copy of the nodes
+    let mut small = Smt::with_entries([(key(0, 0), value(1))]).unwrap();
+    let snapshot = small.snapshot();
+    small.insert(key(1, 0), value(2));
+    assert!(!Arc::ptr_eq(&small.store().base, &snapshot.as_smt().store().base));
+    assert!(small.store().overlay.is_empty());
+    assert_eq!(
+        small,
+        Smt::with_entries([(key(0, 0), value(1)), (key(1, 0), value(2))]).unwrap()
+    );
+    assert_eq!(snapshot.get_value(&key(1, 0)), EMPTY_WORD);
+}
+
+#[test]
+fn test_smt_entries_sorted_and_range() {
+    // keys in the same leaf, keys whose less significant elements are ordered differently from
+    // the most significant ones, and keys at the boundaries of the key space
+    let sorted_keys = [
+        key(0, 0),
+        key(1, 5),
+        key(1, 7),
+        key(1, 9),
+        key(2, 0),
+        key(1 << 32, 3),
+        key(1 << 63, 1),
+    ];
+    let entries: Vec<_> = sorted_keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| (*key, value(i as u64 + 1)))
+        .collect();
+
+    // the order of insertion does not affect the order of iteration
+    let mut smt = Smt::new();
+    for (key, value) in entries.iter().rev() {
+        smt.insert(*key, *value);
+    }
+    let actual: Vec<_> = smt.entries_sorted().copied().collect();
+    assert_eq!(actual, entries);
+
+    assert_eq!(keys_of(smt.range(..)), sorted_keys);
+    assert_eq!(keys_of(smt.range(key(1, 7)..key(2, 0))), sorted_keys[2..4]);
+    assert_eq!(keys_of(smt.range(key(1, 7)..=key(2, 0))), sorted_keys[2..5]);
+    assert_eq!(keys_of(smt.range(key(1, 6)..key(1, 8))), sorted_keys[2..3]);
+    assert_eq!(keys_of(smt.range(..key(1, 9))), sorted_keys[..3]);
+    assert_eq!(keys_of(smt.range(key(3, 0)..)), sorted_keys[5..]);
+    assert_eq!(
+        keys_of(smt.range((Bound::Excluded(key(1, 5)), Bound::Excluded(key(1, 9))))),
+        sorted_keys[2..3]
+    );
+
+    // empty ranges, including ranges whose start is greater than their end
+    assert_eq!(smt.range(key(3, 0)..key(4, 0)).count(), 0);
+    assert_eq!(smt.range(key(1, 7)..key(1, 7)).count(), 0);
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = key(2, 0)..key(1, 0);
+    assert_eq!(smt.range(reversed).count(), 0);
+    assert_eq!(Smt::new().range(..).count(), 0);
+}
+
+#[test]
+fn test_smt_with_database_store() {
+    let mut expected = Smt::new();
+    let mut smt = Smt::with_store(KvDatabaseStore::new(BTreeMap::<Vec<u8>, Vec<u8>>::new()));
+    assert_eq!(smt.root(), expected.root());
+
+    // insertions of keys into new and existing leaves, updates, and removals
+    let updates = [
+        (key(0, 0), value(1)),
+        (key(42, 0), value(2)),
+        (key(42, 1), value(3)),
+        (key(1 << 63, 0), value(4)),
+        (key(42, 0), value(5)),
+        (key(0, 0), EMPTY_WORD),
+        (key(7, 0), EMPTY_WORD),
+    ];
+    for (key, value) in updates {
+        assert_eq!(smt.insert(key, value), expected.insert(key, value));
+        assert_eq!(smt.root(), expected.root());
+    }
+    for (key, _) in updates {
+        assert_eq!(smt.get_value(&key), expected.get_value(&key));
+        assert_eq!(smt.open(&key), expected.open(&key));
+    }
+
+    // batches of updates and deltas work in the same way
+    let batch = [(key(42, 1), EMPTY_WORD), (key(3, 0), value(6))];
+    smt.apply_mutations(smt.compute_mutations(batch)).unwrap();
+    expected.apply_mutations(expected.compute_mutations(batch)).unwrap();
+    assert_eq!(smt.root(), expected.root());
+    assert_eq!(smt.diff(&expected).num_node_mutations(), 0);
+
+    // the tree is reopened from the database with the same root
+    let db = smt.into_store().into_db();
+    let reopened: Smt<SMT_DEPTH, Rpo256, _> =
+        Smt::with_store(KvDatabaseStore::open::<SMT_DEPTH, Rpo256>(db.clone()).unwrap());
+    assert_eq!(reopened.root(), expected.root());
+    assert_eq!(reopened.get_value(&key(42, 0)), value(5));
+
+    // corrupted databases are rejected when they are opened
+    let leaf_key = [[1_u8].as_slice(), &42_u64.to_be_bytes()].concat();
+    let mut corrupted = db.clone();
+    corrupted.insert(leaf_key.clone(), vec![1, 2, 3]);
+    assert!(KvDatabaseStore::open::<SMT_DEPTH, Rpo256>(corrupted).is_err());
+
+    let mut corrupted = db.clone();
+    corrupted.insert(
+        leaf_key.clone(),
+        SmtLeaf::<SMT_DEPTH>::new_single(key(42, 0), value(6)).to_bytes(),
+    );
+    assert!(KvDatabaseStore::open::<SMT_DEPTH, Rpo256>(corrupted).is_err());
+
+    let mut corrupted = db;
+    corrupted.remove(&leaf_key);
+    assert!(KvDatabaseStore::open::<SMT_DEPTH, Rpo256>(corrupted).is_err());
+
+    // the database holds only the non-empty leaves and the non-empty inner nodes
+    let num_entries = reopened.store().db().len();
+    let store = expected.into_store();
+    assert_eq!(num_entries, store.num_leaves() + store.num_inner_nodes());
+}
+
+#[cfg(feature = "sled")]
+#[test]
+fn test_smt_with_sled_store() {
+    let path = std::env::temp_dir()
+        .join(alloc::format!("miden-crypto-smt-{}", rand_utils::rand_value::<u64>()));
+    let entries = [(key(0, 0), value(1)), (key(42, 0), value(2)), (key(42, 1), value(3))];
+    let expected = Smt::with_entries(entries).unwrap();
+
+    // the tree is written into a sled tree, and the database is closed
+    {
+        let db = sled::open(&path).unwrap();
+        let store = KvDatabaseStore::new(db.open_tree("smt").unwrap());
+        let mut smt: Smt<SMT_DEPTH, Rpo256, _> = Smt::with_store(store);
+        for (key, value) in entries {
+            smt.insert(key, value);
+        }
+        smt.insert(key(7, 0), value(4));
+        smt.insert(key(7, 0), EMPTY_WORD);
+        assert_eq!(smt.root(), expected.root());
+        db.flush().unwrap();
+    }
+
+    // the tree is reopened from the database on disk with the same root and values
+    let db = sled::open(&path).unwrap();
+    let store = KvDatabaseStore::open::<SMT_DEPTH, Rpo256>(db.open_tree("smt").unwrap()).unwrap();
+    let reopened: Smt<SMT_DEPTH, Rpo256, _> = Smt::with_store(store);
+    assert_eq!(reopened.root(), expected.root());
+    for (key, value) in entries {
+        assert_eq!(reopened.get_value(&key), value);
+        assert_eq!(reopened.open(&key), expected.open(&key));
+    }
+
+    let store = expected.into_store();
+    assert_eq!(reopened.store().db().len(), store.num_leaves() + store.num_inner_nodes());
+
+    drop(reopened);
+    drop(db);
+    std::fs::remove_dir_all(&path).unwrap();
+}
+
+#[test]
+fn test_smt_node_arena() {
+    // the path of a single leaf has one non-empty child per inner node
+    let mut smt = Smt::new();
+    smt.insert(key(42, 1), value(1));
+    assert_eq!(smt.store.num_inner_nodes(), SMT_DEPTH as usize);
+    assert_eq!(smt.store.num_digests(), SMT_DEPTH as usize);
+
+    // updating the leaf reuses the slots of the digests
+    for i in 2..10 {
+        smt.insert(key(42, 1), value(i));
+    }
+    assert_eq!(smt.store.num_digests(), SMT_DEPTH as usize);
+
+    // a sibling leaf adds a single digest, since the paths of the leaves are shared
+    smt.insert(key(43, 1), value(1));
+    assert_eq!(smt.store.num_digests(), SMT_DEPTH as usize + 1);
+
+    // the nodes are the same as the nodes of a tree built from scratch
+    let expected = Smt::with_entries([(key(42, 1), value(9)), (key(43, 1), value(1))]).unwrap();
+    assert_eq!(smt, expected);
+    assert_eq!(
+        smt.inner_nodes().collect::<Vec<_>>(),
+        expected.inner_nodes().collect::<Vec<_>>()
+    );
+
+    // removing the leaves frees all digests
+    smt.insert(key(42, 1), EMPTY_WORD);
+    smt.insert(key(43, 1), EMPTY_WORD);
+    assert_eq!(smt.store.num_inner_nodes(), 0);
+    assert_eq!(smt.store.num_digests(), 0);
+    assert_eq!(smt, Smt::new());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_frozen_smt_concurrent_reads() {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Smt>();
+    assert_send_sync::<FrozenSmt>();
+
+    let entries: Vec<_> = (0..16).map(|i| (key(i * 1000, 1), value(i + 1))).collect();
+    let snapshot = Smt::with_entries(entries.clone()).unwrap().freeze();
+
+    // readers open the keys of the snapshot while the writer prepares the next version
+    let next = std::thread::scope(|scope| {
+        for _ in 0..4 {
+            let snapshot = snapshot.clone();
+            let entries = &entries;
+            scope.spawn(move || {
+                for (key, value) in entries {
+                    assert_eq!(snapshot.get_value(key), *value);
+                    assert!(snapshot.open(key).verify_membership(key, value, &snapshot.root()));
+                }
+            });
+        }
+
+        let mutations = snapshot.compute_mutations([(key(1, 1), value(100))]);
+        let mut next = snapshot.as_smt().clone();
+        next.apply_mutations(mutations).unwrap();
+        next.freeze()
+    });
+
+    assert_eq!(snapshot.get_value(&key(1, 1)), EMPTY_WORD);
+    assert_eq!(next.get_value(&key(1, 1)), value(100));
+    assert_ne!(snapshot.root(), next.root());
+
+    // the tree is copied only if it is shared by multiple snapshots
+    let handle = next.clone();
+    assert_eq!(next.num_handles(), 2);
+    let mut tree = next.into_smt();
+    tree.insert(key(2, 1), value(200));
+    assert_eq!(handle.get_value(&key(2, 1)), EMPTY_WORD);
+    assert_eq!(handle.num_handles(), 1);
+    assert_eq!(handle.into_smt().get_value(&key(1, 1)), value(100));
+}
+
+#[test]
+fn test_smt_open_many() {
+    let smt = Smt::with_entries([
+        (key(0, 0), value(1)),
+        (key(1, 0), value(2)),
+        (key(42, 0), value(3)),
+        (key(42, 1), value(4)),
+        (key(1 << 32, 0), value(5)),
+        (key(1 << 63, 0), value(6)),
+    ])
+    .unwrap();
+
+    // sibling leaves, keys in the same leaf (opened twice), and keys which are not in the tree
+    let keys = [
+        key(0, 0),
+        key(1, 0),
+        key(42, 0),
+        key(42, 1),
+        key(42, 1),
+        key(43, 0),
+        key(1 << 63, 0),
+        key(7, 0),
+    ];
+    let proof = smt.open_many(&keys).unwrap();
+    assert_eq!(proof.leaves().len(), 6);
+    assert_eq!(proof.compute_root(), smt.root());
+    for key in keys.iter() {
+        let value = smt.get_value(key);
+        assert_eq!(proof.get(key), Some(value));
+        assert!(proof.verify_membership(key, &value, &smt.root()));
+        assert!(!proof.verify_membership(key, &value, &Smt::new().root()));
+    }
+    assert!(!proof.verify_membership(&key(0, 0), &value(2), &smt.root()));
+    assert_eq!(proof.get(&key(1 << 32, 0)), None);
+
+    // the shared nodes are included only once
+    let num_siblings: usize = keys.iter().map(|key| smt.open(key).path().len()).sum();
+    assert!(proof.siblings().len() < num_siblings / 2);
+
+    // a single key results in the same siblings as a regular opening
+    let single = smt.open_many([&key(42, 0)]).unwrap();
+    let (path, leaf) = smt.open(&key(42, 0)).into_parts();
+    assert_eq!(single.siblings(), &path[..]);
+    assert_eq!(single.leaves(), [leaf]);
+
+    // at least one key must be opened
+    assert_eq!(smt.open_many([]), Err(MerkleError::NoKeys));
+
+    // serialization
+    let bytes = proof.to_bytes();
+    assert_eq!(SmtMultiProof::read_from_bytes(&bytes).unwrap(), proof);
+
+    // tampered siblings do not resolve to the root, and malformed proofs are rejected
+    let (leaves, mut siblings) = proof.into_parts();
+    siblings[3] = RpoDigest::default();
+    let tampered = SmtMultiProof::<SMT_DEPTH>::new(leaves.clone(), siblings.clone()).unwrap();
+    assert_ne!(tampered.compute_root(), smt.root());
+    assert_eq!(
+        SmtMultiProof::<SMT_DEPTH>::new(leaves.clone(), siblings[1..].to_vec()),
+        Err(SmtProofError::InvalidNumSiblings {
+            expected: siblings.len(),
+            actual: siblings.len() - 1
+        })
+    );
+    assert_eq!(
+        SmtMultiProof::<SMT_DEPTH>::new(leaves.iter().rev().cloned().collect(), siblings.clone()),
+        Err(SmtProofError::UnsortedLeaves)
+    );
+    assert_eq!(
+        SmtMultiProof::<SMT_DEPTH>::new(Vec::new(), siblings),
+        Err(SmtProofError::NoLeaves)
+    );
+}
+
+#[test]
+fn test_smt_with_smaller_depth() {
+    let mut smt: Smt<32> = Smt::with_store(MemorySmtStore::new());
+    assert_eq!(smt.depth(), 32);
+    assert_eq!(smt.root(), *EmptySubtreeRoots::entry(32, 0));
+
+    // the leaf of a key is identified by the 32 most significant bits of its most significant
+    // element, such that keys which differ only in the low bits share a leaf
+    let entries = [
+        (key(5 << 32, 0), value(1)),
+        (key((5 << 32) + 1, 0), value(2)),
+        (key(6 << 32, 0), value(3)),
+        (key(u32::MAX as u64, 0), value(4)),
+    ];
+    for (key, value) in entries {
+        assert_eq!(smt.insert(key, value), EMPTY_WORD);
+    }
+    assert_eq!(smt.leaves().count(), 3);
+    assert_eq!(smt.get_leaf(&key(5 << 32, 1)).index(), LeafIndex::new(5).unwrap());
+    assert_eq!(smt.get_leaf(&key((5 << 32) + 1, 0)).num_entries(), 2);
+    assert_eq!(smt.get_value(&key((5 << 32) + 1, 0)), value(2));
+
+    // the root matches the root of a simple tree of the same depth with the leaf hashes as leaves
+    let simple_smt = SimpleSmt::<32>::with_leaves(
+        smt.leaves().map(|(index, leaf)| (index.value(), leaf.hash().into())),
+    )
+    .unwrap();
+    assert_eq!(smt.root(), simple_smt.root());
+
+    // openings have paths of the depth of the tree
+    for (key, value) in entries {
+        let proof = smt.open(&key);
+        assert_eq!(proof.path().len(), 32);
+        assert!(proof.verify_membership(&key, &value, &smt.root()));
+    }
+    let keys: Vec<RpoDigest> = entries.iter().map(|(key, _)| *key).collect();
+    assert_eq!(smt.open_many(&keys).unwrap().compute_root(), smt.root());
+    assert_eq!(keys_of(smt.range(key(5 << 32, 0)..key(6 << 32, 0))), keys[..2]);
+
+    // batches of updates, deltas and other stores work in the same way
+    let mut other: Smt<32, Rpo256, _> =
+        Smt::with_store(KvDatabaseStore::new(BTreeMap::<Vec<u8>, Vec<u8>>::new()));
+    other.apply_mutations(other.compute_mutations(entries)).unwrap();
+    assert_eq!(other.root(), smt.root());
+
+    other.insert(key(5 << 32, 0), EMPTY_WORD);
+    smt.apply_delta(smt.diff(&other)).unwrap();
+    assert_eq!(smt.root(), other.root());
+    assert_eq!(smt.get_leaf(&key(5 << 32, 0)).num_entries(), 1);
+}
+
+#[test]
+fn test_smt_with_other_hasher() {
+    let mut smt: Smt<SMT_DEPTH, Keccak256> = Smt::with_store(MemorySmtStore::new());
+    let empty_roots = Keccak256::empty_subtree_roots(SMT_DEPTH);
+    assert_eq!(smt.root(), empty_roots[0]);
+
+    // the root of a tree with a single entry is the hash of the leaf merged with the roots of the
+    // empty subtrees on its path
+    let (key_1, value_1) = (key(42, 1), value(1));
+    smt.insert(key_1, value_1);
+    let leaf = SmtLeaf::<SMT_DEPTH>::new_single(key_1, value_1);
+    let leaf_elements: Vec<Felt> = key_1.iter().chain(value_1.iter()).copied().collect();
+    assert_eq!(leaf.hash_with::<Keccak256>(), Keccak256::hash_elements(&leaf_elements));
+    let expected_root = (1..=SMT_DEPTH).rev().fold(leaf.hash_with::<Keccak256>(), |node, depth| {
+        let sibling = empty_roots[depth as usize];
+        let is_right = (42_u64 >> (SMT_DEPTH - depth)) & 1 == 1;
+        Keccak256::merge(&if is_right { [sibling, node] } else { [node, sibling] })
+    });
+    assert_eq!(smt.root(), expected_root);
+
+    // proofs and multiproofs resolve to the root of the tree
+    let entries = [(key_1, value_1), (key(42, 2), value(2)), (key(u64::MAX >> 1, 3), value(3))];
+    for (key, value) in &entries[1..] {
+        smt.insert(*key, *value);
+    }
+    for (key, value) in entries {
+        let proof = smt.open(&key);
+        assert_eq!(proof.path().len(), SMT_DEPTH as usize);
+        assert!(proof.verify_membership(&key, &value, &smt.root()));
+    }
+    let keys: Vec<RpoDigest> = entries.iter().map(|(key, _)| *key).collect();
+    assert_eq!(smt.open_many(&keys).unwrap().compute_root(), smt.root());
+
+    // batches of updates and other stores work in the same way
+    let mut other: Smt<SMT_DEPTH, Keccak256, _> =
+        Smt::with_store(KvDatabaseStore::new(BTreeMap::<Vec<u8>, Vec<u8>>::new()));
+    other.apply_mutations(other.compute_mutations(entries)).unwrap();
+    assert_eq!(other.root(), smt.root());
+    let reopened: Smt<SMT_DEPTH, Keccak256, _> = Smt::with_store(other.into_store());
+    assert_eq!(reopened.root(), smt.root());
+
+    // removing all entries restores the root of the empty tree
+    for (key, _) in entries {
+        smt.insert(key, EMPTY_WORD);
+    }
+    assert_eq!(smt.root(), empty_roots[0]);
+    assert_eq!(smt.store().num_inner_nodes(), 0);
+
+    // a tree using another hasher holds the same data, but has a different root
+    let rpo_smt = Smt::with_entries(entries).unwrap();
+    let blake_smt = {
+        let mut smt: Smt<SMT_DEPTH, Blake3_256> = Smt::with_store(MemorySmtStore::new());
+        smt.apply_mutations(smt.compute_mutations(entries)).unwrap();
+        smt
+    };
+    assert_eq!(
+        blake_smt.entries_sorted().collect::<Vec<_>>(),
+        rpo_smt.entries_sorted().collect::<Vec<_>>()
+    );
+    assert_ne!(blake_smt.root().as_bytes(), rpo_smt.root().as_bytes());
+}
+
+#[test]
+fn test_smt_leaf_hash_with_rpo() {
+    let key_1 = RpoDigest::from([ONE, ONE, ONE, Felt::new(42)]);
+    let key_2 = RpoDigest::from([Felt::new(2), ONE, ONE, Felt::new(42)]);
+    let value = [Felt::new(7); WORD_SIZE];
+
+    let leaves: [SmtLeaf; 3] = [
+        SmtLeaf::new_empty(LeafIndex::new_max_depth(42)),
+        SmtLeaf::new_single(key_1, value),
+        SmtLeaf::new_multiple(vec![(key_1, value), (key_2, value)]).unwrap(),
+    ];
+    for leaf in leaves {
+        assert_eq!(leaf.hash_with::<Rpo256>(), leaf.hash());
+    }
+}
+
 // SMT LEAF
 // --------------------------------------------------------------------------------------------
 
@@ -346,7 +1113,7 @@ fn test_empty_smt_leaf_serialization() {
 
 #[test]
 fn test_single_smt_leaf_serialization() {
-    let single_leaf = SmtLeaf::new_single(
+    let single_leaf: SmtLeaf = SmtLeaf::new_single(
         RpoDigest::from([10_u32.into(), 11_u32.into(), 12_u32.into(), 13_u32.into()]),
         [1_u32.into(), 2_u32.into(), 3_u32.into(), 4_u32.into()],
     );
@@ -361,7 +1128,7 @@ fn test_single_smt_leaf_serialization() {
 
 #[test]
 fn test_multiple_smt_leaf_serialization_success() {
-    let multiple_leaf = SmtLeaf::new_multiple(vec![
+    let multiple_leaf: SmtLeaf = SmtLeaf::new_multiple(vec![
         (
             RpoDigest::from([10_u32.into(), 11_u32.into(), 12_u32.into(), 13_u32.into()]),
             [1_u32.into(), 2_u32.into(), 3_u32.into(), 4_u32.into()],
@@ -386,9 +1153,9 @@ fn test_multiple_smt_leaf_serialization_success() {
 
 fn build_empty_or_single_leaf_node(key: RpoDigest, value: Word) -> RpoDigest {
     if value == EMPTY_WORD {
-        SmtLeaf::new_empty(key.into()).hash()
+        SmtLeaf::<SMT_DEPTH>::new_empty(key.into()).hash()
     } else {
-        SmtLeaf::Single((key, value)).hash()
+        SmtLeaf::<SMT_DEPTH>::Single((key, value)).hash()
     }
 }
 
@@ -405,3 +1172,18 @@ fn build_multiple_leaf_node(kv_pairs: &[(RpoDigest, Word)]) -> RpoDigest {
 
     Rpo256::hash_elements(&elements)
 }
+
+/// Returns a key in the leaf at index `msb`, distinguished from the other keys in the same leaf by
+/// `lsb`.
+fn key(msb: u64, lsb: u64) -> RpoDigest {
+    RpoDigest::from([Felt::new(lsb), ONE, ONE, Felt::new(msb)])
+}
+
+/// Returns a value with all elements set to `v`.
+fn value(v: u64) -> Word {
+    [Felt::new(v); WORD_SIZE]
+}
+
+fn keys_of<'a>(entries: impl Iterator<Item = &'a (RpoDigest, Word)>) -> Vec<RpoDigest> {
+    entries.map(|(key, _)| *key).collect()
+}
@@ -1,12 +1,24 @@
 use super::{EmptySubtreeRoots, InnerNodeInfo, MerkleError, MerklePath, NodeIndex};
 use crate::{
-    hash::rpo::{Rpo256, RpoDigest},
-    Felt, Word, EMPTY_WORD,
+    hash::{
+        blake::Blake3_256,
+        keccak::Keccak256,
+        rpo::{Rpo256, RpoDigest},
+        rpx::Rpx256,
+        sha2::Sha256,
+        Digest, ElementHasher, Hasher,
+    },
+    utils::{ByteReader, ByteWriter, Deserializable, DeserializationError, Serializable},
+    Felt, Word, EMPTY_WORD, ZERO,
 };
-use alloc::vec::Vec;
+use alloc::{collections::BTreeMap, string::ToString, vec::Vec};
 
 mod full;
-pub use full::{Smt, SmtLeaf, SmtLeafError, SmtProof, SmtProofError, SMT_DEPTH};
+pub use full::{
+    FrozenSmt, KvDatabase, KvDatabaseStore, MemorySmtStore, Reversion, Smt, SmtDelta,
+    SmtHistoryProof, SmtLeaf, SmtLeafError, SmtMultiProof, SmtProof, SmtProofError, SmtStore,
+    SmtWithHistory, SMT_DEPTH,
+};
 
 mod simple;
 pub use simple::SimpleSmt;
@@ -41,16 +53,21 @@ pub const SMT_MAX_DEPTH: u8 = 64;
 /// [crate::merkle::SimpleSmt]. However, if there are more keys than leaves, then [`Self::Leaf`]
 /// must accomodate all keys that map to the same leaf.
 ///
+/// The inner nodes of the tree are computed with [Self::Hasher], and thus the tree, its nodes and
+/// its proofs consist of the digests of this hash function.
+///
 /// [SparseMerkleTree] currently doesn't support optimizations that compress Merkle proofs.
 pub(crate) trait SparseMerkleTree<const DEPTH: u8> {
     /// The type for a key
-    type Key: Clone;
+    type Key: Clone + Ord;
     /// The type for a value
     type Value: Clone + PartialEq;
     /// The type for a leaf
     type Leaf;
     /// The type for an opening (i.e. a "proof") of a leaf
     type Opening;
+    /// The hash function used to compute the nodes of the tree
+    type Hasher: SmtHasher;
 
     /// The default value used to compute the hash of empty leaves
     const EMPTY_VALUE: Self::Value;
@@ -114,7 +131,7 @@ pub(crate) trait SparseMerkleTree<const DEPTH: u8> {
     fn recompute_nodes_from_index_to_root(
         &mut self,
         mut index: NodeIndex,
-        node_hash_at_index: RpoDigest,
+        node_hash_at_index: DigestOf<Self::Hasher>,
     ) {
         let mut node_hash = node_hash_at_index;
         for node_depth in (0..index.depth()).rev() {
@@ -126,9 +143,9 @@ pub(crate) trait SparseMerkleTree<const DEPTH: u8> {
             } else {
                 (node_hash, right)
             };
-            node_hash = Rpo256::merge(&[left, right]);
+            node_hash = Self::Hasher::merge(&[left, right]);
 
-            if node_hash == *EmptySubtreeRoots::entry(DEPTH, node_depth) {
+            if node_hash == self.empty_subtree_root(node_depth) {
                 // If a subtree is empty, when can remove the inner node, since it's equal to the
                 // default value
                 self.remove_inner_node(index)
@@ -139,20 +156,134 @@ pub(crate) trait SparseMerkleTree<const DEPTH: u8> {
         self.set_root(node_hash);
     }
 
+    /// Computes the changes which inserting the provided key-value pairs would make to this tree,
+    /// without applying them.
+    ///
+    /// If a key appears multiple times, the last value associated with it is used, as if the
+    /// pairs were inserted one by one. The affected leaves are hashed first, and then the inner
+    /// nodes above them are computed one level at a time, so that an inner node shared by the
+    /// paths of multiple leaves is hashed only once.
+    fn compute_mutations(
+        &self,
+        kv_pairs: impl IntoIterator<Item = (Self::Key, Self::Value)>,
+    ) -> MutationSet<DEPTH, Self::Key, Self::Value, DigestOf<Self::Hasher>> {
+        let old_root = self.root();
+        let new_pairs: BTreeMap<Self::Key, Self::Value> = kv_pairs.into_iter().collect();
+
+        // compute the new leaves affected by the pairs, grouping the pairs by leaf index
+        let mut leaves: BTreeMap<u64, Self::Leaf> = BTreeMap::new();
+        for (key, value) in new_pairs.iter() {
+            let leaf_index = Self::key_to_leaf_index(key).value();
+            let leaf = leaves.remove(&leaf_index).unwrap_or_else(|| self.get_leaf(key));
+            leaves.insert(leaf_index, Self::construct_prospective_leaf(leaf, key, value));
+        }
+
+        // the changed nodes at the current depth as (index, hash), sorted by index
+        let mut nodes: Vec<(u64, DigestOf<Self::Hasher>)> =
+            leaves.iter().map(|(&index, leaf)| (index, Self::hash_leaf(leaf))).collect();
+        let mut node_mutations = BTreeMap::new();
+
+        for depth in (0..DEPTH).rev() {
+            // pair every changed node with its sibling, which is either changed as well or read
+            // from the tree
+            let mut indices = Vec::with_capacity(nodes.len());
+            let mut pairs = Vec::with_capacity(nodes.len());
+            let mut iter = nodes.iter().peekable();
+            while let Some(&(index, hash)) = iter.next() {
+                let parent_index = NodeIndex::new_unchecked(depth, index >> 1);
+                let InnerNode { left, right } = self.get_inner_node(parent_index);
+                let pair = if index & 1 == 1 {
+                    [left, hash]
+                } else {
+                    match iter.next_if(|(next, _)| *next == index + 1) {
+                        Some(&(_, sibling)) => [hash, sibling],
+                        None => [hash, right],
+                    }
+                };
+                indices.push(parent_index);
+                pairs.push(pair);
+            }
+
+            let hashes = Self::Hasher::merge_many(&pairs);
+            let empty_root = self.empty_subtree_root(depth);
+            for ((&index, [left, right]), &hash) in indices.iter().zip(pairs).zip(hashes.iter()) {
+                // the nodes which are equal to the roots of empty subtrees are removed
+                let mutation = if hash == empty_root {
+                    NodeMutation::Removal
+                } else {
+                    NodeMutation::Addition(InnerNode { left, right })
+                };
+                node_mutations.insert(index, mutation);
+            }
+            nodes = indices.iter().map(|index| index.value()).zip(hashes).collect();
+        }
+
+        let new_root = nodes.first().map_or(old_root, |&(_, root)| root);
+        MutationSet {
+            old_root,
+            node_mutations,
+            new_pairs,
+            new_root,
+        }
+    }
+
+    /// Applies the provided mutations, computed via [Self::compute_mutations()], to this tree.
+    ///
+    /// # Errors
+    /// Returns an error if the mutations were computed against a tree with a root different from
+    /// the root of this tree.
+    fn apply_mutations(
+        &mut self,
+        mutations: MutationSet<DEPTH, Self::Key, Self::Value, DigestOf<Self::Hasher>>,
+    ) -> Result<(), MerkleError> {
+        let MutationSet {
+            old_root,
+            node_mutations,
+            new_pairs,
+            new_root,
+        } = mutations;
+        if old_root != self.root() {
+            return Err(MerkleError::ConflictingMutationRoots(
+                old_root.as_bytes(),
+                self.root().as_bytes(),
+            ));
+        }
+
+        for (index, mutation) in node_mutations {
+            match mutation {
+                NodeMutation::Removal => self.remove_inner_node(index),
+                NodeMutation::Addition(inner_node) => self.insert_inner_node(index, inner_node),
+            }
+        }
+        for (key, value) in new_pairs {
+            self.insert_value(key, value);
+        }
+        self.set_root(new_root);
+
+        Ok(())
+    }
+
     // REQUIRED METHODS
     // ---------------------------------------------------------------------------------------------
 
     /// The root of the tree
-    fn root(&self) -> RpoDigest;
+    fn root(&self) -> DigestOf<Self::Hasher>;
 
     /// Sets the root of the tree
-    fn set_root(&mut self, root: RpoDigest);
+    fn set_root(&mut self, root: DigestOf<Self::Hasher>);
+
+    /// Returns the root of an empty subtree whose root is at the given depth
+    fn empty_subtree_root(&self, depth: u8) -> DigestOf<Self::Hasher>;
 
     /// Retrieves an inner node at the given index
-    fn get_inner_node(&self, index: NodeIndex) -> InnerNode;
+    fn get_inner_node(&self, index: NodeIndex) -> InnerNode<DigestOf<Self::Hasher>>;
 
     /// Inserts an inner node at the given index
-    fn insert_inner_node(&mut self, index: NodeIndex, inner_node: InnerNode);
+    fn insert_inner_node(
+        &mut self,
+        index: NodeIndex,
+        inner_node: InnerNode<DigestOf<Self::Hasher>>,
+    );
 
     /// Removes an inner node at the given index
     fn remove_inner_node(&mut self, index: NodeIndex);
@@ -164,7 +295,15 @@ pub(crate) trait SparseMerkleTree<const DEPTH: u8> {
     fn get_leaf(&self, key: &Self::Key) -> Self::Leaf;
 
     /// Returns the hash of a leaf
-    fn hash_leaf(leaf: &Self::Leaf) -> RpoDigest;
+    fn hash_leaf(leaf: &Self::Leaf) -> DigestOf<Self::Hasher>;
+
+    /// Returns the leaf which results from inserting `value` at `key` into `existing_leaf`, which
+    /// is the leaf to which `key` maps.
+    fn construct_prospective_leaf(
+        existing_leaf: Self::Leaf,
+        key: &Self::Key,
+        value: &Self::Value,
+    ) -> Self::Leaf;
 
     /// Maps a key to a leaf index
     fn key_to_leaf_index(key: &Self::Key) -> LeafIndex<DEPTH>;
@@ -172,25 +311,229 @@ pub(crate) trait SparseMerkleTree<const DEPTH: u8> {
     /// Maps a (MerklePath, Self::Leaf) to an opening.
     ///
     /// The length `path` is guaranteed to be equal to `DEPTH`
-    fn path_and_leaf_to_opening(path: MerklePath, leaf: Self::Leaf) -> Self::Opening;
+    fn path_and_leaf_to_opening(
+        path: MerklePath<DigestOf<Self::Hasher>>,
+        leaf: Self::Leaf,
+    ) -> Self::Opening;
+}
+
+/// The type of the digests of the hash function `H`.
+type DigestOf<H> = <H as Hasher>::Digest;
+
+// SMT HASHER
+// ================================================================================================
+
+/// A hash function which can be used to compute the nodes of a sparse Merkle tree.
+///
+/// The leaves of a tree are hashed via [ElementHasher::hash_elements()], and an inner node is the
+/// result of merging the digests of its children via [Hasher::merge()]. Empty leaves hash to the
+/// default digest. The provided methods can be overridden by hash functions which have faster
+/// implementations of them.
+///
+/// This is implemented for [Rpo256] (which is used by default), as well as for [Rpx256],
+/// [Blake3_256], [Keccak256] and [Sha256], such that trees can be verified by systems which do
+/// not support RPO (e.g., Ethereum smart contracts for Keccak-256).
+pub trait SmtHasher: ElementHasher<BaseField = Felt> {
+    /// Returns the roots of the empty subtrees of a tree of the specified depth, indexed by the
+    /// depths of the roots. The last root is thus the hash of an empty leaf.
+    fn empty_subtree_roots(tree_depth: u8) -> Vec<Self::Digest> {
+        let mut roots = vec![Self::Digest::default(); tree_depth as usize + 1];
+        for depth in (0..tree_depth as usize).rev() {
+            roots[depth] = Self::merge(&[roots[depth + 1]; 2]);
+        }
+        roots
+    }
+
+    /// Returns the results of merging every one of the provided pairs of digests.
+    fn merge_many(pairs: &[[Self::Digest; 2]]) -> Vec<Self::Digest> {
+        pairs.iter().map(Self::merge).collect()
+    }
+}
+
+impl SmtHasher for Rpo256 {
+    fn empty_subtree_roots(tree_depth: u8) -> Vec<RpoDigest> {
+        EmptySubtreeRoots::empty_hashes(tree_depth).to_vec()
+    }
+
+    fn merge_many(pairs: &[[RpoDigest; 2]]) -> Vec<RpoDigest> {
+        Rpo256::merge_in_domain_many(pairs, ZERO)
+    }
 }
 
+impl SmtHasher for Rpx256 {}
+
+impl SmtHasher for Blake3_256 {}
+
+impl SmtHasher for Keccak256 {}
+
+impl SmtHasher for Sha256 {}
+
 // INNER NODE
 // ================================================================================================
 
+/// An inner node of a sparse Merkle tree, represented by the hashes of its two children.
+///
+/// The hashes are [RpoDigest]s by default, or the digests of the [SmtHasher] of the tree.
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
-pub(crate) struct InnerNode {
-    pub left: RpoDigest,
-    pub right: RpoDigest,
+pub struct InnerNode<D = RpoDigest> {
+    pub left: D,
+    pub right: D,
 }
 
 impl InnerNode {
+    /// Returns the hash of this node.
     pub fn hash(&self) -> RpoDigest {
         Rpo256::merge(&[self.left, self.right])
     }
 }
 
+impl<D: Digest> InnerNode<D> {
+    /// Returns the hash of this node computed with the hash function `H`.
+    pub fn hash_with<H: Hasher<Digest = D>>(&self) -> D {
+        H::merge(&[self.left, self.right])
+    }
+}
+
+// MUTATIONS
+// ================================================================================================
+
+/// A change to an inner node of a sparse Merkle tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum NodeMutation<D = RpoDigest> {
+    /// The node is removed, since it is equal to the root of an empty subtree.
+    Removal,
+    /// The node is inserted or replaced.
+    Addition(InnerNode<D>),
+}
+
+/// A set of changes to a sparse Merkle tree which results from inserting a batch of key-value
+/// pairs into the tree.
+///
+/// A mutation set is computed against a specific tree without modifying it, and thus the new root
+/// of the tree is known (and can be validated) before the changes are applied. A mutation set can
+/// be applied only to a tree with the same root as the tree it was computed against. The roots and
+/// the inner nodes are digests of type `D`, i.e., of the hash function of the tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MutationSet<const DEPTH: u8, K, V, D = RpoDigest> {
+    /// The root of the tree the mutations were computed against.
+    old_root: D,
+    /// The changes to the inner nodes of the tree.
+    node_mutations: BTreeMap<NodeIndex, NodeMutation<D>>,
+    /// The key-value pairs to be inserted into the tree.
+    new_pairs: BTreeMap<K, V>,
+    /// The root of the tree after the mutations are applied.
+    new_root: D,
+}
+
+impl<const DEPTH: u8, K, V, D: Copy> MutationSet<DEPTH, K, V, D> {
+    /// Returns the root of the tree the mutations were computed against.
+    pub fn old_root(&self) -> D {
+        self.old_root
+    }
+
+    /// Returns the root of the tree after the mutations are applied.
+    pub fn root(&self) -> D {
+        self.new_root
+    }
+
+    /// Returns the key-value pairs which are inserted by the mutations.
+    pub fn new_pairs(&self) -> &BTreeMap<K, V> {
+        &self.new_pairs
+    }
+
+    /// Returns the number of inner nodes which are changed by the mutations.
+    pub fn num_node_mutations(&self) -> usize {
+        self.node_mutations.len()
+    }
+}
+
+impl<D: Serializable> Serializable for NodeMutation<D> {
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        match self {
+            NodeMutation::Removal => target.write_u8(0),
+            NodeMutation::Addition(InnerNode { left, right }) => {
+                target.write_u8(1);
+                left.write_into(target);
+                right.write_into(target);
+            }
+        }
+    }
+}
+
+impl<D: Deserializable> Deserializable for NodeMutation<D> {
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        match source.read_u8()? {
+            0 => Ok(NodeMutation::Removal),
+            1 => {
+                let left = source.read()?;
+                let right = source.read()?;
+                Ok(NodeMutation::Addition(InnerNode { left, right }))
+            }
+            tag => {
+                Err(DeserializationError::InvalidValue(format!("invalid node mutation tag {tag}")))
+            }
+        }
+    }
+}
+
+impl<const DEPTH: u8, K: Serializable, V: Serializable, D: Serializable> Serializable
+    for MutationSet<DEPTH, K, V, D>
+{
+    fn write_into<W: ByteWriter>(&self, target: &mut W) {
+        self.old_root.write_into(target);
+        self.new_root.write_into(target);
+
+        (self.node_mutations.len() as u64).write_into(target);
+        for (index, mutation) in self.node_mutations.iter() {
+            index.write_into(target);
+            mutation.write_into(target);
+        }
+
+        (self.new_pairs.len() as u64).write_into(target);
+        for (key, value) in self.new_pairs.iter() {
+            key.write_into(target);
+            value.write_into(target);
+        }
+    }
+}
+
+impl<const DEPTH: u8, K: Deserializable + Ord, V: Deserializable, D: Deserializable> Deserializable
+    for MutationSet<DEPTH, K, V, D>
+{
+    fn read_from<R: ByteReader>(source: &mut R) -> Result<Self, DeserializationError> {
+        let old_root = source.read()?;
+        let new_root = source.read()?;
+
+        let num_node_mutations = source.read_u64()?;
+        let mut node_mutations = BTreeMap::new();
+        for _ in 0..num_node_mutations {
+            let index: NodeIndex = source.read()?;
+            if index.depth() >= DEPTH {
+                return Err(DeserializationError::InvalidValue(
+                    MerkleError::DepthTooBig(index.depth() as u64).to_string(),
+                ));
+            }
+            node_mutations.insert(index, source.read()?);
+        }
+
+        let num_new_pairs = source.read_u64()?;
+        let mut new_pairs = BTreeMap::new();
+        for _ in 0..num_new_pairs {
+            let key = source.read()?;
+            let value = source.read()?;
+            new_pairs.insert(key, value);
+        }
+
+        Ok(Self {
+            old_root,
+            node_mutations,
+            new_pairs,
+            new_root,
+        })
+    }
+}
+
 // LEAF INDEX
 // ================================================================================================
 
@@ -213,6 +556,13 @@ impl<const DEPTH: u8> LeafIndex<DEPTH> {
     pub fn value(&self) -> u64 {
         self.index.value()
     }
+
+    /// Returns a new [LeafIndex] without checking that `value` fits into a tree of depth `DEPTH`.
+    pub(crate) const fn new_unchecked(value: u64) -> Self {
+        LeafIndex {
+            index: NodeIndex::new_unchecked(DEPTH, value),
+        }
+    }
 }
 
 impl LeafIndex<SMT_MAX_DEPTH> {
@@ -1,6 +1,6 @@
 use super::{
     super::ValuePath, EmptySubtreeRoots, InnerNode, InnerNodeInfo, LeafIndex, MerkleError,
-    MerklePath, NodeIndex, RpoDigest, SparseMerkleTree, Word, EMPTY_WORD, SMT_MAX_DEPTH,
+    MerklePath, NodeIndex, Rpo256, RpoDigest, SparseMerkleTree, Word, EMPTY_WORD, SMT_MAX_DEPTH,
     SMT_MIN_DEPTH,
 };
 use alloc::collections::{BTreeMap, BTreeSet};
@@ -248,6 +248,7 @@ impl<const DEPTH: u8> SparseMerkleTree<DEPTH> for SimpleSmt<DEPTH> {
     type Value = Word;
     type Leaf = Word;
     type Opening = ValuePath;
+    type Hasher = Rpo256;
 
     const EMPTY_VALUE: Self::Value = EMPTY_WORD;
 
@@ -259,6 +260,10 @@ impl<const DEPTH: u8> SparseMerkleTree<DEPTH> for SimpleSmt<DEPTH> {
         self.root = root;
     }
 
+    fn empty_subtree_root(&self, depth: u8) -> RpoDigest {
+        *EmptySubtreeRoots::entry(DEPTH, depth)
+    }
+
     fn get_inner_node(&self, index: NodeIndex) -> InnerNode {
         self.inner_nodes.get(&index).cloned().unwrap_or_else(|| {
             let node = EmptySubtreeRoots::entry(DEPTH, index.depth() + 1);
@@ -295,6 +300,14 @@ impl<const DEPTH: u8> SparseMerkleTree<DEPTH> for SimpleSmt<DEPTH> {
         leaf.into()
     }
 
+    fn construct_prospective_leaf(
+        _existing_leaf: Word,
+        _key: &LeafIndex<DEPTH>,
+        value: &Word,
+    ) -> Word {
+        *value
+    }
+
     fn key_to_leaf_index(key: &LeafIndex<DEPTH>) -> LeafIndex<DEPTH> {
         *key
     }
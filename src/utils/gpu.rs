@@ -0,0 +1,193 @@
+//! Execution of compute shaders on a GPU via [wgpu](https://wgpu.rs), shared by the GPU backends
+//! of this crate.
+//!
+//! All failures (no adapter, a shader which does not compile for the adapter, out-of-memory
+//! errors, failed buffer mapping) are reported as `None`, so that the callers can fall back to
+//! their CPU implementations.
+
+use alloc::{string::String, vec::Vec};
+use std::{borrow::Cow, sync::OnceLock};
+
+use wgpu::util::DeviceExt;
+
+// GPU CONTEXT
+// ================================================================================================
+
+/// The device and queue of the GPU adapter on which the compute shaders are executed.
+pub(crate) struct GpuContext {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+}
+
+impl GpuContext {
+    /// Returns the context of the default GPU adapter, or `None` if no adapter is available.
+    ///
+    /// The adapter is requested on the first call, and the context is shared by all threads.
+    pub fn get() -> Option<&'static GpuContext> {
+        static CONTEXT: OnceLock<Option<GpuContext>> = OnceLock::new();
+        CONTEXT.get_or_init(Self::new).as_ref()
+    }
+
+    fn new() -> Option<Self> {
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends: wgpu::util::backend_bits_from_env().unwrap_or(wgpu::Backends::all()),
+            ..Default::default()
+        });
+        let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            force_fallback_adapter: false,
+            compatible_surface: None,
+        }))?;
+
+        // request the largest buffers the adapter supports, so that work can be split into as few
+        // dispatches as possible
+        let descriptor = wgpu::DeviceDescriptor {
+            label: Some("miden-crypto"),
+            required_features: wgpu::Features::empty(),
+            required_limits: adapter.limits(),
+        };
+        let (device, queue) = pollster::block_on(adapter.request_device(&descriptor, None)).ok()?;
+        Some(Self { device, queue })
+    }
+
+    // ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns the maximum number of 32-bit words in a buffer bound to a kernel.
+    pub fn max_buffer_words(&self) -> usize {
+        let limits = self.device.limits();
+        let max_bytes = (limits.max_storage_buffer_binding_size as u64).min(limits.max_buffer_size);
+        (max_bytes / 4) as usize
+    }
+
+    /// Returns the maximum number of workgroups in a single dispatch.
+    pub fn max_workgroups(&self) -> u32 {
+        self.device.limits().max_compute_workgroups_per_dimension
+    }
+
+    // KERNELS
+    // --------------------------------------------------------------------------------------------
+
+    /// Compiles the provided WGSL source into a kernel with the specified entry point, or returns
+    /// `None` if the source is not accepted by the adapter.
+    pub fn create_kernel(&self, label: &str, source: String, entry_point: &str) -> Option<Kernel> {
+        self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let module = self.device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(label),
+            source: wgpu::ShaderSource::Wgsl(Cow::Owned(source)),
+        });
+        let pipeline = self.device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some(label),
+            layout: None,
+            module: &module,
+            entry_point,
+        });
+        match pollster::block_on(self.device.pop_error_scope()) {
+            Some(_) => None,
+            None => Some(Kernel { pipeline }),
+        }
+    }
+
+    /// Executes the kernel over `workgroups` workgroups and returns the contents of its output
+    /// buffer, or `None` if the execution failed.
+    ///
+    /// The input buffers are bound to bindings `0..inputs.len()` of group 0 (as read-only
+    /// storage buffers), and the output buffer of `output_words` words is bound to the binding
+    /// following them.
+    pub fn run(
+        &self,
+        kernel: &Kernel,
+        inputs: &[&[u32]],
+        output_words: usize,
+        workgroups: u32,
+    ) -> Option<Vec<u32>> {
+        self.device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+
+        let input_buffers: Vec<wgpu::Buffer> = inputs
+            .iter()
+            .map(|words| {
+                // empty buffers cannot be bound, and thus at least one word is always uploaded
+                let mut contents: Vec<u8> =
+                    words.iter().flat_map(|word| word.to_le_bytes()).collect();
+                if contents.is_empty() {
+                    contents.resize(4, 0);
+                }
+                self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: &contents,
+                    usage: wgpu::BufferUsages::STORAGE,
+                })
+            })
+            .collect();
+        let output_size = (output_words.max(1) * 4) as u64;
+        let output_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: output_size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let staging_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: output_size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let entries: Vec<wgpu::BindGroupEntry> = input_buffers
+            .iter()
+            .chain(core::iter::once(&output_buffer))
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &kernel.pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(&kernel.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(workgroups, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&output_buffer, 0, &staging_buffer, 0, output_size);
+        self.queue.submit([encoder.finish()]);
+
+        let validation_error = pollster::block_on(self.device.pop_error_scope());
+        let memory_error = pollster::block_on(self.device.pop_error_scope());
+        if validation_error.is_some() || memory_error.is_some() {
+            return None;
+        }
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let slice = staging_buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver.recv().ok()?.ok()?;
+
+        let output = slice
+            .get_mapped_range()
+            .chunks_exact(4)
+            .take(output_words)
+            .map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()))
+            .collect();
+        staging_buffer.unmap();
+        Some(output)
+    }
+}
+
+// KERNEL
+// ================================================================================================
+
+/// A compiled compute shader, executed via [GpuContext::run()].
+pub(crate) struct Kernel {
+    pipeline: wgpu::ComputePipeline,
+}
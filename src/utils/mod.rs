@@ -3,7 +3,12 @@
 use alloc::string::String;
 use core::fmt::{self, Display, Write};
 
-use super::Word;
+use subtle::{Choice, ConstantTimeEq};
+
+use super::{Felt, Word};
+
+#[cfg(feature = "gpu")]
+pub(crate) mod gpu;
 
 mod kv_map;
 
@@ -32,6 +37,32 @@ pub fn word_to_hex(w: &Word) -> Result<String, fmt::Error> {
     Ok(s)
 }
 
+/// Returns true if the provided sequences of field elements are equal.
+///
+/// Unlike `==`, the running time of the comparison depends only on the lengths of the sequences
+/// and not on their contents, which makes it suitable for comparing [Word]s and digests (via
+/// `as_elements()`) derived from secret data.
+pub fn ct_eq(a: &[Felt], b: &[Felt]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter()
+        .zip(b)
+        .fold(Choice::from(1), |acc, (x, y)| acc & x.as_int().ct_eq(&y.as_int()))
+        .into()
+}
+
+/// Overwrites the provided field elements with zeros in a way which is not optimized away by the
+/// compiler, e.g., to wipe key material derived from secret data.
+#[cfg(feature = "zeroize")]
+pub(crate) fn zeroize_elements(elements: &mut [Felt]) {
+    for element in elements.iter_mut() {
+        // SAFETY: the pointer is derived from a mutable reference, and is thus valid and aligned
+        unsafe { core::ptr::write_volatile(element, super::ZERO) };
+    }
+    core::sync::atomic::compiler_fence(core::sync::atomic::Ordering::SeqCst);
+}
+
 /// Renders an array of bytes as hex into a String.
 pub fn bytes_to_hex_string<const N: usize>(data: [u8; N]) -> String {
     let mut s = String::with_capacity(N + 2);
@@ -103,3 +134,26 @@ pub fn hex_to_bytes<const N: usize>(value: &str) -> Result<[u8; N], HexParseErro
 
     Ok(decoded)
 }
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::{ct_eq, Felt, Word};
+    use crate::StarkField;
+
+    #[test]
+    fn test_ct_eq() {
+        let a: Word = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+        let mut b = a;
+        assert!(ct_eq(&a, &b));
+
+        b[3] = Felt::new(5);
+        assert!(!ct_eq(&a, &b));
+        assert!(!ct_eq(&a, &a[..3]));
+
+        // non-canonical representations of the same element should be equal
+        assert!(ct_eq(&[Felt::new(0)], &[Felt::new(Felt::MODULUS)]));
+    }
+}